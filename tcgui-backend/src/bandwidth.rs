@@ -8,7 +8,7 @@
 //!
 //! * **Multi-namespace support**: Monitors interfaces across all network namespaces
 //! * **Real-time statistics**: Collects RX/TX bytes, packets, errors, and drops via netlink
-//! * **Rate calculations**: Uses nlink's StatsTracker for automatic rate calculation
+//! * **Rate calculations**: Uses nlink's StatsTracker for byte, packet, error and drop rates
 //! * **Namespace-aware messaging**: Sends updates with namespace context for proper routing
 //! * **Permission handling**: Gracefully handles namespace access permission issues
 
@@ -24,9 +24,12 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tracing::{debug, error, instrument, warn};
 use zenoh::Session;
-use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, CacheConfig, MissDetectionConfig};
+use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, MissDetectionConfig};
 
 use crate::container::Container;
+use crate::export::{self, ExportEventKind, SharedExporter};
+use crate::qos::WithQos;
+use tcgui_shared::qos::{self, QosProfile};
 use tcgui_shared::registry::tc;
 use tcgui_shared::{
     BandwidthUpdate, NetworkBandwidthStats, NetworkInterface, errors::TcguiError,
@@ -60,12 +63,25 @@ struct CachedConnection {
     path: Option<PathBuf>,
 }
 
+/// Per-second rates of an interface's counters
+#[derive(Debug, Clone, Copy, Default)]
+struct InterfaceRates {
+    rx_bytes: f64,
+    tx_bytes: f64,
+    rx_packets: f64,
+    tx_packets: f64,
+    rx_errors: f64,
+    tx_errors: f64,
+    rx_dropped: f64,
+    tx_dropped: f64,
+}
+
 /// Per-namespace statistics tracker
 struct NamespaceStatsTracker {
     /// nlink StatsTracker for automatic rate calculation
     tracker: StatsTracker,
     /// Last known rates by interface index
-    last_rates: HashMap<u32, (f64, f64)>, // (rx_bps, tx_bps)
+    last_rates: HashMap<u32, InterfaceRates>,
 }
 
 impl NamespaceStatsTracker {
@@ -94,6 +110,10 @@ pub struct BandwidthMonitor {
     /// Cached connections per namespace (keyed by namespace name) to avoid
     /// rebuilding the netns-bound connection on every poll cycle.
     namespace_connections: HashMap<String, CachedConnection>,
+    /// JSON-lines export of every sample, when enabled
+    exporter: Option<SharedExporter>,
+    /// QoS of the bandwidth publishers
+    qos: QosProfile,
 }
 
 impl BandwidthMonitor {
@@ -107,9 +127,21 @@ impl BandwidthMonitor {
             container_cache: None,
             namespace_trackers: HashMap::new(),
             namespace_connections: HashMap::new(),
+            exporter: None,
+            qos: qos::BANDWIDTH_UPDATES,
         }
     }
 
+    /// Sets the QoS of the bandwidth publishers declared from now on
+    pub fn set_qos(&mut self, qos: QosProfile) {
+        self.qos = qos;
+    }
+
+    /// Sets the exporter every published sample is also written to
+    pub fn set_exporter(&mut self, exporter: SharedExporter) {
+        self.exporter = Some(exporter);
+    }
+
     /// Sets the container cache for resolving container namespace paths
     pub fn set_container_cache(&mut self, cache: Arc<RwLock<HashMap<String, Container>>>) {
         self.container_cache = Some(cache);
@@ -232,24 +264,32 @@ impl BandwidthMonitor {
                     // Find the stats for this interface
                     interface_stats.get(&ifindex).map(|nlink_stats| {
                         // Get rates from the snapshot or use cached values
-                        let (rx_bps, tx_bps) = if let Some(ref rates) = rates_snapshot {
+                        let rates = if let Some(ref rates) = rates_snapshot {
                             if let Some(link_rates) = rates.links.get(&ifindex) {
-                                let rx = link_rates.rx_bytes_per_sec;
-                                let tx = link_rates.tx_bytes_per_sec;
+                                let link_rates = InterfaceRates {
+                                    rx_bytes: link_rates.rx_bytes_per_sec,
+                                    tx_bytes: link_rates.tx_bytes_per_sec,
+                                    rx_packets: link_rates.rx_packets_per_sec,
+                                    tx_packets: link_rates.tx_packets_per_sec,
+                                    rx_errors: link_rates.rx_errors_per_sec,
+                                    tx_errors: link_rates.tx_errors_per_sec,
+                                    rx_dropped: link_rates.rx_dropped_per_sec,
+                                    tx_dropped: link_rates.tx_dropped_per_sec,
+                                };
                                 // Cache the rates
-                                tracker.last_rates.insert(ifindex, (rx, tx));
-                                (rx, tx)
+                                tracker.last_rates.insert(ifindex, link_rates);
+                                link_rates
                             } else {
                                 // Use cached rates if available
                                 tracker
                                     .last_rates
                                     .get(&ifindex)
                                     .copied()
-                                    .unwrap_or((0.0, 0.0))
+                                    .unwrap_or_default()
                             }
                         } else {
                             // First measurement, no rates yet
-                            (0.0, 0.0)
+                            InterfaceRates::default()
                         };
 
                         let stats = NetworkBandwidthStats {
@@ -262,8 +302,14 @@ impl BandwidthMonitor {
                             rx_dropped: nlink_stats.rx_dropped,
                             tx_dropped: nlink_stats.tx_dropped,
                             timestamp,
-                            rx_bytes_per_sec: rx_bps,
-                            tx_bytes_per_sec: tx_bps,
+                            rx_bytes_per_sec: rates.rx_bytes,
+                            tx_bytes_per_sec: rates.tx_bytes,
+                            rx_packets_per_sec: rates.rx_packets,
+                            tx_packets_per_sec: rates.tx_packets,
+                            rx_errors_per_sec: rates.rx_errors,
+                            tx_errors_per_sec: rates.tx_errors,
+                            rx_dropped_per_sec: rates.rx_dropped,
+                            tx_dropped_per_sec: rates.tx_dropped,
                         };
 
                         BandwidthUpdate {
@@ -292,6 +338,53 @@ impl BandwidthMonitor {
         Ok(())
     }
 
+    /// Reads the current counters of one interface, for a one-shot stats
+    /// query. The rates are the ones of the interface's latest sample: a
+    /// fresh rate would need a second reading, and feeding this one to the
+    /// tracker would shorten the next sample's window.
+    #[instrument(skip(self, interface), fields(backend_name = %self.backend_name, namespace = %interface.namespace, interface = %interface.name))]
+    pub async fn sample(&mut self, interface: &NetworkInterface) -> Result<NetworkBandwidthStats> {
+        let (_, interface_stats) = self.get_netlink_stats(&interface.namespace).await?;
+        let nlink_stats = interface_stats.get(&interface.index).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Interface {} not found in namespace {}",
+                interface.name,
+                interface.namespace
+            )
+        })?;
+
+        let rates = self
+            .namespace_trackers
+            .get(&interface.namespace)
+            .and_then(|tracker| tracker.last_rates.get(&interface.index))
+            .copied()
+            .unwrap_or_default();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Ok(NetworkBandwidthStats {
+            rx_bytes: nlink_stats.rx_bytes,
+            tx_bytes: nlink_stats.tx_bytes,
+            rx_packets: nlink_stats.rx_packets,
+            tx_packets: nlink_stats.tx_packets,
+            rx_errors: nlink_stats.rx_errors,
+            tx_errors: nlink_stats.tx_errors,
+            rx_dropped: nlink_stats.rx_dropped,
+            tx_dropped: nlink_stats.tx_dropped,
+            timestamp,
+            rx_bytes_per_sec: rates.rx_bytes,
+            tx_bytes_per_sec: rates.tx_bytes,
+            rx_packets_per_sec: rates.rx_packets,
+            tx_packets_per_sec: rates.tx_packets,
+            rx_errors_per_sec: rates.rx_errors,
+            tx_errors_per_sec: rates.tx_errors,
+            rx_dropped_per_sec: rates.rx_dropped,
+            tx_dropped_per_sec: rates.tx_dropped,
+        })
+    }
+
     /// Get netlink statistics for a namespace.
     async fn get_netlink_stats(
         &mut self,
@@ -385,6 +478,15 @@ impl BandwidthMonitor {
     /// Sends a bandwidth update message via Zenoh to the frontend.
     #[instrument(skip(self, update), fields(backend_name = %self.backend_name, namespace = %update.namespace, interface = %update.interface))]
     async fn send_bandwidth_update(&mut self, update: BandwidthUpdate) -> Result<()> {
+        if let Some(exporter) = &self.exporter {
+            export::write_shared(
+                exporter,
+                &update.namespace,
+                &update.interface,
+                ExportEventKind::bandwidth(&update),
+            );
+        }
+
         let payload = serde_json::to_string(&update).map_err(TcguiError::SerializationError)?;
 
         // Create publisher key for this specific interface
@@ -405,7 +507,8 @@ impl BandwidthMonitor {
             let publisher = self
                 .session
                 .declare_publisher(zenoh::key_expr::OwnedKeyExpr::from(bandwidth_topic))
-                .cache(CacheConfig::default().max_samples(1))
+                .advanced()
+                .with_qos(&self.qos)
                 .sample_miss_detection(
                     MissDetectionConfig::default().heartbeat(Duration::from_millis(500)),
                 )
@@ -465,6 +568,12 @@ impl BandwidthMonitor {
                         timestamp,
                         rx_bytes_per_sec: 0.0,
                         tx_bytes_per_sec: 0.0,
+                        rx_packets_per_sec: 0.0,
+                        tx_packets_per_sec: 0.0,
+                        rx_errors_per_sec: 0.0,
+                        tx_errors_per_sec: 0.0,
+                        rx_dropped_per_sec: 0.0,
+                        tx_dropped_per_sec: 0.0,
                     };
 
                     stats.insert(interface_name, bandwidth_stats);
@@ -492,6 +601,47 @@ impl BandwidthMonitor {
             (0.0, 0.0)
         }
     }
+
+    /// Calculates packet rates from current and previous statistics (test helper).
+    #[cfg(test)]
+    pub fn calculate_packet_rates_static(
+        current: &NetworkBandwidthStats,
+        previous: &NetworkBandwidthStats,
+    ) -> (f64, f64) {
+        let time_diff = current.timestamp.saturating_sub(previous.timestamp) as f64;
+
+        if time_diff > 0.0 {
+            let rx_packets_diff = current.rx_packets.saturating_sub(previous.rx_packets) as f64;
+            let tx_packets_diff = current.tx_packets.saturating_sub(previous.tx_packets) as f64;
+
+            (rx_packets_diff / time_diff, tx_packets_diff / time_diff)
+        } else {
+            (0.0, 0.0)
+        }
+    }
+
+    /// Calculates error and drop rates from current and previous statistics
+    /// (test helper). Returns (rx errors, tx errors, rx drops, tx drops).
+    #[cfg(test)]
+    pub fn calculate_error_rates_static(
+        current: &NetworkBandwidthStats,
+        previous: &NetworkBandwidthStats,
+    ) -> (f64, f64, f64, f64) {
+        let time_diff = current.timestamp.saturating_sub(previous.timestamp) as f64;
+
+        if time_diff > 0.0 {
+            let rate =
+                |current: u64, previous: u64| current.saturating_sub(previous) as f64 / time_diff;
+            (
+                rate(current.rx_errors, previous.rx_errors),
+                rate(current.tx_errors, previous.tx_errors),
+                rate(current.rx_dropped, previous.rx_dropped),
+                rate(current.tx_dropped, previous.tx_dropped),
+            )
+        } else {
+            (0.0, 0.0, 0.0, 0.0)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -532,6 +682,12 @@ mod tests {
             tx_dropped: 0,
             rx_bytes_per_sec: 0.0,
             tx_bytes_per_sec: 0.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
         };
 
         let current = NetworkBandwidthStats {
@@ -546,6 +702,12 @@ mod tests {
             tx_dropped: 0,
             rx_bytes_per_sec: 0.0,
             tx_bytes_per_sec: 0.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
         };
 
         let (rx_rate, tx_rate) = BandwidthMonitor::calculate_rates_static(&current, &previous);
@@ -556,6 +718,97 @@ mod tests {
         assert_eq!(tx_rate, 500.0);
     }
 
+    #[test]
+    fn test_calculate_packet_rates() {
+        let previous = NetworkBandwidthStats {
+            rx_bytes: 1000,
+            tx_bytes: 500,
+            timestamp: 100,
+            rx_packets: 10,
+            tx_packets: 5,
+            rx_errors: 0,
+            tx_errors: 0,
+            rx_dropped: 0,
+            tx_dropped: 0,
+            rx_bytes_per_sec: 0.0,
+            tx_bytes_per_sec: 0.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
+        };
+
+        let current = NetworkBandwidthStats {
+            rx_bytes: 3000,
+            tx_bytes: 1500,
+            timestamp: 104, // 4 seconds later
+            rx_packets: 4010,
+            tx_packets: 25,
+            rx_errors: 0,
+            tx_errors: 0,
+            rx_dropped: 0,
+            tx_dropped: 0,
+            rx_bytes_per_sec: 0.0,
+            tx_bytes_per_sec: 0.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
+        };
+
+        let (rx_pps, tx_pps) = BandwidthMonitor::calculate_packet_rates_static(&current, &previous);
+
+        // (4010 - 10) / (104 - 100) = 4000 / 4 = 1000 pps
+        assert_eq!(rx_pps, 1000.0);
+        // (25 - 5) / (104 - 100) = 20 / 4 = 5 pps
+        assert_eq!(tx_pps, 5.0);
+
+        // No time between the samples, no rate
+        assert_eq!(
+            BandwidthMonitor::calculate_packet_rates_static(&previous, &previous),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_calculate_error_rates() {
+        let sample = |timestamp, rx_errors, rx_dropped| NetworkBandwidthStats {
+            rx_bytes: 0,
+            tx_bytes: 0,
+            timestamp,
+            rx_packets: 0,
+            tx_packets: 0,
+            rx_errors,
+            tx_errors: 7,
+            rx_dropped,
+            tx_dropped: 3,
+            rx_bytes_per_sec: 0.0,
+            tx_bytes_per_sec: 0.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
+        };
+        let previous = sample(100, 10, 4);
+        let current = sample(102, 16, 24);
+
+        let (rx_errors, tx_errors, rx_dropped, tx_dropped) =
+            BandwidthMonitor::calculate_error_rates_static(&current, &previous);
+
+        // Increasing counters: (16 - 10) / 2 and (24 - 4) / 2
+        assert_eq!(rx_errors, 3.0);
+        assert_eq!(rx_dropped, 10.0);
+        // Flat counters
+        assert_eq!(tx_errors, 0.0);
+        assert_eq!(tx_dropped, 0.0);
+    }
+
     #[test]
     fn test_namespace_grouping() {
         let mut interfaces = HashMap::new();
@@ -573,6 +826,10 @@ mod tests {
                 addresses: Vec::new(),
                 qdisc_kind: None,
                 link_speed_mbps: None,
+                mtu: None,
+                peer_index: None,
+                management: false,
+                description: None,
             },
         );
 
@@ -589,6 +846,10 @@ mod tests {
                 addresses: Vec::new(),
                 qdisc_kind: None,
                 link_speed_mbps: None,
+                mtu: None,
+                peer_index: None,
+                management: false,
+                description: None,
             },
         );
 
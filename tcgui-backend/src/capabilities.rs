@@ -0,0 +1,172 @@
+//! Netem capability reporting.
+//!
+//! Which netem features can be applied depends on two things: whether the
+//! running kernel's `sch_netem` knows the attribute, and whether this backend
+//! builds it. The kernel side is judged from the release the feature first
+//! shipped in; the result is reported on the `capabilities` query so the GUI
+//! can gray out controls that would only fail.
+//!
+//! The iproute2 version of the `tc` binary is probed alongside: the
+//! qdiscs installed through `tc` need syntax older releases don't parse, and
+//! a failed apply names the version (see [`crate::tc_commands::syntax_mismatch`]).
+
+use tcgui_shared::{NetemCapabilities, NetemCapability, NetemFeatureSupport};
+
+/// Where the running kernel release is read from.
+const OSRELEASE_PATH: &str = "/proc/sys/kernel/osrelease";
+
+/// First kernel release (major, minor) that supports a capability, and
+/// whether this backend can apply it.
+fn requirement(capability: NetemCapability) -> ((u32, u32), bool) {
+    match capability {
+        NetemCapability::Delay
+        | NetemCapability::Jitter
+        | NetemCapability::Loss
+        | NetemCapability::Duplicate
+        | NetemCapability::Corrupt
+        | NetemCapability::Reorder
+        | NetemCapability::DelayDistribution => ((2, 6), true),
+        NetemCapability::Rate | NetemCapability::LossGilbertElliott | NetemCapability::Ecn => {
+            ((3, 3), true)
+        }
+        NetemCapability::Limit => ((2, 6), false),
+        NetemCapability::Slot => ((4, 18), true),
+        NetemCapability::Seed => ((6, 6), true),
+    }
+}
+
+/// Parse the `major.minor` prefix of a kernel release such as
+/// `"6.8.0-45-generic"`.
+pub fn parse_kernel_release(release: &str) -> Option<(u32, u32)> {
+    let mut parts = release.trim().split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts
+        .next()?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .ok()?;
+    Some((major, minor))
+}
+
+/// Capabilities for a kernel `release`. An unknown or unparsable release is
+/// given the benefit of the doubt: only what the backend can't build is
+/// reported unsupported.
+pub fn capabilities_for(release: Option<&str>) -> NetemCapabilities {
+    let version = release.and_then(parse_kernel_release);
+
+    let features = NetemCapability::ALL
+        .into_iter()
+        .map(|capability| {
+            let ((major, minor), implemented) = requirement(capability);
+            let reason = if !implemented {
+                Some("not implemented by this backend".to_string())
+            } else {
+                match version {
+                    Some((have_major, have_minor)) if (have_major, have_minor) < (major, minor) => {
+                        Some(format!(
+                            "requires kernel {major}.{minor} or newer (running {have_major}.{have_minor})"
+                        ))
+                    }
+                    _ => None,
+                }
+            };
+            NetemFeatureSupport {
+                capability,
+                supported: reason.is_none(),
+                reason,
+            }
+        })
+        .collect();
+
+    NetemCapabilities {
+        kernel_release: release.map(|r| r.trim().to_string()),
+        tc_version: None,
+        features,
+    }
+}
+
+/// Parse the iproute2 version from `tc -V` output such as
+/// `"tc utility, iproute2-6.1.0, libbpf 1.1.0"`. Releases before 4.x
+/// print a snapshot tag instead (`"iproute2-ss170501"`), which is kept as is.
+pub fn parse_tc_version(output: &str) -> Option<String> {
+    let (_, rest) = output.split_once("iproute2-")?;
+    let version: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '.')
+        .collect();
+    (!version.is_empty()).then_some(version)
+}
+
+/// The iproute2 version of the `tc` on `PATH`, if it runs.
+fn probe_tc_version() -> Option<String> {
+    let output = std::process::Command::new("tc").arg("-V").output().ok()?;
+    parse_tc_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Probe the running kernel and `tc`.
+pub fn probe() -> NetemCapabilities {
+    let release = std::fs::read_to_string(OSRELEASE_PATH).ok();
+    NetemCapabilities {
+        tc_version: probe_tc_version(),
+        ..capabilities_for(release.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kernel_release() {
+        assert_eq!(parse_kernel_release("6.8.0-45-generic\n"), Some((6, 8)));
+        assert_eq!(parse_kernel_release("4.19.112+"), Some((4, 19)));
+        assert_eq!(parse_kernel_release("5.15-rc1"), Some((5, 15)));
+        assert_eq!(parse_kernel_release("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_tc_version() {
+        assert_eq!(
+            parse_tc_version("tc utility, iproute2-6.1.0, libbpf 1.1.0\n").as_deref(),
+            Some("6.1.0")
+        );
+        assert_eq!(
+            parse_tc_version("tc utility, iproute2-5.15.0\n").as_deref(),
+            Some("5.15.0")
+        );
+        assert_eq!(
+            parse_tc_version("tc utility, iproute2-ss170501\n").as_deref(),
+            Some("ss170501")
+        );
+        assert_eq!(parse_tc_version("tc: command not found"), None);
+    }
+
+    #[test]
+    fn test_capabilities_follow_kernel_and_backend() {
+        let modern = capabilities_for(Some("6.8.0"));
+        assert_eq!(modern.features.len(), NetemCapability::ALL.len());
+        assert!(modern.supports(NetemCapability::Delay));
+        assert!(modern.supports(NetemCapability::Rate));
+        assert!(modern.supports(NetemCapability::Slot));
+        assert!(modern.supports(NetemCapability::Ecn));
+        assert!(modern.supports(NetemCapability::Seed));
+        assert!(!capabilities_for(Some("6.1.0")).supports(NetemCapability::Seed));
+
+        let old = capabilities_for(Some("3.2.0"));
+        assert!(old.supports(NetemCapability::Loss));
+        assert!(!old.supports(NetemCapability::Rate));
+        let rate = old
+            .features
+            .iter()
+            .find(|f| f.capability == NetemCapability::Rate)
+            .unwrap();
+        assert!(rate.reason.as_deref().unwrap().contains("3.3"));
+        assert!(!capabilities_for(Some("4.14.0")).supports(NetemCapability::Slot));
+
+        let unknown = capabilities_for(None);
+        assert!(unknown.supports(NetemCapability::Rate));
+        assert_eq!(unknown.kernel_release, None);
+    }
+}
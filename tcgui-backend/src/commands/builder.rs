@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 use std::process::Command;
+use tcgui_shared::{DelayDistribution, GeModel, TcFilterSpec, TcSlotConfig, TcValidate};
 use tracing::{debug, info, warn};
 
 /// Traffic control qdisc types supported by the builder
@@ -32,6 +33,8 @@ pub enum QdiscType {
     Codel,
     /// Fair queue codel (fq_codel)
     FqCodel,
+    /// Common Applications Kept Enhanced (cake)
+    Cake,
     /// Ingress qdisc for ingress traffic
     Ingress,
     /// Custom qdisc type
@@ -50,6 +53,7 @@ impl fmt::Display for QdiscType {
             QdiscType::Red => write!(f, "red"),
             QdiscType::Codel => write!(f, "codel"),
             QdiscType::FqCodel => write!(f, "fq_codel"),
+            QdiscType::Cake => write!(f, "cake"),
             QdiscType::Ingress => write!(f, "ingress"),
             QdiscType::Custom(name) => write!(f, "{}", name),
         }
@@ -107,9 +111,16 @@ impl fmt::Display for TcTarget {
 pub struct NetemParams {
     pub loss_percent: Option<f32>,
     pub loss_correlation: Option<f32>,
+    /// Gilbert-Elliott loss, emitted in place of `loss_percent`
+    pub loss_gemodel: Option<GeModel>,
+    /// ECN-mark lost packets instead of dropping them; needs a loss clause
+    #[serde(default)]
+    pub loss_ecn: bool,
     pub delay_ms: Option<f32>,
     pub delay_jitter_ms: Option<f32>,
     pub delay_correlation: Option<f32>,
+    /// Shape of the jitter; `None` leaves netem's uniform default
+    pub delay_distribution: Option<DelayDistribution>,
     pub duplicate_percent: Option<f32>,
     pub duplicate_correlation: Option<f32>,
     pub reorder_percent: Option<f32>,
@@ -118,6 +129,11 @@ pub struct NetemParams {
     pub corrupt_percent: Option<f32>,
     pub corrupt_correlation: Option<f32>,
     pub rate_limit_kbps: Option<u32>,
+    /// Slotted delivery; emitted only while enabled
+    pub slot: Option<TcSlotConfig>,
+    /// Seed for netem's random choices; `None` lets the kernel pick one
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 /// Token bucket filter (TBF) parameters
@@ -138,31 +154,28 @@ pub struct HtbParams {
     pub direct_qlen: Option<u32>,
 }
 
-/// Priority qdisc parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PrioParams {
-    pub bands: Option<u32>,
-    pub priomap: Option<Vec<u32>>,
-}
+/// Priority (PRIO), stochastic fair queueing (SFQ), random early detection
+/// (RED), fq_codel and CAKE parameters, shared with the message layer so
+/// these qdiscs can be requested over Zenoh
+pub use tcgui_shared::{CakeDiffserv, CakeParams, FqCodelParams, PrioParams, RedParams, SfqParams};
 
-/// Stochastic fair queueing (SFQ) parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SfqParams {
-    pub perturb: Option<u32>,
-    pub quantum: Option<u32>,
-    pub limit: Option<u32>,
+/// What a u32 filter does with the packets it matches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum U32FilterAction {
+    /// Send them to a class, e.g. "1:4"
+    Classify(String),
+    /// Hand them to another device's egress (`action mirred egress redirect`)
+    Redirect(String),
 }
 
-/// Random early detection (RED) parameters
+/// A u32 classifier acting on the packets matching `spec`
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RedParams {
-    pub limit: Option<u32>,
-    pub min: Option<u32>,
-    pub max: Option<u32>,
-    pub avpkt: Option<u32>,
-    pub burst: Option<u32>,
-    pub probability: Option<f32>,
-    pub bandwidth: Option<String>,
+pub struct U32FilterParams {
+    /// Packets to match; `None` matches every packet
+    pub spec: Option<TcFilterSpec>,
+    /// Filter priority; filters with a lower one are tried first
+    pub priority: u16,
+    pub action: U32FilterAction,
 }
 
 /// Qdisc-specific parameters
@@ -174,6 +187,8 @@ pub enum QdiscParams {
     Prio(PrioParams),
     Sfq(SfqParams),
     Red(RedParams),
+    FqCodel(FqCodelParams),
+    Cake(CakeParams),
     /// No parameters for simple qdiscs
     None,
     /// Custom parameters as key-value pairs
@@ -193,8 +208,12 @@ pub struct TcCommandBuilder {
     qdisc_type: Option<QdiscType>,
     /// Command target (root, ingress, handle, parent)
     target: Option<TcTarget>,
+    /// Handle given to the qdisc
+    handle: Option<String>,
     /// Qdisc-specific parameters
     params: Option<QdiscParams>,
+    /// Build a `tc filter` command instead of a `tc qdisc` one
+    filter: Option<U32FilterParams>,
     /// Whether to use sudo
     use_sudo: bool,
     /// Additional raw arguments
@@ -212,7 +231,9 @@ impl TcCommandBuilder {
             namespace: None,
             qdisc_type: None,
             target: None,
+            handle: None,
             params: None,
+            filter: None,
             use_sudo: false,
             raw_args: Vec::new(),
             validate: true,
@@ -250,12 +271,24 @@ impl TcCommandBuilder {
         self
     }
 
+    /// Set the handle of the qdisc, e.g. "1:"
+    pub fn handle<S: Into<String>>(mut self, handle: S) -> Self {
+        self.handle = Some(handle.into());
+        self
+    }
+
     /// Set qdisc parameters
     pub fn params(mut self, params: QdiscParams) -> Self {
         self.params = Some(params);
         self
     }
 
+    /// Build a u32 filter attached at the target instead of a qdisc
+    pub fn u32_filter(mut self, filter: U32FilterParams) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// Enable sudo usage
     pub fn with_sudo(mut self) -> Self {
         self.use_sudo = true;
@@ -290,7 +323,9 @@ impl TcCommandBuilder {
             namespace: self.namespace,
             qdisc_type: self.qdisc_type.unwrap_or(QdiscType::Netem),
             target: self.target.unwrap_or(TcTarget::Root),
+            handle: self.handle,
             params: self.params.unwrap_or(QdiscParams::None),
+            filter: self.filter,
             use_sudo: self.use_sudo,
             raw_args: self.raw_args,
         })
@@ -314,11 +349,25 @@ impl TcCommandBuilder {
             return Err(anyhow!("Device interface cannot be empty"));
         }
 
-        // Validate qdisc type for operations that need it
-        if matches!(
+        // A filter matches on its own parameters and has no qdisc
+        if let Some(ref filter) = self.filter {
+            if let Some(ref spec) = filter.spec {
+                spec.validate().map_err(|e| anyhow!("{}", e.message))?;
+            }
+            match &filter.action {
+                U32FilterAction::Classify(flowid) if flowid.is_empty() => {
+                    return Err(anyhow!("Filter flowid must be specified"));
+                }
+                U32FilterAction::Redirect(device) if device.is_empty() => {
+                    return Err(anyhow!("Filter redirect device must be specified"));
+                }
+                _ => {}
+            }
+        } else if matches!(
             operation,
             TcOperation::Add | TcOperation::Replace | TcOperation::Change
         ) {
+            // Validate qdisc type for operations that need it
             let qdisc_type = self.qdisc_type.as_ref().ok_or_else(|| {
                 anyhow!("Qdisc type must be specified for {} operation", operation)
             })?;
@@ -348,12 +397,30 @@ impl TcCommandBuilder {
             (QdiscType::Tbf, QdiscParams::Tbf(tbf)) => {
                 self.validate_tbf_params(tbf)?;
             }
+            (QdiscType::Red, QdiscParams::Red(red)) => {
+                red.validate().map_err(|e| anyhow!("{}", e.message))?;
+            }
+            (QdiscType::Sfq, QdiscParams::Sfq(sfq)) => {
+                sfq.validate().map_err(|e| anyhow!("{}", e.message))?;
+            }
+            (QdiscType::Prio, QdiscParams::Prio(prio)) => {
+                prio.validate().map_err(|e| anyhow!("{}", e.message))?;
+            }
+            (QdiscType::FqCodel, QdiscParams::FqCodel(fq_codel)) => {
+                fq_codel.validate().map_err(|e| anyhow!("{}", e.message))?;
+            }
+            (QdiscType::Cake, QdiscParams::Cake(cake)) => {
+                cake.validate().map_err(|e| anyhow!("{}", e.message))?;
+            }
             (QdiscType::Netem, _) => {
                 warn!("Netem qdisc without netem parameters - using defaults");
             }
             (QdiscType::Tbf, _) => {
                 return Err(anyhow!("TBF qdisc requires TBF parameters"));
             }
+            (QdiscType::Red, _) => {
+                return Err(anyhow!("RED qdisc requires RED parameters"));
+            }
             _ => {
                 // Other combinations are allowed
             }
@@ -370,6 +437,14 @@ impl TcCommandBuilder {
             return Err(anyhow!("Loss percentage must be between 0.0 and 100.0"));
         }
 
+        if let Some(model) = &params.loss_gemodel {
+            model.validate().map_err(|e| anyhow!("{}", e.message))?;
+        }
+
+        if params.loss_ecn && !TcCommand::has_loss(params) {
+            return Err(anyhow!("ECN marking requires packet loss"));
+        }
+
         // Validate correlation values
         for (name, value) in [
             ("loss_correlation", params.loss_correlation),
@@ -398,6 +473,16 @@ impl TcCommandBuilder {
             return Err(anyhow!("Delay jitter cannot be negative"));
         }
 
+        if let Some(distribution) = params.delay_distribution
+            && distribution.requires_jitter()
+            && params.delay_jitter_ms.is_none_or(|jitter| jitter <= 0.0)
+        {
+            return Err(anyhow!(
+                "The {} delay distribution requires a delay jitter",
+                distribution
+            ));
+        }
+
         // Validate percentage values
         for (name, value) in [
             ("duplicate_percent", params.duplicate_percent),
@@ -425,6 +510,10 @@ impl TcCommandBuilder {
             return Err(anyhow!("Rate limit must be greater than 0"));
         }
 
+        if let Some(slot) = &params.slot {
+            slot.validate().map_err(|e| anyhow!("{}", e.message))?;
+        }
+
         Ok(())
     }
 
@@ -459,7 +548,9 @@ pub struct TcCommand {
     namespace: Option<String>,
     qdisc_type: QdiscType,
     target: TcTarget,
+    handle: Option<String>,
     params: QdiscParams,
+    filter: Option<U32FilterParams>,
     use_sudo: bool,
     raw_args: Vec<String>,
 }
@@ -484,12 +575,25 @@ impl TcCommand {
         };
 
         // Add operation and basic structure
-        cmd.args(["qdisc", &self.operation.to_string()]);
+        let object = if self.filter.is_some() {
+            "filter"
+        } else {
+            "qdisc"
+        };
+        cmd.args([object, &self.operation.to_string()]);
         cmd.args(["dev", &self.device]);
-        cmd.arg(self.target.to_string());
+        cmd.args(self.target.to_string().split(' '));
+        if let Some(ref handle) = self.handle {
+            cmd.args(["handle", handle]);
+        }
 
-        // Add qdisc type and parameters
-        if !matches!(self.operation, TcOperation::Delete | TcOperation::Show) {
+        // Add the filter match, or the qdisc type and parameters. `ingress`
+        // names the qdisc's kind as well as where it goes.
+        if let Some(ref filter) = self.filter {
+            self.add_u32_filter(&mut cmd, filter);
+        } else if !matches!(self.operation, TcOperation::Delete | TcOperation::Show)
+            && self.target != TcTarget::Ingress
+        {
             cmd.arg(self.qdisc_type.to_string());
             self.add_qdisc_params(&mut cmd);
         }
@@ -523,6 +627,12 @@ impl TcCommand {
             QdiscParams::Red(params) => {
                 self.add_red_params(cmd, params);
             }
+            QdiscParams::FqCodel(params) => {
+                self.add_fq_codel_params(cmd, params);
+            }
+            QdiscParams::Cake(params) => {
+                self.add_cake_params(cmd, params);
+            }
             QdiscParams::Custom(params) => {
                 for (key, value) in params {
                     cmd.args([key, value]);
@@ -534,9 +644,23 @@ impl TcCommand {
         }
     }
 
+    /// Whether the netem parameters emit a loss clause
+    fn has_loss(params: &NetemParams) -> bool {
+        params.loss_gemodel.is_some() || params.loss_percent.is_some_and(|loss| loss > 0.0)
+    }
+
     /// Add netem parameters
     fn add_netem_params(&self, cmd: &mut Command, params: &NetemParams) {
-        if let Some(loss) = params.loss_percent
+        if let Some(model) = &params.loss_gemodel {
+            cmd.args([
+                "loss",
+                "gemodel",
+                &format!("{}%", model.p),
+                &format!("{}%", model.r),
+                &format!("{}%", model.one_h),
+                &format!("{}%", model.one_k),
+            ]);
+        } else if let Some(loss) = params.loss_percent
             && loss > 0.0
         {
             cmd.args(["loss", &format!("{}%", loss)]);
@@ -546,6 +670,9 @@ impl TcCommand {
                 cmd.arg(format!("{}%", corr));
             }
         }
+        if params.loss_ecn && Self::has_loss(params) {
+            cmd.arg("ecn");
+        }
 
         if let Some(delay) = params.delay_ms
             && delay > 0.0
@@ -560,6 +687,11 @@ impl TcCommand {
                 {
                     cmd.arg(format!("{}%", corr));
                 }
+                if let Some(distribution) = params.delay_distribution
+                    && !distribution.is_uniform()
+                {
+                    cmd.args(["distribution", distribution.name()]);
+                }
             }
         }
 
@@ -608,6 +740,14 @@ impl TcCommand {
                 cmd.args(["rate", &format!("{}kbit", rate)]);
             }
         }
+
+        if let Some(slot) = params.slot.as_ref().filter(|slot| slot.is_active()) {
+            cmd.args(slot.clause().split_whitespace());
+        }
+
+        if let Some(seed) = params.seed {
+            cmd.args(["seed", &seed.to_string()]);
+        }
     }
 
     /// Add TBF parameters
@@ -652,13 +792,10 @@ impl TcCommand {
             cmd.args(["bands", &format!("{}", bands)]);
         }
 
+        // tc expects every band of the map as its own argument
         if let Some(ref priomap) = params.priomap {
-            let priomap_str = priomap
-                .iter()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-                .join(" ");
-            cmd.args(["priomap", &priomap_str]);
+            cmd.arg("priomap");
+            cmd.args(priomap.iter().map(|band| band.to_string()));
         }
     }
 
@@ -708,6 +845,77 @@ impl TcCommand {
         }
     }
 
+    /// Add fq_codel parameters
+    fn add_fq_codel_params(&self, cmd: &mut Command, params: &FqCodelParams) {
+        if let Some(target) = params.target_us {
+            cmd.args(["target", &format!("{}us", target)]);
+        }
+
+        if let Some(interval) = params.interval_us {
+            cmd.args(["interval", &format!("{}us", interval)]);
+        }
+
+        if let Some(quantum) = params.quantum {
+            cmd.args(["quantum", &format!("{}", quantum)]);
+        }
+
+        match params.ecn {
+            Some(true) => {
+                cmd.arg("ecn");
+            }
+            Some(false) => {
+                cmd.arg("noecn");
+            }
+            None => {}
+        }
+    }
+
+    /// Add CAKE parameters
+    fn add_cake_params(&self, cmd: &mut Command, params: &CakeParams) {
+        if let Some(bandwidth) = params.bandwidth() {
+            cmd.args(["bandwidth", &bandwidth]);
+        }
+
+        if let Some(rtt) = params.rtt_ms {
+            cmd.args(["rtt", &format!("{}ms", rtt)]);
+        }
+
+        if let Some(diffserv) = params.diffserv {
+            cmd.arg(diffserv.name());
+        }
+    }
+
+    /// Add the protocol, priority, u32 matches and action of a filter
+    fn add_u32_filter(&self, cmd: &mut Command, filter: &U32FilterParams) {
+        let protocol = match &filter.spec {
+            Some(spec) if spec.is_ipv6() => "ipv6",
+            Some(_) => "ip",
+            None => "all",
+        };
+        cmd.args(["protocol", protocol]);
+        cmd.args(["prio", &filter.priority.to_string()]);
+        cmd.arg("u32");
+        match &filter.spec {
+            Some(spec) => {
+                for selector in spec.u32_matches() {
+                    cmd.args(selector.split(' '));
+                }
+            }
+            // Any 32 bits masked to nothing: every packet
+            None => {
+                cmd.args(["match", "u32", "0", "0"]);
+            }
+        }
+        match &filter.action {
+            U32FilterAction::Classify(flowid) => {
+                cmd.args(["flowid", flowid]);
+            }
+            U32FilterAction::Redirect(device) => {
+                cmd.args(["action", "mirred", "egress", "redirect", "dev", device]);
+            }
+        }
+    }
+
     /// Execute the command
     pub async fn execute(&self) -> Result<std::process::Output> {
         let mut cmd = self.to_command();
@@ -774,6 +982,606 @@ mod tests {
         assert!(args.contains(&"100ms".to_string()));
     }
 
+    #[test]
+    fn test_netem_loss_gemodel() {
+        let netem_params = NetemParams {
+            loss_percent: Some(5.0),
+            loss_gemodel: Some(GeModel {
+                p: 1.5,
+                r: 20.0,
+                one_h: 90.0,
+                one_k: 0.0,
+            }),
+            ..Default::default()
+        };
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(netem_params.clone()))
+            .build()
+            .unwrap();
+        let args = cmd.to_args();
+        let position = args.iter().position(|arg| arg == "loss").unwrap();
+        assert_eq!(
+            args[position..position + 6],
+            ["loss", "gemodel", "1.5%", "20%", "90%", "0%"]
+        );
+        assert!(!args.contains(&"5%".to_string()));
+
+        // The model never leaving the good state is rejected
+        let result = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(NetemParams {
+                loss_gemodel: Some(GeModel {
+                    p: 0.0,
+                    ..GeModel::default()
+                }),
+                ..netem_params
+            }))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_netem_loss_ecn() {
+        let netem_params = NetemParams {
+            loss_percent: Some(2.0),
+            loss_correlation: Some(25.0),
+            loss_ecn: true,
+            delay_ms: Some(10.0),
+            ..Default::default()
+        };
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(netem_params.clone()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args().join(" "),
+            "tc qdisc replace dev eth0 root netem loss 2% 25% ecn delay 10ms"
+        );
+
+        // There is nothing to mark without loss
+        let result = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(NetemParams {
+                loss_percent: None,
+                ..netem_params
+            }))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_netem_seed() {
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(NetemParams {
+                loss_percent: Some(1.0),
+                seed: Some(42),
+                ..Default::default()
+            }))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args().join(" "),
+            "tc qdisc replace dev eth0 root netem loss 1% seed 42"
+        );
+    }
+
+    #[test]
+    fn test_netem_slot() {
+        let netem_params = NetemParams {
+            delay_ms: Some(5.0),
+            slot: Some(TcSlotConfig {
+                enabled: true,
+                bytes: Some(64000),
+                ..TcSlotConfig::wifi_default()
+            }),
+            ..Default::default()
+        };
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(netem_params.clone()))
+            .build()
+            .unwrap();
+        let args = cmd.to_args();
+        let position = args.iter().position(|arg| arg == "slot").unwrap();
+        assert_eq!(
+            args[position..],
+            ["slot", "10ms", "20ms", "packets", "32", "bytes", "64000"]
+        );
+
+        // A slot that closes before it opens is rejected
+        let result = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(NetemParams {
+                slot: Some(TcSlotConfig {
+                    enabled: true,
+                    min_delay_ms: 30.0,
+                    ..TcSlotConfig::wifi_default()
+                }),
+                ..netem_params
+            }))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_red_command() {
+        let red = RedParams {
+            limit: Some(400_000),
+            min: Some(30_000),
+            max: Some(90_000),
+            avpkt: Some(1000),
+            probability: Some(0.02),
+            ..Default::default()
+        };
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .namespace("test-ns")
+            .qdisc(QdiscType::Red)
+            .params(QdiscParams::Red(red.clone()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args(),
+            [
+                "ip",
+                "netns",
+                "exec",
+                "test-ns",
+                "tc",
+                "qdisc",
+                "replace",
+                "dev",
+                "eth0",
+                "root",
+                "red",
+                "limit",
+                "400000",
+                "min",
+                "30000",
+                "max",
+                "90000",
+                "avpkt",
+                "1000",
+                "probability",
+                "0.02",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_red_validation() {
+        let build = |params: QdiscParams| {
+            TcCommandBuilder::new()
+                .operation(TcOperation::Replace)
+                .device("eth0")
+                .qdisc(QdiscType::Red)
+                .params(params)
+                .build()
+        };
+        let red = RedParams {
+            limit: Some(400_000),
+            min: Some(30_000),
+            max: Some(90_000),
+            avpkt: Some(1000),
+            ..Default::default()
+        };
+        assert!(build(QdiscParams::Red(red.clone())).is_ok());
+
+        // Thresholds out of order, an impossible probability and missing
+        // parameters are all refused before tc runs
+        for invalid in [
+            RedParams {
+                min: Some(90_000),
+                max: Some(30_000),
+                ..red.clone()
+            },
+            RedParams {
+                probability: Some(2.0),
+                ..red.clone()
+            },
+            RedParams {
+                avpkt: None,
+                ..red.clone()
+            },
+        ] {
+            assert!(build(QdiscParams::Red(invalid)).is_err());
+        }
+        assert!(build(QdiscParams::None).is_err());
+    }
+
+    #[test]
+    fn test_sfq_command() {
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Sfq)
+            .params(QdiscParams::Sfq(SfqParams {
+                perturb: Some(10),
+                quantum: None,
+                limit: Some(127),
+            }))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args(),
+            [
+                "tc", "qdisc", "replace", "dev", "eth0", "root", "sfq", "perturb", "10", "limit",
+                "127",
+            ]
+        );
+
+        // SFQ works without any parameters, but not with zero sized ones
+        let build = |params: QdiscParams| {
+            TcCommandBuilder::new()
+                .operation(TcOperation::Replace)
+                .device("eth0")
+                .qdisc(QdiscType::Sfq)
+                .params(params)
+                .build()
+        };
+        assert!(build(QdiscParams::None).is_ok());
+        assert!(
+            build(QdiscParams::Sfq(SfqParams {
+                quantum: Some(0),
+                ..SfqParams::default()
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_prio_command() {
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Prio)
+            .params(QdiscParams::Prio(PrioParams {
+                bands: Some(2),
+                priomap: Some(vec![1, 0, 1]),
+            }))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args(),
+            [
+                "tc", "qdisc", "replace", "dev", "eth0", "root", "prio", "bands", "2", "priomap",
+                "1", "0", "1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prio_root_with_handle() {
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .handle("1:")
+            .qdisc(QdiscType::Prio)
+            .params(QdiscParams::Prio(PrioParams {
+                bands: Some(4),
+                priomap: None,
+            }))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args(),
+            [
+                "tc", "qdisc", "replace", "dev", "eth0", "root", "handle", "1:", "prio", "bands",
+                "4",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_u32_filter_command() {
+        let filter = U32FilterParams {
+            spec: Some(TcFilterSpec {
+                dst_ip: Some("10.0.0.5".to_string()),
+                dst_port: Some(8080),
+                protocol: Some(tcgui_shared::TcFilterProtocol::Tcp),
+                ..Default::default()
+            }),
+            priority: 1,
+            action: U32FilterAction::Classify("1:4".to_string()),
+        };
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Add)
+            .device("eth0")
+            .namespace("test-ns")
+            .target(TcTarget::Parent("1:".to_string()))
+            .u32_filter(filter.clone())
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args(),
+            [
+                "ip",
+                "netns",
+                "exec",
+                "test-ns",
+                "tc",
+                "filter",
+                "add",
+                "dev",
+                "eth0",
+                "parent",
+                "1:",
+                "protocol",
+                "ip",
+                "prio",
+                "1",
+                "u32",
+                "match",
+                "ip",
+                "dst",
+                "10.0.0.5/32",
+                "match",
+                "ip",
+                "dport",
+                "8080",
+                "0xffff",
+                "match",
+                "ip",
+                "protocol",
+                "6",
+                "0xff",
+                "flowid",
+                "1:4",
+            ]
+        );
+
+        // IPv6 filters match on ip6 selectors
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Add)
+            .device("eth0")
+            .target(TcTarget::Parent("1:".to_string()))
+            .u32_filter(U32FilterParams {
+                spec: Some(TcFilterSpec {
+                    src_ip: Some("fd00::/64".to_string()),
+                    ..Default::default()
+                }),
+                ..filter.clone()
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args(),
+            [
+                "tc",
+                "filter",
+                "add",
+                "dev",
+                "eth0",
+                "parent",
+                "1:",
+                "protocol",
+                "ipv6",
+                "prio",
+                "1",
+                "u32",
+                "match",
+                "ip6",
+                "src",
+                "fd00::/64",
+                "flowid",
+                "1:4",
+            ]
+        );
+
+        // A filter without any match field is refused
+        let result = TcCommandBuilder::new()
+            .operation(TcOperation::Add)
+            .device("eth0")
+            .target(TcTarget::Parent("1:".to_string()))
+            .u32_filter(U32FilterParams {
+                spec: Some(TcFilterSpec::default()),
+                ..filter
+            })
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ingress_redirect_commands() {
+        let ingress = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .target(TcTarget::Ingress)
+            .qdisc(QdiscType::Ingress)
+            .build()
+            .unwrap();
+        assert_eq!(
+            ingress.to_args(),
+            ["tc", "qdisc", "replace", "dev", "eth0", "ingress"]
+        );
+
+        let redirect = TcCommandBuilder::new()
+            .operation(TcOperation::Add)
+            .device("eth0")
+            .target(TcTarget::Parent("ffff:".to_string()))
+            .u32_filter(U32FilterParams {
+                spec: None,
+                priority: 1,
+                action: U32FilterAction::Redirect("tcifb3".to_string()),
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            redirect.to_args(),
+            [
+                "tc", "filter", "add", "dev", "eth0", "parent", "ffff:", "protocol", "all", "prio",
+                "1", "u32", "match", "u32", "0", "0", "action", "mirred", "egress", "redirect",
+                "dev", "tcifb3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prio_validation() {
+        let build = |prio: PrioParams| {
+            TcCommandBuilder::new()
+                .operation(TcOperation::Replace)
+                .device("eth0")
+                .qdisc(QdiscType::Prio)
+                .params(QdiscParams::Prio(prio))
+                .build()
+        };
+        assert!(build(PrioParams::default()).is_ok());
+        assert!(
+            build(PrioParams {
+                bands: Some(16),
+                priomap: Some(vec![15; 16]),
+            })
+            .is_ok()
+        );
+
+        // Band counts the kernel refuses, oversized maps and bands past the
+        // last one are all refused before tc runs
+        for invalid in [
+            PrioParams {
+                bands: Some(1),
+                priomap: None,
+            },
+            PrioParams {
+                bands: Some(17),
+                priomap: None,
+            },
+            PrioParams {
+                bands: Some(4),
+                priomap: Some(vec![0; 17]),
+            },
+            PrioParams {
+                bands: Some(2),
+                priomap: Some(vec![0, 1, 2]),
+            },
+        ] {
+            assert!(build(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_fq_codel_command() {
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Add)
+            .device("eth0")
+            .qdisc(QdiscType::FqCodel)
+            .params(QdiscParams::FqCodel(FqCodelParams {
+                target_us: Some(5000),
+                interval_us: Some(100_000),
+                quantum: None,
+                ecn: Some(false),
+            }))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.to_args().join(" "),
+            "tc qdisc add dev eth0 root fq_codel target 5000us interval 100000us noecn"
+        );
+
+        let invalid = TcCommandBuilder::new()
+            .operation(TcOperation::Add)
+            .device("eth0")
+            .qdisc(QdiscType::FqCodel)
+            .params(QdiscParams::FqCodel(FqCodelParams {
+                target_us: Some(100_000),
+                interval_us: Some(5000),
+                ..FqCodelParams::default()
+            }))
+            .build();
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_cake_command() {
+        let build = |cake: CakeParams| {
+            TcCommandBuilder::new()
+                .operation(TcOperation::Add)
+                .device("eth0")
+                .qdisc(QdiscType::Cake)
+                .params(QdiscParams::Cake(cake))
+                .build()
+        };
+        let cmd = build(CakeParams {
+            bandwidth_kbps: Some(10_000),
+            rtt_ms: Some(50),
+            diffserv: None,
+        })
+        .unwrap();
+        assert_eq!(
+            cmd.to_args().join(" "),
+            "tc qdisc add dev eth0 root cake bandwidth 10Mbit rtt 50ms"
+        );
+
+        let cmd = build(CakeParams {
+            diffserv: Some(CakeDiffserv::Besteffort),
+            ..CakeParams::default()
+        })
+        .unwrap();
+        assert_eq!(
+            cmd.to_args().join(" "),
+            "tc qdisc add dev eth0 root cake besteffort"
+        );
+
+        assert!(
+            build(CakeParams {
+                bandwidth_kbps: Some(0),
+                ..CakeParams::default()
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_netem_delay_distribution() {
+        let netem_params = NetemParams {
+            delay_ms: Some(100.0),
+            delay_jitter_ms: Some(20.0),
+            delay_distribution: Some(DelayDistribution::Pareto),
+            ..Default::default()
+        };
+        let cmd = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(netem_params.clone()))
+            .build()
+            .unwrap();
+        let args = cmd.to_args();
+        let position = args.iter().position(|arg| arg == "distribution").unwrap();
+        assert_eq!(args[position - 1], "20ms");
+        assert_eq!(args[position + 1], "pareto");
+
+        // A shaped distribution has nothing to shape without a jitter
+        let result = TcCommandBuilder::new()
+            .operation(TcOperation::Replace)
+            .device("eth0")
+            .qdisc(QdiscType::Netem)
+            .params(QdiscParams::Netem(NetemParams {
+                delay_jitter_ms: None,
+                ..netem_params
+            }))
+            .build();
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_tbf_command() {
         let tbf_params = TbfParams {
@@ -7,8 +7,9 @@ pub mod builder;
 pub mod templates;
 
 pub use builder::{
-    HtbParams, NetemParams, PrioParams, QdiscParams, QdiscType, RedParams, SfqParams, TbfParams,
-    TcCommand, TcCommandBuilder, TcOperation, TcTarget,
+    CakeDiffserv, CakeParams, FqCodelParams, HtbParams, NetemParams, PrioParams, QdiscParams,
+    QdiscType, RedParams, SfqParams, TbfParams, TcCommand, TcCommandBuilder, TcOperation, TcTarget,
+    U32FilterAction, U32FilterParams,
 };
 pub use templates::{
     CustomTemplate, PredefinedTemplate, TcTemplate, TemplateCategory, TemplateManager,
@@ -8,6 +8,9 @@ use std::env;
 use tracing_subscriber;
 
 use super::cli::CliConfig;
+use crate::interface_filter::{InterfaceFilter, LOOPBACK_PATTERN};
+use crate::link_policy::LinkDownTcPolicy;
+use tcgui_shared::qos::QosProfiles;
 
 /// Log level enumeration
 #[derive(Debug, Clone, PartialEq)]
@@ -37,6 +40,9 @@ impl LogLevel {
 pub struct AppConfig {
     pub backend_name: String,
     pub exclude_loopback: bool,
+    /// Interface exclusion patterns, including the loopback pattern when
+    /// `exclude_loopback` is set
+    pub exclude_patterns: Vec<String>,
     pub log_level: LogLevel,
     pub interface_monitor_interval_secs: u64,
     pub bandwidth_monitor_interval_secs: u64,
@@ -44,6 +50,20 @@ pub struct AppConfig {
     pub no_default_scenarios: bool,
     pub preset_dirs: Vec<String>,
     pub no_default_presets: bool,
+    /// Publish connected peer ids (not just the count) in the health status
+    pub expose_peer_info: bool,
+    /// Queries per second accepted from one peer (0 = unlimited)
+    pub query_rate_limit: u32,
+    /// Interfaces that may be impaired at the same time (0 = unlimited)
+    pub max_impaired_interfaces: u32,
+    /// File to append JSON-lines bandwidth and TC events to
+    pub export_jsonl: Option<String>,
+    /// JSON5 file mapping interface names to operator descriptions
+    pub interface_descriptions: Option<String>,
+    /// TC configuration handling when an interface goes down
+    pub link_down_policy: LinkDownTcPolicy,
+    /// Zenoh QoS per published message type
+    pub qos: QosProfiles,
 }
 
 impl AppConfig {
@@ -62,16 +82,44 @@ impl AppConfig {
         Ok(Self {
             backend_name: cli_config.backend_name.clone(),
             exclude_loopback: cli_config.exclude_loopback,
+            exclude_patterns: Self::resolve_exclude_patterns(
+                cli_config.exclude_loopback,
+                cli_config.exclude_patterns.clone(),
+            ),
             log_level,
             interface_monitor_interval_secs: 5, // Default 5 seconds
-            bandwidth_monitor_interval_secs: 2, // Default 2 seconds
+            bandwidth_monitor_interval_secs: cli_config
+                .config_file
+                .as_ref()
+                .and_then(|file| file.monitor.bandwidth_interval_secs)
+                .unwrap_or(2), // Default 2 seconds
             scenario_dirs: cli_config.scenario_dirs.clone(),
             no_default_scenarios: cli_config.no_default_scenarios,
             preset_dirs: cli_config.preset_dirs.clone(),
             no_default_presets: cli_config.no_default_presets,
+            expose_peer_info: cli_config.expose_peer_info,
+            query_rate_limit: cli_config.query_rate_limit,
+            max_impaired_interfaces: cli_config.max_impaired_interfaces,
+            export_jsonl: cli_config.export_jsonl.clone(),
+            interface_descriptions: cli_config.interface_descriptions.clone(),
+            link_down_policy: cli_config
+                .tc_on_link_down
+                .parse()
+                .map_err(|e: String| anyhow::anyhow!(e))?,
+            qos: QosProfiles::with_overrides(cli_config.qos.iter().map(String::as_str))
+                .map_err(|e| anyhow::anyhow!(e))?,
         })
     }
 
+    /// The exclusion patterns in effect: `patterns` plus the loopback
+    /// pattern when loopback is excluded
+    fn resolve_exclude_patterns(exclude_loopback: bool, mut patterns: Vec<String>) -> Vec<String> {
+        if exclude_loopback && !patterns.iter().any(|p| p == LOOPBACK_PATTERN) {
+            patterns.insert(0, LOOPBACK_PATTERN.to_string());
+        }
+        patterns
+    }
+
     /// Parse log level from string
     fn parse_log_level(level_str: &str) -> LogLevel {
         // Extract the main log level from complex RUST_LOG format
@@ -166,6 +214,8 @@ impl AppConfig {
             ));
         }
 
+        InterfaceFilter::new(&self.exclude_patterns)?;
+
         Ok(())
     }
 }
@@ -174,6 +224,7 @@ impl AppConfig {
 pub struct AppConfigBuilder {
     backend_name: Option<String>,
     exclude_loopback: Option<bool>,
+    exclude_patterns: Option<Vec<String>>,
     log_level: Option<LogLevel>,
     interface_monitor_interval_secs: Option<u64>,
     bandwidth_monitor_interval_secs: Option<u64>,
@@ -181,6 +232,13 @@ pub struct AppConfigBuilder {
     no_default_scenarios: Option<bool>,
     preset_dirs: Option<Vec<String>>,
     no_default_presets: Option<bool>,
+    expose_peer_info: Option<bool>,
+    query_rate_limit: Option<u32>,
+    max_impaired_interfaces: Option<u32>,
+    export_jsonl: Option<String>,
+    interface_descriptions: Option<String>,
+    link_down_policy: Option<LinkDownTcPolicy>,
+    qos: Option<QosProfiles>,
 }
 
 impl AppConfigBuilder {
@@ -189,6 +247,7 @@ impl AppConfigBuilder {
         Self {
             backend_name: None,
             exclude_loopback: None,
+            exclude_patterns: None,
             log_level: None,
             interface_monitor_interval_secs: None,
             bandwidth_monitor_interval_secs: None,
@@ -196,6 +255,13 @@ impl AppConfigBuilder {
             no_default_scenarios: None,
             preset_dirs: None,
             no_default_presets: None,
+            expose_peer_info: None,
+            query_rate_limit: None,
+            max_impaired_interfaces: None,
+            export_jsonl: None,
+            interface_descriptions: None,
+            link_down_policy: None,
+            qos: None,
         }
     }
 
@@ -211,6 +277,12 @@ impl AppConfigBuilder {
         self
     }
 
+    /// Set interface exclusion patterns
+    pub fn exclude_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.exclude_patterns = Some(patterns);
+        self
+    }
+
     /// Set log level
     pub fn log_level(mut self, level: LogLevel) -> Self {
         self.log_level = Some(level);
@@ -253,6 +325,48 @@ impl AppConfigBuilder {
         self
     }
 
+    /// Set peer info exposure flag
+    pub fn expose_peer_info(mut self, expose: bool) -> Self {
+        self.expose_peer_info = Some(expose);
+        self
+    }
+
+    /// Set the per-peer query rate limit (0 = unlimited)
+    pub fn query_rate_limit(mut self, per_sec: u32) -> Self {
+        self.query_rate_limit = Some(per_sec);
+        self
+    }
+
+    /// Set the simultaneously impaired interface limit (0 = unlimited)
+    pub fn max_impaired_interfaces(mut self, max: u32) -> Self {
+        self.max_impaired_interfaces = Some(max);
+        self
+    }
+
+    /// Set the JSON-lines export file
+    pub fn export_jsonl<S: Into<String>>(mut self, path: S) -> Self {
+        self.export_jsonl = Some(path.into());
+        self
+    }
+
+    /// Set the interface descriptions file
+    pub fn interface_descriptions<S: Into<String>>(mut self, path: S) -> Self {
+        self.interface_descriptions = Some(path.into());
+        self
+    }
+
+    /// Set the TC policy for interfaces going down
+    pub fn link_down_policy(mut self, policy: LinkDownTcPolicy) -> Self {
+        self.link_down_policy = Some(policy);
+        self
+    }
+
+    /// Set the Zenoh QoS per message type
+    pub fn qos(mut self, qos: QosProfiles) -> Self {
+        self.qos = Some(qos);
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> Result<AppConfig> {
         let config = AppConfig {
@@ -260,6 +374,10 @@ impl AppConfigBuilder {
                 .backend_name
                 .ok_or_else(|| anyhow::anyhow!("Backend name is required"))?,
             exclude_loopback: self.exclude_loopback.unwrap_or(false),
+            exclude_patterns: AppConfig::resolve_exclude_patterns(
+                self.exclude_loopback.unwrap_or(false),
+                self.exclude_patterns.unwrap_or_default(),
+            ),
             log_level: self.log_level.unwrap_or(LogLevel::Info),
             interface_monitor_interval_secs: self.interface_monitor_interval_secs.unwrap_or(5),
             bandwidth_monitor_interval_secs: self.bandwidth_monitor_interval_secs.unwrap_or(2),
@@ -267,6 +385,17 @@ impl AppConfigBuilder {
             no_default_scenarios: self.no_default_scenarios.unwrap_or(false),
             preset_dirs: self.preset_dirs.unwrap_or_default(),
             no_default_presets: self.no_default_presets.unwrap_or(false),
+            expose_peer_info: self.expose_peer_info.unwrap_or(false),
+            query_rate_limit: self
+                .query_rate_limit
+                .unwrap_or(crate::rate_limit::DEFAULT_QUERY_RATE_LIMIT),
+            max_impaired_interfaces: self
+                .max_impaired_interfaces
+                .unwrap_or(crate::impairment_limit::DEFAULT_MAX_IMPAIRED_INTERFACES),
+            export_jsonl: self.export_jsonl,
+            interface_descriptions: self.interface_descriptions,
+            link_down_policy: self.link_down_policy.unwrap_or_default(),
+            qos: self.qos.unwrap_or_default(),
         };
 
         config.validate()?;
@@ -312,6 +441,7 @@ mod tests {
         let cli_config = CliConfig {
             verbose: false,
             exclude_loopback: true,
+            exclude_patterns: Vec::new(),
             backend_name: "test-backend".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -321,6 +451,15 @@ mod tests {
             no_default_scenarios: true,
             preset_dirs: vec!["/custom/presets".to_string()],
             no_default_presets: true,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         let app_config = AppConfig::from_cli(&cli_config).unwrap();
@@ -331,6 +470,15 @@ mod tests {
         assert!(app_config.no_default_scenarios);
         assert_eq!(app_config.preset_dirs, vec!["/custom/presets"]);
         assert!(app_config.no_default_presets);
+        assert_eq!(app_config.qos, QosProfiles::default());
+
+        let mut cli_config = cli_config;
+        cli_config.qos = vec!["interface_events=reliable,block,50".to_string()];
+        let app_config = AppConfig::from_cli(&cli_config).unwrap();
+        assert_eq!(app_config.qos.interface_events.history_depth, Some(50));
+
+        cli_config.qos = vec!["bandwidth_updates=best_effort,drop,50".to_string()];
+        assert!(AppConfig::from_cli(&cli_config).is_err());
     }
 
     #[test]
@@ -338,6 +486,7 @@ mod tests {
         let cli_config = CliConfig {
             verbose: true,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "test-backend".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -347,6 +496,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         let app_config = AppConfig::from_cli(&cli_config).unwrap();
@@ -362,6 +520,7 @@ mod tests {
         let config = AppConfig {
             backend_name: "valid-name".to_string(),
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             log_level: LogLevel::Info,
             interface_monitor_interval_secs: 5,
             bandwidth_monitor_interval_secs: 2,
@@ -369,6 +528,13 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            link_down_policy: LinkDownTcPolicy::Keep,
+            qos: QosProfiles::default(),
         };
 
         assert!(config.validate().is_ok());
@@ -379,6 +545,7 @@ mod tests {
         let config = AppConfig {
             backend_name: "".to_string(),
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             log_level: LogLevel::Info,
             interface_monitor_interval_secs: 5,
             bandwidth_monitor_interval_secs: 2,
@@ -386,6 +553,13 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            link_down_policy: LinkDownTcPolicy::Keep,
+            qos: QosProfiles::default(),
         };
 
         assert!(config.validate().is_err());
@@ -396,6 +570,7 @@ mod tests {
         let config = AppConfig {
             backend_name: "test".to_string(),
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             log_level: LogLevel::Info,
             interface_monitor_interval_secs: 0,
             bandwidth_monitor_interval_secs: 2,
@@ -403,6 +578,13 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            link_down_policy: LinkDownTcPolicy::Keep,
+            qos: QosProfiles::default(),
         };
 
         assert!(config.validate().is_err());
@@ -410,6 +592,7 @@ mod tests {
         let config = AppConfig {
             backend_name: "test".to_string(),
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             log_level: LogLevel::Info,
             interface_monitor_interval_secs: 5,
             bandwidth_monitor_interval_secs: 0,
@@ -417,6 +600,13 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            link_down_policy: LinkDownTcPolicy::Keep,
+            qos: QosProfiles::default(),
         };
 
         assert!(config.validate().is_err());
@@ -449,11 +639,35 @@ mod tests {
 
         assert_eq!(config.backend_name, "test");
         assert!(!config.exclude_loopback);
+        assert!(config.exclude_patterns.is_empty());
         assert_eq!(config.log_level, LogLevel::Info);
         assert_eq!(config.interface_monitor_interval_secs, 5);
         assert_eq!(config.bandwidth_monitor_interval_secs, 2);
     }
 
+    #[test]
+    fn test_app_config_exclude_patterns() {
+        // Loopback exclusion is one more pattern
+        let config = AppConfigBuilder::new()
+            .backend_name("test")
+            .exclude_loopback(true)
+            .exclude_patterns(vec!["docker*".to_string(), "type:bridge".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.exclude_patterns,
+            vec![LOOPBACK_PATTERN, "docker*", "type:bridge"]
+        );
+
+        // Invalid patterns stop the backend from starting
+        let error = AppConfigBuilder::new()
+            .backend_name("test")
+            .exclude_patterns(vec!["type:wireless".to_string()])
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid exclude pattern"));
+    }
+
     #[test]
     fn test_app_config_builder_missing_required() {
         let result = AppConfigBuilder::new().build();
@@ -2,16 +2,22 @@
 //!
 //! This module handles command line argument parsing using clap and provides
 //! a structured representation of CLI configuration that can be used by
-//! other configuration components.
+//! other configuration components. Settings from a `--config` file fill in
+//! every argument not given on the command line.
 
 use anyhow::Result;
+use clap::parser::ValueSource;
 use clap::{Arg, ArgMatches, Command};
 
+use super::file::ConfigFile;
+
 /// CLI configuration structure containing all parsed command line arguments
 #[derive(Debug, Clone)]
 pub struct CliConfig {
     pub verbose: bool,
     pub exclude_loopback: bool,
+    /// `--exclude` patterns (name globs or `type:KIND`)
+    pub exclude_patterns: Vec<String>,
     pub backend_name: String,
     pub zenoh_mode: String,
     pub zenoh_connect: Option<String>,
@@ -21,6 +27,16 @@ pub struct CliConfig {
     pub no_default_scenarios: bool,
     pub preset_dirs: Vec<String>,
     pub no_default_presets: bool,
+    pub expose_peer_info: bool,
+    pub query_rate_limit: u32,
+    pub max_impaired_interfaces: u32,
+    pub export_jsonl: Option<String>,
+    pub interface_descriptions: Option<String>,
+    pub tc_on_link_down: String,
+    pub qos: Vec<String>,
+    pub dump_config: bool,
+    /// The `--config` file, for the settings without a CLI argument
+    pub config_file: Option<ConfigFile>,
 }
 
 impl CliConfig {
@@ -32,11 +48,133 @@ impl CliConfig {
 
     /// Create CliConfig from pre-parsed ArgMatches (useful for testing)
     pub fn from_matches(matches: &ArgMatches) -> Result<Self> {
+        let mut config = Self::from_command_line(matches)?;
+        if let Some(path) = matches.get_one::<String>("config") {
+            let file = ConfigFile::load(path)?;
+            config.merge_file(&file, |id| {
+                matches.value_source(id) != Some(ValueSource::CommandLine)
+            });
+            config.config_file = Some(file);
+        }
+        Ok(config)
+    }
+
+    /// Take each setting of `file` whose argument `from_file` says was not
+    /// given on the command line.
+    fn merge_file(&mut self, file: &ConfigFile, from_file: impl Fn(&str) -> bool) {
+        fn take<T: Clone>(target: &mut T, value: &Option<T>, use_file: bool) {
+            if use_file && let Some(value) = value {
+                *target = value.clone();
+            }
+        }
+
+        take(
+            &mut self.exclude_loopback,
+            &file.exclude_loopback,
+            from_file("exclude-loopback"),
+        );
+        take(
+            &mut self.exclude_patterns,
+            &file.exclude_patterns,
+            from_file("exclude"),
+        );
+        take(
+            &mut self.backend_name,
+            &file.backend_name,
+            from_file("name"),
+        );
+        take(
+            &mut self.zenoh_mode,
+            &file.zenoh.mode.map(|mode| mode.as_str().to_string()),
+            from_file("zenoh-mode"),
+        );
+        take(
+            &mut self.zenoh_connect,
+            &file.zenoh.connect.as_ref().map(|e| Some(e.join(","))),
+            from_file("zenoh-connect"),
+        );
+        take(
+            &mut self.zenoh_listen,
+            &file.zenoh.listen.as_ref().map(|e| Some(e.join(","))),
+            from_file("zenoh-listen"),
+        );
+        take(
+            &mut self.no_multicast,
+            &file.zenoh.no_multicast,
+            from_file("no-multicast"),
+        );
+        take(
+            &mut self.scenario_dirs,
+            &file.scenario_dirs,
+            from_file("scenario-dir"),
+        );
+        take(
+            &mut self.no_default_scenarios,
+            &file.no_default_scenarios,
+            from_file("no-default-scenarios"),
+        );
+        take(
+            &mut self.preset_dirs,
+            &file.preset_dirs,
+            from_file("preset-dir"),
+        );
+        take(
+            &mut self.no_default_presets,
+            &file.no_default_presets,
+            from_file("no-default-presets"),
+        );
+        take(
+            &mut self.expose_peer_info,
+            &file.expose_peer_info,
+            from_file("expose-peer-info"),
+        );
+        take(
+            &mut self.query_rate_limit,
+            &file.query_rate_limit,
+            from_file("query-rate-limit"),
+        );
+        take(
+            &mut self.max_impaired_interfaces,
+            &file.max_impaired_interfaces,
+            from_file("max-impaired-interfaces"),
+        );
+        take(
+            &mut self.export_jsonl,
+            &file.export_jsonl.clone().map(Some),
+            from_file("export-jsonl"),
+        );
+        take(
+            &mut self.interface_descriptions,
+            &file.interface_descriptions.clone().map(Some),
+            from_file("interface-descriptions"),
+        );
+        take(
+            &mut self.tc_on_link_down,
+            &file.tc_on_link_down.as_ref().map(|p| p.get_ref().clone()),
+            from_file("tc-on-link-down"),
+        );
+        take(
+            &mut self.qos,
+            &(!file.qos.is_empty()).then(|| file.qos_overrides()),
+            from_file("qos"),
+        );
+    }
+
+    /// Read the arguments given on the command line (or their defaults)
+    fn from_command_line(matches: &ArgMatches) -> Result<Self> {
         let verbose = matches.get_flag("verbose");
         let exclude_loopback = matches.get_flag("exclude-loopback");
         let no_multicast = matches.get_flag("no-multicast");
         let no_default_scenarios = matches.get_flag("no-default-scenarios");
         let no_default_presets = matches.get_flag("no-default-presets");
+        let expose_peer_info = matches.get_flag("expose-peer-info");
+        let dump_config = matches.get_flag("dump-config");
+        let query_rate_limit = *matches
+            .get_one::<u32>("query-rate-limit")
+            .ok_or_else(|| anyhow::anyhow!("Query rate limit is required"))?;
+        let max_impaired_interfaces = *matches
+            .get_one::<u32>("max-impaired-interfaces")
+            .ok_or_else(|| anyhow::anyhow!("Impaired interface limit is required"))?;
 
         let backend_name = matches
             .get_one::<String>("name")
@@ -50,20 +188,37 @@ impl CliConfig {
 
         let zenoh_connect = matches.get_one::<String>("zenoh-connect").cloned();
         let zenoh_listen = matches.get_one::<String>("zenoh-listen").cloned();
+        let export_jsonl = matches.get_one::<String>("export-jsonl").cloned();
+        let interface_descriptions = matches.get_one::<String>("interface-descriptions").cloned();
+        let tc_on_link_down = matches
+            .get_one::<String>("tc-on-link-down")
+            .ok_or_else(|| anyhow::anyhow!("Link-down TC policy is required"))?
+            .clone();
 
         let scenario_dirs: Vec<String> = matches
             .get_many::<String>("scenario-dir")
             .map(|vals| vals.cloned().collect())
             .unwrap_or_default();
 
+        let exclude_patterns: Vec<String> = matches
+            .get_many::<String>("exclude")
+            .map(|vals| vals.cloned().collect())
+            .unwrap_or_default();
+
         let preset_dirs: Vec<String> = matches
             .get_many::<String>("preset-dir")
             .map(|vals| vals.cloned().collect())
             .unwrap_or_default();
 
+        let qos: Vec<String> = matches
+            .get_many::<String>("qos")
+            .map(|vals| vals.cloned().collect())
+            .unwrap_or_default();
+
         Ok(Self {
             verbose,
             exclude_loopback,
+            exclude_patterns,
             backend_name,
             zenoh_mode,
             zenoh_connect,
@@ -73,6 +228,15 @@ impl CliConfig {
             no_default_scenarios,
             preset_dirs,
             no_default_presets,
+            expose_peer_info,
+            query_rate_limit,
+            max_impaired_interfaces,
+            export_jsonl,
+            interface_descriptions,
+            tc_on_link_down,
+            qos,
+            dump_config,
+            config_file: None,
         })
     }
 
@@ -101,6 +265,18 @@ impl CliConfig {
                     .long_help("Exclude the loopback interface from interface discovery and monitoring. \
                               This can reduce noise in environments where loopback interfaces are not relevant."),
             )
+            .arg(
+                Arg::new("exclude")
+                    .long("exclude")
+                    .value_name("PATTERN")
+                    .action(clap::ArgAction::Append)
+                    .help("Exclude interfaces matching PATTERN from monitoring")
+                    .long_help("Exclude interfaces from discovery and monitoring. PATTERN is a glob on the \
+                              interface name ('*' matches any run of characters, '?' one character), \
+                              e.g. 'docker*', or 'type:KIND' for every interface of a type (physical, \
+                              virtual, veth, bridge, tun, tap, loopback). Can be specified multiple \
+                              times. --exclude-loopback is the same as --exclude type:loopback."),
+            )
             .arg(
                 Arg::new("name")
                     .short('n')
@@ -191,6 +367,114 @@ impl CliConfig {
                               (/usr/share/tcgui/presets, ~/.config/tcgui/presets, ./presets). \
                               Only presets from explicitly specified --preset-dir will be loaded."),
             )
+            .arg(
+                Arg::new("expose-peer-info")
+                    .long("expose-peer-info")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Publish connected peer ids in the health status")
+                    .long_help("Include the Zenoh ids of connected peers in the backend health status. \
+                              By default only the number of connected peers is published, so \
+                              observers of a shared backend are not identified."),
+            )
+            .arg(
+                Arg::new("query-rate-limit")
+                    .long("query-rate-limit")
+                    .value_name("PER_SECOND")
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Maximum TC/interface/diagnostics queries per second per peer (0 = unlimited)")
+                    .long_help("Maximum sustained rate of TC, interface-control and diagnostics queries \
+                              accepted from a single peer, with bursts of up to two seconds worth. \
+                              Queries over the limit are rejected with a 'rate limited' error instead \
+                              of reconfiguring the kernel. Peers that do not identify themselves share \
+                              one limit. 0 disables rate limiting.")
+                    .required(false)
+                    .default_value("50"),
+            )
+            .arg(
+                Arg::new("max-impaired-interfaces")
+                    .long("max-impaired-interfaces")
+                    .value_name("COUNT")
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Maximum interfaces with an active impairment at once (0 = unlimited)")
+                    .long_help("Maximum number of interfaces that may carry an impairment at the same \
+                              time, across all namespaces. Once reached, applying TC to another \
+                              interface is rejected with the current count and the limit until one is \
+                              cleared; impaired interfaces can still be changed. Guards shared hosts \
+                              against impairing every interface by accident. 0 disables the limit.")
+                    .required(false)
+                    .default_value("64"),
+            )
+            .arg(
+                Arg::new("export-jsonl")
+                    .long("export-jsonl")
+                    .value_name("PATH")
+                    .help("Append bandwidth samples and TC changes to a JSON-lines file")
+                    .long_help("Append one JSON object per line to PATH for every bandwidth sample and \
+                              every TC configuration change, on a common millisecond timeline. \
+                              Lines are written as events happen, so the file can be tailed by a \
+                              log shipper (e.g. Promtail for Grafana Loki). See docs/export-format.md \
+                              for the fields.")
+                    .required(false),
+            )
+            .arg(
+                Arg::new("interface-descriptions")
+                    .long("interface-descriptions")
+                    .value_name("PATH")
+                    .help("JSON5 file mapping interface names to descriptions shown in frontends")
+                    .long_help("Read operator descriptions (e.g. \"upstream ISP\") from PATH, a JSON5 \
+                              object mapping interface names to text. A key \"eth1\" matches eth1 in \
+                              every namespace, \"ns/eth1\" only in namespace ns and takes precedence. \
+                              Descriptions are sent with the interface list, so every frontend shows \
+                              the same text. Unlisted interfaces have no description.")
+                    .required(false),
+            )
+            .arg(
+                Arg::new("tc-on-link-down")
+                    .long("tc-on-link-down")
+                    .value_name("POLICY")
+                    .help("What to do with an interface's TC config when it goes down: keep, clear or restore")
+                    .long_help("What to do with an interface's TC configuration when the interface goes \
+                              down, whether disabled explicitly or through loss of carrier. 'keep' leaves \
+                              it in place, so it applies again as soon as the link is back. 'clear' \
+                              removes it. 'restore' removes it and reapplies it when the link comes back \
+                              up, unless a new configuration was applied in the meantime.")
+                    .value_parser(["keep", "clear", "restore"])
+                    .required(false)
+                    .default_value("keep"),
+            )
+            .arg(
+                Arg::new("qos")
+                    .long("qos")
+                    .value_name("TYPE=RELIABILITY,CONGESTION,HISTORY")
+                    .action(clap::ArgAction::Append)
+                    .help("Override the Zenoh QoS of one message type")
+                    .long_help("Override the reliability, congestion control and history depth used to                               publish one message type, e.g. 'bandwidth_updates=reliable,block,none' or                               'interface_events=reliable,block,50'. Types: interface_list,                               bandwidth_updates, interface_events, backend_health. Reliability is                               'reliable' or 'best_effort', congestion 'block' or 'drop', history a                               sample count or 'none'. Can be specified multiple times."),
+            )
+            .arg(
+                Arg::new("config")
+                    .short('c')
+                    .long("config")
+                    .value_name("PATH")
+                    .help("Read settings from a TOML configuration file")
+                    .long_help("Read settings from a TOML configuration file. It can hold every \
+                              setting that has an argument (named in snake_case, e.g. \
+                              backend_name, tc_on_link_down; Zenoh settings under [zenoh]) plus \
+                              [monitor] intervals and [features] flags. Arguments given on the \
+                              command line take precedence over the file, and \
+                              TCGUI_FEATURE_PROFILE over its feature profile. Unknown keys and \
+                              invalid values are rejected with the line they are on.")
+                    .required(false),
+            )
+            .arg(
+                Arg::new("dump-config")
+                    .long("dump-config")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Print the resolved configuration as JSON and exit")
+                    .long_help("Print the fully resolved configuration (application, Zenoh and feature \
+                              flags, after merging CLI arguments, environment and feature profile) as a \
+                              single line of JSON, then exit. Credentials are redacted, so the output \
+                              can be attached to bug reports."),
+            )
     }
 
     /// Validate CLI configuration
@@ -243,6 +527,13 @@ mod tests {
         assert!(!config.no_multicast);
         assert!(!config.no_default_scenarios);
         assert!(!config.no_default_presets);
+        assert!(!config.expose_peer_info);
+        assert_eq!(config.query_rate_limit, 50);
+        assert_eq!(config.max_impaired_interfaces, 64);
+        assert!(config.export_jsonl.is_none());
+        assert_eq!(config.tc_on_link_down, "keep");
+        assert!(config.qos.is_empty());
+        assert!(!config.dump_config);
         assert_eq!(config.backend_name, "default");
         assert_eq!(config.zenoh_mode, "peer");
         assert!(config.zenoh_connect.is_none());
@@ -274,6 +565,20 @@ mod tests {
                 "--preset-dir",
                 "/custom/presets",
                 "--no-default-presets",
+                "--expose-peer-info",
+                "--query-rate-limit",
+                "0",
+                "--max-impaired-interfaces",
+                "0",
+                "--export-jsonl",
+                "/var/log/tcgui/events.jsonl",
+                "--tc-on-link-down",
+                "restore",
+                "--qos",
+                "bandwidth_updates=reliable,block,none",
+                "--qos",
+                "interface_events=reliable,block,50",
+                "--dump-config",
             ])
             .unwrap();
 
@@ -283,6 +588,22 @@ mod tests {
         assert!(config.exclude_loopback);
         assert!(config.no_default_scenarios);
         assert!(config.no_default_presets);
+        assert!(config.expose_peer_info);
+        assert_eq!(config.query_rate_limit, 0);
+        assert_eq!(config.max_impaired_interfaces, 0);
+        assert_eq!(
+            config.export_jsonl.as_deref(),
+            Some("/var/log/tcgui/events.jsonl")
+        );
+        assert_eq!(config.tc_on_link_down, "restore");
+        assert_eq!(
+            config.qos,
+            vec![
+                "bandwidth_updates=reliable,block,none",
+                "interface_events=reliable,block,50"
+            ]
+        );
+        assert!(config.dump_config);
         assert_eq!(config.backend_name, "test-backend");
         assert_eq!(config.zenoh_mode, "client");
         assert_eq!(
@@ -302,6 +623,7 @@ mod tests {
         let config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "valid-name_123".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -311,6 +633,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         assert!(config.validate().is_ok());
@@ -321,6 +652,7 @@ mod tests {
         let config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -330,6 +662,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         assert!(config.validate().is_err());
@@ -340,6 +681,7 @@ mod tests {
         let config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "invalid@name!".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -349,6 +691,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         assert!(config.validate().is_err());
@@ -359,6 +710,7 @@ mod tests {
         let config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "valid-name".to_string(),
             zenoh_mode: "invalid-mode".to_string(),
             zenoh_connect: None,
@@ -368,6 +720,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         assert!(config.validate().is_err());
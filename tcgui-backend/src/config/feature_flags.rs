@@ -69,6 +69,17 @@ pub enum FeatureProfile {
 }
 
 impl FeatureProfile {
+    /// Parse a profile name (`TCGUI_FEATURE_PROFILE` or the config file)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "development" | "dev" => Some(FeatureProfile::Development),
+            "staging" | "stage" => Some(FeatureProfile::Staging),
+            "production" | "prod" => Some(FeatureProfile::Production),
+            "testing" | "test" => Some(FeatureProfile::Testing),
+            _ => None,
+        }
+    }
+
     /// Get feature flags for this profile
     pub fn to_feature_flags(&self) -> FeatureFlags {
         match self {
@@ -154,21 +165,21 @@ impl FeatureToggleManager {
 
     /// Create manager from environment variable
     pub fn from_env() -> Result<Self> {
+        Self::from_env_or(FeatureProfile::Production)
+    }
+
+    /// Create manager from environment variable, using `fallback` when it
+    /// is unset (e.g. the profile from the config file)
+    pub fn from_env_or(fallback: FeatureProfile) -> Result<Self> {
         let profile = match std::env::var("TCGUI_FEATURE_PROFILE") {
-            Ok(profile_str) => match profile_str.to_lowercase().as_str() {
-                "development" | "dev" => FeatureProfile::Development,
-                "staging" | "stage" => FeatureProfile::Staging,
-                "production" | "prod" => FeatureProfile::Production,
-                "testing" | "test" => FeatureProfile::Testing,
-                _ => {
-                    warn!(
-                        "Unknown feature profile '{}', using Production",
-                        profile_str
-                    );
-                    FeatureProfile::Production
-                }
-            },
-            Err(_) => FeatureProfile::Production, // Default
+            Ok(profile_str) => FeatureProfile::from_name(&profile_str).unwrap_or_else(|| {
+                warn!(
+                    "Unknown feature profile '{}', using Production",
+                    profile_str
+                );
+                FeatureProfile::Production
+            }),
+            Err(_) => fallback,
         };
 
         info!("Initializing feature flags with profile: {:?}", profile);
@@ -320,6 +331,25 @@ pub enum Feature {
     Custom(String),
 }
 
+impl Feature {
+    /// Feature named like its [`FeatureFlags`] field; other names are
+    /// custom features
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "bandwidth_monitoring" => Feature::BandwidthMonitoring,
+            "interface_hotplug" => Feature::InterfaceHotplug,
+            "tc_command_caching" => Feature::TcCommandCaching,
+            "zenoh_advanced_features" => Feature::ZenohAdvancedFeatures,
+            "metrics_collection" => Feature::MetricsCollection,
+            "namespace_monitoring" => Feature::NamespaceMonitoring,
+            "tc_parameter_validation" => Feature::TcParameterValidation,
+            "experimental_features" => Feature::ExperimentalFeatures,
+            "ab_testing" => Feature::AbTesting,
+            other => Feature::Custom(other.to_string()),
+        }
+    }
+}
+
 /// Macro for easy feature checking
 #[macro_export]
 macro_rules! feature_enabled {
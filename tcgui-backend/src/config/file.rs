@@ -0,0 +1,239 @@
+//! TOML configuration file for the TC GUI backend (`--config PATH`).
+//!
+//! Every setting is optional; a setting missing from the file keeps its CLI
+//! default, and an argument given on the command line wins over the file.
+//! The file also holds the settings that have no CLI flag (monitoring
+//! intervals, feature flags).
+//!
+//! ```toml
+//! backend_name = "lab-1"
+//! exclude_loopback = true
+//! exclude_patterns = ["docker*", "type:bridge"]
+//! scenario_dirs = ["/etc/tcgui/scenarios"]
+//! tc_on_link_down = "restore"
+//!
+//! [zenoh]
+//! mode = "client"
+//! connect = ["tcp/10.0.0.1:7447"]
+//!
+//! [monitor]
+//! bandwidth_interval_secs = 1
+//!
+//! [qos]
+//! bandwidth_updates = "reliable,block,none"
+//!
+//! [features]
+//! profile = "staging"
+//! flags = { metrics_collection = false }
+//! ```
+//!
+//! Unknown keys are rejected. Errors name the file and the line, and for
+//! syntax errors show the offending line.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::Path;
+use toml::Spanned;
+
+use super::feature_flags::{Feature, FeatureProfile};
+use crate::link_policy::LinkDownTcPolicy;
+use tcgui_shared::qos::QosProfiles;
+
+/// Zenoh session mode, as written in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileZenohMode {
+    Peer,
+    Client,
+}
+
+impl FileZenohMode {
+    /// The `--zenoh-mode` value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileZenohMode::Peer => "peer",
+            FileZenohMode::Client => "client",
+        }
+    }
+}
+
+/// `[zenoh]`: session mode and endpoints
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ZenohSection {
+    pub mode: Option<FileZenohMode>,
+    pub connect: Option<Vec<String>>,
+    pub listen: Option<Vec<String>>,
+    pub no_multicast: Option<bool>,
+}
+
+/// `[monitor]`: polling intervals
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MonitorSection {
+    pub bandwidth_interval_secs: Option<u64>,
+}
+
+/// `[features]`: profile and individual flag overrides
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeaturesSection {
+    /// Profile name, as for `TCGUI_FEATURE_PROFILE`
+    pub profile: Option<Spanned<String>>,
+    /// Flags set on top of the profile; names that aren't built-in features
+    /// become custom flags
+    pub flags: BTreeMap<String, bool>,
+}
+
+/// Contents of a backend configuration file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub backend_name: Option<String>,
+    pub exclude_loopback: Option<bool>,
+    /// Interface exclusion patterns, as for `--exclude`
+    pub exclude_patterns: Option<Vec<String>>,
+    pub scenario_dirs: Option<Vec<String>>,
+    pub no_default_scenarios: Option<bool>,
+    pub preset_dirs: Option<Vec<String>>,
+    pub no_default_presets: Option<bool>,
+    pub expose_peer_info: Option<bool>,
+    pub query_rate_limit: Option<u32>,
+    pub max_impaired_interfaces: Option<u32>,
+    pub export_jsonl: Option<String>,
+    pub interface_descriptions: Option<String>,
+    pub tc_on_link_down: Option<Spanned<String>>,
+    /// Message type to `RELIABILITY,CONGESTION,HISTORY`, as for `--qos`
+    pub qos: BTreeMap<String, Spanned<String>>,
+    pub zenoh: ZenohSection,
+    pub monitor: MonitorSection,
+    pub features: FeaturesSection,
+}
+
+/// 1-based line of a byte offset in `source`
+fn line_of(source: &str, span: Range<usize>) -> usize {
+    source[..span.start.min(source.len())].matches('\n').count() + 1
+}
+
+impl ConfigFile {
+    /// Read and validate the configuration file at `path`.
+    pub fn load(path: &str) -> Result<Self> {
+        let source = std::fs::read_to_string(Path::new(path))
+            .map_err(|e| anyhow::anyhow!("Failed to read config file {}: {}", path, e))?;
+        Self::parse(&source, path)
+    }
+
+    /// Parse and validate configuration file contents; `origin` names the
+    /// file in errors.
+    pub fn parse(source: &str, origin: &str) -> Result<Self> {
+        let file: Self = toml::from_str(source)
+            .map_err(|e| anyhow::anyhow!("Invalid config file {}: {}", origin, e))?;
+
+        let at = |span: Range<usize>, message: String| {
+            anyhow::anyhow!("{}:{}: {}", origin, line_of(source, span), message)
+        };
+        if let Some(policy) = &file.tc_on_link_down {
+            policy
+                .get_ref()
+                .parse::<LinkDownTcPolicy>()
+                .map_err(|e| at(policy.span(), format!("tc_on_link_down: {}", e)))?;
+        }
+        for (kind, value) in &file.qos {
+            QosProfiles::with_overrides([format!("{}={}", kind, value.get_ref()).as_str()])
+                .map_err(|e| at(value.span(), format!("qos.{}: {}", kind, e)))?;
+        }
+        if let Some(profile) = &file.features.profile
+            && FeatureProfile::from_name(profile.get_ref()).is_none()
+        {
+            return Err(at(
+                profile.span(),
+                format!(
+                    "features.profile: unknown profile '{}'. Must be 'development', \
+                     'staging', 'production' or 'testing'",
+                    profile.get_ref()
+                ),
+            ));
+        }
+
+        Ok(file)
+    }
+
+    /// The `--qos` overrides the `[qos]` table stands for
+    pub fn qos_overrides(&self) -> Vec<String> {
+        self.qos
+            .iter()
+            .map(|(kind, value)| format!("{}={}", kind, value.get_ref()))
+            .collect()
+    }
+
+    /// The feature profile named in the file, if any (validated on parse)
+    pub fn feature_profile(&self) -> Option<FeatureProfile> {
+        self.features
+            .profile
+            .as_ref()
+            .and_then(|profile| FeatureProfile::from_name(profile.get_ref()))
+    }
+
+    /// The `[features] flags` overrides
+    pub fn feature_overrides(&self) -> impl Iterator<Item = (Feature, bool)> + '_ {
+        self.features
+            .flags
+            .iter()
+            .map(|(name, enabled)| (Feature::from_name(name), *enabled))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_errors_show_the_line() {
+        let error = ConfigFile::parse("backend_name = \"lab\"\nquery_rate_limit = \n", "a.toml")
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("Invalid config file a.toml"));
+        assert!(error.contains("line 2"), "{}", error);
+
+        let error = ConfigFile::parse("[zenoh]\nmode = \"router\"\n", "a.toml")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("line 2"), "{}", error);
+
+        let error = ConfigFile::parse("backend = \"lab\"\n", "a.toml")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("unknown field `backend`"), "{}", error);
+    }
+
+    #[test]
+    fn test_invalid_values_name_the_key_and_line() {
+        let error = ConfigFile::parse(
+            "backend_name = \"lab\"\n\n[qos]\nbandwidth_updates = \"reliable,sometimes,none\"\n",
+            "a.toml",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            error.starts_with("a.toml:4: qos.bandwidth_updates:"),
+            "{}",
+            error
+        );
+
+        let error = ConfigFile::parse("tc_on_link_down = \"forget\"\n", "a.toml")
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("a.toml:1: tc_on_link_down:"), "{}", error);
+
+        let error = ConfigFile::parse("[features]\nprofile = \"qa\"\n", "a.toml")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.starts_with("a.toml:2: features.profile:"),
+            "{}",
+            error
+        );
+    }
+}
@@ -2,22 +2,87 @@
 //!
 //! This module provides centralized configuration management with support for:
 //! - CLI argument parsing
+//! - TOML configuration files (`--config`), overridden by CLI arguments
 //! - Environment variable configuration
 //! - Configuration validation
 //! - Builder pattern for configuration construction
+//! - Redacted JSON dumps of the resolved configuration (`--dump-config`)
 
 pub mod app_config;
 pub mod cli;
 pub mod feature_flags;
+pub mod file;
 pub mod zenoh_config;
 
 pub use app_config::{AppConfig, AppConfigBuilder, LogLevel};
 pub use cli::CliConfig;
 pub use feature_flags::{Feature, FeatureFlags, FeatureProfile, FeatureToggleManager};
+pub use file::ConfigFile;
 pub use zenoh_config::ZenohConfigManager;
 
 use anyhow::Result;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
 use tcgui_shared::ZenohConfig;
+use tcgui_shared::qos::QosMessageType;
+
+/// Replacement for credentials in configuration dumps
+const REDACTED: &str = "<redacted>";
+
+/// Key fragments that mark a Zenoh property or endpoint option as a credential
+const SECRET_KEY_MARKERS: &[&str] = &[
+    "auth",
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "credential",
+    "private_key",
+];
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+/// Redact credentials embedded in a configured endpoint
+/// (`connect/<locator>` or `listen/<locator>`): user info before the host
+/// (`tcp/user:pass@host:7447`) and secret options in the `#` config part
+/// (`tls/host:7448#private_key_base64=...`).
+fn redact_endpoint(endpoint: &str) -> String {
+    let (direction, endpoint) = match endpoint.split_once('/') {
+        Some((direction, rest)) if direction == "connect" || direction == "listen" => {
+            (format!("{}/", direction), rest)
+        }
+        _ => (String::new(), endpoint),
+    };
+    let (locator, config) = match endpoint.split_once('#') {
+        Some((locator, config)) => (locator, Some(config)),
+        None => (endpoint, None),
+    };
+
+    let locator = match locator.split_once('/') {
+        Some((protocol, address)) => match address.rsplit_once('@') {
+            Some((_, host)) => format!("{}{}/{}@{}", direction, protocol, REDACTED, host),
+            None => format!("{}{}", direction, locator),
+        },
+        None => format!("{}{}", direction, locator),
+    };
+
+    match config {
+        Some(config) => {
+            let options: Vec<String> = config
+                .split(';')
+                .map(|option| match option.split_once('=') {
+                    Some((key, _)) if is_secret_key(key) => format!("{}={}", key, REDACTED),
+                    _ => option.to_string(),
+                })
+                .collect();
+            format!("{}#{}", locator, options.join(";"))
+        }
+        None => locator,
+    }
+}
 
 /// Main configuration manager that combines all configuration sources
 #[derive(Debug, Clone)]
@@ -30,10 +95,23 @@ pub struct ConfigManager {
 impl ConfigManager {
     /// Creates a new configuration manager from CLI arguments and environment
     pub fn from_cli_and_env() -> Result<Self> {
-        let cli_config = CliConfig::from_args()?;
-        let app_config = AppConfig::from_cli(&cli_config)?;
-        let zenoh_config = ZenohConfigManager::from_cli(&cli_config)?;
-        let feature_manager = FeatureToggleManager::from_env()?;
+        Self::from_cli(&CliConfig::from_args()?)
+    }
+
+    /// Creates a configuration manager from parsed CLI arguments and environment
+    pub fn from_cli(cli_config: &CliConfig) -> Result<Self> {
+        let app_config = AppConfig::from_cli(cli_config)?;
+        let zenoh_config = ZenohConfigManager::from_cli(cli_config)?;
+        let feature_manager = match &cli_config.config_file {
+            Some(file) => {
+                let manager = FeatureToggleManager::from_env_or(
+                    file.feature_profile().unwrap_or(FeatureProfile::Production),
+                )?;
+                manager.update_features(file.feature_overrides().collect())?;
+                manager
+            }
+            None => FeatureToggleManager::from_env()?,
+        };
 
         Ok(Self {
             app: app_config,
@@ -55,17 +133,71 @@ impl ConfigManager {
     pub fn init_logging(&self) -> Result<()> {
         self.app.init_logging()
     }
+
+    /// The resolved configuration as JSON, with credentials redacted.
+    pub fn to_redacted_json(&self) -> Value {
+        let app = &self.app;
+        let properties: BTreeMap<&str, &str> = self
+            .zenoh
+            .properties
+            .iter()
+            .map(|(key, value)| {
+                let value = if is_secret_key(key) { REDACTED } else { value };
+                (key.as_str(), value)
+            })
+            .collect();
+        let endpoints: Vec<String> = self
+            .zenoh
+            .endpoints
+            .iter()
+            .map(|endpoint| redact_endpoint(endpoint))
+            .collect();
+
+        json!({
+            "app": {
+                "backend_name": app.backend_name,
+                "exclude_loopback": app.exclude_loopback,
+                "exclude_patterns": app.exclude_patterns,
+                "log_level": app.log_level.to_filter_string(),
+                "interface_monitor_interval_secs": app.interface_monitor_interval_secs,
+                "bandwidth_monitor_interval_secs": app.bandwidth_monitor_interval_secs,
+                "scenario_dirs": app.scenario_dirs,
+                "no_default_scenarios": app.no_default_scenarios,
+                "preset_dirs": app.preset_dirs,
+                "no_default_presets": app.no_default_presets,
+                "expose_peer_info": app.expose_peer_info,
+                "query_rate_limit": app.query_rate_limit,
+                "max_impaired_interfaces": app.max_impaired_interfaces,
+                "export_jsonl": app.export_jsonl,
+                "interface_descriptions": app.interface_descriptions,
+                "link_down_policy": app.link_down_policy.to_string(),
+                "qos": QosMessageType::ALL
+                    .iter()
+                    .map(|kind| (kind.name(), app.qos.get(*kind).to_string()))
+                    .collect::<BTreeMap<_, _>>(),
+            },
+            "zenoh": {
+                "mode": self.zenoh.mode,
+                "endpoints": endpoints,
+                "properties": properties,
+            },
+            "features": {
+                "profile": self.features.get_profile(),
+                "flags": self.features.get_all_flags(),
+            },
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_config_manager_validation() {
-        let cli_config = CliConfig {
+    fn test_cli_config() -> CliConfig {
+        CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "test".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -75,7 +207,21 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
-        };
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
+        }
+    }
+
+    #[test]
+    fn test_config_manager_validation() {
+        let cli_config = test_cli_config();
 
         let app_config = AppConfig::from_cli(&cli_config).unwrap();
         let zenoh_config = ZenohConfigManager::from_cli(&cli_config).unwrap();
@@ -89,4 +235,153 @@ mod tests {
 
         assert!(config_manager.validate().is_ok());
     }
+
+    #[test]
+    fn test_config_file_resolves_with_cli_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backend.toml");
+        std::fs::write(
+            &path,
+            r#"
+backend_name = "lab-1"
+exclude_loopback = true
+scenario_dirs = ["/etc/tcgui/scenarios", "/srv/scenarios"]
+no_default_scenarios = true
+preset_dirs = ["/etc/tcgui/presets"]
+expose_peer_info = true
+query_rate_limit = 10
+max_impaired_interfaces = 8
+export_jsonl = "/var/log/tcgui/events.jsonl"
+tc_on_link_down = "restore"
+
+[zenoh]
+mode = "client"
+connect = ["tcp/10.0.0.1:7447", "tcp/10.0.0.2:7447"]
+no_multicast = true
+
+[monitor]
+bandwidth_interval_secs = 1
+
+[qos]
+bandwidth_updates = "reliable,block,none"
+
+[features]
+profile = "staging"
+flags = { metrics_collection = false, lab_mode = true }
+"#,
+        )
+        .unwrap();
+
+        let matches = CliConfig::build_cli()
+            .try_get_matches_from([
+                "tcgui-backend",
+                "--config",
+                path.to_str().unwrap(),
+                "--name",
+                "from-cli",
+                "--max-impaired-interfaces",
+                "2",
+            ])
+            .unwrap();
+        let config = ConfigManager::from_cli(&CliConfig::from_matches(&matches).unwrap()).unwrap();
+        config.validate().unwrap();
+
+        // The command line wins, the file fills in the rest
+        assert_eq!(config.app.backend_name, "from-cli");
+        assert_eq!(config.app.max_impaired_interfaces, 2);
+        assert!(config.app.exclude_loopback);
+        assert_eq!(
+            config.app.scenario_dirs,
+            vec!["/etc/tcgui/scenarios", "/srv/scenarios"]
+        );
+        assert!(config.app.no_default_scenarios);
+        assert_eq!(config.app.preset_dirs, vec!["/etc/tcgui/presets"]);
+        assert!(!config.app.no_default_presets);
+        assert!(config.app.expose_peer_info);
+        assert_eq!(config.app.query_rate_limit, 10);
+        assert_eq!(
+            config.app.export_jsonl.as_deref(),
+            Some("/var/log/tcgui/events.jsonl")
+        );
+        assert_eq!(
+            config.app.link_down_policy,
+            crate::link_policy::LinkDownTcPolicy::Restore
+        );
+        assert_eq!(config.app.bandwidth_monitor_interval_secs, 1);
+        assert_eq!(
+            config.app.qos.bandwidth_updates.to_string(),
+            "reliable,block,none"
+        );
+
+        assert!(matches!(config.zenoh.mode, tcgui_shared::ZenohMode::Client));
+        assert_eq!(
+            config.zenoh.endpoints,
+            vec!["connect/tcp/10.0.0.1:7447", "connect/tcp/10.0.0.2:7447"]
+        );
+        assert_eq!(
+            config
+                .zenoh
+                .properties
+                .get("scouting/multicast/enabled")
+                .map(String::as_str),
+            Some("false")
+        );
+
+        assert!(matches!(
+            config.features.get_profile(),
+            FeatureProfile::Staging
+        ));
+        assert!(!config.features.is_enabled(&Feature::MetricsCollection));
+        assert!(config.features.is_enabled(&Feature::TcCommandCaching));
+        assert!(
+            config
+                .features
+                .is_enabled(&Feature::Custom("lab_mode".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_dump_config_round_trips_and_redacts_secrets() {
+        let mut cli_config = test_cli_config();
+        cli_config.zenoh_mode = "client".to_string();
+        cli_config.zenoh_connect = Some(
+            "tcp/admin:hunter2@10.0.0.1:7447,tls/router:7448#root_ca_certificate=/etc/ca.pem;private_key_base64=c2VjcmV0"
+                .to_string(),
+        );
+        let mut config_manager = ConfigManager {
+            app: AppConfig::from_cli(&cli_config).unwrap(),
+            zenoh: ZenohConfigManager::from_cli(&cli_config).unwrap(),
+            features: FeatureToggleManager::with_profile(FeatureProfile::Staging),
+        };
+        config_manager.zenoh.properties.insert(
+            "transport/auth/usrpwd/password".to_string(),
+            "hunter2".to_string(),
+        );
+
+        let dump = config_manager.to_redacted_json().to_string();
+        assert!(!dump.contains("hunter2"));
+        assert!(!dump.contains("c2VjcmV0"));
+
+        let parsed: Value = serde_json::from_str(&dump).unwrap();
+        assert_eq!(parsed["app"]["backend_name"], "test");
+        assert_eq!(parsed["app"]["link_down_policy"], "keep");
+        assert_eq!(
+            parsed["app"]["qos"]["bandwidth_updates"],
+            "best_effort,drop,none"
+        );
+        assert_eq!(parsed["zenoh"]["mode"], "Client");
+        assert_eq!(
+            parsed["zenoh"]["endpoints"],
+            json!([
+                "connect/tcp/<redacted>@10.0.0.1:7447",
+                "connect/tls/router:7448#root_ca_certificate=/etc/ca.pem;private_key_base64=<redacted>"
+            ])
+        );
+        assert_eq!(
+            parsed["zenoh"]["properties"]["transport/auth/usrpwd/password"],
+            REDACTED
+        );
+        assert_eq!(parsed["features"]["profile"], "Staging");
+        assert_eq!(parsed["features"]["flags"]["tc_command_caching"], true);
+    }
 }
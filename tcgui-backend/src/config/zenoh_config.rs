@@ -179,6 +179,7 @@ mod tests {
         let cli_config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "test".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -188,6 +189,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         let zenoh_config = ZenohConfigManager::from_cli(&cli_config).unwrap();
@@ -202,6 +212,7 @@ mod tests {
         let cli_config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "test".to_string(),
             zenoh_mode: "client".to_string(),
             zenoh_connect: None,
@@ -211,6 +222,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         let zenoh_config = ZenohConfigManager::from_cli(&cli_config).unwrap();
@@ -222,6 +242,7 @@ mod tests {
         let cli_config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "test".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: Some("tcp/192.168.1.1:7447,udp/192.168.1.2:7447".to_string()),
@@ -231,6 +252,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         let zenoh_config = ZenohConfigManager::from_cli(&cli_config).unwrap();
@@ -242,6 +272,7 @@ mod tests {
         let cli_config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "test".to_string(),
             zenoh_mode: "invalid-mode".to_string(),
             zenoh_connect: None,
@@ -251,6 +282,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         let zenoh_config = ZenohConfigManager::from_cli(&cli_config).unwrap();
@@ -262,6 +302,7 @@ mod tests {
         let cli_config = CliConfig {
             verbose: false,
             exclude_loopback: false,
+            exclude_patterns: Vec::new(),
             backend_name: "test".to_string(),
             zenoh_mode: "peer".to_string(),
             zenoh_connect: None,
@@ -271,6 +312,15 @@ mod tests {
             no_default_scenarios: false,
             preset_dirs: vec![],
             no_default_presets: false,
+            expose_peer_info: false,
+            query_rate_limit: 50,
+            max_impaired_interfaces: 64,
+            export_jsonl: None,
+            interface_descriptions: None,
+            tc_on_link_down: "keep".to_string(),
+            qos: vec![],
+            dump_config: false,
+            config_file: None,
         };
 
         let zenoh_config = ZenohConfigManager::from_cli(&cli_config).unwrap();
@@ -0,0 +1,143 @@
+//! Recent TC configurations published per interface.
+//!
+//! Every configuration the backend publishes for an interface is appended to
+//! that interface's history, unless it repeats the latest entry (forced
+//! refreshes and periodic re-detection republish unchanged configurations).
+//! The history is bounded per interface and served on
+//! `config/{ns}/{iface}/history` (see [`tcgui_shared::config_history`]).
+
+use std::collections::{HashMap, VecDeque};
+
+use tcgui_shared::TcConfiguration;
+use tcgui_shared::config_history::{ConfigHistoryEntry, MAX_CONFIG_HISTORY};
+
+/// Bounded per-interface configuration histories, keyed by
+/// `namespace/interface`.
+#[derive(Debug)]
+pub struct ConfigHistory {
+    capacity: usize,
+    interfaces: HashMap<String, VecDeque<ConfigHistoryEntry>>,
+}
+
+impl Default for ConfigHistory {
+    fn default() -> Self {
+        Self::new(MAX_CONFIG_HISTORY)
+    }
+}
+
+impl ConfigHistory {
+    /// Keep at most `capacity` entries per interface.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            interfaces: HashMap::new(),
+        }
+    }
+
+    /// Record that `configuration` was published at `timestamp` (Unix
+    /// seconds). Returns whether it was added: a repeat of the latest entry
+    /// isn't, nor is a cleared configuration on an interface without history.
+    pub fn record(
+        &mut self,
+        namespace: &str,
+        interface: &str,
+        timestamp: u64,
+        configuration: Option<&TcConfiguration>,
+    ) -> bool {
+        let key = format!("{}/{}", namespace, interface);
+        let latest = self
+            .interfaces
+            .get(&key)
+            .and_then(|entries| entries.back())
+            .map(|entry| entry.configuration.as_ref());
+        let unchanged = match (latest, configuration) {
+            (None | Some(None), None) => true,
+            (Some(Some(latest)), Some(configuration)) => {
+                latest.to_command(interface) == configuration.to_command(interface)
+            }
+            _ => false,
+        };
+        if unchanged {
+            return false;
+        }
+
+        let entries = self.interfaces.entry(key).or_default();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(ConfigHistoryEntry {
+            timestamp,
+            configuration: configuration.cloned(),
+        });
+        true
+    }
+
+    /// The most recent entries of an interface, oldest first: all of them, or
+    /// the last `limit`. Empty for an interface without history.
+    pub fn recent(
+        &self,
+        namespace: &str,
+        interface: &str,
+        limit: Option<usize>,
+    ) -> Vec<ConfigHistoryEntry> {
+        let Some(entries) = self.interfaces.get(&format!("{}/{}", namespace, interface)) else {
+            return Vec::new();
+        };
+        let skip = limit.map_or(0, |limit| entries.len().saturating_sub(limit));
+        entries.iter().skip(skip).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delay(ms: f32) -> TcConfiguration {
+        TcConfiguration {
+            delay_ms: Some(ms),
+            ..TcConfiguration::default()
+        }
+    }
+
+    #[test]
+    fn test_records_changes_only() {
+        let mut history = ConfigHistory::default();
+
+        // Nothing to clear yet
+        assert!(!history.record("default", "eth0", 10, None));
+        assert!(history.recent("default", "eth0", None).is_empty());
+
+        assert!(history.record("default", "eth0", 20, Some(&delay(100.0))));
+        assert!(!history.record("default", "eth0", 25, Some(&delay(100.0))));
+        assert!(history.record("default", "eth0", 30, None));
+        assert!(!history.record("default", "eth0", 35, None));
+
+        let entries = history.recent("default", "eth0", None);
+        let timestamps: Vec<_> = entries.iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![20, 30]);
+        assert_eq!(
+            entries[0].configuration.as_ref().and_then(|c| c.delay_ms),
+            Some(100.0)
+        );
+        assert!(entries[1].configuration.is_none());
+    }
+
+    #[test]
+    fn test_history_is_bounded_per_interface() {
+        let mut history = ConfigHistory::new(3);
+        for i in 0..5 {
+            history.record("default", "eth0", i, Some(&delay(10.0 + i as f32)));
+        }
+        history.record("ns1", "eth0", 1, Some(&delay(50.0)));
+
+        let timestamps: Vec<_> = history
+            .recent("default", "eth0", None)
+            .iter()
+            .map(|e| e.timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![2, 3, 4]);
+        assert_eq!(history.recent("default", "eth0", Some(1))[0].timestamp, 4);
+        assert_eq!(history.recent("ns1", "eth0", None).len(), 1);
+        assert!(history.recent("default", "eth1", None).is_empty());
+    }
+}
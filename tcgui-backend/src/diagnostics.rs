@@ -4,22 +4,428 @@
 //! - Link status checking (up/down, carrier, MTU)
 //! - Connectivity testing via ping
 //! - Latency measurement
+//! - Ping diagnostics with the full round-trip summary
+//! - Throughput measurement with a built-in TCP probe (no iperf3 needed)
+//! - Reading back the impairment the kernel actually has (reconcile)
 //! - Current TC configuration retrieval
+//! - netem vs TBF rate limiter comparison (iperf3 throughput, ping latency)
 
+use crate::netns::{NamespacePath, run_in_namespace};
 use crate::network::NetworkManager;
-use crate::tc_commands::TcCommandManager;
+use crate::tc_commands::{CapturedTcState, QdiscKind, TcCommandManager, gemodel_of, slot_of};
+use futures_util::stream::{self, StreamExt};
 use nlink::netlink::namespace;
 use nlink::netlink::{Connection, Route};
+use std::future::Future;
+use std::io::{ErrorKind, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream, ToSocketAddrs};
 use std::process::Stdio;
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tcgui_shared::rate_comparison::{
+    RateComparisonRequest, RateComparisonResponse, Shaper, ShaperMeasurement,
+};
 use tcgui_shared::{
-    ConnectivityResult, DiagnosticsRequest, DiagnosticsResponse, DiagnosticsResults, LatencyResult,
-    LinkStatus, TcCorruptConfig, TcDelayConfig, TcDiagnosticStats, TcDuplicateConfig, TcLossConfig,
-    TcNetemConfig, TcRateLimitConfig, TcReorderConfig,
+    ConnectivityResult, DiagnosticsRequest, DiagnosticsResponse, DiagnosticsResults,
+    DiagnosticsTest, InterfaceDiagnostics, LatencyResult, LinkStatus, LossModel,
+    NamespaceDiagnosticsRequest, NamespaceDiagnosticsResponse, PingResult, RateLimitMode,
+    RttSummary, TcCorruptConfig, TcDelayConfig, TcDiagnosticStats, TcDuplicateConfig, TcLossConfig,
+    TcNetemConfig, TcRateLimitConfig, TcRateLimitMode, TcReconciliation, TcReorderConfig,
+    TcSlotConfig, ThroughputResult,
 };
 use tokio::process::Command;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
+
+/// Maximum number of interfaces diagnosed at once by a namespace-wide run.
+/// Each run pings for several seconds, so this bounds the number of
+/// concurrent ping processes and namespace switches on the backend.
+pub const MAX_CONCURRENT_DIAGNOSTICS: usize = 4;
+
+/// Longest a namespace-wide run may take. It holds up the backend's query
+/// loop, so interfaces still running then are reported as timed out.
+pub const NAMESPACE_DIAGNOSTICS_BUDGET: Duration = Duration::from_secs(10);
+
+/// Length of each shaper's throughput test in a rate comparison.
+const COMPARISON_SECS: u32 = 5;
+
+/// Note used when a rate comparison has to do without iperf3.
+const IPERF_MISSING: &str = "iperf3 is not installed on the backend host";
+
+/// Seconds between the echo requests of a ping diagnostic, the shortest
+/// interval ping allows without root.
+const PING_INTERVAL: &str = "0.2";
+
+/// Time a ping diagnostic gets beyond its own deadline, e.g. to resolve the
+/// host, before it is killed.
+const PING_GRACE_SECS: u64 = 5;
+
+/// First number of a field of ping's packet count line, e.g. "3 received".
+fn leading_number(field: &str) -> Option<u32> {
+    field.split_whitespace().next()?.parse().ok()
+}
+
+/// Read the summary at the end of ping's output: the packet counts and loss,
+/// and the round-trip times when any reply came back. Understands iputils
+/// and busybox ping. `None` when there is no summary, e.g. when the host
+/// could not be resolved.
+fn parse_ping_summary(output: &str, target: &str) -> Option<PingResult> {
+    // "5 packets transmitted, 3 received, +2 errors, 40% packet loss, time 4005ms"
+    let counts = output
+        .lines()
+        .find(|line| line.contains("packets transmitted"))?;
+    let mut fields = counts.split(',');
+    let transmitted = leading_number(fields.next()?)?;
+    let received = leading_number(fields.next()?)?;
+    let packet_loss_percent = counts
+        .split_whitespace()
+        .find_map(|word| word.strip_suffix('%')?.parse().ok())
+        .unwrap_or(0.0);
+
+    // "rtt min/avg/max/mdev = 11.800/12.067/12.300/0.205 ms" (iputils)
+    // "round-trip min/avg/max = 0.062/0.077/0.093 ms" (busybox)
+    let rtt = output.lines().find_map(|line| {
+        let (names, values) = line.split_once('=')?;
+        if !names.contains("min/avg/max") {
+            return None;
+        }
+        let values: Vec<f32> = values
+            .split_whitespace()
+            .next()?
+            .split('/')
+            .map(|value| value.parse().ok())
+            .collect::<Option<_>>()?;
+        Some(RttSummary {
+            min_ms: *values.first()?,
+            avg_ms: *values.get(1)?,
+            max_ms: *values.get(2)?,
+            mdev_ms: values.get(3).copied(),
+        })
+    });
+
+    Some(PingResult {
+        target: target.to_string(),
+        transmitted,
+        received,
+        packet_loss_percent,
+        rtt,
+        error: None,
+    })
+}
+
+/// Ping `target` `count` times through `interface` from `namespace`, waiting
+/// up to `wait_secs` for each reply. A run that can't be started, prints no
+/// summary or overruns its deadline comes back with [`PingResult::error`].
+async fn run_ping(
+    namespace: &str,
+    interface: &str,
+    target: &str,
+    count: u32,
+    wait_secs: u32,
+) -> PingResult {
+    let failed = |error: String| PingResult {
+        target: target.to_string(),
+        error: Some(error),
+        ..Default::default()
+    };
+
+    // Let ping stop itself, so a slow run still prints its summary
+    let deadline = count.div_ceil(5) + wait_secs;
+    let mut command = if namespace == "default" {
+        Command::new("ping")
+    } else {
+        let mut command = Command::new("ip");
+        command.args(["netns", "exec", namespace, "ping"]);
+        command
+    };
+    command
+        .args([
+            "-c",
+            &count.to_string(),
+            "-i",
+            PING_INTERVAL,
+            "-W",
+            &wait_secs.to_string(),
+            "-w",
+            &deadline.to_string(),
+            "-I",
+            interface,
+            target,
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let limit = Duration::from_secs(deadline as u64 + PING_GRACE_SECS);
+    let output = match tokio::time::timeout(limit, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return failed(format!("Failed to run ping: {}", e)),
+        Err(_) => {
+            return failed(format!(
+                "ping {} timed out after {}s",
+                target,
+                limit.as_secs()
+            ));
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_ping_summary(&stdout, target).unwrap_or_else(|| {
+        // e.g. "ping: nosuchhost: Name or service not known"
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.trim();
+        failed(if reason.is_empty() {
+            format!("ping printed no summary ({})", output.status)
+        } else {
+            reason.to_string()
+        })
+    })
+}
+
+/// Bytes handed to the socket per write by the throughput probe.
+const PROBE_CHUNK_BYTES: usize = 64 * 1024;
+
+/// How long the throughput probe waits to connect to its sink.
+const PROBE_CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Longest a single write of the probe may block, so a stalled connection
+/// can't keep it past its duration.
+const PROBE_WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Share of a probe spent before bytes are counted. Writes complete at
+/// memory speed until the socket's send buffer is full, whatever the link's
+/// rate, so only the steady state after that says what the link carries.
+const PROBE_WARMUP_FRACTION: f64 = 0.25;
+
+/// Rate in kbit/s of `bytes` sent over `elapsed`; `None` for an empty
+/// interval.
+fn throughput_kbps(bytes: u64, elapsed: Duration) -> Option<f64> {
+    let secs = elapsed.as_secs_f64();
+    (secs > 0.0).then(|| bytes as f64 * 8.0 / secs / 1000.0)
+}
+
+/// Write to `stream` for `duration`. Returns the bytes written after the
+/// warm-up and how long that took.
+fn send_for(stream: &mut TcpStream, duration: Duration) -> Result<(u64, Duration), String> {
+    let chunk = [0u8; PROBE_CHUNK_BYTES];
+    stream
+        .set_write_timeout(Some(PROBE_WRITE_TIMEOUT))
+        .map_err(|e| format!("Failed to set up the probe connection: {}", e))?;
+
+    let warmup = duration.mul_f64(PROBE_WARMUP_FRACTION);
+    let start = Instant::now();
+    let mut sent: u64 = 0;
+    let mut counted_from: Option<(Instant, u64)> = None;
+    while start.elapsed() < duration {
+        if counted_from.is_none() && start.elapsed() >= warmup {
+            counted_from = Some((Instant::now(), sent));
+        }
+        match stream.write(&chunk) {
+            Ok(0) => return Err("the sink closed the connection".to_string()),
+            Ok(n) => sent += n as u64,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+                ) => {}
+            Err(e) => return Err(format!("sending failed: {}", e)),
+        }
+    }
+
+    let (from, before) = counted_from.unwrap_or((start, 0));
+    Ok((sent - before, from.elapsed()))
+}
+
+/// Send to the TCP sink `target` ("host:port"), or to a sink of our own on
+/// loopback, for `duration`. Blocking: runs on the namespace's thread.
+fn tcp_probe(target: Option<&str>, duration: Duration) -> Result<(u64, Duration), String> {
+    let Some(target) = target else {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .map_err(|e| format!("Failed to open a loopback sink: {}", e))?;
+        let address = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to open a loopback sink: {}", e))?;
+        // Connected before the sink starts: the kernel queues the connection
+        // until it is accepted, and a failure leaves no thread waiting
+        let mut stream = TcpStream::connect(address)
+            .map_err(|e| format!("Failed to connect over loopback: {}", e))?;
+        let sink = std::thread::spawn(move || -> std::io::Result<u64> {
+            let (mut connection, _) = listener.accept()?;
+            std::io::copy(&mut connection, &mut std::io::sink())
+        });
+        let sent = send_for(&mut stream, duration);
+        drop(stream);
+        let _ = sink.join();
+        return sent;
+    };
+
+    let address = target
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", target, e))?
+        .next()
+        .ok_or_else(|| format!("{} has no address", target))?;
+    let mut stream = TcpStream::connect_timeout(&address, PROBE_CONNECT_TIMEOUT)
+        .map_err(|e| format!("Failed to connect to {}: {}", target, e))?;
+    send_for(&mut stream, duration)
+}
+
+/// Run the throughput probe from `namespace`. A failed probe comes back
+/// with [`ThroughputResult::error`].
+async fn run_throughput_probe(
+    namespace: &str,
+    target: Option<&str>,
+    duration_ms: u32,
+) -> ThroughputResult {
+    let namespace_path = if namespace == "default" {
+        NamespacePath::Default
+    } else {
+        NamespacePath::Named(namespace.to_string())
+    };
+    let probe_target = target.map(str::to_string);
+    let duration = Duration::from_millis(duration_ms as u64);
+    let outcome = run_in_namespace(namespace_path, move || {
+        tcp_probe(probe_target.as_deref(), duration)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|sent| sent);
+
+    let mut result = ThroughputResult {
+        target: target.map(str::to_string),
+        ..Default::default()
+    };
+    match outcome {
+        Ok((bytes, elapsed)) => {
+            result.bytes = bytes;
+            result.measured_ms = elapsed.as_millis() as u64;
+            result.throughput_kbps = throughput_kbps(bytes, elapsed);
+        }
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
+
+/// The impairment a captured kernel state amounts to, in the form the UI
+/// applies, next to the root qdisc it was read from.
+fn reconciliation_of(state: &CapturedTcState) -> TcReconciliation {
+    TcReconciliation {
+        qdisc: state.qdisc_info.clone(),
+        configuration: state
+            .netem_config
+            .as_ref()
+            .map(|config| crate::tc_config::configuration_of(&state.interface, config)),
+    }
+}
+
+/// Throughput measured by one iperf3 run.
+#[derive(Debug, Clone, PartialEq)]
+struct IperfResult {
+    throughput_kbps: f64,
+    retransmits: Option<u64>,
+}
+
+/// Read the receiver-side throughput out of `iperf3 -J` output. iperf3
+/// reports a failed run in the same document, under `error`.
+fn parse_iperf_output(output: &str) -> Result<IperfResult, String> {
+    let report: serde_json::Value =
+        serde_json::from_str(output).map_err(|e| format!("unreadable iperf3 output: {}", e))?;
+    if let Some(error) = report.get("error").and_then(|e| e.as_str()) {
+        return Err(error.to_string());
+    }
+    let end = &report["end"];
+    let bits_per_second = end["sum_received"]["bits_per_second"]
+        .as_f64()
+        .ok_or_else(|| "iperf3 reported no throughput".to_string())?;
+    Ok(IperfResult {
+        throughput_kbps: bits_per_second / 1000.0,
+        retransmits: end["sum_sent"]["retransmits"].as_u64(),
+    })
+}
+
+/// Run an iperf3 client against `target` for `seconds` from `namespace`.
+/// The error is a sentence for the comparison's notes.
+async fn run_iperf(namespace: &str, target: &str, seconds: u32) -> Result<IperfResult, String> {
+    let duration = seconds.to_string();
+    let args = ["-c", target, "-t", duration.as_str(), "-J"];
+    let mut command = if namespace == "default" {
+        Command::new("iperf3")
+    } else {
+        let mut command = Command::new("ip");
+        command.args(["netns", "exec", namespace, "iperf3"]);
+        command
+    };
+    command
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let output = tokio::time::timeout(Duration::from_secs(seconds as u64 + 10), command.output())
+        .await
+        .map_err(|_| format!("iperf3 against {} timed out", target))?
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => IPERF_MISSING.to_string(),
+            _ => format!("Failed to run iperf3: {}", e),
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // `ip netns exec` reports a missing program on stderr
+        if stderr.contains("iperf3") && stderr.contains("No such file or directory") {
+            return Err(IPERF_MISSING.to_string());
+        }
+        let reason = parse_iperf_output(&stdout)
+            .err()
+            .unwrap_or_else(|| stderr.trim().to_string());
+        return Err(format!("iperf3 against {} failed: {}", target, reason));
+    }
+    parse_iperf_output(&stdout).map_err(|e| format!("iperf3 against {} failed: {}", target, e))
+}
+
+/// Run `diagnose` for every interface with at most `limit` runs in flight,
+/// all within `budget`.
+///
+/// A failed run becomes a failed [`DiagnosticsResponse`] for that interface
+/// and does not affect the others, as does a run still going when the budget
+/// is spent. Results are sorted by interface name.
+async fn diagnose_interfaces<F, Fut>(
+    interfaces: Vec<String>,
+    limit: usize,
+    budget: Duration,
+    diagnose: F,
+) -> Vec<InterfaceDiagnostics>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<DiagnosticsResponse, String>>,
+{
+    let deadline = tokio::time::Instant::now() + budget;
+    let mut results: Vec<InterfaceDiagnostics> = stream::iter(interfaces)
+        .map(|interface| {
+            let run = diagnose(interface.clone());
+            async move {
+                let result = tokio::time::timeout_at(deadline, run)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(format!("did not finish within {}s", budget.as_secs_f32()))
+                    });
+                let response = result.unwrap_or_else(|e| DiagnosticsResponse {
+                    success: false,
+                    message: format!("Diagnostics failed: {}", e),
+                    results: DiagnosticsResults::default(),
+                    error_code: Some(-1),
+                });
+                InterfaceDiagnostics {
+                    interface,
+                    response,
+                }
+            }
+        })
+        .buffer_unordered(limit.max(1))
+        .collect()
+        .await;
+
+    results.sort_by(|a, b| a.interface.cmp(&b.interface));
+    results
+}
 
 /// Service for running network diagnostics on interfaces.
 pub struct DiagnosticsService<'a> {
@@ -80,7 +486,97 @@ impl<'a> DiagnosticsService<'a> {
             .get_tc_diagnostic_stats(&request.namespace, &request.interface)
             .await;
 
-        // Step 3: Detect target for connectivity tests
+        // Step 3: Run the requested test
+        match &request.test {
+            DiagnosticsTest::Standard => self.run_connectivity_tests(request, &mut results).await,
+            DiagnosticsTest::Ping { target, count } => {
+                let ping = if results.link_status.is_up && results.link_status.has_carrier {
+                    let wait_secs = (request.timeout_ms / 1000).max(1);
+                    run_ping(
+                        &request.namespace,
+                        &request.interface,
+                        target,
+                        *count,
+                        wait_secs,
+                    )
+                    .await
+                } else {
+                    PingResult {
+                        target: target.clone(),
+                        error: Some("not run: link down".to_string()),
+                        ..Default::default()
+                    }
+                };
+                results.connectivity = Some(ConnectivityResult {
+                    target: target.clone(),
+                    reachable: ping.reachable(),
+                    method: "ping".to_string(),
+                });
+                results.latency = ping.rtt.map(|rtt| LatencyResult {
+                    target: target.clone(),
+                    min_ms: rtt.min_ms,
+                    avg_ms: rtt.avg_ms,
+                    max_ms: rtt.max_ms,
+                    packet_loss_percent: ping.packet_loss_percent,
+                    samples: ping.transmitted,
+                });
+                results.ping = Some(ping);
+            }
+            DiagnosticsTest::Throughput {
+                target,
+                duration_ms,
+            } => {
+                // Loopback doesn't depend on the interface's link
+                let link_ready = results.link_status.is_up && results.link_status.has_carrier;
+                let mut throughput = if target.is_none() || link_ready {
+                    run_throughput_probe(&request.namespace, target.as_deref(), *duration_ms).await
+                } else {
+                    ThroughputResult {
+                        target: target.clone(),
+                        error: Some("not run: link down".to_string()),
+                        ..Default::default()
+                    }
+                };
+                // The interface's qdisc never shapes a loopback probe
+                if target.is_some() {
+                    throughput.configured_rate_kbps = results
+                        .configured_tc
+                        .as_ref()
+                        .filter(|tc| tc.rate_limit.enabled)
+                        .map(|tc| tc.rate_limit.rate_kbps);
+                }
+                results.throughput = Some(throughput);
+            }
+            DiagnosticsTest::ReconcileTc => {
+                // Read the kernel directly: the tracked state is what drifted
+                let state = self
+                    .tc_manager
+                    .capture_tc_state(&request.namespace, &request.interface)
+                    .await
+                    .map_err(|e| format!("Failed to read the qdisc: {}", e))?;
+                results.reconciled = Some(reconciliation_of(&state));
+            }
+        }
+
+        // Build response message
+        let message = self.build_summary_message(&results);
+
+        Ok(DiagnosticsResponse {
+            success: true,
+            message,
+            results,
+            error_code: None,
+        })
+    }
+
+    /// Ping the request's target (or a detected one) a few times for the
+    /// standard diagnostics.
+    async fn run_connectivity_tests(
+        &self,
+        request: &DiagnosticsRequest,
+        results: &mut DiagnosticsResults,
+    ) {
+        // Detect target for connectivity tests
         let target = match &request.target {
             Some(t) => t.clone(),
             None => self
@@ -89,7 +585,7 @@ impl<'a> DiagnosticsService<'a> {
                 .unwrap_or_else(|| "8.8.8.8".to_string()),
         };
 
-        // Step 4: Run connectivity and latency tests (only if link is up)
+        // Run connectivity and latency tests (only if link is up)
         if results.link_status.is_up && results.link_status.has_carrier {
             let timeout_secs = (request.timeout_ms / 1000).max(1);
 
@@ -124,18 +620,198 @@ impl<'a> DiagnosticsService<'a> {
                 method: "skipped (link down)".to_string(),
             });
         }
+    }
 
-        // Build response message
-        let message = self.build_summary_message(&results);
+    /// Shape the interface to the requested rate with netem and then with
+    /// TBF, measuring each, and put the original configuration back.
+    ///
+    /// Throughput is only measured while iperf3 works; after the first
+    /// failure the rest of the run is latency-only and a note says why. Only
+    /// failing to read the original configuration is an error: without it
+    /// the interface could not be restored.
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn run_rate_comparison(
+        &self,
+        request: &RateComparisonRequest,
+    ) -> Result<RateComparisonResponse, String> {
+        info!(
+            "Comparing netem and tbf at {} kbit/s on {}/{}",
+            request.rate_kbps, request.namespace, request.interface
+        );
 
-        Ok(DiagnosticsResponse {
-            success: true,
-            message,
-            results,
-            error_code: None,
+        let target = match &request.target {
+            Some(t) => t.clone(),
+            None => self
+                .detect_target(&request.namespace, &request.interface)
+                .await
+                .unwrap_or_else(|| "8.8.8.8".to_string()),
+        };
+
+        let original = self
+            .tc_manager
+            .capture_tc_state(&request.namespace, &request.interface)
+            .await
+            .map_err(|e| format!("Failed to read the current configuration: {}", e))?;
+
+        let mut notes = Vec::new();
+        let mut with_iperf = true;
+        let netem = self
+            .measure_shaper(request, &target, Shaper::Netem, &mut with_iperf, &mut notes)
+            .await;
+        let tbf = self
+            .measure_shaper(request, &target, Shaper::Tbf, &mut with_iperf, &mut notes)
+            .await;
+
+        // Read the interface back rather than trusting the restore's message
+        let restored = match self.tc_manager.restore_tc_state(&original).await {
+            Ok(_) => self
+                .tc_manager
+                .capture_tc_state(&request.namespace, &request.interface)
+                .await
+                .is_ok_and(|now| {
+                    now.had_tc_config() == original.had_tc_config()
+                        && now.netem_config == original.netem_config
+                }),
+            Err(e) => {
+                warn!(
+                    "Restoring {}/{} failed: {}",
+                    request.namespace, request.interface, e
+                );
+                false
+            }
+        };
+        if !restored {
+            notes.push(
+                "The original configuration could not be confirmed afterwards; check the \
+                 interface and reapply it if needed"
+                    .to_string(),
+            );
+        }
+
+        Ok(RateComparisonResponse {
+            namespace: request.namespace.clone(),
+            interface: request.interface.clone(),
+            rate_kbps: request.rate_kbps,
+            target,
+            netem,
+            tbf,
+            notes,
+            restored,
         })
     }
 
+    /// Apply the requested rate with `shaper` and measure latency, under
+    /// iperf3 load while `with_iperf` holds. An iperf3 failure clears it and
+    /// adds a note.
+    async fn measure_shaper(
+        &self,
+        request: &RateComparisonRequest,
+        target: &str,
+        shaper: Shaper,
+        with_iperf: &mut bool,
+        notes: &mut Vec<String>,
+    ) -> ShaperMeasurement {
+        let kind = match shaper {
+            Shaper::Netem => QdiscKind::Netem,
+            Shaper::Tbf => QdiscKind::Tbf,
+        };
+        if let Err(e) = self
+            .tc_manager
+            .apply_rate_limit_with(
+                &request.namespace,
+                &request.interface,
+                request.rate_kbps,
+                kind,
+            )
+            .await
+        {
+            return ShaperMeasurement::failed(shaper, format!("Failed to apply {}: {}", shaper, e));
+        }
+
+        let ping = self.run_ping_test(
+            &request.namespace,
+            &request.interface,
+            target,
+            COMPARISON_SECS,
+            2,
+        );
+        let (ping, iperf) = if *with_iperf {
+            let (ping, iperf) =
+                tokio::join!(ping, run_iperf(&request.namespace, target, COMPARISON_SECS));
+            (ping, Some(iperf))
+        } else {
+            (ping.await, None)
+        };
+
+        let mut measurement = ShaperMeasurement {
+            shaper,
+            latency: None,
+            throughput_kbps: None,
+            retransmits: None,
+            error: None,
+        };
+        match ping {
+            Ok((connectivity, latency)) => {
+                if !connectivity.reachable {
+                    measurement.error = Some(format!("{} did not answer ping", target));
+                }
+                measurement.latency = latency;
+            }
+            Err(e) => measurement.error = Some(e),
+        }
+        match iperf {
+            Some(Ok(result)) => {
+                measurement.throughput_kbps = Some(result.throughput_kbps);
+                measurement.retransmits = result.retransmits;
+            }
+            Some(Err(e)) => {
+                debug!("Throughput test with {} failed: {}", shaper, e);
+                *with_iperf = false;
+                notes.push(format!("{}; comparing latency only", e));
+            }
+            None => {}
+        }
+        measurement
+    }
+
+    /// Run diagnostics on each of `interfaces` in the request's namespace,
+    /// at most [`MAX_CONCURRENT_DIAGNOSTICS`] at a time and within
+    /// [`NAMESPACE_DIAGNOSTICS_BUDGET`].
+    #[instrument(skip(self, interfaces), fields(namespace = %request.namespace))]
+    pub async fn run_namespace_diagnostics(
+        &self,
+        request: &NamespaceDiagnosticsRequest,
+        interfaces: Vec<String>,
+    ) -> NamespaceDiagnosticsResponse {
+        info!(
+            "Running diagnostics on {} interfaces in namespace {}",
+            interfaces.len(),
+            request.namespace
+        );
+
+        let results = diagnose_interfaces(
+            interfaces,
+            MAX_CONCURRENT_DIAGNOSTICS,
+            NAMESPACE_DIAGNOSTICS_BUDGET,
+            |interface| {
+                let interface_request = DiagnosticsRequest {
+                    namespace: request.namespace.clone(),
+                    interface,
+                    target: request.target.clone(),
+                    timeout_ms: request.timeout_ms,
+                    test: DiagnosticsTest::Standard,
+                };
+                async move { self.run_diagnostics(&interface_request).await }
+            },
+        )
+        .await;
+
+        NamespaceDiagnosticsResponse {
+            namespace: request.namespace.clone(),
+            interfaces: results,
+        }
+    }
+
     /// Check link status for an interface.
     async fn check_link_status(
         &self,
@@ -186,18 +862,26 @@ impl<'a> DiagnosticsService<'a> {
 
         match netem_opts {
             Some(opts) => {
+                let gemodel = gemodel_of(&opts);
                 // Convert nlink NetemOptions to TcNetemConfig
                 let config = TcNetemConfig {
                     loss: TcLossConfig {
-                        enabled: opts.loss().unwrap_or(0.0) > 0.0,
+                        enabled: opts.loss().unwrap_or(0.0) > 0.0 || gemodel.is_some(),
                         percentage: opts.loss().unwrap_or(0.0) as f32,
                         correlation: opts.loss_correlation().unwrap_or(0.0) as f32,
+                        model: gemodel.map_or(LossModel::Random, LossModel::GilbertElliott),
+                        ecn: opts.ecn(),
                     },
                     delay: TcDelayConfig {
                         enabled: opts.delay().map(|d| d.as_millis() > 0).unwrap_or(false),
                         base_ms: opts.delay().map(|d| d.as_millis() as f32).unwrap_or(0.0),
                         jitter_ms: opts.jitter().map(|d| d.as_millis() as f32).unwrap_or(0.0),
                         correlation: opts.delay_correlation().unwrap_or(0.0) as f32,
+                        distribution: self
+                            .tc_manager
+                            .applied_state(namespace, interface)
+                            .distribution
+                            .unwrap_or_default(),
                     },
                     duplicate: TcDuplicateConfig {
                         enabled: opts.duplicate().unwrap_or(0.0) > 0.0,
@@ -218,11 +902,21 @@ impl<'a> DiagnosticsService<'a> {
                     rate_limit: TcRateLimitConfig {
                         enabled: opts.rate_bps().map(|r| r > 0).unwrap_or(false),
                         rate_kbps: opts.rate_bps().map(|r| (r / 1000) as u32).unwrap_or(0),
+                        mode: TcRateLimitMode::Absolute,
+                        shaper: RateLimitMode::Netem,
                     },
+                    slot: slot_of(&opts).unwrap_or_else(TcSlotConfig::wifi_default),
+                    seed: self.tc_manager.applied_state(namespace, interface).seed,
                 };
                 Ok(Some(config))
             }
-            None => Ok(None),
+            // No netem: a TBF root qdisc carries a rate-limit-only config
+            None => self
+                .tc_manager
+                .get_tbf_rate_kbps(namespace, interface)
+                .await
+                .map(|rate| rate.map(crate::tc_commands::rate_limit_only_config))
+                .map_err(|e| format!("Failed to get TC config: {}", e)),
         }
     }
 
@@ -443,6 +1137,37 @@ impl<'a> DiagnosticsService<'a> {
             }
         }
 
+        // Ping diagnostic
+        if let Some(ref ping) = results.ping {
+            match &ping.error {
+                Some(error) => parts.push(format!("ping failed: {}", error)),
+                None => parts.push(format!(
+                    "{}/{} ping replies",
+                    ping.received, ping.transmitted
+                )),
+            }
+        }
+
+        // Throughput probe
+        if let Some(ref throughput) = results.throughput {
+            match (&throughput.error, throughput.throughput_kbps) {
+                (Some(error), _) => parts.push(format!("throughput probe failed: {}", error)),
+                (None, Some(kbps)) => parts.push(format!("throughput {:.0} kbit/s", kbps)),
+                (None, None) => {}
+            }
+            if let Some(rate_kbps) = throughput.configured_rate_kbps {
+                parts.push(format!("rate limit {} kbit/s", rate_kbps));
+            }
+        }
+
+        // Kernel readback
+        if let Some(ref reconciled) = results.reconciled {
+            parts.push(match reconciled.qdisc.as_str() {
+                "" => "kernel has no root qdisc".to_string(),
+                qdisc => format!("kernel has {}", qdisc),
+            });
+        }
+
         // TC status
         if results.configured_tc.is_some() {
             parts.push("TC active".to_string());
@@ -538,6 +1263,160 @@ rtt min/avg/max/mdev = 11.800/12.067/12.300/0.205 ms"#;
         assert!((lat.packet_loss_percent - 0.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_parse_ping_summary() {
+        let output = r#"PING 10.0.0.1 (10.0.0.1) from 10.0.0.2 veth0: 56(84) bytes of data.
+64 bytes from 10.0.0.1: icmp_seq=1 ttl=64 time=20.4 ms
+64 bytes from 10.0.0.1: icmp_seq=3 ttl=64 time=21.9 ms
+64 bytes from 10.0.0.1: icmp_seq=4 ttl=64 time=20.1 ms
+
+--- 10.0.0.1 ping statistics ---
+5 packets transmitted, 3 received, +2 errors, 40% packet loss, time 812ms
+rtt min/avg/max/mdev = 20.100/20.800/21.900/0.787 ms, pipe 2"#;
+        assert_eq!(
+            parse_ping_summary(output, "10.0.0.1"),
+            Some(PingResult {
+                target: "10.0.0.1".to_string(),
+                transmitted: 5,
+                received: 3,
+                packet_loss_percent: 40.0,
+                rtt: Some(RttSummary {
+                    min_ms: 20.1,
+                    avg_ms: 20.8,
+                    max_ms: 21.9,
+                    mdev_ms: Some(0.787),
+                }),
+                error: None,
+            })
+        );
+
+        // busybox reports no mdev
+        let busybox = r#"--- 10.0.0.1 ping statistics ---
+3 packets transmitted, 3 packets received, 0% packet loss
+round-trip min/avg/max = 0.062/0.077/0.093 ms"#;
+        let result = parse_ping_summary(busybox, "10.0.0.1").unwrap();
+        assert_eq!((result.transmitted, result.received), (3, 3));
+        assert_eq!(result.rtt.unwrap().mdev_ms, None);
+    }
+
+    #[test]
+    fn test_parse_ping_summary_without_replies() {
+        let lost = r#"PING 10.0.0.9 (10.0.0.9) 56(84) bytes of data.
+
+--- 10.0.0.9 ping statistics ---
+3 packets transmitted, 0 received, 100% packet loss, time 2045ms
+"#;
+        let result = parse_ping_summary(lost, "10.0.0.9").unwrap();
+        assert_eq!((result.transmitted, result.received), (3, 0));
+        assert_eq!(result.packet_loss_percent, 100.0);
+        assert_eq!(result.rtt, None);
+        assert!(!result.reachable());
+
+        // An unresolvable host prints its error on stderr and no summary
+        assert_eq!(parse_ping_summary("", "nosuchhost.invalid"), None);
+    }
+
+    #[test]
+    fn test_throughput_kbps() {
+        // 1.25 MB in one second is 10 Mbit/s
+        assert_eq!(
+            throughput_kbps(1_250_000, Duration::from_secs(1)),
+            Some(10_000.0)
+        );
+        assert_eq!(
+            throughput_kbps(1_250_000, Duration::from_millis(500)),
+            Some(20_000.0)
+        );
+        assert_eq!(throughput_kbps(0, Duration::from_secs(2)), Some(0.0));
+        assert_eq!(throughput_kbps(1000, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn test_loopback_probe_counts_after_warmup() {
+        let (bytes, elapsed) = tcp_probe(None, Duration::from_millis(400)).unwrap();
+        assert!(bytes > 0);
+        // Only the part after the warm-up is measured
+        assert!(elapsed < Duration::from_millis(400), "{elapsed:?}");
+        assert!(elapsed >= Duration::from_millis(250), "{elapsed:?}");
+    }
+
+    #[test]
+    fn test_reconciliation_of_multi_feature_qdisc() {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = 5.0;
+        config.loss.correlation = 25.0;
+        config.delay.enabled = true;
+        config.delay.base_ms = 100.0;
+        config.delay.jitter_ms = 10.0;
+        config.corrupt.enabled = true;
+        config.corrupt.percentage = 1.0;
+        config.rate_limit.enabled = true;
+        config.rate_limit.rate_kbps = 1000;
+        let state = CapturedTcState {
+            namespace: "lab".to_string(),
+            interface: "veth0".to_string(),
+            qdisc_info: "qdisc netem root".to_string(),
+            had_netem: true,
+            had_tbf: false,
+            had_htb: false,
+            netem_config: Some(config),
+        };
+
+        let reconciled = reconciliation_of(&state);
+        assert_eq!(reconciled.qdisc, "qdisc netem root");
+        let configuration = reconciled.configuration.unwrap();
+        assert_eq!(configuration.loss, 5.0);
+        assert_eq!(configuration.correlation, Some(25.0));
+        assert_eq!(configuration.delay_ms, Some(100.0));
+        assert_eq!(configuration.delay_jitter_ms, Some(10.0));
+        assert_eq!(configuration.corrupt_percent, Some(1.0));
+        assert_eq!(configuration.rate_limit_kbps, Some(1000));
+        assert_eq!(configuration.duplicate_percent, None);
+        assert!(
+            configuration.command.contains("dev veth0"),
+            "{}",
+            configuration.command
+        );
+
+        // A qdisc that impairs nothing reads back as no configuration
+        let fq_codel = CapturedTcState {
+            qdisc_info: "qdisc fq_codel root".to_string(),
+            had_netem: false,
+            netem_config: None,
+            ..state
+        };
+        let reconciled = reconciliation_of(&fq_codel);
+        assert_eq!(reconciled.qdisc, "qdisc fq_codel root");
+        assert!(reconciled.configuration.is_none());
+    }
+
+    #[test]
+    fn test_parse_iperf_output() {
+        let output = r#"{
+            "start": {"connected": [{"remote_host": "10.0.0.1"}]},
+            "end": {
+                "sum_sent": {"bits_per_second": 9812345.6, "retransmits": 42},
+                "sum_received": {"bits_per_second": 9645000.0}
+            }
+        }"#;
+        assert_eq!(
+            parse_iperf_output(output),
+            Ok(IperfResult {
+                throughput_kbps: 9645.0,
+                retransmits: Some(42),
+            })
+        );
+
+        let refused = r#"{"start": {}, "end": {},
+            "error": "unable to connect to server: Connection refused"}"#;
+        assert_eq!(
+            parse_iperf_output(refused),
+            Err("unable to connect to server: Connection refused".to_string())
+        );
+        assert!(parse_iperf_output("iperf3: error").is_err());
+    }
+
     #[test]
     fn test_parse_packet_loss() {
         let output = "3 packets transmitted, 2 received, 33.33% packet loss, time 2003ms";
@@ -545,6 +1424,82 @@ rtt min/avg/max/mdev = 11.800/12.067/12.300/0.205 ms"#;
         assert!((loss - 33.33).abs() < 0.01);
     }
 
+    #[tokio::test]
+    async fn test_diagnose_interfaces_is_bounded_and_isolates_failures() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let interfaces: Vec<String> = (0..10).rev().map(|i| format!("veth{i}")).collect();
+
+        let results = diagnose_interfaces(interfaces, 3, Duration::from_secs(5), |interface| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                if interface == "veth3" {
+                    return Err("link lookup failed".to_string());
+                }
+                Ok(DiagnosticsResponse {
+                    success: true,
+                    message: format!("{interface} ok"),
+                    results: DiagnosticsResults::default(),
+                    error_code: None,
+                })
+            }
+        })
+        .await;
+
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 3);
+        assert_eq!(results.len(), 10);
+        // Sorted by name regardless of completion order
+        assert_eq!(results[0].interface, "veth0");
+        assert_eq!(results[9].interface, "veth9");
+
+        // The failing interface is reported, the others still ran
+        let failed: Vec<_> = results
+            .iter()
+            .filter(|r| !r.response.success)
+            .map(|r| r.interface.as_str())
+            .collect();
+        assert_eq!(failed, vec!["veth3"]);
+        assert!(results[3].response.message.contains("link lookup failed"));
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_interfaces_reports_runs_past_the_budget() {
+        let interfaces = vec!["veth0".to_string(), "veth1".to_string()];
+        let results = diagnose_interfaces(
+            interfaces,
+            2,
+            Duration::from_millis(50),
+            |interface| async move {
+                if interface == "veth1" {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+                Ok(DiagnosticsResponse {
+                    success: true,
+                    message: format!("{interface} ok"),
+                    results: DiagnosticsResults::default(),
+                    error_code: None,
+                })
+            },
+        )
+        .await;
+
+        assert!(results[0].response.success);
+        assert!(!results[1].response.success);
+        assert!(
+            results[1].response.message.contains("did not finish"),
+            "{}",
+            results[1].response.message
+        );
+    }
+
     #[test]
     fn test_parse_packet_loss_integer() {
         let output = "5 packets transmitted, 4 received, 20% packet loss, time 4000ms";
@@ -0,0 +1,308 @@
+//! JSON-lines event export.
+//!
+//! With `--export-jsonl <PATH>` the backend appends one JSON object per line
+//! for every bandwidth sample and every TC configuration change it publishes.
+//! Both event kinds share a timeline (`ts_ms`, Unix milliseconds), so a log
+//! shipper tailing the file (Promtail, Grafana Alloy, Vector, ...) can feed
+//! Loki or any other store that ingests structured lines. Lines are written
+//! and flushed as events happen; nothing is held in memory between them.
+//!
+//! The field set is documented in `docs/export-format.md`. Fields are only
+//! ever added, never renamed or removed, and `schema` is bumped if that
+//! promise has to be broken.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tcgui_shared::{BandwidthUpdate, TcConfiguration};
+use tracing::warn;
+
+/// Version of the line format, written into every event.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// One exported line.
+#[derive(Debug, Serialize)]
+pub struct ExportEvent<'a> {
+    pub schema: u32,
+    /// Unix time in milliseconds
+    pub ts_ms: u64,
+    pub backend: &'a str,
+    pub namespace: &'a str,
+    pub interface: &'a str,
+    #[serde(flatten)]
+    pub kind: ExportEventKind<'a>,
+}
+
+/// Event-specific fields, tagged by `event`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExportEventKind<'a> {
+    /// Periodic counters and rates for an interface
+    Bandwidth {
+        rx_bytes_per_sec: f64,
+        tx_bytes_per_sec: f64,
+        rx_packets_per_sec: f64,
+        tx_packets_per_sec: f64,
+        rx_bytes: u64,
+        tx_bytes: u64,
+        rx_packets: u64,
+        tx_packets: u64,
+        rx_errors: u64,
+        tx_errors: u64,
+        rx_dropped: u64,
+        tx_dropped: u64,
+    },
+    /// The TC configuration of an interface was published. Impairments that
+    /// are not active are `null`; a removal has `has_tc: false` and every
+    /// impairment `null`.
+    TcConfig {
+        has_tc: bool,
+        loss_percent: Option<f32>,
+        loss_correlation: Option<f32>,
+        delay_ms: Option<f32>,
+        jitter_ms: Option<f32>,
+        delay_correlation: Option<f32>,
+        duplicate_percent: Option<f32>,
+        duplicate_correlation: Option<f32>,
+        reorder_percent: Option<f32>,
+        reorder_correlation: Option<f32>,
+        reorder_gap: Option<u32>,
+        corrupt_percent: Option<f32>,
+        corrupt_correlation: Option<f32>,
+        rate_kbps: Option<u32>,
+        command: Option<&'a str>,
+    },
+}
+
+impl<'a> ExportEventKind<'a> {
+    /// Fields for a bandwidth update.
+    pub fn bandwidth(update: &BandwidthUpdate) -> Self {
+        let stats = &update.stats;
+        ExportEventKind::Bandwidth {
+            rx_bytes_per_sec: stats.rx_bytes_per_sec,
+            tx_bytes_per_sec: stats.tx_bytes_per_sec,
+            rx_packets_per_sec: stats.rx_packets_per_sec,
+            tx_packets_per_sec: stats.tx_packets_per_sec,
+            rx_bytes: stats.rx_bytes,
+            tx_bytes: stats.tx_bytes,
+            rx_packets: stats.rx_packets,
+            tx_packets: stats.tx_packets,
+            rx_errors: stats.rx_errors,
+            tx_errors: stats.tx_errors,
+            rx_dropped: stats.rx_dropped,
+            tx_dropped: stats.tx_dropped,
+        }
+    }
+
+    /// Fields for a published configuration (`None` = TC removed).
+    pub fn tc_config(configuration: Option<&'a TcConfiguration>) -> Self {
+        let Some(c) = configuration else {
+            return ExportEventKind::TcConfig {
+                has_tc: false,
+                loss_percent: None,
+                loss_correlation: None,
+                delay_ms: None,
+                jitter_ms: None,
+                delay_correlation: None,
+                duplicate_percent: None,
+                duplicate_correlation: None,
+                reorder_percent: None,
+                reorder_correlation: None,
+                reorder_gap: None,
+                corrupt_percent: None,
+                corrupt_correlation: None,
+                rate_kbps: None,
+                command: None,
+            };
+        };
+        ExportEventKind::TcConfig {
+            has_tc: true,
+            loss_percent: (c.loss > 0.0).then_some(c.loss),
+            loss_correlation: c.correlation,
+            delay_ms: c.delay_ms,
+            jitter_ms: c.delay_jitter_ms,
+            delay_correlation: c.delay_correlation,
+            duplicate_percent: c.duplicate_percent,
+            duplicate_correlation: c.duplicate_correlation,
+            reorder_percent: c.reorder_percent,
+            reorder_correlation: c.reorder_correlation,
+            reorder_gap: c.reorder_gap,
+            corrupt_percent: c.corrupt_percent,
+            corrupt_correlation: c.corrupt_correlation,
+            rate_kbps: c.rate_limit_kbps,
+            command: Some(c.command.as_str()),
+        }
+    }
+}
+
+/// Appends export events to a writer, one line each.
+pub struct JsonLinesExporter<W: Write = File> {
+    backend: String,
+    writer: W,
+}
+
+/// Exporter shared between the bandwidth monitor and the TC publisher.
+pub type SharedExporter = Arc<Mutex<JsonLinesExporter>>;
+
+impl JsonLinesExporter {
+    /// Open `path` for appending, creating it if needed.
+    pub fn open(path: impl AsRef<Path>, backend: impl Into<String>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file, backend))
+    }
+}
+
+impl<W: Write> JsonLinesExporter<W> {
+    pub fn new(writer: W, backend: impl Into<String>) -> Self {
+        Self {
+            backend: backend.into(),
+            writer,
+        }
+    }
+
+    /// Write one event stamped with the current time.
+    pub fn write(
+        &mut self,
+        namespace: &str,
+        interface: &str,
+        kind: ExportEventKind<'_>,
+    ) -> io::Result<()> {
+        let ts_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        self.write_at(ts_ms, namespace, interface, kind)
+    }
+
+    /// Write one event with an explicit timestamp.
+    pub fn write_at(
+        &mut self,
+        ts_ms: u64,
+        namespace: &str,
+        interface: &str,
+        kind: ExportEventKind<'_>,
+    ) -> io::Result<()> {
+        let event = ExportEvent {
+            schema: EXPORT_SCHEMA_VERSION,
+            ts_ms,
+            backend: &self.backend,
+            namespace,
+            interface,
+            kind,
+        };
+        serde_json::to_writer(&mut self.writer, &event)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+
+    #[cfg(test)]
+    fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Write an event through a shared exporter. A failed write is logged and
+/// otherwise ignored: the export must never hold up publishing.
+pub fn write_shared(
+    exporter: &SharedExporter,
+    namespace: &str,
+    interface: &str,
+    kind: ExportEventKind<'_>,
+) {
+    let mut exporter = exporter.lock().unwrap_or_else(|e| e.into_inner());
+    if let Err(e) = exporter.write(namespace, interface, kind) {
+        warn!("Failed to write export event: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tcgui_shared::NetworkBandwidthStats;
+
+    fn lines(exporter: JsonLinesExporter<Vec<u8>>) -> Vec<serde_json::Value> {
+        String::from_utf8(exporter.into_inner())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_events_share_one_timeline() {
+        let mut exporter = JsonLinesExporter::new(Vec::new(), "lab-1");
+        let update = BandwidthUpdate {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            backend_name: "lab-1".to_string(),
+            stats: NetworkBandwidthStats {
+                rx_bytes: 1000,
+                tx_bytes: 2000,
+                rx_packets: 10,
+                tx_packets: 20,
+                rx_errors: 0,
+                tx_errors: 0,
+                rx_dropped: 1,
+                tx_dropped: 0,
+                timestamp: 0,
+                rx_bytes_per_sec: 125.0,
+                tx_bytes_per_sec: 250.0,
+                rx_packets_per_sec: 0.0,
+                tx_packets_per_sec: 0.0,
+                rx_errors_per_sec: 0.0,
+                tx_errors_per_sec: 0.0,
+                rx_dropped_per_sec: 0.0,
+                tx_dropped_per_sec: 0.0,
+            },
+        };
+        let config = TcConfiguration {
+            loss: 5.0,
+            delay_ms: Some(100.0),
+            command: "tc qdisc replace dev eth0 root netem loss 5% delay 100ms".to_string(),
+            ..Default::default()
+        };
+
+        exporter
+            .write_at(
+                1_000,
+                "default",
+                "eth0",
+                ExportEventKind::bandwidth(&update),
+            )
+            .unwrap();
+        exporter
+            .write_at(
+                1_500,
+                "default",
+                "eth0",
+                ExportEventKind::tc_config(Some(&config)),
+            )
+            .unwrap();
+        exporter
+            .write_at(2_000, "default", "eth0", ExportEventKind::tc_config(None))
+            .unwrap();
+
+        let events = lines(exporter);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["event"], "bandwidth");
+        assert_eq!(events[0]["schema"], EXPORT_SCHEMA_VERSION);
+        assert_eq!(events[0]["backend"], "lab-1");
+        assert_eq!(events[0]["rx_bytes_per_sec"], 125.0);
+        assert_eq!(events[0]["rx_dropped"], 1);
+
+        assert_eq!(events[1]["event"], "tc_config");
+        assert_eq!(events[1]["ts_ms"], 1_500);
+        assert_eq!(events[1]["has_tc"], true);
+        assert_eq!(events[1]["loss_percent"], 5.0);
+        assert_eq!(events[1]["delay_ms"], 100.0);
+        assert!(events[1]["rate_kbps"].is_null());
+
+        assert_eq!(events[2]["has_tc"], false);
+        assert!(events[2]["loss_percent"].is_null());
+        assert!(events[2]["command"].is_null());
+    }
+}
@@ -0,0 +1,161 @@
+//! Cap on how many interfaces may be impaired at the same time.
+//!
+//! On a shared host a script or a careless multi-select can impair every
+//! interface at once, production uplinks included. The backend refuses a new
+//! impairment once the configured number of interfaces already carry one;
+//! changing or clearing an impaired interface is always allowed, so the
+//! operator can get back under the limit.
+
+use tcgui_shared::NetworkInterface;
+
+/// Default cap: far above any test bench, well below "every interface"
+pub const DEFAULT_MAX_IMPAIRED_INTERFACES: u32 = 64;
+
+/// Configured cap on simultaneously impaired interfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImpairmentLimit {
+    /// Maximum impaired interfaces, `None` for unlimited
+    max: Option<usize>,
+}
+
+impl ImpairmentLimit {
+    /// Create a limit of `max` interfaces; 0 means unlimited.
+    pub fn new(max: u32) -> Self {
+        Self {
+            max: (max > 0).then_some(max as usize),
+        }
+    }
+
+    /// Check whether `namespace`/`interface` may be impaired, given the
+    /// interfaces the backend knows about (`has_tc_qdisc` marks the impaired
+    /// ones). The error reports the current count and the cap.
+    pub fn check<'a>(
+        &self,
+        interfaces: impl IntoIterator<Item = &'a NetworkInterface>,
+        namespace: &str,
+        interface: &str,
+    ) -> Result<(), String> {
+        let Some(max) = self.max else {
+            return Ok(());
+        };
+
+        let mut impaired = 0;
+        for iface in interfaces.into_iter().filter(|i| i.has_tc_qdisc) {
+            if iface.namespace == namespace && iface.name == interface {
+                // Re-configuring an impaired interface doesn't add one
+                return Ok(());
+            }
+            impaired += 1;
+        }
+
+        if impaired < max {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} interfaces are already impaired, the limit is {}. Clear one before \
+                 impairing {}.",
+                impaired, max, interface
+            ))
+        }
+    }
+}
+
+/// Count `namespace`/`interface` as impaired right away. `has_tc_qdisc` is
+/// otherwise only updated by the kernel's TC events, which come in after the
+/// query has been handled: a batch marks each target it applied, so the next
+/// targets are checked against them.
+pub fn mark_impaired<'a>(
+    interfaces: impl IntoIterator<Item = &'a mut NetworkInterface>,
+    namespace: &str,
+    interface: &str,
+) {
+    if let Some(iface) = interfaces
+        .into_iter()
+        .find(|i| i.namespace == namespace && i.name == interface)
+    {
+        iface.has_tc_qdisc = true;
+    }
+}
+
+impl Default for ImpairmentLimit {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_IMPAIRED_INTERFACES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interface(name: &str, has_tc_qdisc: bool) -> NetworkInterface {
+        NetworkInterface {
+            has_tc_qdisc,
+            ..crate::test_support::interface(name, 0, "default")
+        }
+    }
+
+    #[test]
+    fn test_apply_over_the_limit_is_rejected() {
+        let limit = ImpairmentLimit::new(2);
+        let mut interfaces = vec![
+            interface("eth0", false),
+            interface("eth1", false),
+            interface("eth2", false),
+        ];
+
+        assert!(limit.check(&interfaces, "default", "eth0").is_ok());
+        interfaces[0].has_tc_qdisc = true;
+        assert!(limit.check(&interfaces, "default", "eth1").is_ok());
+        interfaces[1].has_tc_qdisc = true;
+
+        let error = limit.check(&interfaces, "default", "eth2").unwrap_err();
+        assert!(error.contains("2 interfaces are already impaired, the limit is 2"));
+        // The impaired ones can still be changed
+        assert!(limit.check(&interfaces, "default", "eth0").is_ok());
+        assert!(limit.check(&interfaces, "default", "eth1").is_ok());
+
+        // Clearing one makes room again
+        interfaces[0].has_tc_qdisc = false;
+        assert!(limit.check(&interfaces, "default", "eth2").is_ok());
+    }
+
+    #[test]
+    fn test_targets_marked_impaired_count_against_the_limit() {
+        let limit = ImpairmentLimit::new(2);
+        let mut interfaces = vec![
+            interface("eth0", false),
+            interface("eth1", false),
+            interface("eth2", false),
+        ];
+
+        // A batch over all three: each applied target is marked before the
+        // next one is checked
+        let mut applied = Vec::new();
+        for name in ["eth0", "eth1", "eth2"] {
+            if limit.check(&interfaces, "default", name).is_ok() {
+                mark_impaired(&mut interfaces, "default", name);
+                applied.push(name);
+            }
+        }
+        assert_eq!(applied, ["eth0", "eth1"]);
+        assert!(interfaces[0].has_tc_qdisc && interfaces[1].has_tc_qdisc);
+        assert!(!interfaces[2].has_tc_qdisc);
+    }
+
+    #[test]
+    fn test_zero_is_unlimited() {
+        let interfaces: Vec<_> = (0..200)
+            .map(|i| interface(&format!("veth{}", i), true))
+            .collect();
+        assert!(
+            ImpairmentLimit::new(0)
+                .check(&interfaces, "default", "eth0")
+                .is_ok()
+        );
+        assert!(
+            ImpairmentLimit::default()
+                .check(&interfaces, "default", "eth0")
+                .is_err()
+        );
+    }
+}
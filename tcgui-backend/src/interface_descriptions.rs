@@ -0,0 +1,151 @@
+//! Operator-provided interface descriptions.
+//!
+//! `--interface-descriptions PATH` names a JSON5 file mapping interfaces to a
+//! free-text description, so an admin can document them once on the backend
+//! and every frontend shows the same text:
+//!
+//! ```json5
+//! {
+//!     "eth1": "upstream ISP",
+//!     // Only the veth0 in namespace "lab"
+//!     "lab/veth0": "client side of the test bench",
+//! }
+//! ```
+//!
+//! A plain interface name applies in every namespace; `namespace/interface`
+//! applies to one namespace and wins over the plain name. Interfaces the file
+//! doesn't mention get no description.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use tcgui_shared::NetworkInterface;
+
+/// Longest description accepted; longer ones are cut.
+pub const MAX_DESCRIPTION_LEN: usize = 200;
+
+/// Descriptions keyed by interface name, and by namespace and name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterfaceDescriptions {
+    by_name: HashMap<String, String>,
+    by_namespace: HashMap<(String, String), String>,
+}
+
+impl InterfaceDescriptions {
+    /// Build from the file's entries. Blank descriptions are dropped.
+    pub fn from_map(entries: BTreeMap<String, String>) -> Self {
+        let mut descriptions = Self::default();
+        for (key, description) in entries {
+            let description = description.trim();
+            if description.is_empty() {
+                continue;
+            }
+            let description: String = description.chars().take(MAX_DESCRIPTION_LEN).collect();
+            match key.split_once('/') {
+                Some((namespace, name)) => {
+                    descriptions
+                        .by_namespace
+                        .insert((namespace.to_string(), name.to_string()), description);
+                }
+                None => {
+                    descriptions.by_name.insert(key, description);
+                }
+            }
+        }
+        descriptions
+    }
+
+    /// Parse the JSON5 contents of a descriptions file.
+    pub fn parse(content: &str) -> Result<Self> {
+        let entries: BTreeMap<String, String> =
+            json5::from_str(content).context("expected an object of interface descriptions")?;
+        Ok(Self::from_map(entries))
+    }
+
+    /// Load a descriptions file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Number of configured descriptions
+    pub fn len(&self) -> usize {
+        self.by_name.len() + self.by_namespace.len()
+    }
+
+    /// Whether no descriptions are configured
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Description of `name` in `namespace`, if configured.
+    pub fn describe(&self, namespace: &str, name: &str) -> Option<&str> {
+        self.by_namespace
+            .get(&(namespace.to_string(), name.to_string()))
+            .or_else(|| self.by_name.get(name))
+            .map(String::as_str)
+    }
+
+    /// Set the description of every discovered interface.
+    pub fn apply(&self, interfaces: &mut HashMap<u32, NetworkInterface>) {
+        for interface in interfaces.values_mut() {
+            interface.description = self
+                .describe(&interface.namespace, &interface.name)
+                .map(str::to_string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::interface;
+
+    #[test]
+    fn test_namespaced_entries_win_over_plain_names() {
+        let descriptions = InterfaceDescriptions::parse(
+            r#"{
+                "eth1": "upstream ISP",
+                // Comments are allowed
+                "lab/eth1": "bench uplink",
+                "veth0": "  ",
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(descriptions.len(), 2);
+        assert_eq!(
+            descriptions.describe("default", "eth1"),
+            Some("upstream ISP")
+        );
+        assert_eq!(descriptions.describe("lab", "eth1"), Some("bench uplink"));
+        assert_eq!(descriptions.describe("default", "veth0"), None);
+    }
+
+    #[test]
+    fn test_apply_leaves_unmapped_interfaces_without_description() {
+        let descriptions = InterfaceDescriptions::parse(r#"{ "eth1": "upstream ISP" }"#).unwrap();
+        let mut interfaces = HashMap::from([
+            (2, interface("eth1", 2, "default")),
+            (3, interface("eth2", 3, "default")),
+        ]);
+        interfaces.get_mut(&3).unwrap().description = Some("stale".to_string());
+
+        descriptions.apply(&mut interfaces);
+        assert_eq!(interfaces[&2].description.as_deref(), Some("upstream ISP"));
+        assert_eq!(interfaces[&3].description, None);
+    }
+
+    #[test]
+    fn test_invalid_file_is_an_error() {
+        assert!(InterfaceDescriptions::parse(r#"{ "eth1": 5 }"#).is_err());
+        assert!(InterfaceDescriptions::parse("[]").is_err());
+        let long = format!(r#"{{ "eth1": "{}" }}"#, "x".repeat(500));
+        let descriptions = InterfaceDescriptions::parse(&long).unwrap();
+        assert_eq!(
+            descriptions.describe("default", "eth1").unwrap().len(),
+            MAX_DESCRIPTION_LEN
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Interfaces the backend leaves out of discovery.
+//!
+//! Hosts running containers or VMs carry dozens of interfaces nobody impairs
+//! (`docker0`, bridges, one veth per container). Each exclusion pattern is
+//! either a glob on the interface name (`*` any run of characters, `?` one
+//! character) or `type:KIND` for a whole interface type. `--exclude-loopback`
+//! is the [`LOOPBACK_PATTERN`].
+//!
+//! ```text
+//! --exclude 'docker*' --exclude type:bridge --exclude wlan0
+//! ```
+
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashMap;
+use tcgui_shared::{InterfaceType, NetworkInterface};
+
+/// Pattern `--exclude-loopback` stands for
+pub const LOOPBACK_PATTERN: &str = "type:loopback";
+
+/// Interface type names usable after `type:`
+const TYPE_NAMES: [(&str, InterfaceType); 7] = [
+    ("physical", InterfaceType::Physical),
+    ("virtual", InterfaceType::Virtual),
+    ("veth", InterfaceType::Veth),
+    ("bridge", InterfaceType::Bridge),
+    ("tun", InterfaceType::Tun),
+    ("tap", InterfaceType::Tap),
+    ("loopback", InterfaceType::Loopback),
+];
+
+/// One compiled exclusion pattern
+#[derive(Debug, Clone)]
+enum ExclusionPattern {
+    /// Anchored regex translated from a name glob
+    Name(Regex),
+    Type(InterfaceType),
+}
+
+impl ExclusionPattern {
+    fn parse(pattern: &str) -> Result<Self> {
+        if let Some(kind) = pattern.strip_prefix("type:") {
+            return TYPE_NAMES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(kind))
+                .map(|(_, interface_type)| Self::Type(interface_type.clone()))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid exclude pattern '{}': unknown interface type '{}'. Must be one of: {}",
+                        pattern,
+                        kind,
+                        TYPE_NAMES.map(|(name, _)| name).join(", ")
+                    )
+                });
+        }
+
+        if pattern.is_empty() || pattern.chars().any(|c| c.is_whitespace() || c == '/') {
+            return Err(anyhow::anyhow!(
+                "Invalid exclude pattern '{}': interface names can't be empty or contain \
+                 whitespace or '/'",
+                pattern
+            ));
+        }
+        let mut regex = String::from("^");
+        for c in pattern.chars() {
+            match c {
+                '*' => regex.push_str(".*"),
+                '?' => regex.push('.'),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push('$');
+        Ok(Self::Name(Regex::new(&regex)?))
+    }
+
+    fn matches(&self, interface: &NetworkInterface) -> bool {
+        match self {
+            Self::Name(regex) => regex.is_match(&interface.name),
+            Self::Type(interface_type) => interface.interface_type == *interface_type,
+        }
+    }
+}
+
+/// Compiled set of exclusion patterns
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceFilter {
+    patterns: Vec<ExclusionPattern>,
+}
+
+impl InterfaceFilter {
+    /// Compile `patterns`, failing on the first invalid one.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        Ok(Self {
+            patterns: patterns
+                .iter()
+                .map(|pattern| ExclusionPattern::parse(pattern))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Whether any pattern matches `interface`
+    pub fn excludes(&self, interface: &NetworkInterface) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches(interface))
+    }
+
+    /// Drop the interfaces a pattern matches
+    pub fn filter(
+        &self,
+        interfaces: HashMap<u32, NetworkInterface>,
+    ) -> HashMap<u32, NetworkInterface> {
+        if self.patterns.is_empty() {
+            return interfaces;
+        }
+        interfaces
+            .into_iter()
+            .filter(|(_, interface)| !self.excludes(interface))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interface(index: u32, name: &str, interface_type: InterfaceType) -> NetworkInterface {
+        NetworkInterface {
+            interface_type,
+            ..crate::test_support::interface(name, index, "default")
+        }
+    }
+
+    #[test]
+    fn test_patterns_filter_a_sample_host() {
+        let interfaces: HashMap<u32, NetworkInterface> = [
+            interface(1, "lo", InterfaceType::Loopback),
+            interface(2, "eth0", InterfaceType::Physical),
+            interface(3, "eth1", InterfaceType::Physical),
+            interface(4, "docker0", InterfaceType::Bridge),
+            interface(5, "virbr0", InterfaceType::Bridge),
+            interface(6, "veth1a2b", InterfaceType::Veth),
+            interface(7, "wlan0", InterfaceType::Physical),
+            interface(8, "tun0", InterfaceType::Tun),
+            interface(9, "dockerveth", InterfaceType::Veth),
+        ]
+        .into_iter()
+        .map(|i| (i.index, i))
+        .collect();
+
+        let filter = InterfaceFilter::new(&[
+            LOOPBACK_PATTERN.to_string(),
+            "docker*".to_string(),
+            "type:Bridge".to_string(),
+            "wlan?".to_string(),
+        ])
+        .unwrap();
+        let mut kept: Vec<_> = filter
+            .filter(interfaces.clone())
+            .into_values()
+            .map(|i| i.name)
+            .collect();
+        kept.sort();
+        assert_eq!(kept, vec!["eth0", "eth1", "tun0", "veth1a2b"]);
+
+        // Globs match whole names, and no patterns keep everything
+        let filter = InterfaceFilter::new(&["eth".to_string(), "*0".to_string()]).unwrap();
+        let mut kept: Vec<_> = filter
+            .filter(interfaces.clone())
+            .into_values()
+            .map(|i| i.name)
+            .collect();
+        kept.sort();
+        assert_eq!(kept, vec!["dockerveth", "eth1", "lo", "veth1a2b"]);
+        assert_eq!(InterfaceFilter::default().filter(interfaces).len(), 9);
+    }
+
+    #[test]
+    fn test_invalid_patterns_are_rejected() {
+        let error = InterfaceFilter::new(&["type:wifi".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("unknown interface type 'wifi'"), "{}", error);
+        assert!(InterfaceFilter::new(&[String::new()]).is_err());
+        assert!(InterfaceFilter::new(&["eth 0".to_string()]).is_err());
+        // Regex syntax is taken literally
+        let filter = InterfaceFilter::new(&["br.lan+".to_string()]).unwrap();
+        assert!(filter.excludes(&interface(1, "br.lan+", InterfaceType::Bridge)));
+        assert!(!filter.excludes(&interface(2, "brxlann", InterfaceType::Bridge)));
+    }
+}
@@ -3,15 +3,31 @@
 //! This exposes internal modules for integration tests
 
 pub mod bandwidth;
+pub mod capabilities;
 pub mod commands;
 pub mod config;
+pub mod config_history;
 pub mod container;
 pub mod diagnostics;
+pub mod export;
+pub mod impairment_limit;
+pub mod interface_descriptions;
+pub mod interface_filter;
 pub mod interfaces;
+pub mod link_policy;
+pub mod management;
 pub mod namespace_watcher;
 pub mod netns;
 pub mod network;
 pub mod preset_loader;
+pub mod publisher_cache;
+pub mod qos;
+pub mod rate_limit;
 pub mod scenario;
 pub mod tc_commands;
+pub mod tc_config;
+pub mod tc_schedule;
 pub mod utils;
+
+#[cfg(test)]
+mod test_support;
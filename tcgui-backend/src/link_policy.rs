@@ -0,0 +1,165 @@
+//! TC configuration handling when an interface goes down.
+//!
+//! A netem qdisc survives the link going down, so bringing the interface back
+//! up silently reapplies whatever impairment was left on it. The backend can
+//! instead clear the configuration when the link goes down and, optionally,
+//! restore it when the link comes back.
+//!
+//! "Down" means not running: administratively down (an explicit disable) or
+//! without carrier (link loss). Both are detected the same way, by comparing
+//! successive interface lists, so a disable through the GUI and a pulled cable
+//! behave alike. The explicit Enable/Disable operations additionally act
+//! right away instead of waiting for the netlink event.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use tcgui_shared::NetworkInterface;
+
+/// What to do with an interface's TC configuration when its link goes down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LinkDownTcPolicy {
+    /// Leave the configuration in place (kernel default)
+    #[default]
+    Keep,
+    /// Remove the configuration
+    Clear,
+    /// Remove the configuration and reapply it when the link comes back up
+    Restore,
+}
+
+impl FromStr for LinkDownTcPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "clear" => Ok(Self::Clear),
+            "restore" => Ok(Self::Restore),
+            other => Err(format!(
+                "Invalid link-down policy '{}'. Must be 'keep', 'clear' or 'restore'",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LinkDownTcPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Keep => "keep",
+            Self::Clear => "clear",
+            Self::Restore => "restore",
+        })
+    }
+}
+
+/// A change in whether an interface is running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTransition {
+    Down {
+        namespace: String,
+        interface: String,
+    },
+    Up {
+        namespace: String,
+        interface: String,
+    },
+}
+
+/// Whether an interface is passing traffic: administratively up with carrier.
+fn is_running(interface: &NetworkInterface) -> bool {
+    interface.is_up && interface.is_oper_up
+}
+
+/// Interfaces present in both lists whose running state changed.
+pub fn link_transitions(
+    old: &HashMap<u32, NetworkInterface>,
+    new: &HashMap<u32, NetworkInterface>,
+) -> Vec<LinkTransition> {
+    new.iter()
+        .filter_map(|(index, after)| {
+            let before = old.get(index).filter(|before| {
+                before.namespace == after.namespace && before.name == after.name
+            })?;
+            let namespace = after.namespace.clone();
+            let interface = after.name.clone();
+            match (is_running(before), is_running(after)) {
+                (true, false) => Some(LinkTransition::Down {
+                    namespace,
+                    interface,
+                }),
+                (false, true) => Some(LinkTransition::Up {
+                    namespace,
+                    interface,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iface(index: u32, name: &str, is_up: bool, is_oper_up: bool) -> NetworkInterface {
+        NetworkInterface {
+            is_up,
+            is_oper_up,
+            ..crate::test_support::interface(name, index, "default")
+        }
+    }
+
+    fn list(interfaces: Vec<NetworkInterface>) -> HashMap<u32, NetworkInterface> {
+        interfaces.into_iter().map(|i| (i.index, i)).collect()
+    }
+
+    #[test]
+    fn test_disable_and_link_loss_are_both_down() {
+        let old = list(vec![
+            iface(2, "eth0", true, true),
+            iface(3, "eth1", true, true),
+            iface(4, "eth2", false, false),
+        ]);
+        let new = list(vec![
+            iface(2, "eth0", false, false), // disabled
+            iface(3, "eth1", true, false),  // carrier lost
+            iface(4, "eth2", true, true),   // enabled
+        ]);
+
+        let mut transitions = link_transitions(&old, &new);
+        transitions.sort_by_key(|t| format!("{t:?}"));
+        assert_eq!(
+            transitions,
+            vec![
+                LinkTransition::Down {
+                    namespace: "default".to_string(),
+                    interface: "eth0".to_string()
+                },
+                LinkTransition::Down {
+                    namespace: "default".to_string(),
+                    interface: "eth1".to_string()
+                },
+                LinkTransition::Up {
+                    namespace: "default".to_string(),
+                    interface: "eth2".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_new_and_renamed_interfaces_are_not_transitions() {
+        let old = list(vec![iface(2, "eth0", false, false)]);
+        let new = list(vec![
+            iface(2, "renamed0", true, true),
+            iface(5, "veth0", true, true),
+        ]);
+        assert!(link_transitions(&old, &new).is_empty());
+
+        assert_eq!("Restore".parse(), Ok(LinkDownTcPolicy::Restore));
+        assert!("off".parse::<LinkDownTcPolicy>().is_err());
+    }
+}
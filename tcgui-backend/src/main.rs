@@ -1,20 +1,35 @@
 mod bandwidth;
+mod capabilities;
 pub mod config;
+mod config_history;
 mod container;
 mod diagnostics;
+mod export;
 mod hw_shaping;
+mod impairment_limit;
+mod interface_descriptions;
+mod interface_filter;
+mod link_policy;
+mod management;
 mod namespace_watcher;
 mod netlink_events;
+mod netns;
 mod network;
 pub mod preset_loader;
+mod publisher_cache;
+mod qos;
+mod rate_limit;
 mod registry;
 pub mod scenario;
 mod tc_commands;
 mod tc_config;
+mod tc_schedule;
 mod zenoh_query;
 
 #[cfg(test)]
 mod tc_commands_test;
+#[cfg(test)]
+mod test_support;
 
 use anyhow::Result;
 use std::collections::HashMap;
@@ -27,7 +42,8 @@ use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, CacheConfig, Mis
 
 use tcgui_shared::registry::tc;
 use tcgui_shared::{
-    NetworkInterface, TcConfigUpdate, TcConfiguration, TcStatisticsUpdate, ZenohConfig,
+    NetworkInterface, RateLimitMode, TcConfigUpdate, TcConfiguration, TcStatisticsUpdate,
+    ZenohConfig,
     errors::TcguiError,
     identity::{ConcreteOrigin, LocalOrigin},
     presets::PresetList,
@@ -35,6 +51,7 @@ use tcgui_shared::{
 };
 
 use bandwidth::BandwidthMonitor;
+use interface_filter::InterfaceFilter;
 use namespace_watcher::{NamespaceEvent, NamespaceWatcher};
 use netlink_events::NetlinkEventListener;
 use network::NetworkManager;
@@ -42,6 +59,10 @@ use preset_loader::PresetLoader;
 use scenario::{ScenarioExecutionHandlers, ScenarioManager, ScenarioZenohHandlers};
 use tc_commands::TcCommandManager;
 
+/// Minimum spacing of forced refreshes; each one rediscovers every namespace
+/// and reads back every qdisc
+const FORCE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
 struct TcBackend {
     session: Session,
     interfaces: HashMap<u32, NetworkInterface>,
@@ -56,26 +77,54 @@ struct TcBackend {
     preset_list: PresetList,
     /// Per-preset state publishers keyed by preset id (`state/tc/preset/{id}`).
     preset_publishers: HashMap<String, AdvancedPublisher<'static>>,
-    exclude_loopback: bool,
+    /// Interfaces left out of discovery
+    interface_filter: InterfaceFilter,
     /// This host's minted origin — the identity every published key is built from.
     local_origin: LocalOrigin,
     /// Operator-chosen display label — used only in the health document, never as a key.
     backend_name: String,
-    tc_config_publishers: HashMap<String, AdvancedPublisher<'static>>, // namespace/interface -> publisher
+    tc_config_publishers: publisher_cache::PublisherCache<AdvancedPublisher<'static>>, // namespace/interface -> publisher (LRU-bounded)
     tc_stats_publishers: HashMap<String, Publisher<'static>>, // namespace/interface -> publisher (best-effort)
+    /// Failed namespaces last reported in the health document, to publish only on change
+    reported_failed_namespaces: Vec<String>,
+    /// Publish peer ids in the health document rather than just the count
+    expose_peer_info: bool,
+    /// Zenoh ids of the peers connected at the last health check, sorted
+    connected_peers: Vec<String>,
+    /// Per-peer limit on TC / interface / diagnostics queries
+    query_limiter: rate_limit::QueryRateLimiter,
+    /// Cap on simultaneously impaired interfaces
+    impairment_limit: impairment_limit::ImpairmentLimit,
+    /// TC requests waiting for their `apply_at` time
+    tc_schedule: tc_schedule::TcSchedule,
+    /// Recent configurations published per interface
+    config_history: config_history::ConfigHistory,
+    /// Netem features supported on the running kernel, probed at startup
+    netem_capabilities: tcgui_shared::NetemCapabilities,
+    /// JSON-lines export of published TC changes, when enabled
+    exporter: Option<export::SharedExporter>,
+    /// What to do with TC configuration when an interface goes down
+    link_down_policy: link_policy::LinkDownTcPolicy,
+    /// Configurations cleared on link down, to reapply on link up (namespace/interface)
+    suspended_tc: HashMap<String, tc_commands::CapturedTcState>,
+    /// Spacing of forced refreshes, across all peers
+    force_refresh_cooldown: rate_limit::Cooldown,
+    /// Zenoh QoS per published message type
+    qos: tcgui_shared::qos::QosProfiles,
+    /// Spacing of bandwidth samples
+    bandwidth_monitor_interval: Duration,
 }
 
 impl TcBackend {
-    #[instrument(skip(zenoh_config, scenario_dirs, preset_dirs), fields(backend_name = %backend_name, exclude_loopback))]
-    async fn new(
-        exclude_loopback: bool,
-        backend_name: String,
-        zenoh_config: ZenohConfig,
-        scenario_dirs: Vec<String>,
-        no_default_scenarios: bool,
-        preset_dirs: Vec<String>,
-        no_default_presets: bool,
-    ) -> Result<Self> {
+    #[instrument(skip(app_config, zenoh_config), fields(backend_name = %app_config.backend_name, exclude_patterns = ?app_config.exclude_patterns))]
+    async fn new(app_config: &config::AppConfig, zenoh_config: ZenohConfig) -> Result<Self> {
+        let backend_name = app_config.backend_name.clone();
+        let interface_filter = InterfaceFilter::new(&app_config.exclude_patterns)?;
+        let scenario_dirs = app_config.scenario_dirs.clone();
+        let no_default_scenarios = app_config.no_default_scenarios;
+        let preset_dirs = app_config.preset_dirs.clone();
+        let no_default_presets = app_config.no_default_presets;
+
         // Initialize Zenoh session
         let config = zenoh_config
             .to_zenoh_config()
@@ -97,6 +146,25 @@ impl TcBackend {
         let local_origin = tcgui_shared::identity::mint_local_origin();
         info!("[BACKEND] Host origin minted: {}", local_origin.chunk());
 
+        let netem_capabilities = capabilities::probe();
+        info!(
+            "[BACKEND] Kernel {}, iproute2 {}: {} of {} netem features supported",
+            netem_capabilities
+                .kernel_release
+                .as_deref()
+                .unwrap_or("unknown"),
+            netem_capabilities
+                .tc_version
+                .as_deref()
+                .unwrap_or("unknown"),
+            netem_capabilities
+                .features
+                .iter()
+                .filter(|f| f.supported)
+                .count(),
+            netem_capabilities.features.len()
+        );
+
         // Declare the liveliness token on the reserved `state/alive` leaf — distinct
         // from the health document (which is a normal Put on `state/health`).
         let alive_topic = topics::state_alive(&local_origin);
@@ -115,15 +183,52 @@ impl TcBackend {
 
         // Initialize managers with the host origin for key building
         // NetworkManager now creates its own nlink connection internally
-        let network_manager =
+        let mut network_manager =
             NetworkManager::new(session.clone(), local_origin.clone(), backend_name.clone())
                 .await?;
         info!("[BACKEND] Network manager initialized with nlink");
+        let management_endpoints =
+            management::ManagementEndpoint::from_endpoints(&zenoh_config.endpoints);
+        if management_endpoints.is_empty() && !zenoh_config.endpoints.is_empty() {
+            warn!(
+                "[BACKEND] No Zenoh endpoint names a concrete address; management interfaces won't be flagged"
+            );
+        }
+        network_manager.set_management_endpoints(management_endpoints);
+        network_manager.set_qos(app_config.qos.interface_list);
+        if let Some(path) = &app_config.interface_descriptions {
+            let descriptions =
+                interface_descriptions::InterfaceDescriptions::load(std::path::Path::new(path))?;
+            if descriptions.is_empty() {
+                warn!("[BACKEND] {} contains no interface descriptions", path);
+            } else {
+                info!(
+                    "[BACKEND] Loaded {} interface description(s) from {}",
+                    descriptions.len(),
+                    path
+                );
+            }
+            network_manager.set_interface_descriptions(descriptions);
+        }
 
         // Create bandwidth monitor and share container cache for container namespace support
         let mut bandwidth_monitor =
             BandwidthMonitor::new(session.clone(), local_origin.clone(), backend_name.clone());
         bandwidth_monitor.set_container_cache(network_manager.container_cache());
+        bandwidth_monitor.set_qos(app_config.qos.bandwidth_updates);
+
+        // Optional JSON-lines export of bandwidth samples and TC changes
+        let exporter = match &app_config.export_jsonl {
+            Some(path) => {
+                let exporter = export::JsonLinesExporter::open(path, backend_name.clone())
+                    .map_err(|e| anyhow::anyhow!("Failed to open export file {}: {}", path, e))?;
+                info!("[BACKEND] Exporting bandwidth and TC events to {}", path);
+                let exporter = std::sync::Arc::new(std::sync::Mutex::new(exporter));
+                bandwidth_monitor.set_exporter(exporter.clone());
+                Some(exporter)
+            }
+            None => None,
+        };
 
         let tc_manager = TcCommandManager::new();
 
@@ -212,11 +317,29 @@ impl TcBackend {
             _preset_loader: preset_loader,
             preset_list,
             preset_publishers,
-            exclude_loopback,
+            interface_filter,
             local_origin,
             backend_name,
-            tc_config_publishers: HashMap::new(),
+            tc_config_publishers: publisher_cache::PublisherCache::default(),
             tc_stats_publishers: HashMap::new(),
+            reported_failed_namespaces: Vec::new(),
+            expose_peer_info: app_config.expose_peer_info,
+            connected_peers: Vec::new(),
+            query_limiter: rate_limit::QueryRateLimiter::new(app_config.query_rate_limit),
+            impairment_limit: impairment_limit::ImpairmentLimit::new(
+                app_config.max_impaired_interfaces,
+            ),
+            tc_schedule: tc_schedule::TcSchedule::default(),
+            config_history: config_history::ConfigHistory::default(),
+            netem_capabilities,
+            exporter,
+            link_down_policy: app_config.link_down_policy,
+            suspended_tc: HashMap::new(),
+            force_refresh_cooldown: rate_limit::Cooldown::new(FORCE_REFRESH_INTERVAL),
+            qos: app_config.qos,
+            bandwidth_monitor_interval: Duration::from_secs(
+                app_config.bandwidth_monitor_interval_secs,
+            ),
         })
     }
 
@@ -225,20 +348,71 @@ impl TcBackend {
         &self,
         interfaces: HashMap<u32, NetworkInterface>,
     ) -> HashMap<u32, NetworkInterface> {
-        if self.exclude_loopback {
-            interfaces
-                .into_iter()
-                .filter(|(_, interface)| {
-                    // Filter out loopback interfaces (typically "lo")
-                    interface.name != "lo"
-                        && !matches!(
-                            interface.interface_type,
-                            tcgui_shared::InterfaceType::Loopback
-                        )
-                })
-                .collect()
+        self.interface_filter.filter(interfaces)
+    }
+
+    /// Keep the last known interfaces of namespaces whose discovery just failed.
+    ///
+    /// A transient netlink or permission error in one namespace should not
+    /// tombstone its interfaces; they stay as they were until the namespace
+    /// can be read again (or disappears from the namespace list).
+    fn carry_over_failed_namespaces(
+        &self,
+        mut interfaces: HashMap<u32, NetworkInterface>,
+    ) -> HashMap<u32, NetworkInterface> {
+        let failed = self.network_manager.failed_namespaces();
+        if failed.is_empty() {
+            return interfaces;
+        }
+        for (key, interface) in &self.interfaces {
+            if failed.contains(&interface.namespace) {
+                interfaces.entry(*key).or_insert_with(|| interface.clone());
+            }
+        }
+        interfaces
+    }
+
+    /// Publish a health update when the set of failed namespaces changes.
+    async fn report_discovery_failures(&mut self) {
+        let failed = self.network_manager.failed_namespaces();
+        if failed == self.reported_failed_namespaces {
+            return;
+        }
+        let status = if failed.is_empty() {
+            "Interface discovery recovered".to_string()
         } else {
-            interfaces
+            format!(
+                "Interface discovery degraded: {} namespace(s) failed",
+                failed.len()
+            )
+        };
+        self.reported_failed_namespaces = failed;
+        if let Err(e) = self.send_backend_status(&status).await {
+            warn!("Failed to publish backend status: {}", e);
+        }
+    }
+
+    /// Publish a health update when the set of connected Zenoh peers changes.
+    /// Routers count too: in a routed or client deployment the frontends are
+    /// reached through a router and never show up as direct peers.
+    async fn refresh_peer_health(&mut self) {
+        let info = self.session.info();
+        let mut peers: Vec<String> = info
+            .peers_zid()
+            .await
+            .chain(info.routers_zid().await)
+            .map(|zid| zid.to_string())
+            .collect();
+        peers.sort();
+        peers.dedup();
+        if peers == self.connected_peers {
+            return;
+        }
+        info!("[BACKEND] Connected peers: {}", peers.len());
+        self.connected_peers = peers;
+        let status = format!("{} peer(s) connected", self.connected_peers.len());
+        if let Err(e) = self.send_backend_status(&status).await {
+            warn!("Failed to publish backend status: {}", e);
         }
     }
 
@@ -278,6 +452,106 @@ impl TcBackend {
             interface_query_topic.as_str()
         );
 
+        // Set up batch Interface control query handler
+        let interface_batch_query_topic =
+            tc::rpc_serve_key(&self.local_origin, tc::ProcedureId::InterfacesNsSet);
+        let interface_batch_queryable = self
+            .session
+            .declare_queryable(interface_batch_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare Interface batch queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' Interface batch query handler declared on: {}",
+            self.backend_name,
+            interface_batch_query_topic.as_str()
+        );
+
+        // Set up network (link creation/deletion) query handler
+        let network_query_topic =
+            tc::rpc_serve_key(&self.local_origin, tc::ProcedureId::NetworkNsSet);
+        let network_queryable = self
+            .session
+            .declare_queryable(network_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare network queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' network query handler declared on: {}",
+            self.backend_name,
+            network_query_topic.as_str()
+        );
+
+        // Set up feature clear query handler
+        let feature_clear_query_topic =
+            tc::rpc_serve_key(&self.local_origin, tc::ProcedureId::ConfigsNsClear);
+        let feature_clear_queryable = self
+            .session
+            .declare_queryable(feature_clear_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare feature clear queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' feature clear query handler declared on: {}",
+            self.backend_name,
+            feature_clear_query_topic.as_str()
+        );
+
+        // Set up rate limiter comparison query handler
+        let rate_comparison_query_topic = tc::rpc_serve_key(
+            &self.local_origin,
+            tc::ProcedureId::DiagnosticsNsIfaceRateCompare,
+        );
+        let rate_comparison_queryable = self
+            .session
+            .declare_queryable(rate_comparison_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare rate comparison queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' rate comparison query handler declared on: {}",
+            self.backend_name,
+            rate_comparison_query_topic.as_str()
+        );
+
+        // Set up configuration history query handler
+        let config_history_query_topic =
+            tc::rpc_serve_key(&self.local_origin, tc::ProcedureId::ConfigNsIfaceHistory);
+        let config_history_queryable = self
+            .session
+            .declare_queryable(config_history_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare config history queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' config history query handler declared on: {}",
+            self.backend_name,
+            config_history_query_topic.as_str()
+        );
+
+        // Set up stats snapshot query handler
+        let stats_query_topic = tc::rpc_serve_key(
+            &self.local_origin,
+            tc::ProcedureId::BandwidthNsIfaceSnapshot,
+        );
+        let stats_queryable = self
+            .session
+            .declare_queryable(stats_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare stats queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' stats query handler declared on: {}",
+            self.backend_name,
+            stats_query_topic.as_str()
+        );
+
         // Set up Diagnostics query handler
         let diagnostics_query_topic = tc::diagnostics_key(&self.local_origin);
         let diagnostics_queryable = self
@@ -293,6 +567,66 @@ impl TcBackend {
             diagnostics_query_topic.as_str()
         );
 
+        // Set up namespace-wide Diagnostics query handler
+        let namespace_diagnostics_query_topic = tc::diagnostics_namespace_key(&self.local_origin);
+        let namespace_diagnostics_queryable = self
+            .session
+            .declare_queryable(namespace_diagnostics_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare namespace Diagnostics queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' namespace Diagnostics query handler declared on: {}",
+            self.backend_name,
+            namespace_diagnostics_query_topic.as_str()
+        );
+
+        // Set up qdisc tree query handler
+        let tree_query_topic = tc::diagnostics_tree_key(&self.local_origin);
+        let tree_queryable = self
+            .session
+            .declare_queryable(tree_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare qdisc tree queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' qdisc tree query handler declared on: {}",
+            self.backend_name,
+            tree_query_topic.as_str()
+        );
+
+        // Set up netem capabilities query handler
+        let capabilities_query_topic = tc::capabilities_key(&self.local_origin);
+        let capabilities_queryable = self
+            .session
+            .declare_queryable(capabilities_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare capabilities queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' capabilities query handler declared on: {}",
+            self.backend_name,
+            capabilities_query_topic.as_str()
+        );
+
+        // Set up forced refresh query handler
+        let refresh_query_topic = tc::refresh_key(&self.local_origin);
+        let refresh_queryable = self
+            .session
+            .declare_queryable(refresh_query_topic.as_keyexpr())
+            .await
+            .map_err(|e| TcguiError::ZenohError {
+                message: format!("Failed to declare refresh queryable: {}", e),
+            })?;
+        info!(
+            "[BACKEND] Backend '{}' refresh query handler declared on: {}",
+            self.backend_name,
+            refresh_query_topic.as_str()
+        );
+
         // Set up introspect query handler — serves this producer's registry
         // slice as TOML so generic bus tooling (zenctl) needs no compiled-in
         // registry (RFC keyspace-v2 08 §6).
@@ -389,6 +723,7 @@ impl TcBackend {
             .await
             .map_err(|e| anyhow::anyhow!(e))?;
         self.interfaces = self.filter_interfaces(discovered_interfaces);
+        self.report_discovery_failures().await;
 
         // Send interface list (the network manager will organize by namespace)
         self.network_manager
@@ -476,7 +811,8 @@ impl TcBackend {
         // Create intervals for periodic tasks
         // Namespace polling interval increased to 60s since inotify handles immediate detection
         let mut namespace_monitor_interval = interval(Duration::from_secs(60));
-        let mut bandwidth_monitor_interval = interval(Duration::from_secs(2));
+        let mut bandwidth_monitor_interval = interval(self.bandwidth_monitor_interval);
+        let mut peer_health_interval = interval(Duration::from_secs(10));
 
         // Skip the first tick to avoid immediate execution
         namespace_monitor_interval.tick().await;
@@ -489,7 +825,9 @@ impl TcBackend {
                 query = tc_queryable.recv_async() => {
                     match query {
                         Ok(query) => {
-                            if let Err(e) = self.handle_tc_query(query).await {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_tc_query(query).await
+                            {
                                 error!("Failed to handle TC query: {}", e);
                             }
                         }
@@ -503,7 +841,9 @@ impl TcBackend {
                 query = interface_queryable.recv_async() => {
                     match query {
                         Ok(query) => {
-                            if let Err(e) = self.handle_interface_query(query).await {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_interface_query(query).await
+                            {
                                 error!("Failed to handle Interface query: {}", e);
                             }
                         }
@@ -513,11 +853,109 @@ impl TcBackend {
                     }
                 }
 
+                // Handle batch Interface control queries
+                query = interface_batch_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_interface_batch_query(query).await
+                            {
+                                error!("Failed to handle Interface batch query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving Interface batch query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle network (link creation/deletion) queries
+                query = network_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_network_query(query).await
+                            {
+                                error!("Failed to handle network query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving network query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle feature clear queries
+                query = feature_clear_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_feature_clear_query(query).await
+                            {
+                                error!("Failed to handle feature clear query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving feature clear query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle rate limiter comparison queries
+                query = rate_comparison_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_rate_comparison_query(query).await
+                            {
+                                error!("Failed to handle rate comparison query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving rate comparison query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle configuration history queries
+                query = config_history_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_config_history_query(query).await
+                            {
+                                error!("Failed to handle config history query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving config history query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle stats snapshot queries
+                query = stats_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_stats_query(query).await
+                            {
+                                error!("Failed to handle stats query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving stats query: {}", e);
+                        }
+                    }
+                }
+
                 // Handle Diagnostics queries
                 query = diagnostics_queryable.recv_async() => {
                     match query {
                         Ok(query) => {
-                            if let Err(e) = self.handle_diagnostics_query(query).await {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_diagnostics_query(query).await
+                            {
                                 error!("Failed to handle Diagnostics query: {}", e);
                             }
                         }
@@ -527,6 +965,70 @@ impl TcBackend {
                     }
                 }
 
+                // Handle namespace-wide Diagnostics queries
+                query = namespace_diagnostics_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_namespace_diagnostics_query(query).await
+                            {
+                                error!("Failed to handle namespace Diagnostics query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving namespace Diagnostics query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle qdisc tree queries
+                query = tree_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_tree_query(query).await
+                            {
+                                error!("Failed to handle qdisc tree query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving qdisc tree query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle netem capabilities queries
+                query = capabilities_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_capabilities_query(query).await
+                            {
+                                error!("Failed to handle capabilities query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving capabilities query: {}", e);
+                        }
+                    }
+                }
+
+                // Handle forced refresh queries
+                query = refresh_queryable.recv_async() => {
+                    match query {
+                        Ok(query) => {
+                            if self.admit_query(&query).await
+                                && let Err(e) = self.handle_refresh_query(query).await
+                            {
+                                error!("Failed to handle refresh query: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error receiving refresh query: {}", e);
+                        }
+                    }
+                }
+
                 // Handle introspect queries (serve the registry slice as TOML)
                 query = introspect_queryable.recv_async() => {
                     match query {
@@ -677,7 +1179,8 @@ impl TcBackend {
                     // Trigger a full interface refresh on namespace change
                     match self.network_manager.discover_all_interfaces().await {
                         Ok(discovered_interfaces) => {
-                            let updated_interfaces = self.filter_interfaces(discovered_interfaces);
+                            let updated_interfaces = self.carry_over_failed_namespaces(self.filter_interfaces(discovered_interfaces));
+                            self.report_discovery_failures().await;
                             if self.interfaces != updated_interfaces {
                                 info!("Namespace event triggered interface update");
 
@@ -686,8 +1189,10 @@ impl TcBackend {
                                     .map(|i| (i.namespace.clone(), i.name.clone()))
                                     .collect();
 
+                                let link_changes = link_policy::link_transitions(&self.interfaces, &updated_interfaces);
                                 self.cleanup_stale_publishers(&updated_interfaces);
                                 self.interfaces = updated_interfaces;
+                                self.apply_link_down_policy(link_changes).await;
 
                                 if let Err(e) = self.network_manager.send_interface_list(&self.interfaces).await {
                                     error!("Failed to send updated interface list: {}", e);
@@ -715,7 +1220,8 @@ impl TcBackend {
                     tracing::debug!("[BACKEND] Periodic namespace check");
                     match self.network_manager.discover_all_interfaces().await {
                         Ok(discovered_interfaces) => {
-                            let updated_interfaces = self.filter_interfaces(discovered_interfaces);
+                            let updated_interfaces = self.carry_over_failed_namespaces(self.filter_interfaces(discovered_interfaces));
+                            self.report_discovery_failures().await;
                             if self.interfaces != updated_interfaces {
                                 tracing::info!("Namespace poll detected interface changes");
 
@@ -724,8 +1230,10 @@ impl TcBackend {
                                     .map(|i| (i.namespace.clone(), i.name.clone()))
                                     .collect();
 
+                                let link_changes = link_policy::link_transitions(&self.interfaces, &updated_interfaces);
                                 self.cleanup_stale_publishers(&updated_interfaces);
                                 self.interfaces = updated_interfaces;
+                                self.apply_link_down_policy(link_changes).await;
 
                                 if let Err(e) = self.network_manager.send_interface_list(&self.interfaces).await {
                                     error!("Failed to send updated interface list: {}", e);
@@ -745,7 +1253,17 @@ impl TcBackend {
                     }
                 }
 
-                // Periodic bandwidth monitoring (every 2 seconds)
+                // Connected peer tracking for the health document (every 10 seconds)
+                _ = peer_health_interval.tick() => {
+                    self.refresh_peer_health().await;
+                }
+
+                // Scheduled TC requests whose time has come
+                _ = tc_schedule::sleep_until(self.tc_schedule.next_at()) => {
+                    self.run_scheduled_tc().await;
+                }
+
+                // Periodic bandwidth monitoring (every 2 seconds by default)
                 _ = bandwidth_monitor_interval.tick() => {
                     tracing::debug!("[BACKEND] Monitoring bandwidth");
                     // Monitor bandwidth for all namespaces
@@ -859,11 +1377,19 @@ impl TcBackend {
 
     /// Handle link add/remove/state change events by refreshing the interface list
     async fn handle_link_event(&mut self) {
+        self.refresh_interfaces("Netlink link event").await;
+    }
+
+    /// Rediscover interfaces and, if the list changed, adopt it and publish
+    /// the changes. `trigger` names the cause for the log.
+    async fn refresh_interfaces(&mut self, trigger: &str) {
         match self.network_manager.discover_all_interfaces().await {
             Ok(discovered_interfaces) => {
-                let updated_interfaces = self.filter_interfaces(discovered_interfaces);
+                let updated_interfaces = self
+                    .carry_over_failed_namespaces(self.filter_interfaces(discovered_interfaces));
+                self.report_discovery_failures().await;
                 if self.interfaces != updated_interfaces {
-                    tracing::info!("Netlink link event triggered interface update");
+                    tracing::info!("{} triggered interface update", trigger);
 
                     let new_interfaces: Vec<_> = updated_interfaces
                         .values()
@@ -871,8 +1397,11 @@ impl TcBackend {
                         .map(|i| (i.namespace.clone(), i.name.clone()))
                         .collect();
 
+                    let link_changes =
+                        link_policy::link_transitions(&self.interfaces, &updated_interfaces);
                     self.cleanup_stale_publishers(&updated_interfaces);
                     self.interfaces = updated_interfaces;
+                    self.apply_link_down_policy(link_changes).await;
 
                     if let Err(e) = self
                         .network_manager
@@ -899,11 +1428,157 @@ impl TcBackend {
                 }
             }
             Err(e) => {
-                error!("Failed to refresh interfaces after netlink event: {}", e);
+                error!("Failed to refresh interfaces after {}: {}", trigger, e);
             }
         }
     }
 
+    /// Rediscover interfaces and republish every interface record and TC
+    /// configuration, changed or not, for a frontend that suspects it is out
+    /// of sync. The list goes through the same change detection as the
+    /// periodic poll first, so `self.interfaces` stays what the poll would
+    /// have computed and the next tick only reacts to real changes.
+    pub(crate) async fn force_refresh(&mut self) -> Result<tcgui_shared::ForceRefreshResponse> {
+        self.refresh_interfaces("Forced refresh").await;
+
+        self.network_manager
+            .send_interface_list(&self.interfaces)
+            .await?;
+
+        let targets: Vec<_> = self
+            .interfaces
+            .values()
+            .map(|i| (i.namespace.clone(), i.name.clone()))
+            .collect();
+        let mut tc_config_count = 0;
+        for (namespace, interface) in &targets {
+            let current_config = self.detect_current_tc_config(namespace, interface).await;
+            if current_config.is_some() {
+                tc_config_count += 1;
+            }
+            if let Err(e) = self
+                .publish_tc_config(namespace, interface, current_config)
+                .await
+            {
+                warn!(
+                    "Failed to republish TC config for {}:{}: {}",
+                    namespace, interface, e
+                );
+            }
+        }
+
+        info!(
+            "Forced refresh republished {} interfaces, {} with TC configuration",
+            targets.len(),
+            tc_config_count
+        );
+        Ok(tcgui_shared::ForceRefreshResponse {
+            interface_count: targets.len(),
+            tc_config_count,
+        })
+    }
+
+    /// Clear or restore TC configuration for interfaces whose link went down
+    /// or came back up, per the configured policy. Suspended configurations of
+    /// interfaces that disappeared are forgotten.
+    async fn apply_link_down_policy(&mut self, transitions: Vec<link_policy::LinkTransition>) {
+        for transition in transitions {
+            match transition {
+                link_policy::LinkTransition::Down {
+                    namespace,
+                    interface,
+                } => self.suspend_tc_on_link_down(&namespace, &interface).await,
+                link_policy::LinkTransition::Up {
+                    namespace,
+                    interface,
+                } => self.resume_tc_on_link_up(&namespace, &interface).await,
+            }
+        }
+
+        let present: std::collections::HashSet<String> = self
+            .interfaces
+            .values()
+            .map(|i| format!("{}/{}", i.namespace, i.name))
+            .collect();
+        self.suspended_tc.retain(|key, _| present.contains(key));
+    }
+
+    /// Remove the TC configuration of an interface that went down, keeping it
+    /// for restore if the policy asks for that. Safe to call more than once:
+    /// a configuration already suspended is not overwritten.
+    pub(crate) async fn suspend_tc_on_link_down(&mut self, namespace: &str, interface: &str) {
+        if self.link_down_policy == link_policy::LinkDownTcPolicy::Keep {
+            return;
+        }
+        let key = format!("{}/{}", namespace, interface);
+        if self.suspended_tc.contains_key(&key) {
+            return;
+        }
+
+        let state = match self.tc_manager.capture_tc_state(namespace, interface).await {
+            Ok(state) if state.had_tc_config() => state,
+            Ok(_) => return,
+            Err(e) => {
+                warn!("Failed to capture TC state of {}: {}", key, e);
+                return;
+            }
+        };
+        if let Err(e) = self
+            .tc_manager
+            .remove_tc_config_in_namespace(namespace, interface)
+            .await
+        {
+            warn!("Failed to clear TC config of {} on link down: {}", key, e);
+            return;
+        }
+        info!(
+            "Cleared TC config of {} on link down (policy: {})",
+            key, self.link_down_policy
+        );
+        if self.link_down_policy == link_policy::LinkDownTcPolicy::Restore {
+            self.suspended_tc.insert(key, state);
+        }
+        if let Err(e) = self.publish_tc_config(namespace, interface, None).await {
+            warn!("Failed to publish cleared TC config: {}", e);
+        }
+    }
+
+    /// Reapply a configuration suspended on link down. A configuration applied
+    /// while the link was down takes precedence and the suspended one is dropped.
+    pub(crate) async fn resume_tc_on_link_up(&mut self, namespace: &str, interface: &str) {
+        let key = format!("{}/{}", namespace, interface);
+        let Some(state) = self.suspended_tc.remove(&key) else {
+            return;
+        };
+
+        match self.tc_manager.capture_tc_state(namespace, interface).await {
+            Ok(current) if current.had_tc_config() => {
+                info!(
+                    "Not restoring TC config of {}: it was reconfigured while down",
+                    key
+                );
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to capture TC state of {}: {}", key, e),
+        }
+
+        match self.tc_manager.restore_tc_state(&state).await {
+            Ok(message) => info!("Restored TC config of {} on link up: {}", key, message),
+            Err(e) => {
+                warn!("Failed to restore TC config of {}: {}", key, e);
+                return;
+            }
+        }
+        let current_config = self.detect_current_tc_config(namespace, interface).await;
+        if let Err(e) = self
+            .publish_tc_config(namespace, interface, current_config)
+            .await
+        {
+            warn!("Failed to publish restored TC config: {}", e);
+        }
+    }
+
     /// Set up event streams for container namespaces
     ///
     /// This creates EventStreams for each discovered container namespace,
@@ -1129,15 +1804,43 @@ impl TcBackend {
                     .filter(|&c| c > 0.0)
                     .map(|c| c as f32);
 
-                // Convert rate from bytes/sec to kbps
-                let rate_limit_kbps = netem_opts.rate_bps().map(|r| (r * 8 / 1000) as u32);
+                // Convert rate from bytes/sec to kbps. Without a netem rate,
+                // the netem may be the leaf of an HTB rate limit
+                let netem_rate_kbps = netem_opts.rate_bps().map(|r| (r * 8 / 1000) as u32);
+                let htb_rate_kbps = match netem_rate_kbps {
+                    Some(_) => None,
+                    None => self
+                        .tc_manager
+                        .get_htb_rate_kbps(namespace, interface)
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!(
+                                "Failed to detect HTB rate limit on {}:{}: {}",
+                                namespace, interface, e
+                            );
+                            None
+                        }),
+                };
+                let rate_limit_kbps = netem_rate_kbps.or(htb_rate_kbps);
+
+                // The kernel doesn't dump the distribution table; fall back
+                // on what this backend installed
+                let delay_distribution = jitter_ms.filter(|&j| j > 0.0).and_then(|_| {
+                    self.tc_manager
+                        .applied_state(namespace, interface)
+                        .distribution
+                });
 
                 Some(TcConfiguration {
                     loss: loss_pct as f32,
                     correlation,
+                    loss_gemodel: tc_commands::gemodel_of(&netem_opts),
+                    loss_ecn: netem_opts.ecn(),
+                    seed: self.tc_manager.applied_state(namespace, interface).seed,
                     delay_ms,
                     delay_jitter_ms: jitter_ms,
                     delay_correlation,
+                    delay_distribution,
                     duplicate_percent,
                     duplicate_correlation,
                     reorder_percent,
@@ -1146,6 +1849,12 @@ impl TcBackend {
                     corrupt_percent,
                     corrupt_correlation,
                     rate_limit_kbps,
+                    rate_limit_shaper: if htb_rate_kbps.is_some() {
+                        RateLimitMode::Htb
+                    } else {
+                        RateLimitMode::Netem
+                    },
+                    slot: tc_commands::slot_of(&netem_opts),
                     command: format!(
                         "# Detected via netlink: loss={:.1}% delay={:.2}ms",
                         loss_pct, delay_ms_val
@@ -1153,8 +1862,45 @@ impl TcBackend {
                 })
             }
             Ok(None) => {
-                // No netem qdisc found
-                None
+                // No netem qdisc - a TBF root qdisc means a rate-limit-only config
+                match self
+                    .tc_manager
+                    .get_tbf_rate_kbps(namespace, interface)
+                    .await
+                {
+                    Ok(Some(rate_kbps)) => Some(TcConfiguration {
+                        command: format!("# Detected via netlink: tbf rate={}kbit", rate_kbps),
+                        ..tc_config::configuration_of(
+                            interface,
+                            &tc_commands::rate_limit_only_config(rate_kbps),
+                        )
+                    }),
+                    // Neither netem nor TBF: a root queue-shaping qdisc has
+                    // no impairment to show, only its command
+                    Ok(None) => match self.tc_manager.get_queue_qdisc(namespace, interface).await {
+                        Ok(qdisc) => qdisc.map(|qdisc| TcConfiguration {
+                            command: format!(
+                                "# Detected via netlink: {}",
+                                qdisc.command(interface)
+                            ),
+                            ..TcConfiguration::default()
+                        }),
+                        Err(e) => {
+                            warn!(
+                                "Failed to detect queue qdisc on {}:{}: {}",
+                                namespace, interface, e
+                            );
+                            None
+                        }
+                    },
+                    Err(e) => {
+                        warn!(
+                            "Failed to detect TBF configuration on {}:{}: {}",
+                            namespace, interface, e
+                        );
+                        None
+                    }
+                }
             }
             Err(e) => {
                 warn!(
@@ -1176,6 +1922,16 @@ impl TcBackend {
     ) -> Result<()> {
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
         let backend_name = self.backend_name.clone();
+        if let Some(exporter) = &self.exporter {
+            export::write_shared(
+                exporter,
+                namespace,
+                interface,
+                export::ExportEventKind::tc_config(configuration.as_ref()),
+            );
+        }
+        self.config_history
+            .record(namespace, interface, timestamp, configuration.as_ref());
         let publisher = self.get_tc_config_publisher(namespace, interface).await?;
 
         match configuration {
@@ -1224,7 +1980,14 @@ impl TcBackend {
 #[instrument]
 async fn main() -> Result<()> {
     // Load configuration from CLI and environment
-    let config_manager = config::ConfigManager::from_cli_and_env()?;
+    let cli_config = config::CliConfig::from_args()?;
+    let config_manager = config::ConfigManager::from_cli(&cli_config)?;
+
+    // Dump before validating, so invalid configurations can be shared too
+    if cli_config.dump_config {
+        println!("{}", config_manager.to_redacted_json());
+        return Ok(());
+    }
 
     // Validate configuration
     config_manager.validate()?;
@@ -1243,20 +2006,14 @@ async fn main() -> Result<()> {
         "[BACKEND] Zenoh configuration - Mode: {:?}, Endpoints: {:?}",
         config_manager.zenoh.mode, config_manager.zenoh.endpoints
     );
-    if config_manager.app.exclude_loopback {
-        info!("[BACKEND] Loopback interface filtering enabled");
+    if !config_manager.app.exclude_patterns.is_empty() {
+        info!(
+            "[BACKEND] Excluding interfaces matching: {}",
+            config_manager.app.exclude_patterns.join(", ")
+        );
     }
 
-    let mut backend = TcBackend::new(
-        config_manager.app.exclude_loopback,
-        config_manager.app.backend_name.clone(),
-        config_manager.zenoh,
-        config_manager.app.scenario_dirs.clone(),
-        config_manager.app.no_default_scenarios,
-        config_manager.app.preset_dirs.clone(),
-        config_manager.app.no_default_presets,
-    )
-    .await?;
+    let mut backend = TcBackend::new(&config_manager.app, config_manager.zenoh).await?;
     backend.run().await?;
 
     Ok(())
@@ -0,0 +1,189 @@
+//! Detection of the interfaces carrying the backend's own Zenoh traffic.
+//!
+//! When the backend is reached over the network, impairing the interface
+//! its Zenoh session runs over can cut the GUI off from it. The configured
+//! endpoints tell which addresses the session uses:
+//!
+//! - a `listen/` endpoint on a concrete address is served by the interface
+//!   owning that address;
+//! - a `connect/` endpoint is reached through the interface the kernel routes
+//!   its address to ([`crate::network::NetworkManager`] asks the kernel), or,
+//!   failing that, through the interface whose subnet contains it.
+//!
+//! Endpoints naming a host or an unspecified address (`0.0.0.0`, `[::]`)
+//! can't be mapped to one interface and are ignored, as are sessions found
+//! through multicast scouting alone.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use tcgui_shared::{NetworkInterface, TcOperation};
+
+/// Where a configured Zenoh endpoint puts the backend's traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManagementEndpoint {
+    /// The backend accepts connections on this local address
+    Listen(IpAddr),
+    /// The backend connects out to this remote address
+    Connect(IpAddr),
+}
+
+impl ManagementEndpoint {
+    /// Parse a configured endpoint such as `connect/tcp/10.0.0.1:7447` or
+    /// `listen/udp/[fd00::1]:7447#iface=eth0`. Endpoints without a direction
+    /// are dialled, as in Zenoh's `connect` list. Returns `None` for
+    /// endpoints that don't name one concrete IP address.
+    pub fn parse(endpoint: &str) -> Option<Self> {
+        let (listen, locator) = match endpoint.split_once('/') {
+            Some(("listen", rest)) => (true, rest),
+            Some(("connect", rest)) => (false, rest),
+            _ => (false, endpoint),
+        };
+        let (_protocol, address) = locator.split_once('/')?;
+        // Strip locator metadata (`?...`) and endpoint config (`#...`)
+        let address = address.split(['?', '#']).next()?;
+        let ip = address.parse::<std::net::SocketAddr>().ok()?.ip();
+        if ip.is_unspecified() {
+            return None;
+        }
+        Some(if listen {
+            Self::Listen(ip)
+        } else {
+            Self::Connect(ip)
+        })
+    }
+
+    /// Parse every endpoint that can be mapped to an interface.
+    pub fn from_endpoints(endpoints: &[String]) -> Vec<Self> {
+        endpoints.iter().filter_map(|e| Self::parse(e)).collect()
+    }
+}
+
+/// Parse an interface address formatted as `"ip/prefix"`.
+fn parse_cidr(address: &str) -> Option<(IpAddr, u8)> {
+    let (ip, prefix) = address.split_once('/')?;
+    Some((ip.parse().ok()?, prefix.parse().ok()?))
+}
+
+/// Whether `ip` lies in the subnet `network/prefix`.
+fn in_subnet(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) if prefix <= 32 => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) if prefix <= 128 => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Index of the interface that has `ip` assigned.
+pub fn interface_with_address(
+    interfaces: &HashMap<u32, NetworkInterface>,
+    ip: IpAddr,
+) -> Option<u32> {
+    interfaces
+        .values()
+        .find(|interface| {
+            interface
+                .addresses
+                .iter()
+                .filter_map(|a| parse_cidr(a))
+                .any(|(address, _)| address == ip)
+        })
+        .map(|interface| interface.index)
+}
+
+/// Index of the interface whose subnet contains `ip`, preferring the
+/// longest prefix when subnets overlap.
+pub fn on_link_interface(interfaces: &HashMap<u32, NetworkInterface>, ip: IpAddr) -> Option<u32> {
+    interfaces
+        .values()
+        .flat_map(|interface| {
+            interface
+                .addresses
+                .iter()
+                .filter_map(|a| parse_cidr(a))
+                .filter(move |(network, prefix)| in_subnet(ip, *network, *prefix))
+                .map(move |(_, prefix)| (prefix, interface.index))
+        })
+        .max()
+        .map(|(_, index)| index)
+}
+
+/// Whether `operation` may be refused on a management interface: anything
+/// that installs impairment. Removing it only ever restores connectivity.
+pub fn impairs(operation: &TcOperation) -> bool {
+    !matches!(operation, TcOperation::Remove)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interface(name: &str, index: u32, addresses: &[&str]) -> NetworkInterface {
+        NetworkInterface {
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+            ..crate::test_support::interface(name, index, "default")
+        }
+    }
+
+    fn ip(address: &str) -> IpAddr {
+        address.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_endpoints() {
+        assert_eq!(
+            ManagementEndpoint::parse("connect/tcp/10.0.0.1:7447"),
+            Some(ManagementEndpoint::Connect(ip("10.0.0.1")))
+        );
+        assert_eq!(
+            ManagementEndpoint::parse("listen/udp/[fd00::5]:7447#iface=eth0"),
+            Some(ManagementEndpoint::Listen(ip("fd00::5")))
+        );
+        assert_eq!(
+            ManagementEndpoint::parse("tcp/192.168.1.2:7447"),
+            Some(ManagementEndpoint::Connect(ip("192.168.1.2")))
+        );
+        assert_eq!(ManagementEndpoint::parse("listen/tcp/0.0.0.0:7447"), None);
+        assert_eq!(ManagementEndpoint::parse("connect/tcp/router:7447"), None);
+        assert_eq!(ManagementEndpoint::parse("garbage"), None);
+    }
+
+    #[test]
+    fn test_listen_address_maps_to_owning_interface() {
+        let interfaces = HashMap::from([
+            (1, interface("lo", 1, &["127.0.0.1/8"])),
+            (2, interface("eth0", 2, &["10.0.0.5/24", "fe80::1/64"])),
+            (3, interface("eth1", 3, &["192.168.1.5/24"])),
+        ]);
+        assert_eq!(interface_with_address(&interfaces, ip("10.0.0.5")), Some(2));
+        assert_eq!(interface_with_address(&interfaces, ip("fe80::1")), Some(2));
+        assert_eq!(interface_with_address(&interfaces, ip("10.0.0.6")), None);
+    }
+
+    #[test]
+    fn test_connect_address_maps_to_longest_matching_subnet() {
+        let interfaces = HashMap::from([
+            (2, interface("eth0", 2, &["10.0.0.5/8"])),
+            (3, interface("eth1", 3, &["10.1.2.5/24"])),
+            (4, interface("eth2", 4, &["fd00::5/64"])),
+        ]);
+        assert_eq!(on_link_interface(&interfaces, ip("10.1.2.200")), Some(3));
+        assert_eq!(on_link_interface(&interfaces, ip("10.9.9.9")), Some(2));
+        assert_eq!(on_link_interface(&interfaces, ip("fd00::77")), Some(4));
+        assert_eq!(on_link_interface(&interfaces, ip("172.16.0.1")), None);
+    }
+
+    #[test]
+    fn test_only_removal_is_always_allowed() {
+        assert!(!impairs(&TcOperation::Remove));
+        assert!(impairs(&TcOperation::ApplyConfig {
+            config: Default::default()
+        }));
+    }
+}
@@ -18,11 +18,10 @@
 //!
 //! ```rust,no_run
 //! use tcgui_backend::netns::{run_in_namespace, NamespacePath};
-//! use std::path::PathBuf;
 //!
 //! # async fn example() -> anyhow::Result<()> {
-//! // Run code in a container's network namespace
-//! let ns_path = NamespacePath::Path(PathBuf::from("/proc/12345/ns/net"));
+//! // Run code in a named network namespace
+//! let ns_path = NamespacePath::Named("lab".to_string());
 //! let result = run_in_namespace(ns_path, || {
 //!     // Code here runs in the target namespace
 //!     std::fs::read_to_string("/proc/net/dev")
@@ -67,10 +66,6 @@ pub enum NamespaceError {
     /// Traditional namespace not found
     #[error("Network namespace '{0}' not found in /var/run/netns/")]
     TraditionalNamespaceNotFound(String),
-
-    /// Operation failed inside namespace
-    #[error("Operation failed inside namespace: {0}")]
-    OperationFailed(String),
 }
 
 /// Specifies how to locate a network namespace.
@@ -81,9 +76,6 @@ pub enum NamespacePath {
 
     /// A traditional named namespace (found in /var/run/netns/)
     Named(String),
-
-    /// A direct path to a namespace file (e.g., /proc/<pid>/ns/net)
-    Path(PathBuf),
 }
 
 impl NamespacePath {
@@ -102,14 +94,6 @@ impl NamespacePath {
                     Err(NamespaceError::TraditionalNamespaceNotFound(name.clone()))
                 }
             }
-
-            NamespacePath::Path(path) => {
-                if path.exists() {
-                    Ok(Some(path.clone()))
-                } else {
-                    Err(NamespaceError::NamespaceNotFound(path.clone()))
-                }
-            }
         }
     }
 }
@@ -255,80 +239,10 @@ where
     }
 }
 
-/// Reads /proc/net/dev in a specified namespace.
-///
-/// This is a common operation that reads network interface statistics.
-/// Returns the raw contents of /proc/net/dev.
-#[instrument(skip_all, fields(namespace = ?namespace))]
-pub async fn read_proc_net_dev(namespace: NamespacePath) -> Result<String, NamespaceError> {
-    run_in_namespace(namespace, || {
-        std::fs::read_to_string("/proc/net/dev").map_err(|e| e.to_string())
-    })
-    .await?
-    .map_err(NamespaceError::OperationFailed)
-}
-
-/// Lists network interfaces in a namespace by parsing /proc/net/dev.
-///
-/// Note: /sys/class/net reflects the initial mount namespace, not the current
-/// network namespace. We must use /proc/net/dev which is namespace-aware.
-///
-/// Returns a list of interface names.
-#[instrument(skip_all, fields(namespace = ?namespace))]
-pub async fn list_interfaces(namespace: NamespacePath) -> Result<Vec<String>, NamespaceError> {
-    run_in_namespace(namespace, || {
-        let mut interfaces = Vec::new();
-        // /proc/net/dev is namespace-aware, unlike /sys/class/net
-        if let Ok(content) = std::fs::read_to_string("/proc/net/dev") {
-            for line in content.lines().skip(2) {
-                // Lines are like: "  eth0: 12345 ..."
-                if let Some(name) = line.split(':').next() {
-                    let name = name.trim().to_string();
-                    if !name.is_empty() {
-                        interfaces.push(name);
-                    }
-                }
-            }
-        }
-        interfaces
-    })
-    .await
-}
-
-/// Discovers all named network namespaces from /var/run/netns/.
-///
-/// This replaces `ip netns list` with a direct filesystem read.
-pub fn discover_named_namespaces() -> Vec<String> {
-    let netns_dir = Path::new("/var/run/netns");
-    if !netns_dir.exists() {
-        return Vec::new();
-    }
-
-    let mut namespaces = Vec::new();
-    if let Ok(entries) = std::fs::read_dir(netns_dir) {
-        for entry in entries.flatten() {
-            if let Some(name) = entry.file_name().to_str() {
-                namespaces.push(name.to_string());
-            }
-        }
-    }
-
-    namespaces
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_discover_named_namespaces() {
-        // This test just verifies the function doesn't panic
-        // Actual namespaces depend on system state
-        let _namespaces = discover_named_namespaces();
-        // Should return empty or actual namespaces, never panic
-        // (test just verifies no panic occurs)
-    }
-
     #[tokio::test]
     async fn test_run_in_default_namespace() {
         // Running in default namespace should work without issues
@@ -338,28 +252,6 @@ mod tests {
         assert_eq!(result.unwrap(), 42);
     }
 
-    #[tokio::test]
-    async fn test_list_interfaces_default() {
-        // List interfaces in default namespace
-        let result = list_interfaces(NamespacePath::Default).await;
-        assert!(result.is_ok());
-
-        let interfaces = result.unwrap();
-        // Should at least have loopback
-        assert!(interfaces.contains(&"lo".to_string()));
-    }
-
-    #[tokio::test]
-    async fn test_read_proc_net_dev_default() {
-        let result = read_proc_net_dev(NamespacePath::Default).await;
-        assert!(result.is_ok());
-
-        let content = result.unwrap();
-        // Should contain header and at least lo interface
-        assert!(content.contains("Inter-"));
-        assert!(content.contains("lo:"));
-    }
-
     #[tokio::test]
     async fn test_nonexistent_namespace() {
         let result = run_in_namespace(
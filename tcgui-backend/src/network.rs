@@ -13,13 +13,20 @@
 //! * **Robust error handling**: Graceful handling of namespace access permissions
 
 use anyhow::Result;
+use nlink::netlink::link::VethLink;
+use nlink::netlink::namespace::NamespaceFd;
 use nlink::netlink::{Connection, Route, namespace};
 use std::collections::HashMap;
+use std::sync::RwLock;
 use std::time::Duration;
 use tracing::{debug, error, info, instrument, warn};
 use zenoh::Session;
-use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, CacheConfig, MissDetectionConfig};
+use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, MissDetectionConfig};
 
+use crate::interface_descriptions::InterfaceDescriptions;
+use crate::management::{self, ManagementEndpoint};
+use crate::qos::WithQos;
+use tcgui_shared::qos::{self, QosProfile};
 use tcgui_shared::registry::tc;
 use tcgui_shared::{
     InterfaceType, NetworkInterface,
@@ -38,6 +45,58 @@ fn interesting_qdisc_kind(kind: Option<String>) -> Option<String> {
     }
 }
 
+/// Whether a qdisc is one tcgui manages: netem anywhere, or a root TBF (the
+/// rate-limit-only fast path).
+fn is_managed_qdisc(kind: Option<&str>, is_root: bool) -> bool {
+    match kind {
+        Some("netem") => true,
+        Some("tbf") => is_root,
+        _ => false,
+    }
+}
+
+/// Discovery outcome for one namespace, keyed by namespace name.
+type NamespaceDiscovery<E> = (String, Result<HashMap<u32, NetworkInterface>, E>);
+
+/// Merge per-namespace discovery results into one interface map.
+///
+/// Each namespace gets a composite-key slot by position, whether or not its
+/// discovery succeeded, so a namespace that fails transiently does not shift
+/// the keys of the namespaces after it. Returns the merged interfaces and the
+/// namespaces that failed.
+fn merge_namespace_results<E: std::fmt::Display>(
+    results: Vec<NamespaceDiscovery<E>>,
+) -> (HashMap<u32, NetworkInterface>, Vec<String>) {
+    let mut all_interfaces = HashMap::new();
+    let mut failed = Vec::new();
+
+    for (namespace_id, (namespace, result)) in results.into_iter().enumerate() {
+        match result {
+            Ok(interfaces) => {
+                info!(
+                    "Found {} interfaces in namespace '{}'",
+                    interfaces.len(),
+                    namespace
+                );
+                for (index, interface) in interfaces {
+                    // Use a composite key to avoid index conflicts between namespaces
+                    let composite_key = index + (namespace_id as u32 * 1000000);
+                    all_interfaces.insert(composite_key, interface);
+                }
+            }
+            Err(e) => {
+                error!(
+                    "Failed to discover interfaces in namespace {}: {}",
+                    namespace, e
+                );
+                failed.push(namespace);
+            }
+        }
+    }
+
+    (all_interfaces, failed)
+}
+
 /// Network interface manager for multi-namespace operations.
 ///
 /// This struct provides comprehensive network interface management across
@@ -89,6 +148,15 @@ pub struct NetworkManager {
     /// Cache of last discovered containers for namespace type lookup
     /// Key is "container:<name>" to match namespace naming
     cached_containers: std::sync::Arc<tokio::sync::RwLock<HashMap<String, Container>>>,
+    /// Namespaces whose discovery failed during the last full refresh
+    failed_namespaces: RwLock<Vec<String>>,
+    /// Endpoints of the backend's Zenoh session, used to flag the interfaces
+    /// carrying its traffic
+    management_endpoints: Vec<ManagementEndpoint>,
+    /// Operator descriptions attached to discovered interfaces
+    interface_descriptions: InterfaceDescriptions,
+    /// QoS of the per-interface state publishers
+    qos: QosProfile,
 }
 
 impl NetworkManager {
@@ -139,9 +207,59 @@ impl NetworkManager {
             interface_publishers: HashMap::new(),
             container_manager,
             cached_containers,
+            failed_namespaces: RwLock::new(Vec::new()),
+            management_endpoints: Vec::new(),
+            interface_descriptions: InterfaceDescriptions::default(),
+            qos: qos::INTERFACE_LIST,
         })
     }
 
+    /// Set the Zenoh endpoints whose interfaces are flagged as management
+    /// interfaces on discovery.
+    pub fn set_management_endpoints(&mut self, endpoints: Vec<ManagementEndpoint>) {
+        self.management_endpoints = endpoints;
+    }
+
+    /// Set the operator descriptions attached to interfaces on discovery.
+    pub fn set_interface_descriptions(&mut self, descriptions: InterfaceDescriptions) {
+        self.interface_descriptions = descriptions;
+    }
+
+    /// Set the QoS of the per-interface state publishers declared from now on.
+    pub fn set_qos(&mut self, qos: QosProfile) {
+        self.qos = qos;
+    }
+
+    /// Flag the default-namespace interfaces that carry the Zenoh session.
+    /// A `connect` endpoint is resolved with a kernel route lookup, falling
+    /// back to the on-link subnet when the lookup fails.
+    async fn mark_management_interfaces(&self, interfaces: &mut HashMap<u32, NetworkInterface>) {
+        for endpoint in &self.management_endpoints {
+            let index = match *endpoint {
+                ManagementEndpoint::Listen(ip) => {
+                    management::interface_with_address(interfaces, ip)
+                }
+                ManagementEndpoint::Connect(ip) => {
+                    let routed = match ip {
+                        std::net::IpAddr::V4(v4) => self.connection.get_route_v4(v4, 32).await,
+                        std::net::IpAddr::V6(v6) => self.connection.get_route_v6(v6, 128).await,
+                    };
+                    match routed {
+                        Ok(Some(route)) if route.oif().is_some() => route.oif(),
+                        Ok(_) => management::on_link_interface(interfaces, ip),
+                        Err(e) => {
+                            debug!("Route lookup for {} failed: {}", ip, e);
+                            management::on_link_interface(interfaces, ip)
+                        }
+                    }
+                }
+            };
+            if let Some(interface) = index.and_then(|index| interfaces.get_mut(&index)) {
+                interface.management = true;
+            }
+        }
+    }
+
     /// Returns a reference to the container cache for sharing with other components.
     ///
     /// This allows components like BandwidthMonitor to access container namespace paths
@@ -224,9 +342,15 @@ impl NetworkManager {
                     addresses: addr_map.get(&index).cloned().unwrap_or_default(),
                     qdisc_kind,
                     link_speed_mbps: speed_map.get(&name).copied(),
+                    mtu: link.mtu(),
+                    peer_index: Self::veth_peer_index(&link),
+                    management: false,
+                    description: None,
                 },
             );
         }
+        self.mark_management_interfaces(&mut discovered_interfaces)
+            .await;
 
         Ok(discovered_interfaces)
     }
@@ -278,6 +402,10 @@ impl NetworkManager {
                     addresses: addr_map.get(&index).cloned().unwrap_or_default(),
                     qdisc_kind,
                     link_speed_mbps: None,
+                    mtu: link.mtu(),
+                    peer_index: Self::veth_peer_index(&link),
+                    management: false,
+                    description: None,
                 },
             );
         }
@@ -343,6 +471,16 @@ impl NetworkManager {
     }
 
     /// Determine interface type from name and link message
+    /// Peer ifindex of a veth (IFLA_LINK). Other kinds use IFLA_LINK for
+    /// their lower device, which is not a pairing.
+    fn veth_peer_index(link: &nlink::netlink::messages::LinkMessage) -> Option<u32> {
+        if link.kind() == Some("veth") {
+            link.link()
+        } else {
+            None
+        }
+    }
+
     fn determine_interface_type(
         name: &str,
         link: &nlink::netlink::messages::LinkMessage,
@@ -390,21 +528,15 @@ impl NetworkManager {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to get qdiscs for {}: {}", interface, e))?;
 
-        // Check if any of the qdiscs is a netem qdisc
-        for qdisc in qdiscs {
-            if let Some(kind) = qdisc.kind()
-                && kind == "netem"
-            {
-                return Ok(true);
-            }
-        }
-
-        Ok(false)
+        // Check if any of the qdiscs is one we manage
+        Ok(qdiscs
+            .iter()
+            .any(|qdisc| is_managed_qdisc(qdisc.kind(), qdisc.parent().is_root())))
     }
 
     /// Inspect an interface's qdiscs in a single query, returning both whether a
-    /// netem qdisc is present (drives `has_tc_qdisc`, preserving its prior
-    /// meaning) and the root qdisc kind for display (filtered to drop plain
+    /// managed qdisc is present (drives `has_tc_qdisc`: netem, or the root TBF
+    /// of the rate-limit-only fast path) and the root qdisc kind for display (filtered to drop plain
     /// kernel-default qdiscs that carry no user intent).
     async fn qdisc_info(
         &self,
@@ -420,7 +552,7 @@ impl NetworkManager {
         let mut root_kind = None;
         for qdisc in qdiscs {
             let kind = qdisc.kind().map(|k| k.to_string());
-            if kind.as_deref() == Some("netem") {
+            if is_managed_qdisc(kind.as_deref(), qdisc.parent().is_root()) {
                 is_netem = true;
             }
             if qdisc.parent().is_root() {
@@ -443,7 +575,8 @@ impl NetworkManager {
     ///
     /// # Returns
     ///
-    /// `true` if the interface has a netem qdisc, `false` otherwise
+    /// `true` if the interface has a netem qdisc (or a root TBF rate limit),
+    /// `false` otherwise
     pub async fn check_interface_has_netem(
         &self,
         namespace: &str,
@@ -653,6 +786,10 @@ impl NetworkManager {
                     addresses: addr_map.get(&index).cloned().unwrap_or_default(),
                     qdisc_kind,
                     link_speed_mbps: None,
+                    mtu: link.mtu(),
+                    peer_index: Self::veth_peer_index(&link),
+                    management: false,
+                    description: None,
                 },
             );
         }
@@ -671,32 +808,12 @@ impl NetworkManager {
                 .map_err(|e| BackendError::NetworkError {
                     message: format!("Failed to discover namespaces: {}", e),
                 })?;
-        let mut all_interfaces = HashMap::new();
-
-        let mut namespace_id = 0u32;
+        let mut results = Vec::with_capacity(namespaces.len());
         for namespace in namespaces {
-            match self.discover_interfaces_in_namespace(&namespace).await {
-                Ok(interfaces) => {
-                    info!(
-                        "Found {} interfaces in namespace '{}'",
-                        interfaces.len(),
-                        namespace
-                    );
-                    for (index, interface) in interfaces {
-                        // Use a composite key to avoid index conflicts between namespaces
-                        let composite_key = index + (namespace_id * 1000000);
-                        all_interfaces.insert(composite_key, interface);
-                    }
-                    namespace_id += 1;
-                }
-                Err(e) => {
-                    error!(
-                        "Failed to discover interfaces in namespace {}: {}",
-                        namespace, e
-                    );
-                }
-            }
+            let result = self.discover_interfaces_in_namespace(&namespace).await;
+            results.push((namespace, result));
         }
+        let (mut all_interfaces, mut failed) = merge_namespace_results(results);
 
         // Also discover container interfaces
         let containers = self.discover_containers().await;
@@ -724,10 +841,24 @@ impl NetworkManager {
                         "Failed to discover interfaces in container {}: {}",
                         container.name, e
                     );
+                    failed.push(format!("container:{}", container.name));
                 }
             }
         }
 
+        if !failed.is_empty() {
+            warn!(
+                "Interface discovery incomplete, {} namespace(s) failed: {}",
+                failed.len(),
+                failed.join(", ")
+            );
+        }
+        *self
+            .failed_namespaces
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = failed;
+        self.interface_descriptions.apply(&mut all_interfaces);
+
         info!(
             "Discovered {} interfaces across all namespaces and containers",
             all_interfaces.len()
@@ -735,6 +866,18 @@ impl NetworkManager {
         Ok(all_interfaces)
     }
 
+    /// Namespaces whose discovery failed during the last full refresh.
+    ///
+    /// Interfaces from these namespaces are missing from the last result of
+    /// [`discover_all_interfaces`](Self::discover_all_interfaces); callers can
+    /// use this to keep the previously known interfaces instead of removing them.
+    pub fn failed_namespaces(&self) -> Vec<String> {
+        self.failed_namespaces
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
     /// Reconciles the published per-interface state against `interfaces`.
     ///
     /// This is the single interface-state reconciliation feed (keyspace-v2): it
@@ -810,7 +953,8 @@ impl NetworkManager {
             let publisher = self
                 .session
                 .declare_publisher(zenoh::key_expr::OwnedKeyExpr::from(topic))
-                .cache(CacheConfig::default().max_samples(1))
+                .advanced()
+                .with_qos(&self.qos)
                 .sample_miss_detection(
                     MissDetectionConfig::default().heartbeat(Duration::from_millis(500)),
                 )
@@ -927,4 +1071,383 @@ impl NetworkManager {
 
         Ok(())
     }
+
+    /// Sets the MTU of a network interface and returns the MTU it ended up
+    /// with. The MTU is validated before any netlink call.
+    #[instrument(skip(self), fields(backend_name = %self.backend_name, namespace, interface))]
+    pub async fn set_interface_mtu(
+        &self,
+        namespace: &str,
+        interface: &str,
+        mtu: u32,
+    ) -> Result<u32> {
+        tcgui_shared::validation::validate_mtu(mtu).map_err(|e| anyhow::anyhow!(e))?;
+        info!(
+            "Setting MTU of interface {} in namespace {} to {}",
+            interface, namespace, mtu
+        );
+
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        conn.set_link_mtu(interface, mtu)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to set MTU: {}", e))?;
+
+        let mtu = Self::link_mtu(conn, interface).await?;
+        info!(
+            "MTU of interface {} in namespace {} is now {}",
+            interface, namespace, mtu
+        );
+        Ok(mtu)
+    }
+
+    /// Reads the MTU of a network interface.
+    #[instrument(skip(self), fields(backend_name = %self.backend_name, namespace, interface))]
+    pub async fn get_interface_mtu(&self, namespace: &str, interface: &str) -> Result<u32> {
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        Self::link_mtu(conn, interface).await
+    }
+
+    /// Renames a network interface. The kernel only renames a link that is
+    /// down, so an interface that is up is brought down first and back up
+    /// under its new name; it is also brought back up under its old name if
+    /// the rename fails. The name is validated before any netlink call.
+    #[instrument(skip(self), fields(backend_name = %self.backend_name, namespace, interface))]
+    pub async fn rename_interface(
+        &self,
+        namespace: &str,
+        interface: &str,
+        new_name: &str,
+    ) -> Result<()> {
+        tcgui_shared::validation::validate_new_interface_name(new_name)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        info!(
+            "Renaming interface {} in namespace {} to {}",
+            interface, namespace, new_name
+        );
+
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        let was_up = conn
+            .get_link_by_name(interface)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get interface {}: {}", interface, e))?
+            .ok_or_else(|| anyhow::anyhow!("Interface {} not found", interface))?
+            .is_up();
+        if conn
+            .get_link_by_name(new_name)
+            .await
+            .ok()
+            .flatten()
+            .is_some()
+        {
+            return Err(anyhow::anyhow!("Interface {} already exists", new_name));
+        }
+
+        if was_up {
+            conn.set_link_down(interface)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to bring interface down: {}", e))?;
+        }
+        let renamed = conn.set_link_name(interface, new_name).await;
+        let current = if renamed.is_ok() { new_name } else { interface };
+        if was_up && let Err(e) = conn.set_link_up(current).await {
+            warn!(
+                "Failed to bring interface {} in namespace {} back up: {}",
+                current, namespace, e
+            );
+        }
+        renamed.map_err(|e| anyhow::anyhow!("Failed to rename interface: {}", e))?;
+
+        info!(
+            "Renamed interface {} in namespace {} to {}",
+            interface, namespace, new_name
+        );
+        Ok(())
+    }
+
+    /// Creates a veth pair in `namespace`, with the `name_b` end created in
+    /// `ns_b` when given. Neither name may already be taken where its end
+    /// goes.
+    #[instrument(skip(self), fields(backend_name = %self.backend_name, namespace))]
+    pub async fn create_veth_pair(
+        &self,
+        namespace: &str,
+        name_a: &str,
+        name_b: &str,
+        ns_b: Option<&str>,
+    ) -> Result<()> {
+        let ns_b = ns_b.unwrap_or(namespace);
+        self.ensure_link_absent(namespace, name_a).await?;
+        self.ensure_link_absent(ns_b, name_b).await?;
+
+        let mut veth = VethLink::new(name_a, name_b);
+        // Held open until the kernel has created the peer in it
+        let peer_netns = if ns_b != namespace {
+            let fd = self.namespace_fd(ns_b).await?;
+            veth = veth.peer_netns_fd(fd.as_raw_fd());
+            Some(fd)
+        } else {
+            None
+        };
+
+        info!(
+            "Creating veth pair {}/{} <-> {}/{}",
+            namespace, name_a, ns_b, name_b
+        );
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        let created = conn.add_link(veth).await;
+        drop(peer_netns);
+        created.map_err(|e| {
+            if e.is_already_exists() {
+                anyhow::anyhow!("Interface {} or {} already exists", name_a, name_b)
+            } else {
+                anyhow::anyhow!("Failed to create veth pair {}/{}: {}", name_a, name_b, e)
+            }
+        })
+    }
+
+    /// Deletes a link from `namespace`; for a veth, both ends go.
+    #[instrument(skip(self), fields(backend_name = %self.backend_name, namespace))]
+    pub async fn delete_link(&self, namespace: &str, name: &str) -> Result<()> {
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+
+        info!("Deleting link {}/{}", namespace, name);
+        conn.del_link(name).await.map_err(|e| {
+            if e.is_not_found() {
+                anyhow::anyhow!("Interface {} not found in namespace {}", name, namespace)
+            } else {
+                anyhow::anyhow!("Failed to delete link {}: {}", name, e)
+            }
+        })
+    }
+
+    /// Assigns `cidr` to an interface and returns the addresses it then has.
+    /// An address already on the interface, with any prefix, is refused.
+    #[instrument(skip(self), fields(backend_name = %self.backend_name, namespace, interface))]
+    pub async fn add_interface_address(
+        &self,
+        namespace: &str,
+        interface: &str,
+        cidr: &str,
+    ) -> Result<Vec<String>> {
+        let (address, prefix) =
+            tcgui_shared::validation::parse_cidr(cidr).map_err(|e| anyhow::anyhow!(e))?;
+        let cidr = format!("{}/{}", address, prefix);
+
+        let current = self.interface_addresses(namespace, interface).await?;
+        let ip = address.to_string();
+        if let Some(existing) = current
+            .iter()
+            .find(|a| a.split_once('/').is_some_and(|(a, _)| a == ip))
+        {
+            return Err(anyhow::anyhow!(
+                "Address {} is already assigned to {} ({})",
+                ip,
+                interface,
+                existing
+            ));
+        }
+
+        info!(
+            "Adding address {} to interface {} in namespace {}",
+            cidr, interface, namespace
+        );
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        conn.add_address_by_name(interface, address, prefix)
+            .await
+            .map_err(|e| {
+                if e.is_already_exists() {
+                    anyhow::anyhow!("Address {} is already assigned to {}", cidr, interface)
+                } else {
+                    anyhow::anyhow!("Failed to add address {}: {}", cidr, e)
+                }
+            })?;
+        self.interface_addresses(namespace, interface).await
+    }
+
+    /// Removes `cidr` from an interface and returns the addresses it then has.
+    #[instrument(skip(self), fields(backend_name = %self.backend_name, namespace, interface))]
+    pub async fn remove_interface_address(
+        &self,
+        namespace: &str,
+        interface: &str,
+        cidr: &str,
+    ) -> Result<Vec<String>> {
+        let (address, prefix) =
+            tcgui_shared::validation::parse_cidr(cidr).map_err(|e| anyhow::anyhow!(e))?;
+        let cidr = format!("{}/{}", address, prefix);
+
+        let current = self.interface_addresses(namespace, interface).await?;
+        if !current.contains(&cidr) {
+            return Err(anyhow::anyhow!(
+                "Address {} is not assigned to {}",
+                cidr,
+                interface
+            ));
+        }
+
+        info!(
+            "Removing address {} from interface {} in namespace {}",
+            cidr, interface, namespace
+        );
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        conn.del_address(interface, address, prefix)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to remove address {}: {}", cidr, e))?;
+        self.interface_addresses(namespace, interface).await
+    }
+
+    /// The addresses assigned to an interface, as `"ip/prefix"`
+    async fn interface_addresses(&self, namespace: &str, interface: &str) -> Result<Vec<String>> {
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        let ifindex = conn
+            .get_link_by_name(interface)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get interface {}: {}", interface, e))?
+            .ok_or_else(|| anyhow::anyhow!("Interface {} not found", interface))?
+            .ifindex();
+        Ok(Self::address_map(conn)
+            .await
+            .remove(&ifindex)
+            .unwrap_or_default())
+    }
+
+    /// Fails with a descriptive error when `name` already exists in `namespace`.
+    async fn ensure_link_absent(&self, namespace: &str, name: &str) -> Result<()> {
+        let namespace_connection = self.namespace_connection(namespace).await?;
+        let conn = namespace_connection.as_ref().unwrap_or(&self.connection);
+        let existing = conn
+            .get_link_by_name(name)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to look up interface {}: {}", name, e))?;
+        if existing.is_some() {
+            return Err(anyhow::anyhow!(
+                "Interface {} already exists in namespace {}",
+                name,
+                namespace
+            ));
+        }
+        Ok(())
+    }
+
+    async fn link_mtu(conn: &Connection<Route>, interface: &str) -> Result<u32> {
+        conn.get_link_by_name(interface)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get interface {}: {}", interface, e))?
+            .ok_or_else(|| anyhow::anyhow!("Interface {} not found", interface))?
+            .mtu()
+            .ok_or_else(|| anyhow::anyhow!("Interface {} reports no MTU", interface))
+    }
+
+    /// A netlink connection inside `namespace`, or `None` for the default
+    /// namespace, which uses the manager's own connection.
+    async fn namespace_connection(&self, namespace: &str) -> Result<Option<Connection<Route>>> {
+        if namespace == "default" {
+            return Ok(None);
+        }
+        if let Some(container_name) = namespace.strip_prefix("container:") {
+            let cache = self.cached_containers.read().await;
+            let container = cache.get(namespace).ok_or_else(|| {
+                anyhow::anyhow!("Container {} not found in cache", container_name)
+            })?;
+            let ns_path = container.namespace_path.as_ref().ok_or_else(|| {
+                anyhow::anyhow!("Container {} has no namespace path", container_name)
+            })?;
+            return Connection::<Route>::new_in_namespace_path(ns_path)
+                .map(Some)
+                .map_err(|e| anyhow::anyhow!("Failed to connect to container namespace: {}", e));
+        }
+        namespace::connection_for(namespace)
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("Failed to connect to namespace: {}", e))
+    }
+
+    /// An open handle on `namespace`'s network namespace, for creating a
+    /// link end in it. The default namespace is the backend's own.
+    async fn namespace_fd(&self, namespace: &str) -> Result<NamespaceFd> {
+        let fd = if namespace == "default" {
+            namespace::open_pid(std::process::id())
+        } else if let Some(container_name) = namespace.strip_prefix("container:") {
+            let cache = self.cached_containers.read().await;
+            let ns_path = cache
+                .get(namespace)
+                .and_then(|container| container.namespace_path.clone())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Container {} has no namespace path", container_name)
+                })?;
+            namespace::open_path(ns_path)
+        } else {
+            namespace::open(namespace)
+        };
+        fd.map_err(|e| anyhow::anyhow!("Failed to open namespace {}: {}", namespace, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::interface;
+
+    #[test]
+    fn test_merge_namespace_results_isolates_failures() {
+        let default_ns = HashMap::from([
+            (1, interface("lo", 1, "default")),
+            (2, interface("eth0", 2, "default")),
+        ]);
+        let ns_b = HashMap::from([(1, interface("veth-b", 1, "ns-b"))]);
+
+        let results = vec![
+            ("default".to_string(), Ok(default_ns)),
+            (
+                "ns-a".to_string(),
+                Err(anyhow::anyhow!("Permission denied")),
+            ),
+            ("ns-b".to_string(), Ok(ns_b)),
+        ];
+
+        let (interfaces, failed) = merge_namespace_results(results);
+
+        assert_eq!(failed, vec!["ns-a".to_string()]);
+        assert_eq!(interfaces.len(), 3);
+        assert!(interfaces.values().any(|i| i.name == "eth0"));
+        assert!(interfaces.values().any(|i| i.name == "veth-b"));
+    }
+
+    #[test]
+    fn test_merge_namespace_results_keeps_key_slots_stable() {
+        let ns_b = HashMap::from([(7, interface("veth-b", 7, "ns-b"))]);
+
+        let results = vec![
+            ("default".to_string(), Err(anyhow::anyhow!("transient"))),
+            ("ns-a".to_string(), Err(anyhow::anyhow!("transient"))),
+            ("ns-b".to_string(), Ok(ns_b)),
+        ];
+
+        let (interfaces, failed) = merge_namespace_results(results);
+
+        assert_eq!(failed.len(), 2);
+        // ns-b keeps its third slot even though the earlier namespaces failed
+        assert_eq!(
+            interfaces.get(&2000007).map(|i| i.name.as_str()),
+            Some("veth-b")
+        );
+    }
+
+    #[test]
+    fn test_is_managed_qdisc() {
+        assert!(is_managed_qdisc(Some("netem"), true));
+        assert!(is_managed_qdisc(Some("netem"), false));
+        assert!(is_managed_qdisc(Some("tbf"), true));
+        // A TBF child (e.g. under HTB) is not the rate-limit fast path
+        assert!(!is_managed_qdisc(Some("tbf"), false));
+        assert!(!is_managed_qdisc(Some("fq_codel"), true));
+        assert!(!is_managed_qdisc(None, true));
+    }
 }
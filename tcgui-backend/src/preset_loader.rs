@@ -3,6 +3,9 @@
 //! This module provides functionality to scan directories for `.json5` preset files,
 //! parse them, and load them into the system. It supports multiple source directories
 //! with priority ordering (user presets can override system ones).
+//!
+//! Profiles of other tools (`*.comcast`, `*.toxiproxy.json`) found in the same
+//! directories are imported as presets; see [`tcgui_shared::preset_import`].
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -11,6 +14,7 @@ use anyhow::{Context, Result};
 use tracing::{debug, info, warn};
 
 use tcgui_shared::TcNetemConfig;
+use tcgui_shared::preset_import::{ImportFormat, import_preset_file};
 use tcgui_shared::preset_json::{CustomPreset, parse_preset_file};
 use tcgui_shared::presets::PresetList;
 use tcgui_shared::scenario_json::PresetResolver;
@@ -180,6 +184,26 @@ impl PresetLoader {
                 entry.with_context(|| format!("Failed to read directory entry in {:?}", dir))?;
             let path = entry.path();
 
+            // Profiles of other tools are imported
+            if let Some((format, id)) = ImportFormat::from_path(&path) {
+                match import_preset_file(&path, format, &id) {
+                    Ok(imported) => {
+                        for setting in &imported.unsupported {
+                            warn!("Preset {:?}: not imported: {}", path, setting);
+                        }
+                        presets.push(imported.preset);
+                    }
+                    Err(e) => {
+                        warn!("Failed to import preset from {:?}: {}", path, e);
+                        errors.push(PresetLoadError {
+                            file_path: path.display().to_string(),
+                            error: e.to_string(),
+                        });
+                    }
+                }
+                continue;
+            }
+
             // Only process .json5 files
             if path.extension().and_then(|e| e.to_str()) != Some("json5") {
                 continue;
@@ -224,7 +248,10 @@ impl PresetLoader {
                 && let Ok(entries) = std::fs::read_dir(dir)
             {
                 for entry in entries.flatten() {
-                    if entry.path().extension().and_then(|e| e.to_str()) == Some("json5") {
+                    let path = entry.path();
+                    if path.extension().and_then(|e| e.to_str()) == Some("json5")
+                        || ImportFormat::from_path(&path).is_some()
+                    {
                         return true;
                     }
                 }
@@ -388,6 +415,36 @@ mod tests {
         assert_eq!(presets[0].id, "valid");
     }
 
+    #[test]
+    fn test_import_other_tools_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("mobile.comcast"),
+            "--latency=250 --packet-loss=2% --target-proto=tcp\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("redis.toxiproxy.json"),
+            r#"{"name": "Redis", "toxics": [{"type": "bandwidth", "attributes": {"rate": 100}}]}"#,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("empty.comcast"), "--device eth0").unwrap();
+
+        let loader = PresetLoader::with_directories(vec![temp_dir.path().to_path_buf()]);
+        let (mut presets, errors) = loader.load_all_with_errors();
+        presets.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(presets.len(), 2);
+        assert_eq!(presets[0].id, "mobile");
+        assert_eq!(presets[0].config.delay.base_ms, 250.0);
+        assert_eq!(presets[0].config.loss.percentage, 2.0);
+        assert_eq!(presets[1].id, "redis");
+        assert_eq!(presets[1].name, "Redis");
+        assert_eq!(presets[1].config.rate_limit.rate_kbps, 800);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].file_path.ends_with("empty.comcast"));
+    }
+
     #[test]
     fn test_skip_invalid_presets() {
         let temp_dir = TempDir::new().unwrap();
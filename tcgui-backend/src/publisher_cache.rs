@@ -0,0 +1,163 @@
+//! Bounded cache of per-interface publishers.
+//!
+//! The backend keeps one TC config publisher per `namespace/interface`. Stale
+//! entries are dropped when the interface list is refreshed, but under heavy
+//! churn (containers created and destroyed in a loop) that cleanup can lag
+//! behind. [`PublisherCache`] caps the number of entries and evicts the least
+//! recently used one when full; callers recreate an evicted publisher on its
+//! next use, so eviction only costs a re-declaration.
+
+use std::collections::HashMap;
+
+/// Default cap on cached TC config publishers.
+pub const MAX_TC_CONFIG_PUBLISHERS: usize = 1024;
+
+#[derive(Debug)]
+struct Entry<P> {
+    value: P,
+    last_used: u64,
+}
+
+/// Map from key to publisher with a hard capacity and LRU eviction.
+#[derive(Debug)]
+pub struct PublisherCache<P> {
+    capacity: usize,
+    entries: HashMap<String, Entry<P>>,
+    /// Monotonic use counter, bumped on every insert and lookup
+    clock: u64,
+}
+
+impl<P> PublisherCache<P> {
+    /// Create a cache holding at most `capacity` publishers (at least one).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Look up a publisher, marking it as recently used.
+    pub fn get(&mut self, key: &str) -> Option<&P> {
+        let now = self.tick();
+        self.entries.get_mut(key).map(|entry| {
+            entry.last_used = now;
+            &entry.value
+        })
+    }
+
+    /// Insert a publisher as the most recently used one. When the cache is
+    /// full, the least recently used entry is evicted and returned.
+    pub fn insert(&mut self, key: String, value: P) -> Option<(String, P)> {
+        let now = self.tick();
+        let evicted = if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+                .and_then(|oldest| {
+                    self.entries
+                        .remove(&oldest)
+                        .map(|entry| (oldest, entry.value))
+                })
+        } else {
+            None
+        };
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                last_used: now,
+            },
+        );
+        evicted
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<P> {
+        self.entries.remove(key).map(|entry| entry.value)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.keys()
+    }
+}
+
+impl<P> Default for PublisherCache<P> {
+    fn default() -> Self {
+        Self::new(MAX_TC_CONFIG_PUBLISHERS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get-or-create as the backend does it: a missing entry is recreated.
+    fn use_publisher(cache: &mut PublisherCache<String>, key: &str, created: &mut usize) -> String {
+        if !cache.contains_key(key) {
+            *created += 1;
+            cache.insert(key.to_string(), format!("publisher for {key}"));
+        }
+        cache.get(key).unwrap().clone()
+    }
+
+    #[test]
+    fn test_churn_stays_bounded() {
+        let mut cache = PublisherCache::new(16);
+        let mut created = 0;
+
+        // Thousands of short-lived container interfaces, with no cleanup
+        for i in 0..5_000 {
+            let key = format!("ctr-{i}/eth0");
+            assert_eq!(
+                use_publisher(&mut cache, &key, &mut created),
+                format!("publisher for {key}")
+            );
+            assert!(cache.keys().count() <= 16);
+        }
+        assert_eq!(cache.keys().count(), 16);
+        assert_eq!(created, 5_000);
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let mut cache = PublisherCache::new(2);
+        let mut created = 0;
+
+        use_publisher(&mut cache, "default/eth0", &mut created);
+        use_publisher(&mut cache, "default/eth1", &mut created);
+        // eth0 is used again, so eth1 is now the oldest
+        use_publisher(&mut cache, "default/eth0", &mut created);
+
+        let evicted = cache.insert("default/eth2".to_string(), "eth2".to_string());
+        assert_eq!(evicted.map(|(key, _)| key).as_deref(), Some("default/eth1"));
+        assert!(cache.contains_key("default/eth0"));
+
+        // An evicted publisher is transparently recreated on its next use
+        assert_eq!(
+            use_publisher(&mut cache, "default/eth1", &mut created),
+            "publisher for default/eth1"
+        );
+        assert_eq!(created, 3);
+        assert_eq!(cache.keys().count(), 2);
+    }
+
+    #[test]
+    fn test_reinserting_existing_key_does_not_evict() {
+        let mut cache = PublisherCache::new(1);
+        assert!(cache.insert("a".to_string(), 1).is_none());
+        assert!(cache.insert("a".to_string(), 2).is_none());
+        assert_eq!(cache.get("a"), Some(&2));
+        assert_eq!(cache.remove("a"), Some(2));
+        assert_eq!(cache.keys().count(), 0);
+    }
+}
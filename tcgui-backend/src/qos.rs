@@ -0,0 +1,100 @@
+//! Applying the configured Zenoh QoS when declaring publishers.
+//!
+//! Each published message type has a [`QosProfile`] (see `--qos`):
+//!
+//! - `interface_list`: per-interface state documents
+//! - `bandwidth_updates`: bandwidth telemetry
+//! - `interface_events`: TC configuration changes on interfaces
+//! - `backend_health`: the health document (a plain put, so only its
+//!   reliability and congestion control apply)
+
+use tcgui_shared::qos::QosProfile;
+use zenoh_ext::{AdvancedPublisherBuilder, CacheConfig};
+
+/// Configure a publisher builder from a [`QosProfile`].
+pub trait WithQos {
+    /// Set the profile's reliability and congestion control, and cache its
+    /// history depth for late joiners.
+    fn with_qos(self, profile: &QosProfile) -> Self;
+}
+
+impl WithQos for AdvancedPublisherBuilder<'_, '_, '_> {
+    fn with_qos(self, profile: &QosProfile) -> Self {
+        let builder = self
+            .reliability(profile.reliability)
+            .congestion_control(profile.congestion_control);
+        match profile.history_depth {
+            Some(depth) => builder.cache(CacheConfig::default().max_samples(depth)),
+            None => builder,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tcgui_shared::qos::QosProfiles;
+    use zenoh::Wait;
+    use zenoh::qos::CongestionControl;
+    use zenoh_ext::{
+        AdvancedPublisherBuilderExt, AdvancedSubscriberBuilderExt, HistoryConfig,
+        MissDetectionConfig,
+    };
+
+    fn isolated_session() -> zenoh::Session {
+        let mut config = zenoh::Config::default();
+        config
+            .insert_json5("scouting/multicast/enabled", "false")
+            .unwrap();
+        config.insert_json5("listen/endpoints", "[]").unwrap();
+        zenoh::open(config).wait().unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_configured_qos_reaches_the_publisher() {
+        let session = isolated_session();
+        let profiles = QosProfiles::with_overrides([
+            "bandwidth_updates=reliable,block,none",
+            "interface_events=reliable,drop,3",
+        ])
+        .unwrap();
+
+        let bandwidth = session
+            .declare_publisher("test/qos/bandwidth")
+            .advanced()
+            .with_qos(&profiles.bandwidth_updates)
+            .await
+            .unwrap();
+        assert_eq!(bandwidth.congestion_control(), CongestionControl::Block);
+
+        let events = session
+            .declare_publisher("test/qos/events")
+            .advanced()
+            .with_qos(&profiles.interface_events)
+            .sample_miss_detection(MissDetectionConfig::default())
+            .publisher_detection()
+            .await
+            .unwrap();
+        assert_eq!(events.congestion_control(), CongestionControl::Drop);
+
+        // The history depth is what a late joiner gets back
+        for i in 0..5 {
+            events.put(i.to_string()).await.unwrap();
+        }
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let _subscriber = session
+            .declare_subscriber("test/qos/events")
+            .history(HistoryConfig::default().detect_late_publishers())
+            .callback(move |sample| {
+                sink.lock()
+                    .unwrap()
+                    .push(sample.payload().try_to_string().unwrap().to_string());
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(*received.lock().unwrap(), vec!["2", "3", "4"]);
+    }
+}
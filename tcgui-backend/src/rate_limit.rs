@@ -0,0 +1,199 @@
+//! Rate limiting of incoming queries.
+//!
+//! Every TC / interface-control / diagnostics query turns into privileged
+//! netlink work, so a misbehaving frontend looping on `ApplyConfig` would keep
+//! the kernel reconfiguring an interface non-stop. [`QueryRateLimiter`] is a
+//! token bucket per query source. The frontends and the scenario engine name
+//! their Zenoh session in the query's source info, so each of them gets its
+//! own bucket; queries from other clients share one anonymous bucket.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default sustained rate, in queries per second per source. Dragging a
+/// slider produces a handful of applies per second, so this only trips on
+/// scripted floods.
+pub const DEFAULT_QUERY_RATE_LIMIT: u32 = 50;
+
+/// The bucket holds this many seconds worth of queries, so short bursts
+/// (e.g. a batch apply across many interfaces) pass untouched.
+const BURST_SECONDS: f64 = 2.0;
+
+/// Above this many tracked sources, buckets that have refilled completely
+/// (idle sources) are dropped.
+const MAX_TRACKED_SOURCES: usize = 1024;
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token-bucket limiter keyed by query source (`None` = unidentified).
+#[derive(Debug, Clone)]
+pub struct QueryRateLimiter {
+    /// Sustained queries per second; 0 disables limiting
+    rate_per_sec: u32,
+    buckets: HashMap<Option<String>, TokenBucket>,
+}
+
+impl QueryRateLimiter {
+    /// Create a limiter allowing `rate_per_sec` queries per second per source
+    /// (0 disables limiting).
+    pub fn new(rate_per_sec: u32) -> Self {
+        Self {
+            rate_per_sec,
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn capacity(&self) -> f64 {
+        self.rate_per_sec as f64 * BURST_SECONDS
+    }
+
+    /// Take a token for a query from `source` arriving at `now`. Returns
+    /// `false` when the source is over its limit and the query should be
+    /// rejected.
+    pub fn check(&mut self, source: Option<&str>, now: Instant) -> bool {
+        if self.rate_per_sec == 0 {
+            return true;
+        }
+        let capacity = self.capacity();
+        let rate = self.rate_per_sec as f64;
+
+        if self.buckets.len() >= MAX_TRACKED_SOURCES {
+            self.buckets.retain(|_, bucket| {
+                let elapsed = now.saturating_duration_since(bucket.last_refill);
+                bucket.tokens + elapsed.as_secs_f64() * rate < capacity
+            });
+        }
+
+        let bucket = self
+            .buckets
+            .entry(source.map(str::to_owned))
+            .or_insert(TokenBucket {
+                tokens: capacity,
+                last_refill: now,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Configured sustained rate (queries per second, 0 = unlimited)
+    pub fn rate_per_sec(&self) -> u32 {
+        self.rate_per_sec
+    }
+}
+
+impl Default for QueryRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUERY_RATE_LIMIT)
+    }
+}
+
+/// Minimum spacing of expensive operations that are global rather than per
+/// source (e.g. a forced refresh republishing every interface): at most one
+/// per interval, whoever asks.
+#[derive(Debug, Clone)]
+pub struct Cooldown {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Cooldown {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Start the operation at `now` if the previous one is at least an
+    /// interval ago; otherwise return how long the caller has to wait.
+    pub fn try_start(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(last) = self.last {
+            let elapsed = now.saturating_duration_since(last);
+            if elapsed < self.interval {
+                return Err(self.interval - elapsed);
+            }
+        }
+        self.last = Some(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_beyond_limit_is_rejected() {
+        let mut limiter = QueryRateLimiter::new(10);
+        let now = Instant::now();
+
+        let admitted = (0..100).filter(|_| limiter.check(Some("a"), now)).count();
+        assert_eq!(admitted, 20); // two seconds worth of burst
+
+        // Tokens come back at the configured rate
+        let later = now + Duration::from_millis(500);
+        let admitted = (0..100).filter(|_| limiter.check(Some("a"), later)).count();
+        assert_eq!(admitted, 5);
+    }
+
+    #[test]
+    fn test_normal_usage_passes() {
+        let mut limiter = QueryRateLimiter::new(10);
+        let start = Instant::now();
+
+        // Steady 8 queries/s for a minute never trips the limit
+        for i in 0..480u64 {
+            let now = start + Duration::from_millis(i * 125);
+            assert!(limiter.check(None, now), "query {i} rejected");
+        }
+    }
+
+    #[test]
+    fn test_sources_are_limited_independently() {
+        let mut limiter = QueryRateLimiter::new(1);
+        let now = Instant::now();
+
+        assert!(limiter.check(Some("flood"), now));
+        assert!(limiter.check(Some("flood"), now));
+        assert!(!limiter.check(Some("flood"), now));
+
+        // Another peer and anonymous queries are unaffected
+        assert!(limiter.check(Some("other"), now));
+        assert!(limiter.check(None, now));
+    }
+
+    #[test]
+    fn test_zero_disables_limiting() {
+        let mut limiter = QueryRateLimiter::new(0);
+        let now = Instant::now();
+        assert!((0..10_000).all(|_| limiter.check(None, now)));
+    }
+
+    #[test]
+    fn test_cooldown_spaces_operations() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(5));
+        let start = Instant::now();
+
+        assert!(cooldown.try_start(start).is_ok());
+        assert_eq!(
+            cooldown.try_start(start + Duration::from_secs(2)),
+            Err(Duration::from_secs(3))
+        );
+        // A rejected attempt does not push the window back
+        assert!(cooldown.try_start(start + Duration::from_secs(5)).is_ok());
+        assert!(cooldown.try_start(start + Duration::from_secs(6)).is_err());
+    }
+}
@@ -4,6 +4,7 @@
 //! including precise timing control, parameter interpolation, and state management.
 
 use anyhow::Result;
+use futures_util::future::BoxFuture;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -15,9 +16,12 @@ use zenoh::Session;
 use tcgui_shared::identity::LocalOrigin;
 use tcgui_shared::registry::tc;
 use tcgui_shared::scenario::{
-    ExecutionState, ExecutionStats, NetworkScenario, ScenarioError, ScenarioExecution,
+    CleanupPolicy, ExecutionState, ExecutionStats, NetworkScenario, RampPoint, ScenarioEnd,
+    ScenarioError, ScenarioExecution, ScenarioRepeat, ScenarioStep,
+};
+use tcgui_shared::{
+    TcConfiguration, TcDirection, TcNetemConfig, TcOperation, TcRequest, TcResponse, TcSlotConfig,
 };
-use tcgui_shared::{TcOperation, TcRequest, TcResponse};
 
 use crate::tc_commands::{CapturedTcState, TcCommandManager};
 
@@ -37,6 +41,9 @@ pub struct ScenarioExecutionEngine {
     update_sender: mpsc::UnboundedSender<ScenarioExecutionUpdate>,
 }
 
+/// How long a stop waits for the execution task to clean up before aborting it
+const STOP_CLEANUP_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Individual scenario executor for a specific interface
 pub struct ScenarioExecutor {
     /// Current execution state
@@ -64,10 +71,139 @@ pub struct ScenarioExecutionUpdate {
     pub backend_name: String,
 }
 
+/// Current time as a Unix timestamp in milliseconds.
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// When to apply which configuration during `step`, as offsets from its
+/// start. A step without a ramp applies its configuration at once; a ramp
+/// moves there from `previous` in paced increments. The last entry, without
+/// a configuration, marks the end of the step.
+fn step_applies(
+    step: &ScenarioStep,
+    previous: &TcNetemConfig,
+) -> Vec<(u64, Option<TcNetemConfig>)> {
+    let mut applies: Vec<_> = match step.ramp {
+        None => vec![(0, Some(step.tc_config.clone()))],
+        Some(ramp) => ramp
+            .pacing()
+            .schedule(step.duration_ms)
+            .iter()
+            .map(|point| {
+                (
+                    point.offset_ms,
+                    Some(ramped_config(previous, &step.tc_config, point)),
+                )
+            })
+            .collect(),
+    };
+    applies.push((step.duration_ms, None));
+    applies
+}
+
+/// The configuration at `point` of a ramp from `from` to `to`.
+///
+/// Numeric parameters move linearly and are clamped to their valid ranges;
+/// a feature enabled on one side only ramps from or to zero. Everything
+/// else (loss model, delay distribution, reorder gap, slot packet limits)
+/// comes from `to`, or from `from` while ramping a feature out. Rate limits
+/// and slots only ramp between two enabled configurations, a rate only
+/// between two absolute rates.
+fn ramped_config(from: &TcNetemConfig, to: &TcNetemConfig, point: &RampPoint) -> TcNetemConfig {
+    if point.progress >= 1.0 {
+        return to.clone();
+    }
+    // A disabled feature counts as zero
+    let value = |from: Option<f32>, to: Option<f32>, max: f32| {
+        point
+            .interpolate(from.unwrap_or(0.0), to.unwrap_or(0.0))
+            .clamp(0.0, max)
+    };
+    let mut config = to.clone();
+
+    macro_rules! ramp_percentage {
+        ($($feature:ident),*) => {$(
+            let (f, t) = (&from.$feature, &to.$feature);
+            if f.enabled || t.enabled {
+                config.$feature = if t.enabled { t.clone() } else { f.clone() };
+                config.$feature.enabled = true;
+                config.$feature.percentage = value(
+                    f.enabled.then_some(f.percentage),
+                    t.enabled.then_some(t.percentage),
+                    100.0,
+                );
+                config.$feature.correlation = value(
+                    f.enabled.then_some(f.correlation),
+                    t.enabled.then_some(t.correlation),
+                    100.0,
+                );
+            }
+        )*};
+    }
+    ramp_percentage!(loss, duplicate, reorder, corrupt);
+
+    let (f, t) = (&from.delay, &to.delay);
+    if f.enabled || t.enabled {
+        config.delay = if t.enabled { t.clone() } else { f.clone() };
+        config.delay.enabled = true;
+        config.delay.base_ms = value(
+            f.enabled.then_some(f.base_ms),
+            t.enabled.then_some(t.base_ms),
+            5000.0,
+        );
+        config.delay.jitter_ms = value(
+            f.enabled.then_some(f.jitter_ms),
+            t.enabled.then_some(t.jitter_ms),
+            1000.0,
+        );
+        config.delay.correlation = value(
+            f.enabled.then_some(f.correlation),
+            t.enabled.then_some(t.correlation),
+            100.0,
+        );
+    }
+
+    let (f, t) = (&from.rate_limit, &to.rate_limit);
+    if f.enabled && t.enabled && f.mode.is_absolute() && t.mode.is_absolute() {
+        config.rate_limit.rate_kbps = point
+            .interpolate(f.rate_kbps as f32, t.rate_kbps as f32)
+            .round()
+            .clamp(1.0, 1_000_000.0) as u32;
+    }
+
+    let (f, t) = (&from.slot, &to.slot);
+    if f.enabled && t.enabled {
+        config.slot.min_delay_ms = value(
+            Some(f.min_delay_ms),
+            Some(t.min_delay_ms),
+            TcSlotConfig::MAX_DELAY_MS,
+        );
+        config.slot.max_delay_ms = value(
+            Some(f.max_delay_ms),
+            Some(t.max_delay_ms),
+            TcSlotConfig::MAX_DELAY_MS,
+        )
+        .max(config.slot.min_delay_ms);
+    }
+
+    config
+}
+
 impl ScenarioExecutor {
-    /// Cleanup the executor by aborting the task
-    pub fn cleanup(self) {
-        self.task_handle.abort();
+    /// Wait for the stopped execution task to finish its TC cleanup, aborting
+    /// it if that takes longer than `timeout`
+    pub async fn finish(mut self, timeout: Duration) {
+        if tokio::time::timeout(timeout, &mut self.task_handle)
+            .await
+            .is_err()
+        {
+            warn!("Execution task did not finish cleaning up in time, aborting it");
+            self.task_handle.abort();
+        }
     }
 }
 
@@ -112,6 +248,43 @@ impl ScenarioExecutionEngine {
         namespace: String,
         interface: String,
         loop_execution: bool,
+    ) -> Result<String> {
+        self.launch_scenario(scenario, namespace, interface, loop_execution, None)
+            .await
+    }
+
+    /// Schedule a scenario to start at `start_at` (Unix timestamp in milliseconds).
+    ///
+    /// The execution is registered right away in the `Scheduled` state, so it
+    /// occupies the interface and can be cancelled with [`Self::stop_scenario`]
+    /// before it fires. A time that has already passed fires immediately.
+    #[instrument(skip(self), fields(scenario_id = %scenario.id, namespace = %namespace, interface = %interface, start_at = %start_at))]
+    pub async fn schedule_scenario(
+        &self,
+        scenario: NetworkScenario,
+        namespace: String,
+        interface: String,
+        loop_execution: bool,
+        start_at: u64,
+    ) -> Result<String> {
+        self.launch_scenario(
+            scenario,
+            namespace,
+            interface,
+            loop_execution,
+            Some(start_at),
+        )
+        .await
+    }
+
+    /// Register and spawn an execution, either running now or scheduled.
+    async fn launch_scenario(
+        &self,
+        scenario: NetworkScenario,
+        namespace: String,
+        interface: String,
+        loop_execution: bool,
+        start_at: Option<u64>,
     ) -> Result<String> {
         let execution_key = format!("{}/{}", namespace, interface);
 
@@ -128,49 +301,35 @@ impl ScenarioExecutionEngine {
         }
 
         info!(
-            "Starting scenario '{}' on interface {}:{} (cleanup_on_failure={})",
-            scenario.id, namespace, interface, scenario.cleanup_on_failure
+            "Starting scenario '{}' on interface {}:{} (cleanup_on_failure={}, start_at={:?})",
+            scenario.id, namespace, interface, scenario.cleanup_on_failure, start_at
         );
 
-        // Always capture pre-execution TC state for cleanup on stop
-        // (cleanup_on_failure only affects behavior on execution errors, not user stop)
-        let pre_execution_state = match self
-            .tc_manager
-            .capture_tc_state(&namespace, &interface)
-            .await
-        {
-            Ok(state) => {
-                info!(
-                    "Captured pre-execution TC state: had_netem={}",
-                    state.had_netem
-                );
-                Some(state)
-            }
-            Err(e) => {
-                warn!(
-                    "Could not capture pre-execution TC state: {}, cleanup on stop will not be available",
-                    e
-                );
-                None
-            }
+        // A scheduled execution captures the TC state when it fires, not now
+        let pre_execution_state = if start_at.is_some() {
+            None
+        } else {
+            Self::capture_pre_execution_state(&self.tc_manager, &namespace, &interface).await
         };
 
         // Create execution state
-        let start_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+        let (start_time, state) = match start_at {
+            Some(start_at) => (start_at, ExecutionState::Scheduled { start_at }),
+            None => (now_millis(), ExecutionState::Running),
+        };
 
         let execution = ScenarioExecution {
             scenario: scenario.clone(),
             start_time,
             current_step: 0,
-            state: ExecutionState::Running,
+            state,
             target_namespace: namespace.clone(),
             target_interface: interface.clone(),
             stats: ExecutionStats::default(),
-            loop_execution,
+            loop_execution: scenario.repeat_for(loop_execution) == ScenarioRepeat::Forever,
             loop_iteration: 0,
+            paused_ms: 0,
+            current_phase: scenario.phase_of(0).map(str::to_string),
         };
 
         // Create control channels
@@ -211,20 +370,18 @@ impl ScenarioExecutionEngine {
     pub async fn stop_scenario(&self, namespace: &str, interface: &str) -> Result<bool> {
         let execution_key = format!("{}/{}", namespace, interface);
 
-        let mut executions = self.active_executions.write().await;
-        if let Some(mut executor) = executions.remove(&execution_key) {
+        let removed = self.active_executions.write().await.remove(&execution_key);
+        if let Some(mut executor) = removed {
             info!("Stopping scenario execution: {}", execution_key);
 
-            // Send stop signal
-            let _ = executor.control_sender.send(ExecutorControlMessage::Stop);
-
             // Update execution state
             executor.execution.state = ExecutionState::Stopped;
 
             // Clone execution for the update before consuming executor
             let final_execution = executor.execution.clone();
 
-            // Send final update
+            // Send final update before the stop signal: the task follows with
+            // its own, which carries the loop iteration it reached
             let _ = self.update_sender.send(ScenarioExecutionUpdate {
                 namespace: namespace.to_string(),
                 interface: interface.to_string(),
@@ -232,8 +389,11 @@ impl ScenarioExecutionEngine {
                 backend_name: self.backend_name.clone(),
             });
 
-            // Cleanup the executor task
-            executor.cleanup();
+            // Send stop signal
+            let _ = executor.control_sender.send(ExecutorControlMessage::Stop);
+
+            // The task cleans up as the scenario's policy says
+            executor.finish(STOP_CLEANUP_TIMEOUT).await;
 
             Ok(true)
         } else {
@@ -249,6 +409,10 @@ impl ScenarioExecutionEngine {
 
         let mut executions = self.active_executions.write().await;
         if let Some(executor) = executions.get_mut(&execution_key) {
+            if matches!(executor.execution.state, ExecutionState::Scheduled { .. }) {
+                debug!("Cannot pause scheduled execution: {}", execution_key);
+                return Ok(false);
+            }
             info!("Pausing scenario execution: {}", execution_key);
 
             let _ = executor.control_sender.send(ExecutorControlMessage::Pause);
@@ -281,6 +445,10 @@ impl ScenarioExecutionEngine {
 
         let mut executions = self.active_executions.write().await;
         if let Some(executor) = executions.get_mut(&execution_key) {
+            if matches!(executor.execution.state, ExecutionState::Scheduled { .. }) {
+                debug!("Cannot resume scheduled execution: {}", execution_key);
+                return Ok(false);
+            }
             info!("Resuming scenario execution: {}", execution_key);
 
             let _ = executor.control_sender.send(ExecutorControlMessage::Resume);
@@ -330,7 +498,7 @@ impl ScenarioExecutionEngine {
         mut control_receiver: mpsc::UnboundedReceiver<ExecutorControlMessage>,
         tc_manager: TcCommandManager,
         update_sender: mpsc::UnboundedSender<ScenarioExecutionUpdate>,
-        pre_execution_state: Option<CapturedTcState>,
+        mut pre_execution_state: Option<CapturedTcState>,
         cleanup_on_failure: bool,
         execution_key: String,
     ) -> tokio::task::JoinHandle<()> {
@@ -348,14 +516,66 @@ impl ScenarioExecutionEngine {
                 cleanup_on_failure
             );
 
-            let mut paused_duration = Duration::from_millis(0);
-            let mut pause_start: Option<Instant> = None;
+            // Scheduled execution: wait for the start time (or a cancel)
+            if let ExecutionState::Scheduled { start_at } = execution.state {
+                let _ = update_sender.send(ScenarioExecutionUpdate {
+                    namespace: execution.target_namespace.clone(),
+                    interface: execution.target_interface.clone(),
+                    execution: execution.clone(),
+                    backend_name: backend_name.clone(),
+                });
+
+                if Self::wait_until(start_at, &mut control_receiver)
+                    .await
+                    .is_err()
+                {
+                    info!(
+                        "Scheduled scenario '{}' cancelled before start",
+                        execution.scenario.id
+                    );
+                    {
+                        let mut executions = active_executions.write().await;
+                        executions.remove(&execution_key);
+                    }
+                    execution.state = ExecutionState::Stopped;
+                    let _ = update_sender.send(ScenarioExecutionUpdate {
+                        namespace: execution.target_namespace.clone(),
+                        interface: execution.target_interface.clone(),
+                        execution: execution.clone(),
+                        backend_name: backend_name.clone(),
+                    });
+                    return;
+                }
+
+                info!(
+                    "Scheduled start reached for scenario '{}'",
+                    execution.scenario.id
+                );
+                pre_execution_state = Self::capture_pre_execution_state(
+                    &tc_manager,
+                    &execution.target_namespace,
+                    &execution.target_interface,
+                )
+                .await;
+                execution.start_time = now_millis();
+                execution.state = ExecutionState::Running;
+
+                let mut executions = active_executions.write().await;
+                if let Some(executor) = executions.get_mut(&execution_key) {
+                    executor.execution = execution.clone();
+                }
+            }
 
             let scenario_steps = execution.scenario.steps.clone();
-            let loop_execution = execution.loop_execution;
+            let repeat = execution.scenario.repeat_for(execution.loop_execution);
+            // What the first step ramps from
+            let mut previous_config = pre_execution_state
+                .as_ref()
+                .and_then(|state| state.netem_config.clone())
+                .unwrap_or_default();
 
             // Send initial execution update
-            execution.current_step = 0;
+            execution.set_current_step(0);
             let _ = update_sender.send(ScenarioExecutionUpdate {
                 namespace: execution.target_namespace.clone(),
                 interface: execution.target_interface.clone(),
@@ -363,92 +583,174 @@ impl ScenarioExecutionEngine {
                 backend_name: backend_name.clone(),
             });
 
-            // Main execution loop - runs once or forever if loop_execution is true
+            // Main execution loop - runs once, as many times as the scenario
+            // repeats, or forever if it loops
             'execution_loop: loop {
                 for (step_index, step) in scenario_steps.iter().enumerate() {
-                    execution.current_step = step_index;
+                    execution.set_current_step(step_index);
 
-                    // Apply TC configuration for this step
                     info!(
-                        "Executing step {} of scenario '{}': {} (duration: {}ms)",
+                        "Executing step {} of scenario '{}': {} (duration: {}ms{})",
                         step_index + 1,
                         execution.scenario.id,
                         step.description,
-                        step.duration_ms
+                        step.duration_ms,
+                        if step.ramp.is_some() { ", ramped" } else { "" }
                     );
 
-                    let tc_request = TcRequest {
-                        namespace: execution.target_namespace.clone(),
-                        interface: execution.target_interface.clone(),
-                        operation: TcOperation::ApplyConfig {
-                            config: step.tc_config.clone(),
-                        },
-                    };
-
-                    match Self::execute_tc_command(&session, &local_origin, &tc_request).await {
-                        Ok(response) if response.success => {
-                            debug!("Successfully applied TC config for step {}", step_index + 1);
-                            execution.stats.tc_operations += 1;
-                        }
-                        Ok(response) => {
-                            warn!(
-                                "TC operation failed for step {}: {}",
+                    // Apply TC configuration for this step, at once or ramping
+                    // from the previous step's, and hold it for the step
+                    let mut held_ms = 0;
+                    let mut announced = false;
+                    for (offset_ms, config) in step_applies(step, &previous_config) {
+                        if offset_ms > held_ms {
+                            if !announced {
+                                // Send step start update
+                                let _ = update_sender.send(ScenarioExecutionUpdate {
+                                    namespace: execution.target_namespace.clone(),
+                                    interface: execution.target_interface.clone(),
+                                    execution: execution.clone(),
+                                    backend_name: backend_name.clone(),
+                                });
+                                announced = true;
+                            }
+
+                            let hold = Duration::from_millis(offset_ms - held_ms);
+                            debug!(
+                                "Maintaining step {} configuration for {:?}",
                                 step_index + 1,
-                                response.message
+                                hold
                             );
-                            execution.stats.failed_operations += 1;
-                            execution.stats.last_error = Some(response.message.clone());
-
-                            // Mark as failed and trigger rollback
-                            execution.state = ExecutionState::Failed {
-                                error: ScenarioError::permanent(&response.message)
-                                    .at_step(step_index)
-                                    .during("applying TC configuration"),
-                            };
-
-                            // Perform rollback
-                            if cleanup_on_failure
-                                && let Some(ref captured_state) = pre_execution_state
+                            if (Self::interruptible_sleep(
+                                hold,
+                                &mut control_receiver,
+                                &mut execution,
+                                &update_sender,
+                                &backend_name,
+                            )
+                            .await)
+                                .is_err()
                             {
-                                info!("Performing TC state rollback due to execution failure");
-                                match tc_manager.restore_tc_state(captured_state).await {
-                                    Ok(msg) => info!("TC rollback successful: {}", msg),
-                                    Err(e) => error!("TC rollback failed: {}", e),
+                                info!("Execution stopped by user");
+                                Self::clean_up(
+                                    &tc_manager,
+                                    &execution,
+                                    ScenarioEnd::Stopped,
+                                    pre_execution_state.as_ref(),
+                                )
+                                .await;
+
+                                // Remove from active executions (may already be removed by stop_scenario)
+                                {
+                                    let mut executions = active_executions.write().await;
+                                    executions.remove(&execution_key);
                                 }
+
+                                // Send stopped update
+                                execution.state = ExecutionState::Stopped;
+                                let _ = update_sender.send(ScenarioExecutionUpdate {
+                                    namespace: execution.target_namespace.clone(),
+                                    interface: execution.target_interface.clone(),
+                                    execution: execution.clone(),
+                                    backend_name: backend_name.clone(),
+                                });
+                                return;
                             }
+                            held_ms = offset_ms;
+                        }
 
-                            // Remove from active executions
+                        let Some(config) = config else {
+                            continue;
+                        };
+                        let tc_request = TcRequest {
+                            namespace: execution.target_namespace.clone(),
+                            interface: execution.target_interface.clone(),
+                            operation: TcOperation::ApplyConfig {
+                                config: config.clone(),
+                            },
+                            apply_to_peer: false,
+                            // Scenarios don't override the management-interface safeguard
+                            confirm_management: false,
+                            apply_at: None,
+                            filter: None,
+                            direction: TcDirection::Egress,
+                        };
+
+                        let failure =
+                            match Self::execute_tc_command(&session, &local_origin, &tc_request)
+                                .await
                             {
-                                let mut executions = active_executions.write().await;
-                                executions.remove(&execution_key);
-                            }
+                                Ok(response) if response.success => {
+                                    debug!(
+                                        "Successfully applied TC config for step {}",
+                                        step_index + 1
+                                    );
+                                    execution.stats.tc_operations += 1;
+
+                                    // Intermediate configurations of a ramp aren't verified
+                                    if execution.scenario.verify_steps && config == step.tc_config {
+                                        Self::verify_step(
+                                            &tc_manager,
+                                            &execution.target_namespace,
+                                            &execution.target_interface,
+                                            response.applied_config,
+                                            &config,
+                                        )
+                                        .await
+                                        .err()
+                                        .map(
+                                            |discrepancy| {
+                                                warn!(
+                                                    "Step {} did not take effect: {}",
+                                                    step_index + 1,
+                                                    discrepancy
+                                                );
+                                                ScenarioError::permanent(&discrepancy)
+                                                    .at_step(step_index)
+                                                    .during(
+                                                        "verifying the applied TC configuration",
+                                                    )
+                                            },
+                                        )
+                                    } else {
+                                        None
+                                    }
+                                }
+                                Ok(response) => {
+                                    warn!(
+                                        "TC operation failed for step {}: {}",
+                                        step_index + 1,
+                                        response.message
+                                    );
+                                    Some(
+                                        ScenarioError::permanent(&response.message)
+                                            .at_step(step_index)
+                                            .during("applying TC configuration"),
+                                    )
+                                }
+                                Err(e) => {
+                                    error!(
+                                        "Error executing TC command for step {}: {}",
+                                        step_index + 1,
+                                        e
+                                    );
+                                    Some(
+                                        ScenarioError::transient(e.to_string())
+                                            .at_step(step_index)
+                                            .during("executing TC command")
+                                            .with_suggestion(
+                                                "Check network interface status and retry",
+                                            ),
+                                    )
+                                }
+                            };
 
-                            // Send failure update
-                            let _ = update_sender.send(ScenarioExecutionUpdate {
-                                namespace: execution.target_namespace.clone(),
-                                interface: execution.target_interface.clone(),
-                                execution: execution.clone(),
-                                backend_name: backend_name.clone(),
-                            });
-                            return;
-                        }
-                        Err(e) => {
-                            error!(
-                                "Error executing TC command for step {}: {}",
-                                step_index + 1,
-                                e
-                            );
+                        if let Some(error) = failure {
                             execution.stats.failed_operations += 1;
-                            let error_msg = e.to_string();
-                            execution.stats.last_error = Some(error_msg.clone());
+                            execution.stats.last_error = Some(error.message.clone());
 
                             // Mark as failed and trigger rollback
-                            execution.state = ExecutionState::Failed {
-                                error: ScenarioError::transient(&error_msg)
-                                    .at_step(step_index)
-                                    .during("executing TC command")
-                                    .with_suggestion("Check network interface status and retry"),
-                            };
+                            execution.state = ExecutionState::Failed { error };
 
                             // Perform rollback
                             if cleanup_on_failure
@@ -476,60 +778,8 @@ impl ScenarioExecutionEngine {
                             });
                             return;
                         }
-                    }
 
-                    // Send step start update
-                    let _ = update_sender.send(ScenarioExecutionUpdate {
-                        namespace: execution.target_namespace.clone(),
-                        interface: execution.target_interface.clone(),
-                        execution: execution.clone(),
-                        backend_name: backend_name.clone(),
-                    });
-
-                    // Wait for step duration
-                    let step_duration = Duration::from_millis(step.duration_ms);
-                    debug!(
-                        "Maintaining step {} configuration for {:?}",
-                        step_index + 1,
-                        step_duration
-                    );
-
-                    if (Self::interruptible_sleep(
-                        step_duration,
-                        &mut control_receiver,
-                        &mut execution,
-                        &mut pause_start,
-                        &mut paused_duration,
-                        &update_sender,
-                        &backend_name,
-                    )
-                    .await)
-                        .is_err()
-                    {
-                        // Execution was stopped by user - always clean up TC config
-                        info!("Execution stopped by user, cleaning up TC config");
-                        if let Some(ref captured_state) = pre_execution_state {
-                            match tc_manager.restore_tc_state(captured_state).await {
-                                Ok(msg) => info!("TC cleanup successful: {}", msg),
-                                Err(e) => error!("TC cleanup failed: {}", e),
-                            }
-                        }
-
-                        // Remove from active executions (may already be removed by stop_scenario)
-                        {
-                            let mut executions = active_executions.write().await;
-                            executions.remove(&execution_key);
-                        }
-
-                        // Send stopped update
-                        execution.state = ExecutionState::Stopped;
-                        let _ = update_sender.send(ScenarioExecutionUpdate {
-                            namespace: execution.target_namespace.clone(),
-                            interface: execution.target_interface.clone(),
-                            execution: execution.clone(),
-                            backend_name: backend_name.clone(),
-                        });
-                        return;
+                        previous_config = config;
                     }
 
                     execution.stats.steps_completed += 1;
@@ -544,68 +794,58 @@ impl ScenarioExecutionEngine {
                         backend_name: backend_name.clone(),
                     });
 
-                    // Check for stop/pause messages during execution
+                    // Check for stop/pause messages during execution; a pause
+                    // holds the execution here until it is resumed or stopped
                     while let Ok(control_msg) = control_receiver.try_recv() {
-                        match control_msg {
-                            ExecutorControlMessage::Stop => {
-                                info!("Scenario execution stopped by user");
-                                execution.state = ExecutionState::Stopped;
-
-                                // Always clean up TC config on user stop
-                                if let Some(ref captured_state) = pre_execution_state {
-                                    match tc_manager.restore_tc_state(captured_state).await {
-                                        Ok(msg) => info!("TC cleanup successful: {}", msg),
-                                        Err(e) => error!("TC cleanup failed: {}", e),
-                                    }
-                                }
-
-                                // Remove from active executions (may already be removed by stop_scenario)
-                                {
-                                    let mut executions = active_executions.write().await;
-                                    executions.remove(&execution_key);
-                                }
-
-                                // Send stopped update
-                                let _ = update_sender.send(ScenarioExecutionUpdate {
-                                    namespace: execution.target_namespace.clone(),
-                                    interface: execution.target_interface.clone(),
-                                    execution: execution.clone(),
-                                    backend_name: backend_name.clone(),
-                                });
-                                return;
-                            }
-                            ExecutorControlMessage::Pause => {
-                                info!("Scenario execution paused by user");
-                                pause_start = Some(Instant::now());
-                                execution.state = ExecutionState::Paused {
-                                    paused_at: SystemTime::now()
-                                        .duration_since(UNIX_EPOCH)
-                                        .unwrap_or_default()
-                                        .as_millis()
-                                        as u64,
-                                };
-                            }
-                            ExecutorControlMessage::Resume => {
-                                if let Some(paused_at) = pause_start.take() {
-                                    paused_duration += paused_at.elapsed();
-                                    info!(
-                                        "Scenario execution resumed (total paused: {:?})",
-                                        paused_duration
-                                    );
-                                    execution.state = ExecutionState::Running;
-                                }
+                        let stopped = match control_msg {
+                            ExecutorControlMessage::Stop => true,
+                            ExecutorControlMessage::Pause => Self::wait_while_paused(
+                                &mut control_receiver,
+                                &mut execution,
+                                &update_sender,
+                                &backend_name,
+                            )
+                            .await
+                            .is_err(),
+                            // Not paused, ignore
+                            ExecutorControlMessage::Resume => false,
+                        };
+                        if stopped {
+                            info!("Scenario execution stopped by user");
+                            execution.state = ExecutionState::Stopped;
+                            Self::clean_up(
+                                &tc_manager,
+                                &execution,
+                                ScenarioEnd::Stopped,
+                                pre_execution_state.as_ref(),
+                            )
+                            .await;
+
+                            // Remove from active executions (may already be removed by stop_scenario)
+                            {
+                                let mut executions = active_executions.write().await;
+                                executions.remove(&execution_key);
                             }
+
+                            // Send stopped update
+                            let _ = update_sender.send(ScenarioExecutionUpdate {
+                                namespace: execution.target_namespace.clone(),
+                                interface: execution.target_interface.clone(),
+                                execution: execution.clone(),
+                                backend_name: backend_name.clone(),
+                            });
+                            return;
                         }
                     }
                 }
 
-                // End of steps loop - check if we should loop again
-                if loop_execution {
+                // End of steps loop - check if another cycle follows
+                if repeat.runs_again(execution.loop_iteration + 1) {
                     // Increment loop iteration and reset for next loop
                     execution.loop_iteration += 1;
                     execution.stats.steps_completed = 0;
                     execution.stats.progress_percent = 0.0;
-                    execution.current_step = 0;
+                    execution.set_current_step(0);
 
                     info!(
                         "Scenario '{}' completed iteration {}, starting next loop",
@@ -623,7 +863,6 @@ impl ScenarioExecutionEngine {
                     continue 'execution_loop;
                 }
 
-                // Scenario completed successfully (non-looping) - no rollback needed
                 break 'execution_loop;
             } // end of 'execution_loop
 
@@ -631,6 +870,13 @@ impl ScenarioExecutionEngine {
                 "Scenario '{}' execution completed successfully",
                 execution.scenario.id
             );
+            Self::clean_up(
+                &tc_manager,
+                &execution,
+                ScenarioEnd::Completed,
+                pre_execution_state.as_ref(),
+            )
+            .await;
             execution.state = ExecutionState::Completed;
             execution.stats.progress_percent = 100.0;
 
@@ -650,87 +896,245 @@ impl ScenarioExecutionEngine {
         })
     }
 
-    /// Interruptible sleep that handles pause/resume/stop control messages
+    /// Leave, remove or restore the interface's TC configuration after an
+    /// execution ended with `end`, as the scenario's cleanup policy says.
+    async fn clean_up(
+        tc_manager: &TcCommandManager,
+        execution: &ScenarioExecution,
+        end: ScenarioEnd,
+        pre_execution_state: Option<&CapturedTcState>,
+    ) {
+        let namespace = &execution.target_namespace;
+        let interface = &execution.target_interface;
+        let result = match execution.scenario.cleanup_on(end) {
+            CleanupPolicy::LeaveApplied => {
+                info!(
+                    "Leaving the last TC config applied on {}:{}",
+                    namespace, interface
+                );
+                return;
+            }
+            CleanupPolicy::RemoveAll => {
+                tc_manager
+                    .remove_tc_config_in_namespace(namespace, interface)
+                    .await
+            }
+            CleanupPolicy::RestorePrevious => match pre_execution_state {
+                Some(captured_state) => tc_manager.restore_tc_state(captured_state).await,
+                None => {
+                    warn!(
+                        "No TC state was captured before the scenario started on {}:{}, nothing to restore",
+                        namespace, interface
+                    );
+                    return;
+                }
+            },
+        };
+        match result {
+            Ok(msg) => info!("TC cleanup successful: {}", msg),
+            Err(e) => error!("TC cleanup failed: {}", e),
+        }
+    }
+
+    /// Capture the TC state before an execution starts, for restoring it when
+    /// the execution ends or fails.
+    ///
+    /// The future is boxed and type-erased: the netlink capture is deep enough
+    /// to overflow the `Send` check of the query handler awaiting it otherwise.
+    fn capture_pre_execution_state<'a>(
+        tc_manager: &'a TcCommandManager,
+        namespace: &'a str,
+        interface: &'a str,
+    ) -> BoxFuture<'a, Option<CapturedTcState>> {
+        Box::pin(async move {
+            match tc_manager.capture_tc_state(namespace, interface).await {
+                Ok(state) => {
+                    info!(
+                        "Captured pre-execution TC state: had_netem={}",
+                        state.had_netem
+                    );
+                    Some(state)
+                }
+                Err(e) => {
+                    warn!(
+                        "Could not capture pre-execution TC state: {}, cleanup on stop will not be available",
+                        e
+                    );
+                    None
+                }
+            }
+        })
+    }
+
+    /// Read the interface's TC configuration back after a step and compare it
+    /// with what the step applied, with the same tolerance as the frontend's
+    /// out-of-sync detection ([`TcConfiguration::diff`]).
+    ///
+    /// `applied` is the configuration the backend reported applying; when it
+    /// reported none, the step's own configuration is expected. Returns the
+    /// discrepancy on mismatch.
+    fn verify_step<'a>(
+        tc_manager: &'a TcCommandManager,
+        namespace: &'a str,
+        interface: &'a str,
+        applied: Option<TcConfiguration>,
+        step_config: &'a TcNetemConfig,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let expected = applied
+                .unwrap_or_else(|| crate::tc_config::configuration_of(interface, step_config));
+            let state = tc_manager
+                .capture_tc_state(namespace, interface)
+                .await
+                .map_err(|e| format!("could not read back the TC configuration: {}", e))?;
+            let detected = state
+                .netem_config
+                .map(|config| crate::tc_config::configuration_of(interface, &config))
+                .unwrap_or_default();
+
+            let differences = expected.diff(&detected);
+            if differences.is_empty() {
+                debug!("Read back matches on {}:{}", namespace, interface);
+                return Ok(());
+            }
+            Err(format!(
+                "read back differs from the requested configuration: {}",
+                differences
+                    .iter()
+                    .map(|d| format!(
+                        "{} is {}, expected {}",
+                        d.parameter,
+                        d.format_value(d.theirs),
+                        d.format_value(d.ours)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+    }
+
+    /// Wait until `start_at` (Unix timestamp in milliseconds).
+    ///
+    /// Returns `Err(())` if the execution is stopped while waiting. Pause and
+    /// resume do not apply to a scheduled execution and are ignored.
+    async fn wait_until(
+        start_at: u64,
+        control_receiver: &mut mpsc::UnboundedReceiver<ExecutorControlMessage>,
+    ) -> Result<(), ()> {
+        let deadline =
+            Instant::now() + Duration::from_millis(start_at.saturating_sub(now_millis()));
+
+        loop {
+            match tokio::time::timeout_at(deadline, control_receiver.recv()).await {
+                Err(_elapsed) => return Ok(()),
+                Ok(Some(ExecutorControlMessage::Stop) | None) => return Err(()),
+                Ok(Some(ExecutorControlMessage::Pause | ExecutorControlMessage::Resume)) => {}
+            }
+        }
+    }
+
+    /// Hold the current step's configuration for `duration`, not counting
+    /// time spent paused.
+    ///
+    /// Returns `Err(())` if the execution is stopped meanwhile.
     async fn interruptible_sleep(
-        mut duration: Duration,
+        duration: Duration,
         control_receiver: &mut mpsc::UnboundedReceiver<ExecutorControlMessage>,
         execution: &mut ScenarioExecution,
-        pause_start: &mut Option<Instant>,
-        paused_duration: &mut Duration,
         update_sender: &mpsc::UnboundedSender<ScenarioExecutionUpdate>,
         backend_name: &str,
     ) -> Result<(), ()> {
-        let _sleep_start = Instant::now();
+        let mut remaining = duration;
 
         loop {
             // Sleep in small chunks to be responsive to control messages
-            let chunk_duration = Duration::from_millis(100).min(duration);
+            let chunk_start = Instant::now();
+            let chunk_duration = Duration::from_millis(100).min(remaining);
 
             tokio::select! {
                 _ = sleep(chunk_duration) => {
-                    duration = duration.saturating_sub(chunk_duration);
-                    if duration.is_zero() {
-                        return Ok(());
-                    }
+                    remaining = remaining.saturating_sub(chunk_duration);
                 }
 
                 control_msg = control_receiver.recv() => {
+                    // The part of the chunk slept before the message counts
+                    remaining = remaining.saturating_sub(chunk_start.elapsed());
                     match control_msg {
-                        Some(ExecutorControlMessage::Stop) => {
-                            execution.state = ExecutionState::Stopped;
-                            return Err(());
-                        }
                         Some(ExecutorControlMessage::Pause) => {
-                            *pause_start = Some(Instant::now());
-                            execution.state = ExecutionState::Paused { paused_at:
-                                SystemTime::now()
-                                    .duration_since(UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_millis() as u64
-                            };
-
-                            // Send pause update
-                            let _ = update_sender.send(ScenarioExecutionUpdate {
-                                namespace: execution.target_namespace.clone(),
-                                interface: execution.target_interface.clone(),
-                                execution: execution.clone(),
-                                backend_name: backend_name.to_string(),
-                            });
-
-                            // Wait for resume
-                            loop {
-                                if let Some(control_msg) = control_receiver.recv().await {
-                                    match control_msg {
-                                        ExecutorControlMessage::Stop => {
-                                            execution.state = ExecutionState::Stopped;
-                                            return Err(());
-                                        }
-                                        ExecutorControlMessage::Resume => {
-                                            if let Some(paused_at) = pause_start.take() {
-                                                *paused_duration += paused_at.elapsed();
-                                            }
-                                            execution.state = ExecutionState::Running;
-                                            break;
-                                        }
-                                        ExecutorControlMessage::Pause => {
-                                            // Already paused, ignore
-                                        }
-                                    }
-                                }
-                            }
+                            Self::wait_while_paused(
+                                control_receiver,
+                                execution,
+                                update_sender,
+                                backend_name,
+                            )
+                            .await?;
                         }
                         Some(ExecutorControlMessage::Resume) => {
                             // Not paused, ignore
                         }
-                        None => {
-                            // Channel closed, stop execution
+                        Some(ExecutorControlMessage::Stop) | None => {
+                            // Stopped, or the channel closed
                             execution.state = ExecutionState::Stopped;
                             return Err(());
                         }
                     }
                 }
             }
+
+            if remaining.is_zero() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Hold the execution paused, publishing the paused state, until it is
+    /// resumed. The time paused is added to the execution's `paused_ms`.
+    ///
+    /// Returns `Err(())` if the execution is stopped instead.
+    async fn wait_while_paused(
+        control_receiver: &mut mpsc::UnboundedReceiver<ExecutorControlMessage>,
+        execution: &mut ScenarioExecution,
+        update_sender: &mpsc::UnboundedSender<ScenarioExecutionUpdate>,
+        backend_name: &str,
+    ) -> Result<(), ()> {
+        info!("Scenario execution paused by user");
+        let pause_start = Instant::now();
+        execution.state = ExecutionState::Paused {
+            paused_at: now_millis(),
+        };
+        let _ = update_sender.send(ScenarioExecutionUpdate {
+            namespace: execution.target_namespace.clone(),
+            interface: execution.target_interface.clone(),
+            execution: execution.clone(),
+            backend_name: backend_name.to_string(),
+        });
+
+        loop {
+            match control_receiver.recv().await {
+                Some(ExecutorControlMessage::Resume) => break,
+                Some(ExecutorControlMessage::Pause) => {
+                    // Already paused, ignore
+                }
+                Some(ExecutorControlMessage::Stop) | None => {
+                    execution.state = ExecutionState::Stopped;
+                    return Err(());
+                }
+            }
         }
+
+        execution.paused_ms += pause_start.elapsed().as_millis() as u64;
+        execution.state = ExecutionState::Running;
+        info!(
+            "Scenario execution resumed (total paused: {}ms)",
+            execution.paused_ms
+        );
+        let _ = update_sender.send(ScenarioExecutionUpdate {
+            namespace: execution.target_namespace.clone(),
+            interface: execution.target_interface.clone(),
+            execution: execution.clone(),
+            backend_name: backend_name.to_string(),
+        });
+        Ok(())
     }
 
     /// Execute a TC command via Zenoh query on the backend's own concrete
@@ -748,6 +1152,7 @@ impl ScenarioExecutionEngine {
 
         let replies = session
             .get(tc_query_topic.as_str())
+            .source_info(tcgui_shared::identity::querier_info(session))
             .payload(request_payload)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to send TC query: {}", e))?;
@@ -803,7 +1208,7 @@ impl ScenarioExecutionEngine {
 mod tests {
     use super::*;
     use tcgui_shared::TcNetemConfig;
-    use tcgui_shared::scenario::{NetworkScenario, ScenarioStep};
+    use tcgui_shared::scenario::{NetworkScenario, ScenarioStep, requested_start};
     use zenoh::Wait;
 
     fn create_test_scenario() -> NetworkScenario {
@@ -862,18 +1267,195 @@ mod tests {
     }
 
     fn create_test_engine() -> ScenarioExecutionEngine {
-        let session = Arc::new(
-            zenoh::open(zenoh::Config::default())
-                .wait()
-                .expect("Failed to open Zenoh"),
-        );
+        create_recording_test_engine().0
+    }
+
+    /// A test engine, with the configurations it has applied so far
+    fn create_recording_test_engine() -> (
+        ScenarioExecutionEngine,
+        Arc<std::sync::Mutex<Vec<TcNetemConfig>>>,
+    ) {
+        // The engines of concurrently running tests must not reach each
+        // other, or their TC queries get routed between them.
+        let session = Arc::new(crate::test_support::isolated_session());
+        let local_origin = tcgui_shared::identity::local_origin_from_seed("test-backend");
+
+        // Stand-in for the backend's TC queryable: acknowledge every step so
+        // executions run for their scripted duration instead of failing at once.
+        let applies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = applies.clone();
+        let serve_key = tc::rpc_serve_key(&local_origin, tc::ProcedureId::ConfigNsIfaceSet);
+        session
+            .declare_queryable(serve_key.as_keyexpr())
+            .callback(move |query| {
+                if let Some(payload) = query.payload()
+                    && let Ok(request) = serde_json::from_slice::<TcRequest>(&payload.to_bytes())
+                    && let TcOperation::ApplyConfig { config } = request.operation
+                {
+                    sink.lock().unwrap().push(config);
+                }
+                let response = TcResponse {
+                    success: true,
+                    message: "ok".to_string(),
+                    applied_config: None,
+                    error_code: None,
+                    peer: None,
+                    rejected_feature: None,
+                    scheduled_at: None,
+                };
+                let payload = serde_json::to_vec(&response).expect("serialize TcResponse");
+                let _ = query.reply(query.key_expr().clone(), payload).wait();
+            })
+            .background()
+            .wait()
+            .expect("Failed to declare TC queryable");
+
         let tc_manager = crate::tc_commands::TcCommandManager::new();
-        ScenarioExecutionEngine::new(
+        let engine = ScenarioExecutionEngine::new(
             session,
-            tcgui_shared::identity::local_origin_from_seed("test-backend"),
+            local_origin,
             "test-backend".to_string(),
             tc_manager,
-        )
+        );
+        (engine, applies)
+    }
+
+    /// Collect the execution updates the engine publishes for default/lo
+    async fn record_updates(
+        engine: &ScenarioExecutionEngine,
+    ) -> Arc<std::sync::Mutex<Vec<ScenarioExecution>>> {
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = updates.clone();
+        let topic = tc::key(
+            &engine.local_origin,
+            &tc::Subject::execution("default", "lo"),
+        );
+        engine
+            .session
+            .declare_subscriber(topic.as_keyexpr())
+            .callback(move |sample| {
+                let update: tcgui_shared::scenario::ScenarioExecutionUpdate =
+                    serde_json::from_slice(&sample.payload().to_bytes()).unwrap();
+                sink.lock().unwrap().push(update.execution);
+            })
+            .background()
+            .await
+            .unwrap();
+        updates
+    }
+
+    /// A scenario with a single short step, run as `repeat` says
+    fn create_repeating_scenario(repeat: ScenarioRepeat) -> NetworkScenario {
+        let mut scenario = NetworkScenario::new(
+            "repeat-test".to_string(),
+            "Repeat Test".to_string(),
+            "A single short step, repeated".to_string(),
+        );
+        let mut tc_config = TcNetemConfig::new();
+        tc_config.loss.enabled = true;
+        tc_config.loss.percentage = 1.0;
+        scenario.add_step(ScenarioStep::new(20, "Step".to_string(), tc_config));
+        scenario.repeat = repeat;
+        scenario
+    }
+
+    fn loss(percentage: f32) -> TcNetemConfig {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = percentage;
+        config
+    }
+
+    #[test]
+    fn test_ramp_interpolates_from_the_previous_step() {
+        // 0% to 10% loss over 2s with a 1s tick: 5% half way, then 10%
+        let step = ScenarioStep::new(2000, "Ramp".to_string(), loss(10.0)).with_ramp(1000);
+        let applies = step_applies(&step, &TcNetemConfig::new());
+        let losses: Vec<_> = applies
+            .iter()
+            .map(|(offset, config)| (*offset, config.as_ref().map(|c| c.loss.percentage)))
+            .collect();
+        assert_eq!(
+            losses,
+            [(1000, Some(5.0)), (2000, Some(10.0)), (2000, None)]
+        );
+        assert!(applies[0].1.as_ref().unwrap().loss.enabled);
+        assert_eq!(applies[1].1.as_ref(), Some(&step.tc_config));
+
+        // Without a ramp the step applies at once
+        let step = ScenarioStep::new(2000, "Hold".to_string(), loss(10.0));
+        let offsets: Vec<_> = step_applies(&step, &loss(50.0))
+            .iter()
+            .map(|(offset, config)| (*offset, config.is_some()))
+            .collect();
+        assert_eq!(offsets, [(0, true), (2000, false)]);
+    }
+
+    #[test]
+    fn test_ramp_out_and_clamping() {
+        let half_way = RampPoint {
+            offset_ms: 500,
+            progress: 0.5,
+        };
+
+        // A feature the step disables ramps to zero, and is off at the end
+        let mut from = loss(40.0);
+        from.delay.enabled = true;
+        from.delay.base_ms = 200.0;
+        let to = loss(40.0);
+        let ramped = ramped_config(&from, &to, &half_way);
+        assert!(ramped.delay.enabled);
+        assert_eq!(ramped.delay.base_ms, 100.0);
+        assert_eq!(ramped.loss.percentage, 40.0);
+        let end = RampPoint {
+            offset_ms: 1000,
+            progress: 1.0,
+        };
+        assert_eq!(ramped_config(&from, &to, &end), to);
+
+        // Out of range values are clamped
+        let ramped = ramped_config(&loss(-100.0), &loss(400.0), &half_way);
+        assert_eq!(ramped.loss.percentage, 100.0);
+
+        // Rates ramp between absolute rates, rounded to whole kbps
+        let mut from = TcNetemConfig::new();
+        from.rate_limit.enabled = true;
+        from.rate_limit.rate_kbps = 1;
+        let mut to = from.clone();
+        to.rate_limit.rate_kbps = 4;
+        assert_eq!(ramped_config(&from, &to, &half_way).rate_limit.rate_kbps, 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_ramped_step_applies_intermediate_configurations() {
+        let (engine, applies) = create_recording_test_engine();
+        let mut scenario = NetworkScenario::new(
+            "ramp".to_string(),
+            "Ramp".to_string(),
+            "Loss ramping up".to_string(),
+        );
+        scenario.add_step(ScenarioStep::new(100, "Clean".to_string(), loss(0.0)));
+        scenario.add_step(ScenarioStep::new(600, "Ramp".to_string(), loss(10.0)).with_ramp(300));
+
+        engine
+            .start_scenario(scenario, "default".to_string(), "lo".to_string(), false)
+            .await
+            .expect("start should succeed");
+
+        // Half way through the ramp only the intermediate loss is applied
+        tokio::time::sleep(Duration::from_millis(550)).await;
+        let losses: Vec<_> = applies
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|config| config.loss.percentage)
+            .collect();
+        assert_eq!(losses, [0.0, 5.0]);
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let last = applies.lock().unwrap().last().cloned();
+        assert_eq!(last.map(|config| config.loss.percentage), Some(10.0));
+        assert!(engine.get_execution_status("default", "lo").await.is_none());
     }
 
     #[test]
@@ -1058,6 +1640,80 @@ mod tests {
         // because the scenario failed before we could pause it (expected without sudo)
     }
 
+    /// A scenario of a 300ms step followed by a 50ms one
+    fn create_pausable_scenario() -> NetworkScenario {
+        let mut scenario = create_fast_test_scenario();
+        scenario.steps[0].duration_ms = 300;
+        scenario
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_pause_during_step_holds_the_step() {
+        let engine = create_test_engine();
+        let updates = record_updates(&engine).await;
+
+        engine
+            .start_scenario(
+                create_pausable_scenario(),
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(engine.pause_scenario("default", "lo").await.unwrap());
+
+        // Well past the step's 300ms, the execution is still paused in it
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        {
+            let updates = updates.lock().unwrap();
+            assert!(updates.iter().all(|e| e.current_step == 0));
+            let last = updates.last().unwrap();
+            assert!(matches!(last.state, ExecutionState::Paused { .. }));
+            // Paused about 100ms into the 350ms scenario
+            assert!(last.calculate_progress() < 50.0);
+        }
+
+        engine.stop_scenario("default", "lo").await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_resume_continues_the_step_where_it_paused() {
+        let engine = create_test_engine();
+        let updates = record_updates(&engine).await;
+
+        engine
+            .start_scenario(
+                create_pausable_scenario(),
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(engine.pause_scenario("default", "lo").await.unwrap());
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(engine.resume_scenario("default", "lo").await.unwrap());
+
+        // About 200ms of the step remain: the pause did not use them up...
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(
+            updates.lock().unwrap().iter().all(|e| e.current_step == 0),
+            "the step should not skip ahead by the paused time"
+        );
+
+        // ...nor did the step start over
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let updates = updates.lock().unwrap();
+        let last = updates.last().unwrap();
+        assert!(matches!(last.state, ExecutionState::Completed));
+        // The task sees the pause and the resume a little after they are sent
+        assert!(last.paused_ms >= 350, "paused for {}ms", last.paused_ms);
+        assert!(updates.iter().any(|e| e.current_step == 1));
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_pause_nonexistent_scenario_returns_false() {
         let engine = create_test_engine();
@@ -1132,7 +1788,6 @@ mod tests {
         let engine = create_test_engine();
 
         // Create a very fast scenario that will complete quickly
-        // Note: The scenario will fail TC commands (no sudo), but we're testing cleanup
         let mut scenario = NetworkScenario::new(
             "cleanup-test".to_string(),
             "Cleanup Test".to_string(),
@@ -1155,8 +1810,7 @@ mod tests {
             .await;
         assert!(result1.is_ok(), "First start should succeed");
 
-        // Wait for the scenario to complete or fail (TC commands will fail without sudo,
-        // which triggers cleanup)
+        // Wait for the scenario to complete, which triggers cleanup
         tokio::time::sleep(Duration::from_millis(500)).await;
 
         // Verify the execution was cleaned up
@@ -1182,6 +1836,40 @@ mod tests {
         engine.stop_scenario("default", "lo").await.ok();
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_verified_step_that_did_not_apply_aborts_the_run() {
+        let engine = create_test_engine();
+        let updates = record_updates(&engine).await;
+
+        // The stand-in TC queryable acknowledges the step without applying
+        // anything, so reading it back finds no loss on lo
+        let mut scenario = create_test_scenario();
+        scenario.verify_steps = true;
+        engine
+            .start_scenario(scenario, "default".to_string(), "lo".to_string(), false)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert!(
+            !engine
+                .active_executions
+                .read()
+                .await
+                .contains_key("default/lo"),
+            "a step that did not take effect should end the run"
+        );
+        let updates = updates.lock().unwrap();
+        let last = updates.last().expect("execution updates were published");
+        let ExecutionState::Failed { ref error } = last.state else {
+            panic!("expected a failed execution, got {:?}", last.state);
+        };
+        assert_eq!(error.step_index, Some(0));
+        assert!(error.message.contains("loss"), "{}", error.message);
+        assert_eq!(last.stats.tc_operations, 1);
+        assert_eq!(last.stats.failed_operations, 1);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_stop_cleans_up_execution() {
         let engine = create_test_engine();
@@ -1225,4 +1913,193 @@ mod tests {
         // Clean up
         engine.stop_scenario("default", "lo").await.ok();
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_repeat_count_runs_the_steps_that_many_times() {
+        let engine = create_test_engine();
+        let updates = record_updates(&engine).await;
+
+        engine
+            .start_scenario(
+                create_repeating_scenario(ScenarioRepeat::Count(3)),
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let updates = updates.lock().unwrap();
+        let running: Vec<_> = updates
+            .iter()
+            .filter(|e| matches!(e.state, ExecutionState::Running))
+            .collect();
+        // Each cycle starts at the first step with nothing completed...
+        let mut starts: Vec<u32> = running
+            .iter()
+            .filter(|e| e.stats.steps_completed == 0)
+            .map(|e| e.loop_iteration)
+            .collect();
+        starts.dedup();
+        assert_eq!(starts, [0, 1, 2]);
+        // ...and ends once its last step has run
+        let ends: Vec<u32> = running
+            .iter()
+            .filter(|e| e.stats.progress_percent == 100.0)
+            .map(|e| e.loop_iteration)
+            .collect();
+        assert_eq!(ends, [0, 1, 2]);
+
+        let last = updates.last().expect("execution updates were published");
+        assert!(matches!(last.state, ExecutionState::Completed));
+        assert_eq!(last.loop_iteration, 2);
+        assert_eq!(last.stats.tc_operations, 3);
+        assert!(!last.loop_execution);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_stop_breaks_a_forever_loop() {
+        let engine = create_test_engine();
+        let updates = record_updates(&engine).await;
+
+        engine
+            .start_scenario(
+                create_repeating_scenario(ScenarioRepeat::Forever),
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+            )
+            .await
+            .unwrap();
+        // Stop once it has started over at least once
+        let mut looped = false;
+        for _ in 0..100 {
+            if updates.lock().unwrap().iter().any(|e| e.loop_iteration > 0) {
+                looped = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(looped, "should have looped before the stop");
+        assert!(engine.stop_scenario("default", "lo").await.unwrap());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(engine.get_execution_status("default", "lo").await.is_none());
+        let published = updates.lock().unwrap().len();
+        let last = updates.lock().unwrap().last().cloned().unwrap();
+        assert!(matches!(last.state, ExecutionState::Stopped));
+        assert!(last.loop_execution);
+        assert!(last.loop_iteration > 0);
+
+        // Nothing runs after the stop
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(updates.lock().unwrap().len(), published);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_schedule_scenario_waits_and_can_be_cancelled() {
+        let engine = create_test_engine();
+        let scenario = create_fast_test_scenario();
+        let start_at = now_millis() + 60_000;
+
+        let key = engine
+            .schedule_scenario(
+                scenario,
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+                start_at,
+            )
+            .await
+            .expect("schedule should succeed");
+
+        let status = engine.get_execution_status("default", "lo").await.unwrap();
+        assert!(
+            matches!(status.state, ExecutionState::Scheduled { start_at: at } if at == start_at)
+        );
+
+        // A scheduled execution cannot be paused, but it occupies the interface
+        assert!(!engine.pause_scenario("default", "lo").await.unwrap());
+        let duplicate = engine
+            .start_scenario(
+                create_fast_test_scenario(),
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+            )
+            .await;
+        assert!(duplicate.is_err());
+
+        // Cancelling removes it before it ever runs
+        assert!(engine.stop_scenario("default", "lo").await.unwrap());
+        let executions = engine.active_executions.read().await;
+        assert!(!executions.contains_key(&key));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_start_delay_applies_no_tc_until_it_elapses() {
+        let (engine, applies) = create_recording_test_engine();
+        let start_at = requested_start(None, Some(300), now_millis())
+            .unwrap()
+            .expect("a delay schedules the start");
+
+        engine
+            .schedule_scenario(
+                create_fast_test_scenario(),
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+                start_at,
+            )
+            .await
+            .expect("schedule should succeed");
+
+        // Armed and waiting: nothing applied yet
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let status = engine.get_execution_status("default", "lo").await.unwrap();
+        assert!(matches!(status.state, ExecutionState::Scheduled { .. }));
+        assert!(applies.lock().unwrap().is_empty());
+
+        // Once the delay has elapsed the steps run
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(!applies.lock().unwrap().is_empty());
+
+        engine.stop_scenario("default", "lo").await.ok();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_schedule_scenario_in_past_starts_immediately() {
+        let engine = create_test_engine();
+        let scenario = create_fast_test_scenario();
+
+        engine
+            .schedule_scenario(
+                scenario,
+                "default".to_string(),
+                "lo".to_string(),
+                false,
+                now_millis().saturating_sub(60_000),
+            )
+            .await
+            .expect("schedule should succeed");
+
+        // The task fires right away and moves the execution to Running
+        let mut running = false;
+        for _ in 0..50 {
+            match engine.get_execution_status("default", "lo").await {
+                Some(execution) if matches!(execution.state, ExecutionState::Running) => {
+                    running = true;
+                    break;
+                }
+                // Already finished is fine too: it clearly did not wait
+                None => {
+                    running = true;
+                    break;
+                }
+                _ => tokio::time::sleep(Duration::from_millis(20)).await,
+            }
+        }
+        assert!(running, "past start time should start immediately");
+    }
 }
@@ -181,13 +181,17 @@ impl ScenarioManager {
         self.storage.delete_scenario(id).await
     }
 
-    /// Start executing a scenario on specified interface
+    /// Start executing a scenario on specified interface.
+    ///
+    /// With `start_at` (Unix timestamp in milliseconds) the execution is
+    /// scheduled and only starts once that time is reached.
     pub async fn start_scenario_execution(
         &self,
         scenario_id: &str,
         namespace: String,
         interface: String,
         loop_execution: bool,
+        start_at: Option<u64>,
     ) -> Result<String> {
         // Get the scenario from storage or templates
         let scenario = self
@@ -195,9 +199,18 @@ impl ScenarioManager {
             .await?
             .ok_or_else(|| anyhow::anyhow!("Scenario '{}' not found", scenario_id))?;
 
-        self.execution_engine
-            .start_scenario(scenario, namespace, interface, loop_execution)
-            .await
+        match start_at {
+            Some(start_at) => {
+                self.execution_engine
+                    .schedule_scenario(scenario, namespace, interface, loop_execution, start_at)
+                    .await
+            }
+            None => {
+                self.execution_engine
+                    .start_scenario(scenario, namespace, interface, loop_execution)
+                    .await
+            }
+        }
     }
 
     /// Stop scenario execution
@@ -12,7 +12,7 @@ use tcgui_shared::identity::LocalOrigin;
 use tcgui_shared::registry::tc;
 use tcgui_shared::scenario::{
     ScenarioError, ScenarioExecutionRequest, ScenarioExecutionResponse, ScenarioExecutionUpdate,
-    ScenarioRequest, ScenarioResponse,
+    ScenarioRequest, ScenarioResponse, requested_start,
 };
 use zenkey::ConcreteOrigin as _;
 use zenoh::key_expr::OwnedKeyExpr;
@@ -402,30 +402,69 @@ impl ScenarioExecutionHandlers {
                 namespace,
                 interface,
                 loop_execution,
+                start_at,
+                start_after_ms,
             } => {
                 info!(
-                    "Starting scenario '{}' on {}:{} (loop: {})",
-                    scenario_id, namespace, interface, loop_execution
+                    "Starting scenario '{}' on {}:{} (loop: {}, start_at: {:?}, start_after_ms: {:?})",
+                    scenario_id, namespace, interface, loop_execution, start_at, start_after_ms
                 );
+
+                // A start delay counts from now; a start time that has
+                // already passed starts immediately
+                let now_ms = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                let start_at = match requested_start(start_at, start_after_ms, now_ms) {
+                    Ok(start_at) => start_at,
+                    Err(error) => return ScenarioExecutionResponse::Error { error },
+                };
+                let (schedule_at, note) = match start_at {
+                    Some(at) if at > now_ms => (Some(at), None),
+                    Some(at) => {
+                        let note = format!(
+                            "Requested start time was {}s in the past; started immediately",
+                            (now_ms - at) / 1000
+                        );
+                        warn!("{}", note);
+                        (None, Some(note))
+                    }
+                    None => (None, None),
+                };
+
                 match scenario_manager
-                    .start_scenario_execution(&scenario_id, namespace, interface, loop_execution)
+                    .start_scenario_execution(
+                        &scenario_id,
+                        namespace,
+                        interface,
+                        loop_execution,
+                        schedule_at,
+                    )
                     .await
                 {
-                    Ok(execution_id) => {
-                        // Get scenario duration for estimated time
-                        let estimated_duration = scenario_manager
-                            .get_scenario(&scenario_id)
-                            .await
-                            .ok()
-                            .flatten()
-                            .map(|s| s.estimated_total_duration_ms())
-                            .unwrap_or(0);
-
-                        ScenarioExecutionResponse::Started {
+                    Ok(execution_id) => match schedule_at {
+                        Some(start_at) => ScenarioExecutionResponse::Scheduled {
                             execution_id,
-                            estimated_duration_ms: estimated_duration,
+                            start_at,
+                        },
+                        None => {
+                            // Get scenario duration for estimated time
+                            let estimated_duration = scenario_manager
+                                .get_scenario(&scenario_id)
+                                .await
+                                .ok()
+                                .flatten()
+                                .map(|s| s.estimated_total_duration_ms())
+                                .unwrap_or(0);
+
+                            ScenarioExecutionResponse::Started {
+                                execution_id,
+                                estimated_duration_ms: estimated_duration,
+                                note,
+                            }
                         }
-                    }
+                    },
                     Err(e) => {
                         let err_str = e.to_string();
                         error!("Failed to start scenario execution: {}", err_str);
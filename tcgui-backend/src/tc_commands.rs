@@ -9,23 +9,60 @@
 //!
 //! * **Multi-namespace support**: Execute TC commands in default and named namespaces
 //! * **Netem simulation**: Packet loss, delay, duplication, reordering, corruption
+//! * **TBF fast path**: Rate-limit-only configs use a token bucket filter instead of netem
+//! * **HTB shaping**: A rate limit can be shaped by an HTB class with netem as its leaf
+//!   (see [`QdiscKind::Htb`])
+//! * **Ingress impairment**: Received traffic is redirected to an IFB device and
+//!   impaired on its egress (see [`TcCommandManager::apply_netem`])
+//! * **Flow filters**: An impairment can be limited to matching flows by a PRIO
+//!   root and a u32 filter (see [`TcCommandManager::apply_filtered_netem`])
+//! * **Atomic swaps**: A new configuration replaces the old one in a single request, so
+//!   the link is never briefly unshaped (see [`RootSwap`])
 //! * **Native netlink**: Uses nlink for direct kernel communication (no process spawning)
+//! * **Delay distributions, loss models and slots**: Jitter shaped by
+//!   iproute2's distribution tables, Gilbert-Elliott burst loss and slotted
+//!   delivery (see [`ExtendedNetem`])
 //! * **Comprehensive feedback**: Detailed success/error reporting to frontend
 //! * **Robust error handling**: Graceful handling of common TC command failures
 
 use anyhow::Result;
 use nlink::TcHandle;
+use nlink::TcMessage;
 use nlink::netlink::Connection;
+use nlink::netlink::MessageBuilder;
 use nlink::netlink::Route;
+use nlink::netlink::action::{ActionList, MirredAction};
+use nlink::netlink::filter::{FilterConfig, U32Filter};
+use nlink::netlink::link::IfbLink;
 use nlink::netlink::namespace::NamespaceSpec;
-use nlink::netlink::tc::NetemConfig;
-use nlink::netlink::tc_options::{NetemOptions, QdiscOptions};
-use nlink::util::{Percent, Rate};
-use std::path::Path;
+use nlink::netlink::tc::{
+    CakeConfig, FqCodelConfig, HtbClassConfig, HtbQdiscConfig, IngressConfig, NetemConfig,
+    PrioConfig, QdiscConfig, SfqConfig, TbfConfig,
+};
+use nlink::netlink::tc_options::{
+    NetemLossModel, NetemOptions, QdiscOptions, parse_htb_class_options,
+};
+use nlink::netlink::types::tc::filter::matchall::TCA_MATCHALL_ACT;
+use nlink::netlink::types::tc::filter::u32::{TCA_U32_CLASSID, TCA_U32_SEL};
+use nlink::netlink::types::tc::qdisc::fq_codel::TCA_FQ_CODEL_ECN;
+use nlink::netlink::types::tc::qdisc::netem::{
+    NETEM_LOSS_GE, TCA_NETEM_DELAY_DIST, TCA_NETEM_LOSS, TCA_NETEM_SLOT, TcNetemGeModel,
+    percent_to_prob,
+};
+use nlink::util::{Bytes, Percent, Rate};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tracing::{info, instrument, warn};
 
-use tcgui_shared::{TcNetemConfig, TcValidate, errors::TcguiError};
+use tcgui_shared::qdisc_tree::{TcObjectKind, TcTreeEntry};
+use tcgui_shared::{
+    CakeDiffserv, CakeParams, DelayDistribution, FqCodelParams, GeModel, PrioParams, RateLimitMode,
+    RedParams, SfqParams, TcDirection, TcFeature, TcFilterProtocol, TcFilterSpec, TcNetemConfig,
+    TcSlotConfig, TcValidate, TcValidationError, errors::TcguiError,
+};
 
 /// Build a `TcCommandError` from a failed kernel TC operation.
 ///
@@ -44,6 +81,1120 @@ fn tc_kernel_err(context: &str, e: &nlink::netlink::Error) -> TcguiError {
     }
 }
 
+/// Words in an error message that point at one impairment. Checked in this
+/// order: reorder errors mention the delay they depend on ("reordering not
+/// possible without specifying some delay"), so reorder must win, and slot
+/// errors talk about the slot's delays.
+const FEATURE_KEYWORDS: &[(TcFeature, &[&str])] = &[
+    (TcFeature::Slot, &["slot", "slots"]),
+    (TcFeature::Reorder, &["reorder", "reordering", "gap"]),
+    (
+        TcFeature::Duplicate,
+        &["duplicate", "duplicates", "duplicating", "duplication"],
+    ),
+    (TcFeature::Corrupt, &["corrupt", "corruption"]),
+    (TcFeature::Loss, &["loss", "gemodel"]),
+    (TcFeature::Delay, &["delay", "latency", "jitter"]),
+    (TcFeature::RateLimit, &["rate", "tbf", "burst"]),
+];
+
+/// Which impairment a failed apply's error message (kernel ext_ack text,
+/// `tc`-style parameter errors or our own validation) is about, if any.
+///
+/// netem is configured in one atomic request, so the kernel rejects the
+/// whole change; this only tells the frontend which control to point at.
+pub fn rejected_feature(message: &str) -> Option<TcFeature> {
+    let lower = message.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    FEATURE_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| words.iter().any(|w| keywords.contains(w)))
+        .map(|(feature, _)| *feature)
+}
+
+/// How `tc` reports a keyword it can't parse, each followed by the keyword
+/// in double quotes: an unknown option (`What is "slot"?`), a value form it
+/// doesn't know (`Illegal "loss"` for `loss gemodel ...`), an unknown
+/// qdisc kind (`Unknown qdisc "cake", hence option ...`) and an unknown
+/// parameter.
+const SYNTAX_ERROR_MARKERS: [&str; 4] = [
+    "What is \"",
+    "Illegal \"",
+    "Unknown qdisc \"",
+    "Unknown parameter \"",
+];
+
+/// A `tc` command that failed because the installed iproute2 doesn't know
+/// part of its syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcSyntaxMismatch {
+    /// The keyword `tc` stopped at (an option such as `"slot"` or a qdisc
+    /// kind such as `"cake"`)
+    pub keyword: String,
+    /// The impairment the keyword belongs to, if it is a netem one, for
+    /// the frontend to point at
+    pub feature: Option<TcFeature>,
+}
+
+impl TcSyntaxMismatch {
+    /// An actionable message naming the installed iproute2 version, when
+    /// the capability probe found one.
+    pub fn message(&self, tc_version: Option<&str>) -> String {
+        format!(
+            "Your iproute2 version ({}) doesn't support \"{}\"; install a newer iproute2 to use it",
+            tc_version.unwrap_or("unknown"),
+            self.keyword
+        )
+    }
+}
+
+/// Recognize a `tc` failure caused by syntax the installed iproute2
+/// predates, from the command's stderr (or an error message carrying it).
+///
+/// Commands only reach `tc` after validation, so a keyword it can't parse
+/// points at an older iproute2 rather than a bad value.
+pub fn syntax_mismatch(stderr: &str) -> Option<TcSyntaxMismatch> {
+    let keyword = SYNTAX_ERROR_MARKERS.iter().find_map(|marker| {
+        let (_, rest) = stderr.split_once(marker)?;
+        let (keyword, _) = rest.split_once('"')?;
+        (!keyword.is_empty()).then(|| keyword.to_string())
+    })?;
+    Some(TcSyntaxMismatch {
+        feature: rejected_feature(&keyword),
+        keyword,
+    })
+}
+
+/// Root qdisc used to realise a [`TcNetemConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QdiscKind {
+    /// Full netem qdisc (any impairment besides a plain rate limit)
+    Netem,
+    /// Token bucket filter for rate-limit-only configs
+    Tbf,
+    /// HTB root with one class at the rate limit and a netem leaf below it
+    /// carrying the other impairments, for [`RateLimitMode::Htb`]
+    Htb,
+}
+
+impl QdiscKind {
+    /// Pick the qdisc for a configuration.
+    ///
+    /// A rate limit shaped by HTB always gets the HTB tree. Otherwise a rate
+    /// limit on its own is shaped by TBF, which is lighter than netem and
+    /// shapes more cleanly; any other active impairment needs netem.
+    pub fn for_config(config: &TcNetemConfig) -> Self {
+        let rate_limited = config.rate_limit.enabled && config.rate_limit.rate_kbps > 0;
+        if rate_limited && config.rate_limit.shaper == RateLimitMode::Htb {
+            return Self::Htb;
+        }
+        let other_impairments = config.loss.is_active()
+            || config.slot.is_active()
+            || (config.delay.enabled && config.delay.base_ms > 0.0)
+            || (config.duplicate.enabled && config.duplicate.percentage > 0.0)
+            || (config.reorder.enabled && config.reorder.percentage > 0.0)
+            || (config.corrupt.enabled && config.corrupt.percentage > 0.0);
+
+        if rate_limited && !other_impairments {
+            Self::Tbf
+        } else {
+            Self::Netem
+        }
+    }
+}
+
+/// How a new root qdisc takes over from the one currently installed.
+///
+/// Both variants are a single `RTM_NEWQDISC` request with the create and
+/// replace flags set (`tc qdisc replace`), which the kernel applies under its
+/// lock: traffic goes from the old impairment straight to the new one and
+/// never sees an unshaped link. No apply path deletes the root first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootSwap {
+    /// `tc qdisc replace dev X root <kind> ...`. With nothing (or the
+    /// default qdisc) installed, or a qdisc of another kind, the kernel
+    /// creates the new qdisc and grafts it over the old one. With the same
+    /// kind it changes the parameters in place, keeping queued packets.
+    Replace,
+    /// `tc qdisc replace dev X root handle N: <kind> ...` with a handle no
+    /// qdisc uses: the kernel can't treat it as a change, so it grafts a
+    /// fresh qdisc even over one of the same kind. Needed when netem
+    /// parameters are dropped (a change keeps every parameter it isn't
+    /// given) or when the installed tree has children (e.g. an HTB shaper).
+    /// Packets still queued in the old qdisc (delayed ones, say) are
+    /// dropped with it; that is the only cost of the swap.
+    Graft(TcHandle),
+}
+
+impl RootSwap {
+    /// Pick the swap for installing a root qdisc.
+    ///
+    /// `same_kind` is whether the installed root qdisc is of the target
+    /// kind, `removes_parameters` whether the new configuration drops a
+    /// parameter the installed one sets, `has_children` whether qdiscs hang
+    /// below the installed root, and `used_majors` the handle majors of every
+    /// qdisc on the interface.
+    pub fn plan(
+        same_kind: bool,
+        removes_parameters: bool,
+        has_children: bool,
+        used_majors: &[u16],
+    ) -> Self {
+        if same_kind && (removes_parameters || has_children) {
+            Self::Graft(unused_handle(used_majors))
+        } else {
+            Self::Replace
+        }
+    }
+}
+
+/// What is installed on an interface, as far as [`RootSwap::plan`] cares.
+struct RootLayout {
+    /// Kind of the root qdisc, if any
+    root_kind: Option<String>,
+    /// Options of the root qdisc, if it is a netem
+    netem: Option<NetemOptions>,
+    /// Whether qdiscs hang below the root qdisc
+    has_children: bool,
+    /// Handle majors of every qdisc on the interface
+    used_majors: Vec<u16>,
+}
+
+/// The lowest qdisc handle (`N:`) whose major isn't in `used_majors`.
+fn unused_handle(used_majors: &[u16]) -> TcHandle {
+    let major = (1..=u16::MAX)
+        .find(|major| !used_majors.contains(major))
+        .unwrap_or(1);
+    TcHandle::major_only(major)
+}
+
+/// Minor of the HTB class shaping a [`QdiscKind::Htb`] rate limit, which is
+/// also the HTB qdisc's default class.
+const HTB_CLASS_MINOR: u16 = 1;
+
+/// PRIO band a filtered impairment's netem hangs off: one past the three
+/// bands the default priomap uses, so it only gets what a filter sends it.
+const FILTER_BAND_MINOR: u16 = 4;
+
+/// Name of the IFB device receiving the ingress traffic of the interface
+/// with index `ifindex` (at most 15 characters, as the kernel requires)
+fn ifb_name(ifindex: u32) -> String {
+    format!("tcifb{}", ifindex)
+}
+
+/// Handle of the ingress qdisc, which the redirect filter hangs off
+const INGRESS_HANDLE: TcHandle = TcHandle::major_only(0xffff);
+
+/// `ETH_P_ALL`: the redirect filter takes every protocol
+const ETH_P_ALL: u16 = 0x0003;
+
+/// `ETH_P_IP`: protocol of a flow filter on IPv4 addresses
+const ETH_P_IP: u16 = 0x0800;
+
+/// `ETH_P_IPV6`: protocol of a flow filter on IPv6 addresses
+const ETH_P_IPV6: u16 = 0x86dd;
+
+/// A matchall filter redirecting every packet to the egress of the device
+/// with index `ifindex`. nlink's `MatchallFilter` only takes a goto-chain
+/// action, so the mirred action is nested here.
+#[derive(Debug, Clone)]
+struct RedirectAll {
+    ifindex: u32,
+}
+
+impl FilterConfig for RedirectAll {
+    fn kind(&self) -> &'static str {
+        "matchall"
+    }
+
+    fn classid(&self) -> Option<u32> {
+        None
+    }
+
+    fn protocol(&self) -> Option<u16> {
+        Some(ETH_P_ALL)
+    }
+
+    fn priority(&self) -> Option<u16> {
+        Some(1)
+    }
+
+    fn write_options(&self, builder: &mut MessageBuilder) -> nlink::netlink::Result<()> {
+        let token = builder.nest_start(TCA_MATCHALL_ACT);
+        ActionList::new()
+            .with(MirredAction::redirect_by_index(self.ifindex))
+            .write_to(builder)?;
+        builder.nest_end(token);
+        Ok(())
+    }
+}
+
+/// The commands equivalent to [`TcCommandManager::redirect_ingress`]
+/// sending everything `interface` receives to the egress of `ifb`, for
+/// display: the device, an ingress qdisc and a filter matching every
+/// packet
+fn ingress_redirect_commands(interface: &str, ifb: &str) -> Vec<String> {
+    vec![
+        format!("ip link add {} type ifb", ifb),
+        format!("ip link set {} up", ifb),
+        format!("tc qdisc replace dev {} ingress", interface),
+        format!(
+            "tc filter replace dev {} parent ffff: protocol all prio 1 matchall \
+             action mirred egress redirect dev {}",
+            interface, ifb
+        ),
+    ]
+}
+
+/// The u32 keys matching `filter`'s flows, as (value, mask, offset from
+/// the start of the IP header): what `tc` sends for
+/// [`TcFilterSpec::u32_matches`]. Like `tc`'s `match ip sport`, ports are
+/// looked for right after a header without options. A filter matching
+/// everything gets a key masking nothing, as the kernel wants one.
+fn u32_keys(filter: &TcFilterSpec) -> Vec<(u32, u32, i32)> {
+    let ipv6 = filter.is_ipv6();
+    let (src_offset, dst_offset, port_offset) = if ipv6 { (8, 24, 40) } else { (12, 16, 20) };
+    let mut keys = Vec::new();
+    for (ip, offset) in [(&filter.src_ip, src_offset), (&filter.dst_ip, dst_offset)] {
+        let Some((addr, len)) = ip.as_deref().and_then(TcFilterSpec::parse_prefix) else {
+            continue;
+        };
+        let octets = match addr {
+            IpAddr::V4(addr) => addr.octets().to_vec(),
+            IpAddr::V6(addr) => addr.octets().to_vec(),
+        };
+        for (word, chunk) in (0..).zip(octets.chunks(4)) {
+            let bits = u32::from(len).saturating_sub(32 * word).min(32);
+            if bits == 0 {
+                break;
+            }
+            let mask = u32::MAX << (32 - bits);
+            let value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            keys.push((value & mask, mask, offset + 4 * word as i32));
+        }
+    }
+    if let Some(port) = filter.src_port {
+        keys.push((u32::from(port) << 16, 0xffff_0000, port_offset));
+    }
+    if let Some(port) = filter.dst_port {
+        keys.push((u32::from(port), 0x0000_ffff, port_offset));
+    }
+    if let Some(protocol) = filter.protocol {
+        let number = u32::from(protocol.number(ipv6));
+        keys.push(if ipv6 {
+            (number << 8, 0x0000_ff00, 4)
+        } else {
+            (number << 16, 0x00ff_0000, 8)
+        });
+    }
+    if keys.is_empty() {
+        keys.push((0, 0, 0));
+    }
+    keys
+}
+
+/// The flows a u32 filter built from [`u32_keys`] matches, read back from
+/// its keys. A key `u32_keys` wouldn't send leaves the flows unknown.
+fn filter_of_u32_keys(ipv6: bool, keys: &[(u32, u32, i32)]) -> Option<TcFilterSpec> {
+    let (src_offset, dst_offset, port_offset) = if ipv6 { (8, 24, 40) } else { (12, 16, 20) };
+    let words = if ipv6 { 4 } else { 1 };
+    let mut filter = TcFilterSpec::default();
+    // Address words and prefix lengths, source then destination
+    let mut prefixes = [([0u32; 4], 0u32), ([0u32; 4], 0u32)];
+    for &(value, mask, offset) in keys {
+        let side = [src_offset, dst_offset]
+            .iter()
+            .position(|base| (*base..*base + 4 * words).contains(&offset));
+        match (offset, mask, side) {
+            (0, 0, _) => {}
+            (_, 0xffff_0000, _) if offset == port_offset => {
+                filter.src_port = Some((value >> 16) as u16);
+            }
+            (_, 0x0000_ffff, _) if offset == port_offset => filter.dst_port = Some(value as u16),
+            (4, 0x0000_ff00, _) if ipv6 => {
+                filter.protocol = Some(protocol_of((value >> 8) as u8, ipv6)?);
+            }
+            (8, 0x00ff_0000, _) if !ipv6 => {
+                filter.protocol = Some(protocol_of((value >> 16) as u8, ipv6)?);
+            }
+            // A prefix mask: leading ones only
+            (_, _, Some(side)) if mask.leading_ones() + mask.trailing_zeros() == 32 => {
+                let base = [src_offset, dst_offset][side];
+                let (address, len) = &mut prefixes[side];
+                address[((offset - base) / 4) as usize] = value;
+                *len += mask.leading_ones();
+            }
+            _ => return None,
+        }
+    }
+    for ((address, len), ip) in prefixes
+        .into_iter()
+        .zip([&mut filter.src_ip, &mut filter.dst_ip])
+    {
+        if len == 0 {
+            continue;
+        }
+        let addr = if ipv6 {
+            IpAddr::from(Ipv6Addr::from(
+                address
+                    .iter()
+                    .fold(0u128, |bits, word| bits << 32 | u128::from(*word)),
+            ))
+        } else {
+            IpAddr::from(Ipv4Addr::from(address[0]))
+        };
+        let full = if ipv6 { 128 } else { 32 };
+        *ip = Some(if len == full {
+            addr.to_string()
+        } else {
+            format!("{}/{}", addr, len)
+        });
+    }
+    Some(filter)
+}
+
+/// The transport protocol with IP protocol (or IPv6 next header) `number`
+fn protocol_of(number: u8, ipv6: bool) -> Option<TcFilterProtocol> {
+    [
+        TcFilterProtocol::Tcp,
+        TcFilterProtocol::Udp,
+        TcFilterProtocol::Icmp,
+    ]
+    .into_iter()
+    .find(|protocol| protocol.number(ipv6) == number)
+}
+
+/// The keys of a u32 filter's selector (`TCA_U32_SEL`, a `tc_u32_sel`:
+/// a 16-byte header counting the keys, then 16 bytes per key holding the
+/// big-endian mask and value and the offset), as (value, mask, offset).
+fn u32_selector_keys(raw: &[u8]) -> Option<Vec<(u32, u32, i32)>> {
+    let (_, selector) = nested_attrs(raw)
+        .into_iter()
+        .find(|(kind, _)| *kind == TCA_U32_SEL)?;
+    let count = usize::from(*selector.get(2)?);
+    (0..count)
+        .map(|key| {
+            let key = selector.get(16 + 16 * key..32 + 16 * key)?;
+            let be = |at: usize| u32::from_be_bytes(key[at..at + 4].try_into().unwrap());
+            Some((be(4), be(0), u32_at(key, 8)? as i32))
+        })
+        .collect()
+}
+
+/// The `tc` commands building the tree described on
+/// [`TcCommandManager::apply_filtered_netem`] with PRIO root `root` and
+/// netem `leaf`, in the order they would run, for display. Below a PRIO band
+/// there is no HTB class to shape with, so a rate limit is netem's own.
+fn filtered_netem_commands(
+    interface: &str,
+    config: &TcNetemConfig,
+    filter: &TcFilterSpec,
+    root: u16,
+    leaf: u16,
+) -> Vec<String> {
+    let netem = tcgui_shared::TcConfiguration {
+        rate_limit_shaper: RateLimitMode::Netem,
+        ..crate::tc_config::configuration_of(interface, config)
+    };
+    // Everything after the leading `tc qdisc replace ... netem`
+    let options: Vec<String> = netem
+        .command_parts(interface)
+        .into_iter()
+        .skip(1)
+        .map(|part| part.clause)
+        .collect();
+    let matches = match filter.u32_matches() {
+        matches if matches.is_empty() => "match u32 0 0".to_string(),
+        matches => matches.join(" "),
+    };
+    vec![
+        format!(
+            "tc qdisc replace dev {} root handle {:x}: prio bands {}",
+            interface, root, FILTER_BAND_MINOR
+        ),
+        format!(
+            "tc qdisc add dev {} parent {:x}:{:x} handle {:x}: netem{}",
+            interface,
+            root,
+            FILTER_BAND_MINOR,
+            leaf,
+            options
+                .iter()
+                .map(|option| format!(" {}", option))
+                .collect::<String>()
+        ),
+        format!(
+            "tc filter add dev {} parent {:x}: protocol {} prio 1 u32 {} flowid {:x}:{:x}",
+            interface,
+            root,
+            if filter.is_ipv6() { "ipv6" } else { "ip" },
+            matches,
+            root,
+            FILTER_BAND_MINOR
+        ),
+    ]
+}
+
+/// The delay distribution `config` needs a table for: one other than
+/// netem's uniform default, on a delay with jitter.
+fn shaped_distribution(config: &TcNetemConfig) -> Option<DelayDistribution> {
+    Some(config.delay.distribution).filter(|distribution| {
+        config.delay.enabled
+            && config.delay.base_ms > 0.0
+            && config.delay.jitter_ms > 0.0
+            && !distribution.is_uniform()
+    })
+}
+
+/// The slot `config` delivers in, if it has an active one.
+fn active_slot(config: &TcNetemConfig) -> Option<TcSlotConfig> {
+    config.slot.is_active().then(|| config.slot.clone())
+}
+
+/// Whether `config` marks lost packets with ECN: only meaningful while it
+/// drops any.
+fn marks_ecn(config: &TcNetemConfig) -> bool {
+    config.loss.ecn && config.loss.is_active()
+}
+
+/// Directories iproute2 installs its distribution tables to, searched in
+/// order after `$TC_LIB_DIR`.
+const DISTRIBUTION_DIRS: [&str; 3] = ["/usr/lib/tc", "/usr/lib64/tc", "/usr/local/lib/tc"];
+
+/// Largest table the kernel accepts (`NETEM_DIST_MAX`)
+const DISTRIBUTION_MAX_ENTRIES: usize = 16384;
+
+/// Netem option marking ECN-capable packets instead of dropping them
+/// (a `u32` flag, `loss ... ecn` in `tc`)
+const TCA_NETEM_ECN: u16 = 7;
+
+/// Netem option seeding its random number generator (a `u64`, `seed N`
+/// in `tc`)
+const TCA_NETEM_PRNG_SEED: u16 = 14;
+
+/// A netem qdisc with the options nlink's [`NetemConfig`] can't express.
+///
+/// This writes the netem options and then appends what `tc` would send
+/// for `distribution NAME` (`TCA_NETEM_DELAY_DIST`), `loss gemodel ...`
+/// (a nested `TCA_NETEM_LOSS`), `slot ...` (`TCA_NETEM_SLOT`), the
+/// `ecn` loss flag (`TCA_NETEM_ECN`) and `seed N` (`TCA_NETEM_PRNG_SEED`,
+/// left out to let the kernel pick a random seed). The kernel keeps a distribution
+/// table, a slot and the ECN flag across changes that don't send one, and
+/// never reports the table back; a change without a loss model goes back
+/// to random loss. The ECN flag is therefore always sent.
+struct ExtendedNetem {
+    netem: NetemConfig,
+    distribution_table: Option<Vec<i16>>,
+    gemodel: Option<GeModel>,
+    slot: Option<TcSlotConfig>,
+    ecn: bool,
+    seed: Option<u64>,
+}
+
+impl QdiscConfig for ExtendedNetem {
+    fn kind(&self) -> &'static str {
+        self.netem.kind()
+    }
+
+    fn write_options(&self, builder: &mut MessageBuilder) -> nlink::netlink::Result<()> {
+        self.netem.write_options(builder)?;
+        if let Some(table) = &self.distribution_table {
+            let table: Vec<u8> = table.iter().flat_map(|value| value.to_ne_bytes()).collect();
+            builder.append_attr(TCA_NETEM_DELAY_DIST, &table);
+        }
+        if let Some(model) = &self.gemodel {
+            let token = builder.nest_start(TCA_NETEM_LOSS);
+            builder.append_attr(NETEM_LOSS_GE, gemodel_to_kernel(model).as_bytes());
+            builder.nest_end(token);
+        }
+        if let Some(slot) = &self.slot {
+            builder.append_attr(TCA_NETEM_SLOT, &slot_to_kernel(slot));
+        }
+        builder.append_attr(TCA_NETEM_ECN, &u32::from(self.ecn).to_ne_bytes());
+        if let Some(seed) = self.seed {
+            builder.append_attr(TCA_NETEM_PRNG_SEED, &seed.to_ne_bytes());
+        }
+        Ok(())
+    }
+}
+
+/// The kernel's `struct tc_netem_slot`: both delays in nanoseconds, then
+/// the per-slot packet and byte limits (0 for none). The trailing delay
+/// distribution stays zero, so slot delays are spread uniformly.
+fn slot_to_kernel(slot: &TcSlotConfig) -> [u8; 40] {
+    let nanos = |ms: f32| (f64::from(ms) * 1_000_000.0).round() as i64;
+    let mut raw = [0u8; 40];
+    raw[0..8].copy_from_slice(&nanos(slot.min_delay_ms).to_ne_bytes());
+    raw[8..16].copy_from_slice(&nanos(slot.max_delay_ms).to_ne_bytes());
+    raw[16..20].copy_from_slice(&(slot.packets.unwrap_or(0) as i32).to_ne_bytes());
+    raw[20..24].copy_from_slice(&(slot.bytes.unwrap_or(0) as i32).to_ne_bytes());
+    raw
+}
+
+/// The kernel's form of a Gilbert-Elliott model. Like `tc`, the `1-h`
+/// given is sent as `h`, the chance of *keeping* a packet in the bad state.
+fn gemodel_to_kernel(model: &GeModel) -> TcNetemGeModel {
+    let probability = |percent: f32| percent_to_prob(f64::from(percent));
+    TcNetemGeModel {
+        p: probability(model.p),
+        r: probability(model.r),
+        h: probability(100.0 - model.one_h),
+        k1: probability(model.one_k),
+    }
+}
+
+/// The Gilbert-Elliott model an installed netem drops packets with, if any.
+pub fn gemodel_of(opts: &NetemOptions) -> Option<GeModel> {
+    match opts.loss_model()? {
+        NetemLossModel::GilbertElliot { p, r, h, k1 } => Some(GeModel {
+            p: *p as f32,
+            r: *r as f32,
+            one_h: (100.0 - *h) as f32,
+            one_k: *k1 as f32,
+        }),
+        _ => None,
+    }
+}
+
+/// The slotted delivery an installed netem releases packets with, if any.
+pub fn slot_of(opts: &NetemOptions) -> Option<TcSlotConfig> {
+    let slot = opts.slot()?;
+    // The kernel stores an unlimited slot size as `i32::MAX`
+    let limit = |value: i32| {
+        u32::try_from(value)
+            .ok()
+            .filter(|&v| v > 0 && v < i32::MAX as u32)
+    };
+    Some(TcSlotConfig {
+        enabled: true,
+        min_delay_ms: slot.min_delay_ns as f32 / 1_000_000.0,
+        max_delay_ms: slot.max_delay_ns as f32 / 1_000_000.0,
+        packets: limit(slot.max_packets),
+        bytes: limit(slot.max_bytes),
+    })
+}
+
+/// Parse an iproute2 `.dist` file: whitespace separated 16-bit values,
+/// with `#` comment lines.
+fn parse_distribution_table(text: &str) -> Result<Vec<i16>, String> {
+    let table = text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(str::split_whitespace)
+        .map(|value| {
+            value
+                .parse::<i16>()
+                .map_err(|_| format!("invalid table value '{}'", value))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if table.is_empty() {
+        return Err("the table is empty".to_string());
+    }
+    if table.len() > DISTRIBUTION_MAX_ENTRIES {
+        return Err(format!(
+            "the table has {} values, the kernel takes at most {}",
+            table.len(),
+            DISTRIBUTION_MAX_ENTRIES
+        ));
+    }
+    Ok(table)
+}
+
+/// Load the table for `distribution` from the iproute2 library directory.
+fn load_distribution_table(distribution: DelayDistribution) -> Result<Vec<i16>, TcguiError> {
+    let file = format!("{}.dist", distribution.name());
+    let dirs: Vec<PathBuf> = std::env::var_os("TC_LIB_DIR")
+        .map(PathBuf::from)
+        .into_iter()
+        .chain(DISTRIBUTION_DIRS.iter().map(PathBuf::from))
+        .collect();
+    let path = dirs
+        .iter()
+        .map(|dir| dir.join(&file))
+        .find(|path| path.is_file())
+        .ok_or_else(|| TcguiError::TcCommandError {
+            message: format!(
+                "No {} delay distribution table: {} not found in {} (install iproute2 or set TC_LIB_DIR)",
+                distribution,
+                file,
+                dirs.iter()
+                    .map(|dir| dir.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        })?;
+    let text = std::fs::read_to_string(&path).map_err(|e| TcguiError::TcCommandError {
+        message: format!("Failed to read {}: {}", path.display(), e),
+    })?;
+    parse_distribution_table(&text).map_err(|e| TcguiError::TcCommandError {
+        message: format!("Invalid distribution table {}: {}", path.display(), e),
+    })
+}
+
+/// Bucket and queue sizes (in bytes) for a TBF shaping at `rate_kbps`.
+///
+/// The burst covers 10ms of traffic (at least two full-size frames) and the
+/// queue holds a further 50ms on top of it.
+fn tbf_buffer_sizes(rate_kbps: u32) -> (u64, u64) {
+    let bytes_per_sec = u64::from(rate_kbps) * 1000 / 8;
+    let burst = (bytes_per_sec / 100).max(3028);
+    let limit = burst + bytes_per_sec / 20;
+    (burst, limit)
+}
+
+/// Build a rate-limit-only [`TcNetemConfig`], as detected from a TBF qdisc.
+pub fn rate_limit_only_config(rate_kbps: u32) -> TcNetemConfig {
+    TcNetemConfig {
+        rate_limit: tcgui_shared::TcRateLimitConfig {
+            enabled: true,
+            rate_kbps,
+            mode: tcgui_shared::TcRateLimitMode::Absolute,
+            shaper: tcgui_shared::RateLimitMode::Netem,
+        },
+        ..TcNetemConfig::default()
+    }
+}
+
+/// Format a rate in bytes/sec the way `tc` does, e.g. "10mbit" or "512kbit".
+fn format_rate(bytes_per_sec: u64) -> String {
+    let kbit = bytes_per_sec * 8 / 1000;
+    if kbit >= 1000 && kbit.is_multiple_of(1000) {
+        format!("{}mbit", kbit / 1000)
+    } else {
+        format!("{}kbit", kbit)
+    }
+}
+
+/// Active netem parameters, one `tc`-style clause each.
+fn netem_details(netem: &NetemOptions) -> Vec<String> {
+    let mut details = Vec::new();
+    if let Some(delay) = netem.delay() {
+        let mut clause = format!("delay {}ms", delay.as_secs_f64() * 1000.0);
+        if let Some(jitter) = netem.jitter() {
+            clause.push_str(&format!(" {}ms", jitter.as_secs_f64() * 1000.0));
+        }
+        details.push(clause);
+    }
+    let percentages = [
+        ("loss", netem.loss()),
+        ("duplicate", netem.duplicate()),
+        ("reorder", netem.reorder()),
+        ("corrupt", netem.corrupt()),
+    ];
+    for (name, value) in percentages {
+        if let Some(value) = value.filter(|v| *v > 0.0) {
+            details.push(format!("{} {}%", name, value));
+        }
+    }
+    if let Some(rate) = netem.rate_bps().filter(|r| *r > 0) {
+        details.push(format!("rate {}", format_rate(rate)));
+    }
+    if let Some(limit) = netem.limit() {
+        details.push(format!("limit {}", limit));
+    }
+    details
+}
+
+/// `TCA_RED_PARMS`, `TCA_RED_STAB` and `TCA_RED_MAX_P` from
+/// `linux/pkt_sched.h`
+const TCA_RED_PARMS: u16 = 1;
+const TCA_RED_STAB: u16 = 2;
+const TCA_RED_MAX_P: u16 = 3;
+
+/// Split nested netlink attributes into their types and payloads, stopping
+/// at the first malformed one.
+fn nested_attrs(raw: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut attrs = Vec::new();
+    let mut rest = raw;
+    while rest.len() >= 4 {
+        let len = u16::from_ne_bytes([rest[0], rest[1]]) as usize;
+        let kind = u16::from_ne_bytes([rest[2], rest[3]]);
+        if len < 4 || len > rest.len() {
+            break;
+        }
+        attrs.push((kind, &rest[4..len]));
+        // Attributes are padded to 4 bytes
+        rest = rest.get(len.next_multiple_of(4)..).unwrap_or_default();
+    }
+    attrs
+}
+
+/// Read a native-endian u32 at `offset` of an attribute payload
+fn u32_at(payload: &[u8], offset: usize) -> Option<u32> {
+    payload
+        .get(offset..offset + 4)
+        .map(|b| u32::from_ne_bytes(b.try_into().unwrap()))
+}
+
+/// Decode the options of a RED qdisc, which nlink leaves undecoded:
+/// `TCA_RED_PARMS` holds a `tc_red_qopt`
+/// (limit, min and max thresholds as u32, then four u8 fields) and
+/// `TCA_RED_MAX_P` the marking probability as a fraction of 2^32.
+fn parse_red_options(raw: &[u8]) -> Option<RedParams> {
+    let mut params = None;
+    let mut probability = None;
+    for (kind, payload) in nested_attrs(raw) {
+        let u32_at = |offset: usize| u32_at(payload, offset);
+        match kind {
+            TCA_RED_PARMS => {
+                params = Some(RedParams {
+                    limit: u32_at(0),
+                    min: u32_at(4),
+                    max: u32_at(8),
+                    ..RedParams::default()
+                });
+            }
+            TCA_RED_MAX_P => {
+                probability = u32_at(0).map(|p| (p as f64 / 4_294_967_296.0) as f32);
+            }
+            _ => {}
+        }
+    }
+    params.map(|params| RedParams {
+        probability: probability.filter(|p| *p > 0.0),
+        ..params
+    })
+}
+
+/// Bandwidth RED assumes when none is given, as `tc` does (10mbit)
+const RED_DEFAULT_RATE: u64 = 1_250_000;
+
+/// A RED qdisc with the constants `tc` derives from its parameters. nlink's
+/// `RedConfig` fixes the EWMA weight instead of deriving it from `avpkt`
+/// and `burst`, and sends no `TCA_RED_STAB`, which the kernel requires.
+#[derive(Debug, Clone)]
+struct RedQdisc {
+    params: RedParams,
+}
+
+impl QdiscConfig for RedQdisc {
+    fn kind(&self) -> &'static str {
+        "red"
+    }
+
+    fn write_options(&self, builder: &mut MessageBuilder) -> nlink::netlink::Result<()> {
+        let invalid = |message: String| nlink::netlink::Error::InvalidMessage(message);
+        let params = &self.params;
+        let limit = params.limit.unwrap_or_default();
+        let avpkt = params.avpkt.filter(|avpkt| *avpkt > 0).unwrap_or(1000);
+        // Sally Floyd's recommendations, like `tc`
+        let max = params.max.unwrap_or(limit / 4);
+        let min = params.min.unwrap_or(max / 3);
+        let burst = params.burst.unwrap_or((2 * min + max) / (3 * avpkt));
+        let probability = f64::from(params.probability.unwrap_or(0.02));
+        let rate = match &params.bandwidth {
+            Some(bandwidth) => Rate::parse(bandwidth)
+                .map_err(|e| invalid(format!("Invalid RED bandwidth {}: {}", bandwidth, e)))?
+                .as_bytes_per_sec(),
+            None => RED_DEFAULT_RATE,
+        };
+
+        let wlog = red_ewma_log(min, burst, avpkt).ok_or_else(|| {
+            invalid(format!(
+                "RED burst {} is too small, try {}",
+                burst,
+                1 + min / avpkt
+            ))
+        })?;
+        let plog = red_probability_log(min, max, probability)
+            .ok_or_else(|| invalid("RED probability is out of range".to_string()))?;
+        let (scell_log, stab) = red_idle_damping(wlog, avpkt, rate)
+            .ok_or_else(|| invalid("RED bandwidth is out of range".to_string()))?;
+
+        let mut qopt = Vec::with_capacity(16);
+        for value in [limit, min, max] {
+            qopt.extend(value.to_ne_bytes());
+        }
+        // Flags (ECN, harddrop, adaptive) are left off
+        qopt.extend([wlog, plog, scell_log, 0]);
+        builder.append_attr(TCA_RED_PARMS, &qopt);
+        builder.append_attr(TCA_RED_STAB, &stab);
+        builder.append_attr_u32(
+            TCA_RED_MAX_P,
+            (probability * f64::from(u32::MAX)).clamp(0.0, f64::from(u32::MAX)) as u32,
+        );
+        Ok(())
+    }
+}
+
+/// Log2 of the inverse EWMA weight the average queue is computed with:
+/// the lightest weight for which `burst` packets of `avpkt` bytes arriving
+/// at an empty queue take the average to `min` (`tc_red_eval_ewma`).
+fn red_ewma_log(min: u32, burst: u32, avpkt: u32) -> Option<u8> {
+    let target = f64::from(burst) + 1.0 - f64::from(min) / f64::from(avpkt);
+    if target < 1.0 {
+        return None;
+    }
+    (1..32u8).find(|wlog| {
+        let weight = 0.5f64.powi(i32::from(*wlog));
+        target <= (1.0 - (1.0 - weight).powi(burst as i32)) / weight
+    })
+}
+
+/// Log2 of the marking probability's inverse slope between `min` and
+/// `max` (`tc_red_eval_P`). The kernel takes the probability itself from
+/// `TCA_RED_MAX_P`.
+fn red_probability_log(min: u32, max: u32, probability: f64) -> Option<u8> {
+    let range = max.checked_sub(min)?;
+    if range == 0 {
+        return Some(0);
+    }
+    let mut probability = probability / f64::from(range);
+    for plog in 0..32 {
+        if probability > 1.0 {
+            return Some(plog);
+        }
+        probability *= 2.0;
+    }
+    None
+}
+
+/// The cell size log and table the kernel decays the average queue with
+/// while the link idles (`tc_red_eval_idle_damping`): entry `i` is the
+/// shift applied after `i << scell_log` microseconds idle, the time the
+/// link would have taken to send that many `avpkt` packets at `rate`
+/// bytes per second.
+fn red_idle_damping(wlog: u8, avpkt: u32, rate: u64) -> Option<(u8, [u8; 256])> {
+    if rate == 0 {
+        return None;
+    }
+    let xmit_time = 1_000_000.0 * f64::from(avpkt) / rate as f64;
+    let decay = -(1.0 - 1.0 / f64::from(1u32 << wlog)).ln() / xmit_time;
+    let max_time = 31.0 / decay;
+    let scell_log = (0..32u8).find(|log| max_time / f64::from(1u32 << log) < 512.0)?;
+
+    let mut stab = [0u8; 256];
+    for (cell, shift) in stab.iter_mut().enumerate().skip(1) {
+        *shift = ((cell as f64 * f64::from(1u32 << scell_log)) * decay).min(31.0) as u8;
+    }
+    stab[255] = 31;
+    Some((scell_log, stab))
+}
+
+/// nlink's spelling of a CAKE DiffServ mode
+fn cake_diffserv(diffserv: CakeDiffserv) -> nlink::netlink::tc::CakeDiffserv {
+    use nlink::netlink::tc::CakeDiffserv as Mode;
+    match diffserv {
+        CakeDiffserv::Besteffort => Mode::Besteffort,
+        CakeDiffserv::Diffserv3 => Mode::Diffserv3,
+        CakeDiffserv::Diffserv4 => Mode::Diffserv4,
+        CakeDiffserv::Diffserv8 => Mode::Diffserv8,
+        CakeDiffserv::Precedence => Mode::Precedence,
+    }
+}
+
+/// An fq_codel qdisc that can also turn ECN off: nlink's `FqCodelConfig`
+/// only sends the flag to turn it on, and the kernel's default is on.
+#[derive(Debug, Clone)]
+struct FqCodelQdisc {
+    params: FqCodelParams,
+}
+
+impl QdiscConfig for FqCodelQdisc {
+    fn kind(&self) -> &'static str {
+        "fq_codel"
+    }
+
+    fn write_options(&self, builder: &mut MessageBuilder) -> nlink::netlink::Result<()> {
+        let params = &self.params;
+        let mut fq_codel = FqCodelConfig::new().ecn(params.ecn == Some(true));
+        if let Some(target) = params.target_us {
+            fq_codel = fq_codel.target(Duration::from_micros(target.into()));
+        }
+        if let Some(interval) = params.interval_us {
+            fq_codel = fq_codel.interval(Duration::from_micros(interval.into()));
+        }
+        if let Some(quantum) = params.quantum {
+            fq_codel = fq_codel.quantum(quantum);
+        }
+        fq_codel.build().write_options(builder)?;
+        if params.ecn == Some(false) {
+            builder.append_attr_u32(TCA_FQ_CODEL_ECN, 0);
+        }
+        Ok(())
+    }
+}
+
+/// A root qdisc that only shapes the queue, installed on its own rather
+/// than as part of a netem configuration
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueQdisc {
+    Red(RedParams),
+    Sfq(SfqParams),
+    Prio(PrioParams),
+    FqCodel(FqCodelParams),
+    Cake(CakeParams),
+}
+
+impl QueueQdisc {
+    /// Name of the qdisc, as `tc` and the kernel spell it
+    pub fn kind(&self) -> &'static str {
+        match self {
+            QueueQdisc::Red(_) => "red",
+            QueueQdisc::Sfq(_) => "sfq",
+            QueueQdisc::Prio(_) => "prio",
+            QueueQdisc::FqCodel(_) => "fq_codel",
+            QueueQdisc::Cake(_) => "cake",
+        }
+    }
+
+    /// Name of the qdisc for messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            QueueQdisc::Red(_) => "RED",
+            QueueQdisc::Sfq(_) => "SFQ",
+            QueueQdisc::Prio(_) => "PRIO",
+            QueueQdisc::FqCodel(_) => "fq_codel",
+            QueueQdisc::Cake(_) => "CAKE",
+        }
+    }
+
+    /// The `tc` command installing this qdisc on `interface`, for display
+    pub fn command(&self, interface: &str) -> String {
+        match self {
+            QueueQdisc::Red(params) => params.command(interface),
+            QueueQdisc::Sfq(params) => params.command(interface),
+            QueueQdisc::Prio(params) => params.command(interface),
+            QueueQdisc::FqCodel(params) => params.command(interface),
+            QueueQdisc::Cake(params) => params.command(interface),
+        }
+    }
+
+    /// Read back a qdisc of the given kind from its options, or `None` when
+    /// the kind isn't one of these. Options that can't be decoded leave the
+    /// parameters unset.
+    fn decode(kind: &str, options: Option<QdiscOptions>, raw: Option<&[u8]>) -> Option<Self> {
+        Some(match (kind, options) {
+            ("red", _) => QueueQdisc::Red(raw.and_then(parse_red_options).unwrap_or_default()),
+            // The kernel fixes the divisor and flow count
+            ("sfq", Some(QdiscOptions::Sfq(sfq))) => QueueQdisc::Sfq(SfqParams {
+                perturb: u32::try_from(sfq.perturb_period).ok().filter(|p| *p > 0),
+                quantum: Some(sfq.quantum).filter(|q| *q > 0),
+                limit: Some(sfq.limit).filter(|l| *l > 0),
+            }),
+            ("prio", Some(QdiscOptions::Prio(prio))) => QueueQdisc::Prio(PrioParams {
+                bands: u32::try_from(prio.bands).ok(),
+                priomap: Some(prio.priomap.iter().map(|band| u32::from(*band)).collect()),
+            }),
+            // The kernel reports every setting, defaults included
+            ("fq_codel", Some(QdiscOptions::FqCodel(fq_codel))) => {
+                QueueQdisc::FqCodel(FqCodelParams {
+                    target_us: Some(fq_codel.target_us),
+                    interval_us: Some(fq_codel.interval_us),
+                    quantum: Some(fq_codel.quantum),
+                    ecn: Some(fq_codel.ecn),
+                })
+            }
+            // The base rate is in bytes per second, 0 meaning unlimited
+            ("cake", Some(QdiscOptions::Cake(cake))) => QueueQdisc::Cake(CakeParams {
+                bandwidth_kbps: Some(cake.bandwidth_bps)
+                    .filter(|rate| *rate > 0)
+                    .map(|rate| u32::try_from(rate * 8 / 1000).unwrap_or(u32::MAX)),
+                rtt_ms: Some(cake.rtt_us / 1000).filter(|ms| *ms > 0),
+                // Numbered as `CAKE_DIFFSERV_*` in the kernel header
+                diffserv: match cake.diffserv_mode {
+                    0 => Some(CakeDiffserv::Diffserv3),
+                    1 => Some(CakeDiffserv::Diffserv4),
+                    2 => Some(CakeDiffserv::Diffserv8),
+                    3 => Some(CakeDiffserv::Besteffort),
+                    4 => Some(CakeDiffserv::Precedence),
+                    _ => None,
+                },
+            }),
+            ("sfq", _) => QueueQdisc::Sfq(SfqParams::default()),
+            ("prio", _) => QueueQdisc::Prio(PrioParams::default()),
+            ("fq_codel", _) => QueueQdisc::FqCodel(FqCodelParams::default()),
+            ("cake", _) => QueueQdisc::Cake(CakeParams::default()),
+            _ => return None,
+        })
+    }
+
+    /// Check the parameters the way `tc` would before sending them
+    fn validate(&self) -> Result<(), TcValidationError> {
+        match self {
+            QueueQdisc::Red(params) => params.validate(),
+            QueueQdisc::Sfq(params) => params.validate(),
+            QueueQdisc::Prio(params) => params.validate(),
+            QueueQdisc::FqCodel(params) => params.validate(),
+            QueueQdisc::Cake(params) => params.validate(),
+        }
+    }
+}
+
+/// Describe one dumped qdisc, class or filter for the tree view. Options
+/// that can't be decoded are noted rather than treated as an error.
+fn tree_entry(kind: TcObjectKind, msg: &TcMessage) -> TcTreeEntry {
+    let mut details = Vec::new();
+    let mut note = None;
+    match kind {
+        TcObjectKind::Qdisc => match msg.options() {
+            Some(QdiscOptions::Netem(netem)) => details = netem_details(&netem),
+            Some(QdiscOptions::Tbf(tbf)) => {
+                details.push(format!("rate {}", format_rate(tbf.rate)));
+                details.push(format!("burst {}b", tbf.burst));
+            }
+            Some(QdiscOptions::Htb(htb)) => {
+                details.push(format!("default {:x}", htb.default_class));
+            }
+            Some(QdiscOptions::Prio(prio)) => details.push(format!("bands {}", prio.bands)),
+            Some(QdiscOptions::FqCodel(fq_codel)) => {
+                details.push(format!("target {}us", fq_codel.target_us));
+                details.push(format!("limit {}", fq_codel.limit));
+            }
+            Some(QdiscOptions::Cake(cake)) if cake.bandwidth_bps > 0 => {
+                details.push(format!("bandwidth {}", format_rate(cake.bandwidth_bps)));
+            }
+            Some(QdiscOptions::Unknown(raw)) if !raw.is_empty() => {
+                note = Some(format!("options not decoded ({} bytes)", raw.len()));
+            }
+            _ => {}
+        },
+        TcObjectKind::Class => {
+            if msg.kind() == Some("htb") {
+                match msg.raw_options().and_then(parse_htb_class_options) {
+                    Some(htb) => {
+                        details.push(format!("rate {}", format_rate(htb.rate)));
+                        details.push(format!("ceil {}", format_rate(htb.ceil)));
+                    }
+                    None => note = Some("HTB class options not decoded".to_string()),
+                }
+            }
+        }
+        TcObjectKind::Filter => {
+            let protocol = match msg.protocol() {
+                0x0003 => "all".to_string(),
+                0x0800 => "ip".to_string(),
+                0x86DD => "ipv6".to_string(),
+                0x0806 => "arp".to_string(),
+                other => format!("0x{:04x}", other),
+            };
+            details.push(format!("protocol {}", protocol));
+            details.push(format!("pref {}", msg.priority()));
+            if let Some(chain) = msg.chain().filter(|c| *c > 0) {
+                details.push(format!("chain {}", chain));
+            }
+        }
+    }
+    if kind != TcObjectKind::Filter {
+        details.extend([
+            format!("sent {} bytes {} pkt", msg.bytes(), msg.packets()),
+            format!("dropped {} overlimits {}", msg.drops(), msg.overlimits()),
+        ]);
+    }
+    TcTreeEntry {
+        kind,
+        handle: msg.handle_raw(),
+        parent: msg.parent_raw(),
+        type_name: msg.kind().map(str::to_string),
+        details,
+        note,
+    }
+}
+
 /// TC statistics result containing basic, queue, and rate estimator stats.
 #[derive(Debug, Clone)]
 pub struct TcStatisticsResult {
@@ -55,13 +1206,29 @@ pub struct TcStatisticsResult {
     pub rate_est: Option<tcgui_shared::TcStatsRateEst>,
 }
 
+/// What a [`TcCommandManager`] remembers about an interface it impaired,
+/// beyond what the kernel reports back.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AppliedState {
+    /// Non-uniform delay distribution installed: netem doesn't dump its
+    /// distribution table
+    pub distribution: Option<DelayDistribution>,
+    /// Seed explicitly asked for: the kernel reports one even when it
+    /// picked it at random
+    pub seed: Option<u64>,
+}
+
 /// Traffic Control command manager for network emulation.
 ///
 /// This struct manages the execution of Linux TC (traffic control) commands
 /// across multiple network namespaces using nlink's native netlink API.
+/// Connections are created per-operation for namespace isolation; the only
+/// state kept is what the kernel can't report back.
 #[derive(Clone)]
 pub struct TcCommandManager {
-    // Stateless - connections are created per-operation for namespace isolation
+    /// What was applied per (namespace, interface), for the interfaces
+    /// with anything to remember
+    applied: Arc<Mutex<HashMap<(String, String), AppliedState>>>,
 }
 
 impl Default for TcCommandManager {
@@ -73,7 +1240,40 @@ impl Default for TcCommandManager {
 impl TcCommandManager {
     /// Creates a new TcCommandManager instance.
     pub fn new() -> Self {
-        Self {}
+        Self {
+            applied: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// What this manager last applied to an interface.
+    pub fn applied_state(&self, namespace: &str, interface: &str) -> AppliedState {
+        self.applied
+            .lock()
+            .unwrap()
+            .get(&(namespace.to_string(), interface.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Update what is remembered about an interface, forgetting it once
+    /// nothing is left to remember.
+    fn record(&self, namespace: &str, interface: &str, update: impl FnOnce(&mut AppliedState)) {
+        let key = (namespace.to_string(), interface.to_string());
+        let mut applied = self.applied.lock().unwrap();
+        let state = applied.entry(key.clone()).or_default();
+        update(state);
+        if *state == AppliedState::default() {
+            applied.remove(&key);
+        }
+    }
+
+    /// Record the root qdisc now on an interface: netem with `config`, or
+    /// no netem at all.
+    fn record_root(&self, namespace: &str, interface: &str, config: Option<&TcNetemConfig>) {
+        self.record(namespace, interface, |state| {
+            state.distribution = config.and_then(shaped_distribution);
+            state.seed = config.and_then(|config| config.seed);
+        });
     }
 
     /// Check if a namespace is a container namespace (starts with "container:")
@@ -168,7 +1368,9 @@ impl TcCommandManager {
         Ok(String::new()) // No root qdisc found
     }
 
-    /// Get netem options for an interface if it has a netem qdisc configured.
+    /// Get netem options for an interface if it has a netem qdisc configured,
+    /// at the root, as the leaf of an HTB rate limit ([`QdiscKind::Htb`]) or
+    /// on the band of a filtered impairment ([`Self::apply_filtered_netem`]).
     /// Returns None if no netem qdisc is found.
     #[instrument(skip(self), fields(namespace, interface))]
     pub async fn get_netem_options(
@@ -197,10 +1399,20 @@ impl TcCommandManager {
                     message: format!("Failed to get qdiscs for {}: {}", interface, e),
                 })?;
 
+        // A netem leaf counts when the root is an HTB qdisc, or the PRIO
+        // qdisc of a filtered impairment
+        let htb_major = qdiscs
+            .iter()
+            .find(|qdisc| {
+                qdisc.parent().is_root() && matches!(qdisc.kind(), Some("htb") | Some("prio"))
+            })
+            .map(|qdisc| qdisc.handle().major());
+
         // Look for a root netem qdisc
         for qdisc in qdiscs {
             // Check if this is the root qdisc by examining the parent
-            if qdisc.parent().is_root()
+            let parent = qdisc.parent();
+            if (parent.is_root() || Some(parent.major()) == htb_major)
                 && let Some(QdiscOptions::Netem(netem_opts)) = qdisc.options()
             {
                 let loss_pct = netem_opts.loss().unwrap_or(0.0);
@@ -219,6 +1431,102 @@ impl TcCommandManager {
         Ok(None) // No netem qdisc found
     }
 
+    /// Get the rate limit (in kbps) of a root TBF qdisc, if one is configured.
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn get_tbf_rate_kbps(&self, namespace: &str, interface: &str) -> Result<Option<u32>> {
+        self.get_tbf_rate_kbps_with_path(namespace, None, interface)
+            .await
+    }
+
+    /// Get the rate limit of a root TBF qdisc, with optional namespace path for containers.
+    #[instrument(skip(self, namespace_path), fields(namespace, interface))]
+    pub async fn get_tbf_rate_kbps_with_path(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
+    ) -> Result<Option<u32>> {
+        let conn = Self::create_connection(namespace, namespace_path)?;
+
+        let qdiscs =
+            conn.get_qdiscs_by_name(interface)
+                .await
+                .map_err(|e| TcguiError::TcCommandError {
+                    message: format!("Failed to get qdiscs for {}: {}", interface, e),
+                })?;
+
+        for qdisc in qdiscs {
+            if qdisc.parent().is_root()
+                && let Some(QdiscOptions::Tbf(tbf_opts)) = qdisc.options()
+                && let Some(rate) = tbf_opts.rate()
+            {
+                // Convert rate from bytes/sec to kbps
+                let rate_kbps = (rate * 8 / 1000) as u32;
+                info!(
+                    "Found tbf qdisc on {}:{} with rate={}kbps",
+                    namespace, interface, rate_kbps
+                );
+                return Ok(Some(rate_kbps));
+            }
+        }
+
+        Ok(None) // No tbf qdisc found
+    }
+
+    /// Get the rate limit (in kbps) of the class below a root HTB qdisc, if
+    /// the interface is shaped by [`QdiscKind::Htb`].
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn get_htb_rate_kbps(&self, namespace: &str, interface: &str) -> Result<Option<u32>> {
+        self.get_htb_rate_kbps_with_path(namespace, None, interface)
+            .await
+    }
+
+    /// Get the rate limit of an HTB class, with optional namespace path for containers.
+    #[instrument(skip(self, namespace_path), fields(namespace, interface))]
+    pub async fn get_htb_rate_kbps_with_path(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
+    ) -> Result<Option<u32>> {
+        let conn = Self::create_connection(namespace, namespace_path)?;
+
+        let qdiscs =
+            conn.get_qdiscs_by_name(interface)
+                .await
+                .map_err(|e| TcguiError::TcCommandError {
+                    message: format!("Failed to get qdiscs for {}: {}", interface, e),
+                })?;
+        let Some(root) = qdiscs
+            .iter()
+            .find(|qdisc| qdisc.parent().is_root() && qdisc.kind() == Some("htb"))
+        else {
+            return Ok(None);
+        };
+
+        let classes =
+            conn.get_classes_by_name(interface)
+                .await
+                .map_err(|e| TcguiError::TcCommandError {
+                    message: format!("Failed to get classes for {}: {}", interface, e),
+                })?;
+        let rate = classes
+            .iter()
+            .filter(|class| class.kind() == Some("htb") && class.parent() == root.handle())
+            .find_map(|class| class.raw_options().and_then(parse_htb_class_options))
+            .map(|htb| htb.rate);
+
+        Ok(rate.map(|rate| {
+            // Convert rate from bytes/sec to kbps
+            let rate_kbps = (rate * 8 / 1000) as u32;
+            info!(
+                "Found htb class on {}:{} with rate={}kbps",
+                namespace, interface, rate_kbps
+            );
+            rate_kbps
+        }))
+    }
+
     /// Get TC statistics for an interface if it has a netem qdisc configured.
     /// Returns basic stats (bytes/packets), queue stats (drops/overlimits), and rate estimator.
     #[instrument(skip(self), fields(namespace, interface))]
@@ -251,8 +1559,9 @@ impl TcCommandManager {
         // Look for the root qdisc and extract statistics
         for qdisc in qdiscs {
             if qdisc.parent().is_root() {
-                // Only return stats if this is a netem qdisc
-                if qdisc.kind() == Some("netem") {
+                // Only return stats for qdiscs we manage (netem, the TBF fast
+                // path or the root of an HTB rate limit)
+                if matches!(qdisc.kind(), Some("netem") | Some("tbf") | Some("htb")) {
                     let basic = tcgui_shared::TcStatsBasic {
                         bytes: qdisc.bytes(),
                         packets: qdisc.packets(),
@@ -263,6 +1572,10 @@ impl TcCommandManager {
                         drops: qdisc.drops(),
                         requeues: qdisc.requeues(),
                         overlimits: qdisc.overlimits(),
+                        limit: match qdisc.options() {
+                            Some(QdiscOptions::Netem(opts)) => opts.limit(),
+                            _ => None,
+                        },
                     };
                     // Use nlink's bps() and pps() convenience methods for rate estimator
                     let rate_est = if qdisc.bps() > 0 || qdisc.pps() > 0 {
@@ -282,44 +1595,671 @@ impl TcCommandManager {
             }
         }
 
-        Ok(None) // No netem qdisc found
+        Ok(None) // No managed qdisc found
+    }
+
+    /// Dump every qdisc, class and filter on an interface, in that order, for
+    /// [`tcgui_shared::qdisc_tree::build_tree`].
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn get_tc_tree(&self, namespace: &str, interface: &str) -> Result<Vec<TcTreeEntry>> {
+        let conn = Self::create_connection(namespace, None)?;
+        let dump_error = |what: &str, e: nlink::netlink::Error| TcguiError::TcCommandError {
+            message: format!("Failed to get {} for {}: {}", what, interface, e),
+        };
+
+        let qdiscs = conn
+            .get_qdiscs_by_name(interface)
+            .await
+            .map_err(|e| dump_error("qdiscs", e))?;
+        let classes = conn
+            .get_classes_by_name(interface)
+            .await
+            .map_err(|e| dump_error("classes", e))?;
+        let filters = conn
+            .get_filters_by_name(interface)
+            .await
+            .map_err(|e| dump_error("filters", e))?;
+
+        Ok(qdiscs
+            .iter()
+            .map(|msg| tree_entry(TcObjectKind::Qdisc, msg))
+            .chain(
+                classes
+                    .iter()
+                    .map(|msg| tree_entry(TcObjectKind::Class, msg)),
+            )
+            .chain(
+                filters
+                    .iter()
+                    .map(|msg| tree_entry(TcObjectKind::Filter, msg)),
+            )
+            .collect())
+    }
+
+    /// Apply TC config using structured configuration (recommended)
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn apply_tc_config_structured(
+        &self,
+        namespace: &str,
+        interface: &str,
+        config: &TcNetemConfig,
+    ) -> Result<String> {
+        self.apply_tc_config_structured_with_path(namespace, None, interface, config)
+            .await
+    }
+
+    /// Apply TC config using structured configuration with optional namespace path for containers
+    #[instrument(skip(self, namespace_path), fields(namespace, interface))]
+    pub async fn apply_tc_config_structured_with_path(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
+        config: &TcNetemConfig,
+    ) -> Result<String> {
+        // Validate configuration first
+        config.validate().map_err(|e| TcguiError::TcCommandError {
+            message: format!("TC configuration validation failed: {}", e),
+        })?;
+
+        info!(
+            "Applying structured TC config: namespace={}, interface={}, config={:?}",
+            namespace, interface, config
+        );
+
+        self.apply_with_qdisc(
+            namespace,
+            namespace_path,
+            interface,
+            config,
+            QdiscKind::for_config(config),
+        )
+        .await
+    }
+
+    /// Shape an interface to `rate_kbps` with the given root qdisc rather
+    /// than the one [`QdiscKind::for_config`] would pick, for comparing the
+    /// two.
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn apply_rate_limit_with(
+        &self,
+        namespace: &str,
+        interface: &str,
+        rate_kbps: u32,
+        kind: QdiscKind,
+    ) -> Result<String> {
+        info!(
+            "Applying {} kbit/s rate limit with {:?}: namespace={}, interface={}",
+            rate_kbps, kind, namespace, interface
+        );
+        self.apply_with_qdisc(
+            namespace,
+            None,
+            interface,
+            &rate_limit_only_config(rate_kbps),
+            kind,
+        )
+        .await
+    }
+
+    /// Install a validated config as a root qdisc of the given kind.
+    async fn apply_with_qdisc(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
+        config: &TcNetemConfig,
+        kind: QdiscKind,
+    ) -> Result<String> {
+        let conn = Self::create_connection(namespace, namespace_path)?;
+
+        // Get interface index
+        let link = conn
+            .get_link_by_name(interface)
+            .await
+            .map_err(|e| TcguiError::TcCommandError {
+                message: format!("Failed to get interface {}: {}", interface, e),
+            })?
+            .ok_or_else(|| TcguiError::TcCommandError {
+                message: format!("Interface {} not found", interface),
+            })?;
+
+        let ifindex = link.ifindex();
+
+        if kind == QdiscKind::Tbf {
+            self.apply_tbf_rate_limit(
+                &conn,
+                ifindex,
+                namespace,
+                interface,
+                config.rate_limit.rate_kbps,
+            )
+            .await?;
+            self.record_root(namespace, interface, None);
+            return Ok(format!(
+                "TC config applied successfully to {}:{}",
+                namespace, interface
+            ));
+        }
+        if kind == QdiscKind::Htb {
+            self.apply_htb_netem(&conn, ifindex, namespace, interface, config)
+                .await?;
+            return Ok(format!(
+                "TC config applied successfully to {}:{}",
+                namespace, interface
+            ));
+        }
+
+        // Build nlink NetemConfig from TcNetemConfig
+        let netem_config = self.build_netem_config(config);
+        let distribution = shaped_distribution(config);
+        let distribution_table = distribution.map(load_distribution_table).transpose()?;
+        let gemodel = config.loss.model.gemodel().filter(|_| config.loss.enabled);
+        let slot = active_slot(config);
+
+        let layout = Self::root_layout(&conn, ifindex).await;
+        // A change without a table or a slot keeps the installed one, so
+        // going back to uniform jitter or continuous delivery needs a fresh
+        // qdisc
+        let drops_distribution = distribution.is_none()
+            && self
+                .applied_state(namespace, interface)
+                .distribution
+                .is_some();
+        // nlink sees a loss model as a loss percentage, which a config with
+        // a model leaves at zero; a model replacing a model drops nothing
+        let keeps_loss_model = gemodel.is_some()
+            && layout
+                .netem
+                .as_ref()
+                .is_some_and(|current| current.loss_model().is_some());
+        let recreation_probe = if keeps_loss_model {
+            netem_config.clone().loss(Percent::new(100.0))
+        } else {
+            netem_config.clone()
+        };
+        let removes_parameters = layout.netem.as_ref().is_some_and(|current| {
+            drops_distribution
+                || (slot.is_none() && current.slot().is_some())
+                || current.requires_recreation_for(&recreation_probe)
+        });
+        let swap = RootSwap::plan(
+            layout.netem.is_some(),
+            removes_parameters,
+            layout.has_children,
+            &layout.used_majors,
+        );
+        let netem = ExtendedNetem {
+            netem: netem_config,
+            distribution_table,
+            gemodel,
+            slot: slot.clone(),
+            ecn: marks_ecn(config),
+            seed: config.seed,
+        };
+        Self::swap_root(&conn, ifindex, swap, netem, namespace, interface)
+            .await
+            .map_err(|e| tc_kernel_err("Failed to replace netem qdisc", &e))?;
+        self.record_root(namespace, interface, Some(config));
+
+        Ok(format!(
+            "TC config applied successfully to {}:{}",
+            namespace, interface
+        ))
+    }
+
+    /// Shape an interface with a root TBF qdisc (rate-limit-only fast path).
+    async fn apply_tbf_rate_limit(
+        &self,
+        conn: &Connection<Route>,
+        ifindex: u32,
+        namespace: &str,
+        interface: &str,
+        rate_kbps: u32,
+    ) -> Result<()> {
+        let (burst, limit) = tbf_buffer_sizes(rate_kbps);
+        let tbf_config = TbfConfig::new()
+            .rate(Rate::kbit(rate_kbps.into()))
+            .burst(Bytes::new(burst))
+            .limit(Bytes::new(limit))
+            .build();
+
+        // A TBF change takes the full set of parameters, so only children
+        // of an installed TBF force a graft
+        let layout = Self::root_layout(conn, ifindex).await;
+        let swap = RootSwap::plan(
+            layout.root_kind.as_deref() == Some("tbf"),
+            false,
+            layout.has_children,
+            &layout.used_majors,
+        );
+        Self::swap_root(conn, ifindex, swap, tbf_config, namespace, interface)
+            .await
+            .map_err(|e| tc_kernel_err("Failed to replace tbf qdisc", &e))?;
+
+        Ok(())
+    }
+
+    /// The netem qdisc with everything in `config`, loading the delay
+    /// distribution table it needs.
+    fn extended_netem(&self, config: &TcNetemConfig) -> Result<ExtendedNetem> {
+        Ok(ExtendedNetem {
+            netem: self.build_netem_config(config),
+            distribution_table: shaped_distribution(config)
+                .map(load_distribution_table)
+                .transpose()?,
+            gemodel: config.loss.model.gemodel().filter(|_| config.loss.enabled),
+            slot: active_slot(config),
+            ecn: marks_ecn(config),
+            seed: config.seed,
+        })
+    }
+
+    /// Shape an interface with an HTB root qdisc whose single (default)
+    /// class limits the rate, and a netem leaf below the class for the other
+    /// impairments. The `tc` equivalent is [`TcConfiguration::command_parts`]
+    /// with an HTB shaper.
+    ///
+    /// The tree is grafted over the old one under fresh handles (see
+    /// [`RootSwap::Graft`]) and built from the root down. Until the class
+    /// lands, HTB sends traffic straight through; if a later step fails the
+    /// half-built tree is removed and the error reported.
+    ///
+    /// [`TcConfiguration::command_parts`]: tcgui_shared::TcConfiguration::command_parts
+    async fn apply_htb_netem(
+        &self,
+        conn: &Connection<Route>,
+        ifindex: u32,
+        namespace: &str,
+        interface: &str,
+        config: &TcNetemConfig,
+    ) -> Result<()> {
+        let leaf = self.extended_netem(&TcNetemConfig {
+            rate_limit: tcgui_shared::TcRateLimitConfig {
+                enabled: false,
+                ..config.rate_limit.clone()
+            },
+            ..config.clone()
+        })?;
+
+        let mut used_majors = Self::root_layout(conn, ifindex).await.used_majors;
+        let root = unused_handle(&used_majors);
+        used_majors.push(root.major());
+        let leaf_handle = unused_handle(&used_majors);
+        let class = TcHandle::new(root.major(), HTB_CLASS_MINOR);
+
+        let htb = HtbQdiscConfig::new()
+            .default_class(u32::from(HTB_CLASS_MINOR))
+            .build();
+        Self::swap_root(
+            conn,
+            ifindex,
+            RootSwap::Graft(root),
+            htb,
+            namespace,
+            interface,
+        )
+        .await
+        .map_err(|e| tc_kernel_err("Failed to replace htb qdisc", &e))?;
+
+        let rate = HtbClassConfig::new(Rate::kbit(config.rate_limit.rate_kbps.into())).build();
+        if let Err(e) = conn.add_class_by_index(ifindex, root, class, rate).await {
+            // An HTB root without its class doesn't shape anything
+            let _ = conn.del_qdisc_by_index(ifindex, TcHandle::ROOT).await;
+            return Err(tc_kernel_err("Failed to add htb rate class", &e).into());
+        }
+        if let Err(e) = conn
+            .add_qdisc_by_index_full(ifindex, class, Some(leaf_handle), leaf)
+            .await
+        {
+            let _ = conn.del_qdisc_by_index(ifindex, TcHandle::ROOT).await;
+            return Err(tc_kernel_err("Failed to add netem leaf qdisc", &e).into());
+        }
+
+        self.record_root(namespace, interface, Some(config));
+        Ok(())
+    }
+
+    /// The installed root qdisc and the handles in use on an interface.
+    /// Errors reading the qdiscs are treated as an empty interface: the
+    /// replace request then creates the root qdisc.
+    async fn root_layout(conn: &Connection<Route>, ifindex: u32) -> RootLayout {
+        let qdiscs = conn.get_qdiscs_by_index(ifindex).await.unwrap_or_default();
+        let root = qdiscs.iter().find(|qdisc| qdisc.parent().is_root());
+        // The default qdisc has handle 0 and never has children we manage
+        let root_major = root.map(|qdisc| qdisc.handle().major()).filter(|m| *m != 0);
+
+        RootLayout {
+            root_kind: root.and_then(|qdisc| qdisc.kind()).map(str::to_string),
+            netem: root.and_then(|qdisc| match qdisc.options() {
+                Some(QdiscOptions::Netem(opts)) => Some(opts),
+                _ => None,
+            }),
+            has_children: root_major.is_some_and(|major| {
+                qdiscs
+                    .iter()
+                    .any(|qdisc| !qdisc.parent().is_root() && qdisc.parent().major() == major)
+            }),
+            used_majors: qdiscs.iter().map(|qdisc| qdisc.handle().major()).collect(),
+        }
+    }
+
+    /// Install `qdisc` as the root qdisc in one replace request.
+    async fn swap_root<Q: QdiscConfig>(
+        conn: &Connection<Route>,
+        ifindex: u32,
+        swap: RootSwap,
+        qdisc: Q,
+        namespace: &str,
+        interface: &str,
+    ) -> std::result::Result<(), nlink::netlink::Error> {
+        match swap {
+            RootSwap::Replace => {
+                info!(
+                    "Replacing root {} qdisc on {}/{}",
+                    qdisc.kind(),
+                    namespace,
+                    interface
+                );
+                conn.replace_qdisc_by_index(ifindex, qdisc).await
+            }
+            RootSwap::Graft(handle) => {
+                info!(
+                    "Grafting new root {} qdisc {} on {}/{}",
+                    qdisc.kind(),
+                    handle,
+                    namespace,
+                    interface
+                );
+                conn.replace_qdisc_by_index_full(ifindex, TcHandle::ROOT, Some(handle), qdisc)
+                    .await
+            }
+        }
+    }
+
+    /// Apply `config` to the traffic `direction` names and clear the other
+    /// direction, limited to `filter`'s flows when there is one. Received
+    /// traffic is impaired on the egress of an IFB device it is redirected to
+    /// (see [`Self::redirect_ingress`]). Returns the commands making up the
+    /// result when it is more than a root qdisc on the interface.
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn apply_netem(
+        &self,
+        namespace: &str,
+        interface: &str,
+        config: &TcNetemConfig,
+        filter: Option<&TcFilterSpec>,
+        direction: TcDirection,
+    ) -> Result<Option<String>> {
+        if direction == TcDirection::Egress && filter.is_none() {
+            self.remove_ingress(namespace, None, interface).await;
+            self.apply_tc_config_structured(namespace, interface, config)
+                .await?;
+            return Ok(None);
+        }
+
+        let mut commands = Vec::new();
+        if direction.includes_egress() {
+            commands.push(
+                self.apply_on_device(namespace, interface, config, filter)
+                    .await?,
+            );
+        } else {
+            self.remove_root(namespace, None, interface).await?;
+        }
+        if direction.includes_ingress() {
+            let (ifb, redirect) = self.redirect_ingress(namespace, None, interface).await?;
+            commands.extend(redirect);
+            commands.push(
+                self.apply_on_device(namespace, &ifb, config, filter)
+                    .await?,
+            );
+        } else {
+            self.remove_ingress(namespace, None, interface).await;
+        }
+        Ok(Some(commands.join(" && ")))
+    }
+
+    /// Apply `config` as the root of `device`, limited to `filter`'s flows
+    /// when there is one. Returns the `tc` commands.
+    async fn apply_on_device(
+        &self,
+        namespace: &str,
+        device: &str,
+        config: &TcNetemConfig,
+        filter: Option<&TcFilterSpec>,
+    ) -> Result<String> {
+        match filter {
+            Some(filter) => {
+                self.apply_filtered_netem(namespace, device, config, filter)
+                    .await
+            }
+            None => {
+                self.apply_tc_config_structured(namespace, device, config)
+                    .await?;
+                Ok(crate::tc_config::configuration_of(device, config).command)
+            }
+        }
+    }
+
+    /// Redirect everything `interface` receives to the egress of an IFB
+    /// device, where a root qdisc can impair it: the device is created and
+    /// brought up, and an ingress qdisc on the interface gets a filter
+    /// redirecting every packet to it. The device is named after the
+    /// interface's index, so a redirect already in place, even from an
+    /// earlier run, is reused. Returns the device and the commands.
+    async fn redirect_ingress(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
+    ) -> Result<(String, Vec<String>)> {
+        let conn = Self::create_connection(namespace, namespace_path)?;
+        let ifindex = conn
+            .get_link_by_name(interface)
+            .await
+            .map_err(|e| TcguiError::TcCommandError {
+                message: format!("Failed to get interface {}: {}", interface, e),
+            })?
+            .ok_or_else(|| TcguiError::TcCommandError {
+                message: format!("Interface {} not found", interface),
+            })?
+            .ifindex();
+        let ifb = ifb_name(ifindex);
+        let commands = ingress_redirect_commands(interface, &ifb);
+
+        info!(
+            "Redirecting ingress of {}/{} to {}",
+            namespace, interface, ifb
+        );
+        // A device left behind by an earlier run is reused
+        if let Err(e) = conn.add_link(IfbLink::new(&ifb)).await
+            && !e.is_already_exists()
+        {
+            return Err(tc_kernel_err(&format!("Failed to create IFB device {}", ifb), &e).into());
+        }
+        let result = async {
+            conn.set_link_up(ifb.as_str()).await?;
+            let ifb_index = conn
+                .get_link_by_name(&ifb)
+                .await?
+                .ok_or_else(|| nlink::netlink::Error::InvalidMessage(format!("{} vanished", ifb)))?
+                .ifindex();
+            // The ingress qdisc has no options to change: one already there
+            // is kept
+            if let Err(e) = conn
+                .add_qdisc_by_index_full(
+                    ifindex,
+                    TcHandle::INGRESS,
+                    Some(INGRESS_HANDLE),
+                    IngressConfig::new(),
+                )
+                .await
+                && !e.is_already_exists()
+            {
+                return Err(e);
+            }
+            // Replacing the filter by handle keeps a rerun from stacking them
+            let redirect = RedirectAll { ifindex: ifb_index };
+            conn.replace_filter_by_index_full(
+                ifindex,
+                INGRESS_HANDLE,
+                Some(TcHandle::new(0, 1)),
+                ETH_P_ALL,
+                1,
+                redirect,
+            )
+            .await
+        }
+        .await;
+        if let Err(e) = result {
+            // Don't leave a redirect to nowhere, or a stray device
+            Self::delete_ingress_qdisc(&conn, interface).await;
+            let _ = conn.del_link_if_exists(ifb.as_str()).await;
+            return Err(tc_kernel_err(
+                &format!("Failed to redirect ingress of {} to {}", interface, ifb),
+                &e,
+            )
+            .into());
+        }
+
+        Ok((ifb, commands))
+    }
+
+    /// Undo [`Self::redirect_ingress`]: remove the interface's ingress
+    /// qdisc, and the redirect filter with it, then delete the IFB device
+    /// along with the impairment on it. The redirect is found from the
+    /// kernel, so one left by an earlier run is removed too. Returns whether
+    /// there was anything to remove.
+    async fn remove_ingress(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
+    ) -> bool {
+        let conn = match Self::create_connection(namespace, namespace_path) {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("Failed to remove ingress redirect of {}: {}", interface, e);
+                return false;
+            }
+        };
+        let Some(ifb) = Self::ingress_ifb(&conn, interface).await else {
+            return false;
+        };
+        info!(
+            "Removing ingress redirect of {}/{} to {}",
+            namespace, interface, ifb
+        );
+
+        Self::delete_ingress_qdisc(&conn, interface).await;
+        if let Err(e) = conn.del_link_if_exists(ifb.as_str()).await {
+            warn!("Failed to delete IFB device {}: {}", ifb, e);
+        }
+        self.record_root(namespace, &ifb, None);
+        true
+    }
+
+    /// The IFB device [`Self::redirect_ingress`] created for `interface`,
+    /// if it exists
+    async fn ingress_ifb(conn: &Connection<Route>, interface: &str) -> Option<String> {
+        let ifindex = conn.get_link_by_name(interface).await.ok()??.ifindex();
+        let ifb = ifb_name(ifindex);
+        conn.get_link_by_name(&ifb).await.ok()?.map(|_| ifb)
     }
 
-    /// Apply TC config using structured configuration (recommended)
-    #[instrument(skip(self), fields(namespace, interface))]
-    pub async fn apply_tc_config_structured(
-        &self,
-        namespace: &str,
-        interface: &str,
-        config: &TcNetemConfig,
-    ) -> Result<String> {
-        self.apply_tc_config_structured_with_path(namespace, None, interface, config)
+    /// The flows the impairment on an interface is limited to, read back
+    /// from the u32 filter [`Self::apply_filtered_netem`] hangs off its PRIO
+    /// root. None when it isn't filtered or can't be read.
+    pub async fn applied_filter(&self, namespace: &str, interface: &str) -> Option<TcFilterSpec> {
+        let conn = Self::create_connection(namespace, None).ok()?;
+        let ifindex = conn.get_link_by_name(interface).await.ok()??.ifindex();
+        let qdiscs = conn.get_qdiscs_by_index(ifindex).await.ok()?;
+        let root = qdiscs
+            .iter()
+            .find(|qdisc| qdisc.parent().is_root() && qdisc.kind() == Some("prio"))?
+            .handle();
+        let band = TcHandle::new(root.major(), FILTER_BAND_MINOR);
+        conn.get_filters_by_parent_index(ifindex, root)
             .await
+            .ok()?
+            .iter()
+            .filter(|filter| filter.kind() == Some("u32"))
+            .find_map(|filter| {
+                let raw = filter.raw_options()?;
+                // The kernel also lists the hash table the filter lives in
+                let classid = nested_attrs(raw)
+                    .into_iter()
+                    .find(|(kind, _)| *kind == TCA_U32_CLASSID)
+                    .and_then(|(_, payload)| u32_at(payload, 0))?;
+                if classid != band.as_raw() {
+                    return None;
+                }
+                filter_of_u32_keys(filter.protocol() == ETH_P_IPV6, &u32_selector_keys(raw)?)
+            })
     }
 
-    /// Apply TC config using structured configuration with optional namespace path for containers
-    #[instrument(skip(self, namespace_path), fields(namespace, interface))]
-    pub async fn apply_tc_config_structured_with_path(
+    /// The traffic directions impaired on an interface, as the kernel shows
+    /// them: the ingress when it is redirected to an IFB device, the egress
+    /// when the interface has a root qdisc other than the default one.
+    /// Interfaces that can't be read count as impaired on the egress.
+    pub async fn applied_direction(&self, namespace: &str, interface: &str) -> TcDirection {
+        let Ok(conn) = Self::create_connection(namespace, None) else {
+            return TcDirection::Egress;
+        };
+        if Self::ingress_ifb(&conn, interface).await.is_none() {
+            return TcDirection::Egress;
+        }
+        let qdiscs = conn.get_qdiscs_by_name(interface).await.unwrap_or_default();
+        // The default qdisc has handle 0
+        let egress = qdiscs
+            .iter()
+            .any(|qdisc| qdisc.parent().is_root() && qdisc.handle().major() != 0);
+        if egress {
+            TcDirection::Both
+        } else {
+            TcDirection::Ingress
+        }
+    }
+
+    /// Delete the ingress qdisc of `interface`, warning when that fails
+    async fn delete_ingress_qdisc(conn: &Connection<Route>, interface: &str) {
+        if let Err(e) = conn.del_qdisc_if_exists(interface, TcHandle::INGRESS).await {
+            warn!("Failed to remove ingress qdisc of {}: {}", interface, e);
+        }
+    }
+
+    /// Impair only the flows matching `filter`: a PRIO root with a band
+    /// past the ones its priomap uses, a netem with `config` on that band
+    /// and a u32 filter directing matching packets to it. Everything else
+    /// passes through the other bands unimpaired. Returns the equivalent
+    /// `tc` commands.
+    ///
+    /// The PRIO root is grafted over the old tree under a fresh handle, like
+    /// the HTB shaper's; a failure after that removes the half-built
+    /// tree. Removing the root qdisc takes the netem and the filter with it.
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn apply_filtered_netem(
         &self,
         namespace: &str,
-        namespace_path: Option<&Path>,
         interface: &str,
         config: &TcNetemConfig,
+        filter: &TcFilterSpec,
     ) -> Result<String> {
-        // Validate configuration first
         config.validate().map_err(|e| TcguiError::TcCommandError {
             message: format!("TC configuration validation failed: {}", e),
         })?;
+        filter.validate().map_err(|e| TcguiError::TcCommandError {
+            message: format!("Filter validation failed: {}", e),
+        })?;
 
         info!(
-            "Applying structured TC config: namespace={}, interface={}, config={:?}",
-            namespace, interface, config
+            "Applying filtered TC config: namespace={}, interface={}, config={:?}, filter={:?}",
+            namespace, interface, config, filter
         );
 
-        let conn = Self::create_connection(namespace, namespace_path)?;
-
-        // Get interface index
-        let link = conn
+        let conn = Self::create_connection(namespace, None)?;
+        let ifindex = conn
             .get_link_by_name(interface)
             .await
             .map_err(|e| TcguiError::TcCommandError {
@@ -327,78 +2267,206 @@ impl TcCommandManager {
             })?
             .ok_or_else(|| TcguiError::TcCommandError {
                 message: format!("Interface {} not found", interface),
-            })?;
+            })?
+            .ifindex();
+
+        let leaf = self.extended_netem(config)?;
+        let mut used_majors = Self::root_layout(&conn, ifindex).await.used_majors;
+        let root = unused_handle(&used_majors);
+        used_majors.push(root.major());
+        let leaf_handle = unused_handle(&used_majors);
+        let band = TcHandle::new(root.major(), FILTER_BAND_MINOR);
+        let commands =
+            filtered_netem_commands(interface, config, filter, root.major(), leaf_handle.major());
+
+        let prio = PrioConfig::new()
+            .bands(i32::from(FILTER_BAND_MINOR))
+            .build();
+        Self::swap_root(
+            &conn,
+            ifindex,
+            RootSwap::Graft(root),
+            prio,
+            namespace,
+            interface,
+        )
+        .await
+        .map_err(|e| tc_kernel_err("Failed to replace prio qdisc", &e))?;
+
+        if let Err(e) = conn
+            .add_qdisc_by_index_full(ifindex, band, Some(leaf_handle), leaf)
+            .await
+        {
+            // Don't leave a tree behind that impairs nothing
+            let _ = conn.del_qdisc_by_index(ifindex, TcHandle::ROOT).await;
+            return Err(tc_kernel_err("Failed to add netem band qdisc", &e).into());
+        }
+        let protocol = if filter.is_ipv6() {
+            ETH_P_IPV6
+        } else {
+            ETH_P_IP
+        };
+        let u32_filter = u32_keys(filter).into_iter().fold(
+            U32Filter::new()
+                .classid(band)
+                .protocol(protocol)
+                .priority(1),
+            |u32_filter, (val, mask, off)| u32_filter.add_key(val, mask, off),
+        );
+        if let Err(e) = conn
+            .add_filter_by_index_full(ifindex, root, None, protocol, 1, u32_filter)
+            .await
+        {
+            let _ = conn.del_qdisc_by_index(ifindex, TcHandle::ROOT).await;
+            return Err(tc_kernel_err("Failed to add u32 filter", &e).into());
+        }
 
-        let ifindex = link.ifindex();
+        self.record_root(namespace, interface, Some(config));
+        Ok(commands.join(" && "))
+    }
 
-        // Build nlink NetemConfig from TcNetemConfig
-        let netem_config = self.build_netem_config(config);
+    /// Replace the root qdisc of an interface with a queue-shaping qdisc:
+    /// RED (random early detection), SFQ (stochastic fairness queueing),
+    /// PRIO (priority bands), fq_codel or CAKE. A root of the same kind is
+    /// replaced by a fresh one grafted over it, so settings left unset go
+    /// back to their defaults. Returns the equivalent `tc` command.
+    #[instrument(skip(self, qdisc), fields(namespace, interface))]
+    pub async fn apply_queue_qdisc(
+        &self,
+        namespace: &str,
+        interface: &str,
+        qdisc: &QueueQdisc,
+    ) -> Result<String> {
+        let name = qdisc.name();
+        qdisc.validate().map_err(|e| TcguiError::TcCommandError {
+            message: format!("{} configuration validation failed: {}", name, e.message),
+        })?;
+        let command_line = qdisc.command(interface);
 
-        // Check for existing netem options using nlink's typed API
-        let existing_netem = self
-            .get_netem_options_with_path(namespace, namespace_path, interface)
+        info!(
+            "Applying {}: namespace={}, interface={}, command={}",
+            name, namespace, interface, command_line
+        );
+        let conn = Self::create_connection(namespace, None)?;
+        let ifindex = conn
+            .get_link_by_name(interface)
             .await
-            .ok()
-            .flatten();
-
-        match existing_netem {
-            Some(current_opts) => {
-                // Use nlink's requires_recreation_for() to determine if we need delete+add
-                if current_opts.requires_recreation_for(&netem_config) {
-                    info!(
-                        "Recreating netem qdisc on {}/{} (removing parameters)",
-                        namespace, interface
-                    );
-                    let _ = conn.del_qdisc_by_index(ifindex, TcHandle::ROOT).await;
-                    conn.add_qdisc_by_index(ifindex, netem_config)
-                        .await
-                        .map_err(|e| tc_kernel_err("Failed to add netem qdisc after delete", &e))?;
-                } else {
-                    info!("Replacing netem qdisc on {}/{}", namespace, interface);
-                    conn.replace_qdisc_by_index(ifindex, netem_config)
-                        .await
-                        .map_err(|e| tc_kernel_err("Failed to replace netem qdisc", &e))?;
+            .map_err(|e| TcguiError::TcCommandError {
+                message: format!("Failed to get interface {}: {}", interface, e),
+            })?
+            .ok_or_else(|| TcguiError::TcCommandError {
+                message: format!("Interface {} not found", interface),
+            })?
+            .ifindex();
+
+        let layout = Self::root_layout(&conn, ifindex).await;
+        let swap = RootSwap::plan(
+            layout.root_kind.as_deref() == Some(qdisc.kind()),
+            true,
+            layout.has_children,
+            &layout.used_majors,
+        );
+        let result = match qdisc {
+            QueueQdisc::Red(params) => {
+                let red = RedQdisc {
+                    params: params.clone(),
+                };
+                Self::swap_root(&conn, ifindex, swap, red, namespace, interface).await
+            }
+            QueueQdisc::Sfq(params) => {
+                let mut sfq = SfqConfig::new();
+                if let Some(perturb) = params.perturb {
+                    sfq = sfq.perturb(i32::try_from(perturb).unwrap_or(i32::MAX));
+                }
+                if let Some(quantum) = params.quantum {
+                    sfq = sfq.quantum(quantum);
                 }
+                if let Some(limit) = params.limit {
+                    sfq = sfq.limit(limit);
+                }
+                Self::swap_root(&conn, ifindex, swap, sfq.build(), namespace, interface).await
             }
-            None => {
-                // No existing netem qdisc - check if there's any other qdisc
-                let existing_qdisc = self
-                    .check_existing_qdisc_with_path(namespace, namespace_path, interface)
-                    .await
-                    .unwrap_or_default();
-
-                if existing_qdisc.is_empty() || existing_qdisc.contains("noqueue") {
-                    // No qdisc or noqueue - just add
-                    info!("Adding new netem qdisc to {}/{}", namespace, interface);
-                    conn.add_qdisc_by_index(ifindex, netem_config)
-                        .await
-                        .map_err(|e| tc_kernel_err("Failed to add netem qdisc", &e))?;
-                } else {
-                    // Other qdisc type - delete and add
-                    info!(
-                        "Removing existing qdisc and adding netem on {}/{}",
-                        namespace, interface
-                    );
-                    let _ = conn.del_qdisc_by_index(ifindex, TcHandle::ROOT).await;
-                    conn.add_qdisc_by_index(ifindex, netem_config)
-                        .await
-                        .map_err(|e| tc_kernel_err("Failed to add netem qdisc after delete", &e))?;
+            QueueQdisc::Prio(params) => {
+                let mut prio = PrioConfig::new();
+                if let Some(bands) = params.bands {
+                    prio = prio.bands(i32::try_from(bands).unwrap_or(i32::MAX));
+                }
+                if let Some(priomap) = params.priomap.as_deref().and_then(|priomap| {
+                    let bands: Vec<u8> = priomap.iter().map(|band| *band as u8).collect();
+                    <[u8; 16]>::try_from(bands).ok()
+                }) {
+                    prio = prio.priomap(priomap);
                 }
+                Self::swap_root(&conn, ifindex, swap, prio.build(), namespace, interface).await
             }
-        }
+            QueueQdisc::FqCodel(params) => {
+                let fq_codel = FqCodelQdisc {
+                    params: params.clone(),
+                };
+                Self::swap_root(&conn, ifindex, swap, fq_codel, namespace, interface).await
+            }
+            QueueQdisc::Cake(params) => {
+                let mut cake = CakeConfig::new();
+                if let Some(kbps) = params.bandwidth_kbps {
+                    cake = cake.bandwidth(Rate::kbit(kbps.into()));
+                }
+                if let Some(rtt) = params.rtt_ms {
+                    cake = cake.rtt(Duration::from_millis(rtt.into()));
+                }
+                if let Some(diffserv) = params.diffserv {
+                    cake = cake.diffserv_mode(cake_diffserv(diffserv));
+                }
+                Self::swap_root(&conn, ifindex, swap, cake.build(), namespace, interface).await
+            }
+        };
+        result.map_err(|e| tc_kernel_err(&format!("Failed to apply {}", name), &e))?;
 
-        Ok(format!(
-            "TC config applied successfully to {}:{}",
-            namespace, interface
-        ))
+        self.record_root(namespace, interface, None);
+        Ok(command_line)
+    }
+
+    /// Read back a root RED, SFQ, PRIO, fq_codel or CAKE qdisc, if one is
+    /// installed. The
+    /// kernel keeps what it needs to run the qdisc: RED's average packet size
+    /// and burst only served to derive its internal constants and read back
+    /// unset.
+    #[instrument(skip(self), fields(namespace, interface))]
+    pub async fn get_queue_qdisc(
+        &self,
+        namespace: &str,
+        interface: &str,
+    ) -> Result<Option<QueueQdisc>> {
+        let conn = Self::create_connection(namespace, None)?;
+
+        let qdiscs =
+            conn.get_qdiscs_by_name(interface)
+                .await
+                .map_err(|e| TcguiError::TcCommandError {
+                    message: format!("Failed to get qdiscs for {}: {}", interface, e),
+                })?;
+
+        Ok(qdiscs
+            .iter()
+            .filter(|qdisc| qdisc.parent().is_root())
+            .find_map(|qdisc| {
+                QueueQdisc::decode(qdisc.kind()?, qdisc.options(), qdisc.raw_options())
+            })
+            .inspect(|qdisc| {
+                info!(
+                    "Found {} qdisc on {}:{}",
+                    qdisc.kind(),
+                    namespace,
+                    interface
+                );
+            }))
     }
 
     /// Build nlink NetemConfig from TcNetemConfig
     fn build_netem_config(&self, config: &TcNetemConfig) -> NetemConfig {
         let mut netem = NetemConfig::new();
 
-        // Add loss if enabled
-        if config.loss.enabled && config.loss.percentage > 0.0 {
+        // Add loss if enabled (a loss model is added by ExtendedNetem)
+        if config.loss.enabled && config.loss.model.is_random() && config.loss.percentage > 0.0 {
             netem = netem.loss(Percent::new(config.loss.percentage as f64));
             if config.loss.correlation > 0.0 {
                 netem = netem.loss_correlation(Percent::new(config.loss.correlation as f64));
@@ -483,6 +2551,27 @@ impl TcCommandManager {
             interface, namespace
         );
 
+        let removed_ingress = self
+            .remove_ingress(namespace, namespace_path, interface)
+            .await;
+        let removed = self
+            .remove_root(namespace, namespace_path, interface)
+            .await?;
+        Ok(if removed || removed_ingress {
+            "TC config removed successfully".to_string()
+        } else {
+            "No TC config to remove".to_string()
+        })
+    }
+
+    /// Remove the root qdisc of `interface`, if there is one, and forget what
+    /// was tracked about it. Returns whether there was one.
+    async fn remove_root(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
+    ) -> Result<bool> {
         let conn = Self::create_connection(namespace, namespace_path)?;
 
         // The connection is already namespace-bound, so nlink resolves the
@@ -490,15 +2579,18 @@ impl TcCommandManager {
         // 0.25) returns Ok(false) when there's no root qdisc to remove - it
         // folds the ENOENT/ENODEV "nothing there" cases (and the undeletable
         // default-qdisc EINVAL) into a clean bool, so we no longer resolve the
-        // ifindex or match on error predicates by hand.
-        match conn.del_qdisc_if_exists(interface, TcHandle::ROOT).await {
-            Ok(true) => Ok("TC config removed successfully".to_string()),
-            Ok(false) => Ok("No TC config to remove".to_string()),
-            Err(e) => Err(TcguiError::TcCommandError {
+        // ifindex or match on error predicates by hand. Deleting an HTB root
+        // takes its class and netem leaf with it.
+        let removed = conn.del_qdisc_if_exists(interface, TcHandle::ROOT).await;
+        if removed.is_ok() {
+            self.record_root(namespace, interface, None);
+        }
+        removed.map_err(|e| {
+            TcguiError::TcCommandError {
                 message: format!("TC command failed: {}", e),
             }
-            .into()),
-        }
+            .into()
+        })
     }
 
     /// Capture the current TC state for an interface (for rollback purposes)
@@ -508,13 +2600,28 @@ impl TcCommandManager {
         &self,
         namespace: &str,
         interface: &str,
+    ) -> Result<CapturedTcState> {
+        self.capture_tc_state_with_path(namespace, None, interface)
+            .await
+    }
+
+    /// Capture the current TC state, with optional namespace path for containers.
+    #[instrument(skip(self, namespace_path), fields(namespace, interface))]
+    pub async fn capture_tc_state_with_path(
+        &self,
+        namespace: &str,
+        namespace_path: Option<&Path>,
+        interface: &str,
     ) -> Result<CapturedTcState> {
         info!(
             "Capturing TC state for rollback: namespace={}, interface={}",
             namespace, interface
         );
 
-        let qdisc_info = match self.check_existing_qdisc(namespace, interface).await {
+        let qdisc_info = match self
+            .check_existing_qdisc_with_path(namespace, namespace_path, interface)
+            .await
+        {
             Ok(info) => info,
             Err(e) => {
                 warn!(
@@ -526,23 +2633,54 @@ impl TcCommandManager {
         };
 
         let had_netem = qdisc_info.contains("netem");
+        let had_tbf = qdisc_info.contains("tbf");
+        let had_htb = qdisc_info.contains("htb");
+
+        // The rate of an HTB rate limit is on its class, not the netem leaf
+        let htb_rate_kbps = if had_htb {
+            match self
+                .get_htb_rate_kbps_with_path(namespace, namespace_path, interface)
+                .await
+            {
+                Ok(rate_kbps) => rate_kbps,
+                Err(e) => {
+                    warn!("Could not capture htb options: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
 
         // Capture the actual netem configuration if present
-        let netem_config = if had_netem {
-            match self.get_netem_options(namespace, interface).await {
+        let netem_config = if had_netem || htb_rate_kbps.is_some() {
+            match self
+                .get_netem_options_with_path(namespace, namespace_path, interface)
+                .await
+            {
                 Ok(Some(opts)) => {
+                    let gemodel = gemodel_of(&opts);
                     // Convert NetemOptions to TcNetemConfig for storage
                     Some(TcNetemConfig {
                         loss: tcgui_shared::TcLossConfig {
-                            enabled: opts.loss().unwrap_or(0.0) > 0.0,
+                            enabled: opts.loss().unwrap_or(0.0) > 0.0 || gemodel.is_some(),
                             percentage: opts.loss().unwrap_or(0.0) as f32,
                             correlation: opts.loss_correlation().unwrap_or(0.0) as f32,
+                            model: gemodel.map_or(
+                                tcgui_shared::LossModel::Random,
+                                tcgui_shared::LossModel::GilbertElliott,
+                            ),
+                            ecn: opts.ecn(),
                         },
                         delay: tcgui_shared::TcDelayConfig {
                             enabled: opts.delay().map(|d| d.as_millis() > 0).unwrap_or(false),
                             base_ms: opts.delay().map(|d| d.as_millis() as f32).unwrap_or(0.0),
                             jitter_ms: opts.jitter().map(|d| d.as_millis() as f32).unwrap_or(0.0),
                             correlation: opts.delay_correlation().unwrap_or(0.0) as f32,
+                            distribution: self
+                                .applied_state(namespace, interface)
+                                .distribution
+                                .unwrap_or_default(),
                         },
                         duplicate: tcgui_shared::TcDuplicateConfig {
                             enabled: opts.duplicate().unwrap_or(0.0) > 0.0,
@@ -560,10 +2698,22 @@ impl TcCommandManager {
                             percentage: opts.corrupt().unwrap_or(0.0) as f32,
                             correlation: opts.corrupt_correlation().unwrap_or(0.0) as f32,
                         },
-                        rate_limit: tcgui_shared::TcRateLimitConfig {
-                            enabled: opts.rate_bps().map(|r| r > 0).unwrap_or(false),
-                            rate_kbps: opts.rate_bps().map(|r| (r / 1000) as u32).unwrap_or(0),
+                        rate_limit: match htb_rate_kbps {
+                            Some(rate_kbps) => tcgui_shared::TcRateLimitConfig {
+                                enabled: true,
+                                rate_kbps,
+                                mode: tcgui_shared::TcRateLimitMode::Absolute,
+                                shaper: RateLimitMode::Htb,
+                            },
+                            None => tcgui_shared::TcRateLimitConfig {
+                                enabled: opts.rate_bps().map(|r| r > 0).unwrap_or(false),
+                                rate_kbps: opts.rate_bps().map(|r| (r / 1000) as u32).unwrap_or(0),
+                                mode: tcgui_shared::TcRateLimitMode::Absolute,
+                                shaper: RateLimitMode::Netem,
+                            },
                         },
+                        slot: slot_of(&opts).unwrap_or_else(TcSlotConfig::wifi_default),
+                        seed: self.applied_state(namespace, interface).seed,
                     })
                 }
                 Ok(None) => None,
@@ -572,6 +2722,18 @@ impl TcCommandManager {
                     None
                 }
             }
+        } else if had_tbf {
+            // TBF is only used for rate-limit-only configs
+            match self
+                .get_tbf_rate_kbps_with_path(namespace, namespace_path, interface)
+                .await
+            {
+                Ok(rate_kbps) => rate_kbps.map(rate_limit_only_config),
+                Err(e) => {
+                    warn!("Could not capture tbf options: {}", e);
+                    None
+                }
+            }
         } else {
             None
         };
@@ -581,12 +2743,16 @@ impl TcCommandManager {
             interface: interface.to_string(),
             qdisc_info: qdisc_info.clone(),
             had_netem,
+            had_tbf,
+            had_htb,
             netem_config,
         };
 
         info!(
-            "Captured TC state: had_netem={}, has_config={}, qdisc_info='{}'",
+            "Captured TC state: had_netem={}, had_tbf={}, had_htb={}, has_config={}, qdisc_info='{}'",
             had_netem,
+            had_tbf,
+            had_htb,
             state.netem_config.is_some(),
             qdisc_info.trim()
         );
@@ -672,6 +2838,10 @@ pub struct CapturedTcState {
     pub qdisc_info: String,
     /// Whether there was a netem qdisc configured
     pub had_netem: bool,
+    /// Whether there was a TBF qdisc configured (rate-limit-only fast path)
+    pub had_tbf: bool,
+    /// Whether there was an HTB qdisc configured (HTB-shaped rate limit)
+    pub had_htb: bool,
     /// The captured netem configuration (if any) for proper restoration
     pub netem_config: Option<TcNetemConfig>,
 }
@@ -679,6 +2849,636 @@ pub struct CapturedTcState {
 impl CapturedTcState {
     /// Check if there was any TC configuration
     pub fn had_tc_config(&self) -> bool {
-        !self.qdisc_info.is_empty() && self.had_netem
+        !self.qdisc_info.is_empty() && (self.had_netem || self.had_tbf || self.had_htb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nlink::netlink::tc_options::{CakeOptions, FqCodelOptions, PrioOptions, SfqOptions};
+    use std::collections::BTreeSet;
+
+    fn rate_limited(rate_kbps: u32) -> TcNetemConfig {
+        rate_limit_only_config(rate_kbps)
+    }
+
+    #[test]
+    fn test_rate_limit_only_selects_tbf() {
+        assert_eq!(QdiscKind::for_config(&rate_limited(1000)), QdiscKind::Tbf);
+    }
+
+    #[test]
+    fn test_other_impairments_select_netem() {
+        let mut with_delay = rate_limited(1000);
+        with_delay.delay.enabled = true;
+        with_delay.delay.base_ms = 50.0;
+        assert_eq!(QdiscKind::for_config(&with_delay), QdiscKind::Netem);
+
+        let mut with_loss = rate_limited(1000);
+        with_loss.loss.enabled = true;
+        with_loss.loss.percentage = 1.0;
+        assert_eq!(QdiscKind::for_config(&with_loss), QdiscKind::Netem);
+
+        let mut with_corrupt = rate_limited(1000);
+        with_corrupt.corrupt.enabled = true;
+        with_corrupt.corrupt.percentage = 0.5;
+        assert_eq!(QdiscKind::for_config(&with_corrupt), QdiscKind::Netem);
+    }
+
+    #[test]
+    fn test_inactive_features_do_not_force_netem() {
+        // Enabled at 0% (or disabled with a value) is not an active impairment
+        let mut config = rate_limited(500);
+        config.loss.enabled = true;
+        config.delay.base_ms = 100.0;
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Tbf);
+    }
+
+    #[test]
+    fn test_htb_shaper_selects_htb_tree() {
+        let mut config = rate_limited(1000);
+        config.rate_limit.shaper = RateLimitMode::Htb;
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Htb);
+
+        config.delay.enabled = true;
+        config.delay.base_ms = 50.0;
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Htb);
+
+        // Without a rate limit there is nothing for HTB to shape
+        config.rate_limit.enabled = false;
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Netem);
+    }
+
+    #[test]
+    fn test_no_rate_limit_selects_netem() {
+        assert_eq!(
+            QdiscKind::for_config(&TcNetemConfig::new()),
+            QdiscKind::Netem
+        );
+
+        let mut zero_rate = rate_limited(0);
+        zero_rate.rate_limit.enabled = true;
+        assert_eq!(QdiscKind::for_config(&zero_rate), QdiscKind::Netem);
+    }
+
+    #[test]
+    fn test_gemodel_selects_netem_and_encodes_like_tc() {
+        let model = GeModel {
+            p: 1.0,
+            r: 25.0,
+            one_h: 90.0,
+            one_k: 0.0,
+        };
+        let mut config = rate_limited(1000);
+        config.loss.enabled = true;
+        config.loss.model = tcgui_shared::LossModel::GilbertElliott(model);
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Netem);
+
+        // The kernel takes h (loss in the bad state), not 1-h
+        let kernel = gemodel_to_kernel(&model);
+        assert_eq!(kernel.p, percent_to_prob(1.0));
+        assert_eq!(kernel.r, percent_to_prob(25.0));
+        assert_eq!(kernel.h, percent_to_prob(10.0));
+        assert_eq!(kernel.k1, 0);
+    }
+
+    #[test]
+    fn test_red_options_decode() {
+        let attr = |kind: u16, payload: &[u8]| {
+            let mut attr = ((4 + payload.len()) as u16).to_ne_bytes().to_vec();
+            attr.extend(kind.to_ne_bytes());
+            attr.extend(payload);
+            attr.resize(attr.len().next_multiple_of(4), 0);
+            attr
+        };
+        let mut qopt = Vec::new();
+        for value in [400_000u32, 30_000, 90_000] {
+            qopt.extend(value.to_ne_bytes());
+        }
+        qopt.extend([9, 21, 8, 0]);
+        let mut raw = attr(2, &[0; 6]);
+        raw.extend(attr(TCA_RED_PARMS, &qopt));
+        raw.extend(attr(TCA_RED_MAX_P, &(u32::MAX / 50).to_ne_bytes()));
+
+        assert_eq!(
+            QueueQdisc::decode("red", None, Some(&raw)),
+            parse_red_options(&raw).map(QueueQdisc::Red)
+        );
+        let red = parse_red_options(&raw).unwrap();
+        assert_eq!(red.limit, Some(400_000));
+        assert_eq!(red.min, Some(30_000));
+        assert_eq!(red.max, Some(90_000));
+        assert!((red.probability.unwrap() - 0.02).abs() < 1e-6);
+        assert_eq!(red.avpkt, None);
+
+        // Truncated input decodes what it can and never panics
+        assert_eq!(parse_red_options(&raw[..8]), None);
+        assert_eq!(parse_red_options(&[]), None);
+    }
+
+    #[test]
+    fn test_red_constants_match_tc() {
+        // tc's man page example: limit 400000 min 30000 max 90000 avpkt 1000
+        // burst 55 bandwidth 10mbit
+        assert_eq!(red_ewma_log(30_000, 55, 1000), Some(5));
+        assert_eq!(red_probability_log(30_000, 90_000, 0.02), Some(22));
+        let (scell_log, stab) = red_idle_damping(5, 1000, RED_DEFAULT_RATE).unwrap();
+        assert_eq!(scell_log, 11);
+        assert_eq!((stab[0], stab[255]), (0, 31));
+        assert!(stab.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // A burst shorter than `min` worth of packets can't reach it
+        assert_eq!(red_ewma_log(30_000, 20, 1000), None);
+        assert_eq!(red_probability_log(90_000, 30_000, 0.02), None);
+        assert_eq!(red_idle_damping(5, 1000, 0), None);
+    }
+
+    #[test]
+    fn test_fq_codel_and_cake_options_decode() {
+        let fq_codel = QdiscOptions::FqCodel(FqCodelOptions {
+            target_us: 4999,
+            interval_us: 99_999,
+            limit: 10_240,
+            quantum: 1514,
+            ecn: true,
+            ..FqCodelOptions::default()
+        });
+        assert_eq!(
+            QueueQdisc::decode("fq_codel", Some(fq_codel), None),
+            Some(QueueQdisc::FqCodel(FqCodelParams {
+                target_us: Some(4999),
+                interval_us: Some(99_999),
+                quantum: Some(1514),
+                ecn: Some(true),
+            }))
+        );
+
+        // 10 Mbit/s is 1.25 MB/s
+        let cake = QdiscOptions::Cake(CakeOptions {
+            bandwidth_bps: 1_250_000,
+            diffserv_mode: 3,
+            rtt_us: 50_000,
+            ..CakeOptions::default()
+        });
+        let decoded = QueueQdisc::decode("cake", Some(cake), None).unwrap();
+        assert_eq!(
+            decoded,
+            QueueQdisc::Cake(CakeParams {
+                bandwidth_kbps: Some(10_000),
+                rtt_ms: Some(50),
+                diffserv: Some(CakeDiffserv::Besteffort),
+            })
+        );
+        assert_eq!(
+            decoded.command("eth0"),
+            "tc qdisc replace dev eth0 root cake bandwidth 10Mbit rtt 50ms besteffort"
+        );
+
+        // An unlimited CAKE reports a zero rate
+        let unlimited = QdiscOptions::Cake(CakeOptions::default());
+        assert_eq!(
+            QueueQdisc::decode("cake", Some(unlimited), None),
+            Some(QueueQdisc::Cake(CakeParams {
+                diffserv: Some(CakeDiffserv::Diffserv3),
+                ..CakeParams::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_sfq_and_prio_options_decode() {
+        let sfq = QdiscOptions::Sfq(SfqOptions {
+            quantum: 1514,
+            perturb_period: 10,
+            limit: 127,
+            divisor: 1024,
+            flows: 127,
+            ..SfqOptions::default()
+        });
+        assert_eq!(
+            QueueQdisc::decode("sfq", Some(sfq), None),
+            Some(QueueQdisc::Sfq(SfqParams {
+                perturb: Some(10),
+                quantum: Some(1514),
+                limit: Some(127),
+            }))
+        );
+
+        let prio = QdiscOptions::Prio(PrioOptions::default());
+        let decoded = QueueQdisc::decode("prio", Some(prio.clone()), None).unwrap();
+        assert_eq!(
+            decoded,
+            QueueQdisc::Prio(PrioParams {
+                bands: Some(3),
+                priomap: Some(vec![1, 2, 2, 2, 1, 2, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1]),
+            })
+        );
+        assert_eq!(
+            decoded.command("eth0"),
+            "tc qdisc replace dev eth0 root prio bands 3 priomap 1 2 2 2 1 2 0 0 1 1 1 1 1 1 1 1"
+        );
+
+        // Options that couldn't be decoded leave the parameters unset;
+        // other kinds are not queue qdiscs
+        assert_eq!(
+            QueueQdisc::decode("prio", Some(QdiscOptions::Unknown(vec![3])), None),
+            Some(QueueQdisc::Prio(PrioParams::default()))
+        );
+        assert_eq!(
+            QueueQdisc::decode("sfq", None, None),
+            Some(QueueQdisc::Sfq(SfqParams::default()))
+        );
+        assert_eq!(QueueQdisc::decode("netem", Some(prio), None), None);
+    }
+
+    #[test]
+    fn test_filtered_netem_commands() {
+        let mut config = TcNetemConfig::new();
+        config.delay.enabled = true;
+        config.delay.base_ms = 100.0;
+        let filter = TcFilterSpec {
+            dst_ip: Some("192.168.1.10".to_string()),
+            dst_port: Some(5201),
+            protocol: Some(tcgui_shared::TcFilterProtocol::Udp),
+            ..Default::default()
+        };
+        assert_eq!(
+            filtered_netem_commands("veth0", &config, &filter, 1, 2),
+            [
+                "tc qdisc replace dev veth0 root handle 1: prio bands 4",
+                "tc qdisc add dev veth0 parent 1:4 handle 2: netem delay 100ms",
+                "tc filter add dev veth0 parent 1: protocol ip prio 1 u32 \
+                 match ip dst 192.168.1.10/32 match ip dport 5201 0xffff \
+                 match ip protocol 17 0xff flowid 1:4",
+            ]
+        );
+
+        // Handles are hexadecimal, as tc reads them
+        let commands = filtered_netem_commands("eth0", &config, &filter, 10, 11);
+        assert!(commands[1].contains("parent a:4 handle b: netem"));
+    }
+
+    #[test]
+    fn test_u32_keys_match_what_tc_sends() {
+        let filter = TcFilterSpec {
+            src_ip: Some("10.0.0.0/24".to_string()),
+            dst_port: Some(5201),
+            protocol: Some(tcgui_shared::TcFilterProtocol::Udp),
+            ..Default::default()
+        };
+        assert_eq!(
+            u32_keys(&filter),
+            [
+                (0x0a00_0000, 0xffff_ff00, 12),
+                (5201, 0x0000_ffff, 20),
+                (17 << 16, 0x00ff_0000, 8),
+            ]
+        );
+
+        // An IPv6 prefix takes a key per word it covers
+        let filter = TcFilterSpec {
+            dst_ip: Some("2001:db8::/48".to_string()),
+            src_port: Some(443),
+            protocol: Some(tcgui_shared::TcFilterProtocol::Tcp),
+            ..Default::default()
+        };
+        assert_eq!(
+            u32_keys(&filter),
+            [
+                (0x2001_0db8, 0xffff_ffff, 24),
+                (0, 0xffff_0000, 28),
+                (443 << 16, 0xffff_0000, 40),
+                (6 << 8, 0x0000_ff00, 4),
+            ]
+        );
+
+        assert_eq!(u32_keys(&TcFilterSpec::default()), [(0, 0, 0)]);
+    }
+
+    #[test]
+    fn test_filter_of_u32_keys_reverses_u32_keys() {
+        for filter in [
+            TcFilterSpec {
+                src_ip: Some("10.0.0.0/24".to_string()),
+                dst_ip: Some("192.168.1.10".to_string()),
+                src_port: Some(1234),
+                dst_port: Some(5201),
+                protocol: Some(tcgui_shared::TcFilterProtocol::Udp),
+            },
+            TcFilterSpec {
+                dst_ip: Some("2001:db8::/48".to_string()),
+                protocol: Some(tcgui_shared::TcFilterProtocol::Icmp),
+                ..Default::default()
+            },
+            TcFilterSpec {
+                src_ip: Some("fd00::1".to_string()),
+                dst_port: Some(443),
+                ..Default::default()
+            },
+        ] {
+            assert_eq!(
+                filter_of_u32_keys(filter.is_ipv6(), &u32_keys(&filter)),
+                Some(filter)
+            );
+        }
+
+        // Keys matching anything else leave the flows unknown
+        assert_eq!(filter_of_u32_keys(false, &[(1, 0xff, 0)]), None);
+    }
+
+    #[test]
+    fn test_u32_selector_keys() {
+        let mut selector = vec![0u8; 16];
+        selector[2] = 1;
+        selector.extend(0xffff_ff00u32.to_be_bytes());
+        selector.extend(0x0a00_0000u32.to_be_bytes());
+        selector.extend(12i32.to_ne_bytes());
+        selector.extend(0i32.to_ne_bytes());
+        let mut raw = Vec::new();
+        raw.extend((4 + selector.len() as u16).to_ne_bytes());
+        raw.extend(TCA_U32_SEL.to_ne_bytes());
+        raw.extend(selector);
+        assert_eq!(
+            u32_selector_keys(&raw),
+            Some(vec![(0x0a00_0000, 0xffff_ff00, 12)])
+        );
+    }
+
+    #[test]
+    fn test_ingress_redirect_commands() {
+        let ifb = ifb_name(3);
+        assert_eq!(ifb, "tcifb3");
+        assert_eq!(
+            ingress_redirect_commands("veth0", &ifb),
+            [
+                "ip link add tcifb3 type ifb",
+                "ip link set tcifb3 up",
+                "tc qdisc replace dev veth0 ingress",
+                "tc filter replace dev veth0 parent ffff: protocol all prio 1 matchall \
+                 action mirred egress redirect dev tcifb3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slot_selects_netem_and_encodes_like_tc() {
+        let mut config = rate_limited(1000);
+        config.slot = TcSlotConfig {
+            enabled: true,
+            min_delay_ms: 2.5,
+            max_delay_ms: 20.0,
+            packets: None,
+            bytes: Some(64000),
+        };
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Netem);
+        assert_eq!(active_slot(&config), Some(config.slot.clone()));
+
+        let raw = slot_to_kernel(&config.slot);
+        assert_eq!(i64::from_ne_bytes(raw[0..8].try_into().unwrap()), 2_500_000);
+        assert_eq!(
+            i64::from_ne_bytes(raw[8..16].try_into().unwrap()),
+            20_000_000
+        );
+        // No packet limit is sent as 0, which the kernel reads as unlimited
+        assert_eq!(i32::from_ne_bytes(raw[16..20].try_into().unwrap()), 0);
+        assert_eq!(i32::from_ne_bytes(raw[20..24].try_into().unwrap()), 64000);
+        assert!(raw[24..].iter().all(|b| *b == 0));
+
+        // A disabled slot leaves a plain rate limit on TBF
+        config.slot.enabled = false;
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Tbf);
+        assert_eq!(active_slot(&config), None);
+    }
+
+    #[test]
+    fn test_tbf_buffer_sizes() {
+        // Slow links still get room for full-size frames
+        let (burst, limit) = tbf_buffer_sizes(64);
+        assert_eq!(burst, 3028);
+        assert!(limit > burst);
+
+        // 100 Mbps: 12.5 MB/s -> 125 KB burst, plus 625 KB queue
+        let (burst, limit) = tbf_buffer_sizes(100_000);
+        assert_eq!(burst, 125_000);
+        assert_eq!(limit, 125_000 + 625_000);
+    }
+
+    #[test]
+    fn test_parse_distribution_table() {
+        let text = "# This is the distribution table for the normal distribution.\n\
+                    -32768 -28307 -26871\n\
+                    \t-25967 -25298\n\
+                    \n\
+                    # trailing comment\n\
+                    32767\n";
+        assert_eq!(
+            parse_distribution_table(text).unwrap(),
+            vec![-32768, -28307, -26871, -25967, -25298, 32767]
+        );
+        assert!(parse_distribution_table("# only a comment\n").is_err());
+        assert!(
+            parse_distribution_table("1 2 40000")
+                .unwrap_err()
+                .contains("40000")
+        );
+    }
+
+    #[test]
+    fn test_captured_tbf_counts_as_tc_config() {
+        let state = CapturedTcState {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            qdisc_info: "qdisc tbf root".to_string(),
+            had_netem: false,
+            had_tbf: true,
+            had_htb: false,
+            netem_config: Some(rate_limited(1000)),
+        };
+        assert!(state.had_tc_config());
+    }
+
+    /// Root qdisc in a model of the kernel's qdisc replace handling.
+    #[derive(Debug, Clone, PartialEq)]
+    struct ModelRoot {
+        kind: &'static str,
+        handle: u16,
+        params: BTreeSet<&'static str>,
+        child_handle: Option<u16>,
+    }
+
+    impl ModelRoot {
+        fn used_majors(&self) -> Vec<u16> {
+            std::iter::once(self.handle)
+                .chain(self.child_handle)
+                .collect()
+        }
+    }
+
+    /// The kind and parameters a configuration installs.
+    fn target(config: &TcNetemConfig) -> (&'static str, BTreeSet<&'static str>) {
+        let mut params = BTreeSet::new();
+        if QdiscKind::for_config(config) == QdiscKind::Tbf {
+            params.insert("rate");
+            return ("tbf", params);
+        }
+        for (active, name) in [
+            (config.loss.enabled && config.loss.percentage > 0.0, "loss"),
+            (config.delay.enabled && config.delay.base_ms > 0.0, "delay"),
+            (
+                config.reorder.enabled && config.reorder.percentage > 0.0,
+                "reorder",
+            ),
+            (
+                config.rate_limit.enabled && config.rate_limit.rate_kbps > 0,
+                "rate",
+            ),
+        ] {
+            if active {
+                params.insert(name);
+            }
+        }
+        ("netem", params)
+    }
+
+    /// One create+replace `RTM_NEWQDISC` on the root, as `tc_modify_qdisc`
+    /// handles it: an unused handle or another kind grafts a new qdisc in
+    /// one step; the same kind without a handle is changed in place, keeping
+    /// the parameters the request doesn't carry and any children.
+    fn kernel_replace(
+        current: Option<&ModelRoot>,
+        swap: RootSwap,
+        kind: &'static str,
+        params: &BTreeSet<&'static str>,
+    ) -> ModelRoot {
+        match (current, swap) {
+            (Some(root), RootSwap::Replace) if root.kind == kind => ModelRoot {
+                params: root.params.union(params).copied().collect(),
+                ..root.clone()
+            },
+            (current, RootSwap::Graft(handle)) => {
+                let used = current.map(ModelRoot::used_majors).unwrap_or_default();
+                assert!(!used.contains(&handle.major()), "graft handle in use");
+                ModelRoot {
+                    kind,
+                    handle: handle.major(),
+                    params: params.clone(),
+                    child_handle: None,
+                }
+            }
+            (_, RootSwap::Replace) => ModelRoot {
+                kind,
+                handle: 0x8001,
+                params: params.clone(),
+                child_handle: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_swapping_between_configs_is_one_exact_replace() {
+        let mut delay_loss = TcNetemConfig::new();
+        delay_loss.delay.enabled = true;
+        delay_loss.delay.base_ms = 100.0;
+        delay_loss.loss.enabled = true;
+        delay_loss.loss.percentage = 5.0;
+
+        let mut delay_only = TcNetemConfig::new();
+        delay_only.delay = delay_loss.delay.clone();
+
+        let mut reorder = delay_only.clone();
+        reorder.reorder.enabled = true;
+        reorder.reorder.percentage = 25.0;
+
+        let mut loss_rate = rate_limited(2000);
+        loss_rate.loss = delay_loss.loss.clone();
+
+        let configs = [
+            delay_loss,
+            delay_only,
+            reorder,
+            loss_rate,
+            rate_limited(1000),
+        ];
+
+        // Installed roots: nothing, the default qdisc, a netem with a child,
+        // and every target config as the backend would have left it
+        let mut starts = vec![
+            None,
+            Some(ModelRoot {
+                kind: "pfifo_fast",
+                handle: 0,
+                params: BTreeSet::new(),
+                child_handle: None,
+            }),
+            Some(ModelRoot {
+                kind: "netem",
+                handle: 1,
+                params: BTreeSet::from(["delay"]),
+                child_handle: Some(2),
+            }),
+        ];
+        starts.extend(configs.iter().map(|config| {
+            let (kind, params) = target(config);
+            Some(kernel_replace(None, RootSwap::Replace, kind, &params))
+        }));
+
+        for start in &starts {
+            for config in &configs {
+                let (kind, params) = target(config);
+                // Mirrors the apply paths: only netem parameters can linger
+                let same_kind = start.as_ref().is_some_and(|root| root.kind == kind);
+                let removes_parameters = kind == "netem"
+                    && start
+                        .as_ref()
+                        .is_some_and(|root| !root.params.is_subset(&params));
+                let swap = RootSwap::plan(
+                    same_kind,
+                    removes_parameters,
+                    start
+                        .as_ref()
+                        .is_some_and(|root| root.child_handle.is_some()),
+                    &start
+                        .as_ref()
+                        .map(ModelRoot::used_majors)
+                        .unwrap_or_default(),
+                );
+
+                // A single request: the old impairment is in place until
+                // the kernel swaps in exactly the new one
+                let result = kernel_replace(start.as_ref(), swap, kind, &params);
+                assert_eq!(
+                    (result.kind, &result.params, result.child_handle),
+                    (kind, &params, None),
+                    "from {:?} to {:?} via {:?}",
+                    start,
+                    config,
+                    swap
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unused_handle_skips_handles_in_use() {
+        assert_eq!(unused_handle(&[]), TcHandle::major_only(1));
+        assert_eq!(unused_handle(&[0, 1, 2]), TcHandle::major_only(3));
+        assert_eq!(RootSwap::plan(true, false, false, &[1]), RootSwap::Replace);
+        assert_eq!(
+            RootSwap::plan(true, true, false, &[1]),
+            RootSwap::Graft(TcHandle::major_only(2))
+        );
+    }
+
+    #[test]
+    fn test_applied_state_is_forgotten_once_default() {
+        let manager = TcCommandManager::new();
+        let mut config = TcNetemConfig::new();
+        config.seed = Some(7);
+        manager.record_root("default", "eth0", Some(&config));
+        assert_eq!(manager.applied_state("default", "eth0").seed, Some(7));
+
+        manager.record_root("default", "eth0", None);
+        assert!(manager.applied.lock().unwrap().is_empty());
     }
 }
@@ -686,4 +686,109 @@ mod tests {
         let netem_args = get_netem_args(&args);
         assert_eq!(netem_args, &[] as &[String]);
     }
+
+    #[test]
+    fn test_rejected_feature_from_parameter_errors() {
+        use crate::tc_commands::rejected_feature;
+        use tcgui_shared::TcFeature;
+
+        let cases = [
+            // tc(8) parameter errors
+            ("Illegal \"loss\"", Some(TcFeature::Loss)),
+            ("Illegal \"latency\"", Some(TcFeature::Delay)),
+            ("Illegal \"jitter\"", Some(TcFeature::Delay)),
+            ("Illegal \"duplicate\"", Some(TcFeature::Duplicate)),
+            ("Illegal \"corrupt\"", Some(TcFeature::Corrupt)),
+            ("Illegal \"rate\"", Some(TcFeature::RateLimit)),
+            (
+                "reordering not possible without specifying some delay",
+                Some(TcFeature::Reorder),
+            ),
+            // Kernel rejections as they come back through nlink
+            (
+                "Failed to replace tbf qdisc: Invalid argument (os error 22)",
+                Some(TcFeature::RateLimit),
+            ),
+            (
+                "Failed to add netem qdisc: netlink error: Invalid argument: Loss probability out of range",
+                Some(TcFeature::Loss),
+            ),
+            (
+                "Failed to replace netem qdisc: netlink error: Invalid argument: netem: cannot mix duplicating netems with other netems in tree",
+                Some(TcFeature::Duplicate),
+            ),
+            // Our own validation
+            (
+                "TC configuration validation failed: Validation error in field 'jitter_ms': Delay jitter must be 0.0-1000.0ms, got 2000",
+                Some(TcFeature::Delay),
+            ),
+            (
+                "TC configuration validation failed: Validation error in field 'gap': Reorder gap must be 1-10, got 0",
+                Some(TcFeature::Reorder),
+            ),
+            // Nothing to point at
+            (
+                "Failed to replace netem qdisc: Operation not permitted (os error 1)",
+                None,
+            ),
+            ("Interface loss0 not found", None),
+        ];
+
+        for (message, expected) in cases {
+            assert_eq!(rejected_feature(message), expected, "{message}");
+        }
+    }
+
+    #[test]
+    fn test_syntax_mismatch_from_tc_stderr() {
+        use crate::tc_commands::{TcSyntaxMismatch, syntax_mismatch};
+        use tcgui_shared::TcFeature;
+
+        let mismatch = |keyword: &str, feature| {
+            Some(TcSyntaxMismatch {
+                keyword: keyword.to_string(),
+                feature,
+            })
+        };
+        let cases = [
+            // iproute2 before 4.19 has no CAKE
+            (
+                "TC command failed: Unknown qdisc \"cake\", hence option \"bandwidth\" is unparsable\n",
+                mismatch("cake", None),
+            ),
+            // ... and no netem slots
+            (
+                "What is \"slot\"?\nUsage: ... netem [ limit PACKETS ]",
+                mismatch("slot", Some(TcFeature::Slot)),
+            ),
+            // A loss model the parser can't read is reported on `loss`
+            (
+                "Illegal \"loss\"\n",
+                mismatch("loss", Some(TcFeature::Loss)),
+            ),
+            (
+                "Unknown parameter \"diffserv8\"\n",
+                mismatch("diffserv8", None),
+            ),
+            // Not a syntax problem
+            ("RTNETLINK answers: Operation not permitted\n", None),
+            ("Cannot find device \"veth0\"\n", None),
+        ];
+        for (stderr, expected) in cases {
+            assert_eq!(syntax_mismatch(stderr), expected, "{stderr}");
+        }
+
+        let slot = syntax_mismatch("What is \"slot\"?").unwrap();
+        assert_eq!(
+            slot.message(Some("4.15.0")),
+            "Your iproute2 version (4.15.0) doesn't support \"slot\"; \
+             install a newer iproute2 to use it"
+        );
+        assert!(
+            syntax_mismatch("Unknown qdisc \"cake\"")
+                .unwrap()
+                .message(None)
+                .contains("(unknown) doesn't support \"cake\"")
+        );
+    }
 }
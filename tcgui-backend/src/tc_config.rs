@@ -7,205 +7,64 @@
 //! Note: TC configuration parsing is now done via the nlink crate's
 //! `NetemOptions` which directly parses netlink messages from the kernel.
 
-use tcgui_shared::TcConfiguration;
+use tcgui_shared::{TcConfiguration, TcNetemConfig};
 
-/// Build a TC command string for display from configuration parameters.
+/// The flat configuration a structured one amounts to on `interface`.
 ///
-/// This generates a human-readable command string showing what TC configuration
-/// would be applied (useful for logging and UI display).
-#[allow(clippy::too_many_arguments)]
-pub fn build_tc_command_string(
-    interface: &str,
-    loss: f32,
-    correlation: Option<f32>,
-    delay_ms: Option<f32>,
-    delay_jitter_ms: Option<f32>,
-    delay_correlation: Option<f32>,
-    duplicate_percent: Option<f32>,
-    duplicate_correlation: Option<f32>,
-    reorder_percent: Option<f32>,
-    reorder_correlation: Option<f32>,
-    reorder_gap: Option<u32>,
-    corrupt_percent: Option<f32>,
-    corrupt_correlation: Option<f32>,
-    rate_limit_kbps: Option<u32>,
-) -> String {
-    let mut cmd_parts = vec![format!("tc qdisc replace dev {} root netem", interface)];
-
-    if loss > 0.0 {
-        let loss_part = if let Some(corr) = correlation {
-            if corr > 0.0 {
-                format!("loss {}% correlation {}%", loss, corr)
-            } else {
-                format!("loss {}%", loss)
-            }
-        } else {
-            format!("loss {}%", loss)
-        };
-        cmd_parts.push(loss_part);
-    }
-
-    if let Some(delay) = delay_ms
-        && delay > 0.0
-    {
-        let mut delay_part = format!("delay {}ms", delay);
-        if let Some(jitter) = delay_jitter_ms
-            && jitter > 0.0
-        {
-            delay_part.push_str(&format!(" {}ms", jitter));
-            if let Some(delay_corr) = delay_correlation
-                && delay_corr > 0.0
-            {
-                delay_part.push_str(&format!(" {}%", delay_corr));
-            }
-        }
-        cmd_parts.push(delay_part);
-    }
-
-    if let Some(duplicate) = duplicate_percent
-        && duplicate > 0.0
-    {
-        let mut duplicate_part = format!("duplicate {}%", duplicate);
-        if let Some(dup_corr) = duplicate_correlation
-            && dup_corr > 0.0
-        {
-            duplicate_part.push_str(&format!(" {}%", dup_corr));
-        }
-        cmd_parts.push(duplicate_part);
-    }
-
-    if let Some(reorder) = reorder_percent
-        && reorder > 0.0
-    {
-        let mut reorder_part = format!("reorder {}%", reorder);
-        if let Some(reorder_corr) = reorder_correlation
-            && reorder_corr > 0.0
-        {
-            reorder_part.push_str(&format!(" {}%", reorder_corr));
-        }
-        if let Some(gap) = reorder_gap
-            && gap > 0
-        {
-            reorder_part.push_str(&format!(" gap {}", gap));
-        }
-        cmd_parts.push(reorder_part);
-    }
-
-    if let Some(corrupt) = corrupt_percent
-        && corrupt > 0.0
-    {
-        let mut corrupt_part = format!("corrupt {}%", corrupt);
-        if let Some(corrupt_corr) = corrupt_correlation
-            && corrupt_corr > 0.0
-        {
-            corrupt_part.push_str(&format!(" {}%", corrupt_corr));
-        }
-        cmd_parts.push(corrupt_part);
-    }
-
-    if let Some(rate) = rate_limit_kbps
-        && rate > 0
-    {
-        let rate_part = if rate >= 1000 {
-            format!("rate {}mbit", rate / 1000)
-        } else {
-            format!("rate {}kbit", rate)
-        };
-        cmd_parts.push(rate_part);
-    }
-
-    cmd_parts.join(" ")
-}
-
-/// Build a TcConfiguration from individual parameters.
-///
-/// This is a convenience function that combines parameter values with a
-/// generated command string.
-#[allow(clippy::too_many_arguments)]
-pub fn build_tc_configuration(
-    interface: &str,
-    loss: f32,
-    correlation: Option<f32>,
-    delay_ms: Option<f32>,
-    delay_jitter_ms: Option<f32>,
-    delay_correlation: Option<f32>,
-    duplicate_percent: Option<f32>,
-    duplicate_correlation: Option<f32>,
-    reorder_percent: Option<f32>,
-    reorder_correlation: Option<f32>,
-    reorder_gap: Option<u32>,
-    corrupt_percent: Option<f32>,
-    corrupt_correlation: Option<f32>,
-    rate_limit_kbps: Option<u32>,
-) -> TcConfiguration {
-    let command = build_tc_command_string(
-        interface,
-        loss,
-        correlation,
-        delay_ms,
-        delay_jitter_ms,
-        delay_correlation,
-        duplicate_percent,
-        duplicate_correlation,
-        reorder_percent,
-        reorder_correlation,
-        reorder_gap,
-        corrupt_percent,
-        corrupt_correlation,
-        rate_limit_kbps,
-    );
-
-    TcConfiguration {
-        loss,
-        correlation,
-        delay_ms,
-        delay_jitter_ms,
-        delay_correlation,
-        duplicate_percent,
-        duplicate_correlation,
-        reorder_percent,
-        reorder_correlation,
-        reorder_gap,
-        corrupt_percent,
-        corrupt_correlation,
-        rate_limit_kbps,
-        command,
-    }
+/// The command is the human-readable `tc` equivalent of what gets applied
+/// (useful for logging and UI display). Its clauses come from
+/// [`TcConfiguration::command_parts`], which the frontend renders piecewise.
+pub fn configuration_of(interface: &str, config: &TcNetemConfig) -> TcConfiguration {
+    let mut configuration = config.to_legacy_params();
+    configuration.command = configuration.to_command(interface);
+    configuration
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tcgui_shared::{DelayDistribution, GeModel, LossModel};
 
     #[test]
-    fn test_build_tc_command_string_basic() {
-        let cmd = build_tc_command_string(
-            "eth0", 5.0, None, None, None, None, None, None, None, None, None, None, None, None,
+    fn test_configuration_of_basic() {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = 5.0;
+        let configuration = configuration_of("eth0", &config);
+        assert!(
+            configuration
+                .command
+                .contains("tc qdisc replace dev eth0 root netem")
         );
-        assert!(cmd.contains("tc qdisc replace dev eth0 root netem"));
-        assert!(cmd.contains("loss 5%"));
+        assert!(configuration.command.contains("loss 5%"));
     }
 
     #[test]
-    fn test_build_tc_command_string_full() {
-        let cmd = build_tc_command_string(
-            "eth0",
-            10.0,
-            Some(25.0),
-            Some(100.0),
-            Some(10.0),
-            Some(50.0),
-            Some(5.0),
-            Some(10.0),
-            Some(20.0),
-            Some(30.0),
-            Some(3),
-            Some(1.0),
-            Some(5.0),
-            Some(1000),
-        );
+    fn test_configuration_of_full() {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = 10.0;
+        config.loss.correlation = 25.0;
+        config.delay.enabled = true;
+        config.delay.base_ms = 100.0;
+        config.delay.jitter_ms = 10.0;
+        config.delay.correlation = 50.0;
+        config.delay.distribution = DelayDistribution::Normal;
+        config.duplicate.enabled = true;
+        config.duplicate.percentage = 5.0;
+        config.duplicate.correlation = 10.0;
+        config.reorder.enabled = true;
+        config.reorder.percentage = 20.0;
+        config.reorder.correlation = 30.0;
+        config.reorder.gap = 3;
+        config.corrupt.enabled = true;
+        config.corrupt.percentage = 1.0;
+        config.corrupt.correlation = 5.0;
+        config.rate_limit.enabled = true;
+        config.rate_limit.rate_kbps = 1000;
+        let cmd = configuration_of("eth0", &config).command;
         assert!(cmd.contains("loss 10% correlation 25%"));
-        assert!(cmd.contains("delay 100ms 10ms 50%"));
+        assert!(cmd.contains("delay 100ms 10ms 50% distribution normal"));
         assert!(cmd.contains("duplicate 5% 10%"));
         assert!(cmd.contains("reorder 20% 30% gap 3"));
         assert!(cmd.contains("corrupt 1% 5%"));
@@ -213,26 +72,48 @@ mod tests {
     }
 
     #[test]
-    fn test_build_tc_configuration() {
-        let config = build_tc_configuration(
-            "eth0",
-            5.0,
-            None,
-            Some(50.0),
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
+    fn test_configuration_of_keeps_the_parameters() {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = 5.0;
+        config.delay.enabled = true;
+        config.delay.base_ms = 50.0;
+        let configuration = configuration_of("eth0", &config);
+        assert_eq!(configuration.loss, 5.0);
+        assert_eq!(configuration.delay_ms, Some(50.0));
+        assert!(configuration.command.contains("loss 5%"));
+        assert!(configuration.command.contains("delay 50ms"));
+    }
+
+    #[test]
+    fn test_configuration_of_with_gemodel() {
+        let model = GeModel {
+            p: 2.0,
+            r: 30.0,
+            one_h: 80.0,
+            one_k: 0.5,
+        };
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.model = LossModel::GilbertElliott(model);
+        let configuration = configuration_of("eth0", &config);
+        assert_eq!(configuration.loss, 0.0);
+        assert_eq!(configuration.loss_gemodel, Some(model));
+        assert!(
+            configuration
+                .command
+                .ends_with("root netem loss gemodel 2% 30% 80% 0.5%")
         );
-        assert_eq!(config.loss, 5.0);
-        assert_eq!(config.delay_ms, Some(50.0));
-        assert!(config.command.contains("loss 5%"));
-        assert!(config.command.contains("delay 50ms"));
+    }
+
+    #[test]
+    fn test_configuration_of_with_loss_ecn() {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = 5.0;
+        config.loss.ecn = true;
+        let configuration = configuration_of("eth0", &config);
+        assert!(configuration.loss_ecn);
+        assert!(configuration.command.ends_with("root netem loss 5% ecn"));
     }
 }
@@ -0,0 +1,128 @@
+//! TC requests held back until their `apply_at` time.
+//!
+//! A frontend coordinating several backends sends each of them the same
+//! request with a common [`TcRequest::apply_at`]. The backend replies as soon
+//! as the request is accepted and keeps it in a [`TcSchedule`]; the main loop
+//! sleeps until the earliest entry is due ([`sleep_until`]) and performs it
+//! then, so the change lands at the same wall-clock instant everywhere the
+//! clocks agree.
+//!
+//! One request is pending per interface: a newer scheduled request replaces
+//! it, and an immediate request cancels it, so a change the user made in the
+//! meantime is never overwritten by an older plan.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tcgui_shared::TcRequest;
+
+/// Current Unix time in milliseconds.
+pub fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Sleep until the Unix time `at` (milliseconds), or forever without one.
+pub async fn sleep_until(at: Option<u64>) {
+    match at {
+        Some(at) => {
+            tokio::time::sleep(Duration::from_millis(at.saturating_sub(unix_now_ms()))).await
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// A request waiting for its time.
+#[derive(Debug, Clone)]
+pub struct ScheduledTc {
+    /// When to perform it (Unix milliseconds)
+    pub apply_at: u64,
+    pub request: TcRequest,
+}
+
+/// Pending scheduled requests, at most one per interface.
+#[derive(Debug, Default)]
+pub struct TcSchedule {
+    pending: Vec<ScheduledTc>,
+}
+
+impl TcSchedule {
+    /// Hold `request` until `apply_at`. Returns `true` when it replaced a
+    /// request pending for the same interface.
+    pub fn schedule(&mut self, apply_at: u64, request: TcRequest) -> bool {
+        let replaced = self.cancel(&request.namespace, &request.interface);
+        self.pending.push(ScheduledTc { apply_at, request });
+        replaced
+    }
+
+    /// Drop the request pending for `namespace`/`interface`. Returns whether
+    /// there was one.
+    pub fn cancel(&mut self, namespace: &str, interface: &str) -> bool {
+        let before = self.pending.len();
+        self.pending
+            .retain(|s| s.request.namespace != namespace || s.request.interface != interface);
+        self.pending.len() != before
+    }
+
+    /// Time of the earliest pending request.
+    pub fn next_at(&self) -> Option<u64> {
+        self.pending.iter().map(|s| s.apply_at).min()
+    }
+
+    /// Remove and return the requests due at `now`, earliest first.
+    pub fn take_due(&mut self, now: u64) -> Vec<ScheduledTc> {
+        let (mut due, pending): (Vec<_>, Vec<_>) =
+            self.pending.drain(..).partition(|s| s.apply_at <= now);
+        self.pending = pending;
+        due.sort_by_key(|s| s.apply_at);
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tcgui_shared::{TcDirection, TcOperation};
+
+    fn request(interface: &str) -> TcRequest {
+        TcRequest {
+            namespace: "default".to_string(),
+            interface: interface.to_string(),
+            operation: TcOperation::Remove,
+            apply_to_peer: false,
+            confirm_management: false,
+            apply_at: None,
+            filter: None,
+            direction: TcDirection::Egress,
+        }
+    }
+
+    #[test]
+    fn test_due_requests_come_out_in_time_order() {
+        let mut schedule = TcSchedule::default();
+        assert_eq!(schedule.next_at(), None);
+        schedule.schedule(3_000, request("eth0"));
+        schedule.schedule(1_000, request("eth1"));
+        schedule.schedule(2_000, request("eth2"));
+        assert_eq!(schedule.next_at(), Some(1_000));
+
+        let due = schedule.take_due(2_000);
+        let interfaces: Vec<_> = due.iter().map(|s| s.request.interface.as_str()).collect();
+        assert_eq!(interfaces, vec!["eth1", "eth2"]);
+        assert_eq!(schedule.next_at(), Some(3_000));
+    }
+
+    #[test]
+    fn test_one_pending_request_per_interface() {
+        let mut schedule = TcSchedule::default();
+        assert!(!schedule.schedule(1_000, request("eth0")));
+        assert!(schedule.schedule(5_000, request("eth0")));
+        assert_eq!(schedule.next_at(), Some(5_000));
+        assert!(schedule.take_due(4_000).is_empty());
+
+        assert!(schedule.cancel("default", "eth0"));
+        assert!(!schedule.cancel("default", "eth0"));
+        assert_eq!(schedule.next_at(), None);
+    }
+}
@@ -0,0 +1,38 @@
+//! Helpers shared by the backend's unit tests
+
+use tcgui_shared::{InterfaceType, NetworkInterface};
+use zenoh::Wait;
+
+/// Open a Zenoh session that neither scouts nor listens, so the sessions of
+/// concurrently running tests cannot reach each other.
+pub(crate) fn isolated_session() -> zenoh::Session {
+    let mut config = zenoh::Config::default();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .expect("Failed to disable multicast scouting");
+    config
+        .insert_json5("listen/endpoints", "[]")
+        .expect("Failed to clear listen endpoints");
+    zenoh::open(config).wait().expect("Failed to open Zenoh")
+}
+
+/// A physical interface that is up, with no addresses and no TC configured.
+/// Tests override what they exercise with struct update syntax.
+pub(crate) fn interface(name: &str, index: u32, namespace: &str) -> NetworkInterface {
+    NetworkInterface {
+        name: name.to_string(),
+        index,
+        namespace: namespace.to_string(),
+        is_up: true,
+        is_oper_up: true,
+        has_tc_qdisc: false,
+        interface_type: InterfaceType::Physical,
+        addresses: Vec::new(),
+        qdisc_kind: None,
+        link_speed_mbps: None,
+        mtu: None,
+        peer_index: None,
+        management: false,
+        description: None,
+    }
+}
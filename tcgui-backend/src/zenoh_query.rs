@@ -6,24 +6,154 @@
 //! unchanged. These are inherent methods on `TcBackend`; the run loop in
 //! `main.rs` dispatches to them.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 use tokio::time::Duration;
-use tracing::{info, instrument, warn};
-use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, CacheConfig, MissDetectionConfig};
+use tracing::{error, info, instrument, warn};
+use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, MissDetectionConfig};
 
+use tcgui_shared::config_history::{ConfigHistoryRequest, ConfigHistoryResponse};
+use tcgui_shared::qdisc_tree::{TcTreeRequest, TcTreeResponse, build_tree};
+use tcgui_shared::rate_comparison::RateComparisonRequest;
 use tcgui_shared::registry::tc;
+use tcgui_shared::validation::decode_request;
 use tcgui_shared::{
-    BackendHealthStatus, BackendMetadata, InterfaceControlOperation, InterfaceControlRequest,
-    InterfaceControlResponse, NetworkInterface, TcNetemConfig, TcOperation, TcRequest, TcResponse,
-    errors::TcguiError,
+    BackendHealthStatus, BackendMetadata, InterfaceControlBatchRequest,
+    InterfaceControlBatchResponse, InterfaceControlOperation, InterfaceControlRequest,
+    InterfaceControlResponse, InterfaceControlTargetResult, InterfaceType, MAX_APPLY_AHEAD_MS,
+    NetworkInterface, NetworkOperation, NetworkOperationRequest, NetworkOperationResponse,
+    PeerApplyOutcome, StatsRequest, StatsResponse, TcConfiguration, TcDirection, TcFeature,
+    TcFeatureClearRequest, TcFeatureClearResponse, TcFilterSpec, TcNetemConfig, TcOperation,
+    TcRequest, TcResponse, TcValidate, errors::TcguiError, find_veth_peer,
 };
 use zenkey::ConcreteOrigin as _;
 
 use crate::TcBackend;
-use crate::{diagnostics, tc_config};
+use crate::qos::WithQos;
+use crate::tc_commands::QueueQdisc;
+use crate::{diagnostics, tc_commands, tc_config, tc_schedule};
+
+/// Answer `query` on the reply-error channel as `<error_name>: <message>`.
+async fn send_query_error(
+    query: &zenoh::query::Query,
+    error_name: &str,
+    message: &str,
+) -> Result<()> {
+    query
+        .reply_err(format!("{error_name}: {message}"))
+        .await
+        .map_err(|e| TcguiError::ZenohError {
+            message: format!("Failed to reply_err to query: {e}"),
+        })?;
+    Ok(())
+}
+
+/// The session a query came from, when the querier named itself with
+/// [`tcgui_shared::identity::querier_info`]
+fn query_source(query: &zenoh::query::Query) -> Option<String> {
+    query
+        .source_info()
+        .map(|info| info.source_id().zid().to_string())
+}
+
+/// Decode the request carried by `query`. A payload that cannot be decoded is
+/// rejected under `error_name` and `None` returned, so the peer learns what was
+/// wrong with it instead of waiting for a reply that never comes.
+async fn decode_query<T: serde::de::DeserializeOwned>(
+    query: &zenoh::query::Query,
+    payload: &[u8],
+    error_name: &str,
+) -> Result<Option<T>> {
+    match decode_request::<T>(payload) {
+        Ok(request) => Ok(Some(request)),
+        Err(reason) => {
+            warn!("Rejecting query on {}: {}", query.key_expr(), reason);
+            send_query_error(query, error_name, &reason).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// A target of a batch that could not be configured
+#[derive(Debug)]
+struct BatchFailure {
+    namespace: String,
+    interface: String,
+    /// Error key a request for this target alone would have been rejected with
+    error: &'static str,
+    message: String,
+}
+
+/// What a batch did to its targets
+#[derive(Debug)]
+struct BatchOutcome {
+    total: usize,
+    failures: Vec<BatchFailure>,
+}
+
+impl BatchOutcome {
+    fn message(&self) -> String {
+        let applied = self.total - self.failures.len();
+        if self.failures.is_empty() {
+            return format!("TC config applied to {} interfaces", applied);
+        }
+        format!(
+            "TC config applied to {} of {} interfaces; failed: {}",
+            applied,
+            self.total,
+            self.failures
+                .iter()
+                .map(|f| format!(
+                    "{}/{} ({}: {})",
+                    f.namespace, f.interface, f.error, f.message
+                ))
+                .collect::<Vec<_>>()
+                .join("; ")
+        )
+    }
+}
+
+/// Run `apply` on one request per target of `batch`, each carrying the
+/// batch's peer, confirmation and timing options. A failed target doesn't
+/// stop the others.
+async fn apply_batch(
+    batch: &TcRequest,
+    targets: BTreeSet<(String, String)>,
+    config: TcNetemConfig,
+    mut apply: impl AsyncFnMut(&mut TcRequest) -> std::result::Result<(), (&'static str, String)>,
+) -> BatchOutcome {
+    let total = targets.len();
+    let mut failures = Vec::new();
+    for (namespace, interface) in targets {
+        let mut request = TcRequest {
+            namespace,
+            interface,
+            operation: TcOperation::ApplyConfig {
+                config: config.clone(),
+            },
+            apply_to_peer: batch.apply_to_peer,
+            confirm_management: batch.confirm_management,
+            apply_at: batch.apply_at,
+            filter: batch.filter.clone(),
+            direction: batch.direction,
+        };
+        if let Err((error, message)) = apply(&mut request).await {
+            warn!(
+                "Batch apply failed for {}/{}: {}",
+                request.namespace, request.interface, message
+            );
+            failures.push(BatchFailure {
+                namespace: request.namespace,
+                interface: request.interface,
+                error,
+                message,
+            });
+        }
+    }
+    BatchOutcome { total, failures }
+}
 
 impl TcBackend {
     /// Reply to a query with a success value on the queryable's **own concrete
@@ -56,13 +186,37 @@ impl TcBackend {
         error_name: &str,
         message: &str,
     ) -> Result<()> {
-        query
-            .reply_err(format!("{error_name}: {message}"))
+        send_query_error(query, error_name, message).await
+    }
+
+    /// Charge `query` against its source's rate limit. An over-limit query is
+    /// answered on the reply-error channel right away and `false` is returned,
+    /// in which case the caller must drop it without acting on it.
+    pub(crate) async fn admit_query(&mut self, query: &zenoh::query::Query) -> bool {
+        let source = query_source(query);
+        if self
+            .query_limiter
+            .check(source.as_deref(), std::time::Instant::now())
+        {
+            return true;
+        }
+
+        warn!(
+            "Rate limiting query on {} from {}",
+            query.key_expr(),
+            source.as_deref().unwrap_or("unidentified peer")
+        );
+        let message = format!(
+            "rate limited: more than {} queries per second",
+            self.query_limiter.rate_per_sec()
+        );
+        if let Err(e) = self
+            .reply_query_error(query, "error/rate-limited", &message)
             .await
-            .map_err(|e| TcguiError::ZenohError {
-                message: format!("Failed to reply_err to query: {e}"),
-            })?;
-        Ok(())
+        {
+            warn!("Failed to reject rate-limited query: {}", e);
+        }
+        false
     }
 
     /// Reject a TC query on the reply-error channel (used for invalid input).
@@ -73,13 +227,7 @@ impl TcBackend {
 
     #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
     pub(crate) async fn handle_tc_query(&mut self, query: zenoh::query::Query) -> Result<()> {
-        let payload = query.payload().ok_or_else(|| {
-            TcguiError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "TC query missing payload",
-            ))
-        })?;
-        let payload_bytes = payload.to_bytes();
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
         if payload_bytes.len() > tcgui_shared::validation::MAX_REQUEST_PAYLOAD_BYTES {
             return self
                 .reply_tc_error(
@@ -91,16 +239,88 @@ impl TcBackend {
                 )
                 .await;
         }
-        let payload_str = std::str::from_utf8(&payload_bytes).map_err(|e| {
-            TcguiError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Invalid UTF-8: {}", e),
-            ))
-        })?;
 
-        let request = serde_json::from_str::<TcRequest>(payload_str)?;
+        let Some(mut request) =
+            decode_query::<TcRequest>(&query, &payload_bytes, "error/tc/malformed-request").await?
+        else {
+            return Ok(());
+        };
         info!("Received TC query: {:?}", request);
 
+        // A batch is checked and applied target by target, and answered with
+        // the outcome for all of them
+        if let TcOperation::ApplyBatch { targets, config } = &request.operation {
+            let targets: BTreeSet<(String, String)> = targets.iter().cloned().collect();
+            let outcome = self.apply_tc_batch(&request, targets, config.clone()).await;
+            let message = outcome.message();
+            info!("{}", message);
+            // The message lists every failed target with its error
+            if !outcome.failures.is_empty() {
+                return self
+                    .reply_query_error(&query, "error/tc/batch", &message)
+                    .await;
+            }
+            let response = TcResponse {
+                success: true,
+                message,
+                applied_config: None,
+                error_code: None,
+                peer: None,
+                rejected_feature: None,
+                scheduled_at: None,
+            };
+            // Answered on the key the query was addressed to
+            let payload = serde_json::to_string(&response)?;
+            return self
+                .reply_value(
+                    &query,
+                    tc::config_ns_iface_set_key(
+                        &self.local_origin,
+                        &request.namespace,
+                        &request.interface,
+                    )
+                    .into(),
+                    payload,
+                )
+                .await;
+        }
+
+        if let Err((error_name, message)) = self.check_tc_request(&mut request) {
+            return self.reply_query_error(&query, error_name, &message).await;
+        }
+        let response = self.perform_tc_request(&request).await;
+
+        // Success rides the value channel on our concrete key; failure rides
+        // reply_err (RFC 05 §2.1 / §3).
+        if response.success {
+            let payload = serde_json::to_string(&response)?;
+            self.reply_value(
+                &query,
+                tc::config_ns_iface_set_key(
+                    &self.local_origin,
+                    &request.namespace,
+                    &request.interface,
+                )
+                .into(),
+                payload,
+            )
+            .await?;
+        } else {
+            self.reply_query_error(&query, "error/tc/apply", &response.message)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Check a TC request before anything is changed: its target, the
+    /// management interface and impairment limit guards, and for a
+    /// structured config the link-relative rate (resolved in place) and the
+    /// kernel's support. On refusal, returns the reply error name and why.
+    fn check_tc_request(
+        &self,
+        request: &mut TcRequest,
+    ) -> std::result::Result<(), (&'static str, String)> {
         // Validate the request target before any privileged operation.
         if let Err(reason) =
             tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
@@ -109,62 +329,304 @@ impl TcBackend {
                 "Rejecting TC request for {}/{}: {}",
                 request.namespace, request.interface, reason
             );
-            return self
-                .reply_tc_error(&query, format!("Invalid request: {reason}"))
+            return Err((
+                "error/tc/invalid-request",
+                format!("Invalid request: {reason}"),
+            ));
+        }
+
+        // A filter limits a netem impairment to some flows; nothing else
+        // can be limited that way
+        if let Some(filter) = &request.filter {
+            if !matches!(
+                request.operation,
+                TcOperation::ApplyConfig { .. } | TcOperation::Apply { .. }
+            ) {
+                return Err((
+                    "error/tc/invalid-request",
+                    "Invalid request: a filter only applies to netem configurations".to_string(),
+                ));
+            }
+            if let Err(e) = filter.validate() {
+                return Err((
+                    "error/tc/invalid-request",
+                    format!("{}: {}", request.interface, e.message),
+                ));
+            }
+        }
+
+        // Only netem is redirected to impair ingress; removal clears both
+        // directions whatever the request says
+        if request.direction != TcDirection::Egress
+            && !matches!(
+                request.operation,
+                TcOperation::ApplyConfig { .. } | TcOperation::Apply { .. } | TcOperation::Remove
+            )
+        {
+            return Err((
+                "error/tc/invalid-request",
+                format!(
+                    "Invalid request: {} impairment only applies to netem configurations",
+                    request.direction
+                ),
+            ));
+        }
+
+        // Impairing the interface our own Zenoh traffic uses can cut the GUI
+        // off; only go ahead once the user has confirmed it.
+        if !request.confirm_management
+            && crate::management::impairs(&request.operation)
+            && self.is_management_interface(&request.namespace, &request.interface)
+        {
+            warn!(
+                "Refusing unconfirmed TC change on management interface {}/{}",
+                request.namespace, request.interface
+            );
+            return Err((
+                "error/tc/management-interface",
+                format!(
+                    "{} carries this backend's control connection; impairing it may \
+                     disconnect the GUI. Confirm to apply anyway.",
+                    request.interface
+                ),
+            ));
+        }
+
+        // Guard shared hosts against impairing too many interfaces at once
+        if crate::management::impairs(&request.operation)
+            && let Err(reason) = self.impairment_limit.check(
+                self.interfaces.values(),
+                &request.namespace,
+                &request.interface,
+            )
+        {
+            warn!(
+                "Refusing TC change on {}/{}: {}",
+                request.namespace, request.interface, reason
+            );
+            return Err(("error/tc/impairment-limit", reason));
+        }
+
+        // A rate relative to the link speed is resolved against the speed
+        // last detected for the target; the veth peer gets the same rate.
+        if let TcOperation::ApplyConfig { config } = &mut request.operation {
+            let link_speed_mbps = self
+                .interfaces
+                .values()
+                .find(|i| i.namespace == request.namespace && i.name == request.interface)
+                .and_then(|i| i.link_speed_mbps);
+            match config.rate_limit.resolve(link_speed_mbps) {
+                Ok(rate_limit) => config.rate_limit = rate_limit,
+                Err(e) => {
+                    return Err((
+                        "error/tc/invalid-request",
+                        format!("{}: {}", request.interface, e.message),
+                    ));
+                }
+            }
+
+            // The kernel may accept parameters it doesn't implement and
+            // silently ignore them; refuse those instead of a no-op apply.
+            if let Err((feature, e)) = self.netem_capabilities.check_config(config) {
+                warn!(
+                    "Rejecting {:?} settings for {}/{}: {}",
+                    feature, request.namespace, request.interface, e.message
+                );
+                return Err((
+                    "error/tc/unsupported",
+                    format!("{}: {}", request.interface, e.message),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Perform a checked TC request: hold it for later when `apply_at` is
+    /// ahead, otherwise apply it now (and on the veth peer when asked),
+    /// superseding whatever was still scheduled for the interface.
+    async fn perform_tc_request(&mut self, request: &TcRequest) -> TcResponse {
+        let now = tc_schedule::unix_now_ms();
+        match request.apply_at {
+            Some(apply_at) if apply_at > now => self.schedule_tc(request.clone(), apply_at, now),
+            _ => {
+                if self
+                    .tc_schedule
+                    .cancel(&request.namespace, &request.interface)
+                {
+                    info!(
+                        "Scheduled TC change on {}/{} superseded",
+                        request.namespace, request.interface
+                    );
+                }
+                let mut response = self
+                    .execute_tc_operation(
+                        &request.namespace,
+                        &request.interface,
+                        &request.operation,
+                        request.filter.as_ref(),
+                        request.direction,
+                    )
+                    .await;
+                if request.apply_to_peer && response.success {
+                    response = self.apply_tc_to_veth_peer(request, response).await;
+                }
+                response
+            }
+        }
+    }
+
+    /// Apply `config` to every target of a batch request, in name order,
+    /// whatever happens to the others. Each target is checked and applied as
+    /// a request of its own.
+    async fn apply_tc_batch(
+        &mut self,
+        batch: &TcRequest,
+        targets: BTreeSet<(String, String)>,
+        config: TcNetemConfig,
+    ) -> BatchOutcome {
+        apply_batch(batch, targets, config, async |request: &mut TcRequest| {
+            self.check_tc_request(request)?;
+            let response = self.perform_tc_request(request).await;
+            if !response.success {
+                return Err(("error/tc/apply", response.message));
+            }
+            // The next targets are checked against the impairment limit
+            // with this one counted
+            crate::impairment_limit::mark_impaired(
+                self.interfaces.values_mut(),
+                &request.namespace,
+                &request.interface,
+            );
+            Ok(())
+        })
+        .await
+    }
+
+    /// Hold `request` until `apply_at` and describe the outcome.
+    fn schedule_tc(&mut self, request: TcRequest, apply_at: u64, now: u64) -> TcResponse {
+        let response = |success: bool, message: String| TcResponse {
+            success,
+            message,
+            applied_config: None,
+            error_code: None,
+            peer: None,
+            rejected_feature: None,
+            scheduled_at: success.then_some(apply_at),
+        };
+
+        if apply_at - now > MAX_APPLY_AHEAD_MS {
+            return response(
+                false,
+                format!(
+                    "Refusing to schedule a TC change {} s ahead on {}: the limit is {} s",
+                    (apply_at - now) / 1000,
+                    request.interface,
+                    MAX_APPLY_AHEAD_MS / 1000
+                ),
+            );
+        }
+
+        let message = format!(
+            "TC change on {}:{} scheduled in {} ms",
+            request.namespace,
+            request.interface,
+            apply_at - now
+        );
+        info!("{}", message);
+        if self.tc_schedule.schedule(apply_at, request) {
+            info!("Replaced an earlier scheduled TC change on the same interface");
+        }
+        response(true, message)
+    }
+
+    /// Perform the scheduled TC requests that are due. Their configuration
+    /// updates are published as for any other change; failures can only be
+    /// logged, the query was answered when the request was accepted.
+    pub(crate) async fn run_scheduled_tc(&mut self) {
+        let now = tc_schedule::unix_now_ms();
+        for scheduled in self.tc_schedule.take_due(now) {
+            let request = scheduled.request;
+            info!(
+                "Performing scheduled TC change on {}/{} ({} ms after its time)",
+                request.namespace,
+                request.interface,
+                now - scheduled.apply_at
+            );
+
+            // Other interfaces may have been impaired since it was accepted
+            if crate::management::impairs(&request.operation)
+                && let Err(reason) = self.impairment_limit.check(
+                    self.interfaces.values(),
+                    &request.namespace,
+                    &request.interface,
+                )
+            {
+                warn!(
+                    "Dropping scheduled TC change on {}/{}: {}",
+                    request.namespace, request.interface, reason
+                );
+                continue;
+            }
+
+            let mut response = self
+                .execute_tc_operation(
+                    &request.namespace,
+                    &request.interface,
+                    &request.operation,
+                    request.filter.as_ref(),
+                    request.direction,
+                )
                 .await;
+            if request.apply_to_peer && response.success {
+                response = self.apply_tc_to_veth_peer(&request, response).await;
+            }
+            if !response.success {
+                error!(
+                    "Scheduled TC change on {}/{} failed: {}",
+                    request.namespace, request.interface, response.message
+                );
+            }
         }
+    }
+
+    /// Whether the backend's Zenoh traffic goes through `namespace`/`interface`.
+    fn is_management_interface(&self, namespace: &str, interface: &str) -> bool {
+        self.interfaces
+            .values()
+            .any(|i| i.management && i.namespace == namespace && i.name == interface)
+    }
 
-        let response = match &request.operation {
+    /// Perform one TC operation on `namespace`/`interface`, publish the
+    /// resulting configuration and describe the outcome. A netem apply with
+    /// a `filter` only impairs the matching flows, in `direction`.
+    async fn execute_tc_operation(
+        &mut self,
+        namespace: &str,
+        interface: &str,
+        operation: &TcOperation,
+        filter: Option<&TcFilterSpec>,
+        direction: TcDirection,
+    ) -> TcResponse {
+        match operation {
             TcOperation::ApplyConfig { config } => {
                 let result = self
                     .tc_manager
-                    .apply_tc_config_structured(&request.namespace, &request.interface, config)
+                    .apply_netem(namespace, interface, config, filter, direction)
                     .await;
 
                 match result {
-                    Ok(_) => {
-                        // Convert structured config to legacy TcConfiguration for publishing
-                        let (
-                            loss,
-                            correlation,
-                            delay_ms,
-                            delay_jitter_ms,
-                            delay_correlation,
-                            duplicate_percent,
-                            duplicate_correlation,
-                            reorder_percent,
-                            reorder_correlation,
-                            reorder_gap,
-                            corrupt_percent,
-                            corrupt_correlation,
-                            rate_limit_kbps,
-                        ) = config.to_legacy_params();
-
-                        // Use helper function to build configuration
-                        let applied_config = tc_config::build_tc_configuration(
-                            &request.interface,
-                            loss,
-                            correlation,
-                            delay_ms,
-                            delay_jitter_ms,
-                            delay_correlation,
-                            duplicate_percent,
-                            duplicate_correlation,
-                            reorder_percent,
-                            reorder_correlation,
-                            reorder_gap,
-                            corrupt_percent,
-                            corrupt_correlation,
-                            rate_limit_kbps,
-                        );
+                    Ok(commands) => {
+                        let mut applied_config = tc_config::configuration_of(interface, config);
+                        // The tree holding a filtered or ingress impairment
+                        // is more than one netem
+                        if let Some(commands) = commands {
+                            applied_config.command = commands;
+                        }
 
                         // Publish TC configuration update with actual config
                         if let Err(e) = self
-                            .publish_tc_config(
-                                &request.namespace,
-                                &request.interface,
-                                Some(applied_config.clone()),
-                            )
+                            .publish_tc_config(namespace, interface, Some(applied_config.clone()))
                             .await
                         {
                             warn!("Failed to publish TC config update: {}", e);
@@ -174,10 +636,13 @@ impl TcBackend {
                             success: true,
                             message: format!(
                                 "Structured TC config applied successfully to {}:{}",
-                                request.namespace, request.interface
+                                namespace, interface
                             ),
                             applied_config: Some(applied_config),
                             error_code: None,
+                            peer: None,
+                            rejected_feature: None,
+                            scheduled_at: None,
                         }
                     }
                     Err(e) => TcResponse {
@@ -185,6 +650,9 @@ impl TcBackend {
                         message: format!("Failed to apply structured TC config: {}", e),
                         applied_config: None,
                         error_code: Some(-1),
+                        peer: None,
+                        rejected_feature: tc_commands::rejected_feature(&e.to_string()),
+                        scheduled_at: None,
                     },
                 }
             }
@@ -202,6 +670,12 @@ impl TcBackend {
                 corrupt_percent,
                 corrupt_correlation,
                 rate_limit_kbps,
+                delay_distribution,
+                loss_gemodel,
+                rate_limit_shaper,
+                slot,
+                loss_ecn,
+                seed,
             } => {
                 // Convert legacy parameters to structured config
                 let config = TcNetemConfig::from_legacy_params(
@@ -218,6 +692,14 @@ impl TcBackend {
                     *corrupt_percent,
                     *corrupt_correlation,
                     *rate_limit_kbps,
+                )
+                .with_extensions(
+                    *delay_distribution,
+                    *loss_gemodel,
+                    *rate_limit_shaper,
+                    slot.clone(),
+                    *loss_ecn,
+                    *seed,
                 );
 
                 // Check if any features are enabled
@@ -226,62 +708,34 @@ impl TcBackend {
                 let result = if has_meaningful_params {
                     // Apply TC using structured API
                     self.tc_manager
-                        .apply_tc_config_structured(&request.namespace, &request.interface, &config)
+                        .apply_netem(namespace, interface, &config, filter, direction)
                         .await
                 } else {
                     // No meaningful parameters - remove TC qdisc entirely
                     info!(
                         "No meaningful TC parameters provided, removing TC qdisc from {}:{}",
-                        request.namespace, request.interface
+                        namespace, interface
                     );
                     self.tc_manager
-                        .remove_tc_config_in_namespace(&request.namespace, &request.interface)
+                        .remove_tc_config_in_namespace(namespace, interface)
                         .await
+                        .map(|_| None)
                 };
 
                 match result {
-                    Ok(_) => {
+                    Ok(commands) => {
                         if has_meaningful_params {
-                            // Convert back to legacy format for response/publishing
-                            let (
-                                loss,
-                                correlation,
-                                delay_ms,
-                                delay_jitter_ms,
-                                delay_correlation,
-                                duplicate_percent,
-                                duplicate_correlation,
-                                reorder_percent,
-                                reorder_correlation,
-                                reorder_gap,
-                                corrupt_percent,
-                                corrupt_correlation,
-                                rate_limit_kbps,
-                            ) = config.to_legacy_params();
-
-                            // Use helper function to build configuration
-                            let applied_config = tc_config::build_tc_configuration(
-                                &request.interface,
-                                loss,
-                                correlation,
-                                delay_ms,
-                                delay_jitter_ms,
-                                delay_correlation,
-                                duplicate_percent,
-                                duplicate_correlation,
-                                reorder_percent,
-                                reorder_correlation,
-                                reorder_gap,
-                                corrupt_percent,
-                                corrupt_correlation,
-                                rate_limit_kbps,
-                            );
+                            let mut applied_config =
+                                tc_config::configuration_of(interface, &config);
+                            if let Some(commands) = commands {
+                                applied_config.command = commands;
+                            }
 
                             // Publish TC configuration update so frontend knows the current state
                             if let Err(e) = self
                                 .publish_tc_config(
-                                    &request.namespace,
-                                    &request.interface,
+                                    namespace,
+                                    interface,
                                     Some(applied_config.clone()),
                                 )
                                 .await
@@ -293,17 +747,18 @@ impl TcBackend {
                                 success: true,
                                 message: format!(
                                     "TC applied successfully to {}:{}",
-                                    request.namespace, request.interface
+                                    namespace, interface
                                 ),
                                 applied_config: Some(applied_config),
                                 error_code: None,
+                                peer: None,
+                                rejected_feature: None,
+                                scheduled_at: None,
                             }
                         } else {
                             // No meaningful parameters - TC qdisc was removed
                             // Publish TC configuration removal (None config)
-                            if let Err(e) = self
-                                .publish_tc_config(&request.namespace, &request.interface, None)
-                                .await
+                            if let Err(e) = self.publish_tc_config(namespace, interface, None).await
                             {
                                 warn!("Failed to publish TC config removal: {}", e);
                             }
@@ -312,10 +767,13 @@ impl TcBackend {
                                 success: true,
                                 message: format!(
                                     "TC removed from {}:{} (no meaningful parameters)",
-                                    request.namespace, request.interface
+                                    namespace, interface
                                 ),
                                 applied_config: None,
                                 error_code: None,
+                                peer: None,
+                                rejected_feature: None,
+                                scheduled_at: None,
                             }
                         }
                     }
@@ -332,22 +790,55 @@ impl TcBackend {
                         ),
                         applied_config: None,
                         error_code: Some(-1),
+                        peer: None,
+                        rejected_feature: tc_commands::rejected_feature(&e.to_string()),
+                        scheduled_at: None,
                     },
                 }
             }
+            TcOperation::ApplyRed { params } => {
+                self.apply_queue_qdisc(namespace, interface, QueueQdisc::Red(params.clone()))
+                    .await
+            }
+            TcOperation::ApplySfq { params } => {
+                self.apply_queue_qdisc(namespace, interface, QueueQdisc::Sfq(params.clone()))
+                    .await
+            }
+            TcOperation::ApplyPrio { params } => {
+                self.apply_queue_qdisc(namespace, interface, QueueQdisc::Prio(params.clone()))
+                    .await
+            }
+            TcOperation::ApplyFqCodel { params } => {
+                self.apply_queue_qdisc(namespace, interface, QueueQdisc::FqCodel(params.clone()))
+                    .await
+            }
+            TcOperation::ApplyCake { params } => {
+                self.apply_queue_qdisc(namespace, interface, QueueQdisc::Cake(params.clone()))
+                    .await
+            }
+            // Split into its targets by `handle_tc_query`; never run on one
+            TcOperation::ApplyBatch { .. } => TcResponse {
+                success: false,
+                message: format!(
+                    "A batch can't be applied to {}:{} as a single operation",
+                    namespace, interface
+                ),
+                applied_config: None,
+                error_code: Some(-1),
+                peer: None,
+                rejected_feature: None,
+                scheduled_at: None,
+            },
             TcOperation::Remove => {
                 let result = self
                     .tc_manager
-                    .remove_tc_config_in_namespace(&request.namespace, &request.interface)
+                    .remove_tc_config_in_namespace(namespace, interface)
                     .await;
 
                 match result {
                     Ok(_) => {
                         // Publish TC configuration removal (None config)
-                        if let Err(e) = self
-                            .publish_tc_config(&request.namespace, &request.interface, None)
-                            .await
-                        {
+                        if let Err(e) = self.publish_tc_config(namespace, interface, None).await {
                             warn!("Failed to publish TC config removal: {}", e);
                         }
 
@@ -355,10 +846,13 @@ impl TcBackend {
                             success: true,
                             message: format!(
                                 "TC removed successfully from {}:{}",
-                                request.namespace, request.interface
+                                namespace, interface
                             ),
                             applied_config: None,
                             error_code: None,
+                            peer: None,
+                            rejected_feature: None,
+                            scheduled_at: None,
                         }
                     }
                     Err(e) => TcResponse {
@@ -366,121 +860,608 @@ impl TcBackend {
                         message: format!("Failed to remove TC: {}", e),
                         applied_config: None,
                         error_code: Some(-1),
+                        peer: None,
+                        rejected_feature: None,
+                        scheduled_at: None,
                     },
                 }
             }
-        };
-
-        // Success rides the value channel on our concrete key; failure rides
-        // reply_err (RFC 05 §2.1 / §3).
-        if response.success {
-            let payload = serde_json::to_string(&response)?;
-            self.reply_value(
-                &query,
-                tc::config_ns_iface_set_key(
-                    &self.local_origin,
-                    &request.namespace,
-                    &request.interface,
-                )
-                .into(),
-                payload,
-            )
-            .await?;
-        } else {
-            self.reply_query_error(&query, "error/tc/apply", &response.message)
-                .await?;
         }
-
-        Ok(())
     }
 
-    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
-    pub(crate) async fn handle_interface_query(
+    /// Replace the root qdisc with a queue-shaping qdisc and publish
+    /// the result. These shape the queue but impair nothing netem
+    /// describes, so only the command is published.
+    async fn apply_queue_qdisc(
         &mut self,
-        query: zenoh::query::Query,
-    ) -> Result<()> {
-        let payload = query.payload().ok_or_else(|| {
-            TcguiError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Interface query missing payload",
-            ))
-        })?;
-        let payload_bytes = payload.to_bytes();
-        let payload_str = std::str::from_utf8(&payload_bytes).map_err(|e| {
-            TcguiError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Invalid UTF-8: {}", e),
-            ))
-        })?;
+        namespace: &str,
+        interface: &str,
+        qdisc: QueueQdisc,
+    ) -> TcResponse {
+        let name = qdisc.name();
+        match self
+            .tc_manager
+            .apply_queue_qdisc(namespace, interface, &qdisc)
+            .await
+        {
+            Ok(command) => {
+                let applied_config = TcConfiguration {
+                    command: command.clone(),
+                    ..TcConfiguration::default()
+                };
+                if let Err(e) = self
+                    .publish_tc_config(namespace, interface, Some(applied_config.clone()))
+                    .await
+                {
+                    warn!("Failed to publish TC config update: {}", e);
+                }
 
-        let request = serde_json::from_str::<InterfaceControlRequest>(payload_str)?;
-        info!("Received Interface control query: {:?}", request);
+                TcResponse {
+                    success: true,
+                    message: format!(
+                        "{} applied to {}:{}: {}",
+                        name, namespace, interface, command
+                    ),
+                    applied_config: Some(applied_config),
+                    error_code: None,
+                    peer: None,
+                    rejected_feature: None,
+                    scheduled_at: None,
+                }
+            }
+            Err(e) => {
+                // An iproute2 too old for the command gets told so, with
+                // the version the capability probe found
+                let error = e.to_string();
+                let mismatch = tc_commands::syntax_mismatch(&error);
+                let message = match &mismatch {
+                    Some(mismatch) => format!(
+                        "Failed to apply {}: {} ({})",
+                        name,
+                        mismatch.message(self.netem_capabilities.tc_version.as_deref()),
+                        error.trim()
+                    ),
+                    None => format!("Failed to apply {}: {}", name, error),
+                };
+                TcResponse {
+                    success: false,
+                    message,
+                    applied_config: None,
+                    error_code: Some(-1),
+                    peer: None,
+                    rejected_feature: mismatch.and_then(|mismatch| mismatch.feature),
+                    scheduled_at: None,
+                }
+            }
+        }
+    }
 
-        // Validate the request target before any privileged operation.
-        if let Err(reason) =
-            tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
-        {
+    /// Repeat a request's operation on the other end of its veth pair and
+    /// fold the outcome into `response`. When no peer can be identified only
+    /// the requested end has changed, which the response says.
+    async fn apply_tc_to_veth_peer(
+        &mut self,
+        request: &TcRequest,
+        mut response: TcResponse,
+    ) -> TcResponse {
+        let peer = self
+            .interfaces
+            .values()
+            .find(|i| i.namespace == request.namespace && i.name == request.interface)
+            .and_then(|target| find_veth_peer(self.interfaces.values(), target))
+            .map(|peer| (peer.namespace.clone(), peer.name.clone()));
+
+        let Some((peer_namespace, peer_interface)) = peer else {
             warn!(
-                "Rejecting interface request for {}/{}: {}",
-                request.namespace, request.interface, reason
+                "No veth peer known for {}:{}, applied to one end only",
+                request.namespace, request.interface
             );
-            return self
-                .reply_query_error(
-                    &query,
-                    "error/interface/invalid-request",
-                    &format!("Invalid request: {reason}"),
-                )
-                .await;
-        }
+            response.message = format!(
+                "{} (no veth peer found, other end unchanged)",
+                response.message
+            );
+            response.peer = Some(PeerApplyOutcome::NotFound);
+            return response;
+        };
 
-        let response = match &request.operation {
-            InterfaceControlOperation::Enable => {
-                match self
-                    .network_manager
-                    .enable_interface(&request.namespace, &request.interface)
+        let peer_response =
+            match tcgui_shared::validation::validate_target(&peer_namespace, &peer_interface) {
+                Ok(()) => {
+                    // The peer sees the filtered flows the other way round
+                    let filter = request.filter.as_ref().map(TcFilterSpec::reversed);
+                    self.execute_tc_operation(
+                        &peer_namespace,
+                        &peer_interface,
+                        &request.operation,
+                        filter.as_ref(),
+                        request.direction,
+                    )
                     .await
-                {
-                    Ok(_) => InterfaceControlResponse {
-                        success: true,
-                        message: format!(
-                            "Interface {} enabled successfully in namespace {}",
-                            request.interface, request.namespace
-                        ),
-                        new_state: true,
-                        error_code: None,
-                    },
+                }
+                Err(reason) => TcResponse {
+                    success: false,
+                    message: format!("Invalid veth peer: {reason}"),
+                    applied_config: None,
+                    error_code: Some(-1),
+                    peer: None,
+                    rejected_feature: None,
+                    scheduled_at: None,
+                },
+            };
+
+        response.success &= peer_response.success;
+        response.message = format!("{}; peer: {}", response.message, peer_response.message);
+        response.peer = Some(PeerApplyOutcome::Applied {
+            namespace: peer_namespace,
+            interface: peer_interface,
+            success: peer_response.success,
+            message: peer_response.message,
+        });
+        response
+    }
+
+    /// Apply an interface control operation to one interface and describe
+    /// the outcome. TC configuration follows the link down policy.
+    async fn control_interface(
+        &mut self,
+        namespace: &str,
+        interface: &str,
+        operation: InterfaceControlOperation,
+    ) -> InterfaceControlResponse {
+        match operation {
+            InterfaceControlOperation::Enable => {
+                match self
+                    .network_manager
+                    .enable_interface(namespace, interface)
+                    .await
+                {
+                    Ok(_) => {
+                        self.resume_tc_on_link_up(namespace, interface).await;
+                        InterfaceControlResponse {
+                            success: true,
+                            message: format!(
+                                "Interface {} enabled successfully in namespace {}",
+                                interface, namespace
+                            ),
+                            new_state: true,
+                            error_code: None,
+                            mtu: None,
+                            new_name: None,
+                            addresses: None,
+                        }
+                    }
                     Err(e) => InterfaceControlResponse {
                         success: false,
                         message: format!("Failed to enable interface: {}", e),
                         new_state: false,
                         error_code: Some(-1),
+                        mtu: None,
+                        new_name: None,
+                        addresses: None,
                     },
                 }
             }
             InterfaceControlOperation::Disable => {
                 match self
                     .network_manager
-                    .disable_interface(&request.namespace, &request.interface)
+                    .disable_interface(namespace, interface)
                     .await
                 {
-                    Ok(_) => InterfaceControlResponse {
-                        success: true,
-                        message: format!(
-                            "Interface {} disabled successfully in namespace {}",
-                            request.interface, request.namespace
-                        ),
-                        new_state: false,
-                        error_code: None,
-                    },
+                    Ok(_) => {
+                        self.suspend_tc_on_link_down(namespace, interface).await;
+                        InterfaceControlResponse {
+                            success: true,
+                            message: format!(
+                                "Interface {} disabled successfully in namespace {}",
+                                interface, namespace
+                            ),
+                            new_state: false,
+                            error_code: None,
+                            mtu: None,
+                            new_name: None,
+                            addresses: None,
+                        }
+                    }
                     Err(e) => InterfaceControlResponse {
                         success: false,
                         message: format!("Failed to disable interface: {}", e),
                         new_state: true,
                         error_code: Some(-1),
+                        mtu: None,
+                        new_name: None,
+                        addresses: None,
                     },
                 }
             }
+            InterfaceControlOperation::SetMtu { mtu } => {
+                let result = self
+                    .network_manager
+                    .set_interface_mtu(namespace, interface, mtu)
+                    .await;
+                self.mtu_response(namespace, interface, result, "set")
+            }
+            InterfaceControlOperation::GetMtu => {
+                let result = self
+                    .network_manager
+                    .get_interface_mtu(namespace, interface)
+                    .await;
+                self.mtu_response(namespace, interface, result, "read")
+            }
+            InterfaceControlOperation::AddAddress { cidr } => {
+                let result = self
+                    .network_manager
+                    .add_interface_address(namespace, interface, &cidr)
+                    .await;
+                if result.is_ok() {
+                    self.refresh_interfaces("Address change").await;
+                }
+                self.address_response(namespace, interface, result, "add")
+            }
+            InterfaceControlOperation::RemoveAddress { cidr } => {
+                let result = self
+                    .network_manager
+                    .remove_interface_address(namespace, interface, &cidr)
+                    .await;
+                if result.is_ok() {
+                    self.refresh_interfaces("Address change").await;
+                }
+                self.address_response(namespace, interface, result, "remove")
+            }
+            InterfaceControlOperation::Rename { new_name } => {
+                let is_up = self.last_known_is_up(namespace, interface);
+                match self
+                    .network_manager
+                    .rename_interface(namespace, interface, &new_name)
+                    .await
+                {
+                    Ok(()) => {
+                        // Publish the interface under its new name (and the
+                        // removal of the old one) without waiting for netlink
+                        self.refresh_interfaces("Interface rename").await;
+                        InterfaceControlResponse {
+                            success: true,
+                            message: format!(
+                                "Interface {} in namespace {} renamed to {}",
+                                interface, namespace, new_name
+                            ),
+                            new_state: is_up,
+                            error_code: None,
+                            mtu: None,
+                            new_name: Some(new_name),
+                            addresses: None,
+                        }
+                    }
+                    Err(e) => InterfaceControlResponse {
+                        success: false,
+                        message: format!("Failed to rename interface: {}", e),
+                        new_state: is_up,
+                        error_code: Some(-1),
+                        mtu: None,
+                        new_name: None,
+                        addresses: None,
+                    },
+                }
+            }
+        }
+    }
+
+    /// Describe the outcome of an address operation. The link state is left
+    /// as it was, so `new_state` reports the last known one.
+    fn address_response(
+        &self,
+        namespace: &str,
+        interface: &str,
+        result: Result<Vec<String>>,
+        action: &str,
+    ) -> InterfaceControlResponse {
+        let is_up = self.last_known_is_up(namespace, interface);
+        match result {
+            Ok(addresses) => InterfaceControlResponse {
+                success: true,
+                message: format!(
+                    "Addresses of interface {} in namespace {}: {}",
+                    interface,
+                    namespace,
+                    if addresses.is_empty() {
+                        "none".to_string()
+                    } else {
+                        addresses.join(", ")
+                    }
+                ),
+                new_state: is_up,
+                error_code: None,
+                mtu: None,
+                new_name: None,
+                addresses: Some(addresses),
+            },
+            Err(e) => InterfaceControlResponse {
+                success: false,
+                message: format!("Failed to {} address: {}", action, e),
+                new_state: is_up,
+                error_code: Some(-1),
+                mtu: None,
+                new_name: None,
+                addresses: None,
+            },
+        }
+    }
+
+    /// Describe the outcome of an MTU operation. The link state is left as
+    /// it was, so `new_state` reports the last known one.
+    fn mtu_response(
+        &self,
+        namespace: &str,
+        interface: &str,
+        result: Result<u32>,
+        action: &str,
+    ) -> InterfaceControlResponse {
+        let is_up = self.last_known_is_up(namespace, interface);
+        match result {
+            Ok(mtu) => InterfaceControlResponse {
+                success: true,
+                message: format!(
+                    "MTU of interface {} in namespace {} is {}",
+                    interface, namespace, mtu
+                ),
+                new_state: is_up,
+                error_code: None,
+                mtu: Some(mtu),
+                new_name: None,
+                addresses: None,
+            },
+            Err(e) => InterfaceControlResponse {
+                success: false,
+                message: format!("Failed to {} MTU: {}", action, e),
+                new_state: is_up,
+                error_code: Some(-1),
+                mtu: None,
+                new_name: None,
+                addresses: None,
+            },
+        }
+    }
+
+    /// Whether the interface was up when last discovered
+    fn last_known_is_up(&self, namespace: &str, interface: &str) -> bool {
+        self.interfaces
+            .values()
+            .find(|i| i.namespace == namespace && i.name == interface)
+            .is_some_and(|i| i.is_up)
+    }
+
+    /// Bring several interfaces of one namespace up or down. Each one is
+    /// attempted in name order regardless of the others' outcome; the reply
+    /// carries a result per interface, so a partial failure is still a value.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_interface_batch_query(
+        &mut self,
+        query: zenoh::query::Query,
+    ) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        if payload_bytes.len() > tcgui_shared::validation::MAX_REQUEST_PAYLOAD_BYTES {
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/interface/invalid-request",
+                    &format!(
+                        "Interface batch payload too large ({} bytes)",
+                        payload_bytes.len()
+                    ),
+                )
+                .await;
+        }
+        let Some(request) = decode_query::<InterfaceControlBatchRequest>(
+            &query,
+            &payload_bytes,
+            "error/interface/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
         };
+        info!("Received Interface batch query: {:?}", request);
+        if matches!(request.operation, InterfaceControlOperation::Rename { .. }) {
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/interface/invalid-request",
+                    "Interfaces are renamed one at a time",
+                )
+                .await;
+        }
+
+        let targets: BTreeSet<&str> = request.interfaces.iter().map(String::as_str).collect();
+        let mut results = Vec::with_capacity(targets.len());
+        for interface in targets {
+            let result =
+                match tcgui_shared::validation::validate_target(&request.namespace, interface) {
+                    Ok(()) => {
+                        let response = self
+                            .control_interface(
+                                &request.namespace,
+                                interface,
+                                request.operation.clone(),
+                            )
+                            .await;
+                        InterfaceControlTargetResult {
+                            interface: interface.to_string(),
+                            success: response.success,
+                            message: response.message,
+                        }
+                    }
+                    Err(reason) => InterfaceControlTargetResult {
+                        interface: interface.to_string(),
+                        success: false,
+                        message: format!("Invalid request: {reason}"),
+                    },
+                };
+            if !result.success {
+                warn!(
+                    "Batch {:?} failed for {}/{}: {}",
+                    request.operation, request.namespace, result.interface, result.message
+                );
+            }
+            results.push(result);
+        }
+
+        let response = InterfaceControlBatchResponse {
+            namespace: request.namespace.clone(),
+            operation: request.operation,
+            results,
+        };
+        info!("{}", response.summary());
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::interfaces_ns_set_key(&self.local_origin, &request.namespace).into(),
+            payload,
+        )
+        .await
+    }
+
+    /// Remove one impairment from an interface, keeping the others: read the
+    /// configuration back from the kernel, drop the feature and reapply what
+    /// is left (removing the qdisc when nothing is).
+    async fn clear_feature(
+        &mut self,
+        namespace: &str,
+        interface: &str,
+        feature: TcFeature,
+    ) -> InterfaceControlTargetResult {
+        let result = |success, message| InterfaceControlTargetResult {
+            interface: interface.to_string(),
+            success,
+            message,
+        };
+        let current = match self.detect_current_tc_config(namespace, interface).await {
+            Some(current) if current.has_feature(feature) => current,
+            _ => return result(true, format!("No {} configured, unchanged", feature)),
+        };
+
+        let operation = current.without_feature(feature).to_operation();
+        let filter = self.tc_manager.applied_filter(namespace, interface).await;
+        let direction = self
+            .tc_manager
+            .applied_direction(namespace, interface)
+            .await;
+        let response = self
+            .execute_tc_operation(namespace, interface, &operation, filter.as_ref(), direction)
+            .await;
+        if response.success {
+            result(true, format!("Removed {}", feature))
+        } else {
+            result(false, response.message)
+        }
+    }
+
+    /// Remove one impairment from several interfaces of one namespace. Like
+    /// [`Self::handle_interface_batch_query`], every interface is attempted
+    /// in name order and the reply carries a result per interface.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_feature_clear_query(
+        &mut self,
+        query: zenoh::query::Query,
+    ) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        if payload_bytes.len() > tcgui_shared::validation::MAX_REQUEST_PAYLOAD_BYTES {
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/tc/invalid-request",
+                    &format!(
+                        "Feature clear payload too large ({} bytes)",
+                        payload_bytes.len()
+                    ),
+                )
+                .await;
+        }
+        let Some(request) = decode_query::<TcFeatureClearRequest>(
+            &query,
+            &payload_bytes,
+            "error/tc/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        info!("Received feature clear query: {:?}", request);
+
+        let targets: BTreeSet<&str> = request.interfaces.iter().map(String::as_str).collect();
+        let mut results = Vec::with_capacity(targets.len());
+        for interface in targets {
+            let result =
+                match tcgui_shared::validation::validate_target(&request.namespace, interface) {
+                    Ok(()) => {
+                        self.clear_feature(&request.namespace, interface, request.feature)
+                            .await
+                    }
+                    Err(reason) => InterfaceControlTargetResult {
+                        interface: interface.to_string(),
+                        success: false,
+                        message: format!("Invalid request: {reason}"),
+                    },
+                };
+            if !result.success {
+                warn!(
+                    "Removing {} failed for {}/{}: {}",
+                    request.feature, request.namespace, result.interface, result.message
+                );
+            }
+            results.push(result);
+        }
+
+        let response = TcFeatureClearResponse {
+            namespace: request.namespace.clone(),
+            feature: request.feature,
+            results,
+        };
+        info!("{}", response.summary());
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::configs_ns_clear_key(&self.local_origin, &request.namespace).into(),
+            payload,
+        )
+        .await
+    }
+
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_interface_query(
+        &mut self,
+        query: zenoh::query::Query,
+    ) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) = decode_query::<InterfaceControlRequest>(
+            &query,
+            &payload_bytes,
+            "error/interface/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        info!("Received Interface control query: {:?}", request);
+
+        // Validate the request target before any privileged operation.
+        if let Err(reason) =
+            tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
+        {
+            warn!(
+                "Rejecting interface request for {}/{}: {}",
+                request.namespace, request.interface, reason
+            );
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/interface/invalid-request",
+                    &format!("Invalid request: {reason}"),
+                )
+                .await;
+        }
+
+        let response = self
+            .control_interface(&request.namespace, &request.interface, request.operation)
+            .await;
 
         if response.success {
             let payload = serde_json::to_string(&response)?;
@@ -507,21 +1488,16 @@ impl TcBackend {
     pub(crate) async fn handle_diagnostics_query(&self, query: zenoh::query::Query) -> Result<()> {
         use tcgui_shared::{DiagnosticsRequest, DiagnosticsResponse, DiagnosticsResults};
 
-        let payload = query.payload().ok_or_else(|| {
-            TcguiError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Diagnostics query missing payload",
-            ))
-        })?;
-        let payload_bytes = payload.to_bytes();
-        let payload_str = std::str::from_utf8(&payload_bytes).map_err(|e| {
-            TcguiError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Invalid UTF-8: {}", e),
-            ))
-        })?;
-
-        let request = serde_json::from_str::<DiagnosticsRequest>(payload_str)?;
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) = decode_query::<DiagnosticsRequest>(
+            &query,
+            &payload_bytes,
+            "error/diagnostics/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
         info!(
             "Received Diagnostics query for {}/{}",
             request.namespace, request.interface
@@ -530,6 +1506,7 @@ impl TcBackend {
         // Validate the request target before touching the namespace/interface.
         let response = if let Err(reason) =
             tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
+                .and_then(|()| request.test.validate())
         {
             warn!(
                 "Rejecting diagnostics request for {}/{}: {}",
@@ -577,6 +1554,513 @@ impl TcBackend {
         Ok(())
     }
 
+    /// Compare netem and TBF at the requested rate on one interface. The
+    /// interface is reshaped while this runs (about fifteen seconds), so the
+    /// management interface and the impairment limit are respected as for
+    /// an apply.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_rate_comparison_query(
+        &self,
+        query: zenoh::query::Query,
+    ) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) = decode_query::<RateComparisonRequest>(
+            &query,
+            &payload_bytes,
+            "error/diagnostics/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        info!(
+            "Received rate comparison query for {}/{} at {} kbit/s",
+            request.namespace, request.interface, request.rate_kbps
+        );
+
+        let invalid = if let Err(reason) =
+            tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
+        {
+            Some(format!("Invalid request: {reason}"))
+        } else if request.rate_kbps == 0 {
+            Some("Invalid request: the rate must be above 0 kbit/s".to_string())
+        } else {
+            None
+        };
+        if let Some(message) = invalid {
+            return self
+                .reply_query_error(&query, "error/diagnostics/invalid-request", &message)
+                .await;
+        }
+
+        if self.is_management_interface(&request.namespace, &request.interface) {
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/tc/management-interface",
+                    &format!(
+                        "{} carries this backend's control connection; compare on another \
+                         interface",
+                        request.interface
+                    ),
+                )
+                .await;
+        }
+        if let Err(reason) = self.impairment_limit.check(
+            self.interfaces.values(),
+            &request.namespace,
+            &request.interface,
+        ) {
+            return self
+                .reply_query_error(&query, "error/tc/impairment-limit", &reason)
+                .await;
+        }
+
+        let diagnostics_service =
+            diagnostics::DiagnosticsService::new(&self.network_manager, &self.tc_manager);
+        let response = match diagnostics_service.run_rate_comparison(&request).await {
+            Ok(response) => response,
+            Err(e) => {
+                return self
+                    .reply_query_error(&query, "error/diagnostics", &e)
+                    .await;
+            }
+        };
+        if !response.restored {
+            warn!(
+                "{}/{} may not have its original configuration after the rate comparison",
+                request.namespace, request.interface
+            );
+        }
+
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::diagnostics_ns_iface_rate_compare_key(
+                &self.local_origin,
+                &request.namespace,
+                &request.interface,
+            )
+            .into(),
+            payload,
+        )
+        .await
+    }
+
+    /// Reply with the configurations recently published for one interface.
+    /// An interface this backend never configured gets an empty history.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_config_history_query(
+        &self,
+        query: zenoh::query::Query,
+    ) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) = decode_query::<ConfigHistoryRequest>(
+            &query,
+            &payload_bytes,
+            "error/tc/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        info!(
+            "Received config history query for {}/{}",
+            request.namespace, request.interface
+        );
+
+        if let Err(reason) =
+            tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
+        {
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/tc/invalid-request",
+                    &format!("Invalid request: {reason}"),
+                )
+                .await;
+        }
+
+        let response = ConfigHistoryResponse {
+            entries: self.config_history.recent(
+                &request.namespace,
+                &request.interface,
+                request.limit,
+            ),
+            namespace: request.namespace,
+            interface: request.interface,
+        };
+
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::config_ns_iface_history_key(
+                &self.local_origin,
+                &response.namespace,
+                &response.interface,
+            )
+            .into(),
+            payload,
+        )
+        .await
+    }
+
+    /// Reply with the current statistics of one interface, sampled now. An
+    /// interface this backend doesn't track gets an error reply.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_stats_query(&mut self, query: zenoh::query::Query) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) =
+            decode_query::<StatsRequest>(&query, &payload_bytes, "error/tc/malformed-request")
+                .await?
+        else {
+            return Ok(());
+        };
+        info!(
+            "Received stats query for {}/{}",
+            request.namespace, request.interface
+        );
+
+        let Some(interface) = self
+            .interfaces
+            .values()
+            .find(|i| i.namespace == request.namespace && i.name == request.interface)
+            .cloned()
+        else {
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/tc/unknown-interface",
+                    &format!(
+                        "Unknown interface {}/{}",
+                        request.namespace, request.interface
+                    ),
+                )
+                .await;
+        };
+
+        let stats = match self.bandwidth_monitor.sample(&interface).await {
+            Ok(stats) => stats,
+            Err(e) => {
+                warn!(
+                    "Failed to sample {}/{}: {}",
+                    request.namespace, request.interface, e
+                );
+                return self
+                    .reply_query_error(&query, "error/tc/stats", &e.to_string())
+                    .await;
+            }
+        };
+
+        let response = StatsResponse {
+            namespace: request.namespace,
+            interface: request.interface,
+            stats,
+        };
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::bandwidth_ns_iface_snapshot_key(
+                &self.local_origin,
+                &response.namespace,
+                &response.interface,
+            )
+            .into(),
+            payload,
+        )
+        .await
+    }
+
+    /// Create a veth pair or delete a link, then republish the interface
+    /// list. The interface carrying the backend's own traffic is never
+    /// deleted.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_network_query(&mut self, query: zenoh::query::Query) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) = decode_query::<NetworkOperationRequest>(
+            &query,
+            &payload_bytes,
+            "error/network/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        info!("Received network query: {:?}", request);
+
+        let validation = tcgui_shared::validation::validate_namespace(&request.namespace)
+            .and_then(|()| request.operation.validate(&request.namespace));
+        if let Err(reason) = validation {
+            warn!(
+                "Rejecting network request in {}: {}",
+                request.namespace, reason
+            );
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/network/invalid-request",
+                    &format!("Invalid request: {reason}"),
+                )
+                .await;
+        }
+
+        let result = match &request.operation {
+            NetworkOperation::CreateVeth {
+                name_a,
+                name_b,
+                ns_b,
+            } => self
+                .network_manager
+                .create_veth_pair(&request.namespace, name_a, name_b, ns_b.as_deref())
+                .await
+                .map(|()| {
+                    format!(
+                        "Created veth pair {}/{} <-> {}/{}",
+                        request.namespace,
+                        name_a,
+                        ns_b.as_deref().unwrap_or(&request.namespace),
+                        name_b
+                    )
+                }),
+            NetworkOperation::DeleteLink { name } => {
+                let management = self
+                    .interfaces
+                    .values()
+                    .any(|i| i.namespace == request.namespace && &i.name == name && i.management);
+                if management {
+                    Err(anyhow::anyhow!(
+                        "{} carries the backend's control connection",
+                        name
+                    ))
+                } else {
+                    self.network_manager
+                        .delete_link(&request.namespace, name)
+                        .await
+                        .map(|()| format!("Deleted link {}/{}", request.namespace, name))
+                }
+            }
+        };
+
+        let message = match result {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Network operation failed: {}", e);
+                return self
+                    .reply_query_error(&query, "error/network", &e.to_string())
+                    .await;
+            }
+        };
+        info!("{}", message);
+        // Publish the new links (or their removal) without waiting for netlink
+        self.refresh_interfaces("Network operation").await;
+
+        let response = NetworkOperationResponse {
+            namespace: request.namespace,
+            operation: request.operation,
+            success: true,
+            message,
+        };
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::network_ns_set_key(&self.local_origin, &response.namespace).into(),
+            payload,
+        )
+        .await
+    }
+
+    /// Reply with the interface's qdisc/class/filter tree. Objects that can't
+    /// be decoded are part of the reply (with a note); only a failed dump is
+    /// an error.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_tree_query(&self, query: zenoh::query::Query) -> Result<()> {
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) = decode_query::<TcTreeRequest>(
+            &query,
+            &payload_bytes,
+            "error/diagnostics/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        info!(
+            "Received qdisc tree query for {}/{}",
+            request.namespace, request.interface
+        );
+
+        if let Err(reason) =
+            tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
+        {
+            return self
+                .reply_query_error(
+                    &query,
+                    "error/diagnostics/invalid-request",
+                    &format!("Invalid request: {reason}"),
+                )
+                .await;
+        }
+
+        let entries = match self
+            .tc_manager
+            .get_tc_tree(&request.namespace, &request.interface)
+            .await
+        {
+            Ok(entries) => entries,
+            Err(e) => {
+                return self
+                    .reply_query_error(&query, "error/diagnostics", &e.to_string())
+                    .await;
+            }
+        };
+        let response = TcTreeResponse {
+            namespace: request.namespace.clone(),
+            interface: request.interface.clone(),
+            roots: build_tree(entries),
+        };
+        let unparsed = response.unparsed().len();
+        if unparsed > 0 {
+            warn!(
+                "{} of {} TC objects on {}/{} only partly decoded",
+                unparsed,
+                response.nodes().len(),
+                request.namespace,
+                request.interface
+            );
+        }
+
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::diagnostics_tree_key(&self.local_origin).into(),
+            payload,
+        )
+        .await
+    }
+
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_namespace_diagnostics_query(
+        &self,
+        query: zenoh::query::Query,
+    ) -> Result<()> {
+        use tcgui_shared::NamespaceDiagnosticsRequest;
+
+        let payload_bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let Some(request) = decode_query::<NamespaceDiagnosticsRequest>(
+            &query,
+            &payload_bytes,
+            "error/diagnostics/malformed-request",
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        info!(
+            "Received namespace Diagnostics query for {}",
+            request.namespace
+        );
+
+        if let Err(reason) = tcgui_shared::validation::validate_namespace(&request.namespace) {
+            warn!(
+                "Rejecting namespace diagnostics request for {}: {}",
+                request.namespace, reason
+            );
+            self.reply_query_error(
+                &query,
+                "error/diagnostics",
+                &format!("Invalid request: {reason}"),
+            )
+            .await?;
+            return Ok(());
+        }
+
+        let mut interfaces: Vec<String> = self
+            .interfaces
+            .values()
+            // Loopback has no link or peer to test
+            .filter(|iface| {
+                iface.namespace == request.namespace
+                    && iface.interface_type != InterfaceType::Loopback
+            })
+            .map(|iface| iface.name.clone())
+            .collect();
+        interfaces.sort();
+        interfaces.dedup();
+
+        if interfaces.is_empty() {
+            self.reply_query_error(
+                &query,
+                "error/diagnostics",
+                &format!("No interfaces known in namespace '{}'", request.namespace),
+            )
+            .await?;
+            return Ok(());
+        }
+
+        let diagnostics_service =
+            diagnostics::DiagnosticsService::new(&self.network_manager, &self.tc_manager);
+        let response = diagnostics_service
+            .run_namespace_diagnostics(&request, interfaces)
+            .await;
+
+        let payload = serde_json::to_string(&response)?;
+        self.reply_value(
+            &query,
+            tc::diagnostics_namespace_key(&self.local_origin).into(),
+            payload,
+        )
+        .await?;
+        info!(
+            "Namespace diagnostics completed for {}: {}",
+            request.namespace,
+            response.summary()
+        );
+
+        Ok(())
+    }
+
+    /// Reply with the netem features this backend supports.
+    pub(crate) async fn handle_capabilities_query(&self, query: zenoh::query::Query) -> Result<()> {
+        let payload = serde_json::to_string(&self.netem_capabilities)?;
+        self.reply_value(
+            &query,
+            tc::capabilities_key(&self.local_origin).into(),
+            payload,
+        )
+        .await
+    }
+
+    /// Republish everything on request, at most once per cooldown interval
+    /// whichever peer asks.
+    #[instrument(skip(self, query), fields(backend_name = %self.backend_name))]
+    pub(crate) async fn handle_refresh_query(&mut self, query: zenoh::query::Query) -> Result<()> {
+        if let Err(wait) = self
+            .force_refresh_cooldown
+            .try_start(std::time::Instant::now())
+        {
+            let message = format!(
+                "a forced refresh just ran; try again in {:.1}s",
+                wait.as_secs_f64()
+            );
+            warn!("Rejecting refresh query: {}", message);
+            return self
+                .reply_query_error(&query, "error/refresh/rate-limited", &message)
+                .await;
+        }
+
+        match self.force_refresh().await {
+            Ok(response) => {
+                let payload = serde_json::to_string(&response)?;
+                self.reply_value(&query, tc::refresh_key(&self.local_origin).into(), payload)
+                    .await
+            }
+            Err(e) => {
+                self.reply_query_error(&query, "error/refresh", &format!("Refresh failed: {e}"))
+                    .await
+            }
+        }
+    }
+
     #[instrument(skip(self), fields(backend_name = %self.backend_name, status))]
     pub(crate) async fn send_backend_status(&self, status: &str) -> Result<()> {
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
@@ -589,6 +2073,13 @@ impl TcBackend {
             metadata: BackendMetadata::default(),
             namespace_count: 0, // Will be updated by network manager
             interface_count: self.interfaces.len(),
+            failed_namespaces: self.reported_failed_namespaces.clone(),
+            connected_peers: self.connected_peers.len(),
+            peer_ids: if self.expose_peer_info {
+                self.connected_peers.clone()
+            } else {
+                Vec::new()
+            },
         };
 
         let payload = serde_json::to_string(&health_status)?;
@@ -596,6 +2087,8 @@ impl TcBackend {
         self.session
             .put(backend_health_topic.as_keyexpr(), payload)
             .encoding(zenoh::bytes::Encoding::APPLICATION_JSON)
+            .reliability(self.qos.backend_health.reliability)
+            .congestion_control(self.qos.backend_health.congestion_control)
             .await
             .map_err(|e| TcguiError::ZenohError {
                 message: format!("Failed to send backend health status: {}", e),
@@ -653,7 +2146,8 @@ impl TcBackend {
             let publisher = self
                 .session
                 .declare_publisher(zenoh::key_expr::OwnedKeyExpr::from(tc_config_topic))
-                .cache(CacheConfig::default().max_samples(1))
+                .advanced()
+                .with_qos(&self.qos.interface_events)
                 .sample_miss_detection(
                     MissDetectionConfig::default().heartbeat(Duration::from_millis(1000)),
                 )
@@ -663,7 +2157,12 @@ impl TcBackend {
                     message: format!("Failed to declare TC config publisher: {}", e),
                 })?;
 
-            self.tc_config_publishers.insert(key.clone(), publisher);
+            if let Some((evicted, _)) = self.tc_config_publishers.insert(key.clone(), publisher) {
+                info!(
+                    "Evicted least recently used TC config publisher for: {}",
+                    evicted
+                );
+            }
         }
 
         Ok(self.tc_config_publishers.get(&key).unwrap())
@@ -695,3 +2194,133 @@ impl TcBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_batch_applies_the_targets_that_pass_validation() {
+        let batch = TcRequest {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            operation: TcOperation::Remove,
+            apply_to_peer: false,
+            confirm_management: false,
+            apply_at: None,
+            filter: None,
+            direction: TcDirection::Egress,
+        };
+        let targets = [
+            ("default", "eth0"),
+            ("default", "bad name;"),
+            ("default", "mgmt0"),
+            ("ns1", "veth1"),
+        ]
+        .into_iter()
+        .map(|(ns, iface)| (ns.to_string(), iface.to_string()))
+        .collect();
+        let config = TcNetemConfig::default();
+
+        let mut applied = Vec::new();
+        let outcome = apply_batch(&batch, targets, config.clone(), async |request| {
+            tcgui_shared::validation::validate_target(&request.namespace, &request.interface)
+                .map_err(|reason| ("error/tc/invalid-request", reason.to_string()))?;
+            if request.interface == "mgmt0" {
+                return Err(("error/tc/management-interface", "unconfirmed".to_string()));
+            }
+            assert!(
+                matches!(&request.operation, TcOperation::ApplyConfig { config: c } if *c == config)
+            );
+            applied.push(format!("{}/{}", request.namespace, request.interface));
+            Ok(())
+        })
+        .await;
+
+        assert_eq!(applied, ["default/eth0", "ns1/veth1"]);
+        let failed: Vec<_> = outcome
+            .failures
+            .iter()
+            .map(|f| (f.namespace.as_str(), f.interface.as_str(), f.error))
+            .collect();
+        assert_eq!(
+            failed,
+            [
+                ("default", "bad name;", "error/tc/invalid-request"),
+                ("default", "mgmt0", "error/tc/management-interface"),
+            ]
+        );
+        let message = outcome.message();
+        assert!(
+            message
+                .starts_with("TC config applied to 2 of 4 interfaces; failed: default/bad name;"),
+            "{message}"
+        );
+        assert!(message.contains("default/mgmt0 (error/tc/management-interface: unconfirmed)"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_query_source_is_the_querier_session() {
+        let session = crate::test_support::isolated_session();
+        let queryable = session.declare_queryable("test/source").await.unwrap();
+
+        let named = session
+            .get("test/source")
+            .source_info(tcgui_shared::identity::querier_info(&session))
+            .await
+            .unwrap();
+        let query = queryable.recv_async().await.unwrap();
+        assert_eq!(query_source(&query), Some(session.zid().to_string()));
+        drop((query, named));
+
+        let anonymous = session.get("test/source").await.unwrap();
+        let query = queryable.recv_async().await.unwrap();
+        assert_eq!(query_source(&query), None);
+        drop((query, anonymous));
+    }
+
+    /// Query a queryable that decodes requests the way the TC handler does,
+    /// and return the error it replies with
+    async fn reply_error_for(payload: &[u8]) -> String {
+        let session = crate::test_support::isolated_session();
+        let queryable = session.declare_queryable("test/tc").await.unwrap();
+        let handler = tokio::spawn(async move {
+            let query = queryable.recv_async().await.unwrap();
+            let bytes = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+            decode_query::<TcRequest>(&query, &bytes, "error/tc/malformed-request")
+                .await
+                .unwrap()
+        });
+
+        let replies = session
+            .get("test/tc")
+            .payload(payload.to_vec())
+            .await
+            .unwrap();
+        let reply = replies.recv_async().await.unwrap();
+        assert!(handler.await.unwrap().is_none());
+        let error = reply
+            .result()
+            .expect_err("a malformed request must be rejected");
+        error.payload().try_to_string().unwrap().into_owned()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_truncated_payload_is_rejected_as_malformed() {
+        let error = reply_error_for(br#"{"namespace": "default", "interf"#).await;
+        assert!(
+            error.starts_with("error/tc/malformed-request: malformed request: truncated payload"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_garbage_payload_is_rejected_as_malformed() {
+        let error = reply_error_for(&[0xff, 0xfe, 0x00, 0x42]).await;
+        assert!(
+            error
+                .starts_with("error/tc/malformed-request: malformed request: payload is not UTF-8"),
+            "{error}"
+        );
+    }
+}
@@ -586,7 +586,18 @@ mod zenoh_message_tests {
                 corrupt_percent: None,
                 corrupt_correlation: None,
                 rate_limit_kbps: Some(1000),
+                rate_limit_shaper: RateLimitMode::Netem,
+                slot: None,
+                delay_distribution: None,
+                loss_gemodel: None,
+                loss_ecn: false,
+                seed: None,
             },
+            apply_to_peer: false,
+            confirm_management: false,
+            apply_at: None,
+            filter: None,
+            direction: TcDirection::Egress,
         };
 
         let serialized = serde_json::to_string(&request).unwrap();
@@ -616,9 +627,13 @@ mod zenoh_message_tests {
             applied_config: Some(TcConfiguration {
                 loss: 5.0,
                 correlation: Some(25.0),
+                loss_gemodel: None,
+                loss_ecn: false,
+                seed: None,
                 delay_ms: Some(100.0),
                 delay_jitter_ms: None,
                 delay_correlation: None,
+                delay_distribution: None,
                 duplicate_percent: None,
                 duplicate_correlation: None,
                 reorder_percent: None,
@@ -627,10 +642,15 @@ mod zenoh_message_tests {
                 corrupt_percent: None,
                 corrupt_correlation: None,
                 rate_limit_kbps: Some(1000),
+                rate_limit_shaper: RateLimitMode::Netem,
+                slot: None,
                 command: "tc qdisc replace dev eth0 root netem loss 5% delay 100ms rate 1mbit"
                     .to_string(),
             }),
             error_code: None,
+            peer: None,
+            rejected_feature: None,
+            scheduled_at: None,
         };
 
         let serialized = serde_json::to_string(&response).unwrap();
@@ -644,6 +664,32 @@ mod zenoh_message_tests {
         assert_eq!(config.rate_limit_kbps, Some(1000));
     }
 
+    #[test]
+    fn test_tc_batch_serialization() {
+        let request = TcRequest {
+            namespace: "lab".to_string(),
+            interface: "veth0".to_string(),
+            operation: TcOperation::ApplyBatch {
+                targets: vec![
+                    ("lab".to_string(), "veth0".to_string()),
+                    ("lab".to_string(), "veth1".to_string()),
+                ],
+                config: TcNetemConfig::default(),
+            },
+            apply_to_peer: false,
+            confirm_management: false,
+            apply_at: None,
+            filter: None,
+            direction: TcDirection::Egress,
+        };
+        let serialized = serde_json::to_string(&request).unwrap();
+        let deserialized: TcRequest = serde_json::from_str(&serialized).unwrap();
+        let TcOperation::ApplyBatch { targets, .. } = deserialized.operation else {
+            panic!("Expected ApplyBatch operation");
+        };
+        assert_eq!(targets.len(), 2);
+    }
+
     #[test]
     fn test_interface_control_request_serialization() {
         let request = InterfaceControlRequest {
@@ -691,7 +737,18 @@ mod zenoh_message_tests {
                 corrupt_percent: None,
                 corrupt_correlation: None,
                 rate_limit_kbps: None,
+                rate_limit_shaper: RateLimitMode::Netem,
+                slot: None,
+                delay_distribution: None,
+                loss_gemodel: None,
+                loss_ecn: false,
+                seed: None,
             },
+            apply_to_peer: false,
+            confirm_management: false,
+            apply_at: None,
+            filter: None,
+            direction: TcDirection::Egress,
         };
 
         let serialized = serde_json::to_string(&request).unwrap();
@@ -718,3 +775,94 @@ mod zenoh_message_tests {
         }
     }
 }
+
+/// Command sequences for the two rate limit shapers
+#[cfg(test)]
+mod rate_limit_shaper_tests {
+    use tcgui_backend::tc_commands::QdiscKind;
+    use tcgui_shared::{RateLimitMode, TcConfiguration, TcNetemConfig};
+
+    /// 100ms of delay and a 10mbit rate limit shaped by `shaper`, as the
+    /// GUI's legacy Apply operation delivers it to the backend
+    fn delayed_and_limited(shaper: RateLimitMode) -> TcNetemConfig {
+        TcNetemConfig::from_legacy_params(
+            0.0,
+            None,
+            Some(100.0),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(10_000),
+        )
+        .with_extensions(None, None, shaper, None, false, None)
+    }
+
+    fn commands(config: &TcNetemConfig) -> Vec<String> {
+        let applied = config.to_legacy_params();
+        TcConfiguration {
+            delay_ms: Some(config.delay.base_ms),
+            rate_limit_kbps: applied.rate_limit_kbps,
+            rate_limit_shaper: applied.rate_limit_shaper,
+            ..TcConfiguration::default()
+        }
+        .to_command("eth0")
+        .split(" && ")
+        .map(str::to_string)
+        .collect()
+    }
+
+    #[test]
+    fn test_netem_shaper_is_one_netem_qdisc() {
+        let config = delayed_and_limited(RateLimitMode::Netem);
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Netem);
+        assert_eq!(
+            commands(&config),
+            ["tc qdisc replace dev eth0 root netem delay 100ms rate 10mbit"]
+        );
+    }
+
+    #[test]
+    fn test_htb_shaper_is_htb_class_with_netem_leaf() {
+        let config = delayed_and_limited(RateLimitMode::Htb);
+        assert_eq!(QdiscKind::for_config(&config), QdiscKind::Htb);
+        assert_eq!(
+            commands(&config),
+            [
+                "tc qdisc replace dev eth0 root handle 1: htb default 1",
+                "tc class add dev eth0 parent 1: classid 1:1 htb rate 10mbit",
+                "tc qdisc add dev eth0 parent 1:1 handle 10: netem delay 100ms",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_htb_shaper_survives_the_apply_operation() {
+        let applied = TcConfiguration {
+            rate_limit_kbps: Some(512),
+            rate_limit_shaper: RateLimitMode::Htb,
+            ..TcConfiguration::default()
+        };
+        let json = serde_json::to_string(&applied.to_operation()).unwrap();
+        assert!(json.contains(r#""rate_limit_shaper":"htb""#));
+
+        // Older frontends leave the field out and get netem's rate
+        let netem = TcConfiguration {
+            rate_limit_kbps: Some(512),
+            ..TcConfiguration::default()
+        };
+        let json = serde_json::to_string(&netem.to_operation()).unwrap();
+        assert!(!json.contains("rate_limit_shaper"));
+
+        // A rate-limit-only HTB tree still gets an (empty) netem leaf
+        assert_eq!(
+            applied.to_command("eth0").split(" && ").last(),
+            Some("tc qdisc add dev eth0 parent 1:1 handle 10: netem")
+        );
+    }
+}
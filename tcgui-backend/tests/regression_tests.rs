@@ -4,7 +4,7 @@
 //! across refactoring and changes. They test specific scenarios that have
 //! been fixed or implemented to prevent regressions.
 
-use tcgui_shared::TcConfiguration;
+use tcgui_shared::{DelayDistribution, GeModel, RateLimitMode, TcConfiguration, TcSlotConfig};
 
 /// Test TC parameter parsing from real tc command output
 /// This ensures we continue to correctly parse tc qdisc information
@@ -12,14 +12,78 @@ use tcgui_shared::TcConfiguration;
 mod tc_parsing_regression_tests {
     use super::*;
 
-    // Helper function from main.rs tests - kept here for regression testing
+    /// Token following the first occurrence of `keyword` as a whole token.
+    fn value_after<'a>(tokens: &[&'a str], keyword: &str) -> Option<&'a str> {
+        tokens
+            .iter()
+            .position(|token| *token == keyword)
+            .and_then(|i| tokens.get(i + 1))
+            .copied()
+    }
+
+    /// The percentage following `keyword` and, when the token after it is a
+    /// percentage as well, the correlation (`"reorder 30% 75%"`). The command
+    /// line may label it (`"loss 10% correlation 25%"`).
+    fn percent_and_correlation(tokens: &[&str], keyword: &str) -> Option<(f32, Option<f32>)> {
+        let i = tokens.iter().position(|token| *token == keyword)?;
+        let percent = tokens.get(i + 1).and_then(|t| parse_percent(t))?;
+        let mut rest = tokens[i + 2..].iter().skip_while(|t| **t == "correlation");
+        Some((percent, rest.next().and_then(|t| parse_percent(t))))
+    }
+
+    /// A percentage token such as `"27.8%"`, if it is within 0-100.
+    fn parse_percent(token: &str) -> Option<f32> {
+        token
+            .strip_suffix('%')?
+            .parse::<f32>()
+            .ok()
+            .filter(|v| (0.0..=100.0).contains(v))
+    }
+
+    /// A time token (`"1.5ms"`, `"2.95s"`, `"500us"`) in milliseconds.
+    fn parse_time_ms(token: &str) -> Option<f32> {
+        let (number, scale) = if let Some(n) = token.strip_suffix("ms") {
+            (n, 1.0)
+        } else if let Some(n) = token.strip_suffix("us") {
+            (n, 0.001)
+        } else if let Some(n) = token.strip_suffix('s') {
+            (n, 1000.0)
+        } else {
+            return None;
+        };
+        number
+            .parse::<f32>()
+            .ok()
+            .map(|v| v * scale)
+            .filter(|v| v.is_finite() && *v >= 0.0)
+    }
+
+    /// A rate token (`"100Kbit"`, `"2mbit"`) in kbit/s.
+    fn parse_rate_kbps(token: &str) -> Option<u32> {
+        let token = token.to_ascii_lowercase();
+        if let Some(n) = token.strip_suffix("kbit") {
+            n.parse::<u32>().ok()
+        } else if let Some(n) = token.strip_suffix("mbit") {
+            n.parse::<u32>().ok()?.checked_mul(1000)
+        } else {
+            None
+        }
+    }
+
+    // Helper function from main.rs tests - kept here for regression testing.
+    // It reads `tc qdisc show` text, so it works on whitespace-separated
+    // tokens and never slices by byte offset: arbitrary input can't panic it.
     fn parse_tc_parameters_test(qdisc_info: &str) -> TcConfiguration {
         let mut config = TcConfiguration {
             loss: 0.0,
             correlation: None,
+            loss_gemodel: None,
+            loss_ecn: false,
+            seed: None,
             delay_ms: None,
             delay_jitter_ms: None,
             delay_correlation: None,
+            delay_distribution: None,
             duplicate_percent: None,
             duplicate_correlation: None,
             reorder_percent: None,
@@ -28,118 +92,108 @@ mod tc_parsing_regression_tests {
             corrupt_percent: None,
             corrupt_correlation: None,
             rate_limit_kbps: None,
+            rate_limit_shaper: RateLimitMode::Netem,
+            slot: None,
             command: format!("# Detected: {}", qdisc_info.trim()),
         };
+        let tokens: Vec<&str> = qdisc_info.split_whitespace().collect();
+
+        // Parse loss percentage and correlation ("loss 49.1% 30.1%")
+        if let Some((loss, correlation)) = percent_and_correlation(&tokens, "loss") {
+            config.loss = loss;
+            config.correlation = correlation;
+        }
 
-        // Parse loss percentage
-        if let Some(loss_start) = qdisc_info.find("loss ") {
-            let loss_part = &qdisc_info[loss_start + 5..];
-            if let Some(percent_pos) = loss_part.find('%')
-                && let Ok(loss_val) = loss_part[..percent_pos].trim().parse::<f32>()
-            {
-                config.loss = loss_val;
+        // Parse a Gilbert-Elliott model. `tc qdisc show` labels the values
+        // ("loss gemodel p 1% r 25% 1-h 90% 1-k 0%"); the command line takes
+        // them in order, with 1-h and 1-k optional
+        if let Some(i) = tokens
+            .windows(2)
+            .position(|pair| pair == ["loss", "gemodel"])
+        {
+            let values: Vec<f32> = tokens[i + 2..]
+                .iter()
+                .filter(|token| !matches!(**token, "p" | "r" | "1-h" | "1-k"))
+                .map_while(|token| parse_percent(token))
+                .take(4)
+                .collect();
+            if let [p, r, ref rest @ ..] = values[..] {
+                let defaults = GeModel::default();
+                config.loss_gemodel = Some(GeModel {
+                    p,
+                    r,
+                    one_h: rest.first().copied().unwrap_or(defaults.one_h),
+                    one_k: rest.get(1).copied().unwrap_or(defaults.one_k),
+                });
             }
         }
 
+        // Parse the ECN flag trailing the loss clause ("loss 1% ecn"); it
+        // means nothing without loss
+        config.loss_ecn = tokens
+            .iter()
+            .position(|token| *token == "loss")
+            .is_some_and(|i| tokens[i + 1..].contains(&"ecn"))
+            && (config.loss > 0.0 || config.loss_gemodel.is_some());
+
         // Parse delay (format: "delay 100ms 10ms 25%" for delay, jitter, correlation)
         // Also handle seconds format: "delay 2.95s"
-        if let Some(delay_start) = qdisc_info.find("delay ") {
-            let delay_part = &qdisc_info[delay_start + 6..];
-            let delay_tokens: Vec<&str> = delay_part.split_whitespace().collect();
-
-            // Parse base delay (first token)
-            if !delay_tokens.is_empty() {
-                let first_token = delay_tokens[0];
-
-                // Handle milliseconds (ms)
-                if first_token.ends_with("ms") {
-                    let delay_str = first_token.trim_end_matches("ms");
-                    if let Ok(delay_val) = delay_str.parse::<f32>() {
-                        config.delay_ms = Some(delay_val);
-                    }
-                }
-                // Handle seconds (s) - convert to milliseconds
-                else if first_token.ends_with("s") {
-                    let delay_str = first_token.trim_end_matches("s");
-                    if let Ok(delay_val) = delay_str.parse::<f32>() {
-                        let delay_ms = delay_val * 1000.0; // Convert seconds to milliseconds
-                        config.delay_ms = Some(delay_ms);
-                    }
-                }
-
-                // Parse jitter (second token if it ends with ms)
-                if delay_tokens.len() > 1 && delay_tokens[1].ends_with("ms") {
-                    let jitter_str = delay_tokens[1].trim_end_matches("ms");
-                    if let Ok(jitter_val) = jitter_str.parse::<f32>() {
-                        config.delay_jitter_ms = Some(jitter_val);
-                    }
-
-                    // Parse correlation (third token if it ends with %)
-                    if delay_tokens.len() > 2 && delay_tokens[2].ends_with("%") {
-                        let corr_str = delay_tokens[2].trim_end_matches("%");
-                        if let Ok(corr_val) = corr_str.parse::<f32>() {
-                            config.delay_correlation = Some(corr_val);
-                        }
-                    }
+        if let Some(i) = tokens.iter().position(|token| *token == "delay") {
+            let mut rest = tokens[i + 1..].iter();
+            if let Some(delay_ms) = rest.next().and_then(|t| parse_time_ms(t)) {
+                config.delay_ms = Some(delay_ms);
+
+                // Jitter is the next token if it is a time, then correlation
+                if let Some(jitter_ms) = rest.next().and_then(|t| parse_time_ms(t)) {
+                    config.delay_jitter_ms = Some(jitter_ms);
+                    config.delay_correlation = rest.next().and_then(|t| parse_percent(t));
                 }
             }
         }
 
-        // Parse duplicate percentage
-        if let Some(dup_start) = qdisc_info.find("duplicate ") {
-            let dup_part = &qdisc_info[dup_start + 10..];
-            if let Some(percent_pos) = dup_part.find('%')
-                && let Ok(dup_val) = dup_part[..percent_pos].trim().parse::<f32>()
-            {
-                config.duplicate_percent = Some(dup_val);
-            }
-        }
+        // Parse the jitter distribution ("distribution normal"); uniform is
+        // netem's default and reads back as none
+        config.delay_distribution = value_after(&tokens, "distribution")
+            .and_then(|name| name.parse::<DelayDistribution>().ok())
+            .filter(|distribution| !distribution.is_uniform() && config.delay_jitter_ms.is_some());
 
-        // Parse reorder percentage
-        if let Some(reorder_start) = qdisc_info.find("reorder ") {
-            let reorder_part = &qdisc_info[reorder_start + 8..];
-            if let Some(percent_pos) = reorder_part.find('%')
-                && let Ok(reorder_val) = reorder_part[..percent_pos].trim().parse::<f32>()
-            {
-                config.reorder_percent = Some(reorder_val);
-            }
-
-            // Parse reorder gap
-            if let Some(gap_start) = qdisc_info.find("gap ") {
-                let gap_part = &qdisc_info[gap_start + 4..];
-                let gap_end = gap_part.find(' ').unwrap_or(gap_part.len());
-                if let Ok(gap_val) = gap_part[..gap_end].trim().parse::<u32>() {
-                    config.reorder_gap = Some(gap_val);
-                }
-            }
+        // Parse duplicate percentage
+        config.duplicate_percent = value_after(&tokens, "duplicate").and_then(parse_percent);
+
+        // Parse reorder percentage, correlation and gap ("reorder 30% 75% gap 5")
+        if let Some(reorder) = value_after(&tokens, "reorder") {
+            config.reorder_percent = parse_percent(reorder);
+            config.reorder_correlation =
+                percent_and_correlation(&tokens, "reorder").and_then(|(_, corr)| corr);
+            config.reorder_gap = value_after(&tokens, "gap").and_then(|gap| gap.parse().ok());
         }
 
         // Parse corrupt percentage
-        if let Some(corrupt_start) = qdisc_info.find("corrupt ") {
-            let corrupt_part = &qdisc_info[corrupt_start + 8..];
-            if let Some(percent_pos) = corrupt_part.find('%')
-                && let Ok(corrupt_val) = corrupt_part[..percent_pos].trim().parse::<f32>()
-            {
-                config.corrupt_percent = Some(corrupt_val);
-            }
-        }
+        config.corrupt_percent = value_after(&tokens, "corrupt").and_then(parse_percent);
 
         // Parse rate limiting (can be in kbit, Kbit, mbit, Mbit)
-        if let Some(rate_start) = qdisc_info.find("rate ") {
-            let rate_part = &qdisc_info[rate_start + 5..];
-            let rate_part_lower = rate_part.to_lowercase();
-
-            if let Some(kbit_pos) = rate_part_lower.find("kbit") {
-                if let Ok(rate_val) = rate_part[..kbit_pos].trim().parse::<u32>() {
-                    config.rate_limit_kbps = Some(rate_val);
-                }
-            } else if let Some(mbit_pos) = rate_part_lower.find("mbit")
-                && let Ok(rate_val) = rate_part[..mbit_pos].trim().parse::<u32>()
-            {
-                config.rate_limit_kbps = Some(rate_val * 1000); // Convert mbit to kbit
+        config.rate_limit_kbps = value_after(&tokens, "rate").and_then(parse_rate_kbps);
+
+        // Parse slotting ("slot 10ms 20ms packets 32 bytes 1500"); packets
+        // and bytes are only printed when set
+        if let Some(i) = tokens.iter().position(|token| *token == "slot") {
+            let rest = &tokens[i + 1..];
+            let min = rest.first().and_then(|t| parse_time_ms(t));
+            let max = rest.get(1).and_then(|t| parse_time_ms(t));
+            if let (Some(min_delay_ms), Some(max_delay_ms)) = (min, max) {
+                config.slot = Some(TcSlotConfig {
+                    enabled: true,
+                    min_delay_ms,
+                    max_delay_ms,
+                    packets: value_after(rest, "packets").and_then(|n| n.parse().ok()),
+                    bytes: value_after(rest, "bytes").and_then(|n| n.parse().ok()),
+                });
             }
         }
 
+        // Parse the random seed `tc` prints last
+        config.seed = value_after(&tokens, "seed").and_then(|seed| seed.parse().ok());
+
         config
     }
 
@@ -200,11 +254,35 @@ mod tc_parsing_regression_tests {
 
         // All parameters should be parsed correctly
         assert_eq!(config.loss, 49.1);
+        assert_eq!(config.correlation, Some(30.1));
         assert_eq!(config.delay_ms, Some(2950.0)); // 2.95s = 2950ms
         assert_eq!(config.duplicate_percent, Some(27.8));
         assert_eq!(config.reorder_percent, Some(71.8));
+        assert_eq!(config.reorder_correlation, None);
         assert_eq!(config.corrupt_percent, Some(25.3));
         assert_eq!(config.rate_limit_kbps, Some(1000)); // 1Mbit = 1000kbps
+        assert_eq!(config.seed, Some(10478122975723631342));
+    }
+
+    #[test]
+    fn regression_test_loss_ecn_parsing() {
+        let qdisc_info =
+            "qdisc netem 8031: root refcnt 2 limit 1000 delay 10ms loss 2% ecn rate 1Mbit";
+        let config = parse_tc_parameters_test(qdisc_info);
+        assert_eq!(config.loss, 2.0);
+        assert_eq!(config.correlation, None);
+        assert!(config.loss_ecn);
+        assert_eq!(config.rate_limit_kbps, Some(1000));
+
+        let config = parse_tc_parameters_test(
+            "qdisc netem 8032: root loss gemodel p 1% r 25% 1-h 90% 1-k 0% ecn",
+        );
+        assert!(config.loss_gemodel.is_some());
+        assert!(config.loss_ecn);
+
+        // Output without the flag reads back as plain dropping
+        let config = parse_tc_parameters_test("qdisc netem 8033: root loss 2% 25%");
+        assert!(!config.loss_ecn);
     }
 
     #[test]
@@ -214,9 +292,29 @@ mod tc_parsing_regression_tests {
         let config = parse_tc_parameters_test(qdisc_info);
 
         assert_eq!(config.reorder_percent, Some(25.0));
+        assert_eq!(config.reorder_correlation, None);
         assert_eq!(config.reorder_gap, Some(5));
     }
 
+    #[test]
+    fn regression_test_reorder_and_loss_correlation_parsing() {
+        // The correlation used to be dropped, so re-applying a detected
+        // config installed a different qdisc
+        let config = parse_tc_parameters_test(
+            "qdisc netem 802d: root refcnt 2 limit 1000 loss 10% 25% reorder 30% 75% gap 5",
+        );
+        assert_eq!(config.loss, 10.0);
+        assert_eq!(config.correlation, Some(25.0));
+        assert_eq!(config.reorder_percent, Some(30.0));
+        assert_eq!(config.reorder_correlation, Some(75.0));
+        assert_eq!(config.reorder_gap, Some(5));
+
+        // And it survives the round trip through the generated command
+        let reparsed = parse_tc_parameters_test(&config.to_command("eth0"));
+        assert_eq!(reparsed.correlation, Some(25.0));
+        assert_eq!(reparsed.reorder_correlation, Some(75.0));
+    }
+
     #[test]
     fn regression_test_zero_values_not_parsed() {
         // Ensure that "0%" values are not set (they should remain None/default)
@@ -268,6 +366,244 @@ mod tc_parsing_regression_tests {
         assert_eq!(config.corrupt_percent, None);
         assert_eq!(config.rate_limit_kbps, None);
     }
+
+    /// Whatever the input, every parsed value is in range.
+    fn assert_sensible(input: &str, config: &TcConfiguration) {
+        let percents = [
+            Some(config.loss),
+            config.delay_correlation,
+            config.duplicate_percent,
+            config.reorder_percent,
+            config.corrupt_percent,
+        ];
+        for percent in percents.into_iter().flatten() {
+            assert!(
+                (0.0..=100.0).contains(&percent),
+                "percentage {} out of range for {:?}",
+                percent,
+                input
+            );
+        }
+        for ms in [config.delay_ms, config.delay_jitter_ms]
+            .into_iter()
+            .flatten()
+        {
+            assert!(
+                ms.is_finite() && ms >= 0.0,
+                "time {} invalid for {:?}",
+                ms,
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn regression_test_parser_survives_arbitrary_input() {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::{Rng, SeedableRng};
+
+        // Fixed seed so a failure can be reproduced
+        let mut rng = StdRng::seed_from_u64(0x7c6e_7165_6d00);
+
+        let real_outputs = [
+            "qdisc netem 802b: root refcnt 9 limit 1000 delay 2.95s loss 49.1% 30.1% duplicate 27.8% reorder 71.8% corrupt 25.3% rate 1Mbit seed 10478122975723631342",
+            "qdisc netem 8030: root refcnt 2 limit 1000 delay 100ms 10ms 25% loss 5% reorder 25% gap 5",
+            "qdisc tbf 8001: root refcnt 2 rate 2Mbit burst 32Kb lat 50ms",
+        ];
+        // Tokens the parser looks for, their values, and awkward neighbours:
+        // multi-byte characters, characters whose lowercase form is longer,
+        // huge numbers, special floats and bare units
+        let vocabulary = [
+            "loss",
+            "delay",
+            "duplicate",
+            "reorder",
+            "gap",
+            "corrupt",
+            "rate",
+            "5%",
+            "100%",
+            "150%",
+            "-3%",
+            "%",
+            "NaN%",
+            "inf%",
+            "1e40%",
+            "10ms",
+            "ms",
+            "s",
+            "2.95s",
+            "-1ms",
+            "infs",
+            "500us",
+            "1Mbit",
+            "4294967Mbit",
+            "99999999999kbit",
+            "Kbit",
+            "mbit",
+            "İ",
+            "Ⱥ",
+            "é",
+            "🌐",
+            "\t",
+            "\n",
+            "loss5%",
+            "delay\u{0}",
+            "",
+        ];
+
+        // Every prefix and suffix of real output, cut at each char boundary
+        for output in real_outputs {
+            for (i, _) in output.char_indices() {
+                for input in [&output[..i], &output[i..]] {
+                    assert_sensible(input, &parse_tc_parameters_test(input));
+                }
+            }
+        }
+
+        // Random token soups
+        for _ in 0..5_000 {
+            let len = rng.gen_range(0..16);
+            let input: Vec<&str> = (0..len)
+                .map(|_| *vocabulary.choose(&mut rng).unwrap())
+                .collect();
+            let separator = if rng.gen_bool(0.5) { " " } else { "  " };
+            let input = input.join(separator);
+            assert_sensible(&input, &parse_tc_parameters_test(&input));
+        }
+
+        // Random characters, including non-ASCII
+        for _ in 0..5_000 {
+            let len = rng.gen_range(0..64);
+            let input: String = (0..len)
+                .map(|_| match rng.gen_range(0..4) {
+                    0 => ' ',
+                    1 => rng.gen_range('0'..='9'),
+                    2 => *b"lossdelayratekmbit%.".choose(&mut rng).unwrap() as char,
+                    _ => rng.r#gen::<char>(),
+                })
+                .collect();
+            assert_sensible(&input, &parse_tc_parameters_test(&input));
+        }
+    }
+
+    #[test]
+    fn regression_test_parser_rejects_out_of_range_values() {
+        let config = parse_tc_parameters_test(
+            "qdisc netem 1: root loss 150% delay infs duplicate NaN% rate 99999999Mbit",
+        );
+        assert_eq!(config.loss, 0.0);
+        assert_eq!(config.delay_ms, None);
+        assert_eq!(config.duplicate_percent, None);
+        assert_eq!(config.rate_limit_kbps, None);
+
+        // Units are matched case-insensitively without disturbing the number
+        let config = parse_tc_parameters_test("qdisc tbf 1: root rate 512KBIT");
+        assert_eq!(config.rate_limit_kbps, Some(512));
+    }
+
+    #[test]
+    fn regression_test_delay_distribution_round_trips() {
+        for distribution in [
+            DelayDistribution::Normal,
+            DelayDistribution::Pareto,
+            DelayDistribution::ParetoNormal,
+        ] {
+            let applied = TcConfiguration {
+                delay_ms: Some(100.0),
+                delay_jitter_ms: Some(20.0),
+                delay_correlation: Some(25.0),
+                delay_distribution: Some(distribution),
+                ..Default::default()
+            };
+            let config = parse_tc_parameters_test(&applied.to_command("eth0"));
+            assert_eq!(config.delay_ms, Some(100.0));
+            assert_eq!(config.delay_jitter_ms, Some(20.0));
+            assert_eq!(config.delay_correlation, Some(25.0));
+            assert_eq!(config.delay_distribution, Some(distribution));
+        }
+
+        // Without a jitter, or with an unknown name, there is nothing to read
+        let config =
+            parse_tc_parameters_test("qdisc netem 1: root delay 100ms distribution normal");
+        assert_eq!(config.delay_distribution, None);
+        let config =
+            parse_tc_parameters_test("qdisc netem 1: root delay 100ms 20ms distribution weibull");
+        assert_eq!(config.delay_distribution, None);
+    }
+
+    #[test]
+    fn regression_test_gemodel_parsing() {
+        let model = GeModel {
+            p: 2.0,
+            r: 30.0,
+            one_h: 80.0,
+            one_k: 0.5,
+        };
+        let applied = TcConfiguration {
+            loss_gemodel: Some(model),
+            delay_ms: Some(10.0),
+            ..Default::default()
+        };
+        let config = parse_tc_parameters_test(&applied.to_command("eth0"));
+        assert_eq!(config.loss, 0.0);
+        assert_eq!(config.loss_gemodel, Some(model));
+        assert_eq!(config.delay_ms, Some(10.0));
+
+        // The labeled form `tc qdisc show` prints
+        let config = parse_tc_parameters_test(
+            "qdisc netem 8001: root refcnt 2 limit 1000 loss gemodel p 2% r 30% 1-h 80% 1-k 0.5%",
+        );
+        assert_eq!(config.loss_gemodel, Some(model));
+
+        // 1-h and 1-k may be left out; p and r may not
+        let config = parse_tc_parameters_test("qdisc netem 1: root loss gemodel 2% 30%");
+        assert_eq!(
+            config.loss_gemodel,
+            Some(GeModel {
+                one_h: 100.0,
+                one_k: 0.0,
+                ..model
+            })
+        );
+        let config = parse_tc_parameters_test("qdisc netem 1: root loss gemodel 2% delay 5ms");
+        assert_eq!(config.loss_gemodel, None);
+    }
+
+    #[test]
+    fn regression_test_slot_parsing() {
+        let slot = TcSlotConfig {
+            enabled: true,
+            min_delay_ms: 10.0,
+            max_delay_ms: 20.0,
+            packets: Some(32),
+            bytes: None,
+        };
+        let applied = TcConfiguration {
+            delay_ms: Some(5.0),
+            slot: Some(slot.clone()),
+            ..Default::default()
+        };
+        let config = parse_tc_parameters_test(&applied.to_command("eth0"));
+        assert_eq!(config.slot, Some(slot.clone()));
+        assert_eq!(config.delay_ms, Some(5.0));
+
+        let config = parse_tc_parameters_test(
+            "qdisc netem 8001: root refcnt 2 limit 1000 slot 10ms 20ms packets 32 bytes 1500",
+        );
+        assert_eq!(
+            config.slot,
+            Some(TcSlotConfig {
+                bytes: Some(1500),
+                ..slot
+            })
+        );
+
+        // Both delays are required
+        let config = parse_tc_parameters_test("qdisc netem 1: root slot 10ms packets 32");
+        assert_eq!(config.slot, None);
+    }
 }
 
 /// Test parameter removal logic (this was a major bug fix)
@@ -594,6 +930,10 @@ mod zenoh_communication_regression_tests {
                 addresses: Vec::new(),
                 qdisc_kind: None,
                 link_speed_mbps: None,
+                mtu: None,
+                peer_index: None,
+                management: false,
+                description: None,
             });
         }
 
@@ -629,6 +969,10 @@ mod zenoh_communication_regression_tests {
             addresses: Vec::new(),
             qdisc_kind: None,
             link_speed_mbps: None,
+            mtu: None,
+            peer_index: None,
+            management: false,
+            description: None,
         };
 
         // Should handle unicode correctly
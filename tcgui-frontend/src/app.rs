@@ -7,19 +7,29 @@
 use iced::event::{self, Event};
 use iced::keyboard::{Event as KeyboardEvent, Key, Modifiers};
 use iced::mouse::{Event as MouseEvent, ScrollDelta};
-use iced::{Element, Subscription, Task};
+use iced::{Element, Subscription, Task, window};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tcgui_shared::ZenohConfig;
+use tcgui_shared::presets::PresetList;
 use tracing::info;
 
 use crate::backend_manager::BackendManager;
-use crate::bandwidth_history::BandwidthHistoryManager;
+use crate::bandwidth_history::{BandwidthHistoryManager, TcChangeMarker};
+use crate::chart_export;
+use crate::endpoint_history;
+use crate::idle::IdleMonitor;
+use crate::inspector::MessageInspector;
 use crate::message_handlers::*;
 use crate::messages::{TcGuiMessage, ZenohEvent};
 use crate::query_manager::QueryManager;
+use crate::recording::{self, ScenarioRecorder};
 use crate::scenario_manager::ScenarioManager;
 use crate::settings::FrontendSettings;
 use crate::ui_state::UiStateManager;
-use crate::view::render_main_view;
+use crate::ui_state::{AppTab, InterfaceKey, InterfaceViewMode};
+use crate::user_presets::UserPresets;
+use crate::view::{interface_navigation_order, render_main_view};
 use crate::zenoh_manager::ZenohManager;
 
 /// Main application state for the TC GUI frontend with modular architecture.
@@ -53,6 +63,13 @@ pub struct UiNotification {
 /// Maximum notifications retained at once (oldest dropped beyond this).
 const MAX_NOTIFICATIONS: usize = 5;
 
+/// How often idleness is checked when idle disconnects are enabled.
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long interface changes are collected before the interface tree is
+/// rebuilt, so a burst of topology churn is applied once.
+const INTERFACE_UPDATE_WINDOW: Duration = Duration::from_millis(250);
+
 pub struct TcGui {
     /// Backend management and state
     backend_manager: BackendManager,
@@ -68,6 +85,13 @@ pub struct TcGui {
     ui_state: UiStateManager,
     /// Zenoh session management
     zenoh_manager: ZenohManager,
+    /// Raw message log, only with `--inspector`
+    inspector: Option<MessageInspector>,
+    /// Drops the Zenoh session while nobody uses the frontend
+    idle: IdleMonitor,
+    /// Connect endpoints of the session not yet confirmed by a backend
+    /// answering; remembered in the history once one does
+    unconfirmed_endpoints: Vec<String>,
 }
 
 impl TcGui {
@@ -79,35 +103,52 @@ impl TcGui {
             settings.theme_mode, settings.zoom_level
         );
 
+        let mut ui_state = UiStateManager::from_settings(&settings);
+        ui_state.set_custom_presets(UserPresets::load());
         let app = Self {
-            backend_manager: BackendManager::new(),
+            backend_manager: BackendManager::with_labels(settings.backend_labels.clone()),
             notifications: Vec::new(),
             bandwidth_history: BandwidthHistoryManager::default(),
             query_manager: QueryManager::new(),
             scenario_manager: ScenarioManager::new(),
-            ui_state: UiStateManager::from_settings(&settings),
+            ui_state,
             zenoh_manager: ZenohManager::new(ZenohConfig::default()),
+            inspector: None,
+            idle: IdleMonitor::new(None, Instant::now()),
+            unconfirmed_endpoints: Vec::new(),
         };
 
         (app, Task::none())
     }
 
     /// Creates a new TcGui application instance with custom Zenoh configuration.
-    pub fn new_with_config(zenoh_config: ZenohConfig) -> (Self, Task<TcGuiMessage>) {
+    /// `inspector` enables the raw Zenoh message inspector; `idle_timeout`
+    /// closes the session after that long without activity.
+    pub fn new_with_config(
+        zenoh_config: ZenohConfig,
+        inspector: bool,
+        idle_timeout: Option<Duration>,
+    ) -> (Self, Task<TcGuiMessage>) {
         let settings = FrontendSettings::load();
         info!(
             "Loaded settings: theme={:?}, zoom={}",
             settings.theme_mode, settings.zoom_level
         );
 
+        let unconfirmed_endpoints = endpoint_history::connect_endpoints(&zenoh_config);
+        let mut ui_state = UiStateManager::from_settings(&settings);
+        ui_state.set_custom_presets(UserPresets::load());
         let app = Self {
-            backend_manager: BackendManager::new(),
+            backend_manager: BackendManager::with_labels(settings.backend_labels.clone()),
             notifications: Vec::new(),
             bandwidth_history: BandwidthHistoryManager::default(),
             query_manager: QueryManager::new(),
             scenario_manager: ScenarioManager::new(),
-            ui_state: UiStateManager::from_settings(&settings),
-            zenoh_manager: ZenohManager::new(zenoh_config),
+            ui_state,
+            zenoh_manager: ZenohManager::new(zenoh_config).with_inspector(inspector),
+            inspector: inspector.then(MessageInspector::default),
+            idle: IdleMonitor::new(idle_timeout, Instant::now()),
+            unconfirmed_endpoints,
         };
 
         (app, Task::none())
@@ -122,9 +163,75 @@ impl TcGui {
         }
     }
 
+    /// Start recording manual changes on `key`, or stop the recording there
+    /// and save it as a scenario.
+    fn toggle_recording(&mut self, key: InterfaceKey) {
+        let now_ms = crate::clock_skew::unix_now_ms().max(0) as u64;
+
+        if self.scenario_manager.is_recording(&key) {
+            let Some(recorder) = self.scenario_manager.stop_recording() else {
+                return;
+            };
+            self.set_recording_flag(&key, false);
+            let scenario = recorder.finish();
+            let steps = scenario.steps.len();
+            let saved = recording::save_to_scenario_dir(&scenario);
+            if let Err(e) = self
+                .scenario_manager
+                .add_scenario(&key.backend_name, scenario.clone())
+            {
+                tracing::warn!("Recorded scenario not sent to backend: {}", e);
+            }
+            match saved {
+                Ok(path) => self.notify(format!(
+                    "Recorded {} step(s) as scenario '{}' ({})",
+                    steps,
+                    scenario.name,
+                    path.display()
+                )),
+                Err(e) => self.notify(format!(
+                    "Recorded scenario '{}' could not be saved: {}",
+                    scenario.name, e
+                )),
+            }
+            return;
+        }
+
+        let Some(initial) = self
+            .backend_manager
+            .backends()
+            .get(&key.backend_name)
+            .and_then(|backend| backend.namespaces.get(&key.namespace))
+            .and_then(|namespace| namespace.tc_interfaces.get(&key.interface))
+            .map(|tc_interface| tc_interface.netem_config())
+        else {
+            return;
+        };
+        match self
+            .scenario_manager
+            .start_recording(ScenarioRecorder::start(key.clone(), initial, now_ms))
+        {
+            Ok(()) => self.set_recording_flag(&key, true),
+            Err(e) => self.notify(format!("Cannot start recording: {}", e)),
+        }
+    }
+
+    fn set_recording_flag(&mut self, key: &InterfaceKey, recording: bool) {
+        if let Some(tc_interface) = self
+            .backend_manager
+            .backends_mut()
+            .get_mut(&key.backend_name)
+            .and_then(|backend| backend.namespaces.get_mut(&key.namespace))
+            .and_then(|namespace| namespace.tc_interfaces.get_mut(&key.interface))
+        {
+            tc_interface.set_recording(recording);
+        }
+    }
+
     /// Saves current UI settings to disk.
     fn save_settings(&self) {
-        let settings = self.ui_state.to_settings();
+        let mut settings = self.ui_state.to_settings();
+        settings.backend_labels = self.backend_manager.backend_labels().clone();
         if let Err(e) = settings.save() {
             tracing::warn!("Failed to save settings: {}", e);
         }
@@ -142,7 +249,7 @@ impl TcGui {
                 interface,
             } => {
                 self.backend_manager
-                    .handle_interface_upsert(&backend_name, interface);
+                    .queue_interface_upsert(&backend_name, interface);
                 Task::none()
             }
             TcGuiMessage::InterfaceRemoved {
@@ -150,11 +257,12 @@ impl TcGui {
                 namespace,
                 interface,
             } => {
-                self.backend_manager.handle_interface_removed(
-                    &backend_name,
-                    &namespace,
-                    &interface,
-                );
+                self.backend_manager
+                    .queue_interface_removed(&backend_name, &namespace, &interface);
+                Task::none()
+            }
+            TcGuiMessage::FlushInterfaceUpdates => {
+                self.backend_manager.flush_interface_changes();
                 Task::none()
             }
             TcGuiMessage::BackendHealthUpdate(health_status) => {
@@ -169,28 +277,112 @@ impl TcGui {
             } => {
                 self.backend_manager
                     .handle_backend_liveliness(backend_name.clone(), alive);
-                // Auto-refresh scenarios when backend reconnects
+                // Auto-refresh scenarios and running executions when backend reconnects
                 if alive {
+                    // A backend answered: the session's endpoints work
+                    if !self.unconfirmed_endpoints.is_empty() {
+                        for endpoint in std::mem::take(&mut self.unconfirmed_endpoints) {
+                            self.ui_state.endpoint_history_mut().record(&endpoint);
+                        }
+                        self.save_settings();
+                    }
                     self.scenario_manager.set_loading(&backend_name, true);
                     if let Err(e) = self.scenario_manager.request_scenarios(&backend_name) {
                         tracing::error!("Failed to auto-refresh scenarios on reconnect: {}", e);
                         self.scenario_manager.set_loading(&backend_name, false);
                     }
+                    if let Err(e) = self
+                        .scenario_manager
+                        .request_active_executions(&backend_name)
+                    {
+                        tracing::error!("Failed to resync executions on reconnect: {}", e);
+                    }
                 }
                 Task::none()
             }
+            TcGuiMessage::NetemCapabilities {
+                backend_name,
+                capabilities,
+            } => {
+                self.backend_manager
+                    .set_netem_capabilities(&backend_name, capabilities);
+                Task::none()
+            }
             TcGuiMessage::TcConfigUpdate(tc_config_update) => {
-                handle_tc_config_update(&mut self.backend_manager, tc_config_update)
+                // The update may be for an interface that is still queued
+                if self.backend_manager.has_pending_interface_changes() {
+                    self.backend_manager.flush_interface_changes();
+                }
+                // Annotate the bandwidth chart with the change
+                self.bandwidth_history.record_tc_change(
+                    &tc_config_update.backend_name,
+                    &tc_config_update.namespace,
+                    &tc_config_update.interface,
+                    TcChangeMarker::from_config_update(
+                        &tc_config_update,
+                        &self
+                            .backend_manager
+                            .clock_skew(&tc_config_update.backend_name),
+                    ),
+                );
+                let key = InterfaceKey {
+                    backend_name: tc_config_update.backend_name.clone(),
+                    namespace: tc_config_update.namespace.clone(),
+                    interface: tc_config_update.interface.clone(),
+                };
+                if let Some(apply) = self.ui_state.coordinated_apply_mut() {
+                    let now_ms = crate::clock_skew::unix_now_ms().max(0) as u64;
+                    apply.on_config_update(&key, now_ms);
+                }
+                let task = handle_tc_config_update(
+                    &mut self.backend_manager,
+                    tc_config_update,
+                    self.ui_state.adopt_detected_tc(),
+                );
+                if self.scenario_manager.is_recording(&key)
+                    && let Some(tc_interface) = self
+                        .backend_manager
+                        .backends()
+                        .get(&key.backend_name)
+                        .and_then(|backend| backend.namespaces.get(&key.namespace))
+                        .and_then(|namespace| namespace.tc_interfaces.get(&key.interface))
+                {
+                    let config = tc_interface.netem_config();
+                    let now_ms = crate::clock_skew::unix_now_ms().max(0) as u64;
+                    self.scenario_manager.record_change(&key, config, now_ms);
+                }
+                task
             }
             TcGuiMessage::TcStatisticsUpdate(tc_stats_update) => {
                 handle_tc_statistics_update(&mut self.backend_manager, tc_stats_update)
             }
             TcGuiMessage::TcOperationResult {
                 backend_name,
+                namespace,
+                interface,
                 response,
             } => {
+                // Point at the control the kernel objected to (or clear it),
+                // and clear the pending marks of what was applied right away
+                // (a scheduled apply is confirmed by its config update)
+                if let Some(tc_interface) = self
+                    .backend_manager
+                    .backends_mut()
+                    .get_mut(&backend_name)
+                    .and_then(|backend| backend.namespaces.get_mut(&namespace))
+                    .and_then(|group| group.tc_interfaces.get_mut(&interface))
+                {
+                    tc_interface.set_rejected_feature(response.rejected_feature);
+                    if response.success
+                        && response.scheduled_at.is_none()
+                        && let Some(applied) = &response.applied_config
+                    {
+                        tc_interface.confirm_applied(applied);
+                    }
+                }
                 // Only surface failures — successes are already reflected by the
-                // Tc config update that follows.
+                // Tc config update that follows. A veth pair apply is reported
+                // either way, since its message says which ends changed.
                 if !response.success {
                     tracing::warn!(
                         "TC operation failed on '{}': {}",
@@ -198,11 +390,43 @@ impl TcGui {
                         response.message
                     );
                     self.notify(response.message);
+                } else {
+                    if let Some(apply) = self.ui_state.coordinated_apply_mut() {
+                        let key = InterfaceKey {
+                            backend_name,
+                            namespace,
+                            interface,
+                        };
+                        apply.on_accepted(&key, response.scheduled_at);
+                    }
+                    if response.peer.is_some() {
+                        self.notify(response.message);
+                    }
+                }
+                Task::none()
+            }
+            TcGuiMessage::TcOperationError {
+                backend_name,
+                namespace,
+                interface,
+                error,
+            } => {
+                tracing::warn!("Backend '{}' query error: {}", backend_name, error);
+                if let Some(apply) = self.ui_state.coordinated_apply_mut() {
+                    let key = InterfaceKey {
+                        backend_name,
+                        namespace,
+                        interface,
+                    };
+                    apply.on_error(&key, error.clone());
                 }
+                self.notify(error);
                 Task::none()
             }
             TcGuiMessage::InterfaceControlResult {
                 backend_name,
+                namespace,
+                interface,
                 response,
             } => {
                 if !response.success {
@@ -212,6 +436,17 @@ impl TcGui {
                         response.message
                     );
                     self.notify(response.message);
+                } else if let Some(backend_group) =
+                    self.backend_manager.backends_mut().get_mut(&backend_name)
+                    && let Some(namespace_group) = backend_group.namespaces.get_mut(&namespace)
+                    && let Some(tc_interface) = namespace_group.tc_interfaces.get_mut(&interface)
+                {
+                    if let Some(mtu) = response.mtu {
+                        tc_interface.set_mtu(mtu);
+                    }
+                    if let Some(addresses) = response.addresses {
+                        tc_interface.set_addresses(addresses);
+                    }
                 }
                 Task::none()
             }
@@ -249,7 +484,11 @@ impl TcGui {
                     bandwidth_update.stats.rx_bytes_per_sec,
                     bandwidth_update.stats.tx_bytes_per_sec,
                 );
-                handle_bandwidth_update(&mut self.backend_manager, bandwidth_update)
+                handle_bandwidth_update(
+                    &mut self.backend_manager,
+                    bandwidth_update,
+                    self.ui_state.drop_alert_per_sec(),
+                )
             }
 
             // Interface messages
@@ -258,13 +497,20 @@ impl TcGui {
                 namespace,
                 interface_name,
                 tc_message,
-            ) => handle_tc_interface_message(
-                &mut self.backend_manager,
-                backend_name,
-                namespace,
-                interface_name,
-                tc_message,
-            ),
+            ) => {
+                // Offered again by the quick actions menu of any interface
+                if let crate::messages::TcInterfaceMessage::PresetSelected(ref preset) = tc_message
+                {
+                    self.ui_state.set_last_preset(preset.clone());
+                }
+                handle_tc_interface_message(
+                    &mut self.backend_manager,
+                    backend_name,
+                    namespace,
+                    interface_name,
+                    tc_message,
+                )
+            }
 
             // Query channel setup
             TcGuiMessage::SetupTcQueryChannel(sender) => {
@@ -287,20 +533,24 @@ impl TcGui {
                 self.query_manager.setup_diagnostics_query_channel(sender);
                 Task::none()
             }
+            TcGuiMessage::SetupRefreshQueryChannel(sender) => {
+                self.query_manager.setup_refresh_query_channel(sender);
+                Task::none()
+            }
+            TcGuiMessage::SetupInterfaceBatchQueryChannel(sender) => {
+                self.query_manager
+                    .setup_interface_batch_query_channel(sender);
+                Task::none()
+            }
+            TcGuiMessage::SetupFeatureClearQueryChannel(sender) => {
+                self.query_manager.setup_feature_clear_query_channel(sender);
+                Task::none()
+            }
 
             // Scenario events
             TcGuiMessage::ScenarioExecutionUpdate(update) => {
-                use tcgui_shared::scenario::ExecutionState;
-
                 // Check if execution is in a terminal state before updating
-                let is_terminal = matches!(
-                    update.execution.state,
-                    ExecutionState::Completed
-                        | ExecutionState::Stopped
-                        | ExecutionState::Failed { .. }
-                );
-
-                if is_terminal {
+                if update.execution.state.is_terminal() {
                     // Remove completed/stopped/failed executions from tracking
                     self.scenario_manager.remove_execution(
                         &update.backend_name,
@@ -308,8 +558,32 @@ impl TcGui {
                         &update.interface,
                     );
                 } else {
+                    // Mark the step the scenario moved to on the chart of
+                    // the interface it runs on
+                    let marker = self.ui_state.annotate_scenario_steps().then(|| {
+                        TcChangeMarker::scenario_step(
+                            &update.execution,
+                            update.timestamp,
+                            &self.backend_manager.clock_skew(&update.backend_name),
+                        )
+                    });
+                    let target = (
+                        update.backend_name.clone(),
+                        update.namespace.clone(),
+                        update.interface.clone(),
+                    );
                     // Update active execution state (with timestamp-based deduplication)
-                    self.scenario_manager.handle_execution_update(*update);
+                    if self.scenario_manager.handle_execution_update(*update)
+                        && let Some(marker) = marker
+                    {
+                        let (backend_name, namespace, interface) = target;
+                        self.bandwidth_history.record_tc_change(
+                            &backend_name,
+                            &namespace,
+                            &interface,
+                            marker,
+                        );
+                    }
                 }
                 Task::none()
             }
@@ -322,6 +596,23 @@ impl TcGui {
                     .remove_execution(&backend_name, &namespace, &interface);
                 Task::none()
             }
+            TcGuiMessage::ActiveExecutionsListed {
+                backend_name,
+                executions,
+            } => {
+                self.scenario_manager
+                    .reconcile_active_executions(&backend_name, executions);
+                Task::none()
+            }
+            TcGuiMessage::DismissScenarioExecution {
+                backend_name,
+                namespace,
+                interface,
+            } => {
+                self.scenario_manager
+                    .remove_execution(&backend_name, &namespace, &interface);
+                Task::none()
+            }
             // State-plane scenario library upsert / removal
             TcGuiMessage::ScenarioUpsert {
                 backend_name,
@@ -368,6 +659,7 @@ impl TcGui {
                 rate_limit_kbps,
             } => handle_apply_tc(
                 &self.query_manager,
+                &mut self.backend_manager,
                 backend_name,
                 namespace,
                 interface,
@@ -386,6 +678,32 @@ impl TcGui {
                 rate_limit_kbps,
             ),
 
+            TcGuiMessage::ToggleScenarioRecording {
+                backend_name,
+                namespace,
+                interface,
+            } => {
+                self.toggle_recording(InterfaceKey {
+                    backend_name,
+                    namespace,
+                    interface,
+                });
+                Task::none()
+            }
+            TcGuiMessage::ApplyTcToVethPair {
+                backend_name,
+                namespace,
+                interface,
+                config,
+            } => handle_apply_tc_to_veth_pair(
+                &self.query_manager,
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+                &config,
+            ),
+
             TcGuiMessage::RemoveTc {
                 backend_name,
                 namespace,
@@ -403,6 +721,30 @@ impl TcGui {
                 namespace,
                 interface,
             } => handle_disable_interface(&self.query_manager, backend_name, namespace, interface),
+            TcGuiMessage::ChangeInterfaceAddress {
+                backend_name,
+                namespace,
+                interface,
+                operation,
+            } => handle_change_interface_address(
+                &self.query_manager,
+                backend_name,
+                namespace,
+                interface,
+                operation,
+            ),
+            TcGuiMessage::SetInterfaceMtu {
+                backend_name,
+                namespace,
+                interface,
+                mtu,
+            } => handle_set_interface_mtu(
+                &self.query_manager,
+                backend_name,
+                namespace,
+                interface,
+                mtu,
+            ),
 
             // Scenario operations
             TcGuiMessage::ListScenarios { backend_name } => {
@@ -464,6 +806,20 @@ impl TcGui {
                 );
                 Task::none()
             }
+            TcGuiMessage::ToggleExecutionPhase {
+                backend_name,
+                namespace,
+                interface,
+                phase,
+            } => {
+                self.scenario_manager.toggle_execution_phase(
+                    &backend_name,
+                    &namespace,
+                    &interface,
+                    &phase,
+                );
+                Task::none()
+            }
 
             TcGuiMessage::ShowScenarioDetails { scenario } => {
                 self.scenario_manager.show_scenario_details(scenario);
@@ -477,6 +833,10 @@ impl TcGui {
                 self.scenario_manager.set_search_filter(filter);
                 Task::none()
             }
+            TcGuiMessage::ScenarioTagFilterChanged(tag) => {
+                self.scenario_manager.set_tag_filter(tag);
+                Task::none()
+            }
             TcGuiMessage::ScenarioSortOptionChanged(option) => {
                 self.scenario_manager.set_sort_option(option);
                 Task::none()
@@ -487,7 +847,26 @@ impl TcGui {
                 scenario_id,
             } => {
                 self.ui_state
-                    .show_interface_selection_dialog(backend_name, scenario_id);
+                    .show_interface_selection_dialog(backend_name.clone(), scenario_id);
+                let backend = self.backend_manager.backends().get(&backend_name);
+                self.ui_state
+                    .preselect_remembered_target(|namespace, interface| {
+                        backend
+                            .and_then(|backend| backend.namespaces.get(namespace))
+                            .is_some_and(|ns| ns.tc_interfaces.contains_key(interface))
+                    });
+                Task::none()
+            }
+            TcGuiMessage::ShowInterfaceControlDialog(backend_name) => {
+                self.ui_state.show_interface_control_dialog(backend_name);
+                Task::none()
+            }
+            TcGuiMessage::ShowFeatureClearDialog(backend_name) => {
+                self.ui_state.show_feature_clear_dialog(backend_name);
+                Task::none()
+            }
+            TcGuiMessage::SelectClearFeature(feature) => {
+                self.ui_state.select_clear_feature(feature);
                 Task::none()
             }
             TcGuiMessage::HideInterfaceSelectionDialog => {
@@ -502,12 +881,81 @@ impl TcGui {
                 self.ui_state.toggle_execution_interface(interface);
                 Task::none()
             }
+            TcGuiMessage::SelectAllExecutionInterfaces => {
+                let dialog = self.ui_state.interface_selection_dialog();
+                let interfaces: Vec<String> = dialog
+                    .selected_namespace
+                    .as_ref()
+                    .and_then(|namespace| {
+                        self.backend_manager
+                            .backends()
+                            .get(&dialog.backend_name)?
+                            .namespaces
+                            .get(namespace)
+                    })
+                    .map(|ns| ns.tc_interfaces.keys().cloned().collect())
+                    .unwrap_or_default();
+                self.ui_state.select_all_execution_interfaces(interfaces);
+                Task::none()
+            }
+            TcGuiMessage::ConfirmInterfaceControl(operation) => {
+                let dialog = self.ui_state.interface_selection_dialog();
+                if let Some(namespace) = &dialog.selected_namespace {
+                    let mut interfaces: Vec<String> =
+                        dialog.selected_interfaces.iter().cloned().collect();
+                    interfaces.sort();
+                    if let Err(e) = self.query_manager.control_interfaces(
+                        dialog.backend_name.clone(),
+                        namespace.clone(),
+                        interfaces,
+                        operation,
+                    ) {
+                        self.notify(e);
+                    }
+                    self.ui_state.hide_interface_selection_dialog();
+                }
+                Task::none()
+            }
+            TcGuiMessage::ConfirmFeatureClear => {
+                let dialog = self.ui_state.interface_selection_dialog();
+                if let (Some(namespace), Some(feature)) =
+                    (&dialog.selected_namespace, dialog.clear_feature)
+                {
+                    let mut interfaces: Vec<String> =
+                        dialog.selected_interfaces.iter().cloned().collect();
+                    interfaces.sort();
+                    if let Err(e) = self.query_manager.clear_feature(
+                        dialog.backend_name.clone(),
+                        namespace.clone(),
+                        interfaces,
+                        feature,
+                    ) {
+                        self.notify(e);
+                    }
+                    self.ui_state.hide_interface_selection_dialog();
+                }
+                Task::none()
+            }
             TcGuiMessage::ToggleLoopExecution => {
                 self.ui_state.toggle_loop_execution();
                 Task::none()
             }
+            TcGuiMessage::ExecutionStartAtChanged(input) => {
+                self.ui_state.set_execution_start_at(input);
+                Task::none()
+            }
             TcGuiMessage::ConfirmScenarioExecution => {
                 let dialog = self.ui_state.interface_selection_dialog();
+                let start_at = match crate::scenario_manager::parse_start_time(
+                    &dialog.start_at_input,
+                    chrono::Local::now(),
+                ) {
+                    Ok(start_at) => start_at,
+                    Err(e) => {
+                        tracing::warn!("Not starting scenario: {}", e);
+                        return Task::none();
+                    }
+                };
                 if let Some(namespace) = &dialog.selected_namespace {
                     // Start execution on all selected interfaces
                     for interface in &dialog.selected_interfaces {
@@ -531,6 +979,7 @@ impl TcGui {
                             namespace,
                             interface,
                             dialog.loop_execution,
+                            start_at,
                         ) {
                             tracing::error!(
                                 "Failed to start scenario execution on {}: {}",
@@ -539,7 +988,9 @@ impl TcGui {
                             );
                         }
                     }
-                    // Hide the dialog after attempting execution
+                    // Remember the target for the next run, then hide the dialog
+                    self.ui_state.remember_execution_target();
+                    self.save_settings();
                     self.ui_state.hide_interface_selection_dialog();
                 }
                 Task::none()
@@ -589,85 +1040,384 @@ impl TcGui {
             }
             // UI operations
             TcGuiMessage::ToggleNamespaceVisibility(backend_name, namespace_name) => {
-                handle_toggle_namespace_visibility(&mut self.ui_state, backend_name, namespace_name)
+                let task = handle_toggle_namespace_visibility(
+                    &mut self.ui_state,
+                    backend_name,
+                    namespace_name,
+                );
+                self.save_settings();
+                task
+            }
+            TcGuiMessage::ToggleBackendVisibility(backend_name) => {
+                let task = handle_toggle_backend_visibility(&mut self.ui_state, backend_name);
+                self.save_settings();
+                task
+            }
+            TcGuiMessage::ShowAllNamespaces => {
+                let task = handle_show_all_namespaces(&mut self.ui_state);
+                self.save_settings();
+                task
+            }
+            TcGuiMessage::ResetUiState => {
+                let task = handle_reset_ui_state(&mut self.ui_state);
+                self.save_settings();
+                task
+            }
+            TcGuiMessage::ShowAllBackends => {
+                let task = handle_show_all_backends(&mut self.ui_state);
+                self.save_settings();
+                task
+            }
+            TcGuiMessage::ToggleBackendDetails(backend_name) => {
+                let label = self.backend_manager.backend_label(&backend_name);
+                self.ui_state.toggle_backend_details(&backend_name, label);
+                Task::none()
+            }
+            TcGuiMessage::CompareLeftPreset(preset_id) => {
+                self.ui_state.set_compared_preset(false, preset_id);
+                Task::none()
+            }
+            TcGuiMessage::CompareRightPreset(preset_id) => {
+                self.ui_state.set_compared_preset(true, preset_id);
+                Task::none()
+            }
+            TcGuiMessage::BackendLabelDraftChanged(label) => {
+                self.ui_state.set_backend_label_draft(label);
+                Task::none()
+            }
+            TcGuiMessage::SaveBackendLabel(backend_name) => {
+                let label = self.ui_state.backend_label_draft().to_string();
+                self.backend_manager
+                    .set_backend_label(&backend_name, &label);
+                self.save_settings();
+                Task::none()
             }
-            TcGuiMessage::ShowAllNamespaces => handle_show_all_namespaces(&mut self.ui_state),
-            TcGuiMessage::ResetUiState => handle_reset_ui_state(&mut self.ui_state),
-            TcGuiMessage::ShowAllBackends => handle_show_all_backends(&mut self.ui_state),
             TcGuiMessage::SetInterfaceSearch(search) => {
                 self.ui_state.set_interface_search(search);
                 Task::none()
             }
-            TcGuiMessage::SwitchTab(tab) => {
-                self.ui_state.set_current_tab(tab);
-                self.save_settings();
-
-                // Auto-refresh scenarios and templates when switching to Scenarios tab
-                if matches!(tab, crate::ui_state::AppTab::Scenarios) {
-                    // Request scenarios and templates from all connected backends
-                    for backend_name in self.backend_manager.backends().keys() {
-                        // Request scenarios
-                        if let Err(e) = self.scenario_manager.request_scenarios(backend_name) {
-                            tracing::warn!(
-                                "Failed to auto-refresh scenarios from {}: {}",
-                                backend_name,
-                                e
-                            );
-                        }
-
-                        // Request scenarios
-                        if let Err(e) = self.scenario_manager.request_scenarios(backend_name) {
-                            tracing::warn!(
-                                "Failed to auto-refresh scenarios from {}: {}",
-                                backend_name,
-                                e
-                            );
-                        }
-                    }
+            TcGuiMessage::TogglePathPanel => {
+                self.ui_state.toggle_path_panel();
+                Task::none()
+            }
+            TcGuiMessage::UserActivity => {
+                if self.idle.record_activity(Instant::now()) {
+                    // Subscribing again opens a new session; backends
+                    // reannounce and resync as after any reconnect
+                    info!("Activity after idle disconnect, reconnecting");
                 }
-
                 Task::none()
             }
-
-            // Zoom controls (persistent)
-            TcGuiMessage::ZoomIn => {
-                self.ui_state.zoom_in();
-                self.save_settings();
+            TcGuiMessage::IdleCheck => {
+                let busy = self.scenario_manager.is_monitoring()
+                    || self
+                        .backend_manager
+                        .backends()
+                        .values()
+                        .any(|backend| backend.refresh_pending);
+                if self.idle.check(Instant::now(), busy) {
+                    info!("No activity, closing the Zenoh session until the next input");
+                    // No liveliness tokens vanish when our own session goes
+                    let backends: Vec<String> =
+                        self.backend_manager.backends().keys().cloned().collect();
+                    for backend_name in backends {
+                        self.backend_manager
+                            .handle_backend_liveliness(backend_name, false);
+                    }
+                }
                 Task::none()
             }
-            TcGuiMessage::ZoomOut => {
-                self.ui_state.zoom_out();
-                self.save_settings();
+            TcGuiMessage::ToggleConnectionPanel => {
+                self.ui_state.toggle_connection_panel();
                 Task::none()
             }
-            TcGuiMessage::ZoomReset => {
-                self.ui_state.zoom_reset();
-                self.save_settings();
+            TcGuiMessage::EndpointInputChanged(input) => {
+                self.ui_state.set_endpoint_input(input);
                 Task::none()
             }
-            TcGuiMessage::ToggleTheme => {
-                self.ui_state.toggle_theme();
-                self.save_settings();
+            TcGuiMessage::ConnectEndpoint(endpoint) => {
+                let endpoint = endpoint.trim().to_string();
+                if let Err(e) = endpoint_history::validate_endpoint(&endpoint) {
+                    self.notify(format!("Cannot connect to '{}': {}", endpoint, e));
+                    return Task::none();
+                }
+                match self.zenoh_manager.with_connect_endpoint(&endpoint) {
+                    Ok(zenoh_manager) => {
+                        info!("Adding Zenoh connect endpoint {}", endpoint);
+                        // The new configuration restarts the session subscription
+                        self.zenoh_manager = zenoh_manager;
+                        self.unconfirmed_endpoints.push(endpoint);
+                        self.ui_state.set_endpoint_input(String::new());
+                    }
+                    Err(e) => self.notify(format!("Cannot connect to '{}': {}", endpoint, e)),
+                }
                 Task::none()
             }
-            TcGuiMessage::ToggleInterfaceViewMode => {
-                self.ui_state.toggle_interface_view_mode();
+            TcGuiMessage::ForgetEndpoint(endpoint) => {
+                if self.ui_state.endpoint_history_mut().remove(&endpoint) {
+                    self.save_settings();
+                }
                 Task::none()
             }
-            // Namespace filters (persistent)
-            TcGuiMessage::ToggleHostFilter => {
-                self.ui_state.toggle_host_filter();
+            TcGuiMessage::AdoptDetectedTcToggled(adopt) => {
+                self.ui_state.set_adopt_detected_tc(adopt);
                 self.save_settings();
                 Task::none()
             }
-            TcGuiMessage::ToggleNamespaceTypeFilter => {
-                self.ui_state.toggle_namespace_filter();
+            TcGuiMessage::AnnotateScenarioStepsToggled(annotate) => {
+                self.ui_state.set_annotate_scenario_steps(annotate);
                 self.save_settings();
                 Task::none()
             }
-            TcGuiMessage::ToggleContainerFilter => {
-                self.ui_state.toggle_container_filter();
-                self.save_settings();
+            TcGuiMessage::DropAlertInputChanged(input) => {
+                if self.ui_state.set_drop_alert_input(input) {
+                    self.save_settings();
+                }
+                Task::none()
+            }
+            TcGuiMessage::PresetFileInputChanged(input) => {
+                self.ui_state.set_preset_file_input(input);
+                Task::none()
+            }
+            TcGuiMessage::ExportPresets => {
+                let path = PathBuf::from(self.ui_state.preset_file_input().trim());
+                let presets = self.ui_state.custom_presets().list();
+                let message = match presets.export_to_file(&path) {
+                    Ok(()) => format!("Exported {} preset(s) to {}", presets.len(), path.display()),
+                    Err(e) => format!("Cannot export presets: {}", e),
+                };
+                self.notify(message);
+                Task::none()
+            }
+            TcGuiMessage::ImportPresets => {
+                let path = PathBuf::from(self.ui_state.preset_file_input().trim());
+                let message = match PresetList::import_from_file(&path) {
+                    Ok(import) => {
+                        let imported = import.presets.len();
+                        let custom_presets = self.ui_state.custom_presets_mut();
+                        custom_presets.import(import.presets);
+                        let mut message = match custom_presets.save() {
+                            Ok(()) => format!("Imported {} preset(s)", imported),
+                            Err(e) => format!(
+                                "Imported {} preset(s) but cannot save them: {}",
+                                imported, e
+                            ),
+                        };
+                        if !import.skipped.is_empty() {
+                            let skipped: Vec<String> = import
+                                .skipped
+                                .iter()
+                                .map(|s| format!("{} ({})", s.id, s.reason))
+                                .collect();
+                            message.push_str(&format!("; skipped {}", skipped.join(", ")));
+                        }
+                        message
+                    }
+                    Err(e) => format!("Cannot import presets: {}", e),
+                };
+                self.notify(message);
+                Task::none()
+            }
+            TcGuiMessage::AddPathHop(key) => {
+                self.ui_state.add_path_hop(key);
+                Task::none()
+            }
+            TcGuiMessage::RemovePathHop(index) => {
+                self.ui_state.remove_path_hop(index);
+                Task::none()
+            }
+            TcGuiMessage::ClearPath => {
+                self.ui_state.clear_path();
+                Task::none()
+            }
+            TcGuiMessage::CoordinatedActionSelected(action) => {
+                self.ui_state.set_coordinated_action(action);
+                Task::none()
+            }
+            TcGuiMessage::CoordinatedAtChanged(input) => {
+                self.ui_state.set_coordinated_at_input(input);
+                Task::none()
+            }
+            TcGuiMessage::StartCoordinatedApply => {
+                let Some(action) = self.ui_state.coordinated_action().cloned() else {
+                    return Task::none();
+                };
+                let now = chrono::Local::now();
+                let apply_at = match crate::scenario_manager::parse_start_time(
+                    self.ui_state.coordinated_at_input(),
+                    now,
+                ) {
+                    Ok(Some(apply_at)) => apply_at,
+                    Ok(None) => {
+                        now.timestamp_millis().max(0) as u64
+                            + crate::coordinated_apply::DEFAULT_LEAD_MS
+                    }
+                    Err(e) => {
+                        self.notify(e);
+                        return Task::none();
+                    }
+                };
+
+                // Each backend acts on its own clock: warn about the ones
+                // that won't change in step
+                let hops = self.ui_state.path().to_vec();
+                let mut backends: Vec<&str> =
+                    hops.iter().map(|key| key.backend_name.as_str()).collect();
+                backends.sort_unstable();
+                backends.dedup();
+                let warnings =
+                    crate::coordinated_apply::skew_warnings(backends.iter().map(|backend| {
+                        (
+                            self.backend_manager.display_name(backend),
+                            self.backend_manager.clock_skew(backend),
+                        )
+                    }));
+                for warning in &warnings {
+                    tracing::warn!("Coordinated apply: {}", warning);
+                }
+
+                let mut apply = crate::coordinated_apply::CoordinatedApply::new(
+                    action.to_string(),
+                    apply_at,
+                    hops.iter().cloned(),
+                    warnings,
+                );
+                for key in &hops {
+                    if let Err(e) = self.query_manager.schedule_tc(
+                        key.backend_name.clone(),
+                        key.namespace.clone(),
+                        key.interface.clone(),
+                        action.operation(),
+                        apply_at,
+                    ) {
+                        apply.on_error(key, e);
+                    }
+                }
+                self.ui_state.start_coordinated_apply(apply);
+                Task::none()
+            }
+            TcGuiMessage::DismissCoordinatedApply => {
+                self.ui_state.dismiss_coordinated_apply();
+                Task::none()
+            }
+            TcGuiMessage::MoveInterfaceFocus(step) => {
+                // Only the card layout on the interfaces tab is navigable
+                if self.ui_state.current_tab() == AppTab::Interfaces
+                    && self.ui_state.interface_view_mode() == InterfaceViewMode::Cards
+                {
+                    let order = interface_navigation_order(&self.backend_manager, &self.ui_state);
+                    self.ui_state.move_interface_focus(&order, step);
+                }
+                Task::none()
+            }
+            TcGuiMessage::ActivateFocusedInterface => match self.ui_state.focused_interface() {
+                Some(key) => {
+                    let key = key.clone();
+                    handle_tc_interface_message(
+                        &mut self.backend_manager,
+                        key.backend_name,
+                        key.namespace,
+                        key.interface,
+                        crate::messages::TcInterfaceMessage::ToggleChart,
+                    )
+                }
+                None => Task::none(),
+            },
+            TcGuiMessage::ClearInterfaceFocus => {
+                if !self.ui_state.close_interface_menu() {
+                    self.ui_state.clear_interface_focus();
+                }
+                Task::none()
+            }
+            TcGuiMessage::OpenInterfaceMenu(key) => {
+                let position = CURSOR_POSITION
+                    .lock()
+                    .map(|cursor| *cursor)
+                    .unwrap_or(iced::Point::ORIGIN);
+                self.ui_state.open_interface_menu(key, position);
+                Task::none()
+            }
+            TcGuiMessage::CloseInterfaceMenu => {
+                self.ui_state.close_interface_menu();
+                Task::none()
+            }
+            TcGuiMessage::InterfaceMenuAction(action) => {
+                self.ui_state.close_interface_menu();
+                self.update(*action)
+            }
+            TcGuiMessage::CopyToClipboard(contents) => iced::clipboard::write(contents),
+            TcGuiMessage::FocusNextControl => iced::widget::operation::focus_next(),
+            TcGuiMessage::FocusPreviousControl => iced::widget::operation::focus_previous(),
+            TcGuiMessage::SwitchTab(tab) => {
+                self.ui_state.set_current_tab(tab);
+                self.save_settings();
+
+                // Auto-refresh scenarios and templates when switching to Scenarios tab
+                if matches!(tab, crate::ui_state::AppTab::Scenarios) {
+                    // Request scenarios and templates from all connected backends
+                    for backend_name in self.backend_manager.backends().keys() {
+                        // Request scenarios
+                        if let Err(e) = self.scenario_manager.request_scenarios(backend_name) {
+                            tracing::warn!(
+                                "Failed to auto-refresh scenarios from {}: {}",
+                                backend_name,
+                                e
+                            );
+                        }
+
+                        // Request scenarios
+                        if let Err(e) = self.scenario_manager.request_scenarios(backend_name) {
+                            tracing::warn!(
+                                "Failed to auto-refresh scenarios from {}: {}",
+                                backend_name,
+                                e
+                            );
+                        }
+                    }
+                }
+
+                Task::none()
+            }
+
+            // Zoom controls (persistent)
+            TcGuiMessage::ZoomIn => {
+                self.ui_state.zoom_in();
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::ZoomOut => {
+                self.ui_state.zoom_out();
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::ZoomReset => {
+                self.ui_state.zoom_reset();
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::ToggleTheme => {
+                self.ui_state.toggle_theme();
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::ToggleInterfaceViewMode => {
+                self.ui_state.toggle_interface_view_mode();
+                Task::none()
+            }
+            // Namespace filters (persistent)
+            TcGuiMessage::ToggleHostFilter => {
+                self.ui_state.toggle_host_filter();
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::ToggleNamespaceTypeFilter => {
+                self.ui_state.toggle_namespace_filter();
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::ToggleContainerFilter => {
+                self.ui_state.toggle_container_filter();
+                self.save_settings();
                 Task::none()
             }
 
@@ -676,12 +1426,14 @@ impl TcGui {
                 backend_name,
                 namespace,
                 interface,
+                test,
             } => handle_run_diagnostics(
                 &self.query_manager,
                 &mut self.backend_manager,
                 backend_name,
                 namespace,
                 interface,
+                test,
             ),
             TcGuiMessage::DiagnosticsResult {
                 backend_name,
@@ -695,13 +1447,386 @@ impl TcGui {
                 interface,
                 response,
             ),
+            TcGuiMessage::RunNamespaceDiagnostics {
+                backend_name,
+                namespace,
+            } => handle_run_namespace_diagnostics(
+                &self.query_manager,
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+            ),
+            TcGuiMessage::RunQdiscTree {
+                backend_name,
+                namespace,
+                interface,
+            } => handle_run_qdisc_tree(
+                &self.query_manager,
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+            ),
+            TcGuiMessage::QdiscTreeResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => handle_qdisc_tree_result(
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+                result,
+            ),
+            TcGuiMessage::RunRateComparison {
+                backend_name,
+                namespace,
+                interface,
+                rate_kbps,
+            } => handle_run_rate_comparison(
+                &self.query_manager,
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+                rate_kbps,
+            ),
+            TcGuiMessage::RateComparisonResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => handle_rate_comparison_result(
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+                result,
+            ),
+            TcGuiMessage::RunConfigHistory {
+                backend_name,
+                namespace,
+                interface,
+            } => handle_run_config_history(
+                &self.query_manager,
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+            ),
+            TcGuiMessage::ConfigHistoryResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => handle_config_history_result(
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+                result,
+            ),
+            TcGuiMessage::StatsResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => match result {
+                // Fresh counters, but the rates of the latest sample: not
+                // recorded in the chart history, which already has them
+                Ok(response) => handle_bandwidth_update(
+                    &mut self.backend_manager,
+                    tcgui_shared::BandwidthUpdate {
+                        namespace: response.namespace,
+                        interface: response.interface,
+                        stats: response.stats,
+                        backend_name,
+                    },
+                    self.ui_state.drop_alert_per_sec(),
+                ),
+                Err(e) => {
+                    self.notify(format!(
+                        "Could not read the statistics of {}/{} on {}: {}",
+                        namespace, interface, backend_name, e
+                    ));
+                    Task::none()
+                }
+            },
+            TcGuiMessage::NamespaceDiagnosticsResult {
+                backend_name,
+                namespace,
+                result,
+            } => handle_namespace_diagnostics_result(
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                result,
+            ),
+            TcGuiMessage::DismissNamespaceDiagnostics {
+                backend_name,
+                namespace,
+            } => handle_dismiss_namespace_diagnostics(
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+            ),
+            TcGuiMessage::ClearDiagnosticsHistory {
+                backend_name,
+                namespace,
+                interface,
+            } => handle_clear_diagnostics_history(
+                &mut self.backend_manager,
+                backend_name,
+                namespace,
+                interface,
+            ),
+            TcGuiMessage::SetInterfaceColor {
+                backend_name,
+                namespace,
+                interface,
+                color,
+            } => {
+                self.ui_state
+                    .set_interface_color(&backend_name, &namespace, &interface, color);
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::SetInterfaceLabel {
+                backend_name,
+                namespace,
+                interface,
+                label,
+            } => {
+                self.ui_state
+                    .set_interface_label(&backend_name, &namespace, &interface, &label);
+                self.save_settings();
+                Task::none()
+            }
+            TcGuiMessage::ExportBandwidthChart {
+                backend_name,
+                namespace,
+                interface,
+                chart_id,
+            } => chart_export::visible_bounds(chart_id).then(move |bounds| {
+                let (backend_name, namespace, interface) =
+                    (backend_name.clone(), namespace.clone(), interface.clone());
+                window::latest()
+                    .then(|id| match id {
+                        Some(id) => window::screenshot(id).map(Some),
+                        None => Task::done(None),
+                    })
+                    .map(move |screenshot| TcGuiMessage::BandwidthChartCaptured {
+                        backend_name: backend_name.clone(),
+                        namespace: namespace.clone(),
+                        interface: interface.clone(),
+                        bounds,
+                        screenshot,
+                    })
+            }),
+            TcGuiMessage::BandwidthChartCaptured {
+                backend_name,
+                namespace,
+                interface,
+                bounds,
+                screenshot,
+            } => {
+                let png = match (bounds, screenshot) {
+                    (Some(bounds), Some(screenshot)) => chart_export::crop(&screenshot, bounds)
+                        .ok_or_else(|| "the chart is not on screen".to_string())
+                        .and_then(|chart| chart_export::encode_png(&chart)),
+                    (None, _) => Err("the chart is not on screen".to_string()),
+                    (_, None) => Err("screenshots are not supported".to_string()),
+                };
+                let message = match png {
+                    Ok(png) => match chart_export::save(&interface, "png", &png) {
+                        Ok(path) => format!(
+                            "Saved bandwidth chart of {} to {}",
+                            interface,
+                            path.display()
+                        ),
+                        Err(e) => format!("Cannot save bandwidth chart of {}: {}", interface, e),
+                    },
+                    Err(reason) => {
+                        let csv = self.bandwidth_history.export_csv(
+                            &backend_name,
+                            &namespace,
+                            &interface,
+                        );
+                        match chart_export::save(&interface, "csv", csv.as_bytes()) {
+                            Ok(path) => format!(
+                                "Cannot capture bandwidth chart of {} ({}); saved its data to {} instead",
+                                interface,
+                                reason,
+                                path.display()
+                            ),
+                            Err(e) => {
+                                format!("Cannot export bandwidth chart of {}: {}", interface, e)
+                            }
+                        }
+                    }
+                };
+                self.notify(message);
+                Task::none()
+            }
+
+            TcGuiMessage::ExportBandwidthHistory {
+                backend_name,
+                namespace,
+                interface,
+                format,
+            } => {
+                let contents =
+                    self.bandwidth_history
+                        .export(&backend_name, &namespace, &interface, format);
+                let message =
+                    match chart_export::save(&interface, format.extension(), contents.as_bytes()) {
+                        Ok(path) => format!(
+                            "Saved bandwidth history of {} to {}",
+                            interface,
+                            path.display()
+                        ),
+                        Err(e) => format!("Cannot save bandwidth history of {}: {}", interface, e),
+                    };
+                self.notify(message);
+                Task::none()
+            }
+            TcGuiMessage::SaveCustomPreset(preset) => {
+                let name = preset.name.clone();
+                let custom_presets = self.ui_state.custom_presets_mut();
+                let replaced = custom_presets.insert(preset);
+                let message = match custom_presets.save() {
+                    Ok(()) if replaced => format!("Replaced preset {}", name),
+                    Ok(()) => format!("Saved preset {}", name),
+                    Err(e) => format!("Cannot save preset {}: {}", name, e),
+                };
+                self.notify(message);
+                Task::none()
+            }
+
+            TcGuiMessage::InspectorRecord(entry) => {
+                if let Some(inspector) = &mut self.inspector {
+                    inspector.record(*entry);
+                }
+                Task::none()
+            }
+            TcGuiMessage::ToggleInspector => {
+                if let Some(inspector) = &mut self.inspector {
+                    inspector.open = !inspector.open;
+                }
+                Task::none()
+            }
+            TcGuiMessage::ToggleInspectorPause => {
+                if let Some(inspector) = &mut self.inspector {
+                    inspector.paused = !inspector.paused;
+                }
+                Task::none()
+            }
+            TcGuiMessage::ClearInspector => {
+                if let Some(inspector) = &mut self.inspector {
+                    inspector.clear();
+                }
+                Task::none()
+            }
+            TcGuiMessage::InspectorFilterChanged(filter) => {
+                if let Some(inspector) = &mut self.inspector {
+                    inspector.filter = filter;
+                }
+                Task::none()
+            }
+            TcGuiMessage::ForceRefresh(origin) => {
+                let pending = match self.query_manager.force_refresh(origin.clone()) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        self.notify(e);
+                        false
+                    }
+                };
+                if let Some(group) = self.backend_manager.backends_mut().get_mut(&origin) {
+                    group.refresh_pending = pending;
+                }
+                Task::none()
+            }
+            TcGuiMessage::InterfaceBatchResult {
+                backend_name,
+                result,
+            } => {
+                match result {
+                    Ok(response) => {
+                        for failure in response.failures() {
+                            tracing::warn!(
+                                "Backend '{}': {}/{}: {}",
+                                backend_name,
+                                response.namespace,
+                                failure.interface,
+                                failure.message
+                            );
+                        }
+                        if response.failures().next().is_some() {
+                            self.notify(response.summary());
+                        } else {
+                            tracing::info!("Backend '{}': {}", backend_name, response.summary());
+                        }
+                    }
+                    Err(e) => self.notify(format!("Interface batch failed: {}", e)),
+                }
+                Task::none()
+            }
+            TcGuiMessage::FeatureClearResult {
+                backend_name,
+                result,
+            } => {
+                match result {
+                    Ok(response) => {
+                        for result in &response.results {
+                            if result.success {
+                                tracing::info!(
+                                    "Backend '{}': {}/{}: {}",
+                                    backend_name,
+                                    response.namespace,
+                                    result.interface,
+                                    result.message
+                                );
+                            } else {
+                                tracing::warn!(
+                                    "Backend '{}': {}/{}: {}",
+                                    backend_name,
+                                    response.namespace,
+                                    result.interface,
+                                    result.message
+                                );
+                            }
+                        }
+                        self.notify(response.summary());
+                    }
+                    Err(e) => self.notify(format!("Removing impairment failed: {}", e)),
+                }
+                Task::none()
+            }
+            TcGuiMessage::ForceRefreshResult {
+                backend_name,
+                result,
+            } => {
+                if let Some(group) = self.backend_manager.backends_mut().get_mut(&backend_name) {
+                    group.refresh_pending = false;
+                }
+                match result {
+                    Ok(response) => tracing::info!(
+                        "Backend '{}' republished {} interfaces ({} with TC configuration)",
+                        backend_name,
+                        response.interface_count,
+                        response.tc_config_count
+                    ),
+                    Err(e) => self.notify(format!("Refresh failed: {}", e)),
+                }
+                Task::none()
+            }
 
             // Maintenance operations
             TcGuiMessage::CleanupStaleBackends => handle_cleanup_stale_backends(
                 &mut self.backend_manager,
                 &mut self.bandwidth_history,
                 &mut self.query_manager,
-                &mut self.ui_state,
                 &mut self.scenario_manager,
             ),
         }
@@ -709,13 +1834,32 @@ impl TcGui {
 
     /// Renders the application view using the modular view system.
     pub fn view(&self) -> Element<'_, TcGuiMessage> {
-        let main = render_main_view(
+        let mut main = render_main_view(
             &self.backend_manager,
             &self.bandwidth_history,
             &self.ui_state,
             &self.scenario_manager,
+            &self.zenoh_manager.config().endpoints,
         );
 
+        // Developer inspector docked below everything else
+        if let Some(inspector) = &self.inspector
+            && inspector.open
+        {
+            main = iced::widget::column![main, inspector.view()].into();
+        }
+
+        if self.idle.is_disconnected() {
+            use iced::widget::{column, container, text};
+            let banner = container(
+                text("Idle — disconnected. Move the mouse or press a key to reconnect.").size(13),
+            )
+            .padding(8)
+            .width(iced::Length::Fill)
+            .style(container::secondary);
+            main = column![banner, main].into();
+        }
+
         if self.notifications.is_empty() {
             return main;
         }
@@ -754,141 +1898,292 @@ impl TcGui {
 
     /// Sets up subscriptions for Zenoh events and periodic cleanup.
     pub fn subscription(&self) -> Subscription<TcGuiMessage> {
-        Subscription::batch(vec![
+        let mut subscriptions = vec![
             // Keyboard and mouse shortcuts for zoom
             event::listen().filter_map(Self::handle_zoom_event),
-            // Zenoh events subscription
-            self.zenoh_manager.subscription().map(|event| match event {
-                ZenohEvent::InterfaceUpsert {
-                    backend_name,
-                    interface,
-                } => TcGuiMessage::InterfaceUpsert {
-                    backend_name,
-                    interface,
-                },
-                ZenohEvent::InterfaceRemoved {
-                    backend_name,
-                    namespace,
-                    interface,
-                } => TcGuiMessage::InterfaceRemoved {
-                    backend_name,
-                    namespace,
-                    interface,
-                },
-                ZenohEvent::BandwidthUpdate(bandwidth_update) => {
-                    TcGuiMessage::BandwidthUpdate(bandwidth_update)
-                }
-                ZenohEvent::BackendHealthUpdate(health_status) => {
-                    TcGuiMessage::BackendHealthUpdate(health_status)
-                }
-                ZenohEvent::BackendLiveliness {
-                    backend_name,
-                    alive,
-                } => TcGuiMessage::BackendLiveliness {
-                    backend_name,
-                    alive,
-                },
-                ZenohEvent::TcConfigUpdate(tc_config_update) => {
-                    TcGuiMessage::TcConfigUpdate(tc_config_update)
-                }
-                ZenohEvent::TcStatisticsUpdate(tc_stats_update) => {
-                    TcGuiMessage::TcStatisticsUpdate(tc_stats_update)
-                }
-                ZenohEvent::ScenarioExecutionUpdate(execution_update) => {
-                    TcGuiMessage::ScenarioExecutionUpdate(execution_update)
-                }
-                ZenohEvent::ScenarioExecutionRemoved {
-                    backend_name,
-                    namespace,
-                    interface,
-                } => TcGuiMessage::ScenarioExecutionRemoved {
-                    backend_name,
-                    namespace,
-                    interface,
-                },
-                ZenohEvent::ScenarioUpsert {
-                    backend_name,
-                    scenario,
-                } => TcGuiMessage::ScenarioUpsert {
-                    backend_name,
-                    scenario,
-                },
-                ZenohEvent::ScenarioRemoved { backend_name, id } => {
-                    TcGuiMessage::ScenarioRemoved { backend_name, id }
-                }
-                ZenohEvent::ConnectionStatus(connected) => TcGuiMessage::BackendConnectionStatus {
-                    backend_name: "unknown".to_string(),
-                    connected,
-                },
-                ZenohEvent::TcQueryChannelReady(sender) => {
-                    TcGuiMessage::SetupTcQueryChannel(sender)
-                }
-                ZenohEvent::InterfaceQueryChannelReady(sender) => {
-                    TcGuiMessage::SetupInterfaceQueryChannel(sender)
-                }
-                ZenohEvent::ScenarioQueryChannelReady(sender) => {
-                    TcGuiMessage::SetupScenarioQueryChannel(sender)
-                }
-                ZenohEvent::ScenarioExecutionQueryChannelReady(sender) => {
-                    TcGuiMessage::SetupScenarioExecutionQueryChannel(sender)
-                }
-                ZenohEvent::DiagnosticsQueryChannelReady(sender) => {
-                    TcGuiMessage::SetupDiagnosticsQueryChannel(sender)
-                }
-                ZenohEvent::ScenarioResponse {
-                    backend_name,
-                    response,
-                } => TcGuiMessage::ScenarioListResponse {
-                    backend_name,
-                    response,
-                },
-                ZenohEvent::DiagnosticsResponse {
-                    backend_name,
-                    namespace,
-                    interface,
-                    response,
-                } => TcGuiMessage::DiagnosticsResult {
-                    backend_name,
-                    namespace,
-                    interface,
-                    response,
-                },
-                ZenohEvent::TcOperationResult {
-                    backend_name,
-                    response,
-                } => TcGuiMessage::TcOperationResult {
-                    backend_name,
-                    response,
-                },
-                ZenohEvent::InterfaceControlResult {
-                    backend_name,
-                    response,
-                } => TcGuiMessage::InterfaceControlResult {
-                    backend_name,
-                    response,
-                },
-                ZenohEvent::QueryError {
-                    backend_name,
-                    error,
-                } => TcGuiMessage::QueryError {
-                    backend_name,
-                    error,
-                },
-                ZenohEvent::PresetUpsert {
-                    backend_name,
-                    preset,
-                } => TcGuiMessage::PresetUpsert {
-                    backend_name,
-                    preset,
-                },
-                ZenohEvent::PresetRemoved { backend_name, id } => {
-                    TcGuiMessage::PresetRemoved { backend_name, id }
-                }
-            }),
             // Timer for periodic backend cleanup (every 3 seconds)
             iced::time::every(std::time::Duration::from_secs(3))
                 .map(|_| TcGuiMessage::CleanupStaleBackends),
-        ])
+        ];
+        // Dropping the Zenoh subscription closes the session
+        if !self.idle.is_disconnected() {
+            subscriptions.push(self.zenoh_subscription());
+        }
+        if self.backend_manager.has_pending_interface_changes() {
+            subscriptions.push(
+                iced::time::every(INTERFACE_UPDATE_WINDOW)
+                    .map(|_| TcGuiMessage::FlushInterfaceUpdates),
+            );
+        }
+        if self.idle.is_enabled() {
+            // Typing in a field or clicking a button is activity too, so
+            // events captured by widgets are not skipped
+            subscriptions.push(event::listen_with(|event, _status, _window| {
+                Self::activity_event(event)
+            }));
+            subscriptions
+                .push(iced::time::every(IDLE_CHECK_INTERVAL).map(|_| TcGuiMessage::IdleCheck));
+        }
+        Subscription::batch(subscriptions)
+    }
+
+    /// Zenoh events, mapped to application messages.
+    fn zenoh_subscription(&self) -> Subscription<TcGuiMessage> {
+        self.zenoh_manager.subscription().map(|event| match event {
+            ZenohEvent::InterfaceUpsert {
+                backend_name,
+                interface,
+            } => TcGuiMessage::InterfaceUpsert {
+                backend_name,
+                interface,
+            },
+            ZenohEvent::InterfaceRemoved {
+                backend_name,
+                namespace,
+                interface,
+            } => TcGuiMessage::InterfaceRemoved {
+                backend_name,
+                namespace,
+                interface,
+            },
+            ZenohEvent::BandwidthUpdate(bandwidth_update) => {
+                TcGuiMessage::BandwidthUpdate(bandwidth_update)
+            }
+            ZenohEvent::BackendHealthUpdate(health_status) => {
+                TcGuiMessage::BackendHealthUpdate(health_status)
+            }
+            ZenohEvent::BackendLiveliness {
+                backend_name,
+                alive,
+            } => TcGuiMessage::BackendLiveliness {
+                backend_name,
+                alive,
+            },
+            ZenohEvent::NetemCapabilities {
+                backend_name,
+                capabilities,
+            } => TcGuiMessage::NetemCapabilities {
+                backend_name,
+                capabilities,
+            },
+            ZenohEvent::TcConfigUpdate(tc_config_update) => {
+                TcGuiMessage::TcConfigUpdate(tc_config_update)
+            }
+            ZenohEvent::TcStatisticsUpdate(tc_stats_update) => {
+                TcGuiMessage::TcStatisticsUpdate(tc_stats_update)
+            }
+            ZenohEvent::ScenarioExecutionUpdate(execution_update) => {
+                TcGuiMessage::ScenarioExecutionUpdate(execution_update)
+            }
+            ZenohEvent::ScenarioExecutionRemoved {
+                backend_name,
+                namespace,
+                interface,
+            } => TcGuiMessage::ScenarioExecutionRemoved {
+                backend_name,
+                namespace,
+                interface,
+            },
+            ZenohEvent::ActiveExecutionsListed {
+                backend_name,
+                executions,
+            } => TcGuiMessage::ActiveExecutionsListed {
+                backend_name,
+                executions,
+            },
+            ZenohEvent::ScenarioUpsert {
+                backend_name,
+                scenario,
+            } => TcGuiMessage::ScenarioUpsert {
+                backend_name,
+                scenario,
+            },
+            ZenohEvent::ScenarioRemoved { backend_name, id } => {
+                TcGuiMessage::ScenarioRemoved { backend_name, id }
+            }
+            ZenohEvent::ConnectionStatus(connected) => TcGuiMessage::BackendConnectionStatus {
+                backend_name: "unknown".to_string(),
+                connected,
+            },
+            ZenohEvent::TcQueryChannelReady(sender) => TcGuiMessage::SetupTcQueryChannel(sender),
+            ZenohEvent::InterfaceQueryChannelReady(sender) => {
+                TcGuiMessage::SetupInterfaceQueryChannel(sender)
+            }
+            ZenohEvent::ScenarioQueryChannelReady(sender) => {
+                TcGuiMessage::SetupScenarioQueryChannel(sender)
+            }
+            ZenohEvent::ScenarioExecutionQueryChannelReady(sender) => {
+                TcGuiMessage::SetupScenarioExecutionQueryChannel(sender)
+            }
+            ZenohEvent::DiagnosticsQueryChannelReady(sender) => {
+                TcGuiMessage::SetupDiagnosticsQueryChannel(sender)
+            }
+            ZenohEvent::RefreshQueryChannelReady(sender) => {
+                TcGuiMessage::SetupRefreshQueryChannel(sender)
+            }
+            ZenohEvent::InterfaceBatchQueryChannelReady(sender) => {
+                TcGuiMessage::SetupInterfaceBatchQueryChannel(sender)
+            }
+            ZenohEvent::FeatureClearQueryChannelReady(sender) => {
+                TcGuiMessage::SetupFeatureClearQueryChannel(sender)
+            }
+            ZenohEvent::ScenarioResponse {
+                backend_name,
+                response,
+            } => TcGuiMessage::ScenarioListResponse {
+                backend_name,
+                response,
+            },
+            ZenohEvent::DiagnosticsResponse {
+                backend_name,
+                namespace,
+                interface,
+                response,
+            } => TcGuiMessage::DiagnosticsResult {
+                backend_name,
+                namespace,
+                interface,
+                response,
+            },
+            ZenohEvent::NamespaceDiagnosticsResponse {
+                backend_name,
+                namespace,
+                result,
+            } => TcGuiMessage::NamespaceDiagnosticsResult {
+                backend_name,
+                namespace,
+                result,
+            },
+            ZenohEvent::QdiscTreeResponse {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => TcGuiMessage::QdiscTreeResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            },
+            ZenohEvent::RateComparisonResponse {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => TcGuiMessage::RateComparisonResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            },
+            ZenohEvent::ConfigHistoryResponse {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => TcGuiMessage::ConfigHistoryResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            },
+            ZenohEvent::StatsResponse {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            } => TcGuiMessage::StatsResult {
+                backend_name,
+                namespace,
+                interface,
+                result,
+            },
+            ZenohEvent::Inspected(entry) => TcGuiMessage::InspectorRecord(entry),
+            ZenohEvent::ForceRefreshResponse {
+                backend_name,
+                result,
+            } => TcGuiMessage::ForceRefreshResult {
+                backend_name,
+                result,
+            },
+            ZenohEvent::InterfaceBatchResponse {
+                backend_name,
+                result,
+            } => TcGuiMessage::InterfaceBatchResult {
+                backend_name,
+                result,
+            },
+            ZenohEvent::FeatureClearResponse {
+                backend_name,
+                result,
+            } => TcGuiMessage::FeatureClearResult {
+                backend_name,
+                result,
+            },
+            ZenohEvent::TcOperationResult {
+                backend_name,
+                namespace,
+                interface,
+                response,
+            } => TcGuiMessage::TcOperationResult {
+                backend_name,
+                namespace,
+                interface,
+                response,
+            },
+            ZenohEvent::TcOperationError {
+                backend_name,
+                namespace,
+                interface,
+                error,
+            } => TcGuiMessage::TcOperationError {
+                backend_name,
+                namespace,
+                interface,
+                error,
+            },
+            ZenohEvent::InterfaceControlResult {
+                backend_name,
+                namespace,
+                interface,
+                response,
+            } => TcGuiMessage::InterfaceControlResult {
+                backend_name,
+                namespace,
+                interface,
+                response,
+            },
+            ZenohEvent::QueryError {
+                backend_name,
+                error,
+            } => TcGuiMessage::QueryError {
+                backend_name,
+                error,
+            },
+            ZenohEvent::PresetUpsert {
+                backend_name,
+                preset,
+            } => TcGuiMessage::PresetUpsert {
+                backend_name,
+                preset,
+            },
+            ZenohEvent::PresetRemoved { backend_name, id } => {
+                TcGuiMessage::PresetRemoved { backend_name, id }
+            }
+        })
+    }
+
+    /// Input that counts as user activity for the idle disconnect.
+    fn activity_event(event: Event) -> Option<TcGuiMessage> {
+        match event {
+            Event::Keyboard(KeyboardEvent::KeyPressed { .. })
+            | Event::Mouse(
+                MouseEvent::CursorMoved { .. }
+                | MouseEvent::ButtonPressed(_)
+                | MouseEvent::WheelScrolled { .. },
+            ) => Some(TcGuiMessage::UserActivity),
+            _ => None,
+        }
     }
 }
 
@@ -915,17 +2210,42 @@ impl TcGui {
                 CTRL_PRESSED.store(modifiers.control(), std::sync::atomic::Ordering::Relaxed);
                 None
             }
+            Event::Mouse(MouseEvent::CursorMoved { position }) => {
+                if let Ok(mut cursor) = CURSOR_POSITION.lock() {
+                    *cursor = position;
+                }
+                None
+            }
             _ => None,
         }
     }
 
-    /// Handles keyboard shortcuts for zoom and tab switching.
+    /// Handles keyboard shortcuts for zoom and tab switching, plus plain-key
+    /// navigation:
+    /// - Up/Down : Move focus between interface cards
+    /// - Enter : Expand/collapse the focused card's chart
+    /// - Escape : Close the quick actions menu, or drop the interface focus
+    /// - Tab / Shift+Tab : Move between input fields
     fn handle_keyboard_shortcut(key: Key, modifiers: Modifiers) -> Option<TcGuiMessage> {
+        use iced::keyboard::key::Named;
+
         if !modifiers.control() {
-            return None;
+            if modifiers.alt() || modifiers.logo() {
+                return None;
+            }
+            return match key {
+                Key::Named(Named::ArrowDown) => Some(TcGuiMessage::MoveInterfaceFocus(1)),
+                Key::Named(Named::ArrowUp) => Some(TcGuiMessage::MoveInterfaceFocus(-1)),
+                Key::Named(Named::Enter) => Some(TcGuiMessage::ActivateFocusedInterface),
+                Key::Named(Named::Escape) => Some(TcGuiMessage::ClearInterfaceFocus),
+                Key::Named(Named::Tab) if modifiers.shift() => {
+                    Some(TcGuiMessage::FocusPreviousControl)
+                }
+                Key::Named(Named::Tab) => Some(TcGuiMessage::FocusNextControl),
+                _ => None,
+            };
         }
 
-        use crate::ui_state::AppTab;
         match key {
             Key::Character(c) => {
                 let c_str = c.as_str();
@@ -934,6 +2254,8 @@ impl TcGui {
                     "-" | "_" | ")" => Some(TcGuiMessage::ZoomOut),
                     "0" => Some(TcGuiMessage::ZoomReset),
                     // Tab switching: Ctrl+1 = Interfaces, Ctrl+2 = Scenarios.
+                    // Ctrl+Shift+I: show/hide the Zenoh inspector (--inspector only)
+                    "i" | "I" if modifiers.shift() => Some(TcGuiMessage::ToggleInspector),
                     "1" => Some(TcGuiMessage::SwitchTab(AppTab::Interfaces)),
                     "2" => Some(TcGuiMessage::SwitchTab(AppTab::Scenarios)),
                     _ => None,
@@ -975,6 +2297,9 @@ impl TcGui {
 /// Global state to track if Ctrl key is pressed (for mouse scroll zoom).
 static CTRL_PRESSED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
 
+/// Last known cursor position (for placing the quick actions menu).
+static CURSOR_POSITION: std::sync::Mutex<iced::Point> = std::sync::Mutex::new(iced::Point::ORIGIN);
+
 impl Default for TcGui {
     fn default() -> Self {
         let (gui, _) = Self::new();
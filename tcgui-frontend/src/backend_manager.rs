@@ -4,13 +4,17 @@
 //! It provides a centralized way to manage multiple backend connections,
 //! track their health, and route messages appropriately.
 
+use crate::clock_skew::{ClockSkew, unix_now_ms};
+use crate::diagnostics_history::DiagnosticsHistoryManager;
 use crate::interface::TcInterface;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use tcgui_shared::{
-    BackendHealthStatus, NamespaceType, NetworkInterface, NetworkNamespace,
+    BackendHealthStatus, NamespaceDiagnosticsResponse, NamespaceType, NetemCapabilities,
+    NetworkInterface, NetworkNamespace, find_veth_peer,
     presets::{CustomPreset, PresetList},
+    veth_asymmetry,
 };
-use tracing::info;
+use tracing::{info, warn};
 
 /// Current unix time in seconds (best-effort, saturating on clock errors).
 fn now_secs() -> u64 {
@@ -55,6 +59,17 @@ pub struct BackendGroup {
     pub namespaces: HashMap<String, NamespaceGroup>,
     /// Available presets (built-in and custom) from this backend
     pub preset_list: PresetList,
+    /// Most recent health document, shown in the backend details panel
+    pub health: Option<BackendHealthStatus>,
+    /// Estimated offset of this backend's clock from ours
+    pub clock_skew: ClockSkew,
+    /// Netem features the backend supports, once it has answered
+    pub netem_capabilities: Option<NetemCapabilities>,
+    /// A forced refresh was requested and has not been answered yet
+    pub refresh_pending: bool,
+    /// Past diagnostics runs of this backend's interfaces, kept even for
+    /// interfaces that have since disappeared
+    pub diagnostics_history: DiagnosticsHistoryManager,
 }
 
 impl BackendGroup {
@@ -67,6 +82,11 @@ impl BackendGroup {
             disconnected_at: None,
             namespaces: HashMap::new(),
             preset_list: PresetList::default(),
+            health: None,
+            clock_skew: ClockSkew::default(),
+            netem_capabilities: None,
+            refresh_pending: false,
+            diagnostics_history: DiagnosticsHistoryManager::default(),
         }
     }
 }
@@ -79,12 +99,52 @@ pub struct NamespaceGroup {
     pub namespace: NetworkNamespace,
     /// Map of interface name to TcInterface component for this namespace
     pub tc_interfaces: HashMap<String, TcInterface>,
+    /// Namespace-wide diagnostics run, shown as a summary table
+    pub diagnostics: NamespaceDiagnostics,
+}
+
+/// State of a namespace-wide diagnostics run.
+#[derive(Clone, Debug, Default)]
+pub enum NamespaceDiagnostics {
+    /// No run requested (or the last result was dismissed)
+    #[default]
+    Idle,
+    /// Waiting for the backend's combined report
+    Running,
+    /// Combined per-interface report
+    Done(NamespaceDiagnosticsResponse),
+    /// The query itself failed (no per-interface results)
+    Failed(String),
+}
+
+impl NamespaceDiagnostics {
+    /// Whether a run is in progress
+    pub fn is_running(&self) -> bool {
+        matches!(self, Self::Running)
+    }
+}
+
+/// A state-plane interface change waiting for
+/// [`BackendManager::flush_interface_changes`].
+#[derive(Clone, Debug)]
+enum PendingInterfaceChange {
+    /// Put on `state/tc/interface/{ns}/{if}`
+    Upsert(NetworkInterface),
+    /// Delete tombstone on the same key
+    Removed,
 }
 
 /// Manager for backend operations and state.
 pub struct BackendManager {
     /// Backend instances keyed by **host origin** (`h-<12hex>`) for routing.
     backends: HashMap<String, BackendGroup>,
+    /// User-assigned display labels keyed by host origin. Kept apart from
+    /// `backends` so a label survives its backend disconnecting, being
+    /// cleaned up and reconnecting.
+    labels: BTreeMap<String, String>,
+    /// Interface changes received since the last flush, keyed by (origin,
+    /// namespace, interface)
+    pending_interfaces: BTreeMap<(String, String, String), PendingInterfaceChange>,
 }
 
 impl BackendManager {
@@ -92,9 +152,50 @@ impl BackendManager {
     pub fn new() -> Self {
         Self {
             backends: HashMap::new(),
+            labels: BTreeMap::new(),
+            pending_interfaces: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a backend manager with previously persisted display labels.
+    pub fn with_labels(labels: BTreeMap<String, String>) -> Self {
+        Self {
+            labels,
+            ..Self::new()
+        }
+    }
+
+    /// User-assigned display labels, keyed by host origin.
+    pub fn backend_labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    /// User-assigned display label for a backend, if any.
+    pub fn backend_label(&self, origin: &str) -> Option<&str> {
+        self.labels.get(origin).map(String::as_str)
+    }
+
+    /// Assigns a display label to a backend; a blank label removes it.
+    pub fn set_backend_label(&mut self, origin: &str, label: &str) {
+        let label = label.trim();
+        if label.is_empty() {
+            self.labels.remove(origin);
+        } else {
+            self.labels.insert(origin.to_string(), label.to_string());
         }
     }
 
+    /// Name to show for a backend: the user's label, else the operator-chosen
+    /// name from its health document, else the origin itself. Display only —
+    /// routing always uses the origin.
+    pub fn display_name(&self, origin: &str) -> String {
+        self.labels
+            .get(origin)
+            .or_else(|| self.backends.get(origin).map(|group| &group.name))
+            .cloned()
+            .unwrap_or_else(|| origin.to_string())
+    }
+
     /// Gets a reference to the backends map.
     pub fn backends(&self) -> &HashMap<String, BackendGroup> {
         &self.backends
@@ -120,14 +221,109 @@ impl BackendManager {
     /// [`Self::handle_interface_removed`]). The payload's own `namespace` field
     /// selects the namespace group.
     pub fn handle_interface_upsert(&mut self, origin: &str, interface: NetworkInterface) {
+        let backend_group = self.get_or_create(origin);
+        Self::upsert_interface(backend_group, origin, interface);
+        Self::refresh_veth_peers(backend_group);
+    }
+
+    /// Removes a single interface in response to a Delete tombstone on
+    /// `state/tc/interface/{ns}/{if}`. `namespace`/`interface` come from the key
+    /// (the Delete carries no payload).
+    pub fn handle_interface_removed(&mut self, origin: &str, namespace: &str, interface: &str) {
+        if let Some(backend_group) = self.backends.get_mut(origin)
+            && Self::remove_interface(backend_group, origin, namespace, interface)
+        {
+            Self::refresh_veth_peers(backend_group);
+        }
+    }
+
+    /// Queues an interface upsert until the next
+    /// [`Self::flush_interface_changes`], replacing any change to the same
+    /// interface queued before it.
+    pub fn queue_interface_upsert(&mut self, origin: &str, interface: NetworkInterface) {
+        let key = (
+            origin.to_string(),
+            interface.namespace.clone(),
+            interface.name.clone(),
+        );
+        self.pending_interfaces
+            .insert(key, PendingInterfaceChange::Upsert(interface));
+    }
+
+    /// Queues an interface removal until the next
+    /// [`Self::flush_interface_changes`], replacing any change to the same
+    /// interface queued before it.
+    pub fn queue_interface_removed(&mut self, origin: &str, namespace: &str, interface: &str) {
+        let key = (
+            origin.to_string(),
+            namespace.to_string(),
+            interface.to_string(),
+        );
+        self.pending_interfaces
+            .insert(key, PendingInterfaceChange::Removed);
+    }
+
+    /// Whether interface changes are waiting for a flush.
+    pub fn has_pending_interface_changes(&self) -> bool {
+        !self.pending_interfaces.is_empty()
+    }
+
+    /// Applies the queued interface changes, the last one per interface, and
+    /// rebuilds each affected backend's interface tree once. Returns the
+    /// number of backends rebuilt.
+    ///
+    /// An interface removed and re-added within one window (a container
+    /// restarting) is only upserted, so its controls keep their state.
+    /// Interfaces without a queued change are left untouched.
+    pub fn flush_interface_changes(&mut self) -> usize {
+        let pending = std::mem::take(&mut self.pending_interfaces);
+        let mut changed: Vec<String> = Vec::new();
+
+        for ((origin, namespace, interface), change) in pending {
+            let applied = match change {
+                PendingInterfaceChange::Upsert(record) => {
+                    Self::upsert_interface(self.get_or_create(&origin), &origin, record);
+                    true
+                }
+                PendingInterfaceChange::Removed => {
+                    self.backends.get_mut(&origin).is_some_and(|backend_group| {
+                        Self::remove_interface(backend_group, &origin, &namespace, &interface)
+                    })
+                }
+            };
+            if applied && !changed.contains(&origin) {
+                changed.push(origin);
+            }
+        }
+
+        for origin in &changed {
+            if let Some(backend_group) = self.backends.get_mut(origin) {
+                Self::refresh_veth_peers(backend_group);
+            }
+        }
+        changed.len()
+    }
+
+    /// Creates or updates one interface of a backend, without re-checking
+    /// veth pairs.
+    fn upsert_interface(
+        backend_group: &mut BackendGroup,
+        origin: &str,
+        interface: NetworkInterface,
+    ) {
         let namespace = interface.namespace.clone();
         let iface_name = interface.name.clone();
 
-        let backend_group = self.get_or_create(origin);
         backend_group.is_connected = true;
         backend_group.last_seen = now_secs();
         backend_group.disconnected_at = None;
 
+        let unsupported = backend_group
+            .netem_capabilities
+            .as_ref()
+            .map(NetemCapabilities::unsupported_features)
+            .unwrap_or_default();
+
         let namespace_group = backend_group
             .namespaces
             .entry(namespace.clone())
@@ -140,6 +336,7 @@ impl BackendManager {
                     interfaces: Vec::new(),
                 },
                 tc_interfaces: HashMap::new(),
+                diagnostics: NamespaceDiagnostics::default(),
             });
 
         // Keep the namespace's interface record list in sync (message handlers
@@ -160,6 +357,7 @@ impl BackendManager {
             .entry(iface_name.clone())
             .or_insert_with(|| TcInterface::new(&iface_name));
         tc_interface.update_from_backend(&interface);
+        tc_interface.set_unsupported_features(unsupported);
 
         info!(
             "Upserted interface '{}' in namespace '{}' of backend '{}'",
@@ -167,27 +365,102 @@ impl BackendManager {
         );
     }
 
-    /// Removes a single interface in response to a Delete tombstone on
-    /// `state/tc/interface/{ns}/{if}`. `namespace`/`interface` come from the key
-    /// (the Delete carries no payload).
-    pub fn handle_interface_removed(&mut self, origin: &str, namespace: &str, interface: &str) {
-        if let Some(backend_group) = self.backends.get_mut(origin)
-            && let Some(namespace_group) = backend_group.namespaces.get_mut(namespace)
-        {
-            namespace_group.tc_interfaces.remove(interface);
-            namespace_group
-                .namespace
-                .interfaces
-                .retain(|i| i.name != interface);
+    /// Removes one interface of a backend, without re-checking veth pairs.
+    /// Returns whether its namespace was known.
+    fn remove_interface(
+        backend_group: &mut BackendGroup,
+        origin: &str,
+        namespace: &str,
+        interface: &str,
+    ) -> bool {
+        let Some(namespace_group) = backend_group.namespaces.get_mut(namespace) else {
+            return false;
+        };
+        namespace_group.tc_interfaces.remove(interface);
+        namespace_group
+            .namespace
+            .interfaces
+            .retain(|i| i.name != interface);
+        info!(
+            "Removed interface '{}' from namespace '{}' of backend '{}' (tombstone)",
+            interface, namespace, origin
+        );
+        // Drop the namespace group once it has no interfaces left.
+        if namespace_group.tc_interfaces.is_empty() {
+            backend_group.namespaces.remove(namespace);
+        }
+        true
+    }
+
+    /// Re-check both ends of every veth pair of a backend after one end's
+    /// TC configuration changed.
+    pub fn refresh_veth_pairs(&mut self, origin: &str) {
+        if let Some(backend_group) = self.backends.get_mut(origin) {
+            Self::refresh_veth_peers(backend_group);
+        }
+    }
+
+    /// Re-resolve the veth peer of every interface of a backend and compare
+    /// the two ends. Either end may show up (or go away) first, so this runs
+    /// on every change.
+    fn refresh_veth_peers(backend_group: &mut BackendGroup) {
+        let records: Vec<NetworkInterface> = backend_group
+            .namespaces
+            .values()
+            .flat_map(|group| group.namespace.interfaces.iter().cloned())
+            .collect();
+        let applied_config = |backend_group: &BackendGroup, record: &NetworkInterface| {
+            backend_group
+                .namespaces
+                .get(&record.namespace)
+                .and_then(|group| group.tc_interfaces.get(&record.name))
+                .and_then(TcInterface::applied_configuration)
+        };
+
+        for record in &records {
+            let peer = find_veth_peer(&records, record);
+            let asymmetry = match peer {
+                Some(peer) => veth_asymmetry(
+                    record,
+                    applied_config(backend_group, record).as_ref(),
+                    peer,
+                    applied_config(backend_group, peer).as_ref(),
+                ),
+                None => Vec::new(),
+            };
+            let peer = peer.map(|peer| (peer.namespace.clone(), peer.name.clone()));
+            if let Some(tc_interface) = backend_group
+                .namespaces
+                .get_mut(&record.namespace)
+                .and_then(|group| group.tc_interfaces.get_mut(&record.name))
+            {
+                tc_interface.set_veth_peer(peer);
+                tc_interface.set_veth_asymmetry(asymmetry);
+            }
+        }
+    }
+
+    /// Record the netem capabilities a backend reported and gray out the
+    /// features it can't apply on all of its interfaces.
+    pub fn set_netem_capabilities(&mut self, origin: &str, capabilities: NetemCapabilities) {
+        let unsupported = capabilities.unsupported_features();
+        if !unsupported.is_empty() {
             info!(
-                "Removed interface '{}' from namespace '{}' of backend '{}' (tombstone)",
-                interface, namespace, origin
+                "Backend '{}' can't apply: {:?}",
+                origin,
+                unsupported.iter().map(|(f, _)| f).collect::<Vec<_>>()
             );
-            // Drop the namespace group once it has no interfaces left.
-            if namespace_group.tc_interfaces.is_empty() {
-                backend_group.namespaces.remove(namespace);
-            }
         }
+
+        let backend_group = self.get_or_create(origin);
+        for tc_interface in backend_group
+            .namespaces
+            .values_mut()
+            .flat_map(|group| group.tc_interfaces.values_mut())
+        {
+            tc_interface.set_unsupported_features(unsupported.clone());
+        }
+        backend_group.netem_capabilities = Some(capabilities);
     }
 
     /// Handles backend health status updates. `origin` is the key-derived host
@@ -206,6 +479,33 @@ impl BackendManager {
             "Backend '{}' (name '{}') health status: {}",
             origin, health_status.backend_name, health_status.status
         );
+        let timestamp = health_status.timestamp;
+        backend_group.health = Some(health_status);
+        self.observe_backend_timestamp(origin, timestamp);
+    }
+
+    /// Feeds a backend message timestamp (Unix seconds) into that backend's
+    /// clock skew estimate, warning when the skew first becomes excessive.
+    pub fn observe_backend_timestamp(&mut self, origin: &str, timestamp: u64) {
+        if let Some(backend_group) = self.backends.get_mut(origin)
+            && backend_group.clock_skew.observe(timestamp, unix_now_ms())
+        {
+            warn!(
+                "Backend '{}' clock is skewed by {} relative to this machine; \
+                 chart annotations are being compensated",
+                origin,
+                backend_group.clock_skew.describe()
+            );
+        }
+    }
+
+    /// Estimated clock skew for a backend; unknown backends get a fresh
+    /// estimator (no compensation).
+    pub fn clock_skew(&self, origin: &str) -> ClockSkew {
+        self.backends
+            .get(origin)
+            .map(|group| group.clock_skew.clone())
+            .unwrap_or_default()
     }
 
     /// Handles backend liveliness changes.
@@ -356,7 +656,8 @@ impl Default for BackendManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tcgui_shared::{BackendMetadata, InterfaceType, TcNetemConfig};
+    use crate::messages::TcInterfaceMessage;
+    use tcgui_shared::{BackendMetadata, InterfaceType, TcConfiguration, TcNetemConfig};
 
     // Origins are opaque `h-<12hex>` strings on the wire; the manager only ever
     // uses them as map keys, so the tests use recognizable stand-ins.
@@ -375,6 +676,10 @@ mod tests {
             addresses: Vec::new(),
             qdisc_kind: None,
             link_speed_mbps: None,
+            mtu: None,
+            peer_index: None,
+            management: false,
+            description: None,
         }
     }
 
@@ -457,6 +762,75 @@ mod tests {
         assert!(!backends[ORIGIN1].namespaces.contains_key("ns2"));
     }
 
+    #[test]
+    fn test_interface_burst_is_applied_in_one_rebuild() {
+        let mut manager = BackendManager::new();
+
+        for round in 0..20 {
+            for iface in ["veth0", "veth1", "veth2"] {
+                let mut record = create_test_interface(iface, "ns1");
+                record.mtu = Some(1400 + round);
+                manager.queue_interface_upsert(ORIGIN1, record);
+            }
+            manager.queue_interface_removed(ORIGIN1, "ns1", "veth2");
+        }
+        // Nothing is applied until the flush
+        assert!(manager.has_pending_interface_changes());
+        assert_eq!(manager.total_interface_count(), 0);
+
+        assert_eq!(manager.flush_interface_changes(), 1);
+        assert!(!manager.has_pending_interface_changes());
+        let ns = &manager.backends()[ORIGIN1].namespaces["ns1"];
+        assert_eq!(ns.tc_interfaces.len(), 2);
+        assert!(ns.namespace.interfaces.iter().all(|i| i.mtu == Some(1419)));
+
+        // A flush with nothing queued rebuilds nothing
+        assert_eq!(manager.flush_interface_changes(), 0);
+    }
+
+    #[test]
+    fn test_interface_churn_keeps_in_progress_edits() {
+        let mut manager = BackendManager::new();
+        upsert_ns(&mut manager, ORIGIN1, "default", &["eth0", "veth0"]);
+        let edit = |manager: &mut BackendManager, iface: &str| {
+            let tc_interface = manager
+                .backends_mut()
+                .get_mut(ORIGIN1)
+                .unwrap()
+                .namespaces
+                .get_mut("default")
+                .unwrap()
+                .tc_interfaces
+                .get_mut(iface)
+                .unwrap();
+            let _ = tc_interface.update(TcInterfaceMessage::LossToggled(true));
+            let _ = tc_interface.update(TcInterfaceMessage::LossChanged(7.0));
+        };
+        let loss = |manager: &BackendManager, iface: &str| {
+            manager.backends()[ORIGIN1].namespaces["default"].tc_interfaces[iface]
+                .current_configuration()
+                .loss
+        };
+        edit(&mut manager, "eth0");
+        edit(&mut manager, "veth0");
+
+        // An unrelated interface coming and going leaves eth0 alone, and
+        // veth0 removed and re-added within the window keeps its edit
+        manager.queue_interface_upsert(ORIGIN1, create_test_interface("veth1", "default"));
+        manager.queue_interface_removed(ORIGIN1, "default", "veth1");
+        manager.queue_interface_removed(ORIGIN1, "default", "veth0");
+        manager.queue_interface_upsert(ORIGIN1, create_test_interface("veth0", "default"));
+        manager.flush_interface_changes();
+
+        assert_eq!(loss(&manager, "eth0"), 7.0);
+        assert_eq!(loss(&manager, "veth0"), 7.0);
+        assert!(
+            !manager.backends()[ORIGIN1].namespaces["default"]
+                .tc_interfaces
+                .contains_key("veth1")
+        );
+    }
+
     #[test]
     fn test_backend_liveliness() {
         let mut manager = BackendManager::new();
@@ -527,12 +901,18 @@ mod tests {
             metadata: BackendMetadata::default(),
             namespace_count: 1,
             interface_count: 1,
+            failed_namespaces: Vec::new(),
+            connected_peers: 2,
+            peer_ids: Vec::new(),
         };
         manager.handle_backend_health_update(ORIGIN1, health);
 
         // Display name comes from the health doc; routing key stays the origin.
         assert_eq!(manager.backends()[ORIGIN1].name, "lab-router");
         assert!(manager.backends()[ORIGIN1].last_seen >= initial_last_seen);
+        // The document itself is kept for the backend details panel.
+        let stored = manager.backends()[ORIGIN1].health.as_ref().unwrap();
+        assert_eq!(stored.connected_peers, 2);
     }
 
     #[test]
@@ -578,6 +958,126 @@ mod tests {
         assert_eq!(manager.total_interface_count(), 1);
     }
 
+    #[test]
+    fn test_veth_peer_resolved_in_either_order() {
+        let mut manager = BackendManager::new();
+        let mut host = create_test_interface("veth-a", "default");
+        host.index = 7;
+        host.peer_index = Some(2);
+        let mut inside = create_test_interface("eth0", "ns1");
+        inside.index = 2;
+        inside.peer_index = Some(7);
+
+        let peer_of = |manager: &BackendManager, ns: &str, iface: &str| {
+            manager.backends()[ORIGIN1].namespaces[ns].tc_interfaces[iface]
+                .veth_peer()
+                .map(|(ns, name)| format!("{ns}/{name}"))
+        };
+
+        manager.handle_interface_upsert(ORIGIN1, host);
+        assert_eq!(peer_of(&manager, "default", "veth-a"), None);
+
+        manager.handle_interface_upsert(ORIGIN1, inside);
+        assert_eq!(
+            peer_of(&manager, "default", "veth-a").as_deref(),
+            Some("ns1/eth0")
+        );
+        assert_eq!(
+            peer_of(&manager, "ns1", "eth0").as_deref(),
+            Some("default/veth-a")
+        );
+
+        manager.handle_interface_removed(ORIGIN1, "ns1", "eth0");
+        assert_eq!(peer_of(&manager, "default", "veth-a"), None);
+    }
+
+    #[test]
+    fn test_veth_asymmetry_flagged_on_both_ends() {
+        let mut manager = BackendManager::new();
+        let mut host = create_test_interface("veth-a", "default");
+        host.index = 7;
+        host.peer_index = Some(2);
+        let mut inside = create_test_interface("eth0", "ns1");
+        inside.index = 2;
+        inside.peer_index = Some(7);
+        manager.handle_interface_upsert(ORIGIN1, host);
+        manager.handle_interface_upsert(ORIGIN1, inside);
+
+        let set_rate = |manager: &mut BackendManager, ns: &str, iface: &str, kbps| {
+            manager
+                .backends_mut()
+                .get_mut(ORIGIN1)
+                .unwrap()
+                .namespaces
+                .get_mut(ns)
+                .unwrap()
+                .tc_interfaces
+                .get_mut(iface)
+                .unwrap()
+                .check_backend_sync(Some(TcConfiguration {
+                    rate_limit_kbps: Some(kbps),
+                    ..Default::default()
+                }));
+            manager.refresh_veth_pairs(ORIGIN1);
+        };
+        let warnings = |manager: &BackendManager, ns: &str, iface: &str| {
+            manager.backends()[ORIGIN1].namespaces[ns].tc_interfaces[iface]
+                .veth_asymmetry()
+                .to_vec()
+        };
+
+        set_rate(&mut manager, "default", "veth-a", 1000);
+        set_rate(&mut manager, "ns1", "eth0", 2000);
+        assert!(warnings(&manager, "default", "veth-a").is_empty());
+        assert!(warnings(&manager, "ns1", "eth0").is_empty());
+
+        set_rate(&mut manager, "ns1", "eth0", 50_000);
+        assert_eq!(
+            warnings(&manager, "default", "veth-a"),
+            vec!["peer has 50x higher rate (50000 kbps vs 1000 kbps here)"]
+        );
+        assert_eq!(
+            warnings(&manager, "ns1", "eth0"),
+            vec!["peer has 50x lower rate (1000 kbps vs 50000 kbps here)"]
+        );
+    }
+
+    #[test]
+    fn test_netem_capabilities_reach_new_and_existing_interfaces() {
+        use tcgui_shared::{NetemCapability, NetemFeatureSupport, TcFeature};
+
+        let mut manager = BackendManager::new();
+        manager.handle_interface_upsert(ORIGIN1, create_test_interface("eth0", "default"));
+
+        let capabilities = NetemCapabilities {
+            kernel_release: Some("3.2.0".to_string()),
+            tc_version: None,
+            features: NetemCapability::ALL
+                .into_iter()
+                .map(|capability| {
+                    let supported = capability != NetemCapability::Rate;
+                    NetemFeatureSupport {
+                        capability,
+                        supported,
+                        reason: (!supported).then(|| "requires kernel 3.3 or newer".to_string()),
+                    }
+                })
+                .collect(),
+        };
+        manager.set_netem_capabilities(ORIGIN1, capabilities);
+        manager.handle_interface_upsert(ORIGIN1, create_test_interface("eth1", "default"));
+
+        for iface in ["eth0", "eth1"] {
+            let tc_interface =
+                &manager.backends()[ORIGIN1].namespaces["default"].tc_interfaces[iface];
+            assert_eq!(
+                tc_interface.unsupported_reason(TcFeature::RateLimit),
+                Some("requires kernel 3.3 or newer")
+            );
+            assert_eq!(tc_interface.unsupported_reason(TcFeature::Delay), None);
+        }
+    }
+
     #[test]
     fn test_preset_upsert_and_remove() {
         let mut manager = BackendManager::new();
@@ -599,4 +1099,27 @@ mod tests {
         manager.remove_preset(ORIGIN1, "sat");
         assert_eq!(manager.get_preset_list(ORIGIN1).map(|p| p.len()), Some(0));
     }
+
+    #[test]
+    fn test_backend_label_survives_reconnect() {
+        let mut manager = BackendManager::new();
+        upsert_ns(&mut manager, ORIGIN1, "default", &["eth0"]);
+        assert_eq!(manager.display_name(ORIGIN1), ORIGIN1);
+
+        manager.set_backend_label(ORIGIN1, "  Lab Gateway ");
+        assert_eq!(manager.display_name(ORIGIN1), "Lab Gateway");
+        assert_eq!(manager.display_name(ORIGIN2), ORIGIN2);
+
+        // The group disappears and comes back under the same origin.
+        manager.backends_mut().remove(ORIGIN1);
+        upsert_ns(&mut manager, ORIGIN1, "default", &["eth0"]);
+        assert_eq!(manager.display_name(ORIGIN1), "Lab Gateway");
+        assert!(manager.backends().contains_key(ORIGIN1));
+
+        // A blank label falls back to the health-document name.
+        manager.set_backend_label(ORIGIN1, "");
+        assert_eq!(manager.backend_label(ORIGIN1), None);
+        manager.backends_mut().get_mut(ORIGIN1).unwrap().name = "edge-node-01".to_string();
+        assert_eq!(manager.display_name(ORIGIN1), "edge-node-01");
+    }
 }
@@ -5,10 +5,16 @@
 
 use std::time::{Duration, Instant};
 
-use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, Text};
+use iced::widget::canvas::{self, Event, Frame, Geometry, Path, Stroke, Text};
 use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size, Theme, mouse};
 
-use crate::bandwidth_history::BandwidthHistory;
+use crate::bandwidth_history::{BandwidthHistory, TcChangeKind, TcChangeMarker};
+
+/// Maximum number of TC change markers drawn at once.
+const MAX_VISIBLE_MARKERS: usize = 8;
+
+/// Horizontal distance (in pixels) within which the cursor hovers a marker.
+const MARKER_HOVER_DISTANCE: f32 = 4.0;
 
 /// Time window options for the chart display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -45,6 +51,9 @@ pub struct ChartColors {
     pub axis: Color,
     pub text: Color,
     pub background: Color,
+    pub marker_applied: Color,
+    pub marker_removed: Color,
+    pub marker_scenario: Color,
 }
 
 impl Default for ChartColors {
@@ -56,6 +65,9 @@ impl Default for ChartColors {
             axis: Color::from_rgb(0.4, 0.4, 0.4),
             text: Color::from_rgb(0.5, 0.5, 0.5),
             background: Color::from_rgba(0.95, 0.95, 0.95, 0.5),
+            marker_applied: Color::from_rgb(0.2, 0.65, 0.3), // Green for TC applied
+            marker_removed: Color::from_rgb(0.85, 0.25, 0.25), // Red for TC removed
+            marker_scenario: Color::from_rgb(0.55, 0.35, 0.8), // Purple for scenario steps
         }
     }
 }
@@ -70,6 +82,19 @@ impl ChartColors {
             axis: Color::from_rgb(0.7, 0.7, 0.7),
             text: Color::from_rgb(0.7, 0.7, 0.7),
             background: Color::from_rgba(0.15, 0.15, 0.15, 0.5),
+            marker_applied: Color::from_rgb(0.4, 0.85, 0.5),
+            marker_removed: Color::from_rgb(1.0, 0.45, 0.45),
+            marker_scenario: Color::from_rgb(0.7, 0.55, 1.0),
+        }
+    }
+
+    /// Draw both lines in an interface's own color: RX solid, TX as a
+    /// translucent shade of it.
+    pub fn with_accent(self, accent: Color) -> Self {
+        Self {
+            rx: accent,
+            tx: Color { a: 0.5, ..accent },
+            ..self
         }
     }
 }
@@ -146,17 +171,23 @@ where
 /// Create a bandwidth chart element without needing persistent state.
 ///
 /// This is a convenience function for rendering a chart inline without
-/// storing a BandwidthChart instance.
+/// storing a BandwidthChart instance. `accent` overrides the line colors
+/// with the interface's assigned color.
 pub fn bandwidth_chart_view<'a, Message: 'a>(
     history: Option<&'a BandwidthHistory>,
     height: f32,
     dark_mode: bool,
+    accent: Option<Color>,
 ) -> Element<'a, Message, Theme, Renderer> {
     let colors = if dark_mode {
         ChartColors::dark()
     } else {
         ChartColors::default()
     };
+    let colors = match accent {
+        Some(accent) => colors.with_accent(accent),
+        None => colors,
+    };
 
     canvas(StatelessBandwidthChart {
         history,
@@ -179,7 +210,18 @@ struct StatelessBandwidthChart<'a> {
 }
 
 impl<Message> canvas::Program<Message, Theme, Renderer> for StatelessBandwidthChart<'_> {
-    type State = ();
+    /// Whether the cursor was over the chart on the last event.
+    type State = bool;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        hover_redraw(state, event, bounds, cursor)
+    }
 
     fn draw(
         &self,
@@ -187,11 +229,21 @@ impl<Message> canvas::Program<Message, Theme, Renderer> for StatelessBandwidthCh
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
         // Draw fresh each frame for real-time updates (no caching)
         let mut frame = Frame::new(renderer, bounds.size());
         self.draw_chart(&mut frame, bounds.size());
+        if let (Some(history), Some(position)) = (self.history, cursor.position_in(bounds)) {
+            draw_marker_hover(
+                &mut frame,
+                history,
+                self.time_window.duration(),
+                bounds.size(),
+                position,
+                &self.colors,
+            );
+        }
         vec![frame.into_geometry()]
     }
 }
@@ -199,12 +251,7 @@ impl<Message> canvas::Program<Message, Theme, Renderer> for StatelessBandwidthCh
 impl StatelessBandwidthChart<'_> {
     /// Draw the complete chart.
     fn draw_chart(&self, frame: &mut Frame, size: Size) {
-        let padding = ChartPadding {
-            left: 45.0,
-            right: 10.0,
-            top: 10.0,
-            bottom: 20.0,
-        };
+        let padding = ChartPadding::default();
 
         let chart_width = size.width - padding.left - padding.right;
         let chart_height = size.height - padding.top - padding.bottom;
@@ -274,6 +321,20 @@ impl StatelessBandwidthChart<'_> {
             self.colors.tx,
         );
 
+        // Draw TC change markers
+        if let Some(history) = self.history {
+            draw_tc_markers(
+                frame,
+                &history.markers_in_window(window_duration, MAX_VISIBLE_MARKERS),
+                now,
+                window_duration,
+                &padding,
+                chart_width,
+                chart_height,
+                &self.colors,
+            );
+        }
+
         // Draw axes
         self.draw_axes(frame, &padding, chart_width, chart_height);
 
@@ -457,6 +518,160 @@ impl StatelessBandwidthChart<'_> {
     }
 }
 
+/// Create a chart of the transmit throughput as a percentage of the rate
+/// limit, on a fixed 0-100% scale. See
+/// [`BandwidthHistory::efficiency_in_window`].
+pub fn efficiency_chart_view<'a, Message: 'a>(
+    history: Option<&'a BandwidthHistory>,
+    rate_limit_kbps: u32,
+    height: f32,
+    dark_mode: bool,
+    accent: Option<Color>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let colors = if dark_mode {
+        ChartColors::dark()
+    } else {
+        ChartColors::default()
+    };
+    let colors = match accent {
+        Some(accent) => colors.with_accent(accent),
+        None => colors,
+    };
+
+    canvas(EfficiencyChart {
+        history,
+        rate_limit_kbps,
+        time_window: ChartTimeWindow::default(),
+        colors,
+    })
+    .width(Length::Fill)
+    .height(Length::Fixed(height))
+    .into()
+}
+
+/// Stateless canvas program for the rate-limit efficiency chart.
+struct EfficiencyChart<'a> {
+    history: Option<&'a BandwidthHistory>,
+    rate_limit_kbps: u32,
+    time_window: ChartTimeWindow,
+    colors: ChartColors,
+}
+
+impl<Message> canvas::Program<Message, Theme, Renderer> for EfficiencyChart<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        self.draw_chart(&mut frame, bounds.size());
+        vec![frame.into_geometry()]
+    }
+}
+
+impl EfficiencyChart<'_> {
+    /// Draw the complete chart.
+    fn draw_chart(&self, frame: &mut Frame, size: Size) {
+        let padding = ChartPadding::default();
+        let chart_width = size.width - padding.left - padding.right;
+        let chart_height = size.height - padding.top - padding.bottom;
+        if chart_width <= 0.0 || chart_height <= 0.0 {
+            return;
+        }
+
+        frame.fill_rectangle(
+            Point::new(padding.left, padding.top),
+            Size::new(chart_width, chart_height),
+            self.colors.background,
+        );
+
+        let window_duration = self.time_window.duration();
+        let points = self
+            .history
+            .map(|h| h.efficiency_in_window(window_duration, self.rate_limit_kbps))
+            .unwrap_or_default();
+
+        // 0%, 50% and 100% grid lines
+        for i in 0..=2 {
+            let y = padding.top + chart_height * (i as f32 / 2.0);
+            frame.stroke(
+                &Path::line(
+                    Point::new(padding.left, y),
+                    Point::new(padding.left + chart_width, y),
+                ),
+                Stroke::default()
+                    .with_width(1.0)
+                    .with_color(self.colors.grid),
+            );
+            frame.fill_text(Text {
+                content: format!("{}%", 100 - i * 50),
+                position: Point::new(padding.left - 5.0, y),
+                color: self.colors.text,
+                size: 10.0.into(),
+                align_x: iced::alignment::Horizontal::Right.into(),
+                align_y: iced::alignment::Vertical::Center,
+                ..Default::default()
+            });
+        }
+
+        let current = points.last().map(|(_, percent)| *percent);
+        if points.len() < 2 {
+            frame.fill_text(Text {
+                content: "Collecting data...".to_string(),
+                position: Point::new(size.width / 2.0, size.height / 2.0),
+                color: self.colors.text,
+                size: 12.0.into(),
+                align_x: iced::alignment::Horizontal::Center.into(),
+                align_y: iced::alignment::Vertical::Center,
+                ..Default::default()
+            });
+        } else {
+            let now = Instant::now();
+            let path = Path::new(|builder| {
+                for (i, (timestamp, percent)) in points.iter().enumerate() {
+                    let x = time_to_x(*timestamp, now, window_duration, &padding, chart_width)
+                        .unwrap_or(padding.left);
+                    let y = padding.top + chart_height * (1.0 - *percent as f32 / 100.0);
+                    if i == 0 {
+                        builder.move_to(Point::new(x, y));
+                    } else {
+                        builder.line_to(Point::new(x, y));
+                    }
+                }
+            });
+            frame.stroke(
+                &path,
+                Stroke::default().with_width(2.0).with_color(self.colors.tx),
+            );
+        }
+
+        // Legend with the limit and the latest value
+        let y = size.height - 8.0;
+        frame.fill_rectangle(
+            Point::new(50.0, y - 3.0),
+            Size::new(10.0, 6.0),
+            self.colors.tx,
+        );
+        let legend = match current {
+            Some(percent) => format!("TX / {} kbps limit: {:.0}%", self.rate_limit_kbps, percent),
+            None => format!("TX / {} kbps limit", self.rate_limit_kbps),
+        };
+        frame.fill_text(Text {
+            content: legend,
+            position: Point::new(63.0, y),
+            color: self.colors.text,
+            size: 9.0.into(),
+            align_y: iced::alignment::Vertical::Center,
+            ..Default::default()
+        });
+    }
+}
+
 /// Canvas program for rendering the bandwidth chart.
 struct BandwidthChartProgram<'a> {
     history: Option<&'a BandwidthHistory>,
@@ -466,7 +681,18 @@ struct BandwidthChartProgram<'a> {
 }
 
 impl<Message> canvas::Program<Message, Theme, Renderer> for BandwidthChartProgram<'_> {
-    type State = ();
+    /// Whether the cursor was over the chart on the last event.
+    type State = bool;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        hover_redraw(state, event, bounds, cursor)
+    }
 
     fn draw(
         &self,
@@ -474,25 +700,33 @@ impl<Message> canvas::Program<Message, Theme, Renderer> for BandwidthChartProgra
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
         let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
             self.draw_chart(frame, bounds.size());
         });
 
-        vec![geometry]
+        // Hover text changes with the cursor, so it lives outside the cache
+        let mut overlay = Frame::new(renderer, bounds.size());
+        if let (Some(history), Some(position)) = (self.history, cursor.position_in(bounds)) {
+            draw_marker_hover(
+                &mut overlay,
+                history,
+                self.time_window.duration(),
+                bounds.size(),
+                position,
+                &self.colors,
+            );
+        }
+
+        vec![geometry, overlay.into_geometry()]
     }
 }
 
 impl BandwidthChartProgram<'_> {
     /// Draw the complete chart.
     fn draw_chart(&self, frame: &mut Frame, size: Size) {
-        let padding = ChartPadding {
-            left: 45.0,
-            right: 10.0,
-            top: 10.0,
-            bottom: 20.0,
-        };
+        let padding = ChartPadding::default();
 
         let chart_width = size.width - padding.left - padding.right;
         let chart_height = size.height - padding.top - padding.bottom;
@@ -562,6 +796,20 @@ impl BandwidthChartProgram<'_> {
             self.colors.tx,
         );
 
+        // Draw TC change markers
+        if let Some(history) = self.history {
+            draw_tc_markers(
+                frame,
+                &history.markers_in_window(window_duration, MAX_VISIBLE_MARKERS),
+                now,
+                window_duration,
+                &padding,
+                chart_width,
+                chart_height,
+                &self.colors,
+            );
+        }
+
         // Draw axes
         self.draw_axes(frame, &padding, chart_width, chart_height);
 
@@ -753,6 +1001,157 @@ struct ChartPadding {
     bottom: f32,
 }
 
+impl Default for ChartPadding {
+    fn default() -> Self {
+        Self {
+            left: 45.0,
+            right: 10.0,
+            top: 10.0,
+            bottom: 20.0,
+        }
+    }
+}
+
+/// Request a redraw while the cursor moves over the chart (and once when it
+/// leaves) so marker hover text follows the pointer.
+fn hover_redraw<Message>(
+    was_over: &mut bool,
+    event: &Event,
+    bounds: Rectangle,
+    cursor: mouse::Cursor,
+) -> Option<canvas::Action<Message>> {
+    let Event::Mouse(mouse::Event::CursorMoved { .. }) = event else {
+        return None;
+    };
+    let is_over = cursor.is_over(bounds);
+    let changed = is_over || *was_over;
+    *was_over = is_over;
+    changed.then(canvas::Action::request_redraw)
+}
+
+/// X position of a timestamp on the chart, or `None` if outside the window.
+fn time_to_x(
+    timestamp: Instant,
+    now: Instant,
+    window_duration: Duration,
+    padding: &ChartPadding,
+    chart_width: f32,
+) -> Option<f32> {
+    let age = now.saturating_duration_since(timestamp);
+    if age > window_duration {
+        return None;
+    }
+    Some(padding.left + chart_width * (1.0 - age.as_secs_f32() / window_duration.as_secs_f32()))
+}
+
+/// Color used for a marker of the given kind.
+fn marker_color(kind: TcChangeKind, colors: &ChartColors) -> Color {
+    match kind {
+        TcChangeKind::Applied => colors.marker_applied,
+        TcChangeKind::Removed => colors.marker_removed,
+        TcChangeKind::ScenarioStep => colors.marker_scenario,
+    }
+}
+
+/// Draw vertical lines for TC changes within the visible window.
+#[allow(clippy::too_many_arguments)]
+fn draw_tc_markers(
+    frame: &mut Frame,
+    markers: &[&TcChangeMarker],
+    now: Instant,
+    window_duration: Duration,
+    padding: &ChartPadding,
+    chart_width: f32,
+    chart_height: f32,
+    colors: &ChartColors,
+) {
+    for marker in markers {
+        let Some(x) = time_to_x(marker.timestamp, now, window_duration, padding, chart_width)
+        else {
+            continue;
+        };
+        let path = Path::line(
+            Point::new(x, padding.top),
+            Point::new(x, padding.top + chart_height),
+        );
+        frame.stroke(
+            &path,
+            Stroke::default()
+                .with_width(1.5)
+                .with_color(marker_color(marker.kind, colors)),
+        );
+    }
+}
+
+/// Find the visible marker closest to the cursor's X position, if any is
+/// within hover distance.
+fn hovered_marker<'a>(
+    markers: &[&'a TcChangeMarker],
+    cursor_x: f32,
+    now: Instant,
+    window_duration: Duration,
+    padding: &ChartPadding,
+    chart_width: f32,
+) -> Option<(&'a TcChangeMarker, f32)> {
+    markers
+        .iter()
+        .filter_map(|m| {
+            time_to_x(m.timestamp, now, window_duration, padding, chart_width).map(|x| (*m, x))
+        })
+        .filter(|(_, x)| (x - cursor_x).abs() <= MARKER_HOVER_DISTANCE)
+        .min_by(|(_, a), (_, b)| (a - cursor_x).abs().total_cmp(&(b - cursor_x).abs()))
+}
+
+/// Draw the description of the marker under the cursor.
+fn draw_marker_hover(
+    frame: &mut Frame,
+    history: &BandwidthHistory,
+    window_duration: Duration,
+    size: Size,
+    cursor: Point,
+    colors: &ChartColors,
+) {
+    let padding = ChartPadding::default();
+    let chart_width = size.width - padding.left - padding.right;
+    let chart_height = size.height - padding.top - padding.bottom;
+    if chart_width <= 0.0 || chart_height <= 0.0 {
+        return;
+    }
+
+    let markers = history.markers_in_window(window_duration, MAX_VISIBLE_MARKERS);
+    let Some((marker, x)) = hovered_marker(
+        &markers,
+        cursor.x,
+        Instant::now(),
+        window_duration,
+        &padding,
+        chart_width,
+    ) else {
+        return;
+    };
+
+    // Rough text width estimate; keep the label inside the plot area
+    let label_width = marker.description.chars().count() as f32 * 5.5 + 8.0;
+    let label_x = (x + 4.0)
+        .min(padding.left + chart_width - label_width)
+        .max(padding.left);
+    let label_y = padding.top + 2.0;
+
+    frame.fill_rectangle(
+        Point::new(label_x, label_y),
+        Size::new(label_width, 14.0),
+        colors.background,
+    );
+    frame.fill_text(Text {
+        content: marker.description.clone(),
+        position: Point::new(label_x + 4.0, label_y + 7.0),
+        color: marker_color(marker.kind, colors),
+        size: 10.0.into(),
+        align_y: iced::alignment::Vertical::Center,
+        ..Default::default()
+    });
+}
+
 /// Format bytes per second with appropriate units.
 fn format_rate(bytes_per_sec: f64) -> String {
     if bytes_per_sec >= 1_073_741_824.0 {
@@ -825,4 +1224,41 @@ mod tests {
         chart.set_time_window(ChartTimeWindow::FiveMinutes);
         assert_eq!(chart.time_window, ChartTimeWindow::FiveMinutes);
     }
+
+    #[test]
+    fn test_time_to_x_within_window() {
+        let padding = ChartPadding::default();
+        let now = Instant::now();
+        let window = Duration::from_secs(60);
+
+        let x_now = time_to_x(now, now, window, &padding, 100.0).unwrap();
+        assert_eq!(x_now, padding.left + 100.0);
+
+        let x_half = time_to_x(now - Duration::from_secs(30), now, window, &padding, 100.0);
+        assert_eq!(x_half, Some(padding.left + 50.0));
+
+        assert!(time_to_x(now - Duration::from_secs(90), now, window, &padding, 100.0).is_none());
+    }
+
+    #[test]
+    fn test_hovered_marker_picks_nearest() {
+        let padding = ChartPadding::default();
+        let now = Instant::now();
+        let window = Duration::from_secs(60);
+        let make = |secs_ago: u64, description: &str| TcChangeMarker {
+            timestamp: now - Duration::from_secs(secs_ago),
+            kind: TcChangeKind::Applied,
+            description: description.to_string(),
+        };
+        let first = make(30, "first");
+        let second = make(0, "second");
+        let markers = vec![&first, &second];
+
+        // 30s ago maps to the middle of a 100px chart
+        let hit = hovered_marker(&markers, padding.left + 52.0, now, window, &padding, 100.0);
+        assert_eq!(hit.map(|(m, _)| m.description.as_str()), Some("first"));
+
+        let miss = hovered_marker(&markers, padding.left + 20.0, now, window, &padding, 100.0);
+        assert!(miss.is_none());
+    }
 }
@@ -6,6 +6,21 @@
 use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
+use serde::Serialize;
+
+use tcgui_shared::scenario::ScenarioExecution;
+use tcgui_shared::{TcConfigUpdate, TcConfiguration};
+
+use crate::clock_skew::{ClockSkew, unix_now_ms};
+
+/// Maximum number of TC change markers kept per interface.
+const MAX_MARKERS: usize = 32;
+
+/// Minimum spacing of scenario step markers. A step starting sooner after
+/// the previous one replaces its marker, so short steps don't turn the
+/// chart into a wall of lines.
+const MIN_SCENARIO_MARKER_SPACING: Duration = Duration::from_secs(2);
+
 /// Single data point in the bandwidth history.
 #[derive(Debug, Clone, Copy)]
 pub struct BandwidthSample {
@@ -17,6 +32,174 @@ pub struct BandwidthSample {
     pub tx_bytes_per_sec: f64,
 }
 
+/// A retained sample as exported, timed in Unix milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ExportedSample {
+    /// When this sample was recorded, in Unix milliseconds
+    pub unix_ms: i64,
+    /// Receive rate in bytes per second
+    pub rx_bytes_per_sec: f64,
+    /// Transmit rate in bytes per second
+    pub tx_bytes_per_sec: f64,
+}
+
+/// The retained samples of one interface, as exported to JSON.
+#[derive(Debug, Serialize)]
+struct HistoryExport<'a> {
+    backend: &'a str,
+    namespace: &'a str,
+    interface: &'a str,
+    samples: Vec<ExportedSample>,
+}
+
+/// File format of a bandwidth history export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryExportFormat {
+    Csv,
+    Json,
+}
+
+impl HistoryExportFormat {
+    /// File extension of the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            HistoryExportFormat::Csv => "csv",
+            HistoryExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Kind of TC change annotated on the bandwidth chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcChangeKind {
+    /// A TC configuration was applied or changed
+    Applied,
+    /// The TC configuration was removed
+    Removed,
+    /// A scenario running on the interface moved to its next step
+    ScenarioStep,
+}
+
+/// A TC configuration change recorded alongside the bandwidth samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcChangeMarker {
+    /// When the change happened
+    pub timestamp: Instant,
+    /// Whether the change applied or removed TC
+    pub kind: TcChangeKind,
+    /// Human-readable summary shown when hovering the marker
+    pub description: String,
+}
+
+impl TcChangeMarker {
+    /// Build a marker from a backend TC config update.
+    ///
+    /// The update's Unix timestamp is corrected for the backend's clock skew
+    /// and mapped onto the local monotonic clock; updates without a timestamp
+    /// (e.g. a cleared config) are placed at now.
+    pub fn from_config_update(update: &TcConfigUpdate, clock_skew: &ClockSkew) -> Self {
+        let timestamp = if update.timestamp == 0 {
+            Instant::now()
+        } else {
+            local_instant(clock_skew.to_local_ms(update.timestamp))
+        };
+
+        let (kind, description) = match (&update.configuration, update.has_tc) {
+            (Some(config), _) => (TcChangeKind::Applied, describe_configuration(config)),
+            (None, true) => (TcChangeKind::Applied, "TC changed".to_string()),
+            (None, false) => (TcChangeKind::Removed, "TC removed".to_string()),
+        };
+
+        Self {
+            timestamp,
+            kind,
+            description,
+        }
+    }
+
+    /// Build a marker for the step a scenario execution is on, labeled with
+    /// the step's description.
+    ///
+    /// `timestamp_ms` is the execution update's Unix timestamp in
+    /// milliseconds, corrected for the backend's clock skew.
+    pub fn scenario_step(
+        execution: &ScenarioExecution,
+        timestamp_ms: u64,
+        clock_skew: &ClockSkew,
+    ) -> Self {
+        let local_ms = timestamp_ms as i64 - clock_skew.offset_ms().unwrap_or(0);
+        let steps = &execution.scenario.steps;
+        let step = execution.current_step + 1;
+        let description = match steps.get(execution.current_step) {
+            Some(s) if !s.description.is_empty() => {
+                format!("Step {}/{}: {}", step, steps.len(), s.description)
+            }
+            _ => format!("Step {}/{}", step, steps.len()),
+        };
+
+        Self {
+            timestamp: local_instant(local_ms),
+            kind: TcChangeKind::ScenarioStep,
+            description,
+        }
+    }
+}
+
+/// Map a local Unix time in milliseconds onto the monotonic clock. Times in
+/// the future are placed at now.
+fn local_instant(local_ms: i64) -> Instant {
+    let now = Instant::now();
+    let age = Duration::from_millis((unix_now_ms() - local_ms).max(0) as u64);
+    now.checked_sub(age).unwrap_or(now)
+}
+
+/// Summarize the active parameters of a TC configuration.
+fn describe_configuration(config: &TcConfiguration) -> String {
+    let mut parts = Vec::new();
+    if config.loss > 0.0 {
+        parts.push(format!("loss {}%", config.loss));
+    }
+    if let Some(delay) = config.delay_ms.filter(|d| *d > 0.0) {
+        match config.delay_jitter_ms.filter(|j| *j > 0.0) {
+            Some(jitter) => parts.push(format!("delay {}ms ±{}ms", delay, jitter)),
+            None => parts.push(format!("delay {}ms", delay)),
+        }
+    }
+    if let Some(dup) = config.duplicate_percent.filter(|p| *p > 0.0) {
+        parts.push(format!("duplicate {}%", dup));
+    }
+    if let Some(reorder) = config.reorder_percent.filter(|p| *p > 0.0) {
+        parts.push(format!("reorder {}%", reorder));
+    }
+    if let Some(corrupt) = config.corrupt_percent.filter(|p| *p > 0.0) {
+        parts.push(format!("corrupt {}%", corrupt));
+    }
+    if let Some(rate) = config.rate_limit_kbps.filter(|r| *r > 0) {
+        parts.push(format!("rate {}kbps", rate));
+    }
+
+    if parts.is_empty() {
+        "TC applied".to_string()
+    } else {
+        format!("TC applied: {}", parts.join(", "))
+    }
+}
+
+/// Transmit throughput as a percentage of a rate limit (kbit/s), capped at
+/// 100%: bursts briefly exceed the shaped rate and would otherwise read as
+/// more than fully used. `None` without a limit.
+///
+/// netem shapes egress, so the TX rate is what the limit bounds. Near 100%
+/// the limit is the bottleneck; well below it the application isn't using
+/// the link it was given.
+pub fn rate_limit_efficiency(tx_bytes_per_sec: f64, rate_limit_kbps: u32) -> Option<f64> {
+    if rate_limit_kbps == 0 {
+        return None;
+    }
+    let limit_bytes_per_sec = f64::from(rate_limit_kbps) * 1000.0 / 8.0;
+    Some((tx_bytes_per_sec / limit_bytes_per_sec * 100.0).clamp(0.0, 100.0))
+}
+
 /// Time-series data for one interface.
 ///
 /// Stores bandwidth samples in a ring buffer, automatically pruning
@@ -24,6 +207,7 @@ pub struct BandwidthSample {
 #[derive(Debug, Clone)]
 pub struct BandwidthHistory {
     samples: VecDeque<BandwidthSample>,
+    markers: VecDeque<TcChangeMarker>,
     max_duration: Duration,
     max_samples: usize,
 }
@@ -40,6 +224,7 @@ impl BandwidthHistory {
         let max_samples = (max_duration.as_secs() as usize).saturating_add(10);
         Self {
             samples: VecDeque::with_capacity(max_samples.min(512)),
+            markers: VecDeque::new(),
             max_duration,
             max_samples,
         }
@@ -76,6 +261,21 @@ impl BandwidthHistory {
         &self.samples
     }
 
+    /// The stored samples in chronological order, timed in Unix
+    /// milliseconds.
+    pub fn exported_samples(&self) -> Vec<ExportedSample> {
+        let now = Instant::now();
+        let now_ms = unix_now_ms();
+        self.samples
+            .iter()
+            .map(|sample| ExportedSample {
+                unix_ms: now_ms - now.duration_since(sample.timestamp).as_millis() as i64,
+                rx_bytes_per_sec: sample.rx_bytes_per_sec,
+                tx_bytes_per_sec: sample.tx_bytes_per_sec,
+            })
+            .collect()
+    }
+
     /// Get the number of stored samples.
     pub fn len(&self) -> usize {
         self.samples.len()
@@ -116,10 +316,97 @@ impl BandwidthHistory {
         (sum_rx / count, sum_tx / count)
     }
 
+    /// Rate-limit efficiency ([`rate_limit_efficiency`]) of the samples
+    /// within a time window, oldest first.
+    ///
+    /// Only samples since the latest TC change are included: earlier ones
+    /// were shaped by a different configuration (or none), so comparing them
+    /// with the current limit would be meaningless. Scenario step markers
+    /// don't count, the step's own TC change does.
+    pub fn efficiency_in_window(
+        &self,
+        window: Duration,
+        rate_limit_kbps: u32,
+    ) -> Vec<(Instant, f64)> {
+        let since = self
+            .markers
+            .iter()
+            .rev()
+            .find(|m| m.kind != TcChangeKind::ScenarioStep)
+            .map(|m| m.timestamp);
+        self.samples_in_window(window)
+            .filter(|s| since.is_none_or(|since| s.timestamp >= since))
+            .filter_map(|s| {
+                rate_limit_efficiency(s.tx_bytes_per_sec, rate_limit_kbps)
+                    .map(|percent| (s.timestamp, percent))
+            })
+            .collect()
+    }
+
     /// Get the timestamp of the most recent sample, if any.
     pub fn last_update(&self) -> Option<Instant> {
         self.samples.back().map(|s| s.timestamp)
     }
+
+    /// Record a TC change marker.
+    ///
+    /// Markers older than the history duration are dropped, as is a marker
+    /// repeating the latest one (the backend republishes unchanged configs).
+    /// A scenario step marker replaces the previous one when it is within
+    /// [`MIN_SCENARIO_MARKER_SPACING`] of it.
+    pub fn push_marker(&mut self, marker: TcChangeMarker) {
+        let now = Instant::now();
+        if now.duration_since(marker.timestamp) > self.max_duration {
+            return;
+        }
+        if marker.kind == TcChangeKind::ScenarioStep
+            && let Some(previous) = self.markers.iter().rposition(|m| {
+                m.kind == TcChangeKind::ScenarioStep
+                    && m.timestamp.max(marker.timestamp) - m.timestamp.min(marker.timestamp)
+                        < MIN_SCENARIO_MARKER_SPACING
+            })
+        {
+            self.markers.remove(previous);
+        }
+        if self
+            .markers
+            .back()
+            .is_some_and(|last| last.kind == marker.kind && last.description == marker.description)
+        {
+            return;
+        }
+
+        // Keep markers ordered by time even if updates arrive out of order
+        let pos = self
+            .markers
+            .iter()
+            .rposition(|m| m.timestamp <= marker.timestamp)
+            .map_or(0, |i| i + 1);
+        self.markers.insert(pos, marker);
+
+        while let Some(front) = self.markers.front() {
+            if now.duration_since(front.timestamp) > self.max_duration {
+                self.markers.pop_front();
+            } else {
+                break;
+            }
+        }
+        while self.markers.len() > MAX_MARKERS {
+            self.markers.pop_front();
+        }
+    }
+
+    /// Get the most recent markers within a time window, oldest first.
+    pub fn markers_in_window(&self, window: Duration, limit: usize) -> Vec<&TcChangeMarker> {
+        let cutoff = Instant::now().checked_sub(window);
+        let in_window: Vec<_> = self
+            .markers
+            .iter()
+            .filter(|m| cutoff.is_none_or(|cutoff| m.timestamp >= cutoff))
+            .collect();
+        let skip = in_window.len().saturating_sub(limit);
+        in_window.into_iter().skip(skip).collect()
+    }
 }
 
 /// Key for identifying an interface across backends and namespaces.
@@ -185,6 +472,21 @@ impl BandwidthHistoryManager {
             .push(rx_bytes_per_sec, tx_bytes_per_sec);
     }
 
+    /// Record a TC change marker for an interface.
+    pub fn record_tc_change(
+        &mut self,
+        backend: &str,
+        namespace: &str,
+        interface: &str,
+        marker: TcChangeMarker,
+    ) {
+        let key = InterfaceKey::new(backend, namespace, interface);
+        self.histories
+            .entry(key)
+            .or_insert_with(|| BandwidthHistory::new(self.default_duration))
+            .push_marker(marker);
+    }
+
     /// Get the bandwidth history for an interface.
     pub fn get(
         &self,
@@ -221,12 +523,67 @@ impl BandwidthHistoryManager {
     pub fn interface_count(&self) -> usize {
         self.histories.len()
     }
+
+    /// The samples retained for an interface, oldest first; none if it has
+    /// no history.
+    fn exported_samples(
+        &self,
+        backend: &str,
+        namespace: &str,
+        interface: &str,
+    ) -> Vec<ExportedSample> {
+        self.get(backend, namespace, interface)
+            .map(BandwidthHistory::exported_samples)
+            .unwrap_or_default()
+    }
+
+    /// The samples retained for an interface as CSV: Unix time in
+    /// milliseconds, then receive and transmit rates in bytes per second.
+    /// Without history, only the header.
+    pub fn export_csv(&self, backend: &str, namespace: &str, interface: &str) -> String {
+        let mut csv = String::from("unix_ms,rx_bytes_per_sec,tx_bytes_per_sec\n");
+        for sample in self.exported_samples(backend, namespace, interface) {
+            csv.push_str(&format!(
+                "{},{:.0},{:.0}\n",
+                sample.unix_ms, sample.rx_bytes_per_sec, sample.tx_bytes_per_sec
+            ));
+        }
+        csv
+    }
+
+    /// The samples retained for an interface as a JSON document naming the
+    /// interface. Without history, `samples` is empty.
+    pub fn export_json(&self, backend: &str, namespace: &str, interface: &str) -> String {
+        let export = HistoryExport {
+            backend,
+            namespace,
+            interface,
+            samples: self.exported_samples(backend, namespace, interface),
+        };
+        serde_json::to_string_pretty(&export).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// The samples retained for an interface in `format`.
+    pub fn export(
+        &self,
+        backend: &str,
+        namespace: &str,
+        interface: &str,
+        format: HistoryExportFormat,
+    ) -> String {
+        match format {
+            HistoryExportFormat::Csv => self.export_csv(backend, namespace, interface),
+            HistoryExportFormat::Json => self.export_json(backend, namespace, interface),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::thread::sleep;
+    use tcgui_shared::scenario::{ExecutionState, ExecutionStats, NetworkScenario, ScenarioStep};
+    use tcgui_shared::{RateLimitMode, TcNetemConfig};
 
     #[test]
     fn test_bandwidth_history_new() {
@@ -250,6 +607,37 @@ mod tests {
         assert_eq!(samples[1].tx_bytes_per_sec, 1000.0);
     }
 
+    #[test]
+    fn test_rate_limit_efficiency() {
+        // 1000 kbps = 125000 bytes/s
+        assert_eq!(rate_limit_efficiency(62_500.0, 1000), Some(50.0));
+        assert_eq!(rate_limit_efficiency(0.0, 1000), Some(0.0));
+        // Bursts above the limit are capped
+        assert_eq!(rate_limit_efficiency(150_000.0, 1000), Some(100.0));
+        assert_eq!(rate_limit_efficiency(62_500.0, 0), None);
+    }
+
+    #[test]
+    fn test_efficiency_starts_at_latest_tc_change() {
+        let mut history = BandwidthHistory::new(Duration::from_secs(60));
+        history.push(0.0, 125_000.0);
+        sleep(Duration::from_millis(5));
+        history.push_marker(TcChangeMarker {
+            timestamp: Instant::now(),
+            kind: TcChangeKind::Applied,
+            description: "TC applied: rate 1000kbps".to_string(),
+        });
+        history.push(0.0, 25_000.0);
+        history.push(0.0, 125_000.0);
+
+        let efficiency: Vec<f64> = history
+            .efficiency_in_window(Duration::from_secs(60), 1000)
+            .into_iter()
+            .map(|(_, percent)| percent)
+            .collect();
+        assert_eq!(efficiency, [20.0, 100.0]);
+    }
+
     #[test]
     fn test_bandwidth_history_peak() {
         let mut history = BandwidthHistory::new(Duration::from_secs(60));
@@ -359,4 +747,237 @@ mod tests {
         assert_eq!(history.len(), 1);
         assert_eq!(history.samples().back().unwrap().rx_bytes_per_sec, 2000.0);
     }
+
+    fn marker(kind: TcChangeKind, description: &str) -> TcChangeMarker {
+        TcChangeMarker {
+            timestamp: Instant::now(),
+            kind,
+            description: description.to_string(),
+        }
+    }
+
+    fn config_update(configuration: Option<TcConfiguration>, has_tc: bool) -> TcConfigUpdate {
+        TcConfigUpdate {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            backend_name: "backend1".to_string(),
+            timestamp: 0,
+            configuration,
+            has_tc,
+        }
+    }
+
+    #[test]
+    fn test_marker_from_config_update() {
+        let config = TcConfiguration {
+            loss: 5.0,
+            correlation: None,
+            loss_gemodel: None,
+            loss_ecn: false,
+            seed: None,
+            delay_ms: Some(100.0),
+            delay_jitter_ms: Some(10.0),
+            delay_correlation: None,
+            delay_distribution: None,
+            duplicate_percent: None,
+            duplicate_correlation: None,
+            reorder_percent: None,
+            reorder_correlation: None,
+            reorder_gap: None,
+            corrupt_percent: None,
+            corrupt_correlation: None,
+            rate_limit_kbps: Some(1000),
+            rate_limit_shaper: RateLimitMode::Netem,
+            slot: None,
+            command: String::new(),
+        };
+        let applied = TcChangeMarker::from_config_update(
+            &config_update(Some(config), true),
+            &ClockSkew::default(),
+        );
+        assert_eq!(applied.kind, TcChangeKind::Applied);
+        assert_eq!(
+            applied.description,
+            "TC applied: loss 5%, delay 100ms ±10ms, rate 1000kbps"
+        );
+
+        let removed =
+            TcChangeMarker::from_config_update(&config_update(None, false), &ClockSkew::default());
+        assert_eq!(removed.kind, TcChangeKind::Removed);
+        assert_eq!(removed.description, "TC removed");
+    }
+
+    #[test]
+    fn test_marker_compensates_clock_skew() {
+        // A backend whose clock runs a minute behind ours
+        let mut skew = ClockSkew::default();
+        let now_ms = unix_now_ms();
+        for _ in 0..10 {
+            skew.observe(((now_ms - 60_000) / 1000) as u64, now_ms);
+        }
+
+        let mut update = config_update(None, false);
+        update.timestamp = ((now_ms - 60_000) / 1000) as u64;
+
+        // Uncorrected, the change would be drawn a minute in the past
+        let raw = TcChangeMarker::from_config_update(&update, &ClockSkew::default());
+        assert!(raw.timestamp.elapsed() >= Duration::from_secs(55));
+
+        let corrected = TcChangeMarker::from_config_update(&update, &skew);
+        assert!(corrected.timestamp.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_markers_skip_repeats_and_stale() {
+        let mut history = BandwidthHistory::new(Duration::from_secs(60));
+
+        history.push_marker(marker(TcChangeKind::Applied, "TC applied: loss 5%"));
+        history.push_marker(marker(TcChangeKind::Applied, "TC applied: loss 5%"));
+        history.push_marker(marker(TcChangeKind::Removed, "TC removed"));
+        assert_eq!(
+            history.markers_in_window(Duration::from_secs(60), 10).len(),
+            2
+        );
+
+        let mut stale = marker(TcChangeKind::Applied, "TC applied: loss 1%");
+        stale.timestamp = Instant::now() - Duration::from_secs(120);
+        history.push_marker(stale);
+        assert_eq!(
+            history.markers_in_window(Duration::from_secs(60), 10).len(),
+            2
+        );
+    }
+
+    fn execution(current_step: usize) -> ScenarioExecution {
+        let step = |description: &str| ScenarioStep {
+            duration_ms: 10_000,
+            tc_config: TcNetemConfig::new(),
+            description: description.to_string(),
+            ramp: None,
+        };
+        let mut scenario = NetworkScenario::new(
+            "handover".to_string(),
+            "Handover".to_string(),
+            String::new(),
+        );
+        scenario.steps = vec![step("Good signal"), step(""), step("Outage")];
+        ScenarioExecution {
+            scenario,
+            start_time: 0,
+            current_step,
+            state: ExecutionState::Running,
+            target_namespace: "default".to_string(),
+            target_interface: "eth0".to_string(),
+            stats: ExecutionStats::default(),
+            loop_execution: false,
+            loop_iteration: 0,
+            paused_ms: 0,
+            current_phase: None,
+        }
+    }
+
+    #[test]
+    fn test_scenario_step_marker() {
+        let now_ms = unix_now_ms() as u64;
+        let marker =
+            TcChangeMarker::scenario_step(&execution(0), now_ms - 5_000, &ClockSkew::default());
+        assert_eq!(marker.kind, TcChangeKind::ScenarioStep);
+        assert_eq!(marker.description, "Step 1/3: Good signal");
+        assert!(marker.timestamp.elapsed() >= Duration::from_secs(4));
+
+        // Steps without a description are labeled by number alone
+        let marker = TcChangeMarker::scenario_step(&execution(1), now_ms, &ClockSkew::default());
+        assert_eq!(marker.description, "Step 2/3");
+    }
+
+    #[test]
+    fn test_scenario_step_markers_are_thinned() {
+        let mut history = BandwidthHistory::new(Duration::from_secs(60));
+        let step = |description: &str, age_ms: u64| TcChangeMarker {
+            timestamp: Instant::now() - Duration::from_millis(age_ms),
+            kind: TcChangeKind::ScenarioStep,
+            description: description.to_string(),
+        };
+
+        history.push_marker(step("Step 1/3", 10_000));
+        history.push_marker(marker(TcChangeKind::Applied, "TC applied: loss 5%"));
+        // Steps closer together than the minimum spacing keep the latest
+        history.push_marker(step("Step 2/3", 1_000));
+        history.push_marker(step("Step 3/3", 500));
+
+        let descriptions: Vec<_> = history
+            .markers_in_window(Duration::from_secs(60), 10)
+            .into_iter()
+            .map(|m| m.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            ["Step 1/3", "Step 3/3", "TC applied: loss 5%"]
+        );
+
+        // The step markers don't hide the latest TC change from the
+        // efficiency chart
+        history.push(0.0, 125_000.0);
+        assert_eq!(
+            history
+                .efficiency_in_window(Duration::from_secs(60), 1000)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_markers_in_window_limit() {
+        let mut history = BandwidthHistory::new(Duration::from_secs(60));
+        for i in 0..(MAX_MARKERS + 5) {
+            history.push_marker(marker(TcChangeKind::Applied, &format!("change {}", i)));
+        }
+
+        let all = history.markers_in_window(Duration::from_secs(60), usize::MAX);
+        assert_eq!(all.len(), MAX_MARKERS);
+
+        let limited = history.markers_in_window(Duration::from_secs(60), 3);
+        assert_eq!(limited.len(), 3);
+        assert_eq!(
+            limited.last().unwrap().description,
+            format!("change {}", MAX_MARKERS + 4)
+        );
+    }
+
+    #[test]
+    fn test_export_lists_samples_in_order() {
+        let mut manager = BandwidthHistoryManager::default();
+        manager.record("backend1", "default", "eth0", 1000.0, 500.0);
+        manager.record("backend1", "default", "eth0", 2000.4, 0.0);
+
+        let csv = manager.export_csv("backend1", "default", "eth0");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "unix_ms,rx_bytes_per_sec,tx_bytes_per_sec");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(",1000,500"));
+        assert!(lines[2].ends_with(",2000,0"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&manager.export_json("backend1", "default", "eth0")).unwrap();
+        assert_eq!(json["interface"], "eth0");
+        let samples = json["samples"].as_array().unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0]["rx_bytes_per_sec"], 1000.0);
+        assert_eq!(samples[1]["rx_bytes_per_sec"], 2000.4);
+        assert!(samples[0]["unix_ms"].as_i64() <= samples[1]["unix_ms"].as_i64());
+    }
+
+    #[test]
+    fn test_export_without_history() {
+        let manager = BandwidthHistoryManager::default();
+        assert_eq!(
+            manager.export_csv("backend1", "default", "eth0"),
+            "unix_ms,rx_bytes_per_sec,tx_bytes_per_sec\n"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&manager.export_json("backend1", "default", "eth0")).unwrap();
+        assert_eq!(json["backend"], "backend1");
+        assert_eq!(json["samples"], serde_json::json!([]));
+    }
 }
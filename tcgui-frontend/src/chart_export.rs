@@ -0,0 +1,221 @@
+//! Export of a bandwidth chart for reports.
+//!
+//! The chart is captured from a screenshot of the window, cropped to the
+//! chart's on-screen bounds and saved as a PNG. When the renderer can't take
+//! screenshots, or the chart isn't on screen, the chart's samples are saved
+//! as CSV instead so the data is never lost. The samples can also be saved
+//! directly, as CSV or JSON (see
+//! [`BandwidthHistoryManager::export`](crate::bandwidth_history::BandwidthHistoryManager::export)).
+
+use std::path::PathBuf;
+
+use iced::advanced::widget::{self, Operation, operation};
+use iced::window::Screenshot;
+use iced::{Rectangle, Task, Vector};
+
+/// Directory exports are written to, under the user's pictures directory.
+const EXPORT_DIR: &str = "tcgui";
+
+/// Visible bounds of the widget with `id`, in logical pixels relative to the
+/// window, or `None` if it isn't in the widget tree.
+pub fn visible_bounds(id: widget::Id) -> Task<Option<Rectangle>> {
+    widget::operate(VisibleBounds {
+        target: id,
+        depth: 0,
+        scrollables: Vec::new(),
+        bounds: None,
+    })
+}
+
+/// Finds a container and clips its bounds to the scrollables it is in.
+struct VisibleBounds {
+    target: widget::Id,
+    depth: usize,
+    /// Translation and viewport of each enclosing scrollable, with the depth
+    /// it was found at
+    scrollables: Vec<(Vector, Rectangle, usize)>,
+    bounds: Option<Rectangle>,
+}
+
+impl Operation<Option<Rectangle>> for VisibleBounds {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation<Option<Rectangle>>)) {
+        if self.bounds.is_some() {
+            return;
+        }
+        self.depth += 1;
+        operate(self);
+        self.depth -= 1;
+        // Leaving a scrollable's children
+        if matches!(self.scrollables.last(), Some((_, _, depth)) if *depth == self.depth) {
+            self.scrollables.pop();
+        }
+    }
+
+    fn scrollable(
+        &mut self,
+        _id: Option<&widget::Id>,
+        bounds: Rectangle,
+        _content_bounds: Rectangle,
+        translation: Vector,
+        _state: &mut dyn operation::Scrollable,
+    ) {
+        let entry = match self.scrollables.last() {
+            Some((outer_translation, outer_viewport, _)) => (
+                translation + *outer_translation,
+                outer_viewport
+                    .intersection(&(bounds - *outer_translation))
+                    .unwrap_or_default(),
+                self.depth,
+            ),
+            None => (translation, bounds, self.depth),
+        };
+        self.scrollables.push(entry);
+    }
+
+    fn container(&mut self, id: Option<&widget::Id>, bounds: Rectangle) {
+        if self.bounds.is_none() && id == Some(&self.target) {
+            self.bounds = match self.scrollables.last() {
+                Some((translation, viewport, _)) => viewport.intersection(&(bounds - *translation)),
+                None => Some(bounds),
+            };
+        }
+    }
+
+    fn finish(&self) -> operation::Outcome<Option<Rectangle>> {
+        operation::Outcome::Some(self.bounds)
+    }
+}
+
+/// The part of `screenshot` showing `bounds` (logical pixels), or `None` if
+/// the screenshot is empty or doesn't cover any of it.
+pub fn crop(screenshot: &Screenshot, bounds: Rectangle) -> Option<Screenshot> {
+    let scale = screenshot.scale_factor;
+    let x = (bounds.x * scale).round().max(0.0) as u32;
+    let y = (bounds.y * scale).round().max(0.0) as u32;
+    let width = ((bounds.width * scale).round() as u32).min(screenshot.size.width.checked_sub(x)?);
+    let height =
+        ((bounds.height * scale).round() as u32).min(screenshot.size.height.checked_sub(y)?);
+    screenshot
+        .crop(Rectangle {
+            x,
+            y,
+            width,
+            height,
+        })
+        .ok()
+}
+
+/// Encode an RGBA screenshot as PNG.
+pub fn encode_png(screenshot: &Screenshot) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, screenshot.size.width, screenshot.size.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&screenshot.rgba))
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Write an export for `interface` with the given extension, returning the
+/// file it was written to.
+pub fn save(interface: &str, extension: &str, contents: &[u8]) -> Result<PathBuf, String> {
+    let dir = dirs::picture_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "no pictures or home directory".to_string())?
+        .join(EXPORT_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("creating {}: {}", dir.display(), e))?;
+
+    let path = dir.join(file_name(
+        interface,
+        &chrono::Local::now().format("%Y%m%d-%H%M%S").to_string(),
+        extension,
+    ));
+    std::fs::write(&path, contents).map_err(|e| format!("writing {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// File name for an export, with characters that don't belong in a file
+/// name replaced.
+fn file_name(interface: &str, timestamp: &str, extension: &str) -> String {
+    let interface: String = interface
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("bandwidth-{}-{}.{}", interface, timestamp, extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::Size;
+
+    /// A `width` x `height` screenshot whose pixels hold their own x and y.
+    fn screenshot(width: u32, height: u32, scale_factor: f32) -> Screenshot {
+        let rgba: Vec<u8> = (0..height)
+            .flat_map(|y| (0..width).flat_map(move |x| [x as u8, y as u8, 0, 255]))
+            .collect();
+        Screenshot::new(rgba, Size::new(width, height), scale_factor)
+    }
+
+    #[test]
+    fn test_crop_scales_and_clamps_bounds() {
+        let shot = screenshot(100, 50, 2.0);
+        let chart = crop(
+            &shot,
+            Rectangle::new((10.0, 5.0).into(), Size::new(20.0, 10.0)),
+        )
+        .expect("inside the window");
+        assert_eq!(chart.size, Size::new(40, 20));
+        assert_eq!(&chart.rgba[..2], &[20, 10]);
+
+        // A chart partly scrolled out of view keeps the visible part
+        let chart = crop(
+            &shot,
+            Rectangle::new((40.0, 20.0).into(), Size::new(30.0, 30.0)),
+        )
+        .expect("partly inside the window");
+        assert_eq!(chart.size, Size::new(20, 10));
+
+        // Nothing to crop from an empty screenshot or outside the window
+        assert!(
+            crop(
+                &screenshot(0, 0, 1.0),
+                Rectangle::with_size(Size::new(5.0, 5.0))
+            )
+            .is_none()
+        );
+        assert!(
+            crop(
+                &shot,
+                Rectangle::new((60.0, 0.0).into(), Size::new(5.0, 5.0))
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_encode_png_writes_a_png() {
+        let png = encode_png(&screenshot(4, 3, 1.0)).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (4, 3));
+    }
+
+    #[test]
+    fn test_file_name_is_safe() {
+        assert_eq!(
+            file_name("veth0@if3/x", "20261015-120000", "png"),
+            "bandwidth-veth0_if3_x-20261015-120000.png"
+        );
+    }
+}
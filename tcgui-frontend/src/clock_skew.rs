@@ -0,0 +1,162 @@
+//! Per-backend clock skew estimation.
+//!
+//! Backends stamp their messages with Unix seconds from their own clock. When
+//! that clock is off, anything placed on the local timeline from a backend
+//! timestamp (e.g. TC change markers on the bandwidth chart) lands in the wrong
+//! spot, and charts from backends on different machines stop lining up.
+//! [`ClockSkew`] estimates the offset by comparing message timestamps with the
+//! local receive time and maps backend timestamps back onto the local clock.
+
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of recent offset samples the estimate is taken over.
+const WINDOW: usize = 31;
+
+/// Samples required before an estimate is reported.
+const MIN_SAMPLES: usize = 5;
+
+/// Skew (either direction) above which the backend is flagged.
+pub const SKEW_WARNING_THRESHOLD_MS: i64 = 5_000;
+
+/// Current local Unix time in milliseconds.
+pub fn unix_now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Rolling estimate of how far a backend's clock is ahead of ours.
+///
+/// Each sample is `backend timestamp - local receive time`. The estimate is
+/// the median of the last [`WINDOW`] samples, so a single delayed message or
+/// bogus timestamp does not move it.
+#[derive(Debug, Clone, Default)]
+pub struct ClockSkew {
+    samples: VecDeque<i64>,
+    offset_ms: Option<i64>,
+    warned: bool,
+}
+
+impl ClockSkew {
+    /// Record a backend timestamp (Unix seconds) received at `local_ms`.
+    ///
+    /// Returns `true` when the estimate has just crossed
+    /// [`SKEW_WARNING_THRESHOLD_MS`], so the caller can warn once per episode.
+    /// A zero timestamp means "not set" and is ignored.
+    pub fn observe(&mut self, backend_secs: u64, local_ms: i64) -> bool {
+        if backend_secs == 0 {
+            return false;
+        }
+
+        // Backend timestamps are truncated to whole seconds, so on average the
+        // real send time is half a second later than stamped.
+        let sample = backend_secs as i64 * 1000 + 500 - local_ms;
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+
+        if self.samples.len() < MIN_SAMPLES {
+            return false;
+        }
+        let mut sorted: Vec<i64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        self.offset_ms = Some(sorted[sorted.len() / 2]);
+
+        let excessive = self.is_excessive();
+        let newly_excessive = excessive && !self.warned;
+        self.warned = excessive;
+        newly_excessive
+    }
+
+    /// Estimated offset in milliseconds (positive = backend clock ahead), once
+    /// enough samples have arrived.
+    pub fn offset_ms(&self) -> Option<i64> {
+        self.offset_ms
+    }
+
+    /// Whether the estimated skew exceeds [`SKEW_WARNING_THRESHOLD_MS`].
+    pub fn is_excessive(&self) -> bool {
+        self.offset_ms
+            .is_some_and(|offset| offset.abs() > SKEW_WARNING_THRESHOLD_MS)
+    }
+
+    /// Map a backend timestamp (Unix seconds) to local Unix milliseconds.
+    pub fn to_local_ms(&self, backend_secs: u64) -> i64 {
+        backend_secs as i64 * 1000 - self.offset_ms.unwrap_or(0)
+    }
+
+    /// Human-readable estimate, e.g. "+3.2 s (backend ahead)".
+    pub fn describe(&self) -> String {
+        match self.offset_ms {
+            None => "measuring…".to_string(),
+            Some(offset) if offset.abs() < 1000 => "in sync".to_string(),
+            Some(offset) => format!(
+                "{:+.1} s (backend {})",
+                offset as f64 / 1000.0,
+                if offset > 0 { "ahead" } else { "behind" }
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL_MS: i64 = 1_700_000_000_000;
+
+    /// Feed `n` one-second-apart samples from a backend `skew_ms` ahead.
+    fn feed(skew: &mut ClockSkew, n: usize, skew_ms: i64) -> bool {
+        let mut crossed = false;
+        for i in 0..n as i64 {
+            let local = LOCAL_MS + i * 1000;
+            let backend_secs = ((local + skew_ms) / 1000) as u64;
+            crossed |= skew.observe(backend_secs, local);
+        }
+        crossed
+    }
+
+    #[test]
+    fn test_estimate_needs_warmup() {
+        let mut skew = ClockSkew::default();
+        feed(&mut skew, MIN_SAMPLES - 1, 0);
+        assert_eq!(skew.offset_ms(), None);
+        assert_eq!(skew.describe(), "measuring…");
+
+        feed(&mut skew, 1, 0);
+        assert!(skew.offset_ms().unwrap().abs() <= 500);
+        assert_eq!(skew.describe(), "in sync");
+    }
+
+    #[test]
+    fn test_single_outlier_does_not_move_estimate() {
+        let mut skew = ClockSkew::default();
+        feed(&mut skew, 10, 0);
+        let before = skew.offset_ms().unwrap();
+
+        // One message stamped an hour in the future
+        skew.observe((LOCAL_MS / 1000 + 3600) as u64, LOCAL_MS + 10_000);
+        assert_eq!(skew.offset_ms(), Some(before));
+        assert!(!skew.is_excessive());
+    }
+
+    #[test]
+    fn test_large_skew_warns_once_and_maps_back() {
+        let mut skew = ClockSkew::default();
+        assert!(feed(&mut skew, 10, 30_000));
+        let offset = skew.offset_ms().unwrap();
+        assert!((offset - 30_000).abs() <= 500);
+        assert!(skew.is_excessive());
+        assert!(skew.describe().ends_with("(backend ahead)"));
+
+        // Still skewed: no repeated warning
+        assert!(!feed(&mut skew, 5, 30_000));
+
+        // A backend timestamp maps back to (about) the local receive time
+        let backend_secs = ((LOCAL_MS + 30_000) / 1000) as u64;
+        assert!((skew.to_local_ms(backend_secs) - LOCAL_MS).abs() <= 1000);
+    }
+}
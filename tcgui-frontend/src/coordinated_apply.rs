@@ -0,0 +1,288 @@
+//! Impairment changes that land on several backends at the same instant.
+//!
+//! For distributed tests the interfaces of a path, possibly on different
+//! hosts, should change together (e.g. all go into "outage" at once). The
+//! frontend sends every backend the same request with a common
+//! [`TcRequest::apply_at`](tcgui_shared::TcRequest::apply_at); each backend
+//! holds it until that time. A [`CoordinatedApply`] follows the hops from
+//! "sent" through "scheduled" (the backend accepted it) to "applied" (the
+//! configuration update that follows the change arrived).
+//!
+//! Backends act on their own clocks, so the hops only change together when
+//! the clocks agree. [`skew_warnings`] turns the per-backend [`ClockSkew`]
+//! estimates into warnings before anything is sent.
+
+use std::fmt;
+
+use tcgui_shared::TcOperation;
+use tcgui_shared::presets::CustomPreset;
+
+use crate::clock_skew::ClockSkew;
+use crate::ui_state::InterfaceKey;
+
+/// Lead time when no time is entered: enough for every backend to receive
+/// the request before it is due.
+pub const DEFAULT_LEAD_MS: u64 = 5_000;
+
+/// Clock offset above which a backend is expected to change noticeably
+/// early or late.
+pub const COORDINATION_SKEW_MS: i64 = 1_000;
+
+/// Time after the common instant by which a scheduled hop should have
+/// confirmed its change.
+pub const CONFIRMATION_GRACE_MS: u64 = 5_000;
+
+/// What to do on every hop.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoordinatedAction {
+    /// Apply the preset's configuration
+    Apply(Box<CustomPreset>),
+    /// Remove the impairment
+    Clear,
+}
+
+impl CoordinatedAction {
+    /// The TC operation sent to each backend.
+    pub fn operation(&self) -> TcOperation {
+        match self {
+            CoordinatedAction::Apply(preset) => TcOperation::ApplyConfig {
+                config: preset.config.clone(),
+            },
+            CoordinatedAction::Clear => TcOperation::Remove,
+        }
+    }
+}
+
+impl fmt::Display for CoordinatedAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordinatedAction::Apply(preset) => write!(f, "{}", preset.name),
+            CoordinatedAction::Clear => write!(f, "Clear impairment"),
+        }
+    }
+}
+
+/// Where one hop of a coordinated apply stands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HopStatus {
+    /// Request sent, no answer yet
+    Sent,
+    /// The backend holds the request until the common time
+    Scheduled,
+    /// The change was confirmed `offset_ms` after the common time (as seen
+    /// locally, so including the network delay)
+    Applied { offset_ms: i64 },
+    /// The request arrived after the common time and was applied on receipt
+    AppliedLate,
+    /// The backend refused the request
+    Failed(String),
+}
+
+impl HopStatus {
+    /// Whether nothing more is expected for the hop.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, HopStatus::Sent | HopStatus::Scheduled)
+    }
+}
+
+/// One interface of a coordinated apply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinatedHop {
+    pub key: InterfaceKey,
+    pub status: HopStatus,
+}
+
+/// A change sent to several interfaces for the same instant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinatedApply {
+    /// What is applied, e.g. the preset name
+    pub label: String,
+    /// The common time (local Unix milliseconds)
+    pub apply_at_ms: u64,
+    pub hops: Vec<CoordinatedHop>,
+    /// Clock warnings raised when it was sent
+    pub warnings: Vec<String>,
+}
+
+impl CoordinatedApply {
+    /// Start following a change sent to `keys`.
+    pub fn new(
+        label: String,
+        apply_at_ms: u64,
+        keys: impl IntoIterator<Item = InterfaceKey>,
+        warnings: Vec<String>,
+    ) -> Self {
+        Self {
+            label,
+            apply_at_ms,
+            hops: keys
+                .into_iter()
+                .map(|key| CoordinatedHop {
+                    key,
+                    status: HopStatus::Sent,
+                })
+                .collect(),
+            warnings,
+        }
+    }
+
+    fn hop_mut(&mut self, key: &InterfaceKey) -> Option<&mut CoordinatedHop> {
+        self.hops.iter_mut().find(|hop| &hop.key == key)
+    }
+
+    /// A backend answered the request for `key`: `scheduled_at` is set when
+    /// it holds the request, unset when it already applied it.
+    pub fn on_accepted(&mut self, key: &InterfaceKey, scheduled_at: Option<u64>) {
+        if let Some(hop) = self.hop_mut(key)
+            && hop.status == HopStatus::Sent
+        {
+            hop.status = match scheduled_at {
+                Some(_) => HopStatus::Scheduled,
+                None => HopStatus::AppliedLate,
+            };
+        }
+    }
+
+    /// A backend refused the request for `key`.
+    pub fn on_error(&mut self, key: &InterfaceKey, error: String) {
+        if let Some(hop) = self.hop_mut(key)
+            && !hop.status.is_final()
+        {
+            hop.status = HopStatus::Failed(error);
+        }
+    }
+
+    /// A configuration update for `key` arrived at `now_ms`. While a request
+    /// is scheduled the backend replaces it on any other change, so the
+    /// next update is the scheduled change itself.
+    pub fn on_config_update(&mut self, key: &InterfaceKey, now_ms: u64) {
+        let apply_at_ms = self.apply_at_ms;
+        if let Some(hop) = self.hop_mut(key)
+            && hop.status == HopStatus::Scheduled
+        {
+            hop.status = HopStatus::Applied {
+                offset_ms: now_ms as i64 - apply_at_ms as i64,
+            };
+        }
+    }
+
+    /// Whether every hop has an outcome.
+    pub fn is_complete(&self) -> bool {
+        self.hops.iter().all(|hop| hop.status.is_final())
+    }
+
+    /// Hops still waiting to confirm well after the common time.
+    pub fn overdue(&self, now_ms: u64) -> Vec<&CoordinatedHop> {
+        if now_ms < self.apply_at_ms + CONFIRMATION_GRACE_MS {
+            return Vec::new();
+        }
+        self.hops
+            .iter()
+            .filter(|hop| !hop.status.is_final())
+            .collect()
+    }
+
+    /// Count of hops applied, e.g. "2 of 3 applied".
+    pub fn summary(&self) -> String {
+        let applied = self
+            .hops
+            .iter()
+            .filter(|hop| {
+                matches!(
+                    hop.status,
+                    HopStatus::Applied { .. } | HopStatus::AppliedLate
+                )
+            })
+            .count();
+        format!("{} of {} applied", applied, self.hops.len())
+    }
+}
+
+/// Warnings for backends whose clocks will make them change noticeably
+/// early or late, or whose offset is not known yet. `backends` pairs each
+/// backend's display name with its skew estimate.
+pub fn skew_warnings(backends: impl IntoIterator<Item = (String, ClockSkew)>) -> Vec<String> {
+    backends
+        .into_iter()
+        .filter_map(|(name, skew)| match skew.offset_ms() {
+            None => Some(format!("{}: clock offset not measured yet", name)),
+            Some(offset) if offset.abs() >= COORDINATION_SKEW_MS => Some(format!(
+                "{}: clock {}, it will change about {:.1} s {}",
+                name,
+                skew.describe(),
+                offset.abs() as f64 / 1000.0,
+                if offset > 0 { "early" } else { "late" }
+            )),
+            Some(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(backend: &str, interface: &str) -> InterfaceKey {
+        InterfaceKey {
+            backend_name: backend.to_string(),
+            namespace: "default".to_string(),
+            interface: interface.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_hops_report_their_own_outcome() {
+        let (a, b, c) = (key("a", "eth0"), key("b", "eth0"), key("c", "eth1"));
+        let mut apply = CoordinatedApply::new(
+            "Outage".to_string(),
+            10_000,
+            [a.clone(), b.clone(), c.clone()],
+            Vec::new(),
+        );
+        assert_eq!(apply.summary(), "0 of 3 applied");
+
+        apply.on_accepted(&a, Some(10_000));
+        apply.on_accepted(&b, None);
+        apply.on_error(&c, "error/tc/apply: no such device".to_string());
+        assert_eq!(apply.hops[0].status, HopStatus::Scheduled);
+        assert_eq!(apply.hops[1].status, HopStatus::AppliedLate);
+        assert!(matches!(apply.hops[2].status, HopStatus::Failed(_)));
+        assert!(!apply.is_complete());
+        assert!(apply.overdue(12_000).is_empty());
+        assert_eq!(apply.overdue(15_000).len(), 1);
+
+        // Only a scheduled hop is confirmed by a configuration update
+        apply.on_config_update(&b, 10_200);
+        apply.on_config_update(&a, 10_150);
+        assert_eq!(apply.hops[0].status, HopStatus::Applied { offset_ms: 150 });
+        assert_eq!(apply.hops[1].status, HopStatus::AppliedLate);
+        assert!(apply.is_complete());
+        assert_eq!(apply.summary(), "2 of 3 applied");
+    }
+
+    #[test]
+    fn test_skewed_and_unmeasured_backends_are_warned_about() {
+        let local_ms = 1_700_000_000_000_i64;
+        let measured = |skew_ms: i64| {
+            let mut skew = ClockSkew::default();
+            for i in 0..10 {
+                let local = local_ms + i * 1000;
+                skew.observe(((local + skew_ms) / 1000) as u64, local);
+            }
+            skew
+        };
+        let in_sync = measured(0);
+        let ahead = measured(3_000);
+        let unknown = ClockSkew::default();
+
+        let warnings = skew_warnings([
+            ("lab-a".to_string(), in_sync),
+            ("lab-b".to_string(), ahead),
+            ("lab-c".to_string(), unknown),
+        ]);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("lab-b: clock +"));
+        assert!(warnings[0].ends_with("s early"));
+        assert_eq!(warnings[1], "lab-c: clock offset not measured yet");
+    }
+}
@@ -0,0 +1,238 @@
+//! Per-interface history of diagnostics runs.
+//!
+//! Each interface card only shows its latest diagnostics result. This module
+//! keeps the previous runs for the session so successive runs (e.g. before
+//! and after a TC change) can be compared. Histories live on the backend
+//! group and are keyed by namespace and interface name, so they outlive an
+//! interface that disappears and are dropped together with the backend.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Local};
+use tcgui_shared::DiagnosticsResponse;
+
+/// Maximum number of runs kept per interface; older runs are discarded.
+pub const MAX_RUNS: usize = 20;
+
+/// A completed diagnostics run and when its result arrived.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsRun {
+    /// Local time the result was received
+    pub recorded_at: DateTime<Local>,
+    /// The backend's response
+    pub response: DiagnosticsResponse,
+}
+
+impl DiagnosticsRun {
+    /// Average round-trip time in milliseconds, if latency was measured.
+    pub fn avg_rtt_ms(&self) -> Option<f32> {
+        self.response.results.latency.as_ref().map(|l| l.avg_ms)
+    }
+
+    /// Ping loss percentage, if latency was measured.
+    pub fn loss_percent(&self) -> Option<f32> {
+        self.response
+            .results
+            .latency
+            .as_ref()
+            .map(|l| l.packet_loss_percent)
+    }
+
+    /// Kernel-measured qdisc throughput in bytes per second, if reported.
+    pub fn throughput_bps(&self) -> Option<u32> {
+        self.response.results.tc_stats.as_ref().and_then(|s| s.bps)
+    }
+}
+
+/// Change of each metric from one run to a later one. A field is `None`
+/// when either run lacks that measurement.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiagnosticsTrend {
+    pub rtt_ms: Option<f32>,
+    pub loss_percent: Option<f32>,
+    pub throughput_bps: Option<i64>,
+}
+
+impl DiagnosticsTrend {
+    /// Difference `later - earlier` for every metric both runs measured.
+    pub fn between(earlier: &DiagnosticsRun, later: &DiagnosticsRun) -> Self {
+        fn delta<T, D>(a: Option<T>, b: Option<T>, sub: impl Fn(T, T) -> D) -> Option<D> {
+            a.zip(b).map(|(a, b)| sub(b, a))
+        }
+        Self {
+            rtt_ms: delta(earlier.avg_rtt_ms(), later.avg_rtt_ms(), |a, b| a - b),
+            loss_percent: delta(earlier.loss_percent(), later.loss_percent(), |a, b| a - b),
+            throughput_bps: delta(earlier.throughput_bps(), later.throughput_bps(), |a, b| {
+                i64::from(a) - i64::from(b)
+            }),
+        }
+    }
+}
+
+/// Capped list of runs for one interface, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsHistory {
+    runs: VecDeque<DiagnosticsRun>,
+}
+
+impl DiagnosticsHistory {
+    /// Append a run, discarding the oldest one beyond [`MAX_RUNS`].
+    pub fn push(&mut self, run: DiagnosticsRun) {
+        if self.runs.len() == MAX_RUNS {
+            self.runs.pop_front();
+        }
+        self.runs.push_back(run);
+    }
+
+    /// All kept runs, oldest first.
+    pub fn runs(&self) -> &VecDeque<DiagnosticsRun> {
+        &self.runs
+    }
+
+    /// Number of kept runs.
+    pub fn len(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Whether no run has been kept.
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Change from the previous run to the run at `index`.
+    pub fn trend_at(&self, index: usize) -> Option<DiagnosticsTrend> {
+        let previous = self.runs.get(index.checked_sub(1)?)?;
+        Some(DiagnosticsTrend::between(previous, self.runs.get(index)?))
+    }
+
+    /// Change from the oldest to the newest kept run.
+    pub fn overall_trend(&self) -> Option<DiagnosticsTrend> {
+        match (self.runs.front(), self.runs.back()) {
+            (Some(first), Some(last)) if self.runs.len() > 1 => {
+                Some(DiagnosticsTrend::between(first, last))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Diagnostics histories of one backend, keyed by (namespace, interface).
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsHistoryManager {
+    histories: HashMap<(String, String), DiagnosticsHistory>,
+}
+
+impl DiagnosticsHistoryManager {
+    /// Record a completed run for an interface.
+    pub fn record(&mut self, namespace: &str, interface: &str, response: DiagnosticsResponse) {
+        self.histories
+            .entry((namespace.to_string(), interface.to_string()))
+            .or_default()
+            .push(DiagnosticsRun {
+                recorded_at: Local::now(),
+                response,
+            });
+    }
+
+    /// Get the history of an interface, if it has any runs.
+    pub fn get(&self, namespace: &str, interface: &str) -> Option<&DiagnosticsHistory> {
+        self.histories
+            .get(&(namespace.to_string(), interface.to_string()))
+            .filter(|history| !history.is_empty())
+    }
+
+    /// Forget all runs of an interface.
+    pub fn clear(&mut self, namespace: &str, interface: &str) {
+        self.histories
+            .remove(&(namespace.to_string(), interface.to_string()));
+    }
+
+    /// Get the number of interfaces with a history.
+    pub fn interface_count(&self) -> usize {
+        self.histories.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tcgui_shared::{DiagnosticsResults, LatencyResult, TcDiagnosticStats};
+
+    fn response(avg_ms: f32, loss: f32, bps: Option<u32>) -> DiagnosticsResponse {
+        DiagnosticsResponse {
+            success: true,
+            message: "ok".to_string(),
+            results: DiagnosticsResults {
+                latency: Some(LatencyResult {
+                    target: "10.0.0.1".to_string(),
+                    min_ms: avg_ms,
+                    avg_ms,
+                    max_ms: avg_ms,
+                    packet_loss_percent: loss,
+                    samples: 3,
+                }),
+                tc_stats: Some(TcDiagnosticStats {
+                    bps,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn test_history_is_capped_and_keeps_newest() {
+        let mut manager = DiagnosticsHistoryManager::default();
+        for i in 0..MAX_RUNS + 5 {
+            manager.record("default", "eth0", response(i as f32, 0.0, None));
+        }
+
+        let history = manager.get("default", "eth0").unwrap();
+        assert_eq!(history.len(), MAX_RUNS);
+        assert_eq!(history.runs().front().unwrap().avg_rtt_ms(), Some(5.0));
+        assert_eq!(
+            history.runs().back().unwrap().avg_rtt_ms(),
+            Some((MAX_RUNS + 4) as f32)
+        );
+    }
+
+    #[test]
+    fn test_trends_between_runs() {
+        let mut manager = DiagnosticsHistoryManager::default();
+        manager.record("ns1", "veth0", response(10.0, 0.0, Some(1000)));
+        manager.record("ns1", "veth0", response(60.0, 5.0, None));
+        manager.record("ns1", "veth0", response(55.0, 2.0, Some(400)));
+
+        let history = manager.get("ns1", "veth0").unwrap();
+        assert_eq!(history.trend_at(0), None);
+        assert_eq!(
+            history.trend_at(1),
+            Some(DiagnosticsTrend {
+                rtt_ms: Some(50.0),
+                loss_percent: Some(5.0),
+                throughput_bps: None,
+            })
+        );
+        assert_eq!(
+            history.overall_trend(),
+            Some(DiagnosticsTrend {
+                rtt_ms: Some(45.0),
+                loss_percent: Some(2.0),
+                throughput_bps: Some(-600),
+            })
+        );
+    }
+
+    #[test]
+    fn test_clear_only_affects_one_interface() {
+        let mut manager = DiagnosticsHistoryManager::default();
+        manager.record("default", "eth0", response(1.0, 0.0, None));
+        manager.record("default", "eth1", response(1.0, 0.0, None));
+
+        manager.clear("default", "eth0");
+        assert!(manager.get("default", "eth0").is_none());
+        assert!(manager.get("default", "eth1").is_some());
+        assert_eq!(manager.interface_count(), 1);
+    }
+}
@@ -0,0 +1,137 @@
+//! History of the Zenoh endpoints the frontend reached backends through.
+//!
+//! Connect endpoints are remembered once a backend answered through the
+//! session using them, most recent first and at most [`MAX_ENDPOINTS`], and
+//! persisted with the settings so the connection panel can offer them again
+//! instead of having them retyped. Entries that don't pass
+//! [`ZenohConfig`]'s endpoint validation are never offered.
+
+use tcgui_shared::ZenohConfig;
+
+/// Number of endpoints remembered.
+pub const MAX_ENDPOINTS: usize = 10;
+
+/// Connect endpoint a peer-mode frontend always dials, so never remembered.
+pub const DEFAULT_CONNECT_ENDPOINT: &str = "tcp/127.0.0.1:7447";
+
+/// Check a connect endpoint (`tcp/10.0.0.1:7447`) the way the session
+/// configuration would.
+pub fn validate_endpoint(endpoint: &str) -> Result<(), String> {
+    ZenohConfig::new_client()
+        .add_connect_endpoint(endpoint)
+        .validate()
+        .map_err(|e| e.to_string())
+}
+
+/// Connect endpoints of a session configuration worth remembering.
+pub fn connect_endpoints(config: &ZenohConfig) -> Vec<String> {
+    config
+        .endpoints
+        .iter()
+        .filter_map(|endpoint| endpoint.strip_prefix("connect/"))
+        .filter(|endpoint| *endpoint != DEFAULT_CONNECT_ENDPOINT)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Previously used connect endpoints, most recent first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointHistory {
+    entries: Vec<String>,
+}
+
+impl EndpointHistory {
+    /// Restore a persisted history, dropping invalid and duplicate entries.
+    pub fn from_entries(entries: &[String]) -> Self {
+        let mut history = Self::default();
+        for endpoint in entries.iter().rev() {
+            history.record(endpoint);
+        }
+        history
+    }
+
+    /// Remembered endpoints, most recent first.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Remember `endpoint` as the most recently used one. Invalid endpoints
+    /// and the built-in default are ignored.
+    pub fn record(&mut self, endpoint: &str) {
+        let endpoint = endpoint.trim();
+        if endpoint == DEFAULT_CONNECT_ENDPOINT || validate_endpoint(endpoint).is_err() {
+            return;
+        }
+        self.entries.retain(|entry| entry != endpoint);
+        self.entries.insert(0, endpoint.to_string());
+        self.entries.truncate(MAX_ENDPOINTS);
+    }
+
+    /// Forget `endpoint`. Returns whether it was remembered.
+    pub fn remove(&mut self, endpoint: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry != endpoint);
+        self.entries.len() != before
+    }
+
+    /// Remembered endpoints containing `input`, most recent first.
+    pub fn suggestions<'a>(&'a self, input: &'a str) -> impl Iterator<Item = &'a String> {
+        let input = input.trim();
+        self.entries
+            .iter()
+            .filter(move |entry| entry.contains(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_is_most_recent_first_and_capped() {
+        let mut history = EndpointHistory::default();
+        for i in 1..=MAX_ENDPOINTS + 2 {
+            history.record(&format!("tcp/10.0.0.{i}:7447"));
+        }
+        assert_eq!(history.entries().len(), MAX_ENDPOINTS);
+        assert_eq!(history.entries()[0], "tcp/10.0.0.12:7447");
+
+        // Reusing an entry moves it to the front without duplicating it
+        history.record("tcp/10.0.0.5:7447");
+        assert_eq!(history.entries()[0], "tcp/10.0.0.5:7447");
+        assert_eq!(history.entries().len(), MAX_ENDPOINTS);
+
+        assert!(history.remove("tcp/10.0.0.5:7447"));
+        assert!(!history.remove("tcp/10.0.0.5:7447"));
+    }
+
+    #[test]
+    fn test_invalid_and_default_endpoints_are_not_offered() {
+        let history = EndpointHistory::from_entries(&[
+            "tcp/192.168.1.10:7447".to_string(),
+            "tcp/not-an-address".to_string(),
+            "carrier-pigeon/1.2.3.4:7447".to_string(),
+            DEFAULT_CONNECT_ENDPOINT.to_string(),
+            "tcp/192.168.1.10:7447".to_string(),
+            "quic/[fd00::1]:7447".to_string(),
+        ]);
+        assert_eq!(
+            history.entries(),
+            ["tcp/192.168.1.10:7447", "quic/[fd00::1]:7447"]
+        );
+        assert_eq!(
+            history.suggestions("fd00").collect::<Vec<_>>(),
+            ["quic/[fd00::1]:7447"]
+        );
+        assert_eq!(history.suggestions("").count(), 2);
+    }
+
+    #[test]
+    fn test_connect_endpoints_skip_the_default() {
+        let config = ZenohConfig::new_peer()
+            .add_connect_endpoint(DEFAULT_CONNECT_ENDPOINT)
+            .add_listen_endpoint("tcp/127.0.0.1:0")
+            .add_connect_endpoint("tcp/10.1.1.1:7447");
+        assert_eq!(connect_endpoints(&config), ["tcp/10.1.1.1:7447"]);
+    }
+}
@@ -75,6 +75,8 @@ pub enum Icon {
     Activity,
     /// Hourglass/loader
     Loader,
+    /// Clock - scheduled start
+    Clock,
 
     // Labels/Sections
     /// Antenna - no interfaces
@@ -150,6 +152,7 @@ impl Icon {
             Icon::ArrowUp => include_bytes!("../icons/arrow-up.svg"),
             Icon::Activity => include_bytes!("../icons/activity.svg"),
             Icon::Loader => include_bytes!("../icons/loader.svg"),
+            Icon::Clock => include_bytes!("../icons/clock.svg"),
 
             // Labels/Sections
             Icon::Radio => include_bytes!("../icons/radio.svg"),
@@ -0,0 +1,113 @@
+//! Closing the Zenoh session while nobody uses the frontend.
+//!
+//! With an idle timeout configured (`--idle-disconnect`, off by default), the
+//! frontend drops its Zenoh session once neither the user nor the work it
+//! monitors has done anything for that long, and opens a new one on the next
+//! mouse or keyboard input. Backends then announce themselves again and the
+//! usual reconnect resync runs.
+//!
+//! Work that must be watched keeps the session open: running scenario
+//! executions, an active recording and pending refreshes count as activity.
+//! Queries complete within seconds of the user action that started them, so
+//! they can't outlive a timeout measured in minutes.
+
+use std::time::{Duration, Instant};
+
+/// Tracks user activity and whether the session was dropped for idleness.
+#[derive(Debug, Clone)]
+pub struct IdleMonitor {
+    /// `None` disables idle disconnects
+    timeout: Option<Duration>,
+    last_activity: Instant,
+    disconnected: bool,
+}
+
+impl IdleMonitor {
+    pub fn new(timeout: Option<Duration>, now: Instant) -> Self {
+        Self {
+            timeout,
+            last_activity: now,
+            disconnected: false,
+        }
+    }
+
+    /// Whether idle disconnects are enabled
+    pub fn is_enabled(&self) -> bool {
+        self.timeout.is_some()
+    }
+
+    /// Whether the session is currently dropped for idleness
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// Record user activity. Returns `true` when this ends an idle
+    /// disconnect, i.e. the session must be reopened.
+    pub fn record_activity(&mut self, now: Instant) -> bool {
+        self.last_activity = now;
+        std::mem::take(&mut self.disconnected)
+    }
+
+    /// Check for idleness. `busy` means monitored work is still running,
+    /// which counts as activity. Returns `true` when the session should be
+    /// dropped now.
+    pub fn check(&mut self, now: Instant, busy: bool) -> bool {
+        let Some(timeout) = self.timeout else {
+            return false;
+        };
+        if busy {
+            self.last_activity = now;
+            return false;
+        }
+        if self.disconnected || now.duration_since(self.last_activity) < timeout {
+            return false;
+        }
+        self.disconnected = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_disabled_by_default_never_disconnects() {
+        let start = Instant::now();
+        let mut monitor = IdleMonitor::new(None, start);
+        assert!(!monitor.is_enabled());
+        assert!(!monitor.check(start + 24 * 60 * MINUTE, false));
+        assert!(!monitor.is_disconnected());
+    }
+
+    #[test]
+    fn test_disconnects_after_timeout_and_reconnects_on_activity() {
+        let start = Instant::now();
+        let mut monitor = IdleMonitor::new(Some(10 * MINUTE), start);
+
+        assert!(!monitor.check(start + 9 * MINUTE, false));
+        assert!(monitor.check(start + 10 * MINUTE, false));
+        assert!(monitor.is_disconnected());
+        // Reported once
+        assert!(!monitor.check(start + 11 * MINUTE, false));
+
+        assert!(monitor.record_activity(start + 12 * MINUTE));
+        assert!(!monitor.is_disconnected());
+        assert!(!monitor.record_activity(start + 13 * MINUTE));
+        assert!(!monitor.check(start + 22 * MINUTE, false));
+        assert!(monitor.check(start + 23 * MINUTE, false));
+    }
+
+    #[test]
+    fn test_running_work_keeps_the_session() {
+        let start = Instant::now();
+        let mut monitor = IdleMonitor::new(Some(10 * MINUTE), start);
+
+        assert!(!monitor.check(start + 30 * MINUTE, true));
+        // The timeout counts from when the work finished
+        assert!(!monitor.check(start + 39 * MINUTE, false));
+        assert!(monitor.check(start + 40 * MINUTE, false));
+    }
+}
@@ -0,0 +1,433 @@
+//! Developer inspector for the raw Zenoh traffic of the frontend.
+//!
+//! Started with `--inspector`, the Zenoh manager hands a copy of every sample
+//! it receives and every query and reply it exchanges to [`MessageInspector`],
+//! which keeps the most recent [`MAX_ENTRIES`] for a scrollable log panel.
+//! Payloads on keys the registry knows are decoded and pretty-printed;
+//! anything else is shown as raw bytes. Without the flag nothing is recorded
+//! and the panel does not exist.
+
+use std::collections::VecDeque;
+
+use iced::widget::{button, column, container, row, scrollable, text, text_input};
+use iced::{Color, Element, Font, Length};
+use tcgui_shared::topics;
+use zenoh::key_expr::keyexpr;
+use zenoh::query::Reply;
+use zenoh::sample::{Sample, SampleKind};
+
+use crate::messages::TcGuiMessage;
+
+/// Number of entries kept; the oldest are dropped beyond this.
+pub const MAX_ENTRIES: usize = 500;
+
+/// Bytes of an undecoded payload shown in the hex dump.
+const RAW_PREVIEW_BYTES: usize = 256;
+
+/// Whether a message reached or left the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Incoming,
+    Outgoing,
+}
+
+/// What kind of Zenoh message an entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Put,
+    Delete,
+    Query,
+    Reply,
+    ReplyError,
+}
+
+impl MessageKind {
+    fn label(self) -> &'static str {
+        match self {
+            MessageKind::Put => "PUT",
+            MessageKind::Delete => "DEL",
+            MessageKind::Query => "QUERY",
+            MessageKind::Reply => "REPLY",
+            MessageKind::ReplyError => "ERROR",
+        }
+    }
+}
+
+/// A message payload as shown in the log.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InspectorPayload {
+    /// No payload (Delete tombstones, liveliness tokens, bare queries)
+    Empty,
+    /// Payload of a registered type, pretty-printed
+    Decoded { type_name: String, json: String },
+    /// Error text from a reply-error
+    Text(String),
+    /// Payload of an unknown type or that failed to decode
+    Raw(Vec<u8>),
+}
+
+impl InspectorPayload {
+    /// Decode `bytes` as JSON of `type_name`, or keep them raw when the type
+    /// is unknown or they aren't valid JSON.
+    fn decode(type_name: Option<&str>, bytes: &[u8]) -> Self {
+        if bytes.is_empty() {
+            return Self::Empty;
+        }
+        let Some(type_name) = type_name else {
+            return Self::Raw(bytes.to_vec());
+        };
+        match serde_json::from_slice::<serde_json::Value>(bytes)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+        {
+            Ok(json) => Self::Decoded {
+                type_name: type_name.to_string(),
+                json,
+            },
+            Err(_) => Self::Raw(bytes.to_vec()),
+        }
+    }
+}
+
+/// One recorded message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectorEntry {
+    /// Local Unix time in milliseconds the message was seen
+    pub timestamp_ms: i64,
+    pub direction: Direction,
+    pub kind: MessageKind,
+    /// Key expression, as seen by the session (without the `tcgui` namespace)
+    pub key: String,
+    pub payload: InspectorPayload,
+}
+
+impl InspectorEntry {
+    fn new(direction: Direction, kind: MessageKind, key: &str, payload: InspectorPayload) -> Self {
+        Self {
+            timestamp_ms: crate::clock_skew::unix_now_ms(),
+            direction,
+            kind,
+            key: key.to_string(),
+            payload,
+        }
+    }
+
+    /// A sample received by one of the subscribers.
+    pub fn sample(sample: &Sample) -> Self {
+        let key = sample.key_expr().as_str();
+        let kind = match sample.kind() {
+            SampleKind::Put => MessageKind::Put,
+            SampleKind::Delete => MessageKind::Delete,
+        };
+        let payload =
+            InspectorPayload::decode(topics::payload_type(key), &sample.payload().to_bytes());
+        Self::new(Direction::Incoming, kind, key, payload)
+    }
+
+    /// A query sent to a backend, with its request body if any.
+    pub fn query(key: &str, payload: Option<&str>) -> Self {
+        let payload = match payload {
+            Some(payload) => InspectorPayload::decode(
+                topics::parse_procedure(key).map(|procedure| procedure.path()),
+                payload.as_bytes(),
+            ),
+            None => InspectorPayload::Empty,
+        };
+        Self::new(Direction::Outgoing, MessageKind::Query, key, payload)
+    }
+
+    /// A reply to the query sent on `query_key`.
+    pub fn reply(query_key: &str, reply: &Reply) -> Self {
+        match reply.result() {
+            Ok(sample) => Self {
+                kind: MessageKind::Reply,
+                ..Self::sample(sample)
+            },
+            Err(error) => {
+                let bytes = error.payload().to_bytes();
+                let payload = match std::str::from_utf8(&bytes) {
+                    Ok(message) => InspectorPayload::Text(message.to_string()),
+                    Err(_) => InspectorPayload::Raw(bytes.to_vec()),
+                };
+                Self::new(
+                    Direction::Incoming,
+                    MessageKind::ReplyError,
+                    query_key,
+                    payload,
+                )
+            }
+        }
+    }
+}
+
+/// Whether `key` matches the filter: a key expression when it contains a
+/// wildcard (`v1/*/state/**`), a plain substring otherwise.
+pub fn matches_filter(filter: &str, key: &str) -> bool {
+    let filter = filter.trim();
+    if filter.is_empty() {
+        return true;
+    }
+    if filter.contains('*')
+        && let (Ok(filter), Ok(key)) = (keyexpr::new(filter), keyexpr::new(key))
+    {
+        return filter.intersects(key);
+    }
+    key.contains(filter)
+}
+
+/// Hex and ASCII dump of the first [`RAW_PREVIEW_BYTES`] of `bytes`.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump: Vec<String> = bytes[..bytes.len().min(RAW_PREVIEW_BYTES)]
+        .chunks(16)
+        .map(|line| {
+            let hex: Vec<String> = line.iter().map(|b| format!("{b:02x}")).collect();
+            let ascii: String = line
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:<48} {}", hex.join(" "), ascii)
+        })
+        .collect();
+    if bytes.len() > RAW_PREVIEW_BYTES {
+        dump.push(format!("... ({} bytes)", bytes.len()));
+    }
+    dump.join("\n")
+}
+
+/// Log of the recent raw Zenoh messages.
+#[derive(Debug, Clone)]
+pub struct MessageInspector {
+    entries: VecDeque<InspectorEntry>,
+    /// Topic filter as typed, see [`matches_filter`]
+    pub filter: String,
+    /// Whether the panel is shown
+    pub open: bool,
+    /// Whether recording is suspended
+    pub paused: bool,
+}
+
+impl Default for MessageInspector {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(MAX_ENTRIES),
+            filter: String::new(),
+            open: true,
+            paused: false,
+        }
+    }
+}
+
+impl MessageInspector {
+    /// Record a message, dropping the oldest beyond [`MAX_ENTRIES`].
+    pub fn record(&mut self, entry: InspectorEntry) {
+        if self.paused {
+            return;
+        }
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries matching the filter, oldest first.
+    pub fn filtered(&self) -> impl Iterator<Item = &InspectorEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches_filter(&self.filter, &entry.key))
+    }
+
+    /// Renders the log panel.
+    pub fn view(&self) -> Element<'_, TcGuiMessage> {
+        let shown = self.filtered().count();
+        let toolbar = row![
+            text("Zenoh inspector").size(14),
+            text_input(
+                "filter: substring or key expression (v1/*/state/**)",
+                &self.filter
+            )
+            .on_input(TcGuiMessage::InspectorFilterChanged)
+            .size(12)
+            .padding(4)
+            .width(Length::Fill),
+            text(format!("{} of {}", shown, self.len())).size(12),
+            button(text(if self.paused { "Resume" } else { "Pause" }).size(12))
+                .padding(4)
+                .on_press(TcGuiMessage::ToggleInspectorPause)
+                .style(button::secondary),
+            button(text("Clear").size(12))
+                .padding(4)
+                .on_press(TcGuiMessage::ClearInspector)
+                .style(button::secondary),
+            button(text("Close").size(12))
+                .padding(4)
+                .on_press(TcGuiMessage::ToggleInspector)
+                .style(button::secondary),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+
+        let mut log = column![].spacing(6);
+        for entry in self.filtered() {
+            log = log.push(render_entry(entry));
+        }
+
+        container(
+            column![
+                toolbar,
+                scrollable(log)
+                    .anchor_bottom()
+                    .height(Length::Fill)
+                    .width(Length::Fill)
+            ]
+            .spacing(8),
+        )
+        .padding(8)
+        .height(Length::Fixed(300.0))
+        .width(Length::Fill)
+        .style(container::bordered_box)
+        .into()
+    }
+}
+
+/// Renders one log entry: a header line and its payload.
+fn render_entry(entry: &InspectorEntry) -> Element<'_, TcGuiMessage> {
+    let time = chrono::DateTime::from_timestamp_millis(entry.timestamp_ms)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%H:%M:%S%.3f")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let arrow = match entry.direction {
+        Direction::Incoming => "<-",
+        Direction::Outgoing => "->",
+    };
+    let kind_color = match entry.kind {
+        MessageKind::Put | MessageKind::Reply => Color::from_rgb(0.2, 0.6, 0.3),
+        MessageKind::Delete | MessageKind::ReplyError => Color::from_rgb(0.8, 0.3, 0.2),
+        MessageKind::Query => Color::from_rgb(0.2, 0.45, 0.8),
+    };
+    let type_name = match &entry.payload {
+        InspectorPayload::Decoded { type_name, .. } => type_name.as_str(),
+        InspectorPayload::Raw(_) => "raw",
+        InspectorPayload::Text(_) | InspectorPayload::Empty => "",
+    };
+
+    let header = row![
+        text(time).size(12).font(Font::MONOSPACE),
+        text(arrow).size(12).font(Font::MONOSPACE),
+        text(entry.kind.label())
+            .size(12)
+            .font(Font::MONOSPACE)
+            .style(move |_| text::Style {
+                color: Some(kind_color),
+            }),
+        text(entry.key.as_str()).size(12).font(Font::MONOSPACE),
+        text(type_name).size(12),
+    ]
+    .spacing(8);
+
+    let body = match &entry.payload {
+        InspectorPayload::Empty => None,
+        InspectorPayload::Decoded { json, .. } => Some(json.clone()),
+        InspectorPayload::Text(message) => Some(message.clone()),
+        InspectorPayload::Raw(bytes) => Some(hex_dump(bytes)),
+    };
+    match body {
+        Some(body) => column![
+            header,
+            container(text(body).size(11).font(Font::MONOSPACE)).padding([0, 24])
+        ]
+        .spacing(2)
+        .into(),
+        None => header.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str) -> InspectorEntry {
+        InspectorEntry::new(
+            Direction::Incoming,
+            MessageKind::Put,
+            key,
+            InspectorPayload::Empty,
+        )
+    }
+
+    #[test]
+    fn test_known_types_are_decoded_and_unknown_kept_raw() {
+        let decoded = InspectorPayload::decode(Some("TcResponse"), br#"{"success":true}"#);
+        assert_eq!(
+            decoded,
+            InspectorPayload::Decoded {
+                type_name: "TcResponse".to_string(),
+                json: "{\n  \"success\": true\n}".to_string(),
+            }
+        );
+        assert_eq!(
+            InspectorPayload::decode(None, br#"{"success":true}"#),
+            InspectorPayload::Raw(br#"{"success":true}"#.to_vec())
+        );
+        assert_eq!(
+            InspectorPayload::decode(Some("TcResponse"), b"\x00\x01"),
+            InspectorPayload::Raw(vec![0, 1])
+        );
+        assert_eq!(
+            InspectorPayload::decode(Some("TcResponse"), b""),
+            InspectorPayload::Empty
+        );
+    }
+
+    #[test]
+    fn test_filter_accepts_substrings_and_key_expressions() {
+        let key = "v1/h-0123456789ab/state/tc/config/default/eth0";
+        assert!(matches_filter("", key));
+        assert!(matches_filter("config", key));
+        assert!(!matches_filter("bandwidth", key));
+        assert!(matches_filter("v1/*/state/**", key));
+        assert!(!matches_filter("v1/*/telemetry/**", key));
+    }
+
+    #[test]
+    fn test_log_is_capped_and_pausable() {
+        let mut inspector = MessageInspector::default();
+        for i in 0..MAX_ENTRIES + 10 {
+            inspector.record(entry(&format!("v1/key/{i}")));
+        }
+        assert_eq!(inspector.len(), MAX_ENTRIES);
+        assert_eq!(inspector.filtered().next().unwrap().key, "v1/key/10");
+
+        inspector.paused = true;
+        inspector.clear();
+        inspector.record(entry("v1/key/late"));
+        assert!(inspector.is_empty());
+    }
+
+    #[test]
+    fn test_hex_dump_is_truncated() {
+        assert_eq!(hex_dump(b"ab\x00"), format!("{:<48} ab.", "61 62 00"));
+        let dump = hex_dump(&[0u8; 300]);
+        assert_eq!(dump.lines().count(), 17);
+        assert!(dump.ends_with("(300 bytes)"));
+    }
+}
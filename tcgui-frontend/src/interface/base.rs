@@ -4,17 +4,27 @@
 //! feature-specific components while maintaining the same external API as
 //! the original monolithic interface.
 
-use iced::widget::{Column, checkbox, column, container, row, text, tooltip};
+use iced::widget::{Column, checkbox, column, container, row, text, text_input, tooltip};
 use iced::{Background, Color, Element, Task};
 use std::time::Duration;
-use tcgui_shared::NetworkBandwidthStats;
-use tcgui_shared::presets::PresetList;
+use tcgui_shared::config_history::ConfigPeriod;
+use tcgui_shared::presets::{CustomPreset, PresetList};
+use tcgui_shared::shorthand::parse_shorthand;
+use tcgui_shared::{
+    DEFAULT_PING_COUNT, DEFAULT_PROBE_DURATION_MS, DiagnosticsTest, GeModel, LossModel,
+    NetworkBandwidthStats, PingResult, RateLimitMode, TcConfigDifference, TcConfiguration,
+    TcDirection, TcFeature, TcNetemConfig, TcReconciliation, TcSlotConfig, TcValidationError,
+    ThroughputResult,
+};
 
+use super::layers::{ConfigLayer, merge_layers};
 use super::state::InterfaceState;
 use super::value_input;
-use crate::bandwidth_chart::bandwidth_chart_view;
-use crate::bandwidth_history::BandwidthHistory;
+use crate::bandwidth_chart::{bandwidth_chart_view, efficiency_chart_view};
+use crate::bandwidth_history::{BandwidthHistory, HistoryExportFormat};
+use crate::diagnostics_history::DiagnosticsHistory;
 use crate::icons::Icon;
+use crate::interface_style::{AccentColor, InterfaceStyle};
 use crate::messages::TcInterfaceMessage;
 use crate::theme::Theme;
 use crate::view::{scaled, scaled_spacing};
@@ -31,6 +41,95 @@ fn rate_cap_exceeds_link(rate_kbps: u32, link_mbps: Option<u32>) -> bool {
     matches!(link_mbps, Some(mbps) if mbps > 0 && rate_kbps > mbps.saturating_mul(1000))
 }
 
+/// Characters of an interface description that fit under the name.
+const DESCRIPTION_PREVIEW_CHARS: usize = 18;
+
+/// An interface description cut to fit the name column, with an ellipsis
+/// when cut.
+fn shorten_description(description: &str) -> String {
+    if description.chars().count() <= DESCRIPTION_PREVIEW_CHARS {
+        return description.to_string();
+    }
+    let cut: String = description
+        .chars()
+        .take(DESCRIPTION_PREVIEW_CHARS - 1)
+        .collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// One period of the backend's configuration history: when it held (local
+/// time) and what it did, e.g. "10:00:00–10:05:00  Delays every packet by
+/// 100 ms."
+fn config_period_line(period: &ConfigPeriod<'_>) -> String {
+    let time = |timestamp: u64| {
+        chrono::DateTime::from_timestamp(timestamp as i64, 0)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    };
+    let when = match period.until {
+        Some(until) => format!("{}–{}", time(period.from), time(until)),
+        None => format!("since {}", time(period.from)),
+    };
+    let what = match period.configuration {
+        Some(configuration) => tcgui_shared::explain::explain(configuration),
+        None => "cleared".to_string(),
+    };
+    format!("{}  {}", when, what)
+}
+
+/// One line for a ping diagnostic's summary, e.g. "Ping 10.0.0.1: 4/5
+/// received (20% loss), rtt min/avg/max/mdev 20.1/20.8/21.9/0.8 ms".
+fn ping_summary(ping: &PingResult) -> String {
+    if let Some(error) = &ping.error {
+        return format!("Ping {} failed: {}", ping.target, error);
+    }
+    let mut line = format!(
+        "Ping {}: {}/{} received ({:.0}% loss)",
+        ping.target, ping.received, ping.transmitted, ping.packet_loss_percent
+    );
+    if let Some(rtt) = ping.rtt {
+        let mdev = rtt
+            .mdev_ms
+            .map_or_else(|| "-".to_string(), |mdev| format!("{:.1}", mdev));
+        line.push_str(&format!(
+            ", rtt min/avg/max/mdev {:.1}/{:.1}/{:.1}/{} ms",
+            rtt.min_ms, rtt.avg_ms, rtt.max_ms, mdev
+        ));
+    }
+    line
+}
+
+/// One line for a throughput probe, measured vs configured, e.g.
+/// "Throughput to 10.0.0.1:5001: 9512 kbit/s of 10000 kbit/s limit (95%)".
+/// A loopback probe is a baseline and is not compared with the limit.
+fn throughput_summary(throughput: &ThroughputResult) -> String {
+    let target = throughput.target.as_deref().unwrap_or("loopback");
+    if let Some(error) = &throughput.error {
+        return format!("Throughput to {} failed: {}", target, error);
+    }
+    let measured = throughput.throughput_kbps.map_or_else(
+        || "nothing".to_string(),
+        |kbps| format!("{:.0} kbit/s", kbps),
+    );
+    if throughput.target.is_none() {
+        return format!(
+            "Throughput over loopback: {} (baseline, not shaped by the interface)",
+            measured
+        );
+    }
+    match (throughput.configured_rate_kbps, throughput.rate_percent()) {
+        (Some(rate_kbps), Some(percent)) => format!(
+            "Throughput to {}: {} of {} kbit/s limit ({:.0}%)",
+            target, measured, rate_kbps, percent
+        ),
+        _ => format!("Throughput to {}: {} (no rate limit)", target, measured),
+    }
+}
+
 /// This is the refactored version of the original TcInterface that coordinates
 /// multiple feature-specific components while maintaining the same external API.
 #[derive(Clone)]
@@ -100,6 +199,20 @@ impl TcInterface {
                 );
                 Task::none()
             }
+            TcInterfaceMessage::LossModelChanged(model) => {
+                self.state.features.loss.config.model = model;
+                if self.state.features.loss.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::LossEcnToggled(ecn) => {
+                self.state.features.loss.config.ecn = ecn;
+                if self.state.features.loss.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
             TcInterfaceMessage::LossToggled(enabled) => {
                 if enabled {
                     self.state.features.loss.enable();
@@ -146,6 +259,13 @@ impl TcInterface {
                 }
                 Task::none()
             }
+            TcInterfaceMessage::DelayDistributionChanged(distribution) => {
+                self.state.features.delay.config.distribution = distribution;
+                if self.state.features.delay.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
             TcInterfaceMessage::DelayToggled(enabled) => {
                 if enabled {
                     self.state.features.delay.enable();
@@ -283,6 +403,55 @@ impl TcInterface {
                 }
                 Task::none()
             }
+            TcInterfaceMessage::RateLimitShaperChanged(shaper) => {
+                self.state.features.rate_limit.config.shaper = shaper;
+                if self.state.features.rate_limit.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::DirectionChanged(direction) => {
+                self.state.direction = direction;
+                self.state.applying = true;
+                Task::none()
+            }
+            // Slot messages
+            TcInterfaceMessage::SlotToggled(_) => {
+                if self.state.features.slot.enabled {
+                    self.state.features.slot.disable();
+                } else {
+                    self.state.features.slot.enable();
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::SlotMinDelayChanged(v) => {
+                self.state.features.slot.config.min_delay_ms = v;
+                if self.state.features.slot.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::SlotMaxDelayChanged(v) => {
+                self.state.features.slot.config.max_delay_ms = v;
+                if self.state.features.slot.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::SlotPacketsChanged(v) => {
+                self.state.features.slot.config.packets = (v > 0).then_some(v);
+                if self.state.features.slot.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::SlotBytesChanged(v) => {
+                self.state.features.slot.config.bytes = (v > 0).then_some(v);
+                if self.state.features.slot.enabled {
+                    self.state.applying = true;
+                }
+                Task::none()
+            }
             // Preset messages
             TcInterfaceMessage::PresetSelected(preset) => {
                 tracing::debug!("Preset selected: {:?}", preset);
@@ -297,6 +466,11 @@ impl TcInterface {
                 self.preset_manager.toggle_dropdown();
                 Task::none()
             }
+            TcInterfaceMessage::PresetNameChanged(name) => {
+                self.preset_manager.save_name = name;
+                Task::none()
+            }
+            TcInterfaceMessage::SaveAsPreset(_) => Task::none(),
             TcInterfaceMessage::ClearAllFeatures => {
                 tracing::debug!("Clearing all features");
                 self.preset_manager.clear_all_features(&mut self.state);
@@ -308,6 +482,9 @@ impl TcInterface {
                 self.state.chart_expanded = !self.state.chart_expanded;
                 Task::none()
             }
+            TcInterfaceMessage::ExportChart | TcInterfaceMessage::ExportHistory(_) => Task::none(),
+            TcInterfaceMessage::AccentColorSelected(_)
+            | TcInterfaceMessage::DisplayLabelChanged(_) => Task::none(),
             TcInterfaceMessage::StartDiagnostics => {
                 self.state.diagnostics_running = true;
                 self.state.diagnostics_result = None;
@@ -315,127 +492,886 @@ impl TcInterface {
                     .add_status_message("Running diagnostics...".to_string(), false);
                 Task::none()
             }
+            TcInterfaceMessage::PingTargetChanged(input) => {
+                self.state.ping_target_input = input;
+                self.state.ping_error = None;
+                Task::none()
+            }
+            TcInterfaceMessage::StartPing => {
+                match self.requested_ping() {
+                    Ok(_) => {
+                        let message = format!("Pinging {}...", self.state.ping_target_input.trim());
+                        self.state.ping_error = None;
+                        self.state.diagnostics_running = true;
+                        self.state.add_status_message(message, false);
+                    }
+                    Err(e) => self.state.ping_error = Some(e),
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::ProbeTargetChanged(input) => {
+                self.state.probe_target_input = input;
+                self.state.probe_error = None;
+                Task::none()
+            }
+            TcInterfaceMessage::StartThroughputProbe => {
+                match self.requested_probe() {
+                    Ok(_) => {
+                        let target = self.state.probe_target_input.trim();
+                        let message = if target.is_empty() {
+                            "Measuring loopback throughput...".to_string()
+                        } else {
+                            format!("Measuring throughput to {}...", target)
+                        };
+                        self.state.probe_error = None;
+                        self.state.diagnostics_running = true;
+                        self.state.add_status_message(message, false);
+                    }
+                    Err(e) => self.state.probe_error = Some(e),
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::ReadKernelTc => {
+                self.state.diagnostics_running = true;
+                self.state
+                    .add_status_message("Reading TC from the kernel...".to_string(), false);
+                Task::none()
+            }
+            TcInterfaceMessage::SyncFromKernel => {
+                if let Some(reconciled) = self
+                    .state
+                    .diagnostics_result
+                    .as_ref()
+                    .and_then(|result| result.results.reconciled.clone())
+                {
+                    self.sync_from_kernel(&reconciled);
+                }
+                Task::none()
+            }
             TcInterfaceMessage::DiagnosticsComplete(response) => {
                 self.state.diagnostics_running = false;
-                self.state.diagnostics_result = Some(response.clone());
+                // Offer the target the diagnostics tested for the next ping
+                if self.state.ping_target_input.is_empty()
+                    && let Some(connectivity) = &response.results.connectivity
+                {
+                    self.state.ping_target_input = connectivity.target.clone();
+                }
                 self.state
                     .add_status_message(format!("Diagnostics: {}", response.message), false);
+                self.state.diagnostics_result = Some(*response);
                 Task::none()
             }
             TcInterfaceMessage::DismissDiagnostics => {
                 self.state.diagnostics_result = None;
                 Task::none()
             }
+            TcInterfaceMessage::ToggleDiagnosticsHistory => {
+                self.state.diagnostics_history_expanded = !self.state.diagnostics_history_expanded;
+                Task::none()
+            }
+            TcInterfaceMessage::ClearDiagnosticsHistory => {
+                self.state.diagnostics_history_expanded = false;
+                self.state
+                    .add_status_message("Cleared diagnostics history".to_string(), false);
+                Task::none()
+            }
+            TcInterfaceMessage::ShowQdiscTree => {
+                self.state.qdisc_tree_loading = true;
+                Task::none()
+            }
+            TcInterfaceMessage::QdiscTreeLoaded(result) => {
+                self.state.qdisc_tree_loading = false;
+                if let Err(e) = &result {
+                    self.state
+                        .add_status_message(format!("Qdisc tree unavailable: {}", e), false);
+                }
+                self.state.qdisc_tree = Some(result);
+                Task::none()
+            }
+            TcInterfaceMessage::DismissQdiscTree => {
+                self.state.qdisc_tree = None;
+                Task::none()
+            }
+            TcInterfaceMessage::CompareRateLimiters => {
+                self.state.rate_comparison_running = true;
+                Task::none()
+            }
+            TcInterfaceMessage::RateComparisonLoaded(result) => {
+                self.state.rate_comparison_running = false;
+                match &result {
+                    Err(e) => self
+                        .state
+                        .add_status_message(format!("Rate comparison failed: {}", e), false),
+                    Ok(response) if !response.restored => self.state.add_status_message(
+                        "Rate comparison: the original configuration could not be confirmed"
+                            .to_string(),
+                        false,
+                    ),
+                    Ok(_) => {}
+                }
+                self.state.rate_comparison = Some(result);
+                Task::none()
+            }
+            TcInterfaceMessage::DismissRateComparison => {
+                self.state.rate_comparison = None;
+                Task::none()
+            }
+            TcInterfaceMessage::ShowConfigHistory => {
+                self.state.config_history_loading = true;
+                Task::none()
+            }
+            TcInterfaceMessage::ConfigHistoryLoaded(result) => {
+                self.state.config_history_loading = false;
+                if let Err(e) = &result {
+                    self.state.add_status_message(
+                        format!("Configuration history unavailable: {}", e),
+                        false,
+                    );
+                }
+                self.state.config_history = Some(result);
+                Task::none()
+            }
+            TcInterfaceMessage::DismissConfigHistory => {
+                self.state.config_history = None;
+                Task::none()
+            }
+            TcInterfaceMessage::ConfirmManagementImpairment => {
+                self.state.management_confirmed = true;
+                self.state.management_confirmation_pending = false;
+                self.state.add_status_message(
+                    "Impairing the management interface confirmed".to_string(),
+                    false,
+                );
+                Task::none()
+            }
+            TcInterfaceMessage::CancelManagementImpairment => {
+                self.state.management_confirmation_pending = false;
+                Task::none()
+            }
+            TcInterfaceMessage::AdoptBackendState => {
+                if let Some(config) = self.state.backend_config.clone() {
+                    self.adopt_configuration(&config);
+                    self.state
+                        .add_status_message("Adopted backend state".to_string(), false);
+                }
+                self.state.sync_differences.clear();
+                Task::none()
+            }
+            TcInterfaceMessage::LayerNameChanged(name) => {
+                self.state.layer_name_input = name;
+                Task::none()
+            }
+            TcInterfaceMessage::SaveLayer => {
+                let name = match self.state.layer_name_input.trim() {
+                    "" => format!("Layer {}", self.state.layers.len() + 1),
+                    name => name.to_string(),
+                };
+                let config = self.state.features.to_config();
+                match self.state.layers.iter_mut().find(|l| l.name == name) {
+                    Some(existing) => {
+                        existing.config = config;
+                        existing.enabled = true;
+                    }
+                    None => self.state.layers.push(ConfigLayer {
+                        name,
+                        enabled: true,
+                        config,
+                    }),
+                }
+                self.state.layer_name_input.clear();
+                self.apply_layers();
+                Task::none()
+            }
+            TcInterfaceMessage::LayerToggled(index, enabled) => {
+                if let Some(layer) = self.state.layers.get_mut(index) {
+                    layer.enabled = enabled;
+                    self.apply_layers();
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::RemoveLayer(index) => {
+                if index < self.state.layers.len() {
+                    self.state.layers.remove(index);
+                    self.apply_layers();
+                }
+                Task::none()
+            }
+            // Recording is tracked by the scenario manager, which sets the flag
+            TcInterfaceMessage::ToggleRecording => Task::none(),
+            TcInterfaceMessage::ApplyToVethPair => {
+                if let Some((namespace, peer)) = &self.state.veth_peer {
+                    self.state.add_status_message(
+                        format!("Applying to both ends (peer {}/{})", namespace, peer),
+                        false,
+                    );
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::MtuInputChanged(input) => {
+                self.state.mtu_input = input;
+                self.state.mtu_error = None;
+                Task::none()
+            }
+            TcInterfaceMessage::SetMtu => {
+                match self.requested_mtu() {
+                    Ok(mtu) => {
+                        self.state.mtu_error = None;
+                        self.state
+                            .add_status_message(format!("Setting MTU to {}", mtu), true);
+                    }
+                    Err(e) => self.state.mtu_error = Some(e),
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::AddressInputChanged(input) => {
+                self.state.address_input = input;
+                self.state.address_error = None;
+                Task::none()
+            }
+            TcInterfaceMessage::AddAddress => {
+                match self.requested_address() {
+                    Ok(cidr) => {
+                        self.state.address_error = None;
+                        self.state
+                            .add_status_message(format!("Adding address {}", cidr), true);
+                    }
+                    Err(e) => self.state.address_error = Some(e),
+                }
+                Task::none()
+            }
+            TcInterfaceMessage::RemoveAddress(cidr) => {
+                self.state
+                    .add_status_message(format!("Removing address {}", cidr), true);
+                Task::none()
+            }
+            TcInterfaceMessage::ShorthandChanged(input) => {
+                self.state.shorthand_input = input;
+                self.state.shorthand_error = None;
+                Task::none()
+            }
+            TcInterfaceMessage::ApplyShorthand => {
+                match parse_shorthand(&self.state.shorthand_input) {
+                    Ok(config) => {
+                        PresetManagerComponent::apply_config(&config, &mut self.state);
+                        self.state.current_preset_id = None;
+                        self.state.shorthand_error = None;
+                        self.state.add_status_message(
+                            format!("Applying: {}", self.state.shorthand_input.trim()),
+                            false,
+                        );
+                    }
+                    Err(e) => self.state.shorthand_error = Some(e.to_string()),
+                }
+                Task::none()
+            }
         }
     }
 
-    /// Render the complete interface view
-    pub fn view<'a>(
-        &'a self,
-        preset_list: &'a PresetList,
-        theme: &'a Theme,
-        zoom: f32,
-        bandwidth_history: Option<&'a BandwidthHistory>,
-    ) -> Element<'a, TcInterfaceMessage> {
-        let main_row = self.render_main_row(preset_list, theme, zoom);
-        let expandable_rows = self.render_expandable_features(theme, zoom);
-        let diagnostics_panel = self.render_diagnostics_panel(theme, zoom);
-
-        // Build content column with optional chart and diagnostics
-        let content = if self.state.chart_expanded {
-            let chart_height = scaled(80, zoom);
-            let dark_mode = theme.is_dark();
-            let chart_element = bandwidth_chart_view(bandwidth_history, chart_height, dark_mode);
+    /// Replace the settings with the merge of the enabled layers
+    fn apply_layers(&mut self) {
+        let merged = merge_layers(&self.state.layers);
+        PresetManagerComponent::apply_config(&merged, &mut self.state);
+        self.state.current_preset_id = None;
 
-            column![main_row, expandable_rows, diagnostics_panel, chart_element]
-                .spacing(scaled_spacing(4, zoom))
+        let active: Vec<&str> = self
+            .state
+            .layers
+            .iter()
+            .filter(|l| l.enabled)
+            .map(|l| l.name.as_str())
+            .collect();
+        let message = if active.is_empty() {
+            "Layers: none active".to_string()
         } else {
-            column![main_row, expandable_rows, diagnostics_panel].spacing(scaled_spacing(4, zoom))
+            format!("Layers: {}", active.join(" + "))
         };
+        self.state.add_status_message(message, false);
+    }
 
-        // Use static background based on TC state
-        let bg_color = if self.state.has_tc_qdisc() {
-            let tc_active = theme.colors.tc_active;
-            Color::from_rgba(tc_active.r, tc_active.g, tc_active.b, 0.1)
-        } else {
-            Color::TRANSPARENT
-        };
+    /// Named configuration layers of this interface
+    pub fn layers(&self) -> &[ConfigLayer] {
+        &self.state.layers
+    }
 
-        container(content)
-            .padding(scaled_spacing(8, zoom))
-            .style(move |_| iced::widget::container::Style {
-                background: Some(Background::Color(bg_color)),
-                border: iced::Border {
-                    radius: 8.0.into(),
-                    width: 0.0,
-                    color: Color::TRANSPARENT,
-                },
-                ..Default::default()
-            })
-            .into()
+    /// Namespace and name of the other end of this veth, if known
+    pub fn veth_peer(&self) -> Option<(&str, &str)> {
+        self.state
+            .veth_peer
+            .as_ref()
+            .map(|(namespace, name)| (namespace.as_str(), name.as_str()))
     }
 
-    /// Render the main interface row with core controls
-    fn render_main_row<'a>(
-        &'a self,
-        preset_list: &'a PresetList,
-        theme: &'a Theme,
-        zoom: f32,
-    ) -> Element<'a, TcInterfaceMessage> {
-        use iced::Length;
-        use iced::widget::container;
+    /// Record the other end of this veth (resolved by the backend manager)
+    pub fn set_veth_peer(&mut self, peer: Option<(String, String)>) {
+        self.state.veth_peer = peer;
+    }
 
-        // Colors from theme
-        let text_primary = theme.colors.text_primary;
+    /// Significant mismatches with the veth peer, if any
+    pub fn veth_asymmetry(&self) -> &[String] {
+        &self.state.veth_asymmetry
+    }
 
-        // Interface name and icon
-        let interface_icon = if self.state.is_up() {
-            if self.state.has_tc_qdisc() {
-                Icon::Wrench
-            } else {
-                Icon::Radio
-            }
-        } else {
-            Icon::Circle
-        };
+    /// Record the mismatches with the veth peer (computed by the backend
+    /// manager); an empty list clears the warning
+    pub fn set_veth_asymmetry(&mut self, warnings: Vec<String>) {
+        self.state.veth_asymmetry = warnings;
+    }
 
-        // Carrier/link status indicator
-        let carrier_color = if self.state.has_carrier() {
-            theme.colors.success // Green when carrier present
-        } else {
-            theme.colors.text_muted // Dimmed when no carrier
-        };
-        let carrier_icon = Icon::Link.svg_sized_colored(scaled(10, zoom), carrier_color);
+    /// Mark whether manual changes on this interface are being recorded
+    pub fn set_recording(&mut self, recording: bool) {
+        self.state.recording = recording;
+    }
 
-        let name_row = row![
-            interface_icon.svg_sized_colored(scaled(14, zoom), text_primary),
-            text(format!(" {}", self.state.name))
-                .size(scaled(14, zoom))
-                .style(move |_| text::Style {
-                    color: Some(text_primary),
-                }),
-            carrier_icon,
-        ]
-        .spacing(scaled_spacing(4, zoom))
-        .align_y(iced::Alignment::Center);
+    /// The netem settings currently shown for this interface
+    pub fn netem_config(&self) -> TcNetemConfig {
+        self.state.features.to_config()
+    }
 
-        // Show the root qdisc kind + the interface's IP addresses on hover (kept
-        // out of the fixed-width row so the table layout is unaffected).
-        let mut tip_lines: Vec<String> = Vec::new();
-        if let Some(mbps) = self.state.link_speed_mbps {
-            let speed = if mbps >= 1000 && mbps % 1000 == 0 {
-                format!("link: {} Gbit/s", mbps / 1000)
-            } else if mbps >= 1000 {
-                format!("link: {:.1} Gbit/s", mbps as f64 / 1000.0)
-            } else {
-                format!("link: {mbps} Mbit/s")
-            };
-            tip_lines.push(speed);
+    /// The current configuration as a custom preset named `name`, or
+    /// `None` (with a status message) if it can't be saved
+    pub fn save_as_preset(&mut self, name: &str) -> Option<CustomPreset> {
+        match PresetManagerComponent::save_custom_preset(name, &self.netem_config()) {
+            Ok(preset) => {
+                self.preset_manager.save_name.clear();
+                self.state.current_preset_id = Some(preset.id.clone());
+                Some(preset)
+            }
+            Err(e) => {
+                self.state
+                    .add_status_message(format!("Cannot save preset: {}", e), false);
+                None
+            }
         }
-        if let Some(kind) = &self.state.qdisc_kind {
-            tip_lines.push(format!("qdisc: {kind}"));
+    }
+
+    /// Record which features the backend can't apply (from its capabilities)
+    pub fn set_unsupported_features(&mut self, unsupported: Vec<(TcFeature, String)>) {
+        self.state.unsupported_features = unsupported;
+    }
+
+    /// Record the feature the last apply was rejected for (`None` clears it)
+    pub fn set_rejected_feature(&mut self, feature: Option<TcFeature>) {
+        self.state.rejected_feature = feature;
+    }
+
+    /// Feature the last failed apply was rejected for
+    pub fn rejected_feature(&self) -> Option<TcFeature> {
+        self.state.rejected_feature
+    }
+
+    /// Mark the features `applied` agrees with as applied. A feature changed
+    /// again after the request went out disagrees, so it stays pending.
+    pub fn confirm_applied(&mut self, applied: &TcConfiguration) {
+        let differences = self.current_configuration().diff(applied);
+        for feature in TcFeature::ALL {
+            if !differences.iter().any(|d| d.feature() == feature) {
+                self.state.features.mark_applied(feature);
+            }
         }
-        // Warn when a configured rate cap can't actually take effect because it
+    }
+
+    /// Whether `feature` is shown with a value that isn't live yet
+    pub fn feature_pending(&self, feature: TcFeature) -> bool {
+        self.state.features.has_pending_changes(feature)
+    }
+
+    /// Record why the last apply attempt was not sent (`None` clears it)
+    pub fn set_validation_error(&mut self, error: Option<(TcFeature, TcValidationError)>) {
+        self.state.validation_error = error;
+    }
+
+    /// Why the backend can't apply `feature`, if it can't
+    pub fn unsupported_reason(&self, feature: TcFeature) -> Option<&str> {
+        self.state
+            .unsupported_features
+            .iter()
+            .find(|(f, _)| *f == feature)
+            .map(|(_, reason)| reason.as_str())
+    }
+
+    /// Whether the toggle for `feature` can be used: it must be supported, or
+    /// already on (so it can still be switched off)
+    fn feature_toggleable(&self, feature: TcFeature, enabled: bool) -> bool {
+        enabled || self.unsupported_reason(feature).is_none()
+    }
+
+    /// Tooltip for a feature toggle, noting when the backend can't apply it
+    fn feature_tooltip(&self, feature: TcFeature, description: &str) -> String {
+        match self.unsupported_reason(feature) {
+            Some(reason) => format!("{description}\nNot supported by this backend: {reason}"),
+            None => description.to_string(),
+        }
+    }
+
+    /// Whether the last shorthand entry failed to parse (and so must not be
+    /// sent to the backend)
+    pub fn shorthand_rejected(&self) -> bool {
+        self.state.shorthand_error.is_some()
+    }
+
+    /// The MTU typed in the MTU entry field, if it is a valid MTU
+    pub fn requested_mtu(&self) -> Result<u32, String> {
+        let mtu = self
+            .state
+            .mtu_input
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| "MTU must be a number of bytes".to_string())?;
+        tcgui_shared::validation::validate_mtu(mtu)?;
+        Ok(mtu)
+    }
+
+    /// The ping diagnostic for the host typed in the diagnostics panel, if
+    /// it is one that can be pinged
+    pub fn requested_ping(&self) -> Result<DiagnosticsTest, String> {
+        let target = self.state.ping_target_input.trim();
+        tcgui_shared::validation::validate_ping_target(target)?;
+        Ok(DiagnosticsTest::Ping {
+            target: target.to_string(),
+            count: DEFAULT_PING_COUNT,
+        })
+    }
+
+    /// The throughput probe for the sink typed in the diagnostics panel, or
+    /// over loopback when none is typed
+    pub fn requested_probe(&self) -> Result<DiagnosticsTest, String> {
+        let target = self.state.probe_target_input.trim();
+        let target = (!target.is_empty()).then(|| target.to_string());
+        if let Some(target) = &target {
+            tcgui_shared::validation::validate_probe_target(target)?;
+        }
+        Ok(DiagnosticsTest::Throughput {
+            target,
+            duration_ms: DEFAULT_PROBE_DURATION_MS,
+        })
+    }
+
+    /// The address typed in the address entry field, as "ip/prefix", if it
+    /// is one that can be assigned
+    pub fn requested_address(&self) -> Result<String, String> {
+        let (address, prefix) = tcgui_shared::validation::parse_cidr(&self.state.address_input)?;
+        Ok(format!("{}/{}", address, prefix))
+    }
+
+    /// Record the addresses the backend reported after adding or removing one
+    pub fn set_addresses(&mut self, addresses: Vec<String>) {
+        self.state.addresses = addresses;
+        self.state.address_input.clear();
+        self.state
+            .add_status_message("Addresses updated".to_string(), false);
+    }
+
+    /// Record the MTU the backend reported after a set or read
+    pub fn set_mtu(&mut self, mtu: u32) {
+        self.state.mtu = Some(mtu);
+        self.state.mtu_input.clear();
+        self.state
+            .add_status_message(format!("MTU is {}", mtu), false);
+    }
+
+    /// The configuration the UI state describes, built with the same rules
+    /// used when sending it to the backend (a feature counts only when it is
+    /// enabled with a non-zero value).
+    pub fn current_configuration(&self) -> TcConfiguration {
+        let f = &self.state.features;
+        let positive = |enabled: bool, v: f32| (enabled && v > 0.0).then_some(v);
+        let reorder = positive(f.reorder.enabled, f.reorder.config.percentage);
+
+        TcConfiguration {
+            loss: positive(f.loss.enabled, f.loss.config.percentage).unwrap_or(0.0),
+            correlation: positive(true, f.loss.config.correlation),
+            loss_gemodel: f.loss.config.model.gemodel().filter(|_| f.loss.enabled),
+            loss_ecn: f.loss.enabled && f.loss.config.ecn,
+            delay_ms: positive(f.delay.enabled, f.delay.config.base_ms),
+            delay_jitter_ms: positive(f.delay.enabled, f.delay.config.jitter_ms),
+            delay_correlation: positive(f.delay.enabled, f.delay.config.correlation),
+            delay_distribution: positive(f.delay.enabled, f.delay.config.jitter_ms)
+                .map(|_| f.delay.config.distribution)
+                .filter(|distribution| !distribution.is_uniform()),
+            duplicate_percent: positive(f.duplicate.enabled, f.duplicate.config.percentage),
+            duplicate_correlation: positive(f.duplicate.enabled, f.duplicate.config.correlation),
+            reorder_percent: reorder,
+            reorder_correlation: positive(f.reorder.enabled, f.reorder.config.correlation),
+            reorder_gap: reorder.map(|_| f.reorder.config.gap),
+            corrupt_percent: positive(f.corrupt.enabled, f.corrupt.config.percentage),
+            corrupt_correlation: positive(f.corrupt.enabled, f.corrupt.config.correlation),
+            rate_limit_kbps: (f.rate_limit.enabled && f.rate_limit.config.rate_kbps > 0)
+                .then_some(f.rate_limit.config.rate_kbps),
+            rate_limit_shaper: if f.rate_limit.enabled {
+                f.rate_limit.config.shaper
+            } else {
+                RateLimitMode::Netem
+            },
+            slot: f
+                .slot
+                .enabled
+                .then(|| TcSlotConfig {
+                    enabled: true,
+                    ..f.slot.config.clone()
+                })
+                .filter(TcSlotConfig::is_active),
+            seed: self.state.seed,
+            command: String::new(),
+        }
+    }
+
+    /// Rate (kbps) a netem vs tbf comparison runs at: the rate limit
+    /// control's value, whether or not the rate limit is enabled.
+    pub fn comparison_rate_kbps(&self) -> u32 {
+        self.state.features.rate_limit.config.rate_kbps.max(1)
+    }
+
+    /// Rate limit (kbps) in effect on the interface: the one the backend
+    /// reports, or the configured one while the backend's state is unknown.
+    pub fn applied_rate_limit_kbps(&self) -> Option<u32> {
+        self.applied_configuration()?
+            .rate_limit_kbps
+            .filter(|kbps| *kbps > 0)
+    }
+
+    /// Configuration in effect on the interface: the one the backend reports,
+    /// or the configured one while the backend's state is unknown. `None`
+    /// when no qdisc is known to be installed.
+    pub fn applied_configuration(&self) -> Option<TcConfiguration> {
+        match &self.state.backend_config {
+            Some(config) => Some(config.clone()),
+            None if self.state.has_tc_qdisc() => Some(self.current_configuration()),
+            None => None,
+        }
+    }
+
+    /// Record the configuration reported by the backend and compare it with
+    /// the UI state. `None` means the backend state is unknown, which clears
+    /// any previous mismatch.
+    pub fn check_backend_sync(&mut self, backend_config: Option<TcConfiguration>) {
+        self.state.sync_differences = match &backend_config {
+            Some(config) => self.current_configuration().diff(config),
+            None => Vec::new(),
+        };
+        // What the backend reports is live, whoever applied it
+        if let Some(config) = &backend_config {
+            self.confirm_applied(config);
+        }
+        if !self.state.sync_differences.is_empty() {
+            tracing::warn!(
+                "Interface {} is out of sync with the backend: {}",
+                self.state.name,
+                self.state
+                    .sync_differences
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        self.state.backend_config = backend_config;
+    }
+
+    /// Parameters on which the UI state disagrees with the backend
+    pub fn sync_differences(&self) -> &[TcConfigDifference] {
+        &self.state.sync_differences
+    }
+
+    /// Take over TC detected on the backend, e.g. on first connect: the
+    /// feature states are set to `config` in one pass and count as applied,
+    /// so nothing is re-applied and no intermediate state is shown.
+    pub fn adopt_detected_configuration(&mut self, config: &TcConfiguration) {
+        self.set_features(config);
+        self.state.features.mark_all_applied();
+    }
+
+    /// Take over the impairment read back from the kernel, replacing the UI
+    /// state; no configuration means the kernel impairs nothing. It is live
+    /// already, so nothing is re-applied.
+    pub fn sync_from_kernel(&mut self, reconciled: &TcReconciliation) {
+        let config = reconciled.configuration.clone().unwrap_or_default();
+        self.adopt_configuration(&config);
+        self.state.features.mark_all_applied();
+        self.state.sync_differences.clear();
+        self.state
+            .add_status_message("Synced from kernel".to_string(), false);
+    }
+
+    /// Parameters on which the UI state disagrees with a kernel readback
+    pub fn kernel_differences(&self, reconciled: &TcReconciliation) -> Vec<TcConfigDifference> {
+        let kernel = reconciled.configuration.clone().unwrap_or_default();
+        self.current_configuration().diff(&kernel)
+    }
+
+    /// Overwrite the feature states with `config`, dropping the selected
+    /// preset they no longer match.
+    fn adopt_configuration(&mut self, config: &TcConfiguration) {
+        self.set_features(config);
+        self.state.current_preset_id = None;
+    }
+
+    /// Overwrite the feature states with `config` exactly. Slider values of
+    /// features that are off in `config` are kept so re-enabling them
+    /// restores the previous setting.
+    fn set_features(&mut self, config: &TcConfiguration) {
+        fn active(value: Option<f32>) -> Option<f32> {
+            value.filter(|v| *v > 0.0)
+        }
+        let f = &mut self.state.features;
+
+        f.loss.enabled = config.loss > 0.0 || config.loss_gemodel.is_some();
+        if f.loss.enabled {
+            f.loss.config.percentage = config.loss;
+            f.loss.config.correlation = config.correlation.unwrap_or(0.0);
+            f.loss.config.model = config
+                .loss_gemodel
+                .map_or(LossModel::Random, LossModel::GilbertElliott);
+            f.loss.config.ecn = config.loss_ecn;
+        }
+
+        f.delay.enabled = active(config.delay_ms).is_some();
+        if let Some(delay_ms) = active(config.delay_ms) {
+            f.delay.config.base_ms = delay_ms;
+            f.delay.config.jitter_ms = config.delay_jitter_ms.unwrap_or(0.0);
+            f.delay.config.correlation = config.delay_correlation.unwrap_or(0.0);
+            f.delay.config.distribution = config.delay_distribution.unwrap_or_default();
+        }
+
+        f.duplicate.enabled = active(config.duplicate_percent).is_some();
+        if let Some(percent) = active(config.duplicate_percent) {
+            f.duplicate.config.percentage = percent;
+            f.duplicate.config.correlation = config.duplicate_correlation.unwrap_or(0.0);
+        }
+
+        f.reorder.enabled = active(config.reorder_percent).is_some();
+        if let Some(percent) = active(config.reorder_percent) {
+            f.reorder.config.percentage = percent;
+            f.reorder.config.correlation = config.reorder_correlation.unwrap_or(0.0);
+            if let Some(gap) = config.reorder_gap.filter(|g| *g > 0) {
+                f.reorder.config.gap = gap;
+            }
+        }
+
+        f.corrupt.enabled = active(config.corrupt_percent).is_some();
+        if let Some(percent) = active(config.corrupt_percent) {
+            f.corrupt.config.percentage = percent;
+            f.corrupt.config.correlation = config.corrupt_correlation.unwrap_or(0.0);
+        }
+
+        let rate = config.rate_limit_kbps.filter(|r| *r > 0);
+        f.rate_limit.enabled = rate.is_some();
+        if let Some(rate_kbps) = rate {
+            f.rate_limit.config.rate_kbps = rate_kbps;
+            f.rate_limit.config.shaper = config.rate_limit_shaper;
+        }
+
+        f.slot.enabled = config.slot.is_some();
+        if let Some(slot) = &config.slot {
+            f.slot.config = slot.clone();
+        }
+
+        self.state.seed = config.seed;
+    }
+
+    /// Render the complete interface view
+    #[allow(clippy::too_many_arguments)]
+    pub fn view<'a>(
+        &'a self,
+        preset_list: &'a PresetList,
+        custom_presets: &'a PresetList,
+        theme: &'a Theme,
+        zoom: f32,
+        bandwidth_history: Option<&'a BandwidthHistory>,
+        diagnostics_history: Option<&'a DiagnosticsHistory>,
+        style: Option<&'a InterfaceStyle>,
+    ) -> Element<'a, TcInterfaceMessage> {
+        let accent = style
+            .and_then(|s| s.color)
+            .map(|color| theme.accent_color(color));
+        let main_row =
+            self.render_main_row(preset_list, custom_presets, theme, zoom, style, accent);
+        let expandable_rows = self.render_expandable_features(theme, zoom);
+        let shorthand_entry = self.render_shorthand_entry(theme, zoom);
+        let layers_row = self.render_layers(theme, zoom);
+        let mtu_row = self.render_mtu_entry(theme, zoom);
+        let addresses_row = self.render_addresses(theme, zoom);
+        let command_preview = self.render_command_preview(theme, zoom);
+        let diagnostics_panel = self.render_diagnostics_panel(diagnostics_history, theme, zoom);
+        let history_panel = self.render_diagnostics_history(diagnostics_history, theme, zoom);
+        let qdisc_tree_panel = self.render_qdisc_tree_panel(theme, zoom);
+        let rate_comparison_panel = self.render_rate_comparison_panel(theme, zoom);
+        let config_history_panel = self.render_config_history_panel(theme, zoom);
+        let sync_badge = self.render_sync_badge(theme, zoom);
+        let management_badge = self.render_management_badge(theme, zoom);
+        let veth_badge = self.render_veth_asymmetry_badge(theme, zoom);
+
+        // Build content column with optional chart and diagnostics
+        let content = if self.state.chart_expanded {
+            let chart_height = scaled(80, zoom);
+            let dark_mode = theme.is_dark();
+            let chart_element = container(bandwidth_chart_view(
+                bandwidth_history,
+                chart_height,
+                dark_mode,
+                accent,
+            ))
+            .id(self.state.chart_id.clone());
+            let style_editor = self.render_style_editor(style, theme, zoom);
+
+            let mut content = column![
+                main_row,
+                management_badge,
+                sync_badge,
+                veth_badge,
+                expandable_rows,
+                shorthand_entry,
+                layers_row,
+                mtu_row,
+                addresses_row,
+                command_preview,
+                diagnostics_panel,
+                history_panel,
+                qdisc_tree_panel,
+                rate_comparison_panel,
+                config_history_panel,
+                style_editor,
+                chart_element
+            ]
+            .spacing(scaled_spacing(4, zoom));
+            if let Some(rate_kbps) = self.applied_rate_limit_kbps() {
+                content = content.push(efficiency_chart_view(
+                    bandwidth_history,
+                    rate_kbps,
+                    chart_height,
+                    dark_mode,
+                    accent,
+                ));
+            }
+            content
+        } else {
+            column![
+                main_row,
+                management_badge,
+                sync_badge,
+                veth_badge,
+                expandable_rows,
+                shorthand_entry,
+                layers_row,
+                mtu_row,
+                addresses_row,
+                command_preview,
+                diagnostics_panel,
+                history_panel,
+                qdisc_tree_panel,
+                rate_comparison_panel,
+                config_history_panel
+            ]
+            .spacing(scaled_spacing(4, zoom))
+        };
+
+        // Use static background based on TC state
+        let bg_color = if self.state.has_tc_qdisc() {
+            let tc_active = theme.colors.tc_active;
+            Color::from_rgba(tc_active.r, tc_active.g, tc_active.b, 0.1)
+        } else {
+            Color::TRANSPARENT
+        };
+
+        container(content)
+            .padding(scaled_spacing(8, zoom))
+            .style(move |_| iced::widget::container::Style {
+                background: Some(Background::Color(bg_color)),
+                border: iced::Border {
+                    radius: 8.0.into(),
+                    width: 0.0,
+                    color: Color::TRANSPARENT,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// Render the main interface row with core controls
+    fn render_main_row<'a>(
+        &'a self,
+        preset_list: &'a PresetList,
+        custom_presets: &'a PresetList,
+        theme: &'a Theme,
+        zoom: f32,
+        style: Option<&'a InterfaceStyle>,
+        accent: Option<Color>,
+    ) -> Element<'a, TcInterfaceMessage> {
+        use iced::Length;
+        use iced::widget::container;
+
+        // Colors from theme
+        let text_primary = theme.colors.text_primary;
+
+        // Interface name and icon
+        let interface_icon = if self.state.is_up() {
+            if self.state.has_tc_qdisc() {
+                Icon::Wrench
+            } else {
+                Icon::Radio
+            }
+        } else {
+            Icon::Circle
+        };
+
+        // Carrier/link status indicator
+        let carrier_color = if self.state.has_carrier() {
+            theme.colors.success // Green when carrier present
+        } else {
+            theme.colors.text_muted // Dimmed when no carrier
+        };
+        let carrier_icon = Icon::Link.svg_sized_colored(scaled(10, zoom), carrier_color);
+
+        // An assigned color and label replace the default name rendering
+        let name_color = accent.unwrap_or(text_primary);
+        let display_name = style.map_or(self.state.name.as_str(), |s| {
+            s.display_name(&self.state.name)
+        });
+        let mut name_row = row![
+            interface_icon.svg_sized_colored(scaled(14, zoom), name_color),
+            text(format!(" {}", display_name))
+                .size(scaled(14, zoom))
+                .style(move |_| text::Style {
+                    color: Some(name_color),
+                }),
+            carrier_icon,
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .align_y(iced::Alignment::Center);
+        if self.state.management {
+            name_row = name_row.push(
+                Icon::AlertTriangle.svg_sized_colored(scaled(12, zoom), theme.colors.warning),
+            );
+        }
+
+        // Show the root qdisc kind + the interface's IP addresses on hover (kept
+        // out of the fixed-width row so the table layout is unaffected).
+        let mut tip_lines: Vec<String> = Vec::new();
+        if let Some(description) = &self.state.description {
+            tip_lines.push(description.clone());
+        }
+        if self.state.management {
+            tip_lines.push("⚠ carries the backend's control connection".to_string());
+        }
+        if display_name != self.state.name {
+            tip_lines.push(format!("interface: {}", self.state.name));
+        }
+        if let Some(mbps) = self.state.link_speed_mbps {
+            let speed = if mbps >= 1000 && mbps % 1000 == 0 {
+                format!("link: {} Gbit/s", mbps / 1000)
+            } else if mbps >= 1000 {
+                format!("link: {:.1} Gbit/s", mbps as f64 / 1000.0)
+            } else {
+                format!("link: {mbps} Mbit/s")
+            };
+            tip_lines.push(speed);
+        }
+        if let Some(mtu) = self.state.mtu {
+            tip_lines.push(format!("MTU: {mtu}"));
+        }
+        if let Some(kind) = &self.state.qdisc_kind {
+            tip_lines.push(format!("qdisc: {kind}"));
+        }
+        // Warn when a configured rate cap can't actually take effect because it
         // exceeds the physical link speed.
         if self.state.features.rate_limit.enabled
             && rate_cap_exceeds_link(
@@ -456,12 +1392,30 @@ impl TcInterface {
         }
         tip_lines.extend(self.state.addresses.iter().cloned());
 
+        // The description goes under the name, cut to the column width; the
+        // tooltip has it in full.
+        let name_block: Element<'_, TcInterfaceMessage> = match &self.state.description {
+            Some(description) => {
+                let text_muted = theme.colors.text_muted;
+                column![
+                    name_row,
+                    text(shorten_description(description))
+                        .size(scaled(10, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(text_muted),
+                        }),
+                ]
+                .into()
+            }
+            None => name_row.into(),
+        };
+
         let interface_name: Element<'_, TcInterfaceMessage> = if tip_lines.is_empty() {
-            name_row.into()
+            name_block
         } else {
             let tooltip_style = theme.tooltip_style();
             tooltip(
-                name_row,
+                name_block,
                 text(tip_lines.join("\n")).size(scaled(11, zoom)),
                 tooltip::Position::Bottom,
             )
@@ -482,9 +1436,13 @@ impl TcInterface {
         .spacing(scaled_spacing(2, zoom));
 
         // Preset selector
-        let preset_selector =
-            self.preset_manager
-                .view(preset_list, &self.state.current_preset_id, theme, zoom);
+        let preset_selector = self.preset_manager.view(
+            preset_list,
+            custom_presets,
+            &self.state.current_preset_id,
+            theme,
+            zoom,
+        );
 
         // Feature toggles (compact checkboxes)
         let feature_toggles = self.render_feature_toggles(theme, zoom);
@@ -495,8 +1453,14 @@ impl TcInterface {
         // TC stats display (drops/packets when TC is active)
         let tc_stats_display = self.render_tc_stats_display(theme, zoom);
 
-        // Diagnose button
-        let diagnose_button = self.render_diagnose_button(theme, zoom);
+        // Diagnose, qdisc tree, rate comparison and history buttons
+        let diagnose_button = row![
+            self.render_diagnose_button(theme, zoom),
+            self.render_qdisc_tree_button(theme, zoom),
+            self.render_rate_comparison_button(theme, zoom),
+            self.render_config_history_button(theme, zoom)
+        ]
+        .spacing(scaled_spacing(2, zoom));
 
         // Status display
         let status_display = self.render_status_display(theme, zoom);
@@ -529,7 +1493,7 @@ impl TcInterface {
                 .width(Length::Fixed(120.0 * zoom))
                 .align_y(iced::alignment::Vertical::Center),
             container(diagnose_button)
-                .width(Length::Fixed(30.0 * zoom))
+                .width(Length::Fixed(108.0 * zoom))
                 .align_y(iced::alignment::Vertical::Center),
             container(status_display)
                 .width(Length::Fill)
@@ -546,24 +1510,43 @@ impl TcInterface {
         theme: &'a Theme,
         zoom: f32,
     ) -> Element<'a, TcInterfaceMessage> {
-        let text_color = theme.colors.text_primary;
         let tooltip_delay = Duration::from_millis(500);
         let tooltip_style = theme.tooltip_style();
+        // Labels of toggles whose state isn't applied yet are highlighted
+        let label_color = |feature| {
+            if self.feature_pending(feature) {
+                theme.colors.warning
+            } else {
+                theme.colors.text_primary
+            }
+        };
+        let loss_color = label_color(TcFeature::Loss);
+        let delay_color = label_color(TcFeature::Delay);
+        let duplicate_color = label_color(TcFeature::Duplicate);
+        let reorder_color = label_color(TcFeature::Reorder);
+        let corrupt_color = label_color(TcFeature::Corrupt);
+        let rate_limit_color = label_color(TcFeature::RateLimit);
+        let slot_color = label_color(TcFeature::Slot);
 
         row![
             // Loss: randomly drop packets
             tooltip(
                 row![
-                    checkbox(self.state.features.loss.enabled)
-                        .on_toggle(TcInterfaceMessage::LossToggled),
+                    checkbox(self.state.features.loss.enabled).on_toggle_maybe(
+                        self.feature_toggleable(TcFeature::Loss, self.state.features.loss.enabled)
+                            .then_some(TcInterfaceMessage::LossToggled)
+                    ),
                     text("LSS")
                         .size(scaled(12, zoom))
                         .style(move |_| text::Style {
-                            color: Some(text_color)
+                            color: Some(loss_color)
                         })
                 ]
                 .spacing(scaled_spacing(2, zoom)),
-                text("Packet Loss: randomly drop packets at a specified rate"),
+                text(self.feature_tooltip(
+                    TcFeature::Loss,
+                    "Packet Loss: randomly drop packets at a specified rate"
+                )),
                 tooltip::Position::Top
             )
             .delay(tooltip_delay)
@@ -571,16 +1554,26 @@ impl TcInterface {
             // Delay: add latency to packets
             tooltip(
                 row![
-                    checkbox(self.state.features.delay.enabled)
-                        .on_toggle(TcInterfaceMessage::DelayToggled),
+                    checkbox(self.state.features.delay.enabled).on_toggle_maybe(
+                        self.feature_toggleable(
+                            TcFeature::Delay,
+                            self.state.features.delay.enabled
+                        )
+                        .then_some(TcInterfaceMessage::DelayToggled)
+                    ),
                     text("DLY")
                         .size(scaled(12, zoom))
                         .style(move |_| text::Style {
-                            color: Some(text_color)
+                            color: Some(delay_color)
                         })
                 ]
                 .spacing(scaled_spacing(2, zoom)),
-                text("Delay: add latency with optional jitter"),
+                text(
+                    self.feature_tooltip(
+                        TcFeature::Delay,
+                        "Delay: add latency with optional jitter"
+                    )
+                ),
                 tooltip::Position::Top
             )
             .delay(tooltip_delay)
@@ -588,16 +1581,24 @@ impl TcInterface {
             // Duplicate: send duplicate packets
             tooltip(
                 row![
-                    checkbox(self.state.features.duplicate.enabled)
-                        .on_toggle(|_| TcInterfaceMessage::DuplicateToggled(())),
+                    checkbox(self.state.features.duplicate.enabled).on_toggle_maybe(
+                        self.feature_toggleable(
+                            TcFeature::Duplicate,
+                            self.state.features.duplicate.enabled
+                        )
+                        .then_some(|_| TcInterfaceMessage::DuplicateToggled(()))
+                    ),
                     text("DUP")
                         .size(scaled(12, zoom))
                         .style(move |_| text::Style {
-                            color: Some(text_color)
+                            color: Some(duplicate_color)
                         })
                 ]
                 .spacing(scaled_spacing(2, zoom)),
-                text("Duplicate: send duplicate copies of packets"),
+                text(self.feature_tooltip(
+                    TcFeature::Duplicate,
+                    "Duplicate: send duplicate copies of packets"
+                )),
                 tooltip::Position::Top
             )
             .delay(tooltip_delay)
@@ -605,16 +1606,26 @@ impl TcInterface {
             // Reorder: change packet order
             tooltip(
                 row![
-                    checkbox(self.state.features.reorder.enabled)
-                        .on_toggle(|_| TcInterfaceMessage::ReorderToggled(())),
+                    checkbox(self.state.features.reorder.enabled).on_toggle_maybe(
+                        self.feature_toggleable(
+                            TcFeature::Reorder,
+                            self.state.features.reorder.enabled
+                        )
+                        .then_some(|_| TcInterfaceMessage::ReorderToggled(()))
+                    ),
                     text("RO")
                         .size(scaled(12, zoom))
                         .style(move |_| text::Style {
-                            color: Some(text_color)
+                            color: Some(reorder_color)
                         })
                 ]
                 .spacing(scaled_spacing(2, zoom)),
-                text("Reorder: change the order of packets"),
+                text(
+                    self.feature_tooltip(
+                        TcFeature::Reorder,
+                        "Reorder: change the order of packets"
+                    )
+                ),
                 tooltip::Position::Top
             )
             .delay(tooltip_delay)
@@ -622,16 +1633,24 @@ impl TcInterface {
             // Corrupt: introduce bit errors
             tooltip(
                 row![
-                    checkbox(self.state.features.corrupt.enabled)
-                        .on_toggle(|_| TcInterfaceMessage::CorruptToggled(())),
+                    checkbox(self.state.features.corrupt.enabled).on_toggle_maybe(
+                        self.feature_toggleable(
+                            TcFeature::Corrupt,
+                            self.state.features.corrupt.enabled
+                        )
+                        .then_some(|_| TcInterfaceMessage::CorruptToggled(()))
+                    ),
                     text("CR")
                         .size(scaled(12, zoom))
                         .style(move |_| text::Style {
-                            color: Some(text_color)
+                            color: Some(corrupt_color)
                         })
                 ]
                 .spacing(scaled_spacing(2, zoom)),
-                text("Corrupt: introduce random bit errors in packets"),
+                text(self.feature_tooltip(
+                    TcFeature::Corrupt,
+                    "Corrupt: introduce random bit errors in packets"
+                )),
                 tooltip::Position::Top
             )
             .delay(tooltip_delay)
@@ -639,16 +1658,45 @@ impl TcInterface {
             // Rate Limit: cap bandwidth
             tooltip(
                 row![
-                    checkbox(self.state.features.rate_limit.enabled)
-                        .on_toggle(|_| TcInterfaceMessage::RateLimitToggled(())),
+                    checkbox(self.state.features.rate_limit.enabled).on_toggle_maybe(
+                        self.feature_toggleable(
+                            TcFeature::RateLimit,
+                            self.state.features.rate_limit.enabled
+                        )
+                        .then_some(|_| TcInterfaceMessage::RateLimitToggled(()))
+                    ),
                     text("RL")
                         .size(scaled(12, zoom))
                         .style(move |_| text::Style {
-                            color: Some(text_color)
+                            color: Some(rate_limit_color)
+                        })
+                ]
+                .spacing(scaled_spacing(2, zoom)),
+                text(
+                    self.feature_tooltip(TcFeature::RateLimit, "Rate Limit: cap maximum bandwidth")
+                ),
+                tooltip::Position::Top
+            )
+            .delay(tooltip_delay)
+            .style(move |_| tooltip_style),
+            // Slot: deliver packets in bursts
+            tooltip(
+                row![
+                    checkbox(self.state.features.slot.enabled).on_toggle_maybe(
+                        self.feature_toggleable(TcFeature::Slot, self.state.features.slot.enabled)
+                            .then_some(|_| TcInterfaceMessage::SlotToggled(()))
+                    ),
+                    text("SL")
+                        .size(scaled(12, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(slot_color)
                         })
                 ]
                 .spacing(scaled_spacing(2, zoom)),
-                text("Rate Limit: cap maximum bandwidth"),
+                text(self.feature_tooltip(
+                    TcFeature::Slot,
+                    "Slot: hold packets back and deliver them in bursts, like Wi-Fi aggregation"
+                )),
                 tooltip::Position::Top
             )
             .delay(tooltip_delay)
@@ -685,6 +1733,97 @@ impl TcInterface {
             .into()
     }
 
+    /// Render the color swatches and label input shown above the chart
+    fn render_style_editor<'a>(
+        &'a self,
+        style: Option<&'a InterfaceStyle>,
+        theme: &'a Theme,
+        zoom: f32,
+    ) -> Element<'a, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let text_secondary = theme.colors.text_secondary;
+        let current = style.and_then(|s| s.color);
+        let swatch_size = scaled(12, zoom);
+
+        let mut swatches = row![
+            text("Color:")
+                .size(scaled(11, zoom))
+                .style(move |_| text::Style {
+                    color: Some(text_secondary)
+                }),
+            button(text("Default").size(scaled(10, zoom)))
+                .on_press(TcInterfaceMessage::AccentColorSelected(None))
+                .padding([scaled_spacing(1, zoom), scaled_spacing(4, zoom)])
+                .style(if current.is_none() {
+                    button::primary
+                } else {
+                    button::secondary
+                }),
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .align_y(iced::Alignment::Center);
+
+        for accent in AccentColor::all() {
+            let color = theme.accent_color(*accent);
+            let selected = current == Some(*accent);
+            let outline = theme.colors.text_primary;
+            let swatch = button(container(text("")).width(swatch_size).height(swatch_size))
+                .on_press(TcInterfaceMessage::AccentColorSelected(Some(*accent)))
+                .padding(0)
+                .style(move |_, _| button::Style {
+                    background: Some(Background::Color(color)),
+                    border: iced::Border {
+                        radius: 3.0.into(),
+                        width: if selected { 2.0 } else { 0.0 },
+                        color: outline,
+                    },
+                    ..button::Style::default()
+                });
+            swatches = swatches.push(
+                tooltip(swatch, text(accent.name()), tooltip::Position::Top)
+                    .style(move |_| theme.tooltip_style()),
+            );
+        }
+
+        let label = style.and_then(|s| s.label.as_deref()).unwrap_or("");
+        let label_input = text_input("Label (e.g. client-uplink)", label)
+            .on_input(TcInterfaceMessage::DisplayLabelChanged)
+            .size(scaled(11, zoom))
+            .width(scaled(180, zoom));
+
+        let export_button = tooltip(
+            button(text("Export PNG").size(scaled(10, zoom)))
+                .on_press(TcInterfaceMessage::ExportChart)
+                .padding([scaled_spacing(1, zoom), scaled_spacing(4, zoom)])
+                .style(button::secondary),
+            text("Save the bandwidth chart as an image"),
+            tooltip::Position::Top,
+        )
+        .style(move |_| theme.tooltip_style());
+        let history_button = |label: &'static str, format: HistoryExportFormat| {
+            button(text(label).size(scaled(10, zoom)))
+                .on_press(TcInterfaceMessage::ExportHistory(format))
+                .padding([scaled_spacing(1, zoom), scaled_spacing(4, zoom)])
+                .style(button::secondary)
+        };
+        let history_buttons = tooltip(
+            row![
+                history_button("CSV", HistoryExportFormat::Csv),
+                history_button("JSON", HistoryExportFormat::Json),
+            ]
+            .spacing(scaled_spacing(4, zoom)),
+            text("Save the retained bandwidth samples"),
+            tooltip::Position::Top,
+        )
+        .style(move |_| theme.tooltip_style());
+
+        row![swatches, label_input, export_button, history_buttons]
+            .spacing(scaled_spacing(12, zoom))
+            .align_y(iced::Alignment::Center)
+            .into()
+    }
+
     /// Format bytes per second with appropriate units
     fn format_bps(bps: u32) -> String {
         if bps >= 1_000_000_000 {
@@ -714,6 +1853,7 @@ impl TcInterface {
         let text_muted = theme.colors.text_muted;
         let error_color = theme.colors.error;
         let info_color = theme.colors.info;
+        let warning_color = theme.colors.warning;
 
         // Compact integer formatter (e.g. 12345 -> "12K").
         let compact = |n: u32| -> String {
@@ -766,10 +1906,31 @@ impl TcInterface {
                     );
             }
 
-            stats_row
-                .push(text(" ").size(scaled(10, zoom)))
-                .push(Icon::Zap.svg_sized_colored(scaled(10, zoom), info_color))
-                .push(
+            // Queue backlog against the netem limit. Hidden when the limit is
+            // unknown; switches to warning color when tail drops are imminent.
+            if let Some(limit) = queue_stats.limit {
+                let queue_color = if queue_stats.is_near_limit() {
+                    warning_color
+                } else {
+                    text_muted
+                };
+                stats_row = stats_row.push(
+                    text(format!(
+                        " Q{}/{}",
+                        compact(queue_stats.qlen),
+                        compact(limit)
+                    ))
+                    .size(scaled(10, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(queue_color),
+                    }),
+                );
+            }
+
+            stats_row
+                .push(text(" ").size(scaled(10, zoom)))
+                .push(Icon::Zap.svg_sized_colored(scaled(10, zoom), info_color))
+                .push(
                     text(rate_text)
                         .size(scaled(10, zoom))
                         .style(move |_| text::Style {
@@ -790,13 +1951,29 @@ impl TcInterface {
         }
     }
 
-    /// Render status indicator
+    /// Render status indicator, followed by the netem seed the backend
+    /// reports when one was set
     fn render_status_display<'a>(
         &'a self,
         theme: &'a Theme,
         zoom: f32,
     ) -> Element<'a, TcInterfaceMessage> {
-        self.status_display.view(theme, zoom)
+        let status = self.status_display.view(theme, zoom);
+        let Some(seed) = self.state.backend_config.as_ref().and_then(|c| c.seed) else {
+            return status;
+        };
+        let text_muted = theme.colors.text_muted;
+        row![
+            status,
+            text(format!("seed {}", seed))
+                .size(scaled(11, zoom))
+                .style(move |_| text::Style {
+                    color: Some(text_muted)
+                }),
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .align_y(iced::Alignment::Center)
+        .into()
     }
 
     /// Render diagnose button
@@ -822,42 +1999,605 @@ impl TcInterface {
             theme.colors.text_muted
         };
 
-        let icon_size = scaled(12, zoom);
-        let btn = button(Icon::Activity.svg_sized_colored(icon_size, icon_color))
-            .padding(scaled_spacing(4, zoom));
+        let icon_size = scaled(12, zoom);
+        let btn = button(Icon::Activity.svg_sized_colored(icon_size, icon_color))
+            .padding(scaled_spacing(4, zoom));
+
+        let btn = if self.state.diagnostics_running {
+            btn // Disabled while running
+        } else {
+            btn.on_press(TcInterfaceMessage::StartDiagnostics)
+        };
+
+        let tooltip_text = if self.state.diagnostics_running {
+            "Running diagnostics..."
+        } else if let Some(ref result) = self.state.diagnostics_result {
+            if result.success {
+                "Diagnostics passed - click to re-run"
+            } else {
+                "Diagnostics failed - click to re-run"
+            }
+        } else {
+            "Run network diagnostics"
+        };
+
+        let tooltip_style = theme.tooltip_style();
+        tooltip(
+            btn,
+            text(tooltip_text).size(scaled(11, zoom)),
+            tooltip::Position::Top,
+        )
+        .delay(Duration::from_millis(300))
+        .style(move |_| tooltip_style)
+        .into()
+    }
+
+    /// Render the button that fetches the qdisc/class/filter tree
+    fn render_qdisc_tree_button(
+        &self,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::{button, tooltip};
+        use std::time::Duration;
+
+        let icon_color = if self.state.qdisc_tree_loading {
+            theme.colors.warning
+        } else {
+            theme.colors.text_muted
+        };
+
+        let btn = button(Icon::Search.svg_sized_colored(scaled(12, zoom), icon_color))
+            .padding(scaled_spacing(4, zoom));
+        let (btn, tooltip_text) = if self.state.qdisc_tree_loading {
+            (btn, "Reading the qdisc tree...")
+        } else {
+            (
+                btn.on_press(TcInterfaceMessage::ShowQdiscTree),
+                "Show the qdisc/class/filter tree",
+            )
+        };
+
+        let tooltip_style = theme.tooltip_style();
+        tooltip(
+            btn,
+            text(tooltip_text).size(scaled(11, zoom)),
+            tooltip::Position::Top,
+        )
+        .delay(Duration::from_millis(300))
+        .style(move |_| tooltip_style)
+        .into()
+    }
+
+    /// Render the button that compares netem and tbf at the rate limit
+    /// control's rate
+    fn render_rate_comparison_button(
+        &self,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::{button, tooltip};
+        use std::time::Duration;
+
+        let icon_color = if self.state.rate_comparison_running {
+            theme.colors.warning
+        } else {
+            theme.colors.text_muted
+        };
+
+        let btn = button(Icon::BarChart3.svg_sized_colored(scaled(12, zoom), icon_color))
+            .padding(scaled_spacing(4, zoom));
+        let (btn, tooltip_text) = if self.state.rate_comparison_running {
+            (btn, "Comparing netem and tbf...".to_string())
+        } else {
+            (
+                btn.on_press(TcInterfaceMessage::CompareRateLimiters),
+                format!(
+                    "Compare netem and tbf at {} kbit/s (reshapes the interface for about \
+                     15 s, then restores it)",
+                    self.comparison_rate_kbps()
+                ),
+            )
+        };
+
+        let tooltip_style = theme.tooltip_style();
+        tooltip(
+            btn,
+            text(tooltip_text).size(scaled(11, zoom)),
+            tooltip::Position::Top,
+        )
+        .delay(Duration::from_millis(300))
+        .style(move |_| tooltip_style)
+        .into()
+    }
+
+    /// Render the button that fetches the backend's configuration history
+    fn render_config_history_button(
+        &self,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::{button, tooltip};
+        use std::time::Duration;
+
+        let icon_color = if self.state.config_history_loading {
+            theme.colors.warning
+        } else {
+            theme.colors.text_muted
+        };
+
+        let btn = button(Icon::Clock.svg_sized_colored(scaled(12, zoom), icon_color))
+            .padding(scaled_spacing(4, zoom));
+        let (btn, tooltip_text) = if self.state.config_history_loading {
+            (btn, "Reading the configuration history...")
+        } else {
+            (
+                btn.on_press(TcInterfaceMessage::ShowConfigHistory),
+                "Show the configurations the backend recently applied",
+            )
+        };
+
+        let tooltip_style = theme.tooltip_style();
+        tooltip(
+            btn,
+            text(tooltip_text).size(scaled(11, zoom)),
+            tooltip::Position::Top,
+        )
+        .delay(Duration::from_millis(300))
+        .style(move |_| tooltip_style)
+        .into()
+    }
+
+    /// Render the configurations the backend published for this interface,
+    /// newest first (shown once the history has been fetched)
+    fn render_config_history_panel(
+        &self,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::{Space, button};
+
+        let Some(ref result) = self.state.config_history else {
+            return column![].into();
+        };
+
+        let text_primary = theme.colors.text_primary;
+        let text_muted = theme.colors.text_muted;
+        let error_color = theme.colors.error;
+        let line = move |content: String, color: Color| -> Element<'_, TcInterfaceMessage> {
+            text(content)
+                .size(scaled(11, zoom))
+                .style(move |_| text::Style { color: Some(color) })
+                .into()
+        };
+
+        let dismiss_btn = button(Icon::X.svg_sized_colored(scaled(10, zoom), text_muted))
+            .on_press(TcInterfaceMessage::DismissConfigHistory)
+            .padding(scaled_spacing(2, zoom));
+
+        let mut lines = column![
+            row![
+                text("Configuration history (backend):")
+                    .size(scaled(11, zoom))
+                    .color(text_muted),
+                Space::new().width(iced::Length::Fill),
+                dismiss_btn,
+            ]
+            .spacing(scaled_spacing(4, zoom))
+            .align_y(iced::Alignment::Center),
+        ]
+        .spacing(scaled_spacing(2, zoom));
+
+        match result {
+            Err(e) => lines = lines.push(line(e.clone(), error_color)),
+            Ok(response) if response.entries.is_empty() => {
+                lines = lines.push(line(
+                    "Nothing applied since the backend started".to_string(),
+                    text_muted,
+                ));
+            }
+            Ok(response) => {
+                for period in response.periods().iter().rev() {
+                    let color = if period.configuration.is_some() {
+                        text_primary
+                    } else {
+                        text_muted
+                    };
+                    lines = lines.push(line(config_period_line(period), color));
+                }
+            }
+        }
+
+        container(lines)
+            .padding(scaled_spacing(6, zoom))
+            .style(move |_| iced::widget::container::Style {
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: text_muted,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// Render the netem and tbf measurements side by side, with what they
+    /// show (shown once a comparison has finished)
+    fn render_rate_comparison_panel(
+        &self,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::{Space, button};
+        use tcgui_shared::rate_comparison::ShaperMeasurement;
+
+        let Some(ref result) = self.state.rate_comparison else {
+            return column![].into();
+        };
+
+        let text_primary = theme.colors.text_primary;
+        let text_muted = theme.colors.text_muted;
+        let warning_color = theme.colors.warning;
+        let error_color = theme.colors.error;
+        let line = move |content: String, color: Color| -> Element<'_, TcInterfaceMessage> {
+            text(content)
+                .size(scaled(11, zoom))
+                .style(move |_| text::Style { color: Some(color) })
+                .into()
+        };
+
+        let dismiss_btn = button(Icon::X.svg_sized_colored(scaled(10, zoom), text_muted))
+            .on_press(TcInterfaceMessage::DismissRateComparison)
+            .padding(scaled_spacing(2, zoom));
+
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                return row![
+                    line(format!("Rate comparison failed: {}", e), error_color),
+                    Space::new().width(iced::Length::Fill),
+                    dismiss_btn,
+                ]
+                .spacing(scaled_spacing(4, zoom))
+                .into();
+            }
+        };
+
+        let rate_kbps = response.rate_kbps;
+        let shaper_column = move |measurement: &ShaperMeasurement| {
+            let mut lines =
+                column![line(measurement.shaper.to_string(), text_primary)].spacing(2.0 * zoom);
+            if let Some(kbps) = measurement.throughput_kbps {
+                let percent = measurement.rate_percent(rate_kbps).unwrap_or_default();
+                lines = lines.push(line(
+                    format!("{:.0} kbit/s ({:.0}%)", kbps, percent),
+                    text_primary,
+                ));
+            }
+            if let Some(retransmits) = measurement.retransmits {
+                lines = lines.push(line(format!("{} retransmits", retransmits), text_muted));
+            }
+            if let Some(ref latency) = measurement.latency {
+                lines = lines.push(line(
+                    format!(
+                        "{:.1} ms avg, {:.1} ms max, {:.0}% loss",
+                        latency.avg_ms, latency.max_ms, latency.packet_loss_percent
+                    ),
+                    text_primary,
+                ));
+            }
+            if let Some(ref error) = measurement.error {
+                lines = lines.push(line(error.clone(), error_color));
+            }
+            container(lines).width(iced::Length::FillPortion(1))
+        };
+
+        let mut lines = column![
+            row![
+                text(format!(
+                    "netem vs tbf at {} kbit/s against {}",
+                    rate_kbps, response.target
+                ))
+                .size(scaled(11, zoom))
+                .color(text_muted),
+                Space::new().width(iced::Length::Fill),
+                dismiss_btn,
+            ]
+            .spacing(scaled_spacing(4, zoom))
+            .align_y(iced::Alignment::Center),
+            row![shaper_column(&response.netem), shaper_column(&response.tbf)]
+                .spacing(scaled_spacing(8, zoom)),
+        ]
+        .spacing(scaled_spacing(4, zoom));
+
+        for finding in response.findings() {
+            lines = lines.push(line(finding, text_primary));
+        }
+        for note in &response.notes {
+            lines = lines.push(line(note.clone(), warning_color));
+        }
+        if response.restored {
+            lines = lines.push(line(
+                "Original configuration restored".to_string(),
+                text_muted,
+            ));
+        }
+
+        container(lines)
+            .padding(scaled_spacing(6, zoom))
+            .style(move |_| iced::widget::container::Style {
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: text_muted,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// Render the requested configuration next to the qdisc tree the
+    /// backend reported (shown once the tree has been fetched)
+    fn render_qdisc_tree_panel(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::{Space, button};
+
+        let Some(ref result) = self.state.qdisc_tree else {
+            return column![].into();
+        };
+
+        let text_primary = theme.colors.text_primary;
+        let text_muted = theme.colors.text_muted;
+        let warning_color = theme.colors.warning;
+        let error_color = theme.colors.error;
+        let line = move |content: String, color: Color| -> Element<'_, TcInterfaceMessage> {
+            text(content)
+                .size(scaled(11, zoom))
+                .font(iced::Font::MONOSPACE)
+                .style(move |_| text::Style { color: Some(color) })
+                .into()
+        };
+
+        let configuration = self.current_configuration();
+        let requested = if configuration.command_parts(self.name()).len() <= 1 {
+            "no impairment".to_string()
+        } else {
+            configuration.to_command(self.name())
+        };
+
+        let dismiss_btn = button(Icon::X.svg_sized_colored(scaled(10, zoom), text_muted))
+            .on_press(TcInterfaceMessage::DismissQdiscTree)
+            .padding(scaled_spacing(2, zoom));
+
+        let mut lines = column![
+            row![
+                text("Requested:").size(scaled(11, zoom)).color(text_muted),
+                line(requested, text_primary),
+                Space::new().width(iced::Length::Fill),
+                dismiss_btn,
+            ]
+            .spacing(scaled_spacing(4, zoom))
+            .align_y(iced::Alignment::Center),
+            text("Effective:").size(scaled(11, zoom)).color(text_muted),
+        ]
+        .spacing(scaled_spacing(2, zoom));
+
+        match result {
+            Err(e) => lines = lines.push(line(e.clone(), error_color)),
+            Ok(response) if response.roots.is_empty() => {
+                lines = lines.push(line("no qdiscs (kernel default)".to_string(), text_muted));
+            }
+            Ok(response) => {
+                // Depth-first, indenting each level by two spaces
+                let mut stack: Vec<_> = response.roots.iter().rev().map(|n| (n, 0)).collect();
+                while let Some((node, depth)) = stack.pop() {
+                    let indent = "  ".repeat(depth);
+                    let mut headline = format!("{}{}", indent, node.headline());
+                    if !node.details.is_empty() {
+                        headline = format!("{} {}", headline, node.details.join(" "));
+                    }
+                    lines = lines.push(line(headline, text_primary));
+                    if let Some(ref note) = node.note {
+                        lines = lines.push(line(format!("{}  ! {}", indent, note), warning_color));
+                    }
+                    stack.extend(node.children.iter().rev().map(|c| (c, depth + 1)));
+                }
+            }
+        }
+
+        container(lines)
+            .padding(scaled_spacing(6, zoom))
+            .style(move |_| iced::widget::container::Style {
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: text_muted,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// Render diagnostics results panel (shown when diagnostics complete)
+    fn render_sync_badge(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        if self.state.sync_differences.is_empty() {
+            return row![].into();
+        }
+
+        let warning_color = theme.colors.warning;
+        let text_primary = theme.colors.text_primary;
+        let backend_has = self
+            .state
+            .sync_differences
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let adopt_btn = button(text("Adopt backend state").size(scaled(11, zoom)))
+            .on_press(TcInterfaceMessage::AdoptBackendState)
+            .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+
+        let content = row![
+            Icon::AlertTriangle.svg_sized_colored(scaled(12, zoom), warning_color),
+            text(format!("Out of sync — backend has {}", backend_has))
+                .size(scaled(11, zoom))
+                .style(move |_| text::Style {
+                    color: Some(text_primary)
+                }),
+            adopt_btn,
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center);
+
+        container(content)
+            .padding(scaled_spacing(4, zoom))
+            .style(move |_| iced::widget::container::Style {
+                background: Some(Background::Color(Color::from_rgba(
+                    warning_color.r,
+                    warning_color.g,
+                    warning_color.b,
+                    0.1,
+                ))),
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: warning_color,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// Warn that this end of a veth pair is configured very differently from
+    /// its peer. Informational only; nothing is blocked.
+    fn render_veth_asymmetry_badge(
+        &self,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        let Some((namespace, peer)) = &self.state.veth_peer else {
+            return row![].into();
+        };
+        if self.state.veth_asymmetry.is_empty() {
+            return row![].into();
+        }
+
+        let warning_color = theme.colors.warning;
+        let text_primary = theme.colors.text_primary;
+        let content = row![
+            Icon::AlertTriangle.svg_sized_colored(scaled(12, zoom), warning_color),
+            text(format!(
+                "Veth peer {}/{}: {}",
+                namespace,
+                peer,
+                self.state.veth_asymmetry.join(", ")
+            ))
+            .size(scaled(11, zoom))
+            .style(move |_| text::Style {
+                color: Some(text_primary)
+            }),
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center);
+
+        container(content)
+            .padding(scaled_spacing(4, zoom))
+            .style(move |_| iced::widget::container::Style {
+                background: Some(Background::Color(Color::from_rgba(
+                    warning_color.r,
+                    warning_color.g,
+                    warning_color.b,
+                    0.1,
+                ))),
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: warning_color,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// Warn that this interface carries the backend's own traffic, and hold
+    /// a pending apply until the user confirms or cancels it.
+    fn render_management_badge(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
 
-        let btn = if self.state.diagnostics_running {
-            btn // Disabled while running
-        } else {
-            btn.on_press(TcInterfaceMessage::StartDiagnostics)
-        };
+        if !self.state.management {
+            return row![].into();
+        }
 
-        let tooltip_text = if self.state.diagnostics_running {
-            "Running diagnostics..."
-        } else if let Some(ref result) = self.state.diagnostics_result {
-            if result.success {
-                "Diagnostics passed - click to re-run"
-            } else {
-                "Diagnostics failed - click to re-run"
-            }
+        let warning_color = theme.colors.warning;
+        let text_primary = theme.colors.text_primary;
+        let mut content =
+            row![Icon::AlertTriangle.svg_sized_colored(scaled(12, zoom), warning_color)]
+                .spacing(scaled_spacing(6, zoom))
+                .align_y(iced::Alignment::Center);
+
+        if self.state.management_confirmation_pending {
+            content = content
+                .push(
+                    text(format!(
+                        "{} carries this backend's control connection; impairing it may \
+                         disconnect the GUI",
+                        self.state.name
+                    ))
+                    .size(scaled(11, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(text_primary),
+                    }),
+                )
+                .push(
+                    button(text("Apply anyway").size(scaled(11, zoom)))
+                        .on_press(TcInterfaceMessage::ConfirmManagementImpairment)
+                        .style(button::danger)
+                        .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]),
+                )
+                .push(
+                    button(text("Cancel").size(scaled(11, zoom)))
+                        .on_press(TcInterfaceMessage::CancelManagementImpairment)
+                        .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]),
+                );
         } else {
-            "Run network diagnostics"
-        };
+            let note = if self.state.management_confirmed {
+                "Management interface — impairment confirmed for this session"
+            } else {
+                "Management interface — changes need confirmation"
+            };
+            content = content.push(
+                text(note)
+                    .size(scaled(11, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(warning_color),
+                    }),
+            );
+        }
 
-        let tooltip_style = theme.tooltip_style();
-        tooltip(
-            btn,
-            text(tooltip_text).size(scaled(11, zoom)),
-            tooltip::Position::Top,
-        )
-        .delay(Duration::from_millis(300))
-        .style(move |_| tooltip_style)
-        .into()
+        container(content)
+            .padding(scaled_spacing(4, zoom))
+            .style(move |_| iced::widget::container::Style {
+                background: Some(Background::Color(Color::from_rgba(
+                    warning_color.r,
+                    warning_color.g,
+                    warning_color.b,
+                    0.1,
+                ))),
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: warning_color,
+                },
+                ..Default::default()
+            })
+            .into()
     }
 
-    /// Render diagnostics results panel (shown when diagnostics complete)
     fn render_diagnostics_panel(
         &self,
+        history: Option<&DiagnosticsHistory>,
         theme: &Theme,
         zoom: f32,
     ) -> Element<'_, TcInterfaceMessage> {
@@ -956,6 +2696,24 @@ impl TcInterface {
             "TC: not configured".to_string()
         };
 
+        // History toggle, once there is more than this run to look at
+        let run_count = history.map_or(0, DiagnosticsHistory::len);
+        let history_btn: Element<'_, TcInterfaceMessage> = if run_count > 1 {
+            button(
+                row![
+                    Icon::Clock.svg_sized_colored(scaled(10, zoom), text_muted),
+                    text(format!("{} runs", run_count)).size(scaled(10, zoom)),
+                ]
+                .spacing(scaled_spacing(2, zoom))
+                .align_y(iced::Alignment::Center),
+            )
+            .on_press(TcInterfaceMessage::ToggleDiagnosticsHistory)
+            .padding(scaled_spacing(2, zoom))
+            .into()
+        } else {
+            Space::new().into()
+        };
+
         // Dismiss button
         let dismiss_btn = button(Icon::X.svg_sized_colored(scaled(10, zoom), text_muted))
             .on_press(TcInterfaceMessage::DismissDiagnostics)
@@ -968,7 +2726,7 @@ impl TcInterface {
             Color::from_rgba(error_color.r, error_color.g, error_color.b, 0.1)
         };
 
-        let content = row![
+        let summary = row![
             text(link_status_text)
                 .size(scaled(11, zoom))
                 .style(move |_| text::Style {
@@ -1005,11 +2763,20 @@ impl TcInterface {
                     color: Some(text_primary)
                 }),
             Space::new().width(iced::Length::Fill),
+            history_btn,
             dismiss_btn,
         ]
         .spacing(scaled_spacing(4, zoom))
         .align_y(iced::Alignment::Center);
 
+        let content = column![
+            summary,
+            self.render_ping_entry(results.ping.as_ref(), theme, zoom),
+            self.render_probe_entry(results.throughput.as_ref(), theme, zoom),
+            self.render_kernel_readback(results.reconciled.as_ref(), theme, zoom)
+        ]
+        .spacing(scaled_spacing(4, zoom));
+
         container(content)
             .padding(scaled_spacing(6, zoom))
             .style(move |_| iced::widget::container::Style {
@@ -1028,6 +2795,307 @@ impl TcInterface {
             .into()
     }
 
+    /// Render the ping entry of the diagnostics panel, followed by the
+    /// summary of the last ping
+    fn render_ping_entry(
+        &self,
+        ping: Option<&PingResult>,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let input = text_input("host or address", &self.state.ping_target_input)
+            .on_input(TcInterfaceMessage::PingTargetChanged)
+            .on_submit(TcInterfaceMessage::StartPing)
+            .size(scaled(11, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .font(iced::Font::MONOSPACE)
+            .width(iced::Length::Fixed(scaled(140, zoom)));
+        let can_ping =
+            !self.state.diagnostics_running && !self.state.ping_target_input.trim().is_empty();
+        let ping_btn = button(text("Ping").size(scaled(11, zoom)))
+            .on_press_maybe(can_ping.then_some(TcInterfaceMessage::StartPing))
+            .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+
+        let mut entry = row![input, ping_btn]
+            .spacing(scaled_spacing(6, zoom))
+            .align_y(iced::Alignment::Center);
+
+        if let Some(error) = &self.state.ping_error {
+            entry = entry.push(
+                text(error.as_str())
+                    .size(scaled(11, zoom))
+                    .color(theme.colors.error),
+            );
+        } else if let Some(ping) = ping {
+            let color = if !ping.reachable() {
+                theme.colors.error
+            } else if ping.packet_loss_percent > 0.0 {
+                theme.colors.warning
+            } else {
+                theme.colors.success
+            };
+            entry = entry.push(text(ping_summary(ping)).size(scaled(11, zoom)).color(color));
+        }
+
+        entry.into()
+    }
+
+    /// Render the throughput probe entry of the diagnostics panel, followed
+    /// by the last measurement next to the configured rate limit
+    fn render_probe_entry(
+        &self,
+        throughput: Option<&ThroughputResult>,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let input = text_input("host:port (loopback)", &self.state.probe_target_input)
+            .on_input(TcInterfaceMessage::ProbeTargetChanged)
+            .on_submit(TcInterfaceMessage::StartThroughputProbe)
+            .size(scaled(11, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .font(iced::Font::MONOSPACE)
+            .width(iced::Length::Fixed(scaled(140, zoom)));
+        let probe_btn = button(text("Throughput").size(scaled(11, zoom)))
+            .on_press_maybe(
+                (!self.state.diagnostics_running)
+                    .then_some(TcInterfaceMessage::StartThroughputProbe),
+            )
+            .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+
+        let mut entry = row![input, probe_btn]
+            .spacing(scaled_spacing(6, zoom))
+            .align_y(iced::Alignment::Center);
+
+        if let Some(error) = &self.state.probe_error {
+            entry = entry.push(
+                text(error.as_str())
+                    .size(scaled(11, zoom))
+                    .color(theme.colors.error),
+            );
+        } else if let Some(throughput) = throughput {
+            let color = if throughput.error.is_some() {
+                theme.colors.error
+            } else if throughput
+                .rate_percent()
+                .is_some_and(|percent| percent > 110.0)
+            {
+                // Well above the limit: the limit isn't taking effect
+                theme.colors.warning
+            } else {
+                theme.colors.text_primary
+            };
+            entry = entry.push(
+                text(throughput_summary(throughput))
+                    .size(scaled(11, zoom))
+                    .color(color),
+            );
+        }
+
+        entry.into()
+    }
+
+    /// Render the kernel readback of the diagnostics panel: the root qdisc
+    /// and how it differs from the UI state, with a button to take it over
+    fn render_kernel_readback<'a>(
+        &'a self,
+        reconciled: Option<&'a TcReconciliation>,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'a, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let read_btn = button(text("Read kernel TC").size(scaled(11, zoom)))
+            .on_press_maybe(
+                (!self.state.diagnostics_running).then_some(TcInterfaceMessage::ReadKernelTc),
+            )
+            .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+
+        let mut entry = row![read_btn]
+            .spacing(scaled_spacing(6, zoom))
+            .align_y(iced::Alignment::Center);
+
+        let Some(reconciled) = reconciled else {
+            return entry.into();
+        };
+
+        let qdisc = match reconciled.qdisc.as_str() {
+            "" => "no root qdisc",
+            qdisc => qdisc,
+        };
+        entry = entry.push(
+            text(qdisc)
+                .size(scaled(11, zoom))
+                .font(iced::Font::MONOSPACE)
+                .color(theme.colors.text_secondary),
+        );
+
+        let differences = self.kernel_differences(reconciled);
+        if differences.is_empty() {
+            entry = entry.push(
+                text("matches the UI")
+                    .size(scaled(11, zoom))
+                    .color(theme.colors.success),
+            );
+        } else {
+            let kernel_has = differences
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            entry = entry.push(
+                text(format!("kernel has {}", kernel_has))
+                    .size(scaled(11, zoom))
+                    .color(theme.colors.warning),
+            );
+            entry = entry.push(
+                button(text("Sync from kernel").size(scaled(11, zoom)))
+                    .on_press(TcInterfaceMessage::SyncFromKernel)
+                    .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]),
+            );
+        }
+
+        entry.into()
+    }
+
+    /// Render past diagnostics runs, newest first, with the change of each
+    /// metric from the run before it
+    fn render_diagnostics_history<'a>(
+        &'a self,
+        history: Option<&'a DiagnosticsHistory>,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'a, TcInterfaceMessage> {
+        use crate::diagnostics_history::DiagnosticsTrend;
+        use iced::widget::{Space, button};
+
+        let Some(history) = history.filter(|_| self.state.diagnostics_history_expanded) else {
+            return row![].into();
+        };
+
+        let text_primary = theme.colors.text_primary;
+        let text_muted = theme.colors.text_muted;
+        let success_color = theme.colors.success;
+        let error_color = theme.colors.error;
+        let border_color = theme.colors.border;
+
+        // Higher RTT and loss are worse, lower throughput is worse
+        let signed = |value: f32, unit: &str| format!("{:+.1}{}", value, unit);
+        let trend_text = |trend: DiagnosticsTrend| {
+            let mut parts = Vec::new();
+            if let Some(rtt) = trend.rtt_ms {
+                parts.push(format!("RTT {}", signed(rtt, "ms")));
+            }
+            if let Some(loss) = trend.loss_percent {
+                parts.push(format!("loss {}", signed(loss, "%")));
+            }
+            if let Some(bps) = trend.throughput_bps {
+                let sign = if bps < 0 { "-" } else { "+" };
+                parts.push(format!(
+                    "throughput {}{}B/s",
+                    sign,
+                    Self::format_bps(u32::try_from(bps.unsigned_abs()).unwrap_or(u32::MAX))
+                ));
+            }
+            parts.join(", ")
+        };
+
+        let header_text = match history.overall_trend().map(trend_text) {
+            Some(trend) if !trend.is_empty() => format!(
+                "Diagnostics history ({} runs) - since first: {}",
+                history.len(),
+                trend
+            ),
+            _ => format!("Diagnostics history ({} runs)", history.len()),
+        };
+        let header = row![
+            text(header_text)
+                .size(scaled(11, zoom))
+                .style(move |_| text::Style {
+                    color: Some(text_primary)
+                }),
+            Space::new().width(iced::Length::Fill),
+            button(text("Clear").size(scaled(10, zoom)))
+                .on_press(TcInterfaceMessage::ClearDiagnosticsHistory)
+                .padding(scaled_spacing(2, zoom)),
+            button(Icon::X.svg_sized_colored(scaled(10, zoom), text_muted))
+                .on_press(TcInterfaceMessage::ToggleDiagnosticsHistory)
+                .padding(scaled_spacing(2, zoom)),
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .align_y(iced::Alignment::Center);
+
+        let mut rows = Column::new().spacing(scaled_spacing(2, zoom)).push(header);
+        for (index, run) in history.runs().iter().enumerate().rev() {
+            let status_color = if run.response.passed() {
+                success_color
+            } else {
+                error_color
+            };
+            let mut measured = Vec::new();
+            match (run.avg_rtt_ms(), run.loss_percent()) {
+                (Some(rtt), Some(loss)) => {
+                    measured.push(format!("RTT {:.1}ms, loss {:.1}%", rtt, loss))
+                }
+                _ => measured.push("RTT N/A".to_string()),
+            }
+            if let Some(bps) = run.throughput_bps() {
+                measured.push(format!("throughput {}B/s", Self::format_bps(bps)));
+            }
+            let change = history
+                .trend_at(index)
+                .map(trend_text)
+                .filter(|t| !t.is_empty())
+                .map(|t| format!("({})", t))
+                .unwrap_or_default();
+
+            rows = rows.push(
+                row![
+                    text(run.recorded_at.format("%H:%M:%S").to_string())
+                        .size(scaled(10, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(text_muted)
+                        }),
+                    text(if run.response.passed() {
+                        "pass"
+                    } else {
+                        "fail"
+                    })
+                    .size(scaled(10, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(status_color)
+                    }),
+                    text(measured.join(", "))
+                        .size(scaled(10, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(text_primary)
+                        }),
+                    text(change)
+                        .size(scaled(10, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(text_muted)
+                        }),
+                ]
+                .spacing(scaled_spacing(6, zoom)),
+            );
+        }
+
+        container(rows)
+            .padding(scaled_spacing(6, zoom))
+            .style(move |_| iced::widget::container::Style {
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: border_color,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
     /// Render expandable feature cards in a grid layout for proper alignment
     fn render_expandable_features<'a>(
         &'a self,
@@ -1052,19 +3120,282 @@ impl TcInterface {
         if self.state.features.corrupt.enabled {
             cards.push(self.render_corrupt_card(theme, zoom));
         }
-        if self.state.features.rate_limit.enabled {
-            cards.push(self.render_rate_limit_card(theme, zoom));
+        if self.state.features.rate_limit.enabled {
+            cards.push(self.render_rate_limit_card(theme, zoom));
+        }
+        if self.state.features.slot.enabled {
+            cards.push(self.render_slot_card(theme, zoom));
+        }
+
+        if cards.is_empty() {
+            return column![].into();
+        }
+
+        iced::widget::Grid::with_children(cards)
+            .columns(3)
+            .spacing(scaled_spacing(4, zoom))
+            .height(iced::Length::Shrink)
+            .into()
+    }
+
+    /// Render the `tc` command for the current settings, each impairment's
+    /// clause in the accent color of its feature card, with a plain-English
+    /// explanation of what it does underneath
+    fn render_command_preview(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        let configuration = self.current_configuration();
+        let parts = configuration.command_parts(self.name());
+        if parts.len() <= 1 {
+            // Only the `tc qdisc ...` head: nothing is configured
+            return column![].into();
+        }
+
+        let muted = theme.colors.text_secondary;
+        let clauses = parts.into_iter().map(|part| {
+            let color = part.feature.map_or(muted, |f| theme.feature_color(f));
+            text(part.clause)
+                .size(scaled(11, zoom))
+                .font(iced::Font::MONOSPACE)
+                .style(move |_| text::Style { color: Some(color) })
+                .into()
+        });
+
+        let explanation = text(tcgui_shared::explain::explain(&configuration))
+            .size(scaled(11, zoom))
+            .color(muted);
+
+        column![
+            row(clauses).spacing(scaled_spacing(6, zoom)).wrap(),
+            explanation
+        ]
+        .spacing(scaled_spacing(2, zoom))
+        .into()
+    }
+
+    /// Render the shorthand entry field, e.g. "100ms/5%/10mbit", with the
+    /// parse error (if any) next to it, followed by the "both ends" action
+    /// for a veth whose peer is known
+    fn render_shorthand_entry(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let input = text_input("e.g. 100ms~10ms/5%/10mbit", &self.state.shorthand_input)
+            .on_input(TcInterfaceMessage::ShorthandChanged)
+            .on_submit(TcInterfaceMessage::ApplyShorthand)
+            .size(scaled(11, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .font(iced::Font::MONOSPACE)
+            .width(iced::Length::Fixed(scaled(220, zoom)));
+
+        let mut entry = row![
+            text("Quick:")
+                .size(scaled(11, zoom))
+                .color(theme.colors.text_secondary),
+            input
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center);
+
+        if let Some(error) = &self.state.shorthand_error {
+            entry = entry.push(
+                text(error.as_str())
+                    .size(scaled(11, zoom))
+                    .color(theme.colors.error),
+            );
+        }
+        entry = entry.push(value_input::direction_input(
+            self.state.direction,
+            TcInterfaceMessage::DirectionChanged,
+            theme,
+            zoom,
+        ));
+
+        let (record_label, record_tip) = if self.state.recording {
+            (
+                "Stop recording",
+                "Stop and save the recorded changes as a scenario",
+            )
+        } else {
+            (
+                "Record",
+                "Record the changes applied here into a scenario with matching step durations",
+            )
+        };
+        let tooltip_style = theme.tooltip_style();
+        entry = entry.push(
+            tooltip(
+                button(text(record_label).size(scaled(11, zoom)))
+                    .on_press(TcInterfaceMessage::ToggleRecording)
+                    .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]),
+                text(record_tip).size(scaled(11, zoom)),
+                tooltip::Position::Top,
+            )
+            .delay(Duration::from_millis(300))
+            .style(move |_| tooltip_style),
+        );
+
+        if let Some((namespace, peer)) = &self.state.veth_peer {
+            let both_ends_btn = button(text("Apply to both ends").size(scaled(11, zoom)))
+                .on_press(TcInterfaceMessage::ApplyToVethPair)
+                .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+            entry = entry.push(
+                tooltip(
+                    both_ends_btn,
+                    text(format!(
+                        "Apply these settings here and on the veth peer {}/{}",
+                        namespace, peer
+                    ))
+                    .size(scaled(11, zoom)),
+                    tooltip::Position::Top,
+                )
+                .delay(Duration::from_millis(300))
+                .style(move |_| tooltip_style),
+            );
+        }
+
+        entry.into()
+    }
+
+    /// Render the named layers (toggle / remove) and the entry for saving the
+    /// current settings as a new layer
+    fn render_layers(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let mut layers = row![
+            text("Layers:")
+                .size(scaled(11, zoom))
+                .color(theme.colors.text_secondary)
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center);
+
+        for (index, layer) in self.state.layers.iter().enumerate() {
+            let remove_btn =
+                button(Icon::X.svg_sized_colored(scaled(10, zoom), theme.colors.text_muted))
+                    .on_press(TcInterfaceMessage::RemoveLayer(index))
+                    .padding(scaled_spacing(2, zoom));
+            layers = layers.push(
+                row![
+                    checkbox(layer.enabled)
+                        .on_toggle(move |on| TcInterfaceMessage::LayerToggled(index, on)),
+                    text(layer.name.as_str())
+                        .size(scaled(11, zoom))
+                        .color(theme.colors.text_primary),
+                    remove_btn,
+                ]
+                .spacing(scaled_spacing(2, zoom))
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        let name_input = text_input("layer name", &self.state.layer_name_input)
+            .on_input(TcInterfaceMessage::LayerNameChanged)
+            .on_submit(TcInterfaceMessage::SaveLayer)
+            .size(scaled(11, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .width(iced::Length::Fixed(scaled(110, zoom)));
+        let save_btn = button(text("Save as layer").size(scaled(11, zoom)))
+            .on_press(TcInterfaceMessage::SaveLayer)
+            .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+
+        layers.push(name_input).push(save_btn).wrap().into()
+    }
+
+    /// Render the interface MTU with the entry for changing it
+    fn render_mtu_entry(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let current = self
+            .state
+            .mtu
+            .map_or_else(|| "unknown".to_string(), |mtu| mtu.to_string());
+        let input = text_input(&current, &self.state.mtu_input)
+            .on_input(TcInterfaceMessage::MtuInputChanged)
+            .on_submit(TcInterfaceMessage::SetMtu)
+            .size(scaled(11, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .font(iced::Font::MONOSPACE)
+            .width(iced::Length::Fixed(scaled(70, zoom)));
+        let set_btn = button(text("Set MTU").size(scaled(11, zoom)))
+            .on_press_maybe(
+                (!self.state.mtu_input.trim().is_empty()).then_some(TcInterfaceMessage::SetMtu),
+            )
+            .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+
+        let mut entry = row![
+            text(format!("MTU: {current}"))
+                .size(scaled(11, zoom))
+                .color(theme.colors.text_secondary),
+            input,
+            set_btn
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center);
+
+        if let Some(error) = &self.state.mtu_error {
+            entry = entry.push(
+                text(error.as_str())
+                    .size(scaled(11, zoom))
+                    .color(theme.colors.error),
+            );
+        }
+
+        entry.into()
+    }
+
+    /// Render the interface's addresses (each removable) and the entry for
+    /// adding one
+    fn render_addresses(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        use iced::widget::button;
+
+        let mut addresses = row![
+            text("Addresses:")
+                .size(scaled(11, zoom))
+                .color(theme.colors.text_secondary)
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center);
+
+        for address in &self.state.addresses {
+            let remove_btn =
+                button(Icon::X.svg_sized_colored(scaled(10, zoom), theme.colors.text_muted))
+                    .on_press(TcInterfaceMessage::RemoveAddress(address.clone()))
+                    .padding(scaled_spacing(2, zoom));
+            addresses = addresses.push(
+                row![
+                    text(address.as_str())
+                        .size(scaled(11, zoom))
+                        .font(iced::Font::MONOSPACE)
+                        .color(theme.colors.text_primary),
+                    remove_btn,
+                ]
+                .spacing(scaled_spacing(2, zoom))
+                .align_y(iced::Alignment::Center),
+            );
         }
 
-        if cards.is_empty() {
-            return column![].into();
+        let address_input = text_input("e.g. 10.0.0.1/24", &self.state.address_input)
+            .on_input(TcInterfaceMessage::AddressInputChanged)
+            .on_submit(TcInterfaceMessage::AddAddress)
+            .size(scaled(11, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .font(iced::Font::MONOSPACE)
+            .width(iced::Length::Fixed(scaled(140, zoom)));
+        let add_btn = button(text("Add address").size(scaled(11, zoom)))
+            .on_press_maybe(
+                (!self.state.address_input.trim().is_empty())
+                    .then_some(TcInterfaceMessage::AddAddress),
+            )
+            .padding([scaled_spacing(2, zoom), scaled_spacing(8, zoom)]);
+        addresses = addresses.push(address_input).push(add_btn);
+
+        if let Some(error) = &self.state.address_error {
+            addresses = addresses.push(
+                text(error.as_str())
+                    .size(scaled(11, zoom))
+                    .color(theme.colors.error),
+            );
         }
 
-        iced::widget::Grid::with_children(cards)
-            .columns(3)
-            .spacing(scaled_spacing(4, zoom))
-            .height(iced::Length::Shrink)
-            .into()
+        addresses.wrap().into()
     }
 
     /// Render loss feature as a card
@@ -1073,21 +3404,110 @@ impl TcInterface {
 
         let content: Column<'_, TcInterfaceMessage> = Column::new()
             .spacing(scaled_spacing(2, zoom))
-            .push(value_input::loss_input(
-                loss_config.percentage,
-                TcInterfaceMessage::LossChanged,
-                theme,
-                zoom,
-            ))
-            .push(value_input::correlation_input(
-                "Burst:",
-                loss_config.correlation,
-                TcInterfaceMessage::CorrelationChanged,
+            .push(value_input::loss_model_input(
+                loss_config.model,
+                TcInterfaceMessage::LossModelChanged,
                 theme,
                 zoom,
             ));
+        let content = match loss_config.model.gemodel() {
+            None => content
+                .push(value_input::loss_input(
+                    loss_config.percentage,
+                    TcInterfaceMessage::LossChanged,
+                    theme,
+                    zoom,
+                ))
+                .push(value_input::correlation_input(
+                    "Burst:",
+                    loss_config.correlation,
+                    TcInterfaceMessage::CorrelationChanged,
+                    theme,
+                    zoom,
+                )),
+            Some(model) => {
+                let changed = move |set: fn(&mut GeModel, f32)| {
+                    move |v| {
+                        let mut model = model;
+                        set(&mut model, v);
+                        TcInterfaceMessage::LossModelChanged(LossModel::GilbertElliott(model))
+                    }
+                };
+                content
+                    .push(value_input::correlation_input(
+                        "p:",
+                        model.p,
+                        changed(|m, v| m.p = v),
+                        theme,
+                        zoom,
+                    ))
+                    .push(value_input::correlation_input(
+                        "r:",
+                        model.r,
+                        changed(|m, v| m.r = v),
+                        theme,
+                        zoom,
+                    ))
+                    .push(value_input::correlation_input(
+                        "1-h:",
+                        model.one_h,
+                        changed(|m, v| m.one_h = v),
+                        theme,
+                        zoom,
+                    ))
+                    .push(value_input::correlation_input(
+                        "1-k:",
+                        model.one_k,
+                        changed(|m, v| m.one_k = v),
+                        theme,
+                        zoom,
+                    ))
+            }
+        };
+        // The card only shows while loss is enabled, so neither does this
+        let content = content.push(value_input::ecn_input(
+            loss_config.ecn,
+            TcInterfaceMessage::LossEcnToggled,
+            theme,
+            zoom,
+        ));
+
+        self.feature_card("Loss", TcFeature::Loss, content, theme, zoom)
+    }
+
+    /// Wrap a feature's controls in its card. The card is outlined when the
+    /// kernel rejected the feature in the last apply or its values failed
+    /// validation, and a validation error is shown under the controls. A
+    /// card whose values aren't applied yet is marked pending.
+    fn feature_card<'a>(
+        &'a self,
+        title: &'static str,
+        feature: TcFeature,
+        mut content: Column<'a, TcInterfaceMessage>,
+        theme: &Theme,
+        zoom: f32,
+    ) -> Element<'a, TcInterfaceMessage> {
+        let invalid = match &self.state.validation_error {
+            Some((invalid_feature, error)) if *invalid_feature == feature => Some(error),
+            _ => None,
+        };
+        if let Some(error) = invalid {
+            content = content.push(
+                text(error.to_string())
+                    .size(scaled(10, zoom))
+                    .color(theme.colors.error),
+            );
+        }
 
-        value_input::feature_card("Loss", content, theme, zoom)
+        value_input::feature_card(
+            title,
+            feature,
+            content,
+            invalid.is_some() || self.state.rejected_feature == Some(feature),
+            self.feature_pending(feature),
+            theme,
+            zoom,
+        )
     }
 
     /// Render duplicate feature as a card
@@ -1110,7 +3530,7 @@ impl TcInterface {
                 zoom,
             ));
 
-        value_input::feature_card("Duplicate", content, theme, zoom)
+        self.feature_card("Duplicate", TcFeature::Duplicate, content, theme, zoom)
     }
 
     /// Render reorder feature as a card
@@ -1139,7 +3559,7 @@ impl TcInterface {
                 zoom,
             ));
 
-        value_input::feature_card("Reorder", content, theme, zoom)
+        self.feature_card("Reorder", TcFeature::Reorder, content, theme, zoom)
     }
 
     /// Render corrupt feature as a card
@@ -1162,7 +3582,7 @@ impl TcInterface {
                 zoom,
             ));
 
-        value_input::feature_card("Corrupt", content, theme, zoom)
+        self.feature_card("Corrupt", TcFeature::Corrupt, content, theme, zoom)
     }
 
     /// Render rate limit feature as a card
@@ -1176,9 +3596,55 @@ impl TcInterface {
                 TcInterfaceMessage::RateLimitChanged,
                 theme,
                 zoom,
+            ))
+            .push(value_input::shaper_input(
+                rate_config.shaper,
+                TcInterfaceMessage::RateLimitShaperChanged,
+                theme,
+                zoom,
+            ));
+
+        self.feature_card("Rate Limit", TcFeature::RateLimit, content, theme, zoom)
+    }
+
+    /// Render slot feature as a card
+    fn render_slot_card(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
+        let slot_config = &self.state.features.slot.config;
+
+        let content: Column<'_, TcInterfaceMessage> = Column::new()
+            .spacing(scaled_spacing(2, zoom))
+            .push(value_input::slot_delay_input(
+                "Min:",
+                slot_config.min_delay_ms,
+                TcInterfaceMessage::SlotMinDelayChanged,
+                theme,
+                zoom,
+            ))
+            .push(value_input::slot_delay_input(
+                "Max:",
+                slot_config.max_delay_ms,
+                TcInterfaceMessage::SlotMaxDelayChanged,
+                theme,
+                zoom,
+            ))
+            .push(value_input::slot_limit_input(
+                "Pkts:",
+                "pkts",
+                slot_config.packets,
+                TcInterfaceMessage::SlotPacketsChanged,
+                theme,
+                zoom,
+            ))
+            .push(value_input::slot_limit_input(
+                "Bytes:",
+                "B",
+                slot_config.bytes,
+                TcInterfaceMessage::SlotBytesChanged,
+                theme,
+                zoom,
             ));
 
-        value_input::feature_card("Rate Limit", content, theme, zoom)
+        self.feature_card("Slot", TcFeature::Slot, content, theme, zoom)
     }
 
     /// Render delay feature as a card
@@ -1205,9 +3671,15 @@ impl TcInterface {
                 TcInterfaceMessage::DelayCorrelationChanged,
                 theme,
                 zoom,
+            ))
+            .push(value_input::distribution_input(
+                delay_config.distribution,
+                TcInterfaceMessage::DelayDistributionChanged,
+                theme,
+                zoom,
             ));
 
-        value_input::feature_card("Delay", content, theme, zoom)
+        self.feature_card("Delay", TcFeature::Delay, content, theme, zoom)
     }
 
     // Public API methods to maintain compatibility
@@ -1218,6 +3690,13 @@ impl TcInterface {
         self.bandwidth_display.update_stats(stats);
     }
 
+    /// Raise or clear the drop alert against the latest bandwidth statistics
+    pub fn update_drop_alert(&mut self, threshold: f64) {
+        if let Some(stats) = &self.state.bandwidth_stats {
+            self.status_display.update_drop_rate(stats, threshold);
+        }
+    }
+
     /// Update TC qdisc statistics
     pub fn update_tc_statistics(
         &mut self,
@@ -1235,6 +3714,19 @@ impl TcInterface {
     // - add_status_message: Not used in current implementation
     // - mark_config_applied: Not used in current implementation
 
+    /// Latest TC qdisc basic and queue statistics, if reported
+    pub fn tc_statistics(
+        &self,
+    ) -> (
+        Option<&tcgui_shared::TcStatsBasic>,
+        Option<&tcgui_shared::TcStatsQueue>,
+    ) {
+        (
+            self.state.tc_stats_basic.as_ref(),
+            self.state.tc_stats_queue.as_ref(),
+        )
+    }
+
     /// Get interface name (for tests, use name() for general use)
     #[cfg(test)]
     pub fn interface_name(&self) -> &str {
@@ -1261,6 +3753,38 @@ impl TcInterface {
         self.state.addresses = interface.addresses.clone();
         self.state.qdisc_kind = interface.qdisc_kind.clone();
         self.state.link_speed_mbps = interface.link_speed_mbps;
+        self.state.mtu = interface.mtu;
+        self.state.management = interface.management;
+        self.state.description = interface.description.clone();
+    }
+
+    /// Operator-provided description of this interface, if the backend has one
+    pub fn description(&self) -> Option<&str> {
+        self.state.description.as_deref()
+    }
+
+    /// Whether the backend's Zenoh traffic goes through this interface
+    pub fn is_management(&self) -> bool {
+        self.state.management
+    }
+
+    /// Whether the user confirmed impairing this management interface
+    pub fn management_confirmed(&self) -> bool {
+        self.state.management_confirmed
+    }
+
+    /// Hold back the current apply and ask the user to confirm it. Returns
+    /// whether the apply needs confirmation.
+    pub fn hold_for_management_confirmation(&mut self) -> bool {
+        let needed = self.state.management && !self.state.management_confirmed;
+        if needed && !self.state.management_confirmation_pending {
+            self.state.management_confirmation_pending = true;
+            self.state.add_status_message(
+                "Not applied: confirm impairing the management interface".to_string(),
+                false,
+            );
+        }
+        needed
     }
 
     /// Get bandwidth stats (compatibility method)
@@ -1368,15 +3892,58 @@ impl TcInterface {
         self.state.chart_expanded
     }
 
+    /// Widget id of the bandwidth chart
+    pub fn chart_id(&self) -> &iced::widget::Id {
+        &self.state.chart_id
+    }
+
     /// Get interface name
     pub fn name(&self) -> &str {
         &self.state.name
     }
+
+    /// Which way the impairments apply
+    pub fn direction(&self) -> TcDirection {
+        self.state.direction
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tcgui_shared::DelayDistribution;
+
+    #[test]
+    fn test_shorten_description() {
+        assert_eq!(shorten_description("upstream ISP"), "upstream ISP");
+        assert_eq!(
+            shorten_description("client side of the test bench"),
+            "client side of th…"
+        );
+    }
+
+    #[test]
+    fn test_config_period_line() {
+        let delay = TcConfiguration {
+            delay_ms: Some(100.0),
+            ..Default::default()
+        };
+        let past = config_period_line(&ConfigPeriod {
+            from: 36_000,
+            until: Some(36_300),
+            configuration: Some(&delay),
+        });
+        assert!(past.contains('–'));
+        assert!(past.ends_with(&tcgui_shared::explain::explain(&delay)));
+
+        let current = config_period_line(&ConfigPeriod {
+            from: 36_300,
+            until: None,
+            configuration: None,
+        });
+        assert!(current.starts_with("since "));
+        assert!(current.ends_with("  cleared"));
+    }
 
     #[test]
     fn test_rate_cap_exceeds_link() {
@@ -1424,4 +3991,514 @@ mod tests {
         assert!(!interface.state.features.loss.enabled);
         assert_eq!(interface.state.features.loss.config.percentage, 0.0);
     }
+
+    fn config_update(loss: f32, delay_ms: Option<f32>) -> TcConfiguration {
+        TcConfiguration {
+            loss,
+            delay_ms,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backend_sync_detects_and_adopts_drift() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::LossToggled(true));
+        let _ = interface.update(TcInterfaceMessage::LossChanged(5.0));
+
+        // Backend agrees: no badge
+        interface.check_backend_sync(Some(config_update(5.0, None)));
+        assert!(interface.sync_differences().is_empty());
+
+        // Backend changed externally
+        interface.check_backend_sync(Some(config_update(10.0, Some(50.0))));
+        let parameters: Vec<_> = interface
+            .sync_differences()
+            .iter()
+            .map(|d| d.parameter)
+            .collect();
+        assert_eq!(parameters, vec!["loss", "delay"]);
+
+        // Adopting makes the UI match and clears the badge
+        let _ = interface.update(TcInterfaceMessage::AdoptBackendState);
+        assert!(interface.sync_differences().is_empty());
+        assert_eq!(interface.loss(), 10.0);
+        assert!(interface.delay_enabled());
+        assert_eq!(interface.delay_ms(), 50.0);
+        assert!(
+            interface
+                .current_configuration()
+                .diff(&config_update(10.0, Some(50.0)))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_pending_marks_clear_once_applied() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::LossToggled(true));
+        let _ = interface.update(TcInterfaceMessage::LossChanged(5.0));
+        let _ = interface.update(TcInterfaceMessage::DelayToggled(true));
+        let _ = interface.update(TcInterfaceMessage::DelayChanged(100.0));
+        assert!(interface.feature_pending(TcFeature::Loss));
+        assert!(interface.feature_pending(TcFeature::Delay));
+        assert!(!interface.feature_pending(TcFeature::RateLimit));
+
+        // The apply went out with both, but delay was moved again before it
+        // came back: only loss is live
+        let _ = interface.update(TcInterfaceMessage::DelayChanged(150.0));
+        interface.confirm_applied(&config_update(5.0, Some(100.0)));
+        assert!(!interface.feature_pending(TcFeature::Loss));
+        assert!(interface.feature_pending(TcFeature::Delay));
+
+        // The backend's config update for the second apply confirms delay
+        interface.check_backend_sync(Some(config_update(5.0, Some(150.0))));
+        assert!(!interface.state.features.has_any_pending_changes());
+
+        // Switching a live feature off is pending until the backend drops it
+        let _ = interface.update(TcInterfaceMessage::LossToggled(false));
+        assert!(interface.feature_pending(TcFeature::Loss));
+        interface.check_backend_sync(Some(config_update(5.0, Some(150.0))));
+        assert!(interface.feature_pending(TcFeature::Loss));
+        interface.check_backend_sync(Some(config_update(0.0, Some(150.0))));
+        assert!(!interface.feature_pending(TcFeature::Loss));
+    }
+
+    #[test]
+    fn test_adopt_detected_configuration_matches_in_one_pass() {
+        let mut interface = TcInterface::new("eth0");
+        // Leftover UI state the detected config must fully replace
+        let _ = interface.update(TcInterfaceMessage::CorruptPercentageChanged(3.0));
+        let _ = interface.update(TcInterfaceMessage::DuplicatePercentageChanged(1.0));
+        interface.state.applying = false;
+        interface.state.status_messages.clear();
+
+        let detected = TcConfiguration {
+            loss: 2.5,
+            correlation: Some(25.0),
+            delay_ms: Some(100.0),
+            delay_jitter_ms: Some(10.0),
+            delay_distribution: Some(DelayDistribution::Normal),
+            reorder_percent: Some(5.0),
+            reorder_correlation: Some(50.0),
+            reorder_gap: Some(3),
+            rate_limit_kbps: Some(2000),
+            ..Default::default()
+        };
+        interface.adopt_detected_configuration(&detected);
+
+        let f = &interface.state.features;
+        assert!(f.loss.enabled);
+        assert_eq!(f.loss.config.percentage, 2.5);
+        assert_eq!(f.loss.config.correlation, 25.0);
+        assert!(f.delay.enabled);
+        assert_eq!(f.delay.config.base_ms, 100.0);
+        assert_eq!(f.delay.config.jitter_ms, 10.0);
+        assert_eq!(f.delay.config.correlation, 0.0);
+        assert_eq!(f.delay.config.distribution, DelayDistribution::Normal);
+        assert!(!f.duplicate.enabled);
+        assert!(f.reorder.enabled);
+        assert_eq!(f.reorder.config.percentage, 5.0);
+        assert_eq!(f.reorder.config.correlation, 50.0);
+        assert_eq!(f.reorder.config.gap, 3);
+        assert!(!f.corrupt.enabled);
+        assert!(f.rate_limit.enabled);
+        assert_eq!(f.rate_limit.config.rate_kbps, 2000);
+        assert!(interface.current_configuration().diff(&detected).is_empty());
+        assert_eq!(
+            interface.current_configuration().delay_distribution,
+            Some(DelayDistribution::Normal)
+        );
+
+        // Taken over as applied: nothing pending, no apply started
+        assert!(!f.has_any_pending_changes());
+        assert!(!interface.state.applying);
+        assert!(interface.state.status_messages.is_empty());
+    }
+
+    #[test]
+    fn test_adopt_detected_gemodel() {
+        let mut interface = TcInterface::new("eth0");
+        let model = GeModel {
+            p: 2.0,
+            r: 30.0,
+            one_h: 80.0,
+            one_k: 0.0,
+        };
+        let detected = TcConfiguration {
+            loss_gemodel: Some(model),
+            ..Default::default()
+        };
+        interface.adopt_detected_configuration(&detected);
+
+        // A model with no random loss percentage still counts as loss
+        let f = &interface.state.features;
+        assert!(f.loss.enabled);
+        assert_eq!(f.loss.config.model, LossModel::GilbertElliott(model));
+        assert_eq!(interface.current_configuration().loss_gemodel, Some(model));
+
+        let _ = interface.update(TcInterfaceMessage::LossModelChanged(LossModel::Random));
+        assert!(interface.state.applying);
+        assert_eq!(interface.current_configuration().loss_gemodel, None);
+    }
+
+    #[test]
+    fn test_loss_ecn_follows_loss() {
+        let mut interface = TcInterface::new("eth0");
+        interface.adopt_detected_configuration(&TcConfiguration {
+            loss: 2.0,
+            loss_ecn: true,
+            ..Default::default()
+        });
+        assert!(interface.state.features.loss.config.ecn);
+        assert!(interface.current_configuration().loss_ecn);
+
+        let _ = interface.update(TcInterfaceMessage::LossEcnToggled(false));
+        assert!(interface.state.applying);
+        assert!(!interface.current_configuration().loss_ecn);
+
+        // Marking is kept for when loss comes back, but not sent without it
+        let _ = interface.update(TcInterfaceMessage::LossEcnToggled(true));
+        let _ = interface.update(TcInterfaceMessage::LossToggled(false));
+        assert!(!interface.current_configuration().loss_ecn);
+    }
+
+    #[test]
+    fn test_detected_seed_is_kept() {
+        let mut interface = TcInterface::new("eth0");
+        let detected = TcConfiguration {
+            loss: 1.0,
+            seed: Some(42),
+            ..Default::default()
+        };
+        interface.adopt_detected_configuration(&detected);
+        interface.check_backend_sync(Some(detected));
+        assert_eq!(interface.current_configuration().seed, Some(42));
+
+        // Changing an impairment re-sends the seed along with it
+        let _ = interface.update(TcInterfaceMessage::LossToggled(false));
+        assert_eq!(interface.current_configuration().seed, Some(42));
+    }
+
+    #[test]
+    fn test_adopt_detected_slot() {
+        let mut interface = TcInterface::new("eth0");
+        let slot = TcSlotConfig {
+            enabled: true,
+            min_delay_ms: 5.0,
+            max_delay_ms: 15.0,
+            packets: None,
+            bytes: Some(64000),
+        };
+        let detected = TcConfiguration {
+            slot: Some(slot.clone()),
+            ..Default::default()
+        };
+        interface.adopt_detected_configuration(&detected);
+        assert!(interface.state.features.slot.enabled);
+        assert_eq!(interface.current_configuration().slot, Some(slot.clone()));
+
+        // A limit of 0 clears it
+        let _ = interface.update(TcInterfaceMessage::SlotPacketsChanged(16));
+        let _ = interface.update(TcInterfaceMessage::SlotBytesChanged(0));
+        assert!(interface.state.applying);
+        assert_eq!(
+            interface.current_configuration().slot,
+            Some(TcSlotConfig {
+                packets: Some(16),
+                bytes: None,
+                ..slot
+            })
+        );
+
+        let _ = interface.update(TcInterfaceMessage::SlotToggled(()));
+        assert_eq!(interface.current_configuration().slot, None);
+    }
+
+    #[test]
+    fn test_adopt_detected_configuration_without_tc_disables_everything() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::LossToggled(true));
+        let _ = interface.update(TcInterfaceMessage::LossChanged(5.0));
+        let _ = interface.update(TcInterfaceMessage::RateLimitChanged(1000));
+
+        interface.adopt_detected_configuration(&TcConfiguration::default());
+        assert!(!interface.state.features.loss.enabled);
+        assert!(!interface.rate_limit_enabled());
+        assert!(!interface.state.features.has_any_pending_changes());
+        // Slider values are kept for re-enabling
+        assert_eq!(interface.loss(), 5.0);
+    }
+
+    #[test]
+    fn test_backend_sync_unknown_state_clears_badge() {
+        let mut interface = TcInterface::new("eth0");
+        interface.check_backend_sync(Some(config_update(10.0, None)));
+        assert_eq!(interface.sync_differences().len(), 1);
+
+        interface.check_backend_sync(None);
+        assert!(interface.sync_differences().is_empty());
+    }
+
+    #[test]
+    fn test_management_interface_needs_confirmation_once() {
+        let mut interface = TcInterface::new("eth0");
+        assert!(!interface.hold_for_management_confirmation());
+
+        interface.state.management = true;
+        assert!(interface.hold_for_management_confirmation());
+        assert!(interface.state.management_confirmation_pending);
+
+        let _ = interface.update(TcInterfaceMessage::CancelManagementImpairment);
+        assert!(!interface.state.management_confirmation_pending);
+        assert!(!interface.management_confirmed());
+
+        let _ = interface.update(TcInterfaceMessage::ConfirmManagementImpairment);
+        assert!(interface.management_confirmed());
+        assert!(!interface.hold_for_management_confirmation());
+    }
+
+    #[test]
+    fn test_adopt_backend_state_disables_removed_features() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::RateLimitChanged(2000));
+        assert!(interface.rate_limit_enabled());
+
+        // Backend has no qdisc at all
+        interface.check_backend_sync(Some(TcConfiguration::default()));
+        assert_eq!(interface.sync_differences()[0].parameter, "rate");
+
+        let _ = interface.update(TcInterfaceMessage::AdoptBackendState);
+        assert!(!interface.rate_limit_enabled());
+        // The slider keeps its value for when the feature is re-enabled
+        assert_eq!(interface.rate_limit_kbps(), 2000);
+    }
+
+    #[test]
+    fn test_shorthand_replaces_settings_or_reports_error() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::RateLimitChanged(2000));
+
+        let _ = interface.update(TcInterfaceMessage::ShorthandChanged("100ms/5%".to_string()));
+        let _ = interface.update(TcInterfaceMessage::ApplyShorthand);
+        assert!(!interface.shorthand_rejected());
+        assert!(interface.delay_enabled());
+        assert_eq!(interface.delay_ms(), 100.0);
+        assert!(interface.loss_enabled());
+        assert_eq!(interface.loss(), 5.0);
+        // Features not mentioned are switched off
+        assert!(!interface.rate_limit_enabled());
+
+        let _ = interface.update(TcInterfaceMessage::ShorthandChanged("5 apples".to_string()));
+        let _ = interface.update(TcInterfaceMessage::ApplyShorthand);
+        assert!(interface.shorthand_rejected());
+        // A rejected entry leaves the settings alone
+        assert_eq!(interface.delay_ms(), 100.0);
+
+        // Editing the entry clears the error
+        let _ = interface.update(TcInterfaceMessage::ShorthandChanged("5".to_string()));
+        assert!(!interface.shorthand_rejected());
+    }
+
+    #[test]
+    fn test_mtu_entry_is_validated() {
+        let mut interface = TcInterface::new("eth0");
+        for (input, expected) in [("68", Ok(68)), (" 9000 ", Ok(9000)), ("65535", Ok(65535))] {
+            let _ = interface.update(TcInterfaceMessage::MtuInputChanged(input.to_string()));
+            assert_eq!(interface.requested_mtu(), expected, "{input}");
+        }
+        for input in ["67", "65536", "jumbo", ""] {
+            let _ = interface.update(TcInterfaceMessage::MtuInputChanged(input.to_string()));
+            let _ = interface.update(TcInterfaceMessage::SetMtu);
+            assert!(interface.requested_mtu().is_err(), "{input}");
+            assert!(interface.state.mtu_error.is_some(), "{input}");
+        }
+
+        // The MTU the backend reports replaces the entry
+        interface.set_mtu(1400);
+        assert_eq!(interface.state.mtu, Some(1400));
+        assert!(interface.state.mtu_input.is_empty());
+    }
+
+    #[test]
+    fn test_ping_entry_is_validated() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::PingTargetChanged(
+            " 10.0.0.1 ".to_string(),
+        ));
+        assert_eq!(
+            interface.requested_ping(),
+            Ok(DiagnosticsTest::Ping {
+                target: "10.0.0.1".to_string(),
+                count: DEFAULT_PING_COUNT,
+            })
+        );
+        let _ = interface.update(TcInterfaceMessage::StartPing);
+        assert!(interface.state.diagnostics_running);
+
+        let mut interface = TcInterface::new("eth0");
+        for input in ["", "-f", "host name"] {
+            let _ = interface.update(TcInterfaceMessage::PingTargetChanged(input.to_string()));
+            let _ = interface.update(TcInterfaceMessage::StartPing);
+            assert!(interface.state.ping_error.is_some(), "{input:?}");
+            assert!(!interface.state.diagnostics_running, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_ping_summary() {
+        let mut ping = PingResult {
+            target: "10.0.0.1".to_string(),
+            transmitted: 5,
+            received: 4,
+            packet_loss_percent: 20.0,
+            rtt: Some(tcgui_shared::RttSummary {
+                min_ms: 20.1,
+                avg_ms: 20.8,
+                max_ms: 21.9,
+                mdev_ms: Some(0.787),
+            }),
+            error: None,
+        };
+        assert_eq!(
+            ping_summary(&ping),
+            "Ping 10.0.0.1: 4/5 received (20% loss), rtt min/avg/max/mdev \
+             20.1/20.8/21.9/0.8 ms"
+        );
+
+        ping.error = Some("ping: nosuchhost: Name or service not known".to_string());
+        assert_eq!(
+            ping_summary(&ping),
+            "Ping 10.0.0.1 failed: ping: nosuchhost: Name or service not known"
+        );
+    }
+
+    #[test]
+    fn test_throughput_summary() {
+        let mut throughput = ThroughputResult {
+            target: Some("10.0.0.1:5001".to_string()),
+            bytes: 2_853_600,
+            measured_ms: 2400,
+            throughput_kbps: Some(9512.0),
+            configured_rate_kbps: Some(10_000),
+            error: None,
+        };
+        assert_eq!(
+            throughput_summary(&throughput),
+            "Throughput to 10.0.0.1:5001: 9512 kbit/s of 10000 kbit/s limit (95%)"
+        );
+
+        // Without TC the measurement is the baseline
+        throughput.configured_rate_kbps = None;
+        assert_eq!(
+            throughput_summary(&throughput),
+            "Throughput to 10.0.0.1:5001: 9512 kbit/s (no rate limit)"
+        );
+
+        throughput.target = None;
+        assert_eq!(
+            throughput_summary(&throughput),
+            "Throughput over loopback: 9512 kbit/s (baseline, not shaped by the interface)"
+        );
+
+        throughput.error = Some("Failed to open a loopback sink".to_string());
+        assert_eq!(
+            throughput_summary(&throughput),
+            "Throughput to loopback failed: Failed to open a loopback sink"
+        );
+    }
+
+    #[test]
+    fn test_sync_from_kernel() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::LossToggled(true));
+        let _ = interface.update(TcInterfaceMessage::LossChanged(10.0));
+
+        // The kernel has delay and corruption instead
+        let reconciled = TcReconciliation {
+            qdisc: "qdisc netem root".to_string(),
+            configuration: Some(TcConfiguration {
+                delay_ms: Some(80.0),
+                corrupt_percent: Some(2.0),
+                ..Default::default()
+            }),
+        };
+        let features: Vec<_> = interface
+            .kernel_differences(&reconciled)
+            .iter()
+            .map(TcConfigDifference::feature)
+            .collect();
+        assert!(features.contains(&TcFeature::Loss));
+        assert!(features.contains(&TcFeature::Delay));
+        assert!(features.contains(&TcFeature::Corrupt));
+
+        interface.sync_from_kernel(&reconciled);
+        assert!(interface.kernel_differences(&reconciled).is_empty());
+        assert!(!interface.feature_pending(TcFeature::Delay));
+
+        // A kernel without impairment clears the UI
+        interface.sync_from_kernel(&TcReconciliation::default());
+        let cleared = interface.current_configuration();
+        assert_eq!(cleared.loss, 0.0);
+        assert_eq!(cleared.delay_ms, None);
+        assert_eq!(cleared.corrupt_percent, None);
+    }
+
+    #[test]
+    fn test_address_entry_is_validated() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::AddressInputChanged(
+            " 10.0.0.5/24".to_string(),
+        ));
+        let _ = interface.update(TcInterfaceMessage::AddAddress);
+        assert_eq!(interface.requested_address().unwrap(), "10.0.0.5/24");
+        assert!(interface.state.address_error.is_none());
+
+        let _ = interface.update(TcInterfaceMessage::AddressInputChanged(
+            "10.0.0.5".to_string(),
+        ));
+        let _ = interface.update(TcInterfaceMessage::AddAddress);
+        assert!(interface.state.address_error.is_some());
+
+        // Editing the entry clears the error; the reported addresses replace it
+        let _ = interface.update(TcInterfaceMessage::AddressInputChanged(
+            "fd00::1/64".to_string(),
+        ));
+        assert!(interface.state.address_error.is_none());
+        interface.set_addresses(vec!["fd00::1/64".to_string()]);
+        assert_eq!(interface.state.addresses, ["fd00::1/64"]);
+        assert!(interface.state.address_input.is_empty());
+    }
+
+    #[test]
+    fn test_layers_merge_into_applied_settings() {
+        let mut interface = TcInterface::new("eth0");
+        let _ = interface.update(TcInterfaceMessage::DelayToggled(true));
+        let _ = interface.update(TcInterfaceMessage::DelayChanged(50.0));
+        let _ = interface.update(TcInterfaceMessage::LayerNameChanged("base".to_string()));
+        let _ = interface.update(TcInterfaceMessage::SaveLayer);
+
+        // Second layer: loss only, on top of the base delay
+        let _ = interface.update(TcInterfaceMessage::DelayToggled(false));
+        let _ = interface.update(TcInterfaceMessage::LossToggled(true));
+        let _ = interface.update(TcInterfaceMessage::LossChanged(5.0));
+        let _ = interface.update(TcInterfaceMessage::LayerNameChanged(
+            "congestion".to_string(),
+        ));
+        let _ = interface.update(TcInterfaceMessage::SaveLayer);
+
+        assert_eq!(interface.layers().len(), 2);
+        assert!(interface.delay_enabled());
+        assert_eq!(interface.delay_ms(), 50.0);
+        assert!(interface.loss_enabled());
+        assert_eq!(interface.loss(), 5.0);
+
+        let _ = interface.update(TcInterfaceMessage::LayerToggled(1, false));
+        assert!(!interface.loss_enabled());
+        assert_eq!(interface.delay_ms(), 50.0);
+
+        let _ = interface.update(TcInterfaceMessage::RemoveLayer(0));
+        assert_eq!(interface.layers().len(), 1);
+        assert!(!interface.delay_enabled());
+    }
 }
@@ -55,14 +55,30 @@ impl BandwidthDisplayComponent {
         }
     }
 
+    /// Format packets per second with appropriate units
+    fn format_packet_rate(packets_per_sec: f64) -> String {
+        if packets_per_sec >= 1_000_000.0 {
+            format!("{:.1}Mpps", packets_per_sec / 1_000_000.0)
+        } else if packets_per_sec >= 1000.0 {
+            format!("{:.1}Kpps", packets_per_sec / 1000.0)
+        } else if packets_per_sec > 0.0 {
+            format!("{:.0}pps", packets_per_sec)
+        } else {
+            "0pps".to_string()
+        }
+    }
+
     /// Render the bandwidth display
     pub fn view<'a>(&'a self, theme: &'a Theme, zoom: f32) -> Element<'a, TcInterfaceMessage> {
         if let Some(stats) = &self.stats {
             let rx_rate = Self::format_rate(stats.rx_bytes_per_sec);
             let tx_rate = Self::format_rate(stats.tx_bytes_per_sec);
+            let rx_packet_rate = Self::format_packet_rate(stats.rx_packets_per_sec);
+            let tx_packet_rate = Self::format_packet_rate(stats.tx_packets_per_sec);
 
             let rx_color = theme.colors.rx_color;
             let tx_color = theme.colors.tx_color;
+            let text_secondary = theme.colors.text_secondary;
 
             row![
                 Icon::TrendingUp.svg_sized_colored(scaled(11, zoom), rx_color),
@@ -71,11 +87,21 @@ impl BandwidthDisplayComponent {
                     .style(move |_| text::Style {
                         color: Some(rx_color)
                     }),
+                text(rx_packet_rate)
+                    .size(scaled(10, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(text_secondary)
+                    }),
                 Icon::ArrowUp.svg_sized_colored(scaled(11, zoom), tx_color),
                 text(tx_rate)
                     .size(scaled(11, zoom))
                     .style(move |_| text::Style {
                         color: Some(tx_color)
+                    }),
+                text(tx_packet_rate)
+                    .size(scaled(10, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(text_secondary)
                     })
             ]
             .spacing(scaled_spacing(2, zoom))
@@ -125,6 +151,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_packet_rate() {
+        assert_eq!(BandwidthDisplayComponent::format_packet_rate(0.0), "0pps");
+        assert_eq!(
+            BandwidthDisplayComponent::format_packet_rate(850.0),
+            "850pps"
+        );
+        assert_eq!(
+            BandwidthDisplayComponent::format_packet_rate(14_880.0),
+            "14.9Kpps"
+        );
+        assert_eq!(
+            BandwidthDisplayComponent::format_packet_rate(1_488_000.0),
+            "1.5Mpps"
+        );
+    }
+
     #[test]
     fn test_update_stats() {
         let mut component = BandwidthDisplayComponent::new();
@@ -140,6 +183,12 @@ mod tests {
             timestamp: 1234567890,
             rx_bytes_per_sec: 1_000_000.0,
             tx_bytes_per_sec: 500_000.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
         };
 
         component.update_stats(stats.clone());
@@ -167,6 +216,12 @@ mod tests {
             timestamp: 1234567890,
             rx_bytes_per_sec: 1_048_576.0, // 1 MB/s
             tx_bytes_per_sec: 524_288.0,   // 0.5 MB/s
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
         };
 
         component.update_stats(stats);
@@ -197,6 +252,12 @@ mod tests {
             timestamp: 1234567890,
             rx_bytes_per_sec: 1000.0,
             tx_bytes_per_sec: 500.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
         };
 
         component.update_stats(stats);
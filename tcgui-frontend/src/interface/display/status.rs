@@ -1,14 +1,17 @@
 //! Status display component for operation and error indicators.
 //!
 //! This component shows the current status of interface operations with
-//! visual indicators for success, pending operations, and errors.
+//! visual indicators for success, pending operations, and errors, and flags
+//! an interface while it drops packets faster than the alert threshold.
 
-use iced::{Color, Element};
+use iced::widget::{container, row, text};
+use iced::{Background, Color, Element};
+use tcgui_shared::NetworkBandwidthStats;
 
 use crate::icons::Icon;
 use crate::messages::TcInterfaceMessage;
 use crate::theme::Theme;
-use crate::view::scaled;
+use crate::view::{scaled, scaled_spacing};
 
 /// Status indicator types
 /// Note: Some variants currently unused but kept for future extensibility
@@ -41,6 +44,8 @@ pub struct StatusDisplayComponent {
     /// Whether interface state change is in progress (currently unused but kept for future)
     #[allow(dead_code)]
     applying_interface_state: bool,
+    /// Packets dropped per second, while above the alert threshold
+    drop_alert: Option<f64>,
 }
 
 impl Default for StatusDisplayComponent {
@@ -57,9 +62,17 @@ impl StatusDisplayComponent {
             message: None,
             applying: false,
             applying_interface_state: false,
+            drop_alert: None,
         }
     }
 
+    /// Raise the drop alert while `stats` show more packets dropped per
+    /// second, both directions together, than `threshold`; clear it otherwise
+    pub fn update_drop_rate(&mut self, stats: &NetworkBandwidthStats, threshold: f64) {
+        let drops_per_sec = stats.rx_dropped_per_sec + stats.tx_dropped_per_sec;
+        self.drop_alert = (drops_per_sec > threshold).then_some(drops_per_sec);
+    }
+
     // Removed unused methods (kept for tests where needed):
     // - update_status: Complex state management logic available if needed
     // - set_error: Error handling logic available if needed
@@ -95,11 +108,37 @@ impl StatusDisplayComponent {
         }
     }
 
-    /// Render the status display
+    /// Render the status display, with a red badge while the drop alert is
+    /// raised
     pub fn view(&self, theme: &Theme, zoom: f32) -> Element<'_, TcInterfaceMessage> {
         let color = self.status_color(theme);
-        self.status_icon()
-            .svg_sized_colored(scaled(13, zoom), color)
+        let icon = self
+            .status_icon()
+            .svg_sized_colored(scaled(13, zoom), color);
+        let Some(drops_per_sec) = self.drop_alert else {
+            return icon.into();
+        };
+
+        let error_color = theme.colors.error;
+        let badge = container(
+            text(format!("{:.0} drops/s", drops_per_sec))
+                .size(scaled(10, zoom))
+                .style(|_| text::Style {
+                    color: Some(Color::WHITE),
+                }),
+        )
+        .padding([0.0, scaled_spacing(4, zoom)])
+        .style(move |_| iced::widget::container::Style {
+            background: Some(Background::Color(error_color)),
+            border: iced::Border {
+                radius: 6.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        row![icon, badge]
+            .spacing(scaled_spacing(4, zoom))
+            .align_y(iced::Alignment::Center)
             .into()
     }
 
@@ -156,4 +195,42 @@ mod tests {
         component.status = StatusType::InterfaceChanging;
         assert_eq!(component.status_icon(), Icon::RefreshCw); // Interface changing
     }
+
+    #[test]
+    fn test_drop_alert_threshold() {
+        let mut component = StatusDisplayComponent::new();
+        let mut stats = NetworkBandwidthStats {
+            rx_bytes: 0,
+            rx_packets: 0,
+            rx_errors: 0,
+            rx_dropped: 0,
+            tx_bytes: 0,
+            tx_packets: 0,
+            tx_errors: 0,
+            tx_dropped: 0,
+            timestamp: 0,
+            rx_bytes_per_sec: 0.0,
+            tx_bytes_per_sec: 0.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 3.0,
+            tx_dropped_per_sec: 2.0,
+        };
+
+        // Both directions count towards the threshold
+        component.update_drop_rate(&stats, 4.0);
+        assert_eq!(component.drop_alert, Some(5.0));
+
+        component.update_drop_rate(&stats, 5.0);
+        assert_eq!(component.drop_alert, None);
+
+        // Errors alone don't raise it
+        stats.rx_dropped_per_sec = 0.0;
+        stats.tx_dropped_per_sec = 0.0;
+        stats.rx_errors_per_sec = 10.0;
+        component.update_drop_rate(&stats, 0.0);
+        assert_eq!(component.drop_alert, None);
+    }
 }
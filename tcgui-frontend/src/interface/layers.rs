@@ -0,0 +1,166 @@
+//! Named configuration layers.
+//!
+//! An interface can carry an ordered list of named [`TcNetemConfig`] layers,
+//! e.g. a "base latency" layer and a "congestion" layer that is switched on
+//! and off. The configuration applied to the interface is the merge of the
+//! enabled layers, in list order:
+//!
+//! | Feature                  | Merge                                          |
+//! |--------------------------|------------------------------------------------|
+//! | delay, jitter            | added up (delays stack along the path)         |
+//! | loss, duplicate, corrupt | combined as independent events: `1 - Π(1 - p)` |
+//! | reorder                  | latest layer wins (gap can't be combined)      |
+//! | slot                     | latest layer wins                              |
+//! | rate limit               | lowest rate wins (the tightest bottleneck)     |
+//! | correlations             | taken from the latest layer setting the feature |
+//!
+//! A layer only contributes the features it has enabled. Sums are capped at
+//! the ranges the backend accepts.
+
+use tcgui_shared::TcNetemConfig;
+
+/// Largest delay and jitter the backend accepts (see `TcDelayConfig`)
+const MAX_DELAY_MS: f32 = 5000.0;
+const MAX_JITTER_MS: f32 = 1000.0;
+
+/// One named layer of an interface's configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigLayer {
+    pub name: String,
+    /// Whether the layer takes part in the merge
+    pub enabled: bool,
+    pub config: TcNetemConfig,
+}
+
+/// Combine two independent event probabilities given in percent, i.e.
+/// `1 - (1 - a)(1 - b)` written so that a 0% side leaves the other exact.
+fn combine_percent(a: f32, b: f32) -> f32 {
+    a + b - a * b / 100.0
+}
+
+/// Merge the enabled layers into the configuration to apply.
+pub fn merge_layers<'a>(layers: impl IntoIterator<Item = &'a ConfigLayer>) -> TcNetemConfig {
+    let mut merged = TcNetemConfig::new();
+
+    for layer in layers.into_iter().filter(|layer| layer.enabled) {
+        let c = &layer.config;
+
+        if c.delay.enabled {
+            merged.delay.enabled = true;
+            merged.delay.base_ms = (merged.delay.base_ms + c.delay.base_ms).min(MAX_DELAY_MS);
+            merged.delay.jitter_ms =
+                (merged.delay.jitter_ms + c.delay.jitter_ms).min(MAX_JITTER_MS);
+            merged.delay.correlation = c.delay.correlation;
+        }
+        if c.loss.enabled {
+            merged.loss.enabled = true;
+            merged.loss.percentage = combine_percent(merged.loss.percentage, c.loss.percentage);
+            merged.loss.correlation = c.loss.correlation;
+        }
+        if c.duplicate.enabled {
+            merged.duplicate.enabled = true;
+            merged.duplicate.percentage =
+                combine_percent(merged.duplicate.percentage, c.duplicate.percentage);
+            merged.duplicate.correlation = c.duplicate.correlation;
+        }
+        if c.corrupt.enabled {
+            merged.corrupt.enabled = true;
+            merged.corrupt.percentage =
+                combine_percent(merged.corrupt.percentage, c.corrupt.percentage);
+            merged.corrupt.correlation = c.corrupt.correlation;
+        }
+        if c.reorder.enabled {
+            merged.reorder = c.reorder.clone();
+        }
+        if c.slot.enabled {
+            merged.slot = c.slot.clone();
+        }
+        if c.rate_limit.enabled {
+            merged.rate_limit.rate_kbps = if merged.rate_limit.enabled {
+                merged.rate_limit.rate_kbps.min(c.rate_limit.rate_kbps)
+            } else {
+                c.rate_limit.rate_kbps
+            };
+            merged.rate_limit.enabled = true;
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(name: &str, enabled: bool, edit: impl FnOnce(&mut TcNetemConfig)) -> ConfigLayer {
+        let mut config = TcNetemConfig::new();
+        edit(&mut config);
+        ConfigLayer {
+            name: name.to_string(),
+            enabled,
+            config,
+        }
+    }
+
+    #[test]
+    fn test_merge_layers() {
+        let base = layer("base latency", true, |c| {
+            c.delay.enabled = true;
+            c.delay.base_ms = 50.0;
+            c.delay.jitter_ms = 5.0;
+            c.loss.enabled = true;
+            c.loss.percentage = 10.0;
+            c.rate_limit.enabled = true;
+            c.rate_limit.rate_kbps = 10_000;
+        });
+        let congestion = layer("congestion", true, |c| {
+            c.delay.enabled = true;
+            c.delay.base_ms = 100.0;
+            c.loss.enabled = true;
+            c.loss.percentage = 10.0;
+            c.loss.correlation = 25.0;
+            c.rate_limit.enabled = true;
+            c.rate_limit.rate_kbps = 2_000;
+        });
+
+        let merged = merge_layers(&[base.clone(), congestion.clone()]);
+        assert!(merged.delay.enabled);
+        assert_eq!(merged.delay.base_ms, 150.0);
+        assert_eq!(merged.delay.jitter_ms, 5.0);
+        assert!((merged.loss.percentage - 19.0).abs() < 1e-4);
+        assert_eq!(merged.loss.correlation, 25.0);
+        assert_eq!(merged.rate_limit.rate_kbps, 2_000);
+        assert!(!merged.duplicate.enabled);
+
+        // Disabled layers don't count
+        let congestion_off = ConfigLayer {
+            enabled: false,
+            ..congestion
+        };
+        assert_eq!(
+            merge_layers(&[base.clone(), congestion_off]),
+            merge_layers(&[base])
+        );
+        assert!(!merge_layers(&[]).delay.enabled);
+    }
+
+    #[test]
+    fn test_merge_caps_and_latest_reorder() {
+        let slow = |name| {
+            layer(name, true, |c| {
+                c.delay.enabled = true;
+                c.delay.base_ms = 4000.0;
+                c.reorder.enabled = true;
+                c.reorder.percentage = 10.0;
+                c.reorder.gap = 3;
+            })
+        };
+        let mut last = slow("b");
+        last.config.reorder.percentage = 30.0;
+
+        let merged = merge_layers(&[slow("a"), last]);
+        assert_eq!(merged.delay.base_ms, MAX_DELAY_MS);
+        assert_eq!(merged.reorder.percentage, 30.0);
+        assert_eq!(merged.reorder.gap, 3);
+    }
+}
@@ -92,7 +92,7 @@ pub enum DisplayMessage {
 /// Currently unused - prepared for future modular architecture
 #[derive(Debug, Clone)]
 pub enum PresetMessage {
-    PresetSelected(CustomPreset),
+    PresetSelected(Box<CustomPreset>),
     ApplyPreset,
     ToggleVisibility,
 }
@@ -12,6 +12,7 @@
 //! - **Controls**: Feature-specific UI components (removed as unused)
 //! - **Display** (`display/`): Bandwidth and status display components
 //! - **Presets** (`preset/`): Preset management functionality
+//! - **Layers** (`layers.rs`): Named configuration layers and their merge
 //!
 //! # Usage
 //!
@@ -35,6 +36,9 @@ pub mod display;
 // Preset management
 pub mod preset;
 
+// Named configuration layers
+pub mod layers;
+
 // Re-export the main interface component for backward compatibility
 pub use base::TcInterface;
 
@@ -0,0 +1,154 @@
+//! Field-by-field comparison of two presets.
+//!
+//! Presets are compared through [`TcConfiguration::diff`], the helper that
+//! also flags interfaces out of sync with their backend, so both views agree
+//! on what counts as a difference. A rate given as a share of the link speed
+//! has no kbps value to compare and gets its own "rate" row.
+
+use tcgui_shared::{TcConfigDifference, TcConfiguration, TcNetemConfig, TcRateLimitMode};
+
+/// How the two sides of a [`TcConfigDifference`] relate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifferenceKind {
+    /// Active in both presets with different values
+    Changed,
+    /// Only active in the left preset
+    OnlyLeft,
+    /// Only active in the right preset
+    OnlyRight,
+}
+
+impl DifferenceKind {
+    /// Classify a difference produced by [`compare_configs`].
+    pub fn of(difference: &TcConfigDifference) -> Self {
+        match (difference.ours, difference.theirs) {
+            (Some(_), None) => Self::OnlyLeft,
+            (None, Some(_)) => Self::OnlyRight,
+            _ => Self::Changed,
+        }
+    }
+}
+
+/// The qdisc parameters a preset configures, with a rate relative to the
+/// link speed left out.
+fn configuration(config: &TcNetemConfig) -> TcConfiguration {
+    let mut configuration = config.to_legacy_params();
+    if !config.rate_limit.mode.is_absolute() {
+        configuration.rate_limit_kbps = None;
+    }
+    configuration
+}
+
+/// Share of the link speed a preset limits the rate to, if it uses one.
+fn relative_rate(config: &TcNetemConfig) -> Option<f64> {
+    match config.rate_limit.mode {
+        TcRateLimitMode::PercentOfLinkSpeed(percent) if config.rate_limit.enabled => {
+            Some(f64::from(percent))
+        }
+        _ => None,
+    }
+}
+
+/// The parameters on which `left` and `right` differ, in the order of
+/// [`TcConfiguration::diff`]. `ours` is the left side, `theirs` the right.
+/// Disabled features compare equal whatever values they hold.
+pub fn compare_configs(left: &TcNetemConfig, right: &TcNetemConfig) -> Vec<TcConfigDifference> {
+    let mut differences = configuration(left).diff(&configuration(right));
+    let rates = (relative_rate(left), relative_rate(right));
+    let differ = match rates {
+        (Some(a), Some(b)) => (a - b).abs() > 0.01,
+        (None, None) => false,
+        _ => true,
+    };
+    if differ {
+        differences.push(TcConfigDifference {
+            parameter: "rate",
+            unit: "% of link",
+            ours: rates.0,
+            theirs: rates.1,
+        });
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lossy(percentage: f32) -> TcNetemConfig {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = percentage;
+        config
+    }
+
+    #[test]
+    fn test_identical_presets_have_no_differences() {
+        assert!(compare_configs(&lossy(5.0), &lossy(5.0)).is_empty());
+    }
+
+    #[test]
+    fn test_changed_values_are_listed() {
+        let differences = compare_configs(&lossy(5.0), &lossy(2.0));
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].parameter, "loss");
+        assert_eq!(differences[0].ours, Some(5.0));
+        assert_eq!(differences[0].theirs, Some(2.0));
+        assert_eq!(DifferenceKind::of(&differences[0]), DifferenceKind::Changed);
+    }
+
+    #[test]
+    fn test_disjoint_features_show_as_one_sided() {
+        let left = lossy(1.0);
+        let mut right = TcNetemConfig::new();
+        right.delay.enabled = true;
+        right.delay.base_ms = 100.0;
+        right.delay.jitter_ms = 10.0;
+
+        let kinds: Vec<_> = compare_configs(&left, &right)
+            .iter()
+            .map(|d| (d.parameter, DifferenceKind::of(d)))
+            .collect();
+        // Jitter is only compared when both sides delay
+        assert_eq!(
+            kinds,
+            [
+                ("loss", DifferenceKind::OnlyLeft),
+                ("delay", DifferenceKind::OnlyRight),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disabled_features_ignore_their_values() {
+        let mut left = lossy(1.0);
+        left.corrupt.percentage = 30.0;
+        let mut right = lossy(1.0);
+        right.corrupt.percentage = 70.0;
+        assert!(compare_configs(&left, &right).is_empty());
+    }
+
+    #[test]
+    fn test_relative_rates_compare_as_share_of_link() {
+        let mut left = TcNetemConfig::new();
+        left.rate_limit.enabled = true;
+        left.rate_limit.rate_kbps = 5000;
+        let mut right = left.clone();
+        right.rate_limit.mode = TcRateLimitMode::PercentOfLinkSpeed(10.0);
+
+        let differences = compare_configs(&left, &right);
+        assert_eq!(differences.len(), 2);
+        assert_eq!(differences[0].parameter, "rate");
+        assert_eq!(differences[0].unit, "kbps");
+        assert_eq!(
+            DifferenceKind::of(&differences[0]),
+            DifferenceKind::OnlyLeft
+        );
+        assert_eq!(differences[1].unit, "% of link");
+        assert_eq!(differences[1].theirs, Some(10.0));
+        assert_eq!(
+            DifferenceKind::of(&differences[1]),
+            DifferenceKind::OnlyRight
+        );
+    }
+}
@@ -2,13 +2,16 @@
 //!
 //! This component handles the display and management of network presets,
 //! allowing users to quickly apply common traffic control configurations.
-//! Presets are loaded from JSON5 files by the backend.
+//! Built-in presets are loaded from JSON5 files by the backend; custom ones
+//! are saved from an interface's configuration (see [`crate::user_presets`]).
 
 use iced::Element;
-use iced::widget::{button, row, text};
+use iced::widget::{button, row, text, text_input};
 use tcgui_shared::presets::{CustomPreset, PresetList};
+use tcgui_shared::{TcNetemConfig, TcValidate};
 
 use crate::theme::Theme;
+use crate::user_presets::custom_preset_id;
 
 use crate::icons::Icon;
 use crate::interface::state::InterfaceState;
@@ -20,6 +23,8 @@ use crate::view::{scaled, scaled_spacing};
 pub struct PresetManagerComponent {
     /// Whether preset dropdown is visible
     pub show_presets: bool,
+    /// Name typed to save the current configuration as a custom preset
+    pub save_name: String,
 }
 
 impl PresetManagerComponent {
@@ -40,9 +45,29 @@ impl PresetManagerComponent {
     pub fn new() -> Self {
         Self {
             show_presets: false,
+            save_name: String::new(),
         }
     }
 
+    /// A custom preset named `name` holding `config`, if the name isn't
+    /// blank and the configuration is valid and impairs something
+    pub fn save_custom_preset(name: &str, config: &TcNetemConfig) -> Result<CustomPreset, String> {
+        let name = name.trim();
+        if custom_preset_id(name) == custom_preset_id("") {
+            return Err("A preset needs a name".to_string());
+        }
+        config.validate().map_err(|e| e.to_string())?;
+        if !config.has_any_enabled() {
+            return Err("Nothing is configured to save".to_string());
+        }
+        Ok(CustomPreset {
+            id: custom_preset_id(name),
+            name: name.to_string(),
+            description: "Saved from the UI".to_string(),
+            config: config.clone(),
+        })
+    }
+
     /// Toggle the preset dropdown visibility
     pub fn toggle_dropdown(&mut self) {
         self.show_presets = !self.show_presets;
@@ -54,9 +79,14 @@ impl PresetManagerComponent {
     pub fn apply_preset(&mut self, preset: &CustomPreset, state: &mut InterfaceState) -> bool {
         state.current_preset_id = Some(preset.id.clone());
         self.show_presets = false; // Close dropdown after selection
+        Self::apply_config(&preset.config, state)
+    }
 
-        let config = &preset.config;
-
+    /// Apply a netem configuration to the interface state, enabling exactly
+    /// the features that are enabled in `config`
+    ///
+    /// Returns true if settings were changed
+    pub fn apply_config(config: &TcNetemConfig, state: &mut InterfaceState) -> bool {
         // Apply loss settings
         if config.loss.enabled && config.loss.percentage > 0.0 {
             state.features.loss.enable();
@@ -104,12 +134,26 @@ impl PresetManagerComponent {
             state.features.corrupt.disable();
         }
 
-        // Apply rate limit settings
-        if config.rate_limit.enabled {
-            state.features.rate_limit.enable();
-            state.features.rate_limit.config.rate_kbps = config.rate_limit.rate_kbps;
+        // Apply rate limit settings, resolving a rate relative to the link
+        // speed since the card sends absolute rates
+        match config.rate_limit.resolve(state.link_speed_mbps) {
+            Ok(rate_limit) if rate_limit.enabled => {
+                state.features.rate_limit.enable();
+                state.features.rate_limit.config.rate_kbps = rate_limit.rate_kbps;
+            }
+            Ok(_) => state.features.rate_limit.disable(),
+            Err(e) => {
+                state.features.rate_limit.disable();
+                state.add_status_message(format!("Rate limit not set: {}", e.message), false);
+            }
+        }
+
+        // Apply slot settings
+        if config.slot.enabled {
+            state.features.slot.enable();
+            state.features.slot.config = config.slot.clone();
         } else {
-            state.features.rate_limit.disable();
+            state.features.slot.disable();
         }
 
         // Mark as applying to trigger backend update
@@ -129,6 +173,7 @@ impl PresetManagerComponent {
         state.features.reorder.disable();
         state.features.corrupt.disable();
         state.features.rate_limit.disable();
+        state.features.slot.disable();
 
         // Mark as applying to trigger backend update
         state.applying = true;
@@ -148,37 +193,52 @@ impl PresetManagerComponent {
 
     /// Render the preset selector UI
     ///
-    /// Takes a reference to the preset list and current preset ID.
+    /// Takes references to the built-in and custom preset lists and the
+    /// current preset ID. Custom presets follow the built-in ones, marked
+    /// with a star.
     /// Uses a horizontal scrollable row when expanded to avoid vertical layout shifts.
     pub fn view<'a>(
         &self,
         preset_list: &'a PresetList,
+        custom_presets: &'a PresetList,
         current_preset_id: &Option<String>,
         theme: &Theme,
         zoom: f32,
     ) -> Element<'a, TcInterfaceMessage> {
         let current_label = current_preset_id
             .as_ref()
-            .and_then(|id| preset_list.find_by_id(id))
+            .and_then(|id| {
+                preset_list
+                    .find_by_id(id)
+                    .or_else(|| custom_presets.find_by_id(id))
+            })
             .map(|p| p.name.clone())
             .unwrap_or_else(|| "Custom".to_string());
 
         if self.show_presets {
-            // When expanded, show a horizontal row of preset buttons plus Clear
-            let mut buttons: Vec<Element<'a, _>> = preset_list
-                .all()
-                .iter()
-                .map(|preset| {
+            // When expanded, show a horizontal row of preset buttons plus
+            // Clear and the save controls
+            let built_in = preset_list.all().iter().map(|preset| (preset, false));
+            let custom = custom_presets.all().iter().map(|preset| (preset, true));
+            let mut buttons: Vec<Element<'a, _>> = built_in
+                .chain(custom)
+                .map(|(preset, is_custom)| {
                     let is_selected = current_preset_id
                         .as_ref()
                         .map(|id| id == &preset.id)
                         .unwrap_or(false);
-                    let label = Self::short_name(preset);
+                    let label = if is_custom {
+                        format!("★ {}", Self::short_name(preset))
+                    } else {
+                        Self::short_name(preset)
+                    };
 
                     button(text(label).size(scaled(10, zoom)))
                         .padding([2.0 * zoom, 4.0 * zoom])
                         .style(if is_selected {
                             button::primary
+                        } else if is_custom {
+                            button::success
                         } else {
                             button::secondary
                         })
@@ -196,6 +256,35 @@ impl PresetManagerComponent {
                     .into(),
             );
 
+            // Save the current configuration; a name already taken asks
+            // before overwriting
+            let save_name = self.save_name.trim();
+            let taken =
+                !save_name.is_empty() && custom_presets.contains(&custom_preset_id(save_name));
+            buttons.push(
+                text_input("Save as…", &self.save_name)
+                    .on_input(TcInterfaceMessage::PresetNameChanged)
+                    .on_submit(TcInterfaceMessage::SaveAsPreset(self.save_name.clone()))
+                    .size(scaled(10, zoom))
+                    .padding(2.0 * zoom)
+                    .width(scaled(90, zoom))
+                    .into(),
+            );
+            buttons.push(
+                button(text(if taken { "Overwrite?" } else { "Save" }).size(scaled(10, zoom)))
+                    .padding([2.0 * zoom, 4.0 * zoom])
+                    .style(if taken {
+                        button::danger
+                    } else {
+                        button::secondary
+                    })
+                    .on_press_maybe(
+                        (!save_name.is_empty())
+                            .then(|| TcInterfaceMessage::SaveAsPreset(self.save_name.clone())),
+                    )
+                    .into(),
+            );
+
             row(buttons).spacing(scaled_spacing(2, zoom)).into()
         } else {
             // When collapsed, show a button with current preset name
@@ -297,6 +386,27 @@ mod tests {
         assert!(state.applying);
     }
 
+    #[test]
+    fn test_save_custom_preset() {
+        let mut config = TcNetemConfig::default();
+        assert_eq!(
+            PresetManagerComponent::save_custom_preset("Idle", &config),
+            Err("Nothing is configured to save".to_string())
+        );
+
+        config.loss.enabled = true;
+        config.loss.percentage = 3.0;
+        let preset = PresetManagerComponent::save_custom_preset(" Lossy lab ", &config).unwrap();
+        assert_eq!(preset.id, "custom-lossy-lab");
+        assert_eq!(preset.name, "Lossy lab");
+        assert_eq!(preset.config, config);
+
+        assert!(PresetManagerComponent::save_custom_preset(" -- ", &config).is_err());
+
+        config.loss.percentage = 150.0;
+        assert!(PresetManagerComponent::save_custom_preset("Broken", &config).is_err());
+    }
+
     #[test]
     fn test_short_name() {
         let preset1 = create_test_preset("sat", "Satellite Link", 1.0);
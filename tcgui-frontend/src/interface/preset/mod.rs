@@ -4,6 +4,7 @@
 //! traffic control presets, allowing users to quickly configure
 //! common network conditions.
 
+pub mod compare;
 pub mod manager;
 
 // Re-export components for easier access
@@ -3,9 +3,12 @@
 //! This module leverages the structured configuration types from Sprint 1
 //! to provide clean state management across all interface components.
 
+use super::layers::ConfigLayer;
 use tcgui_shared::{
-    DiagnosticsResponse, InterfaceFeatureStates, NetworkBandwidthStats, TcStatsBasic, TcStatsQueue,
-    TcStatsRateEst,
+    DiagnosticsResponse, InterfaceFeatureStates, NetworkBandwidthStats, TcConfigDifference,
+    TcConfiguration, TcDirection, TcFeature, TcStatsBasic, TcStatsQueue, TcStatsRateEst,
+    TcValidationError, config_history::ConfigHistoryResponse, qdisc_tree::TcTreeResponse,
+    rate_comparison::RateComparisonResponse,
 };
 
 /// Centralized state for a network interface and all its components
@@ -32,6 +35,34 @@ pub struct InterfaceState {
     /// Physical link speed in Mbit/s (ethtool), if known.
     pub link_speed_mbps: Option<u32>,
 
+    /// Interface MTU in bytes, if reported by the backend
+    pub mtu: Option<u32>,
+
+    /// Namespace and name of the other end, when this is one end of a veth
+    /// pair whose peer is known to the same backend
+    pub veth_peer: Option<(String, String)>,
+
+    /// Significant mismatches with the veth peer (MTU, rate, delay), worded
+    /// from this end
+    pub veth_asymmetry: Vec<String>,
+
+    /// Whether the backend's Zenoh traffic goes through this interface (from backend)
+    pub management: bool,
+
+    /// Operator-provided description from the backend's configuration
+    pub description: Option<String>,
+
+    /// Whether the user confirmed impairing this management interface; lasts
+    /// for the session
+    pub management_confirmed: bool,
+
+    /// Whether an apply is held back until the user confirms impairing this
+    /// management interface
+    pub management_confirmation_pending: bool,
+
+    /// Whether manual changes on this interface are being recorded
+    pub recording: bool,
+
     /// User's desired interface enable state
     pub interface_enabled: bool,
 
@@ -66,11 +97,101 @@ pub struct InterfaceState {
     /// Whether the bandwidth chart is expanded/visible
     pub chart_expanded: bool,
 
+    /// Widget id of the bandwidth chart, for locating it on screen when
+    /// exporting it
+    pub chart_id: iced::widget::Id,
+
     /// Whether diagnostics are currently running
     pub diagnostics_running: bool,
 
     /// Last diagnostics result (None if never run or dismissed)
     pub diagnostics_result: Option<DiagnosticsResponse>,
+
+    /// Whether the list of past diagnostics runs is shown
+    pub diagnostics_history_expanded: bool,
+
+    /// Host typed in the diagnostics panel's ping entry
+    pub ping_target_input: String,
+
+    /// Why the last ping entry could not be sent
+    pub ping_error: Option<String>,
+
+    /// TCP sink ("host:port") typed for the throughput probe; empty for
+    /// loopback
+    pub probe_target_input: String,
+
+    /// Why the last throughput probe entry could not be sent
+    pub probe_error: Option<String>,
+
+    /// Whether the qdisc tree is being fetched
+    pub qdisc_tree_loading: bool,
+
+    /// Last fetched qdisc tree, or why it couldn't be (None if never fetched
+    /// or dismissed)
+    pub qdisc_tree: Option<Result<TcTreeResponse, String>>,
+
+    /// Whether a netem vs tbf comparison is running
+    pub rate_comparison_running: bool,
+
+    /// Last netem vs tbf comparison, or why it failed (None if never run or
+    /// dismissed)
+    pub rate_comparison: Option<Result<RateComparisonResponse, String>>,
+
+    /// Whether the backend's configuration history is being fetched
+    pub config_history_loading: bool,
+
+    /// Last fetched configuration history, or why it couldn't be (None if
+    /// never fetched or dismissed)
+    pub config_history: Option<Result<ConfigHistoryResponse, String>>,
+
+    /// Configuration last reported by the backend (None if unknown)
+    pub backend_config: Option<TcConfiguration>,
+
+    /// Parameters on which the UI state disagrees with `backend_config`
+    pub sync_differences: Vec<TcConfigDifference>,
+
+    /// Netem seed taken over from the backend, sent back unchanged so a
+    /// re-apply keeps the random sequence (not editable here)
+    pub seed: Option<u64>,
+
+    /// Which way the impairments apply: sent, received (through an IFB
+    /// device on the backend) or both
+    pub direction: TcDirection,
+
+    /// Text in the shorthand entry field
+    pub shorthand_input: String,
+
+    /// Why the last shorthand entry could not be applied
+    pub shorthand_error: Option<String>,
+
+    /// Named configuration layers, merged in order into the applied config
+    pub layers: Vec<ConfigLayer>,
+
+    /// Name typed for the next layer to save
+    pub layer_name_input: String,
+
+    /// MTU typed in the MTU entry field
+    pub mtu_input: String,
+
+    /// Why the last MTU entry could not be sent
+    pub mtu_error: Option<String>,
+
+    /// Address typed in the address entry field ("ip/prefix")
+    pub address_input: String,
+
+    /// Why the last address entry could not be sent
+    pub address_error: Option<String>,
+
+    /// Features the backend reported it can't apply, with the reason
+    pub unsupported_features: Vec<(TcFeature, String)>,
+
+    /// Feature the last failed apply was rejected for, highlighted until the
+    /// next apply result
+    pub rejected_feature: Option<TcFeature>,
+
+    /// Why the settings were not sent: the feature and the validation error,
+    /// until the next apply attempt
+    pub validation_error: Option<(TcFeature, TcValidationError)>,
 }
 
 impl InterfaceState {
@@ -84,6 +205,14 @@ impl InterfaceState {
             addresses: Vec::new(),
             qdisc_kind: None,
             link_speed_mbps: None,
+            mtu: None,
+            veth_peer: None,
+            veth_asymmetry: Vec::new(),
+            management: false,
+            description: None,
+            management_confirmed: false,
+            management_confirmation_pending: false,
+            recording: false,
             interface_enabled: true,
             features: InterfaceFeatureStates::new(),
             bandwidth_stats: None,
@@ -95,8 +224,35 @@ impl InterfaceState {
             applying: false,
             applying_interface_state: false,
             chart_expanded: false,
+            chart_id: iced::widget::Id::unique(),
+            diagnostics_history_expanded: false,
+            ping_target_input: String::new(),
+            ping_error: None,
+            probe_target_input: String::new(),
+            probe_error: None,
             diagnostics_running: false,
             diagnostics_result: None,
+            qdisc_tree_loading: false,
+            qdisc_tree: None,
+            rate_comparison_running: false,
+            rate_comparison: None,
+            config_history_loading: false,
+            config_history: None,
+            backend_config: None,
+            sync_differences: Vec::new(),
+            seed: None,
+            direction: TcDirection::Egress,
+            shorthand_input: String::new(),
+            shorthand_error: None,
+            layers: Vec::new(),
+            layer_name_input: String::new(),
+            mtu_input: String::new(),
+            mtu_error: None,
+            address_input: String::new(),
+            address_error: None,
+            unsupported_features: Vec::new(),
+            rejected_feature: None,
+            validation_error: None,
         }
     }
 
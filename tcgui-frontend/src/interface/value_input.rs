@@ -7,9 +7,12 @@
 //!
 //! Preset values are based on real-world network conditions research.
 
-use iced::widget::{Column, Row, button, container, row, slider, text};
+use iced::widget::{Column, Row, button, checkbox, container, pick_list, row, slider, text};
 use iced::{Alignment, Background, Border, Element, Length, Shadow};
 use iced_aw::NumberInput;
+use tcgui_shared::{
+    DelayDistribution, LossModel, RateLimitMode, TcDirection, TcFeature, TcSlotConfig,
+};
 
 use crate::messages::TcInterfaceMessage;
 use crate::theme::Theme;
@@ -369,6 +372,139 @@ pub fn correlation_input<'a>(
     .into()
 }
 
+/// Shape of the delay jitter: picker over the netem distributions
+pub fn distribution_input<'a>(
+    value: DelayDistribution,
+    on_change: impl Fn(DelayDistribution) -> TcInterfaceMessage + 'a,
+    theme: &Theme,
+    zoom: f32,
+) -> Element<'a, TcInterfaceMessage> {
+    let text_color = theme.colors.text_secondary;
+
+    row![
+        text("Shape:")
+            .size(scaled(TEXT_SIZE, zoom))
+            .width(scaled(42, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+        pick_list(DelayDistribution::ALL, Some(value), on_change)
+            .text_size(scaled(TEXT_SIZE, zoom))
+            .padding(scaled_spacing(2, zoom)),
+    ]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Rate limit shaper: netem's own rate, or an HTB class with netem below it
+pub fn shaper_input<'a>(
+    value: RateLimitMode,
+    on_change: impl Fn(RateLimitMode) -> TcInterfaceMessage + 'a,
+    theme: &Theme,
+    zoom: f32,
+) -> Element<'a, TcInterfaceMessage> {
+    let text_color = theme.colors.text_secondary;
+
+    row![
+        text("Shaper:")
+            .size(scaled(TEXT_SIZE, zoom))
+            .width(scaled(42, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+        pick_list(RateLimitMode::ALL, Some(value), on_change)
+            .text_size(scaled(TEXT_SIZE, zoom))
+            .padding(scaled_spacing(2, zoom)),
+    ]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Direction the impairments apply in: egress, ingress or both
+pub fn direction_input<'a>(
+    value: TcDirection,
+    on_change: impl Fn(TcDirection) -> TcInterfaceMessage + 'a,
+    theme: &Theme,
+    zoom: f32,
+) -> Element<'a, TcInterfaceMessage> {
+    let text_color = theme.colors.text_secondary;
+
+    row![
+        text("Direction:")
+            .size(scaled(TEXT_SIZE, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+        pick_list(TcDirection::ALL, Some(value), on_change)
+            .text_size(scaled(TEXT_SIZE, zoom))
+            .padding(scaled_spacing(2, zoom)),
+    ]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Loss model: random loss, or Gilbert-Elliott bursts (switching to it starts
+/// from the default model)
+pub fn loss_model_input<'a>(
+    value: LossModel,
+    on_change: impl Fn(LossModel) -> TcInterfaceMessage + 'a,
+    theme: &Theme,
+    zoom: f32,
+) -> Element<'a, TcInterfaceMessage> {
+    const MODELS: [&str; 2] = ["Random", "Gilbert-Elliott"];
+    let text_color = theme.colors.text_secondary;
+
+    row![
+        text("Model:")
+            .size(scaled(TEXT_SIZE, zoom))
+            .width(scaled(42, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+        pick_list(
+            MODELS,
+            Some(MODELS[usize::from(!value.is_random())]),
+            move |name| {
+                on_change(if name == MODELS[1] {
+                    LossModel::GilbertElliott(value.gemodel().unwrap_or_default())
+                } else {
+                    LossModel::Random
+                })
+            }
+        )
+        .text_size(scaled(TEXT_SIZE, zoom))
+        .padding(scaled_spacing(2, zoom)),
+    ]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// ECN: mark ECN-capable packets the loss picks instead of dropping them
+pub fn ecn_input<'a>(
+    value: bool,
+    on_toggle: impl Fn(bool) -> TcInterfaceMessage + 'a,
+    theme: &Theme,
+    zoom: f32,
+) -> Element<'a, TcInterfaceMessage> {
+    let text_color = theme.colors.text_secondary;
+
+    row![
+        checkbox(value).on_toggle(on_toggle),
+        text("ECN mark instead of drop")
+            .size(scaled(TEXT_SIZE, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+    ]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(Alignment::Center)
+    .into()
+}
+
 /// Duplicate percentage: chips + NumberInput
 pub fn duplicate_input<'a>(
     value: f32,
@@ -542,6 +678,76 @@ pub fn rate_input<'a>(
     .into()
 }
 
+/// Slot delay bound in ms: NumberInput only
+pub fn slot_delay_input<'a>(
+    label: &'static str,
+    value: f32,
+    on_change: impl Fn(f32) -> TcInterfaceMessage + Clone + 'static,
+    theme: &Theme,
+    zoom: f32,
+) -> Element<'a, TcInterfaceMessage> {
+    let text_color = theme.colors.text_secondary;
+
+    row![
+        text(label)
+            .size(scaled(TEXT_SIZE, zoom))
+            .width(scaled(42, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+        NumberInput::new(&value, 0.0..=TcSlotConfig::MAX_DELAY_MS, on_change)
+            .step(1.0)
+            .set_size(scaled(TEXT_SIZE, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .ignore_buttons(true)
+            .width(scaled(50, zoom)),
+        text("ms")
+            .size(scaled(TEXT_SIZE, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+    ]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Per-slot limit (packets or bytes): NumberInput where 0 means no limit
+pub fn slot_limit_input<'a>(
+    label: &'static str,
+    unit: &'static str,
+    value: Option<u32>,
+    on_change: impl Fn(u32) -> TcInterfaceMessage + Clone + 'static,
+    theme: &Theme,
+    zoom: f32,
+) -> Element<'a, TcInterfaceMessage> {
+    let text_color = theme.colors.text_secondary;
+    let value = value.unwrap_or(0);
+
+    row![
+        text(label)
+            .size(scaled(TEXT_SIZE, zoom))
+            .width(scaled(42, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+        NumberInput::new(&value, 0..=i32::MAX as u32, on_change)
+            .step(1)
+            .set_size(scaled(TEXT_SIZE, zoom))
+            .padding(scaled_spacing(2, zoom))
+            .ignore_buttons(true)
+            .width(scaled(60, zoom)),
+        text(unit)
+            .size(scaled(TEXT_SIZE, zoom))
+            .style(move |_| iced::widget::text::Style {
+                color: Some(text_color)
+            }),
+    ]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(Alignment::Center)
+    .into()
+}
+
 // ============================================================================
 // Feature Card - styled container for grouping feature controls
 // ============================================================================
@@ -564,21 +770,42 @@ fn card_style(colors: CardColors) -> impl Fn(&iced::Theme) -> container::Style {
     }
 }
 
-/// Create a feature card with title and content
+/// Create a feature card with title and content, outlined in the error color
+/// when `rejected`, or in the warning color with a "pending" marker when its
+/// values aren't applied yet
 pub fn feature_card<'a>(
     title: &'static str,
+    feature: TcFeature,
     content: Column<'a, TcInterfaceMessage>,
+    rejected: bool,
+    pending: bool,
     theme: &Theme,
     zoom: f32,
 ) -> Element<'a, TcInterfaceMessage> {
-    let text_color = theme.colors.text_primary;
-    let card_colors = CardColors::from_theme(theme);
-
-    let header = text(title)
-        .size(scaled(12, zoom))
-        .style(move |_| iced::widget::text::Style {
-            color: Some(text_color),
-        });
+    // Title in the feature's accent color, matching its command clause
+    let title_color = theme.feature_color(feature);
+    let mut card_colors = CardColors::from_theme(theme);
+    // Outline the card the kernel objected to in the last apply
+    if rejected {
+        card_colors.border_color = theme.colors.error;
+    } else if pending {
+        card_colors.border_color = theme.colors.warning;
+    }
+
+    let mut header = iced::widget::row![text(title).size(scaled(12, zoom)).style(move |_| {
+        iced::widget::text::Style {
+            color: Some(title_color),
+        }
+    })]
+    .spacing(scaled_spacing(4, zoom))
+    .align_y(iced::Alignment::Center);
+    if pending {
+        header = header.push(
+            text("pending")
+                .size(scaled(10, zoom))
+                .color(theme.colors.warning),
+        );
+    }
 
     container(iced::widget::column![header, content].spacing(scaled_spacing(2, zoom)))
         .padding(scaled_spacing(4, zoom))
@@ -0,0 +1,116 @@
+//! Per-interface display color and label.
+//!
+//! Interfaces that are watched repeatedly can be given a color and a short
+//! label, used by their bandwidth chart, their card and the most active
+//! interfaces list in the header. Colors are picked from a fixed palette whose
+//! shades are chosen per theme (see [`crate::theme::Theme::accent_color`]),
+//! so a style stays legible after switching between light and dark mode.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of an interface label, in characters.
+pub const MAX_LABEL_LEN: usize = 24;
+
+/// Color that can be assigned to an interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccentColor {
+    Blue,
+    Green,
+    Orange,
+    Purple,
+    Red,
+    Teal,
+}
+
+impl AccentColor {
+    /// All colors, in the order they are offered.
+    pub fn all() -> &'static [AccentColor] {
+        &[
+            AccentColor::Blue,
+            AccentColor::Green,
+            AccentColor::Orange,
+            AccentColor::Purple,
+            AccentColor::Red,
+            AccentColor::Teal,
+        ]
+    }
+
+    /// Human-readable name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AccentColor::Blue => "Blue",
+            AccentColor::Green => "Green",
+            AccentColor::Orange => "Orange",
+            AccentColor::Purple => "Purple",
+            AccentColor::Red => "Red",
+            AccentColor::Teal => "Teal",
+        }
+    }
+}
+
+/// Display overrides of one interface. Unset fields fall back to defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<AccentColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl InterfaceStyle {
+    /// Whether no override is set.
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.label.is_none()
+    }
+
+    /// Set the label as typed, capped at [`MAX_LABEL_LEN`]; a blank label
+    /// clears it.
+    pub fn set_label(&mut self, label: &str) {
+        let label: String = label.chars().take(MAX_LABEL_LEN).collect();
+        self.label = (!label.trim().is_empty()).then_some(label);
+    }
+
+    /// Name to show for an interface: its label if set, else the interface name.
+    pub fn display_name<'a>(&'a self, interface: &'a str) -> &'a str {
+        self.label.as_deref().map_or(interface, str::trim)
+    }
+}
+
+/// Settings key of an interface's style: "origin/namespace/interface".
+pub fn style_key(backend_name: &str, namespace: &str, interface: &str) -> String {
+    format!("{}/{}/{}", backend_name, namespace, interface)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_is_capped_trimmed_for_display_and_cleared_when_blank() {
+        let mut style = InterfaceStyle::default();
+        style.set_label("  client-uplink  ");
+        assert_eq!(style.display_name("veth0"), "client-uplink");
+
+        style.set_label(&"x".repeat(40));
+        assert_eq!(style.label.as_ref().unwrap().len(), MAX_LABEL_LEN);
+
+        style.set_label("   ");
+        assert_eq!(style.display_name("veth0"), "veth0");
+        assert!(style.is_empty());
+    }
+
+    #[test]
+    fn test_style_serializes_compactly() {
+        let style = InterfaceStyle {
+            color: Some(AccentColor::Teal),
+            label: None,
+        };
+        let json = serde_json::to_string(&style).unwrap();
+        assert_eq!(json, r#"{"color":"teal"}"#);
+        assert_eq!(
+            serde_json::from_str::<InterfaceStyle>(&json).unwrap(),
+            style
+        );
+    }
+}
@@ -6,17 +6,28 @@ pub mod app;
 pub mod backend_manager;
 pub mod bandwidth_chart;
 pub mod bandwidth_history;
+pub mod chart_export;
+pub mod clock_skew;
+pub mod coordinated_apply;
+pub mod diagnostics_history;
+pub mod endpoint_history;
 pub mod icons;
+pub mod idle;
+pub mod inspector;
 pub mod interface;
 pub mod interface_selector;
+pub mod interface_style;
 pub mod message_handlers;
 pub mod messages;
+pub mod path_stats;
 pub mod query_manager;
+pub mod recording;
 pub mod scenario_manager;
 pub mod scenario_view;
 pub mod settings;
 pub mod table_view;
 pub mod theme;
 pub mod ui_state;
+pub mod user_presets;
 pub mod view;
 pub mod zenoh_manager;
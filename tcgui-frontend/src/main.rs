@@ -2,6 +2,7 @@ use clap::{Arg, Command};
 use tracing::info;
 
 use tcgui_frontend::app::TcGui;
+use tcgui_frontend::endpoint_history::DEFAULT_CONNECT_ENDPOINT;
 use tcgui_shared::{ZenohConfig, ZenohMode, errors::ZenohConfigError};
 
 pub fn main() -> iced::Result {
@@ -36,6 +37,20 @@ pub fn main() -> iced::Result {
                 .help("Zenoh listen endpoints (comma-separated, e.g., tcp/0.0.0.0:7447)")
                 .required(false),
         )
+        .arg(
+            Arg::new("inspector")
+                .long("inspector")
+                .action(clap::ArgAction::SetTrue)
+                .hide(true)
+                .help("Show the raw Zenoh message inspector (developer tool, Ctrl+Shift+I)"),
+        )
+        .arg(
+            Arg::new("idle-disconnect")
+                .long("idle-disconnect")
+                .value_name("MINUTES")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Close the Zenoh session after this many minutes without activity, reconnecting on input"),
+        )
         .arg(
             Arg::new("no-multicast")
                 .long("no-multicast")
@@ -100,7 +115,7 @@ pub fn main() -> iced::Result {
     // Client mode cannot have listen endpoints
     if matches!(zenoh_config.mode, ZenohMode::Peer) {
         zenoh_config = zenoh_config
-            .add_connect_endpoint("tcp/127.0.0.1:7447")
+            .add_connect_endpoint(DEFAULT_CONNECT_ENDPOINT)
             .add_listen_endpoint("tcp/127.0.0.1:0");
     }
 
@@ -161,6 +176,11 @@ pub fn main() -> iced::Result {
         std::process::exit(1);
     }
 
+    let inspector = matches.get_flag("inspector");
+    let idle_timeout = matches
+        .get_one::<u64>("idle-disconnect")
+        .map(|minutes| std::time::Duration::from_secs(minutes * 60));
+
     info!("[FRONTEND] Starting tcgui-frontend");
     info!(
         "Zenoh configuration - Mode: {:?}, Endpoints: {:?}",
@@ -168,7 +188,7 @@ pub fn main() -> iced::Result {
     );
 
     iced::application(
-        move || TcGui::new_with_config(zenoh_config.clone()),
+        move || TcGui::new_with_config(zenoh_config.clone(), inspector, idle_timeout),
         TcGui::update,
         TcGui::view,
     )
@@ -4,22 +4,26 @@
 //! that the application receives, separating the message handling
 //! logic from the main application update loop.
 
-use crate::backend_manager::BackendManager;
-use crate::interface::TcInterface;
+use crate::backend_manager::{BackendManager, NamespaceDiagnostics};
 use crate::messages::{TcGuiMessage, TcInterfaceMessage};
 use crate::query_manager::QueryManager;
 use crate::scenario_manager::ScenarioManager;
 use crate::ui_state::UiStateManager;
 use iced::Task;
-use tcgui_shared::{TcConfigUpdate, TcStatisticsUpdate};
+use tcgui_shared::{
+    DiagnosticsTest, InterfaceControlOperation, NamespaceDiagnosticsResponse, TcConfigUpdate,
+    TcConfiguration, TcNetemConfig, TcStatisticsUpdate,
+};
 use tracing::{info, warn};
 
 /// Handles bandwidth update messages.
 pub fn handle_bandwidth_update(
     backend_manager: &mut BackendManager,
     bandwidth_update: tcgui_shared::BandwidthUpdate,
+    drop_alert_per_sec: f64,
 ) -> Task<TcGuiMessage> {
     let backend_name = &bandwidth_update.backend_name;
+    backend_manager.observe_backend_timestamp(backend_name, bandwidth_update.stats.timestamp);
 
     if let Some(backend_group) = backend_manager.backends_mut().get_mut(backend_name) {
         if let Some(namespace_group) = backend_group
@@ -37,6 +41,7 @@ pub fn handle_bandwidth_update(
                     bandwidth_update.backend_name
                 );
                 tc_interface.update_bandwidth_stats(bandwidth_update.stats.clone());
+                tc_interface.update_drop_alert(drop_alert_per_sec);
             } else {
                 let available_interfaces: Vec<String> =
                     namespace_group.tc_interfaces.keys().cloned().collect();
@@ -73,201 +78,15 @@ pub fn handle_bandwidth_update(
     Task::none()
 }
 
-/// Apply complete TC configuration to interface component
-fn apply_tc_configuration_to_interface(
-    tc_interface: &mut TcInterface,
-    tc_config_update: &TcConfigUpdate,
-) {
-    // Apply TC configuration parameters directly (no master TC checkbox anymore)
-
-    // If there's a configuration, apply the specific parameters with values
-    if let Some(config) = &tc_config_update.configuration {
-        info!(
-            "Applying TC configuration parameters to {}/{}: loss={}%, delay={:?}ms, duplicate={:?}%, reorder={:?}%, corrupt={:?}%, rate={:?}kbps",
-            tc_config_update.namespace,
-            tc_config_update.interface,
-            config.loss,
-            config.delay_ms,
-            config.duplicate_percent,
-            config.reorder_percent,
-            config.corrupt_percent,
-            config.rate_limit_kbps
-        );
-
-        // Apply loss configuration - update checkbox based on actual value
-        if config.loss > 0.0 {
-            let _ = tc_interface.update(TcInterfaceMessage::LossToggled(true));
-            let _ = tc_interface.update(TcInterfaceMessage::LossChanged(config.loss));
-
-            if let Some(correlation) = config.correlation
-                && correlation > 0.0
-            {
-                let _ = tc_interface.update(TcInterfaceMessage::CorrelationChanged(correlation));
-            }
-        } else {
-            // If loss is 0.0, uncheck the Loss checkbox
-            let _ = tc_interface.update(TcInterfaceMessage::LossToggled(false));
-        }
-
-        // Apply delay configuration - update checkbox based on actual value
-        if let Some(delay_ms) = config.delay_ms {
-            if delay_ms > 0.0 {
-                let _ = tc_interface.update(TcInterfaceMessage::DelayToggled(true));
-                let _ = tc_interface.update(TcInterfaceMessage::DelayChanged(delay_ms));
-
-                if let Some(jitter) = config.delay_jitter_ms
-                    && jitter > 0.0
-                {
-                    let _ = tc_interface.update(TcInterfaceMessage::DelayJitterChanged(jitter));
-                }
-
-                if let Some(delay_corr) = config.delay_correlation
-                    && delay_corr > 0.0
-                {
-                    let _ = tc_interface
-                        .update(TcInterfaceMessage::DelayCorrelationChanged(delay_corr));
-                }
-            } else {
-                // If delay is 0.0, uncheck the Delay checkbox
-                let _ = tc_interface.update(TcInterfaceMessage::DelayToggled(false));
-            }
-        } else {
-            // If delay is None, uncheck the Delay checkbox
-            let _ = tc_interface.update(TcInterfaceMessage::DelayToggled(false));
-        }
-
-        // Apply duplicate configuration - set parameters directly (auto-enable logic will handle checkbox)
-        if let Some(duplicate_percent) = config.duplicate_percent {
-            if duplicate_percent > 0.0 {
-                // Set duplicate parameter - this will auto-enable the checkbox
-                let _ = tc_interface.update(TcInterfaceMessage::DuplicatePercentageChanged(
-                    duplicate_percent,
-                ));
-
-                if let Some(dup_corr) = config.duplicate_correlation
-                    && dup_corr > 0.0
-                {
-                    let _ = tc_interface
-                        .update(TcInterfaceMessage::DuplicateCorrelationChanged(dup_corr));
-                }
-            } else {
-                // If duplicate is 0.0, disable the Duplicate checkbox if enabled
-                if tc_interface.duplicate_enabled() {
-                    let _ = tc_interface.update(TcInterfaceMessage::DuplicateToggled(()));
-                }
-            }
-        } else {
-            // If duplicate is None, disable the Duplicate checkbox if enabled
-            if tc_interface.duplicate_enabled() {
-                let _ = tc_interface.update(TcInterfaceMessage::DuplicateToggled(()));
-            }
-        }
-
-        // Apply reorder configuration - set parameters directly (auto-enable logic will handle checkbox)
-        if let Some(reorder_percent) = config.reorder_percent {
-            if reorder_percent > 0.0 {
-                // Set reorder parameter - this will auto-enable the checkbox
-                let _ = tc_interface.update(TcInterfaceMessage::ReorderPercentageChanged(
-                    reorder_percent,
-                ));
-
-                if let Some(reorder_corr) = config.reorder_correlation
-                    && reorder_corr > 0.0
-                {
-                    let _ = tc_interface
-                        .update(TcInterfaceMessage::ReorderCorrelationChanged(reorder_corr));
-                }
-
-                if let Some(gap) = config.reorder_gap
-                    && gap > 0
-                {
-                    let _ = tc_interface.update(TcInterfaceMessage::ReorderGapChanged(gap));
-                }
-            } else {
-                // If reorder is 0.0, disable the Reorder checkbox
-                if tc_interface.reorder_enabled() {
-                    let _ = tc_interface.update(TcInterfaceMessage::ReorderToggled(()));
-                }
-            }
-        } else {
-            // If reorder is None, disable the Reorder checkbox
-            if tc_interface.reorder_enabled() {
-                let _ = tc_interface.update(TcInterfaceMessage::ReorderToggled(()));
-            }
-        }
-
-        // Apply corrupt configuration - set parameters directly (auto-enable logic will handle checkbox)
-        if let Some(corrupt_percent) = config.corrupt_percent {
-            if corrupt_percent > 0.0 {
-                // Set corrupt parameter - this will auto-enable the checkbox
-                let _ = tc_interface.update(TcInterfaceMessage::CorruptPercentageChanged(
-                    corrupt_percent,
-                ));
-
-                if let Some(corrupt_corr) = config.corrupt_correlation
-                    && corrupt_corr > 0.0
-                {
-                    let _ = tc_interface
-                        .update(TcInterfaceMessage::CorruptCorrelationChanged(corrupt_corr));
-                }
-            } else {
-                // If corrupt is 0.0, disable the Corrupt checkbox
-                if tc_interface.corrupt_enabled() {
-                    let _ = tc_interface.update(TcInterfaceMessage::CorruptToggled(()));
-                }
-            }
-        } else {
-            // If corrupt is None, disable the Corrupt checkbox
-            if tc_interface.corrupt_enabled() {
-                let _ = tc_interface.update(TcInterfaceMessage::CorruptToggled(()));
-            }
-        }
-
-        // Apply rate limit configuration - set parameters directly (auto-enable logic will handle checkbox)
-        if let Some(rate_kbps) = config.rate_limit_kbps {
-            if rate_kbps > 0 {
-                // Set rate limit parameter - this will auto-enable the checkbox
-                let _ = tc_interface.update(TcInterfaceMessage::RateLimitChanged(rate_kbps));
-            } else {
-                // If rate is 0, disable the Rate Limit checkbox
-                if tc_interface.rate_limit_enabled() {
-                    let _ = tc_interface.update(TcInterfaceMessage::RateLimitToggled(()));
-                }
-            }
-        } else {
-            // If rate is None, disable the Rate Limit checkbox
-            if tc_interface.rate_limit_enabled() {
-                let _ = tc_interface.update(TcInterfaceMessage::RateLimitToggled(()));
-            }
-        }
-    } else if !tc_config_update.has_tc {
-        // Only disable features if we're certain there's no TC configuration at all
-        // This handles the case where the interface truly has no TC configured
-        let _ = tc_interface.update(TcInterfaceMessage::LossToggled(false));
-        let _ = tc_interface.update(TcInterfaceMessage::DelayToggled(false));
-
-        // Disable unit-type toggles if they are currently enabled
-        if tc_interface.duplicate_enabled() {
-            let _ = tc_interface.update(TcInterfaceMessage::DuplicateToggled(()));
-        }
-        if tc_interface.reorder_enabled() {
-            let _ = tc_interface.update(TcInterfaceMessage::ReorderToggled(()));
-        }
-        if tc_interface.corrupt_enabled() {
-            let _ = tc_interface.update(TcInterfaceMessage::CorruptToggled(()));
-        }
-        if tc_interface.rate_limit_enabled() {
-            let _ = tc_interface.update(TcInterfaceMessage::RateLimitToggled(()));
-        }
-    }
-    // If has_tc is true but configuration is None, we don't override any checkboxes
-    // This allows the user to interact with the interface without being overridden
-}
-
 /// Handles TC configuration update messages from backend.
+///
+/// With `adopt_detected` the interface's settings are replaced by the
+/// reported configuration; otherwise they are kept and only the differences
+/// are flagged.
 pub fn handle_tc_config_update(
     backend_manager: &mut BackendManager,
     tc_config_update: TcConfigUpdate,
+    adopt_detected: bool,
 ) -> Task<TcGuiMessage> {
     let backend_name = &tc_config_update.backend_name;
 
@@ -301,8 +120,31 @@ pub fn handle_tc_config_update(
                     tc_interface.update_from_backend(&network_interface);
                 }
 
-                // Apply the complete TC configuration from the backend
-                apply_tc_configuration_to_interface(tc_interface, &tc_config_update);
+                // No qdisc means every feature is off. With has_tc but no
+                // parsed configuration there is nothing to adopt or compare
+                // against, so the UI is left alone.
+                let backend_config = match &tc_config_update.configuration {
+                    Some(config) => Some(config.clone()),
+                    None if !tc_config_update.has_tc => Some(TcConfiguration::default()),
+                    None => None,
+                };
+                if adopt_detected && let Some(config) = &backend_config {
+                    info!(
+                        "Adopting TC configuration of {}/{}: loss={}%, delay={:?}ms, duplicate={:?}%, reorder={:?}%, corrupt={:?}%, rate={:?}kbps",
+                        tc_config_update.namespace,
+                        tc_config_update.interface,
+                        config.loss,
+                        config.delay_ms,
+                        config.duplicate_percent,
+                        config.reorder_percent,
+                        config.corrupt_percent,
+                        config.rate_limit_kbps
+                    );
+                    tc_interface.adopt_detected_configuration(config);
+                }
+
+                // Flag anything the frontend state still disagrees on
+                tc_interface.check_backend_sync(backend_config);
             } else {
                 warn!(
                     "Could not find TC interface '{}' in namespace '{}' of backend '{}' to update TC config",
@@ -325,6 +167,8 @@ pub fn handle_tc_config_update(
             tc_config_update.backend_name, tc_config_update.namespace, tc_config_update.interface
         );
     }
+    // The change may open or close a gap with the interface's veth peer
+    backend_manager.refresh_veth_pairs(&tc_config_update.backend_name);
 
     Task::none()
 }
@@ -373,9 +217,14 @@ pub fn handle_tc_interface_message(
             // Slider and NumberInput changes trigger backend apply
             TcInterfaceMessage::LossChanged(_)
             | TcInterfaceMessage::CorrelationChanged(_)
+            | TcInterfaceMessage::LossModelChanged(_)
+            | TcInterfaceMessage::LossEcnToggled(_)
             | TcInterfaceMessage::DelayChanged(_)
             | TcInterfaceMessage::DelayJitterChanged(_)
             | TcInterfaceMessage::DelayCorrelationChanged(_)
+            | TcInterfaceMessage::DelayDistributionChanged(_)
+            | TcInterfaceMessage::RateLimitShaperChanged(_)
+            | TcInterfaceMessage::DirectionChanged(_)
             | TcInterfaceMessage::DuplicatePercentageChanged(_)
             | TcInterfaceMessage::DuplicateCorrelationChanged(_)
             | TcInterfaceMessage::ReorderPercentageChanged(_)
@@ -1100,9 +949,68 @@ pub fn handle_tc_interface_message(
                     })
                 }
             }
-            // Preset messages - apply all TC settings from preset
-            TcInterfaceMessage::PresetSelected(_) => {
-                // Preset was applied in TcInterface::update(), now send to backend
+            // Typing into the shorthand field is UI-only, and an entry that
+            // failed to parse leaves the settings untouched
+            TcInterfaceMessage::ShorthandChanged(_) => Task::none(),
+            TcInterfaceMessage::LayerNameChanged(_) => Task::none(),
+            TcInterfaceMessage::MtuInputChanged(_) => Task::none(),
+            TcInterfaceMessage::AddressInputChanged(_) => Task::none(),
+            TcInterfaceMessage::AddAddress => match tc_interface.requested_address() {
+                Ok(cidr) => Task::done(TcGuiMessage::ChangeInterfaceAddress {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    operation: InterfaceControlOperation::AddAddress { cidr },
+                }),
+                Err(_) => Task::none(),
+            },
+            TcInterfaceMessage::RemoveAddress(cidr) => {
+                Task::done(TcGuiMessage::ChangeInterfaceAddress {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    operation: InterfaceControlOperation::RemoveAddress { cidr },
+                })
+            }
+            TcInterfaceMessage::SetMtu => match tc_interface.requested_mtu() {
+                Ok(mtu) => Task::done(TcGuiMessage::SetInterfaceMtu {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    mtu,
+                }),
+                Err(_) => Task::none(),
+            },
+            TcInterfaceMessage::ToggleRecording => {
+                Task::done(TcGuiMessage::ToggleScenarioRecording {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                })
+            }
+            TcInterfaceMessage::ApplyToVethPair => Task::done(TcGuiMessage::ApplyTcToVethPair {
+                backend_name: backend_name.clone(),
+                namespace: namespace.clone(),
+                interface: interface_name.clone(),
+                config: tc_interface.current_configuration(),
+            }),
+            TcInterfaceMessage::ApplyShorthand if tc_interface.shorthand_rejected() => Task::none(),
+            TcInterfaceMessage::CancelManagementImpairment => Task::none(),
+            // Preset, shorthand and layer messages - apply all TC settings at
+            // once; so does confirming a held-back management interface apply,
+            // and so do slot changes, which have no legacy parameter of their own
+            TcInterfaceMessage::PresetSelected(_)
+            | TcInterfaceMessage::ConfirmManagementImpairment
+            | TcInterfaceMessage::ApplyShorthand
+            | TcInterfaceMessage::SaveLayer
+            | TcInterfaceMessage::LayerToggled(..)
+            | TcInterfaceMessage::RemoveLayer(_)
+            | TcInterfaceMessage::SlotToggled(_)
+            | TcInterfaceMessage::SlotMinDelayChanged(_)
+            | TcInterfaceMessage::SlotMaxDelayChanged(_)
+            | TcInterfaceMessage::SlotPacketsChanged(_)
+            | TcInterfaceMessage::SlotBytesChanged(_) => {
+                // Settings were applied in TcInterface::update(), now send to backend
                 Task::done(TcGuiMessage::ApplyTc {
                     backend_name: backend_name.clone(),
                     namespace: namespace.clone(),
@@ -1198,6 +1106,12 @@ pub fn handle_tc_interface_message(
             }
             // Toggle preset dropdown is UI-only, no backend action needed
             TcInterfaceMessage::TogglePresetDropdown => Task::none(),
+            // Saving a preset keeps it in the frontend; nothing is applied
+            TcInterfaceMessage::PresetNameChanged(_) => Task::none(),
+            TcInterfaceMessage::SaveAsPreset(name) => match tc_interface.save_as_preset(&name) {
+                Some(preset) => Task::done(TcGuiMessage::SaveCustomPreset(preset)),
+                None => Task::none(),
+            },
             // Clear all features - remove the TC qdisc entirely
             TcInterfaceMessage::ClearAllFeatures => Task::done(TcGuiMessage::RemoveTc {
                 backend_name: backend_name.clone(),
@@ -1206,15 +1120,112 @@ pub fn handle_tc_interface_message(
             }),
             // Toggle chart visibility is UI-only, no backend action needed
             TcInterfaceMessage::ToggleChart => Task::none(),
+            TcInterfaceMessage::ExportChart => Task::done(TcGuiMessage::ExportBandwidthChart {
+                backend_name: backend_name.clone(),
+                namespace: namespace.clone(),
+                interface: interface_name.clone(),
+                chart_id: tc_interface.chart_id().clone(),
+            }),
+            TcInterfaceMessage::ExportHistory(format) => {
+                Task::done(TcGuiMessage::ExportBandwidthHistory {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    format,
+                })
+            }
+            // Display styles are persisted with the UI settings
+            TcInterfaceMessage::AccentColorSelected(color) => {
+                Task::done(TcGuiMessage::SetInterfaceColor {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    color,
+                })
+            }
+            TcInterfaceMessage::DisplayLabelChanged(label) => {
+                Task::done(TcGuiMessage::SetInterfaceLabel {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    label,
+                })
+            }
             // Diagnostics messages - StartDiagnostics triggers backend query
             TcInterfaceMessage::StartDiagnostics => Task::done(TcGuiMessage::RunDiagnostics {
                 backend_name: backend_name.clone(),
                 namespace: namespace.clone(),
                 interface: interface_name.clone(),
+                test: DiagnosticsTest::Standard,
+            }),
+            TcInterfaceMessage::PingTargetChanged(_) => Task::none(),
+            TcInterfaceMessage::ProbeTargetChanged(_) => Task::none(),
+            TcInterfaceMessage::ReadKernelTc => Task::done(TcGuiMessage::RunDiagnostics {
+                backend_name: backend_name.clone(),
+                namespace: namespace.clone(),
+                interface: interface_name.clone(),
+                test: DiagnosticsTest::ReconcileTc,
             }),
+            // Taking over the kernel's state is UI-only: it is already live
+            TcInterfaceMessage::SyncFromKernel => Task::none(),
+            TcInterfaceMessage::StartThroughputProbe => match tc_interface.requested_probe() {
+                Ok(test) => Task::done(TcGuiMessage::RunDiagnostics {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    test,
+                }),
+                Err(_) => Task::none(),
+            },
+            TcInterfaceMessage::StartPing => match tc_interface.requested_ping() {
+                Ok(test) => Task::done(TcGuiMessage::RunDiagnostics {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    test,
+                }),
+                Err(_) => Task::none(),
+            },
             // DiagnosticsComplete and DismissDiagnostics are UI-only state updates
             TcInterfaceMessage::DiagnosticsComplete(_) => Task::none(),
             TcInterfaceMessage::DismissDiagnostics => Task::none(),
+            TcInterfaceMessage::ToggleDiagnosticsHistory => Task::none(),
+            TcInterfaceMessage::ShowQdiscTree => Task::done(TcGuiMessage::RunQdiscTree {
+                backend_name: backend_name.clone(),
+                namespace: namespace.clone(),
+                interface: interface_name.clone(),
+            }),
+            TcInterfaceMessage::QdiscTreeLoaded(_) | TcInterfaceMessage::DismissQdiscTree => {
+                Task::none()
+            }
+            TcInterfaceMessage::CompareRateLimiters => {
+                Task::done(TcGuiMessage::RunRateComparison {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                    rate_kbps: tc_interface.comparison_rate_kbps(),
+                })
+            }
+            TcInterfaceMessage::RateComparisonLoaded(_)
+            | TcInterfaceMessage::DismissRateComparison => Task::none(),
+            TcInterfaceMessage::ShowConfigHistory => Task::done(TcGuiMessage::RunConfigHistory {
+                backend_name: backend_name.clone(),
+                namespace: namespace.clone(),
+                interface: interface_name.clone(),
+            }),
+            TcInterfaceMessage::ConfigHistoryLoaded(_)
+            | TcInterfaceMessage::DismissConfigHistory => Task::none(),
+            // The history is kept per backend so it outlives the interface
+            TcInterfaceMessage::ClearDiagnosticsHistory => {
+                Task::done(TcGuiMessage::ClearDiagnosticsHistory {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace.clone(),
+                    interface: interface_name.clone(),
+                })
+            }
+            // Adopting the backend state only updates the UI: the backend
+            // already has that configuration applied
+            TcInterfaceMessage::AdoptBackendState => Task::none(),
         };
 
         let backend_copy = backend_name.clone();
@@ -1304,6 +1315,24 @@ pub fn handle_reset_ui_state(ui_state: &mut UiStateManager) -> Task<TcGuiMessage
     Task::none()
 }
 
+/// Handles collapsing or expanding a backend.
+pub fn handle_toggle_backend_visibility(
+    ui_state: &mut UiStateManager,
+    backend_name: String,
+) -> Task<TcGuiMessage> {
+    ui_state.toggle_backend_visibility(&backend_name);
+    info!(
+        "Toggled backend '{}' - now {}",
+        backend_name,
+        if ui_state.is_backend_hidden(&backend_name) {
+            "collapsed"
+        } else {
+            "expanded"
+        }
+    );
+    Task::none()
+}
+
 /// Handles showing all hidden backends.
 pub fn handle_show_all_backends(ui_state: &mut UiStateManager) -> Task<TcGuiMessage> {
     ui_state.show_all_backends();
@@ -1311,10 +1340,74 @@ pub fn handle_show_all_backends(ui_state: &mut UiStateManager) -> Task<TcGuiMess
     Task::none()
 }
 
+/// Check settings before they are sent, against the backend's reported
+/// capabilities once known, and record the outcome on the interface, so the
+/// offending control shows the error until the next attempt. Returns whether
+/// the apply may go ahead.
+fn validate_before_apply(
+    backend_manager: &mut BackendManager,
+    backend_name: &str,
+    namespace: &str,
+    interface: &str,
+    config: &TcNetemConfig,
+) -> bool {
+    let capabilities = backend_manager
+        .backends()
+        .get(backend_name)
+        .and_then(|backend| backend.netem_capabilities.as_ref());
+    let error = match capabilities {
+        Some(capabilities) => config.validate_for(capabilities).err(),
+        None => config.validate_features().err(),
+    };
+    if let Some((feature, e)) = &error {
+        warn!(
+            "Not applying invalid {:?} settings to {}/{}: {}",
+            feature, namespace, interface, e
+        );
+    }
+    if let Some(tc_interface) = backend_manager
+        .backends_mut()
+        .get_mut(backend_name)
+        .and_then(|backend| backend.namespaces.get_mut(namespace))
+        .and_then(|group| group.tc_interfaces.get_mut(interface))
+    {
+        tc_interface.set_validation_error(error.clone());
+    }
+    error.is_none()
+}
+
+/// Hold back an apply to an interface carrying the backend's traffic until
+/// the user confirms it on the card. Returns `None` when held back, else
+/// whether to send the confirmation along.
+fn confirm_management_before_apply(
+    backend_manager: &mut BackendManager,
+    backend_name: &str,
+    namespace: &str,
+    interface: &str,
+) -> Option<bool> {
+    let Some(tc_interface) = backend_manager
+        .backends_mut()
+        .get_mut(backend_name)
+        .and_then(|backend| backend.namespaces.get_mut(namespace))
+        .and_then(|group| group.tc_interfaces.get_mut(interface))
+    else {
+        return Some(false);
+    };
+    if tc_interface.hold_for_management_confirmation() {
+        warn!(
+            "Holding back TC change on management interface {}/{} until confirmed",
+            namespace, interface
+        );
+        return None;
+    }
+    Some(tc_interface.management_confirmed())
+}
+
 /// Handles TC operations (apply/remove).
 #[allow(clippy::too_many_arguments)] // Legacy handler maintained for backward compatibility
 pub fn handle_apply_tc(
     query_manager: &QueryManager,
+    backend_manager: &mut BackendManager,
     backend_name: String,
     namespace: String,
     interface: String,
@@ -1332,6 +1425,66 @@ pub fn handle_apply_tc(
     corrupt_correlation: Option<f32>,
     rate_limit_kbps: Option<u32>,
 ) -> Task<TcGuiMessage> {
+    // The legacy parameters carry no delay distribution, loss model, rate
+    // limit shaper, slot, ECN flag, seed or direction, so those come from
+    // the interface's own state
+    let (delay_distribution, loss_gemodel, rate_limit_shaper, slot, loss_ecn, seed, direction) =
+        backend_manager
+            .backends()
+            .get(&backend_name)
+            .and_then(|backend| backend.namespaces.get(&namespace))
+            .and_then(|group| group.tc_interfaces.get(&interface))
+            .map(|tc_interface| {
+                let current = tc_interface.current_configuration();
+                (
+                    current.delay_distribution,
+                    current.loss_gemodel,
+                    current.rate_limit_shaper,
+                    current.slot,
+                    current.loss_ecn,
+                    current.seed,
+                    tc_interface.direction(),
+                )
+            })
+            .unwrap_or_default();
+    let config = TcNetemConfig::from_legacy_params(
+        loss,
+        correlation,
+        delay_ms,
+        delay_jitter_ms,
+        delay_correlation,
+        duplicate_percent,
+        duplicate_correlation,
+        reorder_percent,
+        reorder_correlation,
+        reorder_gap,
+        corrupt_percent,
+        corrupt_correlation,
+        rate_limit_kbps,
+    )
+    .with_extensions(
+        delay_distribution,
+        loss_gemodel,
+        rate_limit_shaper,
+        slot.clone(),
+        loss_ecn,
+        seed,
+    );
+    if !validate_before_apply(
+        backend_manager,
+        &backend_name,
+        &namespace,
+        &interface,
+        &config,
+    ) {
+        return Task::none();
+    }
+    let Some(confirm_management) =
+        confirm_management_before_apply(backend_manager, &backend_name, &namespace, &interface)
+    else {
+        return Task::none();
+    };
+
     if let Err(e) = query_manager.apply_tc(
         backend_name.clone(),
         namespace,
@@ -1349,12 +1502,79 @@ pub fn handle_apply_tc(
         corrupt_percent,
         corrupt_correlation,
         rate_limit_kbps,
+        delay_distribution,
+        loss_gemodel,
+        rate_limit_shaper,
+        slot,
+        config.loss.ecn,
+        config.seed,
+        direction,
+        confirm_management,
     ) {
         tracing::error!("Failed to apply TC: {}", e);
     }
     Task::none()
 }
 
+/// Handles applying TC to both ends of a veth pair.
+pub fn handle_apply_tc_to_veth_pair(
+    query_manager: &QueryManager,
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+    config: &TcConfiguration,
+) -> Task<TcGuiMessage> {
+    let netem_config = TcNetemConfig::from_legacy_params(
+        config.loss,
+        config.correlation,
+        config.delay_ms,
+        config.delay_jitter_ms,
+        config.delay_correlation,
+        config.duplicate_percent,
+        config.duplicate_correlation,
+        config.reorder_percent,
+        config.reorder_correlation,
+        config.reorder_gap,
+        config.corrupt_percent,
+        config.corrupt_correlation,
+        config.rate_limit_kbps,
+    );
+    if !validate_before_apply(
+        backend_manager,
+        &backend_name,
+        &namespace,
+        &interface,
+        &netem_config,
+    ) {
+        return Task::none();
+    }
+    let Some(confirm_management) =
+        confirm_management_before_apply(backend_manager, &backend_name, &namespace, &interface)
+    else {
+        return Task::none();
+    };
+    let direction = backend_manager
+        .backends()
+        .get(&backend_name)
+        .and_then(|backend| backend.namespaces.get(&namespace))
+        .and_then(|group| group.tc_interfaces.get(&interface))
+        .map(|tc_interface| tc_interface.direction())
+        .unwrap_or_default();
+
+    if let Err(e) = query_manager.apply_tc_to_veth_pair(
+        backend_name,
+        namespace,
+        interface,
+        config,
+        direction,
+        confirm_management,
+    ) {
+        tracing::error!("Failed to apply TC to veth pair: {}", e);
+    }
+    Task::none()
+}
+
 /// Handles TC removal operations (clears netem qdisc).
 pub fn handle_remove_tc(
     query_manager: &QueryManager,
@@ -1394,12 +1614,41 @@ pub fn handle_disable_interface(
     Task::none()
 }
 
+/// Handles interface MTU changes.
+pub fn handle_set_interface_mtu(
+    query_manager: &QueryManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+    mtu: u32,
+) -> Task<TcGuiMessage> {
+    if let Err(e) = query_manager.set_interface_mtu(backend_name, namespace, interface, mtu) {
+        tracing::error!("Failed to set interface MTU: {}", e);
+    }
+    Task::none()
+}
+
+/// Handles adding or removing an interface address.
+pub fn handle_change_interface_address(
+    query_manager: &QueryManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+    operation: InterfaceControlOperation,
+) -> Task<TcGuiMessage> {
+    if let Err(e) =
+        query_manager.change_interface_address(backend_name, namespace, interface, operation)
+    {
+        tracing::error!("Failed to change interface address: {}", e);
+    }
+    Task::none()
+}
+
 /// Handles backend cleanup operations.
 pub fn handle_cleanup_stale_backends(
     backend_manager: &mut BackendManager,
     bandwidth_history: &mut crate::bandwidth_history::BandwidthHistoryManager,
     _query_manager: &mut QueryManager,
-    ui_state: &mut UiStateManager,
     scenario_manager: &mut ScenarioManager,
 ) -> Task<TcGuiMessage> {
     let current_time = std::time::SystemTime::now()
@@ -1436,10 +1685,8 @@ pub fn handle_cleanup_stale_backends(
                     .sum::<usize>()
             );
 
-            // Temporarily toggle backend visibility before cleanup (demo usage of unused method)
-            ui_state.toggle_backend_visibility(backend_name);
-            // Clean up UI state for this backend
-            ui_state.cleanup_backend_state(backend_name);
+            // Collapse state is keyed by origin and persisted, so it is kept
+            // for when the backend comes back
             // Clean up scenario state for this backend
             scenario_manager.cleanup_backend_state(backend_name);
             // Clean up bandwidth history for this backend
@@ -1471,14 +1718,17 @@ pub fn handle_run_diagnostics(
     backend_name: String,
     namespace: String,
     interface: String,
+    test: DiagnosticsTest,
 ) -> Task<TcGuiMessage> {
     info!(
         "Running diagnostics for {}/{}/{}",
         backend_name, namespace, interface
     );
 
-    // Mark diagnostics as running in the interface state
-    if let Some(backend_group) = backend_manager.backends_mut().get_mut(&backend_name)
+    // Mark diagnostics as running in the interface state; the other tests
+    // were marked by the card they were started from
+    if test == DiagnosticsTest::Standard
+        && let Some(backend_group) = backend_manager.backends_mut().get_mut(&backend_name)
         && let Some(namespace_group) = backend_group.namespaces.get_mut(&namespace)
         && let Some(tc_interface) = namespace_group.tc_interfaces.get_mut(&interface)
     {
@@ -1486,9 +1736,12 @@ pub fn handle_run_diagnostics(
     }
 
     // Send diagnostics query to backend
-    if let Err(e) =
-        query_manager.run_diagnostics(backend_name.clone(), namespace.clone(), interface.clone())
-    {
+    if let Err(e) = query_manager.run_diagnostics(
+        backend_name.clone(),
+        namespace.clone(),
+        interface.clone(),
+        test,
+    ) {
         warn!("Failed to run diagnostics: {}", e);
     }
 
@@ -1501,20 +1754,300 @@ pub fn handle_diagnostics_result(
     backend_name: String,
     namespace: String,
     interface: String,
-    response: tcgui_shared::DiagnosticsResponse,
+    response: Box<tcgui_shared::DiagnosticsResponse>,
 ) -> Task<TcGuiMessage> {
     info!(
         "Received diagnostics result for {}/{}/{}: {}",
         backend_name, namespace, interface, response.message
     );
 
+    let Some(backend_group) = backend_manager.backends_mut().get_mut(&backend_name) else {
+        return Task::none();
+    };
+    backend_group
+        .diagnostics_history
+        .record(&namespace, &interface, (*response).clone());
+
     // Update the interface with diagnostics result
+    if let Some(namespace_group) = backend_group.namespaces.get_mut(&namespace)
+        && let Some(tc_interface) = namespace_group.tc_interfaces.get_mut(&interface)
+    {
+        let _ = tc_interface.update(TcInterfaceMessage::DiagnosticsComplete(response));
+    }
+
+    Task::none()
+}
+
+/// Handles fetching the qdisc tree of an interface.
+pub fn handle_run_qdisc_tree(
+    query_manager: &QueryManager,
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+) -> Task<TcGuiMessage> {
+    let Some(tc_interface) = backend_manager
+        .backends_mut()
+        .get_mut(&backend_name)
+        .and_then(|backend_group| backend_group.namespaces.get_mut(&namespace))
+        .and_then(|namespace_group| namespace_group.tc_interfaces.get_mut(&interface))
+    else {
+        return Task::none();
+    };
+
+    let _ = tc_interface.update(TcInterfaceMessage::ShowQdiscTree);
+    if let Err(e) = query_manager.request_qdisc_tree(backend_name, namespace, interface) {
+        let _ = tc_interface.update(TcInterfaceMessage::QdiscTreeLoaded(Err(e)));
+    }
+
+    Task::none()
+}
+
+/// Handles a qdisc tree (or the query error) from a backend.
+pub fn handle_qdisc_tree_result(
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+    result: Result<tcgui_shared::qdisc_tree::TcTreeResponse, String>,
+) -> Task<TcGuiMessage> {
+    match &result {
+        Ok(response) => info!(
+            "Received qdisc tree for {}/{}/{}: {} nodes",
+            backend_name,
+            namespace,
+            interface,
+            response.nodes().len()
+        ),
+        Err(e) => warn!(
+            "Qdisc tree query for {}/{}/{} failed: {}",
+            backend_name, namespace, interface, e
+        ),
+    }
+
     if let Some(backend_group) = backend_manager.backends_mut().get_mut(&backend_name)
         && let Some(namespace_group) = backend_group.namespaces.get_mut(&namespace)
         && let Some(tc_interface) = namespace_group.tc_interfaces.get_mut(&interface)
     {
-        let _ = tc_interface.update(TcInterfaceMessage::DiagnosticsComplete(response));
+        let _ = tc_interface.update(TcInterfaceMessage::QdiscTreeLoaded(result));
     }
 
     Task::none()
 }
+
+/// Handles starting a netem vs tbf comparison on an interface.
+pub fn handle_run_rate_comparison(
+    query_manager: &QueryManager,
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+    rate_kbps: u32,
+) -> Task<TcGuiMessage> {
+    let Some(tc_interface) = backend_manager
+        .backends_mut()
+        .get_mut(&backend_name)
+        .and_then(|backend_group| backend_group.namespaces.get_mut(&namespace))
+        .and_then(|namespace_group| namespace_group.tc_interfaces.get_mut(&interface))
+    else {
+        return Task::none();
+    };
+
+    let _ = tc_interface.update(TcInterfaceMessage::CompareRateLimiters);
+    if let Err(e) =
+        query_manager.request_rate_comparison(backend_name, namespace, interface, rate_kbps)
+    {
+        let _ = tc_interface.update(TcInterfaceMessage::RateComparisonLoaded(Err(e)));
+    }
+
+    Task::none()
+}
+
+/// Handles a netem vs tbf comparison (or the query error) from a backend.
+pub fn handle_rate_comparison_result(
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+    result: Result<tcgui_shared::rate_comparison::RateComparisonResponse, String>,
+) -> Task<TcGuiMessage> {
+    match &result {
+        Ok(response) if !response.restored => warn!(
+            "Rate comparison on {}/{}/{} could not confirm the original configuration",
+            backend_name, namespace, interface
+        ),
+        Ok(response) => info!(
+            "Received rate comparison for {}/{}/{} at {} kbit/s",
+            backend_name, namespace, interface, response.rate_kbps
+        ),
+        Err(e) => warn!(
+            "Rate comparison on {}/{}/{} failed: {}",
+            backend_name, namespace, interface, e
+        ),
+    }
+
+    if let Some(backend_group) = backend_manager.backends_mut().get_mut(&backend_name)
+        && let Some(namespace_group) = backend_group.namespaces.get_mut(&namespace)
+        && let Some(tc_interface) = namespace_group.tc_interfaces.get_mut(&interface)
+    {
+        let _ = tc_interface.update(TcInterfaceMessage::RateComparisonLoaded(result));
+    }
+
+    Task::none()
+}
+
+/// Handles fetching the configuration history of an interface.
+pub fn handle_run_config_history(
+    query_manager: &QueryManager,
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+) -> Task<TcGuiMessage> {
+    let Some(tc_interface) = backend_manager
+        .backends_mut()
+        .get_mut(&backend_name)
+        .and_then(|backend_group| backend_group.namespaces.get_mut(&namespace))
+        .and_then(|namespace_group| namespace_group.tc_interfaces.get_mut(&interface))
+    else {
+        return Task::none();
+    };
+
+    let _ = tc_interface.update(TcInterfaceMessage::ShowConfigHistory);
+    if let Err(e) = query_manager.request_config_history(backend_name, namespace, interface) {
+        let _ = tc_interface.update(TcInterfaceMessage::ConfigHistoryLoaded(Err(e)));
+    }
+
+    Task::none()
+}
+
+/// Handles a configuration history (or the query error) from a backend.
+pub fn handle_config_history_result(
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+    result: Result<tcgui_shared::config_history::ConfigHistoryResponse, String>,
+) -> Task<TcGuiMessage> {
+    match &result {
+        Ok(response) => info!(
+            "Received configuration history for {}/{}/{}: {} entries",
+            backend_name,
+            namespace,
+            interface,
+            response.entries.len()
+        ),
+        Err(e) => warn!(
+            "Configuration history query for {}/{}/{} failed: {}",
+            backend_name, namespace, interface, e
+        ),
+    }
+
+    if let Some(backend_group) = backend_manager.backends_mut().get_mut(&backend_name)
+        && let Some(namespace_group) = backend_group.namespaces.get_mut(&namespace)
+        && let Some(tc_interface) = namespace_group.tc_interfaces.get_mut(&interface)
+    {
+        let _ = tc_interface.update(TcInterfaceMessage::ConfigHistoryLoaded(result));
+    }
+
+    Task::none()
+}
+
+/// Sets the diagnostics state of a namespace group, if it is still known.
+fn set_namespace_diagnostics(
+    backend_manager: &mut BackendManager,
+    backend_name: &str,
+    namespace: &str,
+    state: NamespaceDiagnostics,
+) {
+    if let Some(backend_group) = backend_manager.backends_mut().get_mut(backend_name)
+        && let Some(namespace_group) = backend_group.namespaces.get_mut(namespace)
+    {
+        namespace_group.diagnostics = state;
+    }
+}
+
+/// Handles running diagnostics on every interface of a namespace.
+pub fn handle_run_namespace_diagnostics(
+    query_manager: &QueryManager,
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+) -> Task<TcGuiMessage> {
+    info!(
+        "Running diagnostics for all interfaces in {}/{}",
+        backend_name, namespace
+    );
+
+    let state =
+        match query_manager.run_namespace_diagnostics(backend_name.clone(), namespace.clone()) {
+            Ok(()) => NamespaceDiagnostics::Running,
+            Err(e) => {
+                warn!("Failed to run namespace diagnostics: {}", e);
+                NamespaceDiagnostics::Failed(e)
+            }
+        };
+    set_namespace_diagnostics(backend_manager, &backend_name, &namespace, state);
+
+    Task::none()
+}
+
+/// Handles the result of a namespace-wide diagnostics run.
+pub fn handle_namespace_diagnostics_result(
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    result: Result<NamespaceDiagnosticsResponse, String>,
+) -> Task<TcGuiMessage> {
+    let state = match result {
+        Ok(response) => {
+            info!(
+                "Namespace diagnostics for {}/{}: {}",
+                backend_name,
+                namespace,
+                response.summary()
+            );
+            NamespaceDiagnostics::Done(response)
+        }
+        Err(e) => {
+            warn!(
+                "Namespace diagnostics for {}/{} failed: {}",
+                backend_name, namespace, e
+            );
+            NamespaceDiagnostics::Failed(e)
+        }
+    };
+    set_namespace_diagnostics(backend_manager, &backend_name, &namespace, state);
+
+    Task::none()
+}
+
+/// Handles clearing the diagnostics history of one interface.
+pub fn handle_clear_diagnostics_history(
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+    interface: String,
+) -> Task<TcGuiMessage> {
+    if let Some(backend_group) = backend_manager.backends_mut().get_mut(&backend_name) {
+        backend_group
+            .diagnostics_history
+            .clear(&namespace, &interface);
+    }
+    Task::none()
+}
+
+/// Handles dismissing a namespace diagnostics summary.
+pub fn handle_dismiss_namespace_diagnostics(
+    backend_manager: &mut BackendManager,
+    backend_name: String,
+    namespace: String,
+) -> Task<TcGuiMessage> {
+    set_namespace_diagnostics(
+        backend_manager,
+        &backend_name,
+        &namespace,
+        NamespaceDiagnostics::Idle,
+    );
+    Task::none()
+}
@@ -1,15 +1,26 @@
 use tcgui_shared::{
-    BackendHealthStatus, BandwidthUpdate, DiagnosticsRequest, DiagnosticsResponse,
-    InterfaceControlRequest, InterfaceControlResponse, NetworkInterface, TcConfigUpdate, TcRequest,
-    TcResponse, TcStatisticsUpdate,
+    BackendHealthStatus, BandwidthUpdate, DelayDistribution, DiagnosticsRequest,
+    DiagnosticsResponse, DiagnosticsTest, ForceRefreshResponse, InterfaceControlBatchRequest,
+    InterfaceControlBatchResponse, InterfaceControlRequest, InterfaceControlResponse, LossModel,
+    NamespaceDiagnosticsRequest, NamespaceDiagnosticsResponse, NetemCapabilities, NetworkInterface,
+    RateLimitMode, StatsRequest, StatsResponse, TcConfigUpdate, TcConfiguration, TcDirection,
+    TcFeature, TcFeatureClearRequest, TcFeatureClearResponse, TcRequest, TcResponse,
+    TcStatisticsUpdate,
+    config_history::{ConfigHistoryRequest, ConfigHistoryResponse},
     presets::CustomPreset,
+    qdisc_tree::{TcTreeRequest, TcTreeResponse},
+    rate_comparison::{RateComparisonRequest, RateComparisonResponse},
     scenario::{
-        NetworkScenario, ScenarioExecutionRequest, ScenarioExecutionResponse,
+        NetworkScenario, ScenarioExecution, ScenarioExecutionRequest, ScenarioExecutionResponse,
         ScenarioExecutionUpdate, ScenarioRequest, ScenarioResponse,
     },
 };
 use tokio::sync::mpsc;
 
+use crate::bandwidth_history::HistoryExportFormat;
+use crate::inspector::InspectorEntry;
+use crate::interface_style::AccentColor;
+
 // NOTE (keyspace-v2): every `backend_name` field on the query-message structs
 // below now carries the **host origin** (`h-<12hex>`), not the operator-chosen
 // display label. The zenoh_manager parses it with `RemoteOrigin::parse` to build
@@ -49,14 +60,47 @@ pub struct InterfaceControlQueryMessage {
     pub response_sender: Option<mpsc::UnboundedSender<(String, InterfaceControlResponse)>>,
 }
 
+/// Diagnostics query: one interface, every interface of a namespace, the
+/// qdisc tree of one interface, a netem vs tbf comparison on one, the
+/// configuration history of one, or its current statistics
+#[derive(Debug, Clone)]
+pub enum DiagnosticsQuery {
+    Interface(DiagnosticsRequest),
+    Namespace(NamespaceDiagnosticsRequest),
+    Tree(TcTreeRequest),
+    RateComparison(RateComparisonRequest),
+    ConfigHistory(ConfigHistoryRequest),
+    Stats(StatsRequest),
+}
+
 /// Message for diagnostics query operations
 #[derive(Debug, Clone)]
 pub struct DiagnosticsQueryMessage {
     pub backend_name: String,
-    pub request: DiagnosticsRequest,
+    pub request: DiagnosticsQuery,
     pub response_sender: Option<mpsc::UnboundedSender<(String, DiagnosticsResponse)>>,
 }
 
+/// Message asking a backend to republish all of its state
+#[derive(Debug, Clone)]
+pub struct RefreshQueryMessage {
+    pub backend_name: String,
+}
+
+/// Message for bringing several interfaces of one namespace up or down
+#[derive(Debug, Clone)]
+pub struct InterfaceBatchQueryMessage {
+    pub backend_name: String,
+    pub request: InterfaceControlBatchRequest,
+}
+
+/// Message for removing one impairment from several interfaces of one namespace
+#[derive(Debug, Clone)]
+pub struct FeatureClearQueryMessage {
+    pub backend_name: String,
+    pub request: TcFeatureClearRequest,
+}
+
 /// Frontend application messages with new communication architecture
 #[derive(Debug, Clone)]
 pub enum TcGuiMessage {
@@ -72,12 +116,18 @@ pub enum TcGuiMessage {
         namespace: String,
         interface: String,
     },
+    // Apply the interface changes queued since the last flush.
+    FlushInterfaceUpdates,
     BandwidthUpdate(BandwidthUpdate),
     BackendHealthUpdate(BackendHealthStatus),
     BackendLiveliness {
         backend_name: String,
         alive: bool,
     },
+    NetemCapabilities {
+        backend_name: String,
+        capabilities: NetemCapabilities,
+    },
     TcConfigUpdate(TcConfigUpdate),
     TcStatisticsUpdate(TcStatisticsUpdate),
     // State-plane per-preset upsert / removal (state/tc/preset/{id}).
@@ -114,18 +164,67 @@ pub enum TcGuiMessage {
         namespace: String,
         interface: String,
     },
+    // Executions a backend reported as active (ListActive reply), used to
+    // resynchronise after a reconnect
+    ActiveExecutionsListed {
+        backend_name: String,
+        executions: Vec<ScenarioExecution>,
+    },
     // Query channel setup
     SetupTcQueryChannel(mpsc::UnboundedSender<TcQueryMessage>),
     SetupInterfaceQueryChannel(mpsc::UnboundedSender<InterfaceControlQueryMessage>),
     SetupScenarioQueryChannel(mpsc::UnboundedSender<ScenarioQueryMessage>),
     SetupScenarioExecutionQueryChannel(mpsc::UnboundedSender<ScenarioExecutionQueryMessage>),
     SetupDiagnosticsQueryChannel(mpsc::UnboundedSender<DiagnosticsQueryMessage>),
+    SetupRefreshQueryChannel(mpsc::UnboundedSender<RefreshQueryMessage>),
+    SetupInterfaceBatchQueryChannel(mpsc::UnboundedSender<InterfaceBatchQueryMessage>),
+    SetupFeatureClearQueryChannel(mpsc::UnboundedSender<FeatureClearQueryMessage>),
     ToggleNamespaceVisibility(String, String), // (backend_name, namespace_name)
+    ToggleBackendVisibility(String),           // backend_name
     ShowAllNamespaces,                         // Show all hidden namespaces
     ResetUiState,                              // Reset all UI visibility state
     ShowAllBackends,                           // Show all hidden backends
+    ToggleBackendDetails(String),              // Open/close a backend's details panel (origin)
+    BackendLabelDraftChanged(String),          // Edit the label in the backend details panel
+    SaveBackendLabel(String),                  // Persist the drafted label for a backend (origin)
+    CompareLeftPreset(String),                 // Pick the left preset to compare (preset id)
+    CompareRightPreset(String),                // Pick the right preset to compare (preset id)
     SwitchTab(crate::ui_state::AppTab),        // Switch application tab
     SetInterfaceSearch(String),                // Update the interface-name search filter
+    // Path loss view
+    TogglePathPanel,                           // Open/close the path panel
+    AddPathHop(crate::ui_state::InterfaceKey), // Append an interface to the path
+    RemovePathHop(usize),                      // Remove the hop at this position
+    ClearPath,                                 // Remove every hop
+    CoordinatedActionSelected(crate::coordinated_apply::CoordinatedAction), // Pick the change for all hops
+    CoordinatedAtChanged(String), // Edit when the change applies on all hops
+    StartCoordinatedApply,        // Send the change to every hop for the same instant
+    DismissCoordinatedApply,      // Hide the status of the last coordinated change
+    // Idle disconnect (only with --idle-disconnect)
+    UserActivity, // Mouse or keyboard input
+    IdleCheck,    // Periodic check for idleness
+    // Connection panel
+    ToggleConnectionPanel,              // Open/close the connection panel
+    EndpointInputChanged(String),       // Edit the endpoint typed in the connection panel
+    ConnectEndpoint(String),            // Add a connect endpoint to the session
+    ForgetEndpoint(String),             // Drop an endpoint from the history
+    AdoptDetectedTcToggled(bool),       // Whether TC found on backends replaces the UI settings
+    AnnotateScenarioStepsToggled(bool), // Whether scenario steps are marked on bandwidth charts
+    DropAlertInputChanged(String),      // Edit the drops per second that flag an interface
+    PresetFileInputChanged(String),     // Edit the file custom presets are shared through
+    ExportPresets,                      // Write the custom presets to that file
+    ImportPresets,                      // Add the presets in that file to the custom ones
+    // Keyboard navigation
+    MoveInterfaceFocus(isize), // Move focus between interface cards (+1 next, -1 previous)
+    ActivateFocusedInterface,  // Expand/collapse the focused card's chart
+    ClearInterfaceFocus,       // Drop the interface focus (or close the quick actions menu)
+    // Interface quick actions menu
+    OpenInterfaceMenu(crate::ui_state::InterfaceKey), // Right click on an interface card
+    CloseInterfaceMenu,                               // Click outside the menu
+    InterfaceMenuAction(Box<TcGuiMessage>),           // Close the menu, then run the action
+    CopyToClipboard(String),                          // Put text on the clipboard
+    FocusNextControl,                                 // Tab to the next input field
+    FocusPreviousControl,                             // Shift+Tab to the previous input field
     // Zoom controls
     ZoomIn,
     ZoomOut,
@@ -157,6 +256,19 @@ pub enum TcGuiMessage {
         corrupt_correlation: Option<f32>,
         rate_limit_kbps: Option<u32>,
     },
+    /// Apply `config` to an interface and the other end of its veth pair
+    ApplyTcToVethPair {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        config: TcConfiguration,
+    },
+    // Start or stop recording manual changes on an interface into a scenario
+    ToggleScenarioRecording {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    },
     RemoveTc {
         backend_name: String,
         namespace: String,
@@ -172,6 +284,19 @@ pub enum TcGuiMessage {
         namespace: String,
         interface: String,
     },
+    SetInterfaceMtu {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        mtu: u32,
+    },
+    // Add or remove an interface address
+    ChangeInterfaceAddress {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        operation: tcgui_shared::InterfaceControlOperation,
+    },
     // Scenario operations
     ListScenarios {
         backend_name: String,
@@ -181,6 +306,11 @@ pub enum TcGuiMessage {
         namespace: String,
         interface: String,
     },
+    DismissScenarioExecution {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    },
     PauseScenarioExecution {
         backend_name: String,
         namespace: String,
@@ -196,6 +326,13 @@ pub enum TcGuiMessage {
         namespace: String,
         interface: String,
     },
+    /// Collapse or expand one phase's steps in an execution timeline
+    ToggleExecutionPhase {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        phase: String,
+    },
 
     ShowScenarioDetails {
         scenario: NetworkScenario,
@@ -203,16 +340,30 @@ pub enum TcGuiMessage {
     HideScenarioDetails,
     // Scenario list filter/sort messages
     ScenarioSearchFilterChanged(String),
+    ScenarioTagFilterChanged(Option<String>),
     ScenarioSortOptionChanged(crate::scenario_manager::ScenarioSortOption),
     // Interface selection dialog messages
     ShowInterfaceSelectionDialog {
         backend_name: String,
         scenario_id: String,
     },
+    /// Open the selection dialog to bring interfaces of a backend up or down (origin)
+    ShowInterfaceControlDialog(String),
     HideInterfaceSelectionDialog,
     SelectExecutionNamespace(String),
     ToggleExecutionInterface(String),
+    SelectAllExecutionInterfaces,
+    /// Bring every interface selected in the dialog up or down
+    ConfirmInterfaceControl(tcgui_shared::InterfaceControlOperation),
+    /// Open the selection dialog to remove one impairment from interfaces of
+    /// a backend (origin)
+    ShowFeatureClearDialog(String),
+    /// Pick the impairment the feature clear dialog removes
+    SelectClearFeature(TcFeature),
+    /// Remove the picked impairment from every interface selected in the dialog
+    ConfirmFeatureClear,
     ToggleLoopExecution,
+    ExecutionStartAtChanged(String),
     ConfirmScenarioExecution,
     // Scenario response messages
     ScenarioListResponse {
@@ -225,22 +376,154 @@ pub enum TcGuiMessage {
         backend_name: String,
         namespace: String,
         interface: String,
+        test: DiagnosticsTest,
     },
     DiagnosticsResult {
         backend_name: String,
         namespace: String,
         interface: String,
-        response: DiagnosticsResponse,
+        response: Box<DiagnosticsResponse>,
+    },
+    RunNamespaceDiagnostics {
+        backend_name: String,
+        namespace: String,
+    },
+    NamespaceDiagnosticsResult {
+        backend_name: String,
+        namespace: String,
+        result: Result<NamespaceDiagnosticsResponse, String>,
+    },
+    DismissNamespaceDiagnostics {
+        backend_name: String,
+        namespace: String,
+    },
+    /// Fetch the qdisc/class/filter tree of an interface
+    RunQdiscTree {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    },
+    QdiscTreeResult {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<TcTreeResponse, String>,
+    },
+    /// Shape an interface with netem and then tbf at `rate_kbps` and compare
+    RunRateComparison {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        rate_kbps: u32,
+    },
+    RateComparisonResult {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<RateComparisonResponse, String>,
+    },
+    /// Fetch the configurations a backend recently published for an interface
+    RunConfigHistory {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    },
+    ConfigHistoryResult {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<ConfigHistoryResponse, String>,
+    },
+    /// Current statistics of an interface, queried on demand
+    StatsResult {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<StatsResponse, String>,
+    },
+    ClearDiagnosticsHistory {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    },
+    SetInterfaceColor {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        color: Option<AccentColor>,
+    },
+    SetInterfaceLabel {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        label: String,
+    },
+    // Bandwidth chart export: locate the chart and screenshot the window,
+    // then save the chart as PNG (or its data as CSV)
+    ExportBandwidthChart {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        chart_id: iced::widget::Id,
+    },
+    BandwidthChartCaptured {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        bounds: Option<iced::Rectangle>,
+        screenshot: Option<iced::window::Screenshot>,
+    },
+    // Save the retained bandwidth samples of an interface to a file
+    ExportBandwidthHistory {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        format: HistoryExportFormat,
+    },
+    // Keep a configuration saved from an interface as a custom preset
+    SaveCustomPreset(CustomPreset),
+    // Zenoh inspector (only with --inspector)
+    InspectorRecord(Box<InspectorEntry>),
+    ToggleInspector,
+    ToggleInspectorPause,
+    ClearInspector,
+    InspectorFilterChanged(String),
+
+    /// Ask a backend to republish every interface and TC config (origin)
+    ForceRefresh(String),
+    ForceRefreshResult {
+        backend_name: String,
+        result: Result<ForceRefreshResponse, String>,
+    },
+    InterfaceBatchResult {
+        backend_name: String,
+        result: Result<InterfaceControlBatchResponse, String>,
+    },
+    FeatureClearResult {
+        backend_name: String,
+        result: Result<TcFeatureClearResponse, String>,
     },
 
     /// Result of a TC apply/remove operation, used to surface failures.
     TcOperationResult {
         backend_name: String,
+        namespace: String,
+        interface: String,
         response: TcResponse,
     },
-    /// Result of an interface enable/disable operation, to surface failures.
+    /// A TC apply/remove query was refused on the reply-error channel.
+    TcOperationError {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        error: String,
+    },
+    /// Result of an interface control operation, to surface failures and
+    /// the MTU or addresses it reports.
     InterfaceControlResult {
         backend_name: String,
+        namespace: String,
+        interface: String,
         response: InterfaceControlResponse,
     },
     /// A backend query failed on Zenoh's reply-error channel (RFC 05 §3).
@@ -275,6 +558,11 @@ pub enum ZenohEvent {
         backend_name: String,
         alive: bool,
     },
+    // Netem features a backend supports (reply to the capabilities query)
+    NetemCapabilities {
+        backend_name: String,
+        capabilities: NetemCapabilities,
+    },
     TcConfigUpdate(TcConfigUpdate),
     TcStatisticsUpdate(TcStatisticsUpdate),
     // State-plane per-preset upsert / removal.
@@ -303,12 +591,20 @@ pub enum ZenohEvent {
         namespace: String,
         interface: String,
     },
+    // Reply to a ListActive execution query.
+    ActiveExecutionsListed {
+        backend_name: String,
+        executions: Vec<ScenarioExecution>,
+    },
     // Query channels
     TcQueryChannelReady(mpsc::UnboundedSender<TcQueryMessage>),
     InterfaceQueryChannelReady(mpsc::UnboundedSender<InterfaceControlQueryMessage>),
     ScenarioQueryChannelReady(mpsc::UnboundedSender<ScenarioQueryMessage>),
     ScenarioExecutionQueryChannelReady(mpsc::UnboundedSender<ScenarioExecutionQueryMessage>),
     DiagnosticsQueryChannelReady(mpsc::UnboundedSender<DiagnosticsQueryMessage>),
+    RefreshQueryChannelReady(mpsc::UnboundedSender<RefreshQueryMessage>),
+    InterfaceBatchQueryChannelReady(mpsc::UnboundedSender<InterfaceBatchQueryMessage>),
+    FeatureClearQueryChannelReady(mpsc::UnboundedSender<FeatureClearQueryMessage>),
     // Scenario query responses
     ScenarioResponse {
         backend_name: String,
@@ -319,16 +615,80 @@ pub enum ZenohEvent {
         backend_name: String,
         namespace: String,
         interface: String,
-        response: tcgui_shared::DiagnosticsResponse,
+        response: Box<DiagnosticsResponse>,
+    },
+    /// Namespace-wide diagnostics report, or the query error
+    NamespaceDiagnosticsResponse {
+        backend_name: String,
+        namespace: String,
+        result: Result<NamespaceDiagnosticsResponse, String>,
+    },
+    /// Qdisc tree of one interface, or the query error
+    QdiscTreeResponse {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<TcTreeResponse, String>,
+    },
+    /// netem vs tbf comparison on one interface, or the query error
+    RateComparisonResponse {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<RateComparisonResponse, String>,
+    },
+    /// Configuration history of one interface, or the query error
+    ConfigHistoryResponse {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<ConfigHistoryResponse, String>,
+    },
+    /// Current statistics of one interface, or the query error
+    StatsResponse {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        result: Result<StatsResponse, String>,
+    },
+    /// Copy of a raw message for the inspector (only with --inspector)
+    Inspected(Box<InspectorEntry>),
+    /// Forced refresh summary, or the query error (including the cooldown)
+    ForceRefreshResponse {
+        backend_name: String,
+        result: Result<ForceRefreshResponse, String>,
+    },
+    /// Per-interface outcome of a batch enable/disable, or the query error
+    InterfaceBatchResponse {
+        backend_name: String,
+        result: Result<InterfaceControlBatchResponse, String>,
+    },
+    /// Per-interface outcome of removing one impairment, or the query error
+    FeatureClearResponse {
+        backend_name: String,
+        result: Result<TcFeatureClearResponse, String>,
     },
     /// Result of a TC apply/remove query (used to surface failures in the UI).
     TcOperationResult {
         backend_name: String,
+        namespace: String,
+        interface: String,
         response: TcResponse,
     },
-    /// Result of an interface enable/disable query (to surface failures).
+    /// A TC apply/remove query failed on the reply-error channel; like
+    /// [`ZenohEvent::QueryError`], with the interface it was for.
+    TcOperationError {
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        error: String,
+    },
+    /// Result of an interface control query (to surface failures and the
+    /// reported MTU or addresses).
     InterfaceControlResult {
         backend_name: String,
+        namespace: String,
+        interface: String,
         response: InterfaceControlResponse,
     },
     /// A query failed on Zenoh's reply-error channel (RFC keyspace-v2 05 §3).
@@ -348,6 +708,8 @@ pub enum TcInterfaceMessage {
     LossChanged(f32),
     LossToggled(bool),
     CorrelationChanged(f32),
+    LossModelChanged(LossModel),
+    LossEcnToggled(bool),
 
     // Interface state
     InterfaceToggled(bool),
@@ -357,6 +719,7 @@ pub enum TcInterfaceMessage {
     DelayChanged(f32),
     DelayJitterChanged(f32),
     DelayCorrelationChanged(f32),
+    DelayDistributionChanged(DelayDistribution),
 
     // Duplicate control
     DuplicateToggled(()),
@@ -377,17 +740,90 @@ pub enum TcInterfaceMessage {
     // Rate limit control
     RateLimitToggled(()),
     RateLimitChanged(u32),
+    RateLimitShaperChanged(RateLimitMode),
+
+    // Direction the impairments apply in
+    DirectionChanged(TcDirection),
+
+    // Slot control (a limit of 0 means none)
+    SlotToggled(()),
+    SlotMinDelayChanged(f32),
+    SlotMaxDelayChanged(f32),
+    SlotPacketsChanged(u32),
+    SlotBytesChanged(u32),
 
     // Preset control
     PresetSelected(CustomPreset),
     TogglePresetDropdown,
     ClearAllFeatures,
+    PresetNameChanged(String),
+    SaveAsPreset(String),
 
     // Chart control
     ToggleChart,
+    ExportChart,
+    ExportHistory(HistoryExportFormat),
+    // Display color and label (kept in the UI state, not on the card)
+    AccentColorSelected(Option<AccentColor>),
+    DisplayLabelChanged(String),
 
     // Diagnostics control
     StartDiagnostics,
-    DiagnosticsComplete(DiagnosticsResponse),
+    PingTargetChanged(String),
+    StartPing,
+    ProbeTargetChanged(String),
+    StartThroughputProbe,
+    ReadKernelTc,
+    SyncFromKernel,
+    DiagnosticsComplete(Box<DiagnosticsResponse>),
     DismissDiagnostics,
+    ToggleDiagnosticsHistory,
+    ClearDiagnosticsHistory,
+
+    // Qdisc tree inspection
+    ShowQdiscTree,
+    QdiscTreeLoaded(Result<TcTreeResponse, String>),
+    DismissQdiscTree,
+
+    // netem vs tbf rate limiter comparison
+    CompareRateLimiters,
+    RateComparisonLoaded(Result<RateComparisonResponse, String>),
+    DismissRateComparison,
+
+    // Backend-side configuration history
+    ShowConfigHistory,
+    ConfigHistoryLoaded(Result<ConfigHistoryResponse, String>),
+    DismissConfigHistory,
+
+    // Backend sync control
+    AdoptBackendState,
+
+    // Shorthand entry (e.g. "100ms/5%/10mbit")
+    ShorthandChanged(String),
+    ApplyShorthand,
+
+    // Apply the current settings to this interface and its veth peer
+    ApplyToVethPair,
+
+    // Confirm or cancel impairing the interface carrying the backend's traffic
+    ConfirmManagementImpairment,
+    CancelManagementImpairment,
+
+    // Start or stop recording manual changes into a scenario
+    ToggleRecording,
+
+    // Named configuration layers
+    LayerNameChanged(String),
+    SaveLayer,
+    LayerToggled(usize, bool),
+    RemoveLayer(usize),
+
+    // Interface MTU entry
+    MtuInputChanged(String),
+    SetMtu,
+
+    // Interface address entry ("ip/prefix") and removal
+    AddressInputChanged(String),
+    AddAddress,
+    RemoveAddress(String),
 }
@@ -0,0 +1,323 @@
+//! End-to-end loss along a user-defined path of interfaces.
+//!
+//! An emulated path is often several hops (e.g. veth pairs through a chain of
+//! namespaces), each with its own netem qdisc. A path here is just an ordered
+//! list of [`InterfaceKey`]s; [`summarize_path`] looks up each hop's latest
+//! statistics and combines them. Hops are independent, so a packet survives
+//! the path with probability `Π(1 - pᵢ)` and the composite loss is
+//! `1 - Π(1 - pᵢ)`.
+//!
+//! Everything is derived from the statistics the backend already publishes;
+//! nothing is queried.
+
+use crate::backend_manager::BackendManager;
+use crate::interface::TcInterface;
+use crate::ui_state::InterfaceKey;
+
+/// Observed egress counters of one hop.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HopCounters {
+    /// Packets dropped or failed on egress
+    pub dropped: u64,
+    /// Packets that were offered for transmission (sent + dropped)
+    pub attempted: u64,
+}
+
+impl HopCounters {
+    /// Counters for an interface, preferring the qdisc statistics (which is
+    /// where netem loss is accounted) over the link counters.
+    pub fn from_interface(tc_interface: &TcInterface) -> Self {
+        let (basic, queue) = tc_interface.tc_statistics();
+        let link = tc_interface.bandwidth_stats();
+        let link_failed = link.map_or(0, |s| s.tx_errors);
+
+        match (basic, queue) {
+            (Some(basic), Some(queue)) => {
+                let dropped = u64::from(queue.drops) + link_failed;
+                Self {
+                    dropped,
+                    attempted: basic.packets + dropped,
+                }
+            }
+            _ => match link {
+                Some(stats) => {
+                    let dropped = stats.tx_dropped + stats.tx_errors;
+                    Self {
+                        dropped,
+                        attempted: stats.tx_packets + dropped,
+                    }
+                }
+                None => Self::default(),
+            },
+        }
+    }
+
+    /// Fraction of offered packets that were lost, if anything was offered
+    pub fn loss_ratio(&self) -> Option<f64> {
+        (self.attempted > 0).then(|| self.dropped as f64 / self.attempted as f64)
+    }
+}
+
+/// One hop of a summarized path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathHop {
+    pub key: InterfaceKey,
+    /// `None` when the interface no longer exists (or its backend is gone)
+    pub stats: Option<HopStats>,
+}
+
+/// Figures for a hop that is present.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HopStats {
+    pub counters: HopCounters,
+    /// Loss configured on the hop's netem qdisc (fraction, 0 when off)
+    pub configured_loss: f64,
+}
+
+/// Per-hop figures and the composite along the whole path.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PathSummary {
+    pub hops: Vec<PathHop>,
+    /// Composite of the observed per-hop loss ratios (hops without traffic
+    /// count as lossless); `None` when no hop has seen traffic
+    pub observed_loss: Option<f64>,
+    /// Composite of the configured netem loss of the present hops
+    pub configured_loss: f64,
+}
+
+impl PathSummary {
+    /// Number of hops that could not be found
+    pub fn missing_hops(&self) -> usize {
+        self.hops.iter().filter(|hop| hop.stats.is_none()).count()
+    }
+}
+
+/// Combine independent per-hop loss fractions into the path's loss.
+pub fn composite_loss(ratios: impl IntoIterator<Item = f64>) -> f64 {
+    1.0 - ratios
+        .into_iter()
+        .map(|p| 1.0 - p.clamp(0.0, 1.0))
+        .product::<f64>()
+}
+
+/// Look up every hop of `path` and compute the composite figures. Hops that
+/// have disappeared are reported as missing and left out of the composite.
+pub fn summarize_path(path: &[InterfaceKey], backend_manager: &BackendManager) -> PathSummary {
+    let hops: Vec<PathHop> = path
+        .iter()
+        .map(|key| {
+            let stats = backend_manager
+                .backends()
+                .get(&key.backend_name)
+                .and_then(|backend| backend.namespaces.get(&key.namespace))
+                .and_then(|namespace| namespace.tc_interfaces.get(&key.interface))
+                .map(|tc_interface| HopStats {
+                    counters: HopCounters::from_interface(tc_interface),
+                    configured_loss: if tc_interface.loss_enabled() {
+                        f64::from(tc_interface.loss()) / 100.0
+                    } else {
+                        0.0
+                    },
+                });
+            PathHop {
+                key: key.clone(),
+                stats,
+            }
+        })
+        .collect();
+
+    let present: Vec<&HopStats> = hops.iter().filter_map(|hop| hop.stats.as_ref()).collect();
+    let observed: Vec<Option<f64>> = present.iter().map(|s| s.counters.loss_ratio()).collect();
+    let observed_loss = observed
+        .iter()
+        .any(Option::is_some)
+        .then(|| composite_loss(observed.iter().map(|r| r.unwrap_or(0.0))));
+    let configured_loss = composite_loss(present.iter().map(|s| s.configured_loss));
+
+    PathSummary {
+        hops,
+        observed_loss,
+        configured_loss,
+    }
+}
+
+/// Every interface of every known backend, for picking path hops.
+pub fn path_candidates(backend_manager: &BackendManager) -> Vec<InterfaceKey> {
+    let mut keys: Vec<InterfaceKey> = backend_manager
+        .backends()
+        .iter()
+        .flat_map(|(backend_name, backend)| {
+            backend
+                .namespaces
+                .iter()
+                .flat_map(move |(namespace, group)| {
+                    group
+                        .tc_interfaces
+                        .keys()
+                        .map(move |interface| InterfaceKey {
+                            backend_name: backend_name.clone(),
+                            namespace: namespace.clone(),
+                            interface: interface.clone(),
+                        })
+                })
+        })
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::TcInterfaceMessage;
+    use tcgui_shared::{
+        InterfaceType, NetworkBandwidthStats, NetworkInterface, TcStatsBasic, TcStatsQueue,
+    };
+
+    const ORIGIN: &str = "h-000000000001";
+
+    fn key(namespace: &str, interface: &str) -> InterfaceKey {
+        InterfaceKey {
+            backend_name: ORIGIN.to_string(),
+            namespace: namespace.to_string(),
+            interface: interface.to_string(),
+        }
+    }
+
+    fn add_interface(manager: &mut BackendManager, namespace: &str, interface: &str) {
+        manager.handle_interface_upsert(
+            ORIGIN,
+            NetworkInterface {
+                name: interface.to_string(),
+                index: 1,
+                namespace: namespace.to_string(),
+                is_up: true,
+                is_oper_up: true,
+                has_tc_qdisc: true,
+                interface_type: InterfaceType::Virtual,
+                addresses: Vec::new(),
+                qdisc_kind: None,
+                link_speed_mbps: None,
+                mtu: None,
+                peer_index: None,
+                management: false,
+                description: None,
+            },
+        );
+    }
+
+    fn tc_interface<'a>(manager: &'a mut BackendManager, k: &InterfaceKey) -> &'a mut TcInterface {
+        manager
+            .backends_mut()
+            .get_mut(&k.backend_name)
+            .unwrap()
+            .namespaces
+            .get_mut(&k.namespace)
+            .unwrap()
+            .tc_interfaces
+            .get_mut(&k.interface)
+            .unwrap()
+    }
+
+    fn set_qdisc_stats(manager: &mut BackendManager, k: &InterfaceKey, sent: u64, drops: u32) {
+        tc_interface(manager, k).update_tc_statistics(
+            Some(TcStatsBasic {
+                bytes: sent * 1000,
+                packets: sent,
+            }),
+            Some(TcStatsQueue {
+                drops,
+                ..Default::default()
+            }),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_composite_loss() {
+        assert_eq!(composite_loss([]), 0.0);
+        assert!((composite_loss([0.1]) - 0.1).abs() < 1e-12);
+        // Two 10% hops lose 19% end to end, not 20%
+        assert!((composite_loss([0.1, 0.1]) - 0.19).abs() < 1e-12);
+        assert_eq!(composite_loss([0.5, 1.0]), 1.0);
+    }
+
+    #[test]
+    fn test_counters_fall_back_to_link_stats() {
+        let mut manager = BackendManager::new();
+        let hop = key("ns1", "veth0");
+        add_interface(&mut manager, "ns1", "veth0");
+        tc_interface(&mut manager, &hop).update_bandwidth_stats(NetworkBandwidthStats {
+            rx_bytes: 0,
+            rx_packets: 0,
+            rx_errors: 0,
+            rx_dropped: 0,
+            tx_bytes: 95_000,
+            tx_packets: 95,
+            tx_errors: 2,
+            tx_dropped: 3,
+            timestamp: 0,
+            rx_bytes_per_sec: 0.0,
+            tx_bytes_per_sec: 0.0,
+            rx_packets_per_sec: 0.0,
+            tx_packets_per_sec: 0.0,
+            rx_errors_per_sec: 0.0,
+            tx_errors_per_sec: 0.0,
+            rx_dropped_per_sec: 0.0,
+            tx_dropped_per_sec: 0.0,
+        });
+
+        let counters = HopCounters::from_interface(tc_interface(&mut manager, &hop));
+        assert_eq!(
+            counters,
+            HopCounters {
+                dropped: 5,
+                attempted: 100
+            }
+        );
+        assert_eq!(counters.loss_ratio(), Some(0.05));
+    }
+
+    #[test]
+    fn test_summarize_path_with_missing_hop() {
+        let mut manager = BackendManager::new();
+        let (a, b, gone) = (
+            key("ns1", "veth0"),
+            key("ns2", "veth1"),
+            key("ns3", "veth2"),
+        );
+        add_interface(&mut manager, "ns1", "veth0");
+        add_interface(&mut manager, "ns2", "veth1");
+        set_qdisc_stats(&mut manager, &a, 90, 10);
+        set_qdisc_stats(&mut manager, &b, 80, 20);
+        let _ = tc_interface(&mut manager, &a).update(TcInterfaceMessage::LossToggled(true));
+        let _ = tc_interface(&mut manager, &a).update(TcInterfaceMessage::LossChanged(10.0));
+
+        let summary = summarize_path(&[a.clone(), gone.clone(), b.clone()], &manager);
+        assert_eq!(summary.hops.len(), 3);
+        assert_eq!(summary.missing_hops(), 1);
+        assert!(summary.hops[1].stats.is_none());
+
+        // 10% then 20%: 1 - 0.9 * 0.8
+        assert!((summary.observed_loss.unwrap() - 0.28).abs() < 1e-9);
+        assert!((summary.configured_loss - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_summarize_path_without_traffic() {
+        let mut manager = BackendManager::new();
+        add_interface(&mut manager, "ns1", "veth0");
+
+        let summary = summarize_path(&[key("ns1", "veth0")], &manager);
+        assert_eq!(summary.observed_loss, None);
+        assert_eq!(summary.missing_hops(), 0);
+        assert!(
+            summary.hops[0]
+                .stats
+                .unwrap()
+                .counters
+                .loss_ratio()
+                .is_none()
+        );
+    }
+}
@@ -3,9 +3,16 @@
 //! This module handles TC and interface control query channels,
 //! providing a centralized way to send queries to backends.
 
-use crate::messages::{DiagnosticsQueryMessage, InterfaceControlQueryMessage, TcQueryMessage};
+use crate::messages::{
+    DiagnosticsQuery, DiagnosticsQueryMessage, FeatureClearQueryMessage,
+    InterfaceBatchQueryMessage, InterfaceControlQueryMessage, RefreshQueryMessage, TcQueryMessage,
+};
 use tcgui_shared::{
-    DiagnosticsRequest, InterfaceControlOperation, InterfaceControlRequest, TcOperation, TcRequest,
+    DelayDistribution, DiagnosticsRequest, DiagnosticsTest, GeModel, InterfaceControlBatchRequest,
+    InterfaceControlOperation, InterfaceControlRequest, NamespaceDiagnosticsRequest, RateLimitMode,
+    StatsRequest, TcConfiguration, TcDirection, TcFeature, TcFeatureClearRequest, TcNetemConfig,
+    TcOperation, TcRequest, TcSlotConfig, config_history::ConfigHistoryRequest,
+    qdisc_tree::TcTreeRequest, rate_comparison::RateComparisonRequest,
 };
 use tokio::sync::mpsc;
 use tracing::{error, info};
@@ -18,6 +25,12 @@ pub struct QueryManager {
     interface_query_sender: Option<mpsc::UnboundedSender<InterfaceControlQueryMessage>>,
     /// Channel for sending diagnostics queries to specific backends
     diagnostics_query_sender: Option<mpsc::UnboundedSender<DiagnosticsQueryMessage>>,
+    /// Channel for sending forced refresh queries to specific backends
+    refresh_query_sender: Option<mpsc::UnboundedSender<RefreshQueryMessage>>,
+    /// Channel for sending batch interface control queries
+    interface_batch_query_sender: Option<mpsc::UnboundedSender<InterfaceBatchQueryMessage>>,
+    /// Channel for sending feature clear queries
+    feature_clear_query_sender: Option<mpsc::UnboundedSender<FeatureClearQueryMessage>>,
 }
 
 impl QueryManager {
@@ -27,6 +40,9 @@ impl QueryManager {
             tc_query_sender: None,
             interface_query_sender: None,
             diagnostics_query_sender: None,
+            refresh_query_sender: None,
+            interface_batch_query_sender: None,
+            feature_clear_query_sender: None,
         }
     }
 
@@ -65,6 +81,14 @@ impl QueryManager {
         corrupt_percent: Option<f32>,
         corrupt_correlation: Option<f32>,
         rate_limit_kbps: Option<u32>,
+        delay_distribution: Option<DelayDistribution>,
+        loss_gemodel: Option<GeModel>,
+        rate_limit_shaper: RateLimitMode,
+        slot: Option<TcSlotConfig>,
+        loss_ecn: bool,
+        seed: Option<u64>,
+        direction: TcDirection,
+        confirm_management: bool,
     ) -> Result<(), String> {
         if let Some(sender) = &self.tc_query_sender {
             let request = TcRequest {
@@ -84,7 +108,18 @@ impl QueryManager {
                     corrupt_percent,
                     corrupt_correlation,
                     rate_limit_kbps,
+                    delay_distribution,
+                    loss_gemodel,
+                    rate_limit_shaper,
+                    slot,
+                    loss_ecn,
+                    seed,
                 },
+                apply_to_peer: false,
+                confirm_management,
+                apply_at: None,
+                filter: None,
+                direction,
             };
             let tc_query_message = TcQueryMessage {
                 backend_name: backend_name.clone(),
@@ -113,6 +148,129 @@ impl QueryManager {
         }
     }
 
+    /// Sends an apply TC query for an interface and the other end of its veth
+    /// pair. The backend resolves the peer and falls back to the one end when
+    /// it can't.
+    pub fn apply_tc_to_veth_pair(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        config: &TcConfiguration,
+        direction: TcDirection,
+        confirm_management: bool,
+    ) -> Result<(), String> {
+        if let Some(sender) = &self.tc_query_sender {
+            let request = TcRequest {
+                namespace: namespace.clone(),
+                interface: interface.clone(),
+                operation: TcOperation::Apply {
+                    loss: config.loss,
+                    correlation: config.correlation,
+                    delay_ms: config.delay_ms,
+                    delay_jitter_ms: config.delay_jitter_ms,
+                    delay_correlation: config.delay_correlation,
+                    duplicate_percent: config.duplicate_percent,
+                    duplicate_correlation: config.duplicate_correlation,
+                    reorder_percent: config.reorder_percent,
+                    reorder_correlation: config.reorder_correlation,
+                    reorder_gap: config.reorder_gap,
+                    corrupt_percent: config.corrupt_percent,
+                    corrupt_correlation: config.corrupt_correlation,
+                    rate_limit_kbps: config.rate_limit_kbps,
+                    delay_distribution: config.delay_distribution,
+                    loss_gemodel: config.loss_gemodel,
+                    rate_limit_shaper: config.rate_limit_shaper,
+                    slot: config.slot.clone(),
+                    loss_ecn: config.loss_ecn,
+                    seed: config.seed,
+                },
+                apply_to_peer: true,
+                confirm_management,
+                apply_at: None,
+                filter: None,
+                direction,
+            };
+            let tc_query_message = TcQueryMessage {
+                backend_name: backend_name.clone(),
+                request,
+                response_sender: None,
+            };
+
+            if let Err(e) = sender.send(tc_query_message) {
+                let error_msg = format!(
+                    "Failed to send TC apply query to backend '{}': {}",
+                    backend_name, e
+                );
+                error!("{}", error_msg);
+                return Err(error_msg);
+            }
+
+            info!(
+                "Sent TC apply query to backend '{}' for {}/{} and its veth peer",
+                backend_name, namespace, interface
+            );
+            Ok(())
+        } else {
+            let error_msg = "TC query sender not available".to_string();
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+
+    /// Sends one query applying `config` to several (namespace, interface)
+    /// targets of a backend. The backend applies each target on its own; if
+    /// any fail, it answers with an error naming each of them.
+    pub fn apply_tc_batch(
+        &self,
+        backend_name: String,
+        targets: Vec<(String, String)>,
+        config: TcNetemConfig,
+        confirm_management: bool,
+    ) -> Result<(), String> {
+        // The query is addressed to the first target's key
+        let Some((namespace, interface)) = targets.first().cloned() else {
+            return Err("No interfaces to apply TC to".to_string());
+        };
+        if let Some(sender) = &self.tc_query_sender {
+            let count = targets.len();
+            let request = TcRequest {
+                namespace,
+                interface,
+                operation: TcOperation::ApplyBatch { targets, config },
+                apply_to_peer: false,
+                confirm_management,
+                apply_at: None,
+                filter: None,
+                direction: TcDirection::Egress,
+            };
+            let tc_query_message = TcQueryMessage {
+                backend_name: backend_name.clone(),
+                request,
+                response_sender: None,
+            };
+
+            if let Err(e) = sender.send(tc_query_message) {
+                let error_msg = format!(
+                    "Failed to send TC batch query to backend '{}': {}",
+                    backend_name, e
+                );
+                error!("{}", error_msg);
+                return Err(error_msg);
+            }
+
+            info!(
+                "Sent TC batch query to backend '{}' for {} interfaces",
+                backend_name, count
+            );
+            Ok(())
+        } else {
+            let error_msg = "TC query sender not available".to_string();
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+
     /// Sends a remove TC query to a backend (deletes the netem qdisc).
     pub fn remove_tc(
         &self,
@@ -125,6 +283,11 @@ impl QueryManager {
                 namespace: namespace.clone(),
                 interface: interface.clone(),
                 operation: TcOperation::Remove,
+                apply_to_peer: false,
+                confirm_management: false,
+                apply_at: None,
+                filter: None,
+                direction: TcDirection::Egress,
             };
             let tc_query_message = TcQueryMessage {
                 backend_name: backend_name.clone(),
@@ -153,6 +316,54 @@ impl QueryManager {
         }
     }
 
+    /// Sends a TC query the backend performs at `apply_at` (Unix milliseconds)
+    /// rather than on receipt.
+    pub fn schedule_tc(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        operation: TcOperation,
+        apply_at: u64,
+    ) -> Result<(), String> {
+        if let Some(sender) = &self.tc_query_sender {
+            let request = TcRequest {
+                namespace: namespace.clone(),
+                interface: interface.clone(),
+                operation,
+                apply_to_peer: false,
+                confirm_management: false,
+                apply_at: Some(apply_at),
+                filter: None,
+                direction: TcDirection::Egress,
+            };
+            let tc_query_message = TcQueryMessage {
+                backend_name: backend_name.clone(),
+                request,
+                response_sender: None,
+            };
+
+            if let Err(e) = sender.send(tc_query_message) {
+                let error_msg = format!(
+                    "Failed to send scheduled TC query to backend '{}': {}",
+                    backend_name, e
+                );
+                error!("{}", error_msg);
+                return Err(error_msg);
+            }
+
+            info!(
+                "Sent TC query to backend '{}' for {}/{}, scheduled at {}",
+                backend_name, namespace, interface, apply_at
+            );
+            Ok(())
+        } else {
+            let error_msg = "TC query sender not available".to_string();
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+
     /// Sends an enable interface query to a backend.
     pub fn enable_interface(
         &self,
@@ -233,6 +444,87 @@ impl QueryManager {
         }
     }
 
+    /// Sends a set-MTU query for an interface to a backend.
+    pub fn set_interface_mtu(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        mtu: u32,
+    ) -> Result<(), String> {
+        if let Some(sender) = &self.interface_query_sender {
+            let request = InterfaceControlRequest {
+                namespace: namespace.clone(),
+                interface: interface.clone(),
+                operation: InterfaceControlOperation::SetMtu { mtu },
+            };
+            let query_message = InterfaceControlQueryMessage {
+                backend_name: backend_name.clone(),
+                request,
+                response_sender: None, // The reported MTU comes back as an event
+            };
+
+            if let Err(e) = sender.send(query_message) {
+                let error_msg = format!(
+                    "Failed to send interface MTU query to backend '{}': {}",
+                    backend_name, e
+                );
+                error!("{}", error_msg);
+                return Err(error_msg);
+            }
+
+            info!(
+                "Sent interface MTU query ({}) to backend '{}' for {}/{}",
+                mtu, backend_name, namespace, interface
+            );
+            Ok(())
+        } else {
+            let error_msg = "Interface query sender not available".to_string();
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+
+    /// Sends an add- or remove-address query for an interface to a backend.
+    pub fn change_interface_address(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        operation: InterfaceControlOperation,
+    ) -> Result<(), String> {
+        if let Some(sender) = &self.interface_query_sender {
+            info!(
+                "Sending {:?} to backend '{}' for {}/{}",
+                operation, backend_name, namespace, interface
+            );
+            let request = InterfaceControlRequest {
+                namespace,
+                interface,
+                operation,
+            };
+            let query_message = InterfaceControlQueryMessage {
+                backend_name: backend_name.clone(),
+                request,
+                response_sender: None, // The reported addresses come back as an event
+            };
+
+            if let Err(e) = sender.send(query_message) {
+                let error_msg = format!(
+                    "Failed to send interface address query to backend '{}': {}",
+                    backend_name, e
+                );
+                error!("{}", error_msg);
+                return Err(error_msg);
+            }
+            Ok(())
+        } else {
+            let error_msg = "Interface query sender not available".to_string();
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+
     /// Sets up the diagnostics query channel.
     pub fn setup_diagnostics_query_channel(
         &mut self,
@@ -248,6 +540,7 @@ impl QueryManager {
         backend_name: String,
         namespace: String,
         interface: String,
+        test: DiagnosticsTest,
     ) -> Result<(), String> {
         if let Some(sender) = &self.diagnostics_query_sender {
             let request = DiagnosticsRequest {
@@ -255,10 +548,11 @@ impl QueryManager {
                 interface: interface.clone(),
                 target: None, // Auto-detect target
                 timeout_ms: 5000,
+                test,
             };
             let query_message = DiagnosticsQueryMessage {
                 backend_name: backend_name.clone(),
-                request,
+                request: DiagnosticsQuery::Interface(request),
                 response_sender: None, // Response handled via ZenohEvent
             };
 
@@ -282,6 +576,332 @@ impl QueryManager {
             Err(error_msg)
         }
     }
+
+    /// Sends a namespace-wide diagnostics query to a backend.
+    pub fn run_namespace_diagnostics(
+        &self,
+        backend_name: String,
+        namespace: String,
+    ) -> Result<(), String> {
+        if let Some(sender) = &self.diagnostics_query_sender {
+            let request = NamespaceDiagnosticsRequest {
+                namespace: namespace.clone(),
+                target: None, // Auto-detect target per interface
+                timeout_ms: 5000,
+            };
+            let query_message = DiagnosticsQueryMessage {
+                backend_name: backend_name.clone(),
+                request: DiagnosticsQuery::Namespace(request),
+                response_sender: None, // Response handled via ZenohEvent
+            };
+
+            if let Err(e) = sender.send(query_message) {
+                let error_msg = format!(
+                    "Failed to send namespace diagnostics query to backend '{}': {}",
+                    backend_name, e
+                );
+                error!("{}", error_msg);
+                return Err(error_msg);
+            }
+
+            info!(
+                "Sent namespace diagnostics query to backend '{}' for {}",
+                backend_name, namespace
+            );
+            Ok(())
+        } else {
+            let error_msg = "Diagnostics query sender not available".to_string();
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+
+    /// Asks a backend for the qdisc/class/filter tree of an interface.
+    pub fn request_qdisc_tree(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    ) -> Result<(), String> {
+        let Some(sender) = &self.diagnostics_query_sender else {
+            let error_msg = "Diagnostics query sender not available".to_string();
+            error!("{}", error_msg);
+            return Err(error_msg);
+        };
+
+        let query_message = DiagnosticsQueryMessage {
+            backend_name: backend_name.clone(),
+            request: DiagnosticsQuery::Tree(TcTreeRequest {
+                namespace: namespace.clone(),
+                interface: interface.clone(),
+            }),
+            response_sender: None, // Response handled via ZenohEvent
+        };
+        if let Err(e) = sender.send(query_message) {
+            let error_msg = format!(
+                "Failed to send qdisc tree query to backend '{}': {}",
+                backend_name, e
+            );
+            error!("{}", error_msg);
+            return Err(error_msg);
+        }
+
+        info!(
+            "Sent qdisc tree query to backend '{}' for {}/{}",
+            backend_name, namespace, interface
+        );
+        Ok(())
+    }
+
+    /// Asks a backend to compare netem and tbf at `rate_kbps` on an
+    /// interface, measuring against its default gateway.
+    pub fn request_rate_comparison(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+        rate_kbps: u32,
+    ) -> Result<(), String> {
+        let Some(sender) = &self.diagnostics_query_sender else {
+            let error_msg = "Diagnostics query sender not available".to_string();
+            error!("{}", error_msg);
+            return Err(error_msg);
+        };
+
+        let query_message = DiagnosticsQueryMessage {
+            backend_name: backend_name.clone(),
+            request: DiagnosticsQuery::RateComparison(RateComparisonRequest {
+                namespace: namespace.clone(),
+                interface: interface.clone(),
+                rate_kbps,
+                target: None,
+            }),
+            response_sender: None, // Response handled via ZenohEvent
+        };
+        if let Err(e) = sender.send(query_message) {
+            let error_msg = format!(
+                "Failed to send rate comparison query to backend '{}': {}",
+                backend_name, e
+            );
+            error!("{}", error_msg);
+            return Err(error_msg);
+        }
+
+        info!(
+            "Sent rate comparison query to backend '{}' for {}/{} at {} kbit/s",
+            backend_name, namespace, interface, rate_kbps
+        );
+        Ok(())
+    }
+
+    /// Asks a backend for the configurations it recently published for an
+    /// interface.
+    pub fn request_config_history(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    ) -> Result<(), String> {
+        let Some(sender) = &self.diagnostics_query_sender else {
+            let error_msg = "Diagnostics query sender not available".to_string();
+            error!("{}", error_msg);
+            return Err(error_msg);
+        };
+
+        let query_message = DiagnosticsQueryMessage {
+            backend_name: backend_name.clone(),
+            request: DiagnosticsQuery::ConfigHistory(ConfigHistoryRequest {
+                namespace: namespace.clone(),
+                interface: interface.clone(),
+                limit: None,
+            }),
+            response_sender: None, // Response handled via ZenohEvent
+        };
+        if let Err(e) = sender.send(query_message) {
+            let error_msg = format!(
+                "Failed to send config history query to backend '{}': {}",
+                backend_name, e
+            );
+            error!("{}", error_msg);
+            return Err(error_msg);
+        }
+
+        info!(
+            "Sent config history query to backend '{}' for {}/{}",
+            backend_name, namespace, interface
+        );
+        Ok(())
+    }
+
+    /// Asks a backend for the current statistics of an interface, without
+    /// waiting for its next bandwidth sample.
+    pub fn request_stats(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interface: String,
+    ) -> Result<(), String> {
+        let Some(sender) = &self.diagnostics_query_sender else {
+            let error_msg = "Diagnostics query sender not available".to_string();
+            error!("{}", error_msg);
+            return Err(error_msg);
+        };
+
+        let query_message = DiagnosticsQueryMessage {
+            backend_name: backend_name.clone(),
+            request: DiagnosticsQuery::Stats(StatsRequest {
+                namespace: namespace.clone(),
+                interface: interface.clone(),
+            }),
+            response_sender: None, // Response handled via ZenohEvent
+        };
+        if let Err(e) = sender.send(query_message) {
+            let error_msg = format!(
+                "Failed to send stats query to backend '{}': {}",
+                backend_name, e
+            );
+            error!("{}", error_msg);
+            return Err(error_msg);
+        }
+
+        info!(
+            "Sent stats query to backend '{}' for {}/{}",
+            backend_name, namespace, interface
+        );
+        Ok(())
+    }
+
+    /// Sets up the forced refresh query channel.
+    pub fn setup_refresh_query_channel(
+        &mut self,
+        sender: mpsc::UnboundedSender<RefreshQueryMessage>,
+    ) {
+        info!("Setting up refresh query channel for multi-backend communication");
+        self.refresh_query_sender = Some(sender);
+    }
+
+    /// Asks a backend to republish all interfaces and TC configs, bypassing
+    /// its change detection.
+    pub fn force_refresh(&self, backend_name: String) -> Result<(), String> {
+        let Some(sender) = &self.refresh_query_sender else {
+            let error_msg = "Refresh query sender not available".to_string();
+            error!("{}", error_msg);
+            return Err(error_msg);
+        };
+
+        if let Err(e) = sender.send(RefreshQueryMessage {
+            backend_name: backend_name.clone(),
+        }) {
+            let error_msg = format!(
+                "Failed to send refresh query to backend '{}': {}",
+                backend_name, e
+            );
+            error!("{}", error_msg);
+            return Err(error_msg);
+        }
+
+        info!("Sent forced refresh query to backend '{}'", backend_name);
+        Ok(())
+    }
+
+    /// Sets up the batch interface control query channel.
+    pub fn setup_interface_batch_query_channel(
+        &mut self,
+        sender: mpsc::UnboundedSender<InterfaceBatchQueryMessage>,
+    ) {
+        info!("Setting up interface batch query channel for multi-backend communication");
+        self.interface_batch_query_sender = Some(sender);
+    }
+
+    /// Brings several interfaces of one namespace up or down. The backend
+    /// attempts every interface and reports a result for each.
+    pub fn control_interfaces(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interfaces: Vec<String>,
+        operation: InterfaceControlOperation,
+    ) -> Result<(), String> {
+        let Some(sender) = &self.interface_batch_query_sender else {
+            let error_msg = "Interface batch query sender not available".to_string();
+            error!("{}", error_msg);
+            return Err(error_msg);
+        };
+
+        let count = interfaces.len();
+        let request = InterfaceControlBatchRequest {
+            namespace: namespace.clone(),
+            interfaces,
+            operation: operation.clone(),
+        };
+        if let Err(e) = sender.send(InterfaceBatchQueryMessage {
+            backend_name: backend_name.clone(),
+            request,
+        }) {
+            let error_msg = format!(
+                "Failed to send interface batch query to backend '{}': {}",
+                backend_name, e
+            );
+            error!("{}", error_msg);
+            return Err(error_msg);
+        }
+
+        info!(
+            "Sent {:?} for {} interfaces in {} to backend '{}'",
+            operation, count, namespace, backend_name
+        );
+        Ok(())
+    }
+
+    /// Sets up the feature clear query channel.
+    pub fn setup_feature_clear_query_channel(
+        &mut self,
+        sender: mpsc::UnboundedSender<FeatureClearQueryMessage>,
+    ) {
+        info!("Setting up feature clear query channel for multi-backend communication");
+        self.feature_clear_query_sender = Some(sender);
+    }
+
+    /// Removes one impairment from several interfaces of one namespace,
+    /// keeping their other impairments. The backend reports a result for
+    /// each interface.
+    pub fn clear_feature(
+        &self,
+        backend_name: String,
+        namespace: String,
+        interfaces: Vec<String>,
+        feature: TcFeature,
+    ) -> Result<(), String> {
+        let Some(sender) = &self.feature_clear_query_sender else {
+            let error_msg = "Feature clear query sender not available".to_string();
+            error!("{}", error_msg);
+            return Err(error_msg);
+        };
+
+        let count = interfaces.len();
+        let request = TcFeatureClearRequest {
+            namespace: namespace.clone(),
+            interfaces,
+            feature,
+        };
+        if let Err(e) = sender.send(FeatureClearQueryMessage {
+            backend_name: backend_name.clone(),
+            request,
+        }) {
+            let error_msg = format!(
+                "Failed to send feature clear query to backend '{}': {}",
+                backend_name, e
+            );
+            error!("{}", error_msg);
+            return Err(error_msg);
+        }
+
+        info!(
+            "Sent removal of {} for {} interfaces in {} to backend '{}'",
+            feature, count, namespace, backend_name
+        );
+        Ok(())
+    }
 }
 
 impl Default for QueryManager {
@@ -0,0 +1,246 @@
+//! Recording manual TC changes into a replayable scenario.
+//!
+//! While a recording runs, every configuration the backend confirms for the
+//! target interface is timestamped. Stopping turns the sequence into a
+//! [`NetworkScenario`] whose steps last as long as each configuration was in
+//! place. The last configuration has no later change to end it, so its step
+//! gets [`FINAL_STEP_MS`]; how long it sat before Stop was pressed says little
+//! about how long it should last on replay.
+//!
+//! The result is written in the scenario file format to the user scenario
+//! directory, where it can be edited and is picked up by a backend on the
+//! same host.
+
+use std::path::PathBuf;
+
+use tcgui_shared::TcNetemConfig;
+use tcgui_shared::scenario::{NetworkScenario, ScenarioMetadata, ScenarioRepeat, ScenarioStep};
+use tcgui_shared::scenario_json::scenario_to_json5;
+
+use crate::ui_state::InterfaceKey;
+
+/// Changes closer together than this are folded into one step, so dragging a
+/// slider records where it stopped rather than every position on the way.
+pub const MIN_STEP_MS: u64 = 1000;
+
+/// Duration given to the final, open-ended step.
+pub const FINAL_STEP_MS: u64 = 30_000;
+
+/// Scenario directory below the user's config directory (the backend's
+/// `~/.config/tcgui/scenarios`).
+const USER_SCENARIO_DIR: &str = "tcgui/scenarios";
+
+/// A recording in progress on one interface.
+#[derive(Debug, Clone)]
+pub struct ScenarioRecorder {
+    pub target: InterfaceKey,
+    /// Unix time (ms) the recording started
+    started_at_ms: u64,
+    /// Configurations in the order they took effect, with their unix time (ms)
+    changes: Vec<(u64, TcNetemConfig)>,
+}
+
+impl ScenarioRecorder {
+    /// Start recording `target`, whose configuration is `initial` right now.
+    pub fn start(target: InterfaceKey, initial: TcNetemConfig, now_ms: u64) -> Self {
+        Self {
+            target,
+            started_at_ms: now_ms,
+            changes: vec![(now_ms, initial)],
+        }
+    }
+
+    /// Record that `config` took effect at `now_ms`. Repeats of the current
+    /// configuration are ignored.
+    pub fn record(&mut self, config: TcNetemConfig, now_ms: u64) {
+        let Some((last_at, last_config)) = self.changes.last_mut() else {
+            self.changes.push((now_ms, config));
+            return;
+        };
+        if *last_config == config {
+            return;
+        }
+        if now_ms.saturating_sub(*last_at) < MIN_STEP_MS {
+            *last_config = config;
+            // Folding may land back on the configuration before it
+            let n = self.changes.len();
+            if n > 1 && self.changes[n - 2].1 == self.changes[n - 1].1 {
+                self.changes.pop();
+            }
+        } else {
+            self.changes.push((now_ms, config));
+        }
+    }
+
+    /// Number of steps the scenario would have if stopped now
+    pub fn step_count(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Stop recording and build the scenario.
+    pub fn finish(self) -> NetworkScenario {
+        let mut steps: Vec<ScenarioStep> = self
+            .changes
+            .windows(2)
+            .map(|pair| ScenarioStep {
+                duration_ms: pair[1].0.saturating_sub(pair[0].0).max(1),
+                description: describe_config(&pair[0].1),
+                tc_config: pair[0].1.clone(),
+                ramp: None,
+            })
+            .collect();
+        if let Some((_, last)) = self.changes.last() {
+            steps.push(ScenarioStep {
+                duration_ms: FINAL_STEP_MS,
+                description: describe_config(last),
+                tc_config: last.clone(),
+                ramp: None,
+            });
+        }
+
+        let created_at = self.started_at_ms / 1000;
+        let target = &self.target;
+        NetworkScenario {
+            id: format!("recorded-{}-{}", target.interface, created_at),
+            name: format!("Recorded on {}", target.interface),
+            description: format!(
+                "Manual changes recorded on {}/{}",
+                target.namespace, target.interface
+            ),
+            loop_scenario: false,
+            repeat: ScenarioRepeat::Once,
+            created_at,
+            modified_at: created_at,
+            metadata: ScenarioMetadata {
+                tags: vec!["recorded".to_string()],
+                author: None,
+                category: None,
+                version: "1.0".to_string(),
+                duration_ms: steps.iter().map(|s| s.duration_ms).sum(),
+            },
+            steps,
+            phases: Vec::new(),
+            cleanup_on_failure: true,
+            verify_steps: false,
+            cleanup_policy: None,
+        }
+    }
+}
+
+/// Step description listing the active impairments.
+fn describe_config(config: &TcNetemConfig) -> String {
+    let mut parts = Vec::new();
+    if config.delay.enabled {
+        if config.delay.jitter_ms > 0.0 {
+            parts.push(format!(
+                "delay {}ms ±{}ms",
+                config.delay.base_ms, config.delay.jitter_ms
+            ));
+        } else {
+            parts.push(format!("delay {}ms", config.delay.base_ms));
+        }
+    }
+    if config.loss.enabled {
+        parts.push(format!("loss {}%", config.loss.percentage));
+    }
+    if config.duplicate.enabled {
+        parts.push(format!("duplicate {}%", config.duplicate.percentage));
+    }
+    if config.reorder.enabled {
+        parts.push(format!("reorder {}%", config.reorder.percentage));
+    }
+    if config.corrupt.enabled {
+        parts.push(format!("corrupt {}%", config.corrupt.percentage));
+    }
+    if config.rate_limit.enabled {
+        parts.push(format!("rate {}kbps", config.rate_limit.rate_kbps));
+    }
+
+    if parts.is_empty() {
+        "No impairment".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// Write a recorded scenario to the user scenario directory, returning the
+/// file it was written to.
+pub fn save_to_scenario_dir(scenario: &NetworkScenario) -> Result<PathBuf, String> {
+    let dir = dirs::config_dir()
+        .ok_or_else(|| "no config directory".to_string())?
+        .join(USER_SCENARIO_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("creating {}: {}", dir.display(), e))?;
+
+    let path = dir.join(format!("{}.json5", scenario.id));
+    std::fs::write(&path, scenario_to_json5(scenario))
+        .map_err(|e| format!("writing {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tcgui_shared::TcValidate;
+
+    fn key() -> InterfaceKey {
+        InterfaceKey {
+            backend_name: "h-000000000001".to_string(),
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+        }
+    }
+
+    fn delay(ms: f32) -> TcNetemConfig {
+        let mut config = TcNetemConfig::new();
+        config.delay.enabled = true;
+        config.delay.base_ms = ms;
+        config
+    }
+
+    #[test]
+    fn test_steps_follow_intervals_between_changes() {
+        let mut recorder = ScenarioRecorder::start(key(), TcNetemConfig::new(), 10_000);
+        recorder.record(delay(50.0), 25_000);
+        recorder.record(delay(50.0), 30_000); // republished, not a change
+        recorder.record(delay(200.0), 85_000);
+
+        let scenario = recorder.finish();
+        let durations: Vec<u64> = scenario.steps.iter().map(|s| s.duration_ms).collect();
+        assert_eq!(durations, vec![15_000, 60_000, FINAL_STEP_MS]);
+        assert_eq!(scenario.steps[0].description, "No impairment");
+        assert_eq!(scenario.steps[2].tc_config, delay(200.0));
+        assert_eq!(scenario.metadata.duration_ms, 75_000 + FINAL_STEP_MS);
+        assert!(scenario.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rapid_changes_fold_into_one_step() {
+        let mut recorder = ScenarioRecorder::start(key(), TcNetemConfig::new(), 0);
+        recorder.record(delay(10.0), 5_000);
+        recorder.record(delay(20.0), 5_200);
+        recorder.record(delay(30.0), 5_400);
+        assert_eq!(recorder.step_count(), 2);
+
+        // Dragging back to where it started leaves no step behind
+        recorder.record(TcNetemConfig::new(), 5_600);
+        assert_eq!(recorder.step_count(), 1);
+
+        let scenario = recorder.finish();
+        assert_eq!(scenario.steps.len(), 1);
+        assert_eq!(scenario.steps[0].duration_ms, FINAL_STEP_MS);
+    }
+
+    #[test]
+    fn test_recorded_scenario_round_trips_through_file_format() {
+        let mut recorder = ScenarioRecorder::start(key(), delay(100.0), 0);
+        recorder.record(delay(300.0), 12_500);
+        let scenario = recorder.finish();
+
+        let parsed =
+            tcgui_shared::scenario_json::parse_scenario(&scenario_to_json5(&scenario)).unwrap();
+        assert_eq!(parsed.id, scenario.id);
+        assert_eq!(parsed.steps.len(), 2);
+        assert_eq!(parsed.steps[0].duration_ms, 12_500);
+        assert_eq!(parsed.steps[1].tc_config, delay(300.0));
+    }
+}
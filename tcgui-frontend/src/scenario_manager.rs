@@ -3,16 +3,19 @@
 //! This module provides the ScenarioManager that handles scenario-related state,
 //! Zenoh queries, and coordination between the UI and backend scenario services.
 
-use std::collections::HashMap;
+use chrono::{DateTime, Local, NaiveTime};
+use std::collections::{BTreeSet, HashMap};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 use tcgui_shared::scenario::{
-    NetworkScenario, ScenarioExecution, ScenarioExecutionRequest, ScenarioExecutionUpdate,
-    ScenarioLoadError, ScenarioRequest,
+    ExecutionState, NetworkScenario, ScenarioExecution, ScenarioExecutionRequest,
+    ScenarioExecutionUpdate, ScenarioLoadError, ScenarioRequest,
 };
 
 use crate::messages::{ScenarioExecutionQueryMessage, ScenarioQueryMessage};
+use crate::recording::ScenarioRecorder;
+use crate::ui_state::InterfaceKey;
 
 /// Tracked execution with timestamp for deduplication
 #[derive(Clone, Debug)]
@@ -64,6 +67,8 @@ pub struct ScenarioManager {
     execution_query_sender: Option<mpsc::UnboundedSender<ScenarioExecutionQueryMessage>>,
     /// Search/filter text for scenario list
     search_filter: String,
+    /// Tag the scenario list is restricted to, if any
+    tag_filter: Option<String>,
     /// Current sort option
     sort_option: ScenarioSortOption,
     /// Whether sort is ascending
@@ -72,8 +77,12 @@ pub struct ScenarioManager {
     loading_backends: std::collections::HashSet<String>,
     /// Execution timelines that are collapsed (key: "backend/namespace/interface")
     collapsed_timelines: std::collections::HashSet<String>,
+    /// Phases collapsed in execution timelines (key: "backend/namespace/interface/phase")
+    collapsed_phases: std::collections::HashSet<String>,
     /// Errors that occurred while loading scenario files
     load_errors: HashMap<String, Vec<ScenarioLoadError>>, // backend_name -> errors
+    /// Manual changes being recorded into a scenario, if any
+    recording: Option<ScenarioRecorder>,
 }
 
 impl ScenarioManager {
@@ -112,17 +121,22 @@ impl ScenarioManager {
     pub fn get_available_scenarios(&self, backend_name: &str) -> Vec<NetworkScenario> {
         let mut scenarios = self.get_raw_scenarios(backend_name);
 
+        // Apply tag filter
+        if let Some(tag) = &self.tag_filter {
+            scenarios.retain(|s| s.metadata.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+        }
+
         // Apply search filter
         if !self.search_filter.is_empty() {
             let filter_lower = self.search_filter.to_lowercase();
+            let matches = |field: &str| field.to_lowercase().contains(&filter_lower);
             scenarios.retain(|s| {
-                s.name.to_lowercase().contains(&filter_lower)
-                    || s.id.to_lowercase().contains(&filter_lower)
-                    || s.description.to_lowercase().contains(&filter_lower)
-                    || s.metadata
-                        .tags
-                        .iter()
-                        .any(|t| t.to_lowercase().contains(&filter_lower))
+                matches(&s.name)
+                    || matches(&s.id)
+                    || matches(&s.description)
+                    || s.metadata.tags.iter().any(|t| matches(t))
+                    || s.metadata.category.as_deref().is_some_and(matches)
+                    || s.metadata.author.as_deref().is_some_and(matches)
             });
         }
 
@@ -161,6 +175,34 @@ impl ScenarioManager {
         self.search_filter = filter;
     }
 
+    /// Get the tag the scenario list is restricted to
+    pub fn get_tag_filter(&self) -> Option<&str> {
+        self.tag_filter.as_deref()
+    }
+
+    /// Restrict the scenario list to a tag, or lift the restriction
+    pub fn set_tag_filter(&mut self, tag: Option<String>) {
+        debug!("Setting tag filter to: {:?}", tag);
+        self.tag_filter = tag;
+    }
+
+    /// Check if the scenario list is filtered by search text or tag
+    pub fn has_active_filter(&self) -> bool {
+        !self.search_filter.is_empty() || self.tag_filter.is_some()
+    }
+
+    /// Get the distinct tags of a backend's scenarios, sorted
+    pub fn get_available_tags(&self, backend_name: &str) -> Vec<String> {
+        let tags: BTreeSet<&String> = self
+            .available_scenarios
+            .get(backend_name)
+            .into_iter()
+            .flatten()
+            .flat_map(|s| &s.metadata.tags)
+            .collect();
+        tags.into_iter().cloned().collect()
+    }
+
     /// Get the current sort option
     pub fn get_sort_option(&self) -> ScenarioSortOption {
         self.sort_option
@@ -218,6 +260,16 @@ impl ScenarioManager {
             .unwrap_or_default()
     }
 
+    /// Whether anything needs watching: a running execution or a recording
+    pub fn is_monitoring(&self) -> bool {
+        self.recording.is_some()
+            || self
+                .active_executions
+                .values()
+                .flat_map(|executions| executions.values())
+                .any(|tracked| !tracked.execution.state.is_terminal())
+    }
+
     /// Check if there's an execution running on an interface
     pub fn is_execution_active(
         &self,
@@ -228,7 +280,8 @@ impl ScenarioManager {
         let execution_key = format!("{}/{}", namespace, interface);
         self.active_executions
             .get(backend_name)
-            .is_some_and(|executions| executions.contains_key(&execution_key))
+            .and_then(|executions| executions.get(&execution_key))
+            .is_some_and(|tracked| !tracked.execution.state.is_terminal())
     }
 
     /// Get currently selected scenario
@@ -272,6 +325,32 @@ impl ScenarioManager {
         }
     }
 
+    /// Toggle a phase's step list in an execution timeline
+    pub fn toggle_execution_phase(
+        &mut self,
+        backend_name: &str,
+        namespace: &str,
+        interface: &str,
+        phase: &str,
+    ) {
+        let key = format!("{}/{}/{}/{}", backend_name, namespace, interface, phase);
+        if !self.collapsed_phases.remove(&key) {
+            self.collapsed_phases.insert(key);
+        }
+    }
+
+    /// Check if a phase's step list is collapsed in an execution timeline
+    pub fn is_phase_collapsed(
+        &self,
+        backend_name: &str,
+        namespace: &str,
+        interface: &str,
+        phase: &str,
+    ) -> bool {
+        let key = format!("{}/{}/{}/{}", backend_name, namespace, interface, phase);
+        self.collapsed_phases.contains(&key)
+    }
+
     /// Check if execution timeline is collapsed
     pub fn is_timeline_collapsed(
         &self,
@@ -306,6 +385,74 @@ impl ScenarioManager {
         }
     }
 
+    /// Send a new scenario to a backend
+    pub fn add_scenario(
+        &self,
+        backend_name: &str,
+        scenario: NetworkScenario,
+    ) -> Result<(), String> {
+        if let Some(sender) = &self.scenario_query_sender {
+            let id = scenario.id.clone();
+            let message = ScenarioQueryMessage {
+                backend_name: backend_name.to_string(),
+                request: ScenarioRequest::Add(scenario),
+                response_sender: None,
+            };
+
+            if let Err(e) = sender.send(message) {
+                error!("Failed to send scenario add request: {}", e);
+                return Err(format!("Failed to add scenario: {}", e));
+            }
+
+            info!("Sent scenario '{}' to backend: {}", id, backend_name);
+            Ok(())
+        } else {
+            warn!("Scenario query channel not available");
+            Err("Scenario query channel not available".to_string())
+        }
+    }
+
+    /// Whether manual changes on `key` are being recorded
+    pub fn is_recording(&self, key: &InterfaceKey) -> bool {
+        self.recording.as_ref().is_some_and(|r| &r.target == key)
+    }
+
+    /// Start recording manual changes (only one recording runs at a time)
+    pub fn start_recording(&mut self, recorder: ScenarioRecorder) -> Result<(), String> {
+        if let Some(current) = &self.recording {
+            return Err(format!(
+                "Already recording {}/{}",
+                current.target.namespace, current.target.interface
+            ));
+        }
+        info!(
+            "Recording manual changes on {}/{}",
+            recorder.target.namespace, recorder.target.interface
+        );
+        self.recording = Some(recorder);
+        Ok(())
+    }
+
+    /// Stop the recording in progress and return it
+    pub fn stop_recording(&mut self) -> Option<ScenarioRecorder> {
+        self.recording.take()
+    }
+
+    /// Feed a configuration confirmed for `key` into the recording, if it is
+    /// the recorded interface
+    pub fn record_change(
+        &mut self,
+        key: &InterfaceKey,
+        config: tcgui_shared::TcNetemConfig,
+        now_ms: u64,
+    ) {
+        if let Some(recorder) = self.recording.as_mut()
+            && &recorder.target == key
+        {
+            recorder.record(config, now_ms);
+        }
+    }
+
     /// Start scenario execution
     pub fn start_execution(
         &self,
@@ -314,6 +461,7 @@ impl ScenarioManager {
         namespace: &str,
         interface: &str,
         loop_execution: bool,
+        start_at: Option<u64>,
     ) -> Result<(), String> {
         if let Some(sender) = &self.execution_query_sender {
             let request = ScenarioExecutionRequest::Start {
@@ -321,6 +469,8 @@ impl ScenarioManager {
                 namespace: namespace.to_string(),
                 interface: interface.to_string(),
                 loop_execution,
+                start_at,
+                start_after_ms: None,
             };
 
             let message = ScenarioExecutionQueryMessage {
@@ -335,8 +485,8 @@ impl ScenarioManager {
             }
 
             info!(
-                "Started scenario '{}' execution on {}:{} (loop: {})",
-                scenario_id, namespace, interface, loop_execution
+                "Started scenario '{}' execution on {}:{} (loop: {}, start_at: {:?})",
+                scenario_id, namespace, interface, loop_execution, start_at
             );
             Ok(())
         } else {
@@ -377,6 +527,29 @@ impl ScenarioManager {
         }
     }
 
+    /// Ask a backend for its active executions, e.g. after it reconnects.
+    /// The reply arrives as `TcGuiMessage::ActiveExecutionsListed`.
+    pub fn request_active_executions(&self, backend_name: &str) -> Result<(), String> {
+        if let Some(sender) = &self.execution_query_sender {
+            let message = ScenarioExecutionQueryMessage {
+                backend_name: backend_name.to_string(),
+                request: ScenarioExecutionRequest::ListActive,
+                response_sender: None,
+            };
+
+            if let Err(e) = sender.send(message) {
+                error!("Failed to send active executions request: {}", e);
+                return Err(format!("Failed to list active executions: {}", e));
+            }
+
+            debug!("Requested active executions from {}", backend_name);
+            Ok(())
+        } else {
+            warn!("Execution query channel not available");
+            Err("Execution query channel not available".to_string())
+        }
+    }
+
     /// Pause scenario execution
     pub fn pause_execution(
         &self,
@@ -491,8 +664,11 @@ impl ScenarioManager {
         self.load_errors.get(backend_name)
     }
 
-    /// Handle execution status update with timestamp-based deduplication
-    pub fn handle_execution_update(&mut self, update: ScenarioExecutionUpdate) {
+    /// Handle execution status update with timestamp-based deduplication.
+    ///
+    /// Returns whether the update moved the execution to another step (or
+    /// is the first one seen for it), for annotating the bandwidth chart.
+    pub fn handle_execution_update(&mut self, update: ScenarioExecutionUpdate) -> bool {
         let execution_key = format!("{}/{}", update.namespace, update.interface);
 
         let executions = self
@@ -506,6 +682,14 @@ impl ScenarioManager {
             None => true,
         };
 
+        let step_changed = should_update
+            && executions.get(&execution_key).is_none_or(|existing| {
+                let existing = &existing.execution;
+                existing.start_time != update.execution.start_time
+                    || existing.current_step != update.execution.current_step
+                    || existing.loop_iteration != update.execution.loop_iteration
+            });
+
         if should_update {
             debug!(
                 "Updating execution status for {}: {} - {:?} (step {}, ts={})",
@@ -528,6 +712,58 @@ impl ScenarioManager {
                 execution_key, update.timestamp
             );
         }
+        step_changed
+    }
+
+    /// Resynchronise a backend's executions with the list it reported.
+    ///
+    /// The backend is the source of truth: reported executions replace the
+    /// tracked ones with their current progress. A tracked execution the
+    /// backend no longer runs ended while we were disconnected, so it is kept
+    /// as finished until dismissed instead of silently disappearing.
+    pub fn reconcile_active_executions(
+        &mut self,
+        backend_name: &str,
+        executions: Vec<ScenarioExecution>,
+    ) {
+        let tracked = self
+            .active_executions
+            .entry(backend_name.to_string())
+            .or_default();
+        let mut reconciled = HashMap::new();
+
+        for execution in executions {
+            let key = format!(
+                "{}/{}",
+                execution.target_namespace, execution.target_interface
+            );
+            let previous = tracked.remove(&key);
+            if execution.state.is_terminal() && previous.is_none() {
+                continue;
+            }
+            // Keep the last seen timestamp so older history samples stay ignored
+            let timestamp = previous.map_or(0, |p| p.timestamp);
+            reconciled.insert(
+                key,
+                TrackedExecution {
+                    execution,
+                    timestamp,
+                },
+            );
+        }
+
+        for (key, mut ended) in tracked.drain() {
+            if !ended.execution.state.is_terminal() {
+                info!(
+                    "Execution {} on {} ended while disconnected",
+                    ended.execution.scenario.id, key
+                );
+                ended.execution.state = ExecutionState::Completed;
+            }
+            reconciled.insert(key, ended);
+        }
+
+        *tracked = reconciled;
     }
 
     /// Remove execution when it completes or is stopped
@@ -591,11 +827,92 @@ impl std::fmt::Display for ScenarioManagerStats {
     }
 }
 
+/// Parse a scheduled start time entered in the execution dialog.
+///
+/// Accepts a local wall-clock time today (`HH:MM` or `HH:MM:SS`) or a delay
+/// from `now` (`+10m`, `90s`, `1h30m`). An empty input means "start now".
+/// Returns the start time as a Unix timestamp in milliseconds. A wall-clock
+/// time that has already passed is returned as-is; the backend starts such
+/// an execution immediately.
+pub fn parse_start_time(input: &str, now: DateTime<Local>) -> Result<Option<u64>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+
+    if input.contains(':') {
+        let time = NaiveTime::parse_from_str(input, "%H:%M")
+            .or_else(|_| NaiveTime::parse_from_str(input, "%H:%M:%S"))
+            .map_err(|_| format!("Invalid time '{}', expected HH:MM or HH:MM:SS", input))?;
+        let start = now
+            .date_naive()
+            .and_time(time)
+            .and_local_timezone(Local)
+            .earliest()
+            .ok_or_else(|| format!("Time '{}' does not exist today", input))?;
+        return Ok(Some(start.timestamp_millis().max(0) as u64));
+    }
+
+    let delay_ms = parse_delay_ms(input.strip_prefix('+').unwrap_or(input)).ok_or_else(|| {
+        format!(
+            "Invalid delay '{}', expected e.g. +10m, 90s or 1h30m",
+            input
+        )
+    })?;
+    Ok(Some(
+        (now.timestamp_millis().max(0) as u64).saturating_add(delay_ms),
+    ))
+}
+
+/// Format a Unix timestamp in milliseconds as local `HH:MM:SS`, with the date
+/// when it is not today.
+pub fn format_start_time(start_at_ms: u64) -> String {
+    let Some(start) = DateTime::from_timestamp_millis(start_at_ms as i64) else {
+        return start_at_ms.to_string();
+    };
+    let start = start.with_timezone(&Local);
+    if start.date_naive() == Local::now().date_naive() {
+        start.format("%H:%M:%S").to_string()
+    } else {
+        start.format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+}
+
+/// Parse a delay made of `<number><unit>` parts (units `h`, `m`, `s`).
+fn parse_delay_ms(input: &str) -> Option<u64> {
+    if input.is_empty() {
+        return None;
+    }
+
+    let mut total_ms: u64 = 0;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit_ms = match c {
+            'h' => 3_600_000,
+            'm' => 60_000,
+            's' => 1_000,
+            _ => return None,
+        };
+        let value: u64 = digits.parse().ok()?;
+        total_ms = total_ms.checked_add(value.checked_mul(unit_ms)?)?;
+        digits.clear();
+    }
+
+    // Trailing digits without a unit are not accepted
+    digits.is_empty().then_some(total_ms)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tcgui_shared::TcNetemConfig;
-    use tcgui_shared::scenario::{ExecutionState, ExecutionStats, ScenarioMetadata, ScenarioStep};
+    use tcgui_shared::scenario::{
+        ExecutionState, ExecutionStats, ScenarioMetadata, ScenarioRepeat, ScenarioStep,
+    };
 
     fn create_test_scenario(id: &str, name: &str, steps: usize) -> NetworkScenario {
         NetworkScenario {
@@ -608,12 +925,17 @@ mod tests {
                     duration_ms: 1000,
                     tc_config: TcNetemConfig::default(),
                     description: format!("Step {}", i),
+                    ramp: None,
                 })
                 .collect(),
+            phases: Vec::new(),
             loop_scenario: false,
+            repeat: ScenarioRepeat::Once,
             created_at: 0,
             modified_at: 0,
             cleanup_on_failure: true,
+            verify_steps: false,
+            cleanup_policy: None,
         }
     }
 
@@ -632,6 +954,8 @@ mod tests {
             stats: ExecutionStats::default(),
             loop_execution: false,
             loop_iteration: 0,
+            paused_ms: 0,
+            current_phase: None,
         }
     }
 
@@ -784,6 +1108,55 @@ mod tests {
         assert_eq!(manager.get_available_scenarios("backend1").len(), 0);
     }
 
+    #[test]
+    fn test_tag_filter_returns_tagged_subset() {
+        let mut manager = ScenarioManager::new();
+
+        let tagged = |id: &str, tags: &[&str], category: Option<&str>| {
+            let mut scenario = create_test_scenario(id, id, 1);
+            scenario.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+            scenario.metadata.category = category.map(str::to_string);
+            scenario
+        };
+        let scenarios = vec![
+            tagged("lte-handover", &["mobile", "wireless"], Some("mobile")),
+            tagged("wifi-fade", &["Wireless"], None),
+            tagged("peak-hour", &["congestion"], Some("mobile")),
+            tagged("untagged", &[], None),
+        ];
+        manager.handle_scenario_list_response("backend1".to_string(), scenarios, vec![]);
+        manager.set_sort_option(ScenarioSortOption::Name);
+
+        assert_eq!(
+            manager.get_available_tags("backend1"),
+            vec!["Wireless", "congestion", "mobile", "wireless"]
+        );
+
+        let ids = |manager: &ScenarioManager| -> Vec<String> {
+            manager
+                .get_available_scenarios("backend1")
+                .into_iter()
+                .map(|s| s.id)
+                .collect()
+        };
+
+        // Tags match case-insensitively
+        manager.set_tag_filter(Some("wireless".to_string()));
+        assert!(manager.has_active_filter());
+        assert_eq!(ids(&manager), vec!["lte-handover", "wifi-fade"]);
+
+        // Tag and text filters combine; text also matches the category
+        manager.set_search_filter("mobile".to_string());
+        assert_eq!(ids(&manager), vec!["lte-handover"]);
+
+        manager.set_tag_filter(None);
+        assert_eq!(ids(&manager), vec!["lte-handover", "peak-hour"]);
+
+        manager.set_search_filter(String::new());
+        assert!(!manager.has_active_filter());
+        assert_eq!(ids(&manager).len(), 4);
+    }
+
     #[test]
     fn test_scenario_details() {
         let mut manager = ScenarioManager::new();
@@ -838,7 +1211,12 @@ mod tests {
             execution: create_test_execution("scenario1", 0, ExecutionState::Running),
             timestamp: 1000,
         };
-        manager.handle_execution_update(update1);
+        assert!(manager.handle_execution_update(update1.clone()));
+        // A progress update on the same step is no step change
+        assert!(!manager.handle_execution_update(ScenarioExecutionUpdate {
+            timestamp: 1500,
+            ..update1
+        }));
 
         // Newer update should be applied
         let update2 = ScenarioExecutionUpdate {
@@ -848,7 +1226,7 @@ mod tests {
             execution: create_test_execution("scenario1", 1, ExecutionState::Running),
             timestamp: 2000,
         };
-        manager.handle_execution_update(update2);
+        assert!(manager.handle_execution_update(update2));
 
         let executions = manager.get_active_executions("backend1");
         assert_eq!(executions[0].current_step, 1);
@@ -861,12 +1239,67 @@ mod tests {
             execution: create_test_execution("scenario1", 0, ExecutionState::Running),
             timestamp: 500,
         };
-        manager.handle_execution_update(update3);
+        assert!(!manager.handle_execution_update(update3));
 
         let executions = manager.get_active_executions("backend1");
         assert_eq!(executions[0].current_step, 1); // Still at step 1
     }
 
+    #[test]
+    fn test_reconcile_after_reconnect() {
+        let mut manager = ScenarioManager::new();
+        for (interface, step) in [("eth0", 0), ("eth1", 1)] {
+            let mut execution = create_test_execution("scenario1", step, ExecutionState::Running);
+            execution.target_interface = interface.to_string();
+            manager.handle_execution_update(ScenarioExecutionUpdate {
+                backend_name: "backend1".to_string(),
+                namespace: "ns1".to_string(),
+                interface: interface.to_string(),
+                execution,
+                timestamp: 1000,
+            });
+        }
+
+        // While disconnected, eth0 progressed, eth1 finished and eth2 started
+        let mut eth2 = create_test_execution("scenario2", 0, ExecutionState::Running);
+        eth2.target_interface = "eth2".to_string();
+        manager.reconcile_active_executions(
+            "backend1",
+            vec![
+                create_test_execution("scenario1", 2, ExecutionState::Running),
+                eth2,
+            ],
+        );
+
+        let mut executions = manager.get_active_executions("backend1");
+        executions.sort_by(|a, b| a.target_interface.cmp(&b.target_interface));
+        assert_eq!(executions.len(), 3);
+        assert_eq!(executions[0].current_step, 2);
+        assert!(matches!(executions[1].state, ExecutionState::Completed));
+        assert_eq!(executions[2].scenario.id, "scenario2");
+
+        // The finished execution no longer blocks a new start and can be dismissed
+        assert!(manager.is_execution_active("backend1", "ns1", "eth0"));
+        assert!(!manager.is_execution_active("backend1", "ns1", "eth1"));
+        manager.remove_execution("backend1", "ns1", "eth1");
+        assert_eq!(manager.get_active_executions("backend1").len(), 2);
+
+        // History older than what was seen before the disconnect stays ignored
+        manager.handle_execution_update(ScenarioExecutionUpdate {
+            backend_name: "backend1".to_string(),
+            namespace: "ns1".to_string(),
+            interface: "eth0".to_string(),
+            execution: create_test_execution("scenario1", 0, ExecutionState::Running),
+            timestamp: 500,
+        });
+        let executions = manager.get_active_executions("backend1");
+        let eth0 = executions
+            .iter()
+            .find(|e| e.target_interface == "eth0")
+            .unwrap();
+        assert_eq!(eth0.current_step, 2);
+    }
+
     #[test]
     fn test_execution_removal() {
         let mut manager = ScenarioManager::new();
@@ -897,6 +1330,13 @@ mod tests {
 
         manager.toggle_execution_timeline("backend1", "ns1", "eth0");
         assert!(!manager.is_timeline_collapsed("backend1", "ns1", "eth0"));
+
+        manager.toggle_execution_phase("backend1", "ns1", "eth0", "Warmup");
+        assert!(manager.is_phase_collapsed("backend1", "ns1", "eth0", "Warmup"));
+        assert!(!manager.is_phase_collapsed("backend1", "ns1", "eth0", "Outage"));
+        assert!(!manager.is_phase_collapsed("backend1", "ns1", "eth1", "Warmup"));
+        manager.toggle_execution_phase("backend1", "ns1", "eth0", "Warmup");
+        assert!(!manager.is_phase_collapsed("backend1", "ns1", "eth0", "Warmup"));
     }
 
     #[test]
@@ -966,11 +1406,49 @@ mod tests {
         assert!(manager.request_scenarios("backend1").is_err());
         assert!(
             manager
-                .start_execution("backend1", "scenario1", "ns1", "eth0", false)
+                .start_execution("backend1", "scenario1", "ns1", "eth0", false, None)
                 .is_err()
         );
         assert!(manager.stop_execution("backend1", "ns1", "eth0").is_err());
         assert!(manager.pause_execution("backend1", "ns1", "eth0").is_err());
         assert!(manager.resume_execution("backend1", "ns1", "eth0").is_err());
     }
+
+    #[test]
+    fn test_parse_start_time_empty_means_now() {
+        assert_eq!(parse_start_time("", Local::now()), Ok(None));
+        assert_eq!(parse_start_time("   ", Local::now()), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_start_time_relative_delay() {
+        let now = Local::now();
+        let now_ms = now.timestamp_millis() as u64;
+
+        assert_eq!(parse_start_time("+10m", now), Ok(Some(now_ms + 600_000)));
+        assert_eq!(parse_start_time("90s", now), Ok(Some(now_ms + 90_000)));
+        assert_eq!(parse_start_time("1h30m", now), Ok(Some(now_ms + 5_400_000)));
+        assert!(parse_start_time("10", now).is_err());
+        assert!(parse_start_time("+", now).is_err());
+        assert!(parse_start_time("5d", now).is_err());
+    }
+
+    #[test]
+    fn test_parse_start_time_wall_clock() {
+        let now = Local::now();
+        let start = parse_start_time("23:59:59", now).unwrap().unwrap();
+        let expected = now
+            .date_naive()
+            .and_hms_opt(23, 59, 59)
+            .unwrap()
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap();
+        assert_eq!(start, expected.timestamp_millis() as u64);
+
+        // Past times are passed through; the backend starts them immediately
+        assert!(parse_start_time("00:00", now).unwrap().is_some());
+        assert!(parse_start_time("25:00", now).is_err());
+        assert!(parse_start_time("12:xx", now).is_err());
+    }
 }
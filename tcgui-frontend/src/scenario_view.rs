@@ -6,12 +6,12 @@
 use iced::widget::{Column, button, column, container, row, scrollable, space, text, text_input};
 use iced::{Color, Element, Length};
 
-use tcgui_shared::scenario::{ExecutionState, NetworkScenario, ScenarioExecution};
+use tcgui_shared::scenario::{ExecutionState, NetworkScenario, ScenarioExecution, ScenarioRepeat};
 
 use crate::backend_manager::BackendManager;
 use crate::icons::Icon;
 use crate::messages::TcGuiMessage;
-use crate::scenario_manager::{ScenarioManager, ScenarioSortOption};
+use crate::scenario_manager::{ScenarioManager, ScenarioSortOption, format_start_time};
 use crate::theme::Theme;
 use crate::view::{scaled, scaled_padding, scaled_spacing};
 
@@ -202,7 +202,7 @@ fn render_no_backends<'a>(colors: ScenarioColorPalette, zoom: f32) -> Element<'a
 fn render_backend_scenarios<'a>(
     backend_name: &str,
     scenario_manager: &'a ScenarioManager,
-    _backend_manager: &'a BackendManager,
+    backend_manager: &'a BackendManager,
     colors: ScenarioColorPalette,
     zoom: f32,
 ) -> Element<'a, TcGuiMessage> {
@@ -236,11 +236,14 @@ fn render_backend_scenarios<'a>(
     backend_content = backend_content.push(
         row![
             Icon::Monitor.svg_sized_colored(scaled(18, zoom), colors.text_primary),
-            text(format!(" Backend: {}", backend_name))
-                .size(scaled(18, zoom))
-                .style(move |_| text::Style {
-                    color: Some(colors.text_primary)
-                }),
+            text(format!(
+                " Backend: {}",
+                backend_manager.display_name(backend_name)
+            ))
+            .size(scaled(18, zoom))
+            .style(move |_| text::Style {
+                color: Some(colors.text_primary)
+            }),
             space().width(Length::Fill),
             refresh_button
         ]
@@ -250,7 +253,7 @@ fn render_backend_scenarios<'a>(
 
     // Search and sort controls
     let raw_count = scenario_manager.get_raw_scenario_count(backend_name);
-    if raw_count > 0 || !scenario_manager.get_search_filter().is_empty() {
+    if raw_count > 0 || scenario_manager.has_active_filter() {
         let current_sort = scenario_manager.get_sort_option();
         let sort_ascending = scenario_manager.is_sort_ascending();
 
@@ -297,6 +300,45 @@ fn render_backend_scenarios<'a>(
                 .spacing(scaled_spacing(12, zoom))
                 .align_y(iced::Alignment::Center),
         );
+
+        // Tag filter chips
+        let tags = scenario_manager.get_available_tags(backend_name);
+        if !tags.is_empty() {
+            let active_tag = scenario_manager.get_tag_filter();
+            let mut tag_buttons = row![
+                text("Tags:")
+                    .size(scaled(12, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(colors.text_secondary)
+                    }),
+                button(text("All").size(scaled(11, zoom)))
+                    .on_press(TcGuiMessage::ScenarioTagFilterChanged(None))
+                    .style(if active_tag.is_none() {
+                        button::primary
+                    } else {
+                        button::secondary
+                    })
+            ]
+            .spacing(scaled_spacing(4, zoom))
+            .align_y(iced::Alignment::Center);
+
+            for tag in tags {
+                let is_active = active_tag == Some(tag.as_str());
+                // Clicking the active tag again clears the filter
+                let next = (!is_active).then(|| tag.clone());
+                tag_buttons = tag_buttons.push(
+                    button(text(tag).size(scaled(11, zoom)))
+                        .on_press(TcGuiMessage::ScenarioTagFilterChanged(next))
+                        .style(if is_active {
+                            button::primary
+                        } else {
+                            button::secondary
+                        }),
+                );
+            }
+
+            backend_content = backend_content.push(tag_buttons.wrap());
+        }
     }
 
     // Available scenarios section
@@ -305,7 +347,7 @@ fn render_backend_scenarios<'a>(
 
     if !available_scenarios.is_empty() {
         // Show count info if filtering
-        let header_text = if !scenario_manager.get_search_filter().is_empty() {
+        let header_text = if scenario_manager.has_active_filter() {
             format!(
                 "Scenarios ({} of {})",
                 available_scenarios.len(),
@@ -413,7 +455,7 @@ fn render_backend_scenarios<'a>(
     if available_scenarios.is_empty() {
         let message = if is_loading {
             "Loading scenarios..."
-        } else if !scenario_manager.get_search_filter().is_empty() && raw_scenario_count > 0 {
+        } else if scenario_manager.has_active_filter() && raw_scenario_count > 0 {
             "No scenarios match your search"
         } else {
             "Click 'Refresh' to load scenarios"
@@ -557,6 +599,7 @@ fn render_scenario_card<'a>(
             ]
             .spacing(scaled_spacing(4, zoom))
             .align_y(iced::Alignment::Center),
+            render_scenario_metadata(scenario, colors.clone(), zoom),
             if !scenario.description.is_empty() {
                 Element::<'_, TcGuiMessage>::from(
                     text(scenario.description.clone())
@@ -584,6 +627,35 @@ fn render_scenario_card<'a>(
     .into()
 }
 
+/// Renders the category, tags and author of a scenario card, if any are set
+fn render_scenario_metadata<'a>(
+    scenario: &NetworkScenario,
+    colors: ScenarioColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    let metadata = &scenario.metadata;
+    let mut parts = Vec::new();
+    if let Some(category) = &metadata.category {
+        parts.push(format!("Category: {}", category));
+    }
+    if !metadata.tags.is_empty() {
+        parts.push(format!("Tags: {}", metadata.tags.join(", ")));
+    }
+    if let Some(author) = &metadata.author {
+        parts.push(format!("By {}", author));
+    }
+    if parts.is_empty() {
+        return space().height(0).into();
+    }
+
+    text(parts.join(" • "))
+        .size(scaled(12, zoom))
+        .style(move |_: &iced::Theme| text::Style {
+            color: Some(colors.text_secondary),
+        })
+        .into()
+}
+
 /// Renders active scenario executions
 fn render_active_executions<'a>(
     executions: &[ScenarioExecution],
@@ -600,10 +672,25 @@ fn render_active_executions<'a>(
             &execution.target_namespace,
             &execution.target_interface,
         );
+        let collapsed_phases: Vec<String> = execution
+            .scenario
+            .phases
+            .iter()
+            .filter(|phase| {
+                scenario_manager.is_phase_collapsed(
+                    backend_name,
+                    &execution.target_namespace,
+                    &execution.target_interface,
+                    &phase.name,
+                )
+            })
+            .map(|phase| phase.name.clone())
+            .collect();
         list_content = list_content.push(render_execution_card(
             execution,
             backend_name,
             is_collapsed,
+            &collapsed_phases,
             colors.clone(),
             zoom,
         ));
@@ -617,10 +704,12 @@ fn render_execution_card<'a>(
     execution: &ScenarioExecution,
     backend_name: &str,
     is_timeline_collapsed: bool,
+    collapsed_phases: &[String],
     colors: ScenarioColorPalette,
     zoom: f32,
 ) -> Element<'a, TcGuiMessage> {
     let (state_icon, state_color) = match &execution.state {
+        ExecutionState::Scheduled { .. } => (Icon::Clock, colors.primary_blue),
         ExecutionState::Running => (Icon::Play, colors.success_green),
         ExecutionState::Paused { .. } => (Icon::Pause, colors.warning_orange),
         ExecutionState::Stopped => (Icon::Square, colors.error_red),
@@ -647,10 +736,30 @@ fn render_execution_card<'a>(
         format!("~{} remaining", format_duration(remaining_duration))
     } else {
         match &execution.state {
+            ExecutionState::Scheduled { start_at } => {
+                let now_ms = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                format!(
+                    "Scheduled for {} (in {})",
+                    format_start_time(*start_at),
+                    format_duration(start_at.saturating_sub(now_ms))
+                )
+            }
             ExecutionState::Completed => "Completed".to_string(),
             ExecutionState::Stopped => "Stopped".to_string(),
             ExecutionState::Failed { .. } => "Failed".to_string(),
-            ExecutionState::Paused { .. } => "Paused".to_string(),
+            ExecutionState::Paused { paused_at } => {
+                let now_ms = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                format!(
+                    "Paused for {}",
+                    format_duration(now_ms.saturating_sub(*paused_at))
+                )
+            }
             _ => String::new(),
         }
     };
@@ -665,6 +774,7 @@ fn render_execution_card<'a>(
 
     // Progress bar colors
     let bar_color = match &execution.state {
+        ExecutionState::Scheduled { .. } => colors.text_secondary,
         ExecutionState::Running => colors.success_green,
         ExecutionState::Paused { .. } => colors.warning_orange,
         ExecutionState::Completed => colors.primary_blue,
@@ -675,11 +785,67 @@ fn render_execution_card<'a>(
 
     // Build step timeline
     let mut timeline_content = column![].spacing(scaled_spacing(2, zoom));
+    let phase_ranges: Vec<_> = execution.scenario.phase_ranges().collect();
     for (i, step) in execution.scenario.steps.iter().enumerate() {
+        // Phase header before the phase's first step
+        if let Some((phase, range)) = phase_ranges.iter().find(|(_, range)| range.start == i) {
+            let is_collapsed = collapsed_phases.contains(&phase.name);
+            let phase_color = if range.end <= execution.current_step {
+                colors.success_green
+            } else if range.contains(&execution.current_step) {
+                colors.primary_blue
+            } else {
+                colors.text_secondary
+            };
+            let toggle_icon = if is_collapsed {
+                Icon::Play
+            } else {
+                Icon::ArrowUp
+            };
+            timeline_content = timeline_content.push(
+                button(
+                    row![
+                        toggle_icon.svg_sized_colored(scaled(10, zoom), phase_color),
+                        text(format!(
+                            " {} ({} step{})",
+                            phase.name,
+                            phase.step_count,
+                            if phase.step_count == 1 { "" } else { "s" }
+                        ))
+                        .size(scaled(11, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(phase_color),
+                        })
+                    ]
+                    .align_y(iced::Alignment::Center),
+                )
+                .padding([scaled_padding(2, zoom), 0.0])
+                .style(move |_, _| button::Style {
+                    background: Some(iced::Background::Color(Color::TRANSPARENT)),
+                    text_color: phase_color,
+                    ..button::Style::default()
+                })
+                .on_press(TcGuiMessage::ToggleExecutionPhase {
+                    backend_name: backend_name.to_string(),
+                    namespace: execution.target_namespace.clone(),
+                    interface: execution.target_interface.clone(),
+                    phase: phase.name.clone(),
+                }),
+            );
+        }
+        if execution
+            .scenario
+            .phase_of(i)
+            .is_some_and(|name| collapsed_phases.iter().any(|c| c == name))
+        {
+            continue;
+        }
+
         let (step_icon, step_color) = if i < execution.current_step {
             (Icon::Check, colors.success_green)
         } else if i == execution.current_step {
             match &execution.state {
+                ExecutionState::Scheduled { .. } => (Icon::Clock, colors.text_secondary),
                 ExecutionState::Running => (Icon::Play, colors.primary_blue),
                 ExecutionState::Paused { .. } => (Icon::Pause, colors.warning_orange),
                 ExecutionState::Stopped => (Icon::Square, colors.error_red),
@@ -741,10 +907,12 @@ fn render_execution_card<'a>(
     let mut card_content = column![].spacing(scaled_spacing(8, zoom));
 
     // Header row with name, loop indicator, and controls
-    let loop_info = if execution.loop_execution {
-        format!(" Loop #{}", execution.loop_iteration + 1)
-    } else {
-        String::new()
+    let loop_info = match execution.scenario.repeat {
+        _ if execution.loop_execution => format!(" Loop #{}", execution.loop_iteration + 1),
+        ScenarioRepeat::Count(count) if count > 1 => {
+            format!(" Cycle {}/{}", execution.loop_iteration + 1, count)
+        }
+        _ => String::new(),
     };
 
     card_content = card_content.push(
@@ -821,6 +989,46 @@ fn render_execution_card<'a>(
         }),
     );
 
+    // Phase breadcrumb, highlighting the running phase
+    if !execution.scenario.phases.is_empty() {
+        let mut breadcrumb =
+            row![
+                text("Phase:")
+                    .size(scaled(11, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(colors.text_secondary)
+                    })
+            ]
+            .spacing(scaled_spacing(4, zoom))
+            .align_y(iced::Alignment::Center);
+        for (n, (phase, range)) in phase_ranges.iter().enumerate() {
+            if n > 0 {
+                breadcrumb =
+                    breadcrumb.push(
+                        text("→")
+                            .size(scaled(11, zoom))
+                            .style(move |_| text::Style {
+                                color: Some(colors.text_secondary),
+                            }),
+                    );
+            }
+            let is_current = execution.current_phase.as_deref() == Some(phase.name.as_str());
+            let phase_color = if is_current {
+                state_color
+            } else if range.end <= execution.current_step {
+                colors.success_green
+            } else {
+                colors.text_secondary
+            };
+            breadcrumb = breadcrumb.push(text(phase.name.clone()).size(scaled(11, zoom)).style(
+                move |_| text::Style {
+                    color: Some(phase_color),
+                },
+            ));
+        }
+        card_content = card_content.push(breadcrumb);
+    }
+
     // Current step details
     card_content = card_content.push(
         row![
@@ -969,6 +1177,20 @@ fn render_execution_controls<'a>(
     zoom: f32,
 ) -> Element<'a, TcGuiMessage> {
     match &execution.state {
+        ExecutionState::Scheduled { .. } => {
+            button(Icon::X.svg_sized_colored(scaled(14, zoom), Color::WHITE))
+                .on_press(TcGuiMessage::StopScenarioExecution {
+                    backend_name: backend_name.to_string(),
+                    namespace: execution.target_namespace.clone(),
+                    interface: execution.target_interface.clone(),
+                })
+                .style(move |_, _| button::Style {
+                    background: Some(iced::Background::Color(colors.error_red)),
+                    text_color: Color::WHITE,
+                    ..button::Style::default()
+                })
+                .into()
+        }
         ExecutionState::Running => row![
             button(Icon::Pause.svg_sized_colored(scaled(14, zoom), Color::WHITE))
                 .on_press(TcGuiMessage::PauseScenarioExecution {
@@ -1022,12 +1244,23 @@ fn render_execution_controls<'a>(
         .spacing(scaled_spacing(4, zoom))
         .into(),
         ExecutionState::Stopped | ExecutionState::Completed | ExecutionState::Failed { .. } => {
-            text("Finished")
-                .size(scaled(12, zoom))
-                .style(move |_| text::Style {
-                    color: Some(colors.text_secondary),
-                })
-                .into()
+            row![
+                text("Finished")
+                    .size(scaled(12, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(colors.text_secondary),
+                    }),
+                button(Icon::X.svg_sized_colored(scaled(14, zoom), colors.text_primary))
+                    .on_press(TcGuiMessage::DismissScenarioExecution {
+                        backend_name: backend_name.to_string(),
+                        namespace: execution.target_namespace.clone(),
+                        interface: execution.target_interface.clone(),
+                    })
+                    .style(button::secondary)
+            ]
+            .spacing(scaled_spacing(4, zoom))
+            .align_y(iced::Alignment::Center)
+            .into()
         }
     }
 }
@@ -1108,7 +1341,17 @@ fn render_scenario_details<'a>(
             },
         ));
 
+        let phase_ranges: Vec<_> = scenario.phase_ranges().collect();
         for (i, step) in scenario.steps.iter().enumerate() {
+            if let Some((phase, _)) = phase_ranges.iter().find(|(_, range)| range.start == i) {
+                steps_content = steps_content.push(
+                    text(format!("Phase: {}", phase.name))
+                        .size(scaled(14, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(colors.primary_blue),
+                        }),
+                );
+            }
             let timing_info = format_duration(step.duration_ms);
 
             steps_content = steps_content.push(
@@ -1120,6 +1363,11 @@ fn render_scenario_details<'a>(
                                 color: Some(colors.text_primary)
                             }),
                         text(format!("Timing: {}", timing_info))
+                            .size(scaled(12, zoom))
+                            .style(move |_| text::Style {
+                                color: Some(colors.text_secondary)
+                            }),
+                        text(tcgui_shared::explain::explain_netem(&step.tc_config))
                             .size(scaled(12, zoom))
                             .style(move |_| text::Style {
                                 color: Some(colors.text_secondary)
@@ -3,17 +3,19 @@
 //! This module handles loading and saving user preferences to a JSON5 configuration file.
 //! Settings are stored in `~/.config/tcgui/frontend.json5` following XDG conventions.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
+use crate::interface_style::InterfaceStyle;
 use crate::theme::ThemeMode;
 use crate::ui_state::{AppTab, NamespaceFilter, ZOOM_DEFAULT, ZOOM_MAX, ZOOM_MIN};
 
 /// Configuration directory name
-const CONFIG_DIR: &str = "tcgui";
+pub(crate) const CONFIG_DIR: &str = "tcgui";
 /// Settings file name
 const SETTINGS_FILE: &str = "frontend.json5";
 
@@ -37,12 +39,69 @@ pub struct FrontendSettings {
     /// Last active tab
     #[serde(default)]
     pub current_tab: AppTabJson,
+
+    /// Display labels for backends, keyed by host origin
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub backend_labels: BTreeMap<String, String>,
+
+    /// Last target each scenario was run on, keyed by scenario id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scenario_targets: BTreeMap<String, ScenarioTarget>,
+
+    /// Backends collapsed to their summary header, by host origin
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub collapsed_backends: BTreeSet<String>,
+
+    /// Namespaces collapsed to their header, as "origin/namespace"
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub collapsed_namespaces: BTreeSet<String>,
+
+    /// Display color and label of interfaces, as "origin/namespace/interface"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub interface_styles: BTreeMap<String, InterfaceStyle>,
+
+    /// Connect endpoints a backend was reached through, most recent first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoint_history: Vec<String>,
+
+    /// Whether TC found on a backend replaces an interface's settings in
+    /// the UI; when off the UI keeps its settings and flags the difference
+    #[serde(default = "default_true")]
+    pub adopt_detected_tc: bool,
+
+    /// Whether the bandwidth chart of an interface running a scenario gets a
+    /// marker, labelled with the step, each time the scenario moves to its
+    /// next step. On by default.
+    #[serde(default = "default_true")]
+    pub annotate_scenario_steps: bool,
+
+    /// Packets dropped per second (both directions) above which an
+    /// interface is flagged
+    #[serde(default = "default_drop_alert_per_sec")]
+    pub drop_alert_per_sec: f64,
+}
+
+/// Where a scenario was last run: a backend, a namespace on it and the
+/// interfaces selected there.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioTarget {
+    /// Backend host origin
+    pub backend: String,
+    pub namespace: String,
+    pub interfaces: Vec<String>,
 }
 
 fn default_zoom() -> f32 {
     ZOOM_DEFAULT
 }
 
+/// Default drop rate alert threshold, in packets per second
+pub const DROP_ALERT_DEFAULT: f64 = 1.0;
+
+fn default_drop_alert_per_sec() -> f64 {
+    DROP_ALERT_DEFAULT
+}
+
 impl Default for FrontendSettings {
     fn default() -> Self {
         Self {
@@ -50,6 +109,15 @@ impl Default for FrontendSettings {
             zoom_level: ZOOM_DEFAULT,
             namespace_filter: NamespaceFilterJson::default(),
             current_tab: AppTabJson::Interfaces,
+            backend_labels: BTreeMap::new(),
+            scenario_targets: BTreeMap::new(),
+            collapsed_backends: BTreeSet::new(),
+            collapsed_namespaces: BTreeSet::new(),
+            interface_styles: BTreeMap::new(),
+            endpoint_history: Vec::new(),
+            adopt_detected_tc: true,
+            annotate_scenario_steps: true,
+            drop_alert_per_sec: DROP_ALERT_DEFAULT,
         }
     }
 }
@@ -208,6 +276,14 @@ impl FrontendSettings {
             );
             settings.zoom_level = settings.zoom_level.clamp(ZOOM_MIN, ZOOM_MAX);
         }
+
+        if settings.drop_alert_per_sec.is_nan() || settings.drop_alert_per_sec < 0.0 {
+            warn!(
+                "Drop alert threshold {} is invalid, using {}",
+                settings.drop_alert_per_sec, DROP_ALERT_DEFAULT
+            );
+            settings.drop_alert_per_sec = DROP_ALERT_DEFAULT;
+        }
     }
 
     /// Saves settings to the configuration file.
@@ -264,6 +340,7 @@ impl std::error::Error for SettingsError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::interface_style::AccentColor;
 
     #[test]
     fn test_default_settings() {
@@ -287,6 +364,31 @@ mod tests {
                 show_containers: true,
             },
             current_tab: AppTabJson::Scenarios,
+            backend_labels: BTreeMap::from([(
+                "h-000000000001".to_string(),
+                "Lab Gateway".to_string(),
+            )]),
+            scenario_targets: BTreeMap::from([(
+                "mobile-handover".to_string(),
+                ScenarioTarget {
+                    backend: "h-000000000001".to_string(),
+                    namespace: "lab".to_string(),
+                    interfaces: vec!["veth0".to_string(), "veth1".to_string()],
+                },
+            )]),
+            collapsed_backends: BTreeSet::from(["h-000000000002".to_string()]),
+            collapsed_namespaces: BTreeSet::from(["h-000000000001/lab".to_string()]),
+            interface_styles: BTreeMap::from([(
+                "h-000000000001/lab/veth0".to_string(),
+                InterfaceStyle {
+                    color: Some(AccentColor::Blue),
+                    label: Some("client-uplink".to_string()),
+                },
+            )]),
+            endpoint_history: vec!["tcp/192.168.1.10:7447".to_string()],
+            adopt_detected_tc: false,
+            annotate_scenario_steps: false,
+            drop_alert_per_sec: 25.0,
         };
 
         let json = settings.to_json5_string();
@@ -298,6 +400,18 @@ mod tests {
         assert!(!parsed.namespace_filter.show_namespaces);
         assert!(parsed.namespace_filter.show_containers);
         assert!(matches!(parsed.current_tab, AppTabJson::Scenarios));
+        assert_eq!(parsed.backend_labels["h-000000000001"], "Lab Gateway");
+        assert_eq!(
+            parsed.scenario_targets["mobile-handover"],
+            settings.scenario_targets["mobile-handover"]
+        );
+        assert_eq!(parsed.collapsed_backends, settings.collapsed_backends);
+        assert_eq!(parsed.collapsed_namespaces, settings.collapsed_namespaces);
+        assert_eq!(parsed.interface_styles, settings.interface_styles);
+        assert_eq!(parsed.endpoint_history, settings.endpoint_history);
+        assert!(!parsed.adopt_detected_tc);
+        assert!(!parsed.annotate_scenario_steps);
+        assert_eq!(parsed.drop_alert_per_sec, 25.0);
     }
 
     #[test]
@@ -308,6 +422,7 @@ mod tests {
         // All defaults should apply
         assert!(matches!(settings.theme_mode, ThemeModeJson::Light));
         assert_eq!(settings.zoom_level, ZOOM_DEFAULT);
+        assert_eq!(settings.drop_alert_per_sec, DROP_ALERT_DEFAULT);
     }
 
     #[test]
@@ -332,6 +447,10 @@ mod tests {
         settings.zoom_level = 0.1; // Too low
         FrontendSettings::validate(&mut settings);
         assert_eq!(settings.zoom_level, ZOOM_MIN);
+
+        settings.drop_alert_per_sec = -1.0;
+        FrontendSettings::validate(&mut settings);
+        assert_eq!(settings.drop_alert_per_sec, DROP_ALERT_DEFAULT);
     }
 
     #[test]
@@ -14,12 +14,14 @@ use iced::{Color, Element, Length};
 /// Data structure representing a row in the interface table
 #[derive(Clone)]
 pub struct InterfaceTableRow {
-    /// Backend name
-    pub backend_name: String,
+    /// Backend display name (user label or health-document name)
+    pub backend_label: String,
     /// Namespace name
     pub namespace: String,
     /// Interface name
     pub interface_name: String,
+    /// Operator-provided description from the backend
+    pub description: Option<String>,
     /// Whether the interface is up
     pub is_up: bool,
     /// Whether TC qdisc is configured
@@ -64,9 +66,10 @@ pub fn collect_interface_rows(backend_manager: &BackendManager) -> Vec<Interface
                 let bandwidth = tc_interface.bandwidth_stats();
 
                 rows.push(InterfaceTableRow {
-                    backend_name: backend_name.clone(),
+                    backend_label: backend_manager.display_name(backend_name),
                     namespace: namespace_name.clone(),
                     interface_name: interface_name.clone(),
+                    description: tc_interface.description().map(str::to_string),
                     is_up: tc_interface.is_up(),
                     has_tc: tc_interface.has_tc_qdisc(),
                     rx_rate: bandwidth.map(|b| b.rx_bytes_per_sec),
@@ -78,8 +81,8 @@ pub fn collect_interface_rows(backend_manager: &BackendManager) -> Vec<Interface
 
     // Sort by backend, namespace, then interface name
     rows.sort_by(|a, b| {
-        a.backend_name
-            .cmp(&b.backend_name)
+        a.backend_label
+            .cmp(&b.backend_label)
             .then(a.namespace.cmp(&b.namespace))
             .then(a.interface_name.cmp(&b.interface_name))
     });
@@ -150,6 +153,18 @@ pub fn render_interface_table(
     )
     .width(Length::Fixed(120.0 * zoom));
 
+    let description_col = table::column(
+        header_cell("Description", text_size, primary),
+        move |row: InterfaceTableRow| -> Element<'static, TcGuiMessage> {
+            text_cell(
+                row.description.unwrap_or_default(),
+                small_text_size,
+                secondary,
+            )
+        },
+    )
+    .width(Length::Fixed(160.0 * zoom));
+
     let status_col = table::column(
         header_cell("Status", text_size, primary),
         move |row: InterfaceTableRow| -> Element<'static, TcGuiMessage> {
@@ -201,7 +216,7 @@ pub fn render_interface_table(
     let backend_col = table::column(
         header_cell("Backend", text_size, primary),
         move |row: InterfaceTableRow| -> Element<'static, TcGuiMessage> {
-            text_cell(row.backend_name, small_text_size, secondary)
+            text_cell(row.backend_label, small_text_size, secondary)
         },
     )
     .width(Length::Fixed(100.0 * zoom));
@@ -211,6 +226,7 @@ pub fn render_interface_table(
         [
             interface_col,
             namespace_col,
+            description_col,
             status_col,
             tc_col,
             rx_col,
@@ -5,6 +5,9 @@
 
 use iced::widget::scrollable::{self, AutoScroll, Rail, Scroller, Status};
 use iced::{Background, Border, Color, Shadow};
+use tcgui_shared::TcFeature;
+
+use crate::interface_style::AccentColor;
 
 /// Theme mode selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -193,6 +196,45 @@ impl Theme {
         self.mode == ThemeMode::Dark
     }
 
+    /// Accent color for an impairment, shared by its feature card and its
+    /// clause in the command preview.
+    pub fn feature_color(&self, feature: TcFeature) -> Color {
+        match feature {
+            TcFeature::Loss => self.colors.error,
+            TcFeature::Delay => self.colors.info,
+            TcFeature::Duplicate => self.colors.rx_color,
+            TcFeature::Reorder => self.colors.warning,
+            TcFeature::Corrupt => match self.mode {
+                ThemeMode::Light => Color::from_rgb(0.6, 0.3, 0.8),
+                ThemeMode::Dark => Color::from_rgb(0.75, 0.55, 1.0),
+            },
+            TcFeature::RateLimit => self.colors.success,
+            TcFeature::Slot => match self.mode {
+                ThemeMode::Light => Color::from_rgb(0.0, 0.5, 0.55),
+                ThemeMode::Dark => Color::from_rgb(0.3, 0.8, 0.85),
+            },
+        }
+    }
+
+    /// Shade of a user-assigned interface color for this theme: deep tones
+    /// on the light surfaces, pale ones on the dark surfaces.
+    pub fn accent_color(&self, accent: AccentColor) -> Color {
+        match (self.mode, accent) {
+            (ThemeMode::Light, AccentColor::Blue) => Color::from_rgb(0.1, 0.4, 0.8),
+            (ThemeMode::Light, AccentColor::Green) => Color::from_rgb(0.1, 0.55, 0.25),
+            (ThemeMode::Light, AccentColor::Orange) => Color::from_rgb(0.8, 0.4, 0.0),
+            (ThemeMode::Light, AccentColor::Purple) => Color::from_rgb(0.5, 0.25, 0.75),
+            (ThemeMode::Light, AccentColor::Red) => Color::from_rgb(0.8, 0.15, 0.15),
+            (ThemeMode::Light, AccentColor::Teal) => Color::from_rgb(0.0, 0.5, 0.5),
+            (ThemeMode::Dark, AccentColor::Blue) => Color::from_rgb(0.4, 0.7, 1.0),
+            (ThemeMode::Dark, AccentColor::Green) => Color::from_rgb(0.4, 0.85, 0.5),
+            (ThemeMode::Dark, AccentColor::Orange) => Color::from_rgb(1.0, 0.65, 0.3),
+            (ThemeMode::Dark, AccentColor::Purple) => Color::from_rgb(0.75, 0.6, 1.0),
+            (ThemeMode::Dark, AccentColor::Red) => Color::from_rgb(1.0, 0.5, 0.5),
+            (ThemeMode::Dark, AccentColor::Teal) => Color::from_rgb(0.3, 0.85, 0.85),
+        }
+    }
+
     /// Create a tooltip style for this theme.
     ///
     /// Tooltips have a solid background with good contrast for readability
@@ -319,4 +361,39 @@ mod tests {
         assert!(!Theme::light().is_dark());
         assert!(Theme::dark().is_dark());
     }
+
+    /// WCAG contrast ratio between two colors.
+    fn contrast(a: Color, b: Color) -> f32 {
+        fn luminance(c: Color) -> f32 {
+            let channel = |v: f32| {
+                if v <= 0.03928 {
+                    v / 12.92
+                } else {
+                    ((v + 0.055) / 1.055).powf(2.4)
+                }
+            };
+            0.2126 * channel(c.r) + 0.7152 * channel(c.g) + 0.0722 * channel(c.b)
+        }
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+
+    #[test]
+    fn test_accent_colors_are_legible_in_both_themes() {
+        for theme in [Theme::light(), Theme::dark()] {
+            for accent in AccentColor::all() {
+                let color = theme.accent_color(*accent);
+                for surface in [theme.colors.background, theme.colors.surface] {
+                    // 3:1 is the WCAG minimum for graphics and large text
+                    assert!(
+                        contrast(color, surface) >= 3.0,
+                        "{:?} {} on {:?}",
+                        theme.mode,
+                        accent.name(),
+                        surface
+                    );
+                }
+            }
+        }
+    }
 }
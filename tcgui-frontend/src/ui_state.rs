@@ -3,10 +3,18 @@
 //! This module handles UI visibility toggles, state management,
 //! and provides utilities for managing the user interface state.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-use crate::settings::FrontendSettings;
+use tcgui_shared::TcFeature;
+use tcgui_shared::presets::CustomPreset;
+
+use crate::coordinated_apply::{CoordinatedAction, CoordinatedApply};
+use crate::endpoint_history::EndpointHistory;
+use crate::interface_style::{AccentColor, InterfaceStyle, style_key};
+use crate::scenario_manager::parse_start_time;
+use crate::settings::{DROP_ALERT_DEFAULT, FrontendSettings, ScenarioTarget};
 use crate::theme::{Theme, ThemeMode};
+use crate::user_presets::UserPresets;
 
 /// Available application tabs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -26,11 +34,25 @@ pub enum InterfaceViewMode {
     Table,
 }
 
+/// What the interfaces picked in the selection dialog are for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelectionPurpose {
+    /// Run the dialog's scenario on them
+    #[default]
+    ScenarioExecution,
+    /// Bring them all up or down at once
+    InterfaceControl,
+    /// Remove one impairment from them, keeping the others
+    FeatureClear,
+}
+
 /// Interface selection dialog state
 #[derive(Debug, Clone, Default)]
 pub struct InterfaceSelectionDialog {
     /// Whether the dialog is visible
     pub visible: bool,
+    /// What the selection is for
+    pub purpose: SelectionPurpose,
     /// Backend name for the execution
     pub backend_name: String,
     /// Scenario ID to execute
@@ -41,6 +63,13 @@ pub struct InterfaceSelectionDialog {
     pub selected_interfaces: HashSet<String>,
     /// Whether to loop the scenario execution
     pub loop_execution: bool,
+    /// Scheduled start time as typed (empty = start now), see
+    /// [`crate::scenario_manager::parse_start_time`]
+    pub start_at_input: String,
+    /// Why the remembered target could not be (fully) preselected
+    pub target_note: Option<String>,
+    /// Impairment to remove, for [`SelectionPurpose::FeatureClear`]
+    pub clear_feature: Option<TcFeature>,
 }
 
 /// Zoom level constraints
@@ -94,10 +123,48 @@ impl NamespaceFilter {
     }
 }
 
+/// Identity of one interface card: backend origin, namespace and interface
+/// name. Focus is keyed on this rather than a list position, so it follows
+/// the card when the list is re-sorted or filtered.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InterfaceKey {
+    pub backend_name: String,
+    pub namespace: String,
+    pub interface: String,
+}
+
+/// Quick actions menu opened by right-clicking an interface card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceMenu {
+    /// Interface the menu acts on
+    pub key: InterfaceKey,
+    /// Window position of the right click, where the menu opens
+    pub position: iced::Point,
+}
+
+/// One entry of the interface quick actions menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceMenuAction {
+    /// Apply the preset most recently picked on any interface
+    ApplyLastPreset,
+    /// Remove all TC from the interface
+    ClearTc,
+    /// Bring the interface up
+    Enable,
+    /// Bring the interface down
+    Disable,
+    RunDiagnostics,
+    /// Copy the `tc` command of the current configuration
+    CopyCommand,
+    /// Append the interface to the path
+    AddToPath,
+}
+
 /// Manager for UI state and visibility toggles.
 #[derive(Clone)]
 pub struct UiStateManager {
-    /// Set of backend names that are collapsed/hidden in the UI
+    /// Set of backend names that are collapsed/hidden in the UI. A collapsed
+    /// backend shows only its summary header.
     hidden_backends: HashSet<String>,
     /// Set of "backend_name/namespace_name" combinations that are collapsed/hidden in the UI
     hidden_namespaces: HashSet<String>,
@@ -115,6 +182,50 @@ pub struct UiStateManager {
     interface_view_mode: InterfaceViewMode,
     /// Free-text filter applied to interface names (empty = show all)
     interface_search: String,
+    /// Backend (host origin) whose details panel is open in the header, if any
+    backend_details: Option<String>,
+    /// Label being edited in the open backend details panel
+    backend_label_draft: String,
+    /// Preset ids picked for comparison in the open backend details panel
+    compared_presets: (Option<String>, Option<String>),
+    /// Interface card with keyboard focus, if any
+    focused_interface: Option<InterfaceKey>,
+    /// Ordered interfaces of the path whose end-to-end loss is shown
+    path: Vec<InterfaceKey>,
+    /// Whether the path panel is open in the header
+    path_panel_open: bool,
+    /// Change picked for applying to every hop of the path at once
+    coordinated_action: Option<CoordinatedAction>,
+    /// When to apply it, as typed (`HH:MM[:SS]` or a delay like `+10s`)
+    coordinated_at_input: String,
+    /// The last change applied to every hop at once, and how each hop fared
+    coordinated_apply: Option<CoordinatedApply>,
+    /// Last target each scenario was run on, keyed by scenario id
+    scenario_targets: BTreeMap<String, ScenarioTarget>,
+    /// Display color and label of interfaces, keyed by [`style_key`]
+    interface_styles: BTreeMap<String, InterfaceStyle>,
+    /// Connect endpoints a backend was reached through
+    endpoint_history: EndpointHistory,
+    /// Whether the connection panel is open in the header
+    connection_panel_open: bool,
+    /// Endpoint being typed in the connection panel
+    endpoint_input: String,
+    /// Whether TC reported by backends replaces the interfaces' UI settings
+    adopt_detected_tc: bool,
+    /// Loaded from and saved to [`FrontendSettings::annotate_scenario_steps`]
+    annotate_scenario_steps: bool,
+    /// Drops per second above which an interface is flagged
+    drop_alert_per_sec: f64,
+    /// Drop alert threshold being typed in the connection panel
+    drop_alert_input: String,
+    /// File custom presets are exported to and imported from
+    preset_file_input: String,
+    /// Open quick actions menu, if any
+    interface_menu: Option<InterfaceMenu>,
+    /// Preset most recently picked on any interface
+    last_preset: Option<CustomPreset>,
+    /// Presets saved from interface configurations
+    custom_presets: UserPresets,
 }
 
 impl Default for UiStateManager {
@@ -129,6 +240,28 @@ impl Default for UiStateManager {
             namespace_filter: NamespaceFilter::default(),
             interface_view_mode: InterfaceViewMode::default(),
             interface_search: String::new(),
+            backend_details: None,
+            backend_label_draft: String::new(),
+            compared_presets: (None, None),
+            focused_interface: None,
+            path: Vec::new(),
+            path_panel_open: false,
+            coordinated_action: None,
+            coordinated_at_input: String::new(),
+            coordinated_apply: None,
+            scenario_targets: BTreeMap::new(),
+            interface_styles: BTreeMap::new(),
+            endpoint_history: EndpointHistory::default(),
+            connection_panel_open: false,
+            endpoint_input: String::new(),
+            adopt_detected_tc: true,
+            annotate_scenario_steps: true,
+            drop_alert_per_sec: DROP_ALERT_DEFAULT,
+            drop_alert_input: DROP_ALERT_DEFAULT.to_string(),
+            preset_file_input: UserPresets::default_exchange_path(),
+            interface_menu: None,
+            last_preset: None,
+            custom_presets: UserPresets::default(),
         }
     }
 }
@@ -148,8 +281,8 @@ impl UiStateManager {
         };
 
         Self {
-            hidden_backends: HashSet::new(),
-            hidden_namespaces: HashSet::new(),
+            hidden_backends: settings.collapsed_backends.iter().cloned().collect(),
+            hidden_namespaces: settings.collapsed_namespaces.iter().cloned().collect(),
             current_tab: settings.current_tab.into(),
             interface_selection_dialog: InterfaceSelectionDialog::default(),
             zoom_level: settings.zoom_level,
@@ -157,6 +290,28 @@ impl UiStateManager {
             namespace_filter: settings.namespace_filter.clone().into(),
             interface_view_mode: InterfaceViewMode::default(),
             interface_search: String::new(),
+            backend_details: None,
+            backend_label_draft: String::new(),
+            compared_presets: (None, None),
+            focused_interface: None,
+            path: Vec::new(),
+            path_panel_open: false,
+            coordinated_action: None,
+            coordinated_at_input: String::new(),
+            coordinated_apply: None,
+            scenario_targets: settings.scenario_targets.clone(),
+            interface_styles: settings.interface_styles.clone(),
+            endpoint_history: EndpointHistory::from_entries(&settings.endpoint_history),
+            connection_panel_open: false,
+            endpoint_input: String::new(),
+            adopt_detected_tc: settings.adopt_detected_tc,
+            annotate_scenario_steps: settings.annotate_scenario_steps,
+            drop_alert_per_sec: settings.drop_alert_per_sec,
+            drop_alert_input: settings.drop_alert_per_sec.to_string(),
+            preset_file_input: UserPresets::default_exchange_path(),
+            interface_menu: None,
+            last_preset: None,
+            custom_presets: UserPresets::default(),
         }
     }
 
@@ -169,6 +324,15 @@ impl UiStateManager {
             zoom_level: self.zoom_level,
             namespace_filter: NamespaceFilterJson::from(&self.namespace_filter),
             current_tab: AppTabJson::from(self.current_tab),
+            scenario_targets: self.scenario_targets.clone(),
+            interface_styles: self.interface_styles.clone(),
+            collapsed_backends: self.hidden_backends.iter().cloned().collect(),
+            collapsed_namespaces: self.hidden_namespaces.iter().cloned().collect(),
+            endpoint_history: self.endpoint_history.entries().to_vec(),
+            adopt_detected_tc: self.adopt_detected_tc,
+            annotate_scenario_steps: self.annotate_scenario_steps,
+            drop_alert_per_sec: self.drop_alert_per_sec,
+            ..FrontendSettings::default()
         }
     }
 
@@ -333,6 +497,303 @@ impl UiStateManager {
         self.interface_search = search;
     }
 
+    /// Backend (host origin) whose details panel is open, if any.
+    pub fn backend_details(&self) -> Option<&str> {
+        self.backend_details.as_deref()
+    }
+
+    /// Opens the details panel for a backend, or closes it if it is already open.
+    ///
+    /// `current_label` seeds the label editor shown in the panel.
+    pub fn toggle_backend_details(&mut self, backend_name: &str, current_label: Option<&str>) {
+        if self.backend_details.as_deref() == Some(backend_name) {
+            self.backend_details = None;
+        } else {
+            self.backend_details = Some(backend_name.to_string());
+            self.backend_label_draft = current_label.unwrap_or_default().to_string();
+            self.compared_presets = (None, None);
+        }
+    }
+
+    /// Preset ids picked for comparison, left and right
+    pub fn compared_presets(&self) -> (Option<&str>, Option<&str>) {
+        (
+            self.compared_presets.0.as_deref(),
+            self.compared_presets.1.as_deref(),
+        )
+    }
+
+    /// Pick the preset shown on the left (`right == false`) or right side
+    /// of the comparison
+    pub fn set_compared_preset(&mut self, right: bool, preset_id: String) {
+        if right {
+            self.compared_presets.1 = Some(preset_id);
+        } else {
+            self.compared_presets.0 = Some(preset_id);
+        }
+    }
+
+    /// Label being edited in the backend details panel.
+    pub fn backend_label_draft(&self) -> &str {
+        &self.backend_label_draft
+    }
+
+    /// Update the label being edited in the backend details panel.
+    pub fn set_backend_label_draft(&mut self, label: String) {
+        self.backend_label_draft = label;
+    }
+
+    /// Interface card with keyboard focus, if any.
+    pub fn focused_interface(&self) -> Option<&InterfaceKey> {
+        self.focused_interface.as_ref()
+    }
+
+    /// Move keyboard focus `step` cards through `order` (the cards as laid
+    /// out). Without a focus, or if the focused card is no longer listed,
+    /// focus lands on the first card when moving forward and the last when
+    /// moving back. Movement stops at either end.
+    pub fn move_interface_focus(&mut self, order: &[InterfaceKey], step: isize) {
+        let Some(last) = order.len().checked_sub(1) else {
+            self.focused_interface = None;
+            return;
+        };
+        let position = self
+            .focused_interface
+            .as_ref()
+            .and_then(|focused| order.iter().position(|key| key == focused));
+        let next = match position {
+            Some(index) => index.saturating_add_signed(step).min(last),
+            None if step < 0 => last,
+            None => 0,
+        };
+        self.focused_interface = Some(order[next].clone());
+    }
+
+    /// Drop keyboard focus.
+    pub fn clear_interface_focus(&mut self) {
+        self.focused_interface = None;
+    }
+
+    /// Hops of the user-defined path, in order
+    pub fn path(&self) -> &[InterfaceKey] {
+        &self.path
+    }
+
+    /// Whether the path panel is open
+    pub fn is_path_panel_open(&self) -> bool {
+        self.path_panel_open
+    }
+
+    /// Open or close the path panel
+    pub fn toggle_path_panel(&mut self) {
+        self.path_panel_open = !self.path_panel_open;
+    }
+
+    /// Whether the connection panel is open
+    pub fn is_connection_panel_open(&self) -> bool {
+        self.connection_panel_open
+    }
+
+    /// Open or close the connection panel
+    pub fn toggle_connection_panel(&mut self) {
+        self.connection_panel_open = !self.connection_panel_open;
+    }
+
+    /// Endpoint being typed in the connection panel
+    pub fn endpoint_input(&self) -> &str {
+        &self.endpoint_input
+    }
+
+    pub fn set_endpoint_input(&mut self, input: String) {
+        self.endpoint_input = input;
+    }
+
+    /// Connect endpoints a backend was reached through
+    pub fn endpoint_history(&self) -> &EndpointHistory {
+        &self.endpoint_history
+    }
+
+    pub fn endpoint_history_mut(&mut self) -> &mut EndpointHistory {
+        &mut self.endpoint_history
+    }
+
+    /// Whether TC reported by backends replaces the interfaces' UI settings
+    pub fn adopt_detected_tc(&self) -> bool {
+        self.adopt_detected_tc
+    }
+
+    pub fn set_adopt_detected_tc(&mut self, adopt: bool) {
+        self.adopt_detected_tc = adopt;
+    }
+
+    /// Whether an execution update should add a step marker to the chart
+    pub fn annotate_scenario_steps(&self) -> bool {
+        self.annotate_scenario_steps
+    }
+
+    pub fn set_annotate_scenario_steps(&mut self, annotate: bool) {
+        self.annotate_scenario_steps = annotate;
+    }
+
+    /// Drops per second above which an interface is flagged
+    pub fn drop_alert_per_sec(&self) -> f64 {
+        self.drop_alert_per_sec
+    }
+
+    pub fn drop_alert_input(&self) -> &str {
+        &self.drop_alert_input
+    }
+
+    /// Edit the drop alert threshold; it changes once the input is a
+    /// non-negative number. Returns whether it changed.
+    pub fn set_drop_alert_input(&mut self, input: String) -> bool {
+        let threshold = input
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|threshold| *threshold >= 0.0);
+        self.drop_alert_input = input;
+        match threshold {
+            Some(threshold) if threshold != self.drop_alert_per_sec => {
+                self.drop_alert_per_sec = threshold;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn preset_file_input(&self) -> &str {
+        &self.preset_file_input
+    }
+
+    pub fn set_preset_file_input(&mut self, input: String) {
+        self.preset_file_input = input;
+    }
+
+    /// Presets saved from interface configurations
+    pub fn custom_presets(&self) -> &UserPresets {
+        &self.custom_presets
+    }
+
+    pub fn custom_presets_mut(&mut self) -> &mut UserPresets {
+        &mut self.custom_presets
+    }
+
+    pub fn set_custom_presets(&mut self, presets: UserPresets) {
+        self.custom_presets = presets;
+    }
+
+    /// Open the quick actions menu of an interface at `position`
+    pub fn open_interface_menu(&mut self, key: InterfaceKey, position: iced::Point) {
+        self.interface_menu = Some(InterfaceMenu { key, position });
+    }
+
+    /// Close the quick actions menu. Returns whether it was open.
+    pub fn close_interface_menu(&mut self) -> bool {
+        self.interface_menu.take().is_some()
+    }
+
+    /// Open quick actions menu, if any
+    pub fn interface_menu(&self) -> Option<&InterfaceMenu> {
+        self.interface_menu.as_ref()
+    }
+
+    /// Preset most recently picked on any interface
+    pub fn last_preset(&self) -> Option<&CustomPreset> {
+        self.last_preset.as_ref()
+    }
+
+    pub fn set_last_preset(&mut self, preset: CustomPreset) {
+        self.last_preset = Some(preset);
+    }
+
+    /// Entries of the quick actions menu for an interface, in menu order:
+    /// "Clear TC" and "Copy command" only with TC present, "Enable" or
+    /// "Disable" depending on the link, "Add to path" unless already on it.
+    pub fn interface_menu_actions(
+        &self,
+        key: &InterfaceKey,
+        is_up: bool,
+        has_tc: bool,
+    ) -> Vec<InterfaceMenuAction> {
+        let mut actions = Vec::new();
+        if self.last_preset.is_some() {
+            actions.push(InterfaceMenuAction::ApplyLastPreset);
+        }
+        if has_tc {
+            actions.push(InterfaceMenuAction::ClearTc);
+        }
+        actions.push(if is_up {
+            InterfaceMenuAction::Disable
+        } else {
+            InterfaceMenuAction::Enable
+        });
+        actions.push(InterfaceMenuAction::RunDiagnostics);
+        if has_tc {
+            actions.push(InterfaceMenuAction::CopyCommand);
+        }
+        if !self.path.contains(key) {
+            actions.push(InterfaceMenuAction::AddToPath);
+        }
+        actions
+    }
+
+    /// Append a hop to the path. A path visits each interface once, so a hop
+    /// already on it is ignored.
+    pub fn add_path_hop(&mut self, key: InterfaceKey) {
+        if !self.path.contains(&key) {
+            self.path.push(key);
+        }
+    }
+
+    /// Remove the hop at `index` (no-op when out of range)
+    pub fn remove_path_hop(&mut self, index: usize) {
+        if index < self.path.len() {
+            self.path.remove(index);
+        }
+    }
+
+    /// Remove every hop
+    pub fn clear_path(&mut self) {
+        self.path.clear();
+    }
+
+    /// Change picked for applying to every hop at once
+    pub fn coordinated_action(&self) -> Option<&CoordinatedAction> {
+        self.coordinated_action.as_ref()
+    }
+
+    pub fn set_coordinated_action(&mut self, action: CoordinatedAction) {
+        self.coordinated_action = Some(action);
+    }
+
+    /// When to apply the coordinated change, as typed
+    pub fn coordinated_at_input(&self) -> &str {
+        &self.coordinated_at_input
+    }
+
+    pub fn set_coordinated_at_input(&mut self, input: String) {
+        self.coordinated_at_input = input;
+    }
+
+    /// The last coordinated change and how each hop fared
+    pub fn coordinated_apply(&self) -> Option<&CoordinatedApply> {
+        self.coordinated_apply.as_ref()
+    }
+
+    pub fn coordinated_apply_mut(&mut self) -> Option<&mut CoordinatedApply> {
+        self.coordinated_apply.as_mut()
+    }
+
+    /// Start following a coordinated change, replacing the previous one
+    pub fn start_coordinated_apply(&mut self, apply: CoordinatedApply) {
+        self.coordinated_apply = Some(apply);
+    }
+
+    pub fn dismiss_coordinated_apply(&mut self) {
+        self.coordinated_apply = None;
+    }
+
     /// Set the current tab
     pub fn set_current_tab(&mut self, tab: AppTab) {
         self.current_tab = tab;
@@ -360,14 +821,156 @@ impl UiStateManager {
     pub fn show_interface_selection_dialog(&mut self, backend_name: String, scenario_id: String) {
         self.interface_selection_dialog = InterfaceSelectionDialog {
             visible: true,
+            purpose: SelectionPurpose::ScenarioExecution,
             backend_name,
             scenario_id,
             selected_namespace: None,
             selected_interfaces: HashSet::new(),
             loop_execution: false,
+            start_at_input: String::new(),
+            target_note: None,
+            clear_feature: None,
+        };
+    }
+
+    /// Show the interface selection dialog to bring several interfaces of a
+    /// backend up or down at once
+    pub fn show_interface_control_dialog(&mut self, backend_name: String) {
+        self.interface_selection_dialog = InterfaceSelectionDialog {
+            visible: true,
+            purpose: SelectionPurpose::InterfaceControl,
+            backend_name,
+            ..InterfaceSelectionDialog::default()
+        };
+    }
+
+    /// Show the interface selection dialog to remove one impairment from
+    /// several interfaces of a backend at once
+    pub fn show_feature_clear_dialog(&mut self, backend_name: String) {
+        self.interface_selection_dialog = InterfaceSelectionDialog {
+            visible: true,
+            purpose: SelectionPurpose::FeatureClear,
+            backend_name,
+            ..InterfaceSelectionDialog::default()
         };
     }
 
+    /// Pick the impairment the feature clear dialog removes
+    pub fn select_clear_feature(&mut self, feature: TcFeature) {
+        self.interface_selection_dialog.clear_feature = Some(feature);
+    }
+
+    /// Preselect the target the dialog's scenario was last run on, if it was
+    /// on this backend. `is_available(namespace, interface)` tells whether an
+    /// interface still exists; missing ones are left out and noted, and if
+    /// none remain nothing is preselected.
+    pub fn preselect_remembered_target(&mut self, is_available: impl Fn(&str, &str) -> bool) {
+        let dialog = &mut self.interface_selection_dialog;
+        let Some(target) = self.scenario_targets.get(&dialog.scenario_id) else {
+            return;
+        };
+        if target.backend != dialog.backend_name {
+            return;
+        }
+
+        let (available, missing): (Vec<&String>, Vec<&String>) = target
+            .interfaces
+            .iter()
+            .partition(|interface| is_available(&target.namespace, interface));
+        if available.is_empty() {
+            dialog.target_note = Some(format!(
+                "Last target {}/{} no longer exists, select a target",
+                target.namespace,
+                target.interfaces.join(", ")
+            ));
+            return;
+        }
+
+        dialog.selected_namespace = Some(target.namespace.clone());
+        dialog.selected_interfaces = available.into_iter().cloned().collect();
+        if !missing.is_empty() {
+            dialog.target_note = Some(format!(
+                "{} from the last run no longer exist",
+                missing
+                    .iter()
+                    .map(|interface| format!("{}/{}", target.namespace, interface))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+
+    /// Remember the dialog's current selection as its scenario's target.
+    pub fn remember_execution_target(&mut self) {
+        let dialog = &self.interface_selection_dialog;
+        let Some(namespace) = &dialog.selected_namespace else {
+            return;
+        };
+        let mut interfaces: Vec<String> = dialog.selected_interfaces.iter().cloned().collect();
+        interfaces.sort();
+        self.scenario_targets.insert(
+            dialog.scenario_id.clone(),
+            ScenarioTarget {
+                backend: dialog.backend_name.clone(),
+                namespace: namespace.clone(),
+                interfaces,
+            },
+        );
+    }
+
+    /// Gets the display color and label of an interface, if any is set.
+    pub fn interface_style(
+        &self,
+        backend_name: &str,
+        namespace: &str,
+        interface: &str,
+    ) -> Option<&InterfaceStyle> {
+        self.interface_styles
+            .get(&style_key(backend_name, namespace, interface))
+    }
+
+    /// Sets or clears the display color of an interface.
+    pub fn set_interface_color(
+        &mut self,
+        backend_name: &str,
+        namespace: &str,
+        interface: &str,
+        color: Option<AccentColor>,
+    ) {
+        self.update_interface_style(backend_name, namespace, interface, |style| {
+            style.color = color
+        });
+    }
+
+    /// Sets the display label of an interface; a blank label clears it.
+    pub fn set_interface_label(
+        &mut self,
+        backend_name: &str,
+        namespace: &str,
+        interface: &str,
+        label: &str,
+    ) {
+        self.update_interface_style(backend_name, namespace, interface, |style| {
+            style.set_label(label)
+        });
+    }
+
+    /// Applies a change to an interface's style, dropping it once empty.
+    fn update_interface_style(
+        &mut self,
+        backend_name: &str,
+        namespace: &str,
+        interface: &str,
+        change: impl FnOnce(&mut InterfaceStyle),
+    ) {
+        let key = style_key(backend_name, namespace, interface);
+        let style = self.interface_styles.entry(key.clone()).or_default();
+        change(style);
+        if style.is_empty() {
+            self.interface_styles.remove(&key);
+        }
+    }
+
     /// Hide the interface selection dialog
     pub fn hide_interface_selection_dialog(&mut self) {
         self.interface_selection_dialog = InterfaceSelectionDialog::default();
@@ -402,13 +1005,38 @@ impl UiStateManager {
         }
     }
 
-    /// Check if execution can be confirmed (namespace selected and at least one interface)
+    /// Select every given interface in the dialog
+    pub fn select_all_execution_interfaces(
+        &mut self,
+        interfaces: impl IntoIterator<Item = String>,
+    ) {
+        self.interface_selection_dialog
+            .selected_interfaces
+            .extend(interfaces);
+    }
+
+    /// Check if execution can be confirmed (namespace selected, at least one
+    /// interface, and a valid start time if one was entered)
     pub fn can_confirm_execution(&self) -> bool {
+        let dialog = &self.interface_selection_dialog;
+        if dialog.purpose == SelectionPurpose::FeatureClear && dialog.clear_feature.is_none() {
+            return false;
+        }
         self.interface_selection_dialog.selected_namespace.is_some()
             && !self
                 .interface_selection_dialog
                 .selected_interfaces
                 .is_empty()
+            && parse_start_time(
+                &self.interface_selection_dialog.start_at_input,
+                chrono::Local::now(),
+            )
+            .is_ok()
+    }
+
+    /// Set the scheduled start time input in the dialog
+    pub fn set_execution_start_at(&mut self, input: String) {
+        self.interface_selection_dialog.start_at_input = input;
     }
 
     /// Toggle loop execution in the dialog
@@ -537,6 +1165,47 @@ mod tests {
         assert!(!manager.is_namespace_hidden("backend1", "ns1"));
     }
 
+    #[test]
+    fn test_collapse_state_survives_settings_round_trip() {
+        let mut manager = UiStateManager::new();
+        manager.toggle_backend_visibility("h-000000000001");
+        manager.toggle_namespace_visibility("h-000000000002", "lab");
+
+        let manager = UiStateManager::from_settings(&manager.to_settings());
+        assert!(manager.is_backend_hidden("h-000000000001"));
+        assert!(manager.is_namespace_hidden("h-000000000002", "lab"));
+        assert!(!manager.is_backend_hidden("h-000000000002"));
+    }
+
+    #[test]
+    fn test_interface_style_persists_and_clears() {
+        let mut manager = UiStateManager::new();
+        manager.set_interface_color("h-000000000001", "lab", "veth0", Some(AccentColor::Blue));
+        manager.set_interface_label("h-000000000001", "lab", "veth0", "client-uplink");
+
+        let mut manager = UiStateManager::from_settings(&manager.to_settings());
+        let style = manager
+            .interface_style("h-000000000001", "lab", "veth0")
+            .unwrap();
+        assert_eq!(style.color, Some(AccentColor::Blue));
+        assert_eq!(style.display_name("veth0"), "client-uplink");
+        assert!(
+            manager
+                .interface_style("h-000000000001", "lab", "veth1")
+                .is_none()
+        );
+
+        // Clearing both overrides forgets the interface
+        manager.set_interface_color("h-000000000001", "lab", "veth0", None);
+        manager.set_interface_label("h-000000000001", "lab", "veth0", "");
+        assert!(
+            manager
+                .interface_style("h-000000000001", "lab", "veth0")
+                .is_none()
+        );
+        assert!(manager.to_settings().interface_styles.is_empty());
+    }
+
     #[test]
     fn test_cleanup_backend_state() {
         let mut manager = UiStateManager::new();
@@ -603,6 +1272,104 @@ mod tests {
         assert_eq!(manager.current_tab(), AppTab::Scenarios);
     }
 
+    fn key(interface: &str) -> InterfaceKey {
+        InterfaceKey {
+            backend_name: "h-000000000001".to_string(),
+            namespace: "default".to_string(),
+            interface: interface.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_interface_menu_adapts_to_interface_state() {
+        use InterfaceMenuAction::*;
+        let mut manager = UiStateManager::new();
+
+        let actions = manager.interface_menu_actions(&key("eth0"), false, false);
+        assert_eq!(actions, vec![Enable, RunDiagnostics, AddToPath]);
+
+        manager.set_last_preset(CustomPreset {
+            id: "lossy".to_string(),
+            name: "Lossy".to_string(),
+            description: String::new(),
+            config: tcgui_shared::TcNetemConfig::default(),
+        });
+        manager.add_path_hop(key("eth0"));
+        let actions = manager.interface_menu_actions(&key("eth0"), true, true);
+        assert_eq!(
+            actions,
+            vec![
+                ApplyLastPreset,
+                ClearTc,
+                Disable,
+                RunDiagnostics,
+                CopyCommand
+            ]
+        );
+
+        manager.open_interface_menu(key("eth0"), iced::Point::new(10.0, 20.0));
+        assert_eq!(manager.interface_menu().unwrap().key, key("eth0"));
+        assert!(manager.close_interface_menu());
+        assert!(!manager.close_interface_menu());
+    }
+
+    #[test]
+    fn test_interface_focus_moves_and_clamps() {
+        let mut manager = UiStateManager::new();
+        let order = vec![key("eth0"), key("eth1"), key("eth2")];
+
+        manager.move_interface_focus(&order, 1);
+        assert_eq!(manager.focused_interface(), Some(&key("eth0")));
+        manager.move_interface_focus(&order, 1);
+        manager.move_interface_focus(&order, 1);
+        manager.move_interface_focus(&order, 1);
+        assert_eq!(manager.focused_interface(), Some(&key("eth2")));
+        manager.move_interface_focus(&order, -1);
+        assert_eq!(manager.focused_interface(), Some(&key("eth1")));
+
+        manager.clear_interface_focus();
+        manager.move_interface_focus(&order, -1);
+        assert_eq!(manager.focused_interface(), Some(&key("eth2")));
+
+        manager.move_interface_focus(&[], 1);
+        assert_eq!(manager.focused_interface(), None);
+    }
+
+    #[test]
+    fn test_interface_focus_follows_identity_across_reorder() {
+        let mut manager = UiStateManager::new();
+        let order = vec![key("eth0"), key("eth1"), key("eth2")];
+        manager.move_interface_focus(&order, 1);
+        manager.move_interface_focus(&order, 1);
+        assert_eq!(manager.focused_interface(), Some(&key("eth1")));
+
+        // eth1 moves to the end; the next step is relative to its new place
+        let reordered = vec![key("eth0"), key("eth2"), key("eth1")];
+        manager.move_interface_focus(&reordered, -1);
+        assert_eq!(manager.focused_interface(), Some(&key("eth2")));
+    }
+
+    #[test]
+    fn test_backend_details_toggle() {
+        let mut manager = UiStateManager::new();
+        assert_eq!(manager.backend_details(), None);
+
+        manager.toggle_backend_details("h-000000000001", Some("Lab Gateway"));
+        assert_eq!(manager.backend_details(), Some("h-000000000001"));
+        assert_eq!(manager.backend_label_draft(), "Lab Gateway");
+        manager.set_compared_preset(false, "satellite".to_string());
+
+        // Opening another backend replaces the open panel, its draft label
+        // and the presets picked for comparison
+        manager.toggle_backend_details("h-000000000002", None);
+        assert_eq!(manager.backend_details(), Some("h-000000000002"));
+        assert_eq!(manager.backend_label_draft(), "");
+        assert_eq!(manager.compared_presets(), (None, None));
+
+        manager.toggle_backend_details("h-000000000002", None);
+        assert_eq!(manager.backend_details(), None);
+    }
+
     #[test]
     fn test_tab_switching() {
         let mut manager = UiStateManager::new();
@@ -616,6 +1383,109 @@ mod tests {
         assert_eq!(manager.current_tab(), AppTab::Interfaces);
     }
 
+    #[test]
+    fn test_scenario_target_is_remembered_and_preselected() {
+        let mut manager = UiStateManager::new();
+        manager.show_interface_selection_dialog("backend1".to_string(), "scenario1".to_string());
+        manager.select_execution_namespace("lab".to_string());
+        manager.toggle_execution_interface("veth0".to_string());
+        manager.toggle_execution_interface("veth1".to_string());
+        manager.remember_execution_target();
+        manager.hide_interface_selection_dialog();
+
+        // Survives a settings round trip
+        let mut manager = UiStateManager::from_settings(&manager.to_settings());
+
+        // Everything still there: preselected, no note
+        manager.show_interface_selection_dialog("backend1".to_string(), "scenario1".to_string());
+        manager.preselect_remembered_target(|_, _| true);
+        let dialog = manager.interface_selection_dialog();
+        assert_eq!(dialog.selected_namespace.as_deref(), Some("lab"));
+        assert_eq!(dialog.selected_interfaces.len(), 2);
+        assert!(dialog.target_note.is_none());
+        assert!(manager.can_confirm_execution());
+
+        // One interface gone: the rest is preselected with a note
+        manager.show_interface_selection_dialog("backend1".to_string(), "scenario1".to_string());
+        manager.preselect_remembered_target(|_, interface| interface == "veth0");
+        let dialog = manager.interface_selection_dialog();
+        assert!(dialog.selected_interfaces.contains("veth0"));
+        assert!(dialog.target_note.as_deref().unwrap().contains("lab/veth1"));
+
+        // All gone: nothing preselected, selection required
+        manager.show_interface_selection_dialog("backend1".to_string(), "scenario1".to_string());
+        manager.preselect_remembered_target(|_, _| false);
+        assert!(manager.interface_selection_dialog().target_note.is_some());
+        assert!(!manager.can_confirm_execution());
+
+        // Another backend or scenario: untouched
+        manager.show_interface_selection_dialog("backend2".to_string(), "scenario1".to_string());
+        manager.preselect_remembered_target(|_, _| true);
+        assert!(
+            manager
+                .interface_selection_dialog()
+                .selected_namespace
+                .is_none()
+        );
+        manager.show_interface_selection_dialog("backend1".to_string(), "scenario2".to_string());
+        manager.preselect_remembered_target(|_, _| true);
+        assert!(
+            manager
+                .interface_selection_dialog()
+                .selected_namespace
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_interface_control_dialog_selects_all() {
+        let mut manager = UiStateManager::new();
+        manager.show_interface_control_dialog("backend1".to_string());
+        let dialog = manager.interface_selection_dialog();
+        assert!(dialog.visible);
+        assert_eq!(dialog.purpose, SelectionPurpose::InterfaceControl);
+        assert!(!manager.can_confirm_execution());
+
+        manager.select_execution_namespace("lab".to_string());
+        manager.toggle_execution_interface("veth0".to_string());
+        manager.select_all_execution_interfaces(["veth0".to_string(), "veth1".to_string()]);
+        assert_eq!(
+            manager
+                .interface_selection_dialog()
+                .selected_interfaces
+                .len(),
+            2
+        );
+        assert!(manager.can_confirm_execution());
+
+        // Reopening for a scenario goes back to execution
+        manager.show_interface_selection_dialog("backend1".to_string(), "scenario1".to_string());
+        assert_eq!(
+            manager.interface_selection_dialog().purpose,
+            SelectionPurpose::ScenarioExecution
+        );
+    }
+
+    #[test]
+    fn test_feature_clear_dialog_needs_a_feature() {
+        let mut manager = UiStateManager::new();
+        manager.show_feature_clear_dialog("backend1".to_string());
+        manager.select_execution_namespace("lab".to_string());
+        manager.select_all_execution_interfaces(["veth0".to_string(), "veth1".to_string()]);
+        assert!(!manager.can_confirm_execution());
+
+        manager.select_clear_feature(TcFeature::RateLimit);
+        assert!(manager.can_confirm_execution());
+        assert_eq!(
+            manager.interface_selection_dialog().clear_feature,
+            Some(TcFeature::RateLimit)
+        );
+
+        // Reopening starts without a feature picked
+        manager.show_feature_clear_dialog("backend1".to_string());
+        assert_eq!(manager.interface_selection_dialog().clear_feature, None);
+    }
+
     #[test]
     fn test_interface_selection_dialog() {
         let mut manager = UiStateManager::new();
@@ -660,6 +1530,12 @@ mod tests {
         manager.toggle_loop_execution();
         assert!(manager.interface_selection_dialog().loop_execution);
 
+        // An invalid start time blocks confirmation
+        manager.set_execution_start_at("soon".to_string());
+        assert!(!manager.can_confirm_execution());
+        manager.set_execution_start_at("+5m".to_string());
+        assert!(manager.can_confirm_execution());
+
         // Hide dialog
         manager.hide_interface_selection_dialog();
         assert!(!manager.interface_selection_dialog().visible);
@@ -728,4 +1604,26 @@ mod tests {
         assert_eq!(namespaces.len(), 1);
         assert!(namespaces.contains(&"backend1/ns1".to_string()));
     }
+
+    #[test]
+    fn test_path_hops() {
+        let mut manager = UiStateManager::new();
+        let hop = |interface: &str| InterfaceKey {
+            backend_name: "h-000000000001".to_string(),
+            namespace: "default".to_string(),
+            interface: interface.to_string(),
+        };
+
+        manager.add_path_hop(hop("veth0"));
+        manager.add_path_hop(hop("veth1"));
+        manager.add_path_hop(hop("veth0")); // already on the path
+        assert_eq!(manager.path(), &[hop("veth0"), hop("veth1")]);
+
+        manager.remove_path_hop(5);
+        manager.remove_path_hop(0);
+        assert_eq!(manager.path(), &[hop("veth1")]);
+
+        manager.clear_path();
+        assert!(manager.path().is_empty());
+    }
 }
@@ -0,0 +1,213 @@
+//! Custom presets saved from the UI.
+//!
+//! Built-in presets come from the backend and are read-only. Configurations
+//! the user saves as presets are kept in `~/.config/tcgui/presets.json` and
+//! offered next to the built-in ones on every backend.
+
+use std::fs;
+use std::path::PathBuf;
+
+use tcgui_shared::presets::{CustomPreset, PresetList};
+use tracing::{debug, error, info};
+
+use crate::settings::{CONFIG_DIR, SettingsError};
+
+/// Custom presets file name
+const PRESETS_FILE: &str = "presets.json";
+
+/// File presets are shared through unless the user picks another, in the
+/// home directory
+const EXCHANGE_FILE: &str = "tcgui-presets.json";
+
+/// Prefix of custom preset ids, keeping them apart from built-in ones
+const CUSTOM_ID_PREFIX: &str = "custom-";
+
+/// Id of the custom preset named `name`: names differing only in case or
+/// punctuation share an id, and saving one replaces the other.
+pub fn custom_preset_id(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("{}{}", CUSTOM_ID_PREFIX, slug)
+}
+
+/// The custom presets, in the order they were first saved.
+#[derive(Debug, Clone, Default)]
+pub struct UserPresets {
+    presets: PresetList,
+}
+
+impl UserPresets {
+    /// Gets the path to the custom presets file.
+    ///
+    /// Returns `~/.config/tcgui/presets.json` on Linux.
+    pub fn presets_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|config| config.join(CONFIG_DIR).join(PRESETS_FILE))
+    }
+
+    /// Suggested file to export presets to and import them from.
+    pub fn default_exchange_path() -> String {
+        dirs::home_dir()
+            .map(|home| home.join(EXCHANGE_FILE).display().to_string())
+            .unwrap_or_else(|| EXCHANGE_FILE.to_string())
+    }
+
+    /// Loads the custom presets.
+    ///
+    /// Returns no presets if the file doesn't exist or can't be parsed.
+    pub fn load() -> Self {
+        let Some(path) = Self::presets_path() else {
+            return Self::default();
+        };
+
+        if !path.exists() {
+            debug!("Custom presets file does not exist");
+            return Self::default();
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(content) => match Self::from_json(&content) {
+                Ok(presets) => {
+                    info!(
+                        "Loaded {} custom preset(s) from {}",
+                        presets.list().len(),
+                        path.display()
+                    );
+                    presets
+                }
+                Err(e) => {
+                    error!("Failed to parse custom presets file: {}", e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                error!("Failed to read custom presets file: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    fn from_json(content: &str) -> Result<Self, serde_json::Error> {
+        let presets: Vec<CustomPreset> = serde_json::from_str(content)?;
+        Ok(Self {
+            presets: PresetList::new(presets),
+        })
+    }
+
+    /// Saves the custom presets to their file.
+    ///
+    /// Creates the configuration directory if it doesn't exist.
+    pub fn save(&self) -> Result<(), SettingsError> {
+        let path = Self::presets_path().ok_or(SettingsError::NoConfigDir)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| SettingsError::CreateDir(e.to_string()))?;
+        }
+
+        fs::write(&path, self.to_json()).map_err(|e| SettingsError::Write(e.to_string()))?;
+
+        debug!("Saved custom presets to {}", path.display());
+        Ok(())
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self.presets.all()).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// The custom presets
+    pub fn list(&self) -> &PresetList {
+        &self.presets
+    }
+
+    /// Add imported presets, replacing custom presets of the same name. Ids
+    /// are derived from the names, as for presets saved here.
+    pub fn import(&mut self, presets: PresetList) {
+        for mut preset in presets.presets {
+            preset.id = custom_preset_id(&preset.name);
+            self.insert(preset);
+        }
+    }
+
+    /// Add `preset`, replacing the custom preset with the same id. Returns
+    /// whether one was replaced.
+    pub fn insert(&mut self, preset: CustomPreset) -> bool {
+        let presets = &mut self.presets.presets;
+        match presets.iter_mut().find(|p| p.id == preset.id) {
+            Some(existing) => {
+                *existing = preset;
+                true
+            }
+            None => {
+                presets.push(preset);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tcgui_shared::TcNetemConfig;
+
+    fn preset(name: &str, loss: f32) -> CustomPreset {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = loss;
+        CustomPreset {
+            id: custom_preset_id(name),
+            name: name.to_string(),
+            description: String::new(),
+            config,
+        }
+    }
+
+    #[test]
+    fn test_custom_preset_id() {
+        assert_eq!(custom_preset_id("Lossy Wi-Fi"), "custom-lossy-wi-fi");
+        assert_eq!(custom_preset_id("  lossy  WI-FI! "), "custom-lossy-wi-fi");
+    }
+
+    #[test]
+    fn test_insert_overwrites_same_name() {
+        let mut presets = UserPresets::default();
+        assert!(!presets.insert(preset("Lab", 1.0)));
+        assert!(!presets.insert(preset("Uplink", 2.0)));
+        assert!(presets.insert(preset("lab", 5.0)));
+
+        let list = presets.list();
+        assert_eq!(list.len(), 2);
+        let lab = list.find_by_id("custom-lab").unwrap();
+        assert_eq!(lab.name, "lab");
+        assert_eq!(lab.config.loss.percentage, 5.0);
+    }
+
+    #[test]
+    fn test_import_renames_ids() {
+        let mut presets = UserPresets::default();
+        presets.insert(preset("Lab", 1.0));
+
+        let mut shared = preset("lab", 4.0);
+        shared.id = "lab".to_string();
+        let mut uplink = preset("Uplink", 2.0);
+        uplink.id = "uplink".to_string();
+        presets.import(PresetList::new(vec![shared, uplink]));
+
+        let ids: Vec<_> = presets.list().all().iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["custom-lab", "custom-uplink"]);
+        assert_eq!(presets.list().all()[0].config.loss.percentage, 4.0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut presets = UserPresets::default();
+        presets.insert(preset("Lab", 1.5));
+
+        let parsed = UserPresets::from_json(&presets.to_json()).unwrap();
+        assert_eq!(parsed.list().all(), presets.list().all());
+        assert!(UserPresets::from_json("not json").is_err());
+    }
+}
@@ -3,19 +3,26 @@
 //! This module provides a comprehensive UI that displays backend and interface information
 //! with modern styling, bandwidth summaries, and full traffic control features.
 
-use crate::backend_manager::{BackendGroup, BackendManager, NamespaceGroup};
+use crate::backend_manager::{BackendGroup, BackendManager, NamespaceDiagnostics, NamespaceGroup};
 use crate::bandwidth_history::BandwidthHistoryManager;
+use crate::diagnostics_history::DiagnosticsHistoryManager;
 use crate::icons::Icon;
+use crate::interface::TcInterface;
+use crate::interface::preset::compare::{DifferenceKind, compare_configs};
 use crate::messages::TcGuiMessage;
+use crate::path_stats::{PathSummary, path_candidates, summarize_path};
 use crate::scenario_manager::ScenarioManager;
 use crate::scenario_view;
 use crate::table_view;
 use crate::theme::{Theme, ThemeMode};
-use crate::ui_state::{InterfaceViewMode, UiStateManager};
-use iced::widget::{button, checkbox, column, container, row, scrollable, space, text, text_input};
+use crate::ui_state::{InterfaceKey, InterfaceViewMode, SelectionPurpose, UiStateManager};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text, text_input,
+};
 use iced::{Color, Element, Length};
 use std::collections::HashMap;
-use tcgui_shared::NamespaceType;
+use tcgui_shared::presets::PresetList;
+use tcgui_shared::{DiagnosticsTest, InterfaceControlOperation, NamespaceType, TcFeature};
 
 /// Helper function to scale a font size by the zoom factor.
 /// Takes a base size and zoom level, returns the scaled size as f32 for Iced Pixels.
@@ -77,6 +84,7 @@ pub fn render_main_view<'a>(
     bandwidth_history: &'a BandwidthHistoryManager,
     ui_state: &'a UiStateManager,
     _scenario_manager: &'a ScenarioManager,
+    session_endpoints: &'a [String],
 ) -> Element<'a, TcGuiMessage> {
     let theme = ui_state.theme();
     let colors = ColorPalette::from_theme(theme);
@@ -89,7 +97,14 @@ pub fn render_main_view<'a>(
         .values()
         .any(|bg| bg.is_connected);
 
-    let header = render_header(backend_manager, ui_state, theme, colors.clone(), zoom);
+    let header = render_header(
+        backend_manager,
+        ui_state,
+        session_endpoints,
+        theme,
+        colors.clone(),
+        zoom,
+    );
     let tabs = render_tabs(ui_state, colors.clone(), zoom);
 
     let content = match ui_state.current_tab() {
@@ -126,11 +141,157 @@ pub fn render_main_view<'a>(
             render_interface_selection_dialog(backend_manager, ui_state, colors, zoom)
         ]
         .into()
+    } else if let Some(menu) = render_interface_menu(backend_manager, ui_state, colors, zoom) {
+        // A click anywhere outside the menu closes it
+        let backdrop =
+            iced::widget::mouse_area(container(space()).width(Length::Fill).height(Length::Fill))
+                .on_press(TcGuiMessage::CloseInterfaceMenu)
+                .on_right_press(TcGuiMessage::CloseInterfaceMenu);
+        iced::widget::stack![main_content, backdrop, menu].into()
     } else {
         main_content.into()
     }
 }
 
+/// Renders the quick actions menu of an interface card at the right-click
+/// position, if one is open and the interface still exists
+fn render_interface_menu<'a>(
+    backend_manager: &'a BackendManager,
+    ui_state: &'a UiStateManager,
+    colors: ColorPalette,
+    zoom: f32,
+) -> Option<Element<'a, TcGuiMessage>> {
+    use crate::ui_state::InterfaceMenuAction;
+
+    let menu = ui_state.interface_menu()?;
+    let key = &menu.key;
+    let interface = backend_manager
+        .backends()
+        .get(&key.backend_name)?
+        .namespaces
+        .get(&key.namespace)?
+        .tc_interfaces
+        .get(&key.interface)?;
+
+    let target = || {
+        (
+            key.backend_name.clone(),
+            key.namespace.clone(),
+            key.interface.clone(),
+        )
+    };
+    let entries = ui_state
+        .interface_menu_actions(key, interface.is_up(), interface.has_tc_qdisc())
+        .into_iter()
+        .filter_map(|action| {
+            let (backend_name, namespace, interface_name) = target();
+            let (label, message) = match action {
+                InterfaceMenuAction::ApplyLastPreset => {
+                    let preset = ui_state.last_preset()?.clone();
+                    (
+                        format!("Apply \"{}\"", preset.name),
+                        TcGuiMessage::TcInterfaceMessage(
+                            backend_name,
+                            namespace,
+                            interface_name,
+                            crate::messages::TcInterfaceMessage::PresetSelected(preset),
+                        ),
+                    )
+                }
+                InterfaceMenuAction::ClearTc => (
+                    "Clear TC".to_string(),
+                    TcGuiMessage::TcInterfaceMessage(
+                        backend_name,
+                        namespace,
+                        interface_name,
+                        crate::messages::TcInterfaceMessage::ClearAllFeatures,
+                    ),
+                ),
+                InterfaceMenuAction::Enable => (
+                    "Enable".to_string(),
+                    TcGuiMessage::EnableInterface {
+                        backend_name,
+                        namespace,
+                        interface: interface_name,
+                    },
+                ),
+                InterfaceMenuAction::Disable => (
+                    "Disable".to_string(),
+                    TcGuiMessage::DisableInterface {
+                        backend_name,
+                        namespace,
+                        interface: interface_name,
+                    },
+                ),
+                InterfaceMenuAction::RunDiagnostics => (
+                    "Run diagnostics".to_string(),
+                    TcGuiMessage::RunDiagnostics {
+                        backend_name,
+                        namespace,
+                        interface: interface_name,
+                        test: DiagnosticsTest::Standard,
+                    },
+                ),
+                InterfaceMenuAction::CopyCommand => (
+                    "Copy tc command".to_string(),
+                    TcGuiMessage::CopyToClipboard(
+                        interface
+                            .current_configuration()
+                            .to_command(interface.name()),
+                    ),
+                ),
+                InterfaceMenuAction::AddToPath => (
+                    "Add to path".to_string(),
+                    TcGuiMessage::AddPathHop(key.clone()),
+                ),
+            };
+            Some(
+                button(text(label).size(scaled(13, zoom)))
+                    .width(Length::Fill)
+                    .padding([scaled_padding(4, zoom), scaled_padding(10, zoom)])
+                    .on_press(TcGuiMessage::InterfaceMenuAction(Box::new(message)))
+                    .style(move |_, status| button::Style {
+                        background: matches!(status, button::Status::Hovered).then_some(
+                            iced::Background::Color(Color::from_rgba(0.5, 0.5, 0.5, 0.15)),
+                        ),
+                        text_color: colors.text_primary,
+                        ..button::Style::default()
+                    })
+                    .into(),
+            )
+        });
+
+    let menu_box = container(
+        column![
+            text(&key.interface)
+                .size(scaled(11, zoom))
+                .style(move |_| text::Style {
+                    color: Some(colors.text_secondary),
+                }),
+            column(entries),
+        ]
+        .spacing(scaled_spacing(2, zoom)),
+    )
+    .width(Length::Fixed(200.0 * zoom))
+    .padding(scaled_padding(6, zoom))
+    .style(move |_| container::Style {
+        background: Some(iced::Background::Color(colors.background_card)),
+        border: iced::Border {
+            radius: 6.0.into(),
+            width: 1.0,
+            color: colors.text_secondary,
+        },
+        shadow: iced::Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+            offset: iced::Vector::new(0.0, 4.0),
+            blur_radius: 8.0,
+        },
+        ..container::Style::default()
+    });
+
+    Some(iced::widget::pin(menu_box).position(menu.position).into())
+}
+
 /// Renders the tab navigation
 fn render_tabs<'a>(
     ui_state: &'a UiStateManager,
@@ -224,6 +385,7 @@ fn render_tabs<'a>(
 fn render_header<'a>(
     backend_manager: &'a BackendManager,
     ui_state: &'a UiStateManager,
+    session_endpoints: &'a [String],
     theme: &'a Theme,
     colors: ColorPalette,
     zoom: f32,
@@ -232,8 +394,14 @@ fn render_header<'a>(
     let overall_summary = get_bandwidth_summary(backend_manager);
 
     let status_line = render_status_line(backend_manager, colors.clone(), zoom);
-    let active_interfaces_display =
-        render_active_interfaces(namespace_summaries, overall_summary, colors.clone(), zoom);
+    let active_interfaces_display = render_active_interfaces(
+        namespace_summaries,
+        overall_summary,
+        ui_state,
+        theme,
+        colors.clone(),
+        zoom,
+    );
 
     // Theme toggle button
     let theme_icon = match theme.mode {
@@ -275,6 +443,64 @@ fn render_header<'a>(
                 ..button::Style::default()
             });
 
+    // Path loss panel toggle
+    let path_button_color = if ui_state.is_path_panel_open() {
+        colors.primary_blue
+    } else {
+        colors.text_primary
+    };
+    let path_button = button(
+        row![
+            Icon::ArrowRight.svg_sized_colored(scaled(14, zoom), path_button_color),
+            text("Path")
+                .size(scaled(12, zoom))
+                .style(move |_| text::Style {
+                    color: Some(path_button_color),
+                }),
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .align_y(iced::Alignment::Center),
+    )
+    .padding([scaled_padding(4, zoom), scaled_padding(8, zoom)])
+    .on_press(TcGuiMessage::TogglePathPanel)
+    .style(move |_, _| button::Style {
+        background: Some(iced::Background::Color(colors.background_card)),
+        text_color: colors.text_primary,
+        border: iced::Border {
+            radius: 6.0.into(),
+            width: 1.0,
+            color: colors.text_secondary,
+        },
+        ..button::Style::default()
+    });
+
+    // Connection panel toggle
+    let connect_button_color = if ui_state.is_connection_panel_open() {
+        colors.primary_blue
+    } else {
+        colors.text_primary
+    };
+    let connect_button =
+        button(
+            text("Connect")
+                .size(scaled(12, zoom))
+                .style(move |_| text::Style {
+                    color: Some(connect_button_color),
+                }),
+        )
+        .padding([scaled_padding(4, zoom), scaled_padding(8, zoom)])
+        .on_press(TcGuiMessage::ToggleConnectionPanel)
+        .style(move |_, _| button::Style {
+            background: Some(iced::Background::Color(colors.background_card)),
+            text_color: colors.text_primary,
+            border: iced::Border {
+                radius: 6.0.into(),
+                width: 1.0,
+                color: colors.text_secondary,
+            },
+            ..button::Style::default()
+        });
+
     // Zoom indicator (display only - use Ctrl+Scroll or Ctrl+/- to zoom)
     let zoom_indicator = row![
         Icon::Search.svg_sized_colored(scaled(12, zoom), colors.text_secondary),
@@ -382,17 +608,50 @@ fn render_header<'a>(
         .spacing(scaled_spacing(12, zoom))
         .align_y(iced::Alignment::Center);
 
+    let backend_details: Element<_> = match ui_state
+        .backend_details()
+        .and_then(|origin| Some((origin, backend_manager.backends().get(origin)?)))
+    {
+        Some((origin, backend_group)) => render_backend_details(
+            origin,
+            backend_group,
+            backend_manager.backend_label(origin),
+            ui_state.backend_label_draft(),
+            ui_state.compared_presets(),
+            colors.clone(),
+            zoom,
+        ),
+        None => column![].into(),
+    };
+
+    let path_panel: Element<_> = if ui_state.is_path_panel_open() {
+        render_path_panel(backend_manager, ui_state, colors.clone(), zoom)
+    } else {
+        column![].into()
+    };
+
+    let connection_panel: Element<_> = if ui_state.is_connection_panel_open() {
+        render_connection_panel(ui_state, session_endpoints, colors.clone(), zoom)
+    } else {
+        column![].into()
+    };
+
     let header_content = column![
         row![
             status_line,
             space::horizontal(),
             filter_row,
+            connect_button,
+            path_button,
             view_mode_button,
             theme_button,
             zoom_indicator,
         ]
         .spacing(scaled_spacing(8, zoom))
         .align_y(iced::Alignment::Center),
+        backend_details,
+        connection_panel,
+        path_panel,
         container(
             column![
                 text("Most Active Interfaces")
@@ -427,6 +686,723 @@ fn render_header<'a>(
         .into()
 }
 
+/// A preset offered in the comparison pick lists
+#[derive(Debug, Clone, PartialEq)]
+struct PresetChoice {
+    id: String,
+    name: String,
+}
+
+impl std::fmt::Display for PresetChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Renders the preset comparison of the backend details panel: two preset
+/// pickers and the parameters on which the picked presets differ
+fn render_preset_comparison<'a>(
+    preset_list: &PresetList,
+    (left_id, right_id): (Option<&str>, Option<&str>),
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    let small = move |s: String, color: Color| {
+        text(s)
+            .size(scaled(12, zoom))
+            .style(move |_| text::Style { color: Some(color) })
+    };
+    let choices: Vec<PresetChoice> = preset_list
+        .all()
+        .iter()
+        .map(|preset| PresetChoice {
+            id: preset.id.clone(),
+            name: preset.name.clone(),
+        })
+        .collect();
+    let picked = |id: Option<&str>| choices.iter().find(|c| Some(c.id.as_str()) == id).cloned();
+    let left = left_id.and_then(|id| preset_list.find_by_id(id));
+    let right = right_id.and_then(|id| preset_list.find_by_id(id));
+
+    let pickers = row![
+        small("Compare presets:".to_string(), colors.text_secondary),
+        pick_list(choices.clone(), picked(left_id), |choice| {
+            TcGuiMessage::CompareLeftPreset(choice.id)
+        })
+        .placeholder("preset…")
+        .text_size(scaled(12, zoom)),
+        small("with".to_string(), colors.text_secondary),
+        pick_list(choices.clone(), picked(right_id), |choice| {
+            TcGuiMessage::CompareRightPreset(choice.id)
+        })
+        .placeholder("preset…")
+        .text_size(scaled(12, zoom)),
+    ]
+    .spacing(scaled_spacing(6, zoom))
+    .align_y(iced::Alignment::Center);
+
+    let (Some(left), Some(right)) = (left, right) else {
+        return pickers.into();
+    };
+    let differences = compare_configs(&left.config, &right.config);
+    if differences.is_empty() {
+        return column![
+            pickers,
+            small(
+                "Both presets configure the same impairments".to_string(),
+                colors.success_green
+            )
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .into();
+    }
+
+    let cell = |s: String, color: Color| {
+        container(small(s, color)).width(Length::Fixed(scaled(140, zoom)))
+    };
+    let mut table = column![row![
+        cell(String::new(), colors.text_secondary),
+        cell(left.name.clone(), colors.text_secondary),
+        cell(right.name.clone(), colors.text_secondary),
+    ]]
+    .spacing(scaled_spacing(2, zoom));
+    for difference in &differences {
+        // Highlight the side a one-sided feature is enabled on
+        let (left_color, right_color) = match DifferenceKind::of(difference) {
+            DifferenceKind::Changed => (colors.warning_orange, colors.warning_orange),
+            DifferenceKind::OnlyLeft => (colors.primary_blue, colors.text_secondary),
+            DifferenceKind::OnlyRight => (colors.text_secondary, colors.primary_blue),
+        };
+        table = table.push(row![
+            cell(difference.parameter.to_string(), colors.text_primary),
+            cell(difference.format_value(difference.ours), left_color),
+            cell(difference.format_value(difference.theirs), right_color),
+        ]);
+    }
+
+    column![pickers, table]
+        .spacing(scaled_spacing(4, zoom))
+        .into()
+}
+
+/// Renders the connection panel: the session's connect endpoints, and an
+/// input for another one with previously used endpoints as suggestions
+fn render_connection_panel<'a>(
+    ui_state: &'a UiStateManager,
+    session_endpoints: &'a [String],
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    let secondary = colors.text_secondary;
+    let small = move |s: String, color: Color| {
+        text(s)
+            .size(scaled(12, zoom))
+            .style(move |_| text::Style { color: Some(color) })
+    };
+
+    let connected: Vec<&str> = session_endpoints
+        .iter()
+        .filter_map(|endpoint| endpoint.strip_prefix("connect/"))
+        .collect();
+    let current = if connected.is_empty() {
+        "Session endpoints: none (scouting only)".to_string()
+    } else {
+        format!("Session endpoints: {}", connected.join(", "))
+    };
+
+    let input = ui_state.endpoint_input();
+    let connect =
+        (!input.trim().is_empty()).then(|| TcGuiMessage::ConnectEndpoint(input.to_string()));
+    let controls = row![
+        small("Connect to".to_string(), colors.text_primary),
+        text_input("tcp/192.168.1.10:7447", input)
+            .on_input(TcGuiMessage::EndpointInputChanged)
+            .on_submit_maybe(connect.clone())
+            .size(scaled(12, zoom))
+            .padding(scaled_padding(2, zoom))
+            .width(Length::Fixed(scaled(220, zoom))),
+        button(small("Connect".to_string(), colors.text_primary))
+            .padding([scaled_padding(2, zoom), scaled_padding(8, zoom)])
+            .on_press_maybe(connect),
+    ]
+    .spacing(scaled_spacing(8, zoom))
+    .align_y(iced::Alignment::Center);
+
+    // Remembered endpoints not already in the session, filtered by the input
+    let suggestions: Vec<Element<_>> = ui_state
+        .endpoint_history()
+        .suggestions(input)
+        .filter(|endpoint| !connected.contains(&endpoint.as_str()))
+        .map(|endpoint| {
+            row![
+                button(small(endpoint.clone(), colors.primary_blue))
+                    .padding([scaled_padding(2, zoom), scaled_padding(6, zoom)])
+                    .on_press(TcGuiMessage::ConnectEndpoint(endpoint.clone()))
+                    .style(button::text),
+                button(Icon::X.svg_sized_colored(scaled(10, zoom), secondary))
+                    .padding(scaled_padding(2, zoom))
+                    .on_press(TcGuiMessage::ForgetEndpoint(endpoint.clone()))
+                    .style(button::text),
+            ]
+            .align_y(iced::Alignment::Center)
+            .into()
+        })
+        .collect();
+
+    let adopt = checkbox(ui_state.adopt_detected_tc())
+        .label("Take over TC found on backends")
+        .on_toggle(TcGuiMessage::AdoptDetectedTcToggled)
+        .size(scaled(14, zoom))
+        .text_size(scaled(12, zoom));
+    let annotate = checkbox(ui_state.annotate_scenario_steps())
+        .label("Mark scenario steps on charts")
+        .on_toggle(TcGuiMessage::AnnotateScenarioStepsToggled)
+        .size(scaled(14, zoom))
+        .text_size(scaled(12, zoom));
+
+    let drop_alert = row![
+        small(
+            "Flag interfaces dropping more than".to_string(),
+            colors.text_primary
+        ),
+        text_input("1", ui_state.drop_alert_input())
+            .on_input(TcGuiMessage::DropAlertInputChanged)
+            .size(scaled(12, zoom))
+            .padding(scaled_padding(2, zoom))
+            .width(Length::Fixed(scaled(60, zoom))),
+        small("packets/s".to_string(), colors.text_primary),
+    ]
+    .spacing(scaled_spacing(8, zoom))
+    .align_y(iced::Alignment::Center);
+
+    let preset_file = ui_state.preset_file_input();
+    let has_file = !preset_file.trim().is_empty();
+    let has_custom = !ui_state.custom_presets().list().is_empty();
+    let preset_exchange = row![
+        small("Custom presets file".to_string(), colors.text_primary),
+        text_input("~/tcgui-presets.json", preset_file)
+            .on_input(TcGuiMessage::PresetFileInputChanged)
+            .size(scaled(12, zoom))
+            .padding(scaled_padding(2, zoom))
+            .width(Length::Fixed(scaled(220, zoom))),
+        button(small("Import".to_string(), colors.text_primary))
+            .padding([scaled_padding(2, zoom), scaled_padding(8, zoom)])
+            .on_press_maybe(has_file.then_some(TcGuiMessage::ImportPresets)),
+        button(small("Export".to_string(), colors.text_primary))
+            .padding([scaled_padding(2, zoom), scaled_padding(8, zoom)])
+            .on_press_maybe((has_file && has_custom).then_some(TcGuiMessage::ExportPresets)),
+    ]
+    .spacing(scaled_spacing(8, zoom))
+    .align_y(iced::Alignment::Center);
+
+    let mut panel = column![
+        controls,
+        small(current, secondary),
+        adopt,
+        annotate,
+        drop_alert,
+        preset_exchange
+    ]
+    .spacing(scaled_spacing(6, zoom));
+    if !suggestions.is_empty() {
+        panel = panel.push(small("Recent endpoints".to_string(), secondary));
+        panel = panel.push(row(suggestions).spacing(scaled_spacing(6, zoom)).wrap());
+    }
+    panel.into()
+}
+
+/// An interface offered in the path panel's "add hop" list
+#[derive(Debug, Clone, PartialEq)]
+struct PathChoice {
+    key: InterfaceKey,
+    label: String,
+}
+
+impl std::fmt::Display for PathChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+/// Format a loss fraction as a percentage for the path panel
+fn format_loss(fraction: f64) -> String {
+    format!("{:.2}%", fraction * 100.0)
+}
+
+/// Renders the path panel: the hops of the user-defined path with their
+/// observed and configured loss, and the composite end to end
+fn render_path_panel<'a>(
+    backend_manager: &'a BackendManager,
+    ui_state: &'a UiStateManager,
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    let secondary = colors.text_secondary;
+    let small = move |s: String, color: Color| {
+        text(s)
+            .size(scaled(12, zoom))
+            .style(move |_| text::Style { color: Some(color) })
+    };
+
+    let path = ui_state.path();
+    let summary: PathSummary = summarize_path(path, backend_manager);
+
+    let choices: Vec<PathChoice> = path_candidates(backend_manager)
+        .into_iter()
+        .filter(|key| !path.contains(key))
+        .map(|key| PathChoice {
+            label: format!(
+                "{}: {}/{}",
+                backend_manager.display_name(&key.backend_name),
+                key.namespace,
+                key.interface
+            ),
+            key,
+        })
+        .collect();
+    let add_hop = pick_list(choices, None::<PathChoice>, |choice| {
+        TcGuiMessage::AddPathHop(choice.key)
+    })
+    .placeholder("add hop…")
+    .text_size(scaled(12, zoom));
+
+    let clear = button(small("Clear".to_string(), colors.text_primary))
+        .padding([scaled_padding(2, zoom), scaled_padding(8, zoom)])
+        .on_press_maybe((!path.is_empty()).then_some(TcGuiMessage::ClearPath));
+
+    let controls = row![
+        small("Path loss".to_string(), colors.text_primary),
+        add_hop,
+        clear,
+    ]
+    .spacing(scaled_spacing(8, zoom))
+    .align_y(iced::Alignment::Center);
+
+    if summary.hops.is_empty() {
+        return column![
+            controls,
+            small(
+                "Add the interfaces a flow traverses, in order, to estimate its end-to-end loss."
+                    .to_string(),
+                secondary
+            )
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .into();
+    }
+
+    let mut hops = Vec::new();
+    for (index, hop) in summary.hops.iter().enumerate() {
+        if index > 0 {
+            hops.push(
+                Icon::ArrowRight
+                    .svg_sized_colored(scaled(12, zoom), secondary)
+                    .into(),
+            );
+        }
+        let name = format!("{}/{}", hop.key.namespace, hop.key.interface);
+        let figures = match &hop.stats {
+            Some(stats) => small(
+                format!(
+                    "observed {} · configured {}",
+                    stats
+                        .counters
+                        .loss_ratio()
+                        .map_or_else(|| "no traffic".to_string(), format_loss),
+                    format_loss(stats.configured_loss)
+                ),
+                secondary,
+            ),
+            None => small("missing".to_string(), colors.warning_orange),
+        };
+        let remove = button(Icon::X.svg_sized_colored(scaled(10, zoom), secondary))
+            .padding(scaled_padding(2, zoom))
+            .on_press(TcGuiMessage::RemovePathHop(index))
+            .style(button::text);
+        hops.push(
+            container(
+                row![column![small(name, colors.text_primary), figures], remove]
+                    .spacing(scaled_spacing(4, zoom))
+                    .align_y(iced::Alignment::Center),
+            )
+            .padding([scaled_padding(4, zoom), scaled_padding(8, zoom)])
+            .style(move |_| container::Style {
+                border: iced::Border {
+                    radius: 6.0.into(),
+                    width: 1.0,
+                    color: secondary,
+                },
+                ..container::Style::default()
+            })
+            .into(),
+        );
+    }
+
+    let mut totals = format!(
+        "End to end: observed {} · configured {}",
+        summary
+            .observed_loss
+            .map_or_else(|| "no traffic".to_string(), format_loss),
+        format_loss(summary.configured_loss)
+    );
+    match summary.missing_hops() {
+        0 => {}
+        1 => totals.push_str(" (1 missing hop not counted)"),
+        n => totals.push_str(&format!(" ({n} missing hops not counted)")),
+    }
+
+    column![
+        controls,
+        row(hops)
+            .spacing(scaled_spacing(6, zoom))
+            .align_y(iced::Alignment::Center)
+            .wrap(),
+        small(totals, colors.text_primary),
+        render_coordinated_apply(backend_manager, ui_state, colors, zoom),
+    ]
+    .spacing(scaled_spacing(6, zoom))
+    .into()
+}
+
+/// Renders the controls applying one change to every hop of the path at the
+/// same instant, and how each hop fared the last time
+fn render_coordinated_apply<'a>(
+    backend_manager: &'a BackendManager,
+    ui_state: &'a UiStateManager,
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    use crate::coordinated_apply::{CoordinatedAction, DEFAULT_LEAD_MS, HopStatus};
+
+    let secondary = colors.text_secondary;
+    let small = move |s: String, color: Color| {
+        text(s)
+            .size(scaled(12, zoom))
+            .style(move |_| text::Style { color: Some(color) })
+    };
+
+    // Presets known to any backend on the path, first one wins
+    let mut choices = vec![CoordinatedAction::Clear];
+    for key in ui_state.path() {
+        if let Some(preset_list) = backend_manager.get_preset_list(&key.backend_name) {
+            for preset in preset_list.all() {
+                if !choices
+                    .iter()
+                    .any(|c| matches!(c, CoordinatedAction::Apply(p) if p.id == preset.id))
+                {
+                    choices.push(CoordinatedAction::Apply(Box::new(preset.clone())));
+                }
+            }
+        }
+    }
+
+    let at_input = ui_state.coordinated_at_input();
+    let parsed = crate::scenario_manager::parse_start_time(at_input, chrono::Local::now());
+    let ready = ui_state.coordinated_action().is_some() && parsed.is_ok();
+    let (hint, hint_color) = match parsed {
+        Ok(None) => (format!("in {} s", DEFAULT_LEAD_MS / 1000), secondary),
+        Ok(Some(at)) => (
+            format!("at {}", crate::scenario_manager::format_start_time(at)),
+            secondary,
+        ),
+        Err(e) => (e, colors.error_red),
+    };
+    let controls = row![
+        small("All hops at once".to_string(), colors.text_primary),
+        pick_list(
+            choices,
+            ui_state.coordinated_action().cloned(),
+            TcGuiMessage::CoordinatedActionSelected
+        )
+        .placeholder("change…")
+        .text_size(scaled(12, zoom)),
+        text_input("HH:MM:SS or +10s", at_input)
+            .on_input(TcGuiMessage::CoordinatedAtChanged)
+            .size(scaled(12, zoom))
+            .padding(scaled_padding(4, zoom))
+            .width(Length::Fixed(130.0 * zoom)),
+        small(hint, hint_color),
+        button(small("Apply".to_string(), colors.text_primary))
+            .padding([scaled_padding(2, zoom), scaled_padding(8, zoom)])
+            .on_press_maybe(ready.then_some(TcGuiMessage::StartCoordinatedApply)),
+    ]
+    .spacing(scaled_spacing(8, zoom))
+    .align_y(iced::Alignment::Center);
+
+    let Some(apply) = ui_state.coordinated_apply() else {
+        return controls.into();
+    };
+
+    let now_ms = crate::clock_skew::unix_now_ms().max(0) as u64;
+    let overdue = apply.overdue(now_ms);
+    let mut status = column![
+        row![
+            small(
+                format!(
+                    "{} at {}: {}",
+                    apply.label,
+                    crate::scenario_manager::format_start_time(apply.apply_at_ms),
+                    apply.summary()
+                ),
+                colors.text_primary
+            ),
+            button(Icon::X.svg_sized_colored(scaled(10, zoom), secondary))
+                .padding(scaled_padding(2, zoom))
+                .on_press(TcGuiMessage::DismissCoordinatedApply)
+                .style(button::text),
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .align_y(iced::Alignment::Center)
+    ]
+    .spacing(scaled_spacing(2, zoom));
+    for warning in &apply.warnings {
+        status = status.push(small(warning.clone(), colors.warning_orange));
+    }
+    for hop in &apply.hops {
+        let (state, color) = match &hop.status {
+            _ if overdue.iter().any(|o| o.key == hop.key) => {
+                ("no confirmation".to_string(), colors.warning_orange)
+            }
+            HopStatus::Sent => ("sent".to_string(), secondary),
+            HopStatus::Scheduled => ("scheduled".to_string(), colors.primary_blue),
+            HopStatus::Applied { offset_ms } => (
+                format!("applied ({:+.1} s)", *offset_ms as f64 / 1000.0),
+                colors.success_green,
+            ),
+            HopStatus::AppliedLate => (
+                "applied late, the request arrived after the time".to_string(),
+                colors.warning_orange,
+            ),
+            HopStatus::Failed(error) => (format!("failed: {}", error), colors.error_red),
+        };
+        status = status.push(small(
+            format!(
+                "{}: {}/{} {}",
+                backend_manager.display_name(&hop.key.backend_name),
+                hop.key.namespace,
+                hop.key.interface,
+                state
+            ),
+            color,
+        ));
+    }
+
+    column![controls, status]
+        .spacing(scaled_spacing(4, zoom))
+        .into()
+}
+
+/// Renders the details panel for one backend, opened from the status line
+fn render_backend_details<'a>(
+    origin: &str,
+    backend_group: &BackendGroup,
+    user_label: Option<&str>,
+    label_draft: &str,
+    compared_presets: (Option<&str>, Option<&str>),
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    let label = move |s: String| {
+        text(s).size(scaled(12, zoom)).style(move |_| text::Style {
+            color: Some(colors.text_secondary),
+        })
+    };
+    let value = move |s: String| {
+        text(s).size(scaled(12, zoom)).style(move |_| text::Style {
+            color: Some(colors.text_primary),
+        })
+    };
+
+    // The user's label is display-only; the name and origin stay visible so
+    // it is always clear which host is being addressed.
+    let backend_text = match user_label {
+        Some(user_label) => format!("{} — {} ({})", user_label, backend_group.name, origin),
+        None => format!("{} ({})", backend_group.name, origin),
+    };
+    let save_label = (label_draft.trim() != user_label.unwrap_or_default())
+        .then(|| TcGuiMessage::SaveBackendLabel(origin.to_string()));
+
+    let mut lines = column![
+        row![label("Backend:".to_string()), value(backend_text)].spacing(scaled_spacing(6, zoom)),
+        row![
+            label("Label:".to_string()),
+            text_input(&backend_group.name, label_draft)
+                .on_input(TcGuiMessage::BackendLabelDraftChanged)
+                .on_submit_maybe(save_label.clone())
+                .size(scaled(12, zoom))
+                .padding(scaled_padding(2, zoom))
+                .width(Length::Fixed(scaled(200, zoom))),
+            button(text("Save").size(scaled(12, zoom)))
+                .padding(scaled_padding(2, zoom))
+                .on_press_maybe(save_label)
+                .style(button::secondary),
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(scaled_spacing(4, zoom));
+
+    // Escape hatch when the view looks out of sync: the backend republishes
+    // everything, even what its change detection considers unchanged.
+    let refresh_label = if backend_group.refresh_pending {
+        "Refreshing..."
+    } else {
+        "Force refresh"
+    };
+    lines = lines.push(
+        row![
+            button(text(refresh_label).size(scaled(12, zoom)))
+                .padding(scaled_padding(2, zoom))
+                .on_press_maybe(
+                    (!backend_group.refresh_pending && backend_group.is_connected)
+                        .then(|| TcGuiMessage::ForceRefresh(origin.to_string()))
+                )
+                .style(button::secondary),
+            label("Republish all interfaces and TC configurations".to_string()),
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center),
+    );
+    lines = lines.push(
+        row![
+            button(text("Bring up/down...").size(scaled(12, zoom)))
+                .padding(scaled_padding(2, zoom))
+                .on_press_maybe(
+                    backend_group
+                        .is_connected
+                        .then(|| TcGuiMessage::ShowInterfaceControlDialog(origin.to_string()))
+                )
+                .style(button::secondary),
+            label("Enable or disable several interfaces of a namespace at once".to_string()),
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center),
+    );
+    lines = lines.push(
+        row![
+            button(text("Remove impairment...").size(scaled(12, zoom)))
+                .padding(scaled_padding(2, zoom))
+                .on_press_maybe(
+                    backend_group
+                        .is_connected
+                        .then(|| TcGuiMessage::ShowFeatureClearDialog(origin.to_string()))
+                )
+                .style(button::secondary),
+            label("Remove one impairment from several interfaces, keeping the rest".to_string()),
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center),
+    );
+    if backend_group.preset_list.len() > 1 {
+        lines = lines.push(render_preset_comparison(
+            &backend_group.preset_list,
+            compared_presets,
+            colors.clone(),
+            zoom,
+        ));
+    }
+
+    let skew = &backend_group.clock_skew;
+    let skew_text = if skew.is_excessive() {
+        format!("{} — chart annotations are compensated", skew.describe())
+    } else {
+        skew.describe()
+    };
+    let skew_color = if skew.is_excessive() {
+        colors.warning_orange
+    } else {
+        colors.text_primary
+    };
+    lines = lines.push(
+        row![
+            label("Clock skew:".to_string()),
+            text(skew_text)
+                .size(scaled(12, zoom))
+                .style(move |_| text::Style {
+                    color: Some(skew_color),
+                })
+        ]
+        .spacing(scaled_spacing(6, zoom)),
+    );
+
+    match &backend_group.health {
+        Some(health) => {
+            let version = health.metadata.version.as_deref().unwrap_or("unknown");
+            let hostname = health.metadata.hostname.as_deref().unwrap_or("unknown");
+            lines = lines
+                .push(
+                    row![label("Status:".to_string()), value(health.status.clone())]
+                        .spacing(scaled_spacing(6, zoom)),
+                )
+                .push(
+                    row![
+                        label("Host:".to_string()),
+                        value(format!("{} • version {}", hostname, version))
+                    ]
+                    .spacing(scaled_spacing(6, zoom)),
+                )
+                .push(
+                    row![
+                        label("Interfaces:".to_string()),
+                        value(health.interface_count.to_string())
+                    ]
+                    .spacing(scaled_spacing(6, zoom)),
+                );
+
+            // Peer ids are only present when the backend opts in; otherwise
+            // just the count is known.
+            let peers = if health.peer_ids.is_empty() {
+                health.connected_peers.to_string()
+            } else {
+                format!(
+                    "{} ({})",
+                    health.connected_peers,
+                    health.peer_ids.join(", ")
+                )
+            };
+            lines = lines.push(
+                row![label("Connected peers:".to_string()), value(peers)]
+                    .spacing(scaled_spacing(6, zoom)),
+            );
+
+            if !health.failed_namespaces.is_empty() {
+                let warning = colors.warning_orange;
+                lines = lines.push(
+                    row![
+                        Icon::AlertTriangle.svg_sized_colored(scaled(12, zoom), warning),
+                        text(format!(
+                            " Discovery failed in: {}",
+                            health.failed_namespaces.join(", ")
+                        ))
+                        .size(scaled(12, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(warning),
+                        })
+                    ]
+                    .align_y(iced::Alignment::Center),
+                );
+            }
+        }
+        None => {
+            lines = lines.push(label("No health report received yet".to_string()));
+        }
+    }
+
+    container(lines)
+        .padding(scaled_padding(8, zoom))
+        .width(Length::Fill)
+        .style(move |_| container::Style {
+            background: Some(iced::Background::Color(colors.background_primary)),
+            border: iced::Border {
+                radius: 6.0.into(),
+                width: 1.0,
+                color: colors.text_secondary,
+            },
+            ..container::Style::default()
+        })
+        .into()
+}
+
 /// Renders the backend connection status line
 fn render_status_line<'a>(
     backend_manager: &'a BackendManager,
@@ -462,10 +1438,19 @@ fn render_status_line<'a>(
                 backend_statuses.push(icon.svg_sized_colored(scaled(14, zoom), color).into());
                 backend_statuses.push(
                     // Display the operator-chosen name; the map key is the origin.
-                    text(backend_group.name.clone())
-                        .size(scaled(14, zoom))
-                        .style(move |_| text::Style { color: Some(color) })
-                        .into(),
+                    // Clicking the name opens the backend details panel.
+                    button(
+                        text(backend_manager.display_name(backend_name))
+                            .size(scaled(14, zoom))
+                            .style(move |_| text::Style { color: Some(color) }),
+                    )
+                    .padding(0)
+                    .on_press(TcGuiMessage::ToggleBackendDetails(backend_name.clone()))
+                    .style(|_, _| button::Style {
+                        background: None,
+                        ..button::Style::default()
+                    })
+                    .into(),
                 );
 
                 // Add separator if not the last backend
@@ -529,6 +1514,8 @@ fn render_active_interfaces(
         (String, tcgui_shared::NetworkBandwidthStats, f64, String),
     >,
     _overall_summary: Option<(String, String, f64, String)>,
+    ui_state: &UiStateManager,
+    theme: &Theme,
     colors: ColorPalette,
     zoom: f32,
 ) -> Element<'static, TcGuiMessage> {
@@ -557,7 +1544,20 @@ fn render_active_interfaces(
             .into_iter()
             .take(5)
             .map(|(ns_key, iface, rate_display, _)| {
-                render_active_interface_item(ns_key, iface, rate_display, colors.clone(), zoom)
+                let (backend_name, namespace) = ns_key.split_once('/').unwrap_or((&ns_key, ""));
+                let style = ui_state.interface_style(backend_name, namespace, &iface);
+                let accent = style
+                    .and_then(|s| s.color)
+                    .map(|color| theme.accent_color(color));
+                let label = style.map(|s| s.display_name(&iface).to_string());
+                render_active_interface_item(
+                    ns_key.clone(),
+                    label.unwrap_or(iface),
+                    rate_display,
+                    accent,
+                    colors.clone(),
+                    zoom,
+                )
             })
             .collect();
 
@@ -598,9 +1598,12 @@ fn render_active_interface_item(
     ns_key: String,
     iface: String,
     rate_display: String,
+    accent: Option<Color>,
     colors: ColorPalette,
     zoom: f32,
 ) -> Element<'static, TcGuiMessage> {
+    // An assigned interface color replaces the default highlight
+    let highlight = accent.unwrap_or(colors.primary_blue);
     // Parse backend/namespace from key
     let parts: Vec<&str> = ns_key.splitn(2, '/').collect();
     let display_ns = if parts.len() == 2 {
@@ -615,7 +1618,7 @@ fn render_active_interface_item(
 
     container(
         row![
-            Icon::Activity.svg_sized_colored(scaled(11, zoom), colors.primary_blue),
+            Icon::Activity.svg_sized_colored(scaled(11, zoom), highlight),
             text(format!(" {}: {}", display_ns, iface))
                 .size(scaled(11, zoom))
                 .style(move |_| text::Style {
@@ -625,7 +1628,7 @@ fn render_active_interface_item(
             text(rate_display)
                 .size(scaled(11, zoom))
                 .style(move |_| text::Style {
-                    color: Some(colors.primary_blue)
+                    color: Some(highlight)
                 })
         ]
         .spacing(scaled_spacing(4, zoom))
@@ -633,9 +1636,10 @@ fn render_active_interface_item(
     )
     .padding([scaled_padding(4, zoom), scaled_padding(8, zoom)])
     .style(move |_| container::Style {
-        background: Some(iced::Background::Color(Color::from_rgba(
-            0.2, 0.6, 1.0, 0.08,
-        ))),
+        background: Some(iced::Background::Color(match accent {
+            Some(accent) => Color { a: 0.12, ..accent },
+            None => Color::from_rgba(0.2, 0.6, 1.0, 0.08),
+        })),
         border: iced::Border {
             radius: 3.0.into(),
             width: 0.0,
@@ -779,27 +1783,27 @@ fn render_namespace_sections<'a>(
 ) -> Vec<Element<'a, TcGuiMessage>> {
     let mut namespace_sections: Vec<Element<TcGuiMessage>> = Vec::new();
 
-    // Sort backends for consistent display order
-    let mut sorted_backends: Vec<_> = backend_manager.backends().iter().collect();
-    sorted_backends.sort_by_key(|(name, _)| {
-        // Put "default" backend first, then alphabetical
-        if *name == "default" {
-            (0, (*name).clone())
-        } else {
-            (1, (*name).clone())
-        }
-    });
-
     let namespace_bandwidth_summaries = get_namespace_bandwidth_summaries(backend_manager);
 
-    for (backend_name, backend_group) in sorted_backends {
-        // Skip hidden backends
-        if ui_state.is_backend_hidden(backend_name) {
+    for (backend_name, backend_group) in shown_backends(backend_manager) {
+        let backend_label = backend_manager.display_name(backend_name);
+        let is_collapsed = ui_state.is_backend_hidden(backend_name);
+        namespace_sections.push(render_backend_header(
+            backend_name,
+            &backend_label,
+            backend_group,
+            ui_state,
+            is_collapsed,
+            colors.clone(),
+            zoom,
+        ));
+        if is_collapsed {
             continue;
         }
 
         let backend_namespace_sections = render_backend_namespaces(
             backend_name,
+            &backend_label,
             backend_group,
             bandwidth_history,
             ui_state,
@@ -811,13 +1815,159 @@ fn render_namespace_sections<'a>(
         namespace_sections.extend(backend_namespace_sections);
     }
 
-    namespace_sections
+    namespace_sections
+}
+
+/// All backends in display order ("default" first, then alphabetical).
+/// Collapsed backends are included: they still show their header.
+fn shown_backends(backend_manager: &BackendManager) -> Vec<(&String, &BackendGroup)> {
+    let mut backends: Vec<_> = backend_manager.backends().iter().collect();
+    backends.sort_by_key(|(name, _)| (*name != "default", (*name).clone()));
+    backends
+}
+
+/// Namespaces of a backend that have interfaces and pass the namespace type
+/// filter, in display order ("default" first, then alphabetical).
+fn shown_namespaces<'a>(
+    backend_group: &'a BackendGroup,
+    ui_state: &UiStateManager,
+) -> Vec<(&'a String, &'a NamespaceGroup)> {
+    let filter = ui_state.namespace_filter();
+    let mut namespaces: Vec<_> = backend_group
+        .namespaces
+        .iter()
+        .filter(|(_, group)| !group.tc_interfaces.is_empty())
+        .filter(|(_, group)| match &group.namespace.namespace_type {
+            NamespaceType::Default => filter.show_host,
+            NamespaceType::Traditional => filter.show_namespaces,
+            NamespaceType::Container { .. } => filter.show_containers,
+        })
+        .collect();
+    namespaces.sort_by_key(|(name, _)| (*name != "default", (*name).clone()));
+    namespaces
+}
+
+/// Interfaces of a namespace matching the search filter, alphabetically.
+fn shown_interfaces<'a>(
+    namespace_group: &'a NamespaceGroup,
+    search: &str,
+) -> Vec<(&'a String, &'a TcInterface)> {
+    let mut interfaces: Vec<_> = namespace_group
+        .tc_interfaces
+        .iter()
+        .filter(|(name, _)| interface_matches_search(name, search))
+        .collect();
+    interfaces.sort_by_key(|(name, _)| (*name).clone());
+    interfaces
+}
+
+/// Interface cards in the order they are laid out, skipping collapsed
+/// backends and namespaces. Keyboard navigation walks this list.
+pub fn interface_navigation_order(
+    backend_manager: &BackendManager,
+    ui_state: &UiStateManager,
+) -> Vec<InterfaceKey> {
+    let mut order = Vec::new();
+    for (backend_name, backend_group) in shown_backends(backend_manager) {
+        if ui_state.is_backend_hidden(backend_name) {
+            continue;
+        }
+        for (namespace_name, namespace_group) in shown_namespaces(backend_group, ui_state) {
+            if ui_state.is_namespace_hidden(backend_name, namespace_name) {
+                continue;
+            }
+            for (interface_name, _) in
+                shown_interfaces(namespace_group, ui_state.interface_search())
+            {
+                order.push(InterfaceKey {
+                    backend_name: backend_name.clone(),
+                    namespace: namespace_name.clone(),
+                    interface: interface_name.clone(),
+                });
+            }
+        }
+    }
+    order
+}
+
+/// Renders the header at the top of a backend's namespaces: a collapse
+/// toggle, the backend label and, while collapsed, a summary of what is
+/// hidden.
+fn render_backend_header<'a>(
+    backend_name: &'a str,
+    backend_label: &str,
+    backend_group: &'a BackendGroup,
+    ui_state: &UiStateManager,
+    is_collapsed: bool,
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    let chevron = if is_collapsed {
+        Icon::ChevronRight
+    } else {
+        Icon::ChevronDown
+    };
+    let toggle = button(
+        row![
+            chevron.svg_sized_colored(scaled(14, zoom), colors.text_primary),
+            Icon::Monitor.svg_sized_colored(scaled(16, zoom), colors.text_primary),
+            text(backend_label.to_string())
+                .size(scaled(16, zoom))
+                .style(move |_| text::Style {
+                    color: Some(colors.text_primary),
+                }),
+        ]
+        .spacing(scaled_spacing(6, zoom))
+        .align_y(iced::Alignment::Center),
+    )
+    .padding(scaled_padding(4, zoom))
+    .on_press(TcGuiMessage::ToggleBackendVisibility(
+        backend_name.to_string(),
+    ))
+    .style(|_, _| button::Style {
+        background: None,
+        ..button::Style::default()
+    });
+
+    let summary: Element<'a, TcGuiMessage> = if is_collapsed {
+        // Count what the user would see when expanded
+        let namespaces = shown_namespaces(backend_group, ui_state);
+        let interface_count: usize = namespaces
+            .iter()
+            .map(|(_, group)| group.tc_interfaces.len())
+            .sum();
+        let total_rate: f64 = namespaces
+            .iter()
+            .flat_map(|(_, group)| group.tc_interfaces.values())
+            .filter_map(|interface| interface.bandwidth_stats())
+            .map(|stats| stats.rx_bytes_per_sec + stats.tx_bytes_per_sec)
+            .sum();
+        text(format!(
+            "{} namespaces, {} interfaces, {} total",
+            namespaces.len(),
+            interface_count,
+            format_bandwidth_rate(total_rate)
+        ))
+        .size(scaled(13, zoom))
+        .style(move |_| text::Style {
+            color: Some(colors.text_secondary),
+        })
+        .into()
+    } else {
+        space::horizontal().width(Length::Shrink).into()
+    };
+
+    row![toggle, space::horizontal(), summary]
+        .spacing(scaled_spacing(12, zoom))
+        .align_y(iced::Alignment::Center)
+        .into()
 }
 
 /// Renders namespaces for a specific backend
 #[allow(clippy::too_many_arguments)]
 fn render_backend_namespaces<'a>(
     backend_name: &'a str,
+    backend_label: &str,
     backend_group: &'a BackendGroup,
     bandwidth_history: &'a BandwidthHistoryManager,
     ui_state: &'a UiStateManager,
@@ -831,53 +1981,34 @@ fn render_backend_namespaces<'a>(
 ) -> Vec<Element<'a, TcGuiMessage>> {
     let mut sections = Vec::new();
 
-    // Sort namespaces within each backend
-    let mut sorted_namespaces: Vec<_> = backend_group.namespaces.iter().collect();
-    sorted_namespaces.sort_by_key(|(name, _)| {
-        // Put "default" namespace first, then alphabetical
-        if *name == "default" {
-            (0, (*name).clone())
-        } else {
-            (1, (*name).clone())
-        }
-    });
-
-    for (namespace_name, namespace_group) in sorted_namespaces {
-        if !namespace_group.tc_interfaces.is_empty() {
-            // Apply namespace type filter
-            let ns_type = &namespace_group.namespace.namespace_type;
-            let filter = ui_state.namespace_filter();
-            let should_show = match ns_type {
-                NamespaceType::Default => filter.show_host,
-                NamespaceType::Traditional => filter.show_namespaces,
-                NamespaceType::Container { .. } => filter.show_containers,
-            };
-
-            if !should_show {
-                continue;
-            }
+    for (namespace_name, namespace_group) in shown_namespaces(backend_group, ui_state) {
+        let ns_type = &namespace_group.namespace.namespace_type;
+        let namespace_key = format!("{}/{}", backend_name, namespace_name);
+        let is_hidden = ui_state.is_namespace_hidden(backend_name, namespace_name);
+        let focused_interface = ui_state
+            .focused_interface()
+            .filter(|key| key.backend_name == backend_name && key.namespace == *namespace_name)
+            .map(|key| key.interface.as_str());
 
-            let namespace_key = format!("{}/{}", backend_name, namespace_name);
-            let is_hidden = ui_state.is_namespace_hidden(backend_name, namespace_name);
-
-            let section = render_namespace_section(
-                backend_name,
-                &backend_group.name,
-                namespace_name,
-                namespace_group,
-                &backend_group.preset_list,
-                bandwidth_history,
-                namespace_key,
-                is_hidden,
-                ns_type,
-                namespace_bandwidth_summaries.clone(),
-                colors.clone(),
-                zoom,
-                theme,
-                ui_state.interface_search(),
-            );
-            sections.push(section);
-        }
+        let section = render_namespace_section(
+            backend_name,
+            backend_label,
+            namespace_name,
+            namespace_group,
+            &backend_group.preset_list,
+            bandwidth_history,
+            &backend_group.diagnostics_history,
+            namespace_key,
+            is_hidden,
+            ns_type,
+            namespace_bandwidth_summaries.clone(),
+            colors.clone(),
+            zoom,
+            theme,
+            ui_state,
+            focused_interface,
+        );
+        sections.push(section);
     }
 
     sections
@@ -887,11 +2018,12 @@ fn render_backend_namespaces<'a>(
 #[allow(clippy::too_many_arguments)]
 fn render_namespace_section<'a>(
     backend_name: &'a str,
-    backend_label: &'a str,
+    backend_label: &str,
     namespace_name: &'a str,
     namespace_group: &'a NamespaceGroup,
     preset_list: &'a tcgui_shared::presets::PresetList,
     bandwidth_history: &'a BandwidthHistoryManager,
+    diagnostics_history: &'a DiagnosticsHistoryManager,
     namespace_key: String,
     is_hidden: bool,
     namespace_type: &'a NamespaceType,
@@ -902,7 +2034,8 @@ fn render_namespace_section<'a>(
     colors: ColorPalette,
     zoom: f32,
     theme: &'a Theme,
-    search: &'a str,
+    ui_state: &'a UiStateManager,
+    focused_interface: Option<&str>,
 ) -> Element<'a, TcGuiMessage> {
     let namespace_header = render_namespace_header(
         backend_name,
@@ -911,6 +2044,7 @@ fn render_namespace_section<'a>(
         namespace_key.clone(),
         is_hidden,
         namespace_type,
+        &namespace_group.diagnostics,
         namespace_bandwidth_summaries.clone(),
         colors.clone(),
         zoom,
@@ -943,7 +2077,9 @@ fn render_namespace_section<'a>(
             theme,
             zoom,
             bandwidth_history,
-            search,
+            diagnostics_history,
+            ui_state,
+            focused_interface,
         );
         // Use wrapping column to flow interface cards horizontally on wide screens
         let interfaces_column: Element<_> = column(interfaces)
@@ -951,8 +2087,20 @@ fn render_namespace_section<'a>(
             .wrap()
             .into();
 
+        let mut content = column![namespace_header].spacing(scaled_spacing(16, zoom));
+        if let Some(summary) = render_namespace_diagnostics_summary(
+            backend_name,
+            namespace_name,
+            &namespace_group.diagnostics,
+            colors.clone(),
+            zoom,
+        ) {
+            content = content.push(summary);
+        }
+        content = content.push(interfaces_column);
+
         // Modern namespace container with card styling
-        container(column![namespace_header, interfaces_column].spacing(scaled_spacing(16, zoom)))
+        container(content)
             .padding(scaled_padding(20, zoom))
             .width(Length::Fill)
             .style(move |_| container::Style {
@@ -977,11 +2125,12 @@ fn render_namespace_section<'a>(
 #[allow(clippy::too_many_arguments)]
 fn render_namespace_header<'a>(
     backend_name: &'a str,
-    backend_label: &'a str,
+    backend_label: &str,
     namespace_name: &'a str,
     namespace_key: String,
     is_hidden: bool,
     namespace_type: &'a NamespaceType,
+    diagnostics: &NamespaceDiagnostics,
     namespace_bandwidth_summaries: HashMap<
         String,
         (String, tcgui_shared::NetworkBandwidthStats, f64, String),
@@ -1059,10 +2208,19 @@ fn render_namespace_header<'a>(
         zoom,
     );
 
+    let diagnose_button = render_namespace_diagnose_button(
+        backend_name,
+        namespace_name,
+        diagnostics.is_running(),
+        colors.clone(),
+        zoom,
+    );
+
     row![
         namespace_title,
         space::horizontal(),
         namespace_bandwidth_summary,
+        diagnose_button,
         toggle_button,
     ]
     .spacing(scaled_spacing(12, zoom))
@@ -1144,6 +2302,167 @@ fn render_toggle_button<'a>(
     }
 }
 
+/// Renders the button that diagnoses every interface of a namespace
+fn render_namespace_diagnose_button<'a>(
+    backend_name: &'a str,
+    namespace_name: &'a str,
+    running: bool,
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    let label = if running {
+        " Diagnosing…"
+    } else {
+        " Diagnose all"
+    };
+    let primary_blue = colors.primary_blue;
+    let text_secondary = colors.text_secondary;
+
+    button(
+        row![
+            Icon::Activity.svg_sized_colored(scaled(12, zoom), Color::WHITE),
+            text(label).size(scaled(12, zoom))
+        ]
+        .spacing(scaled_spacing(4, zoom))
+        .align_y(iced::Alignment::Center),
+    )
+    .padding(scaled_padding(8, zoom))
+    .on_press_maybe((!running).then(|| TcGuiMessage::RunNamespaceDiagnostics {
+        backend_name: backend_name.to_string(),
+        namespace: namespace_name.to_string(),
+    }))
+    .style(move |_, status| button::Style {
+        background: Some(iced::Background::Color(match status {
+            button::Status::Disabled => text_secondary,
+            _ => primary_blue,
+        })),
+        text_color: Color::WHITE,
+        border: iced::Border {
+            radius: 6.0.into(),
+            width: 0.0,
+            color: Color::TRANSPARENT,
+        },
+        shadow: iced::Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
+            offset: iced::Vector::new(0.0, 1.0),
+            blur_radius: 2.0,
+        },
+        snap: false,
+    })
+    .into()
+}
+
+/// Renders the per-interface pass/fail table of a namespace diagnostics run,
+/// or nothing when no run has completed.
+fn render_namespace_diagnostics_summary<'a>(
+    backend_name: &'a str,
+    namespace_name: &'a str,
+    diagnostics: &NamespaceDiagnostics,
+    colors: ColorPalette,
+    zoom: f32,
+) -> Option<Element<'a, TcGuiMessage>> {
+    let text_primary = colors.text_primary;
+    let text_secondary = colors.text_secondary;
+
+    let (heading, heading_color, rows): (String, Color, Vec<Element<'a, TcGuiMessage>>) =
+        match diagnostics {
+            NamespaceDiagnostics::Idle | NamespaceDiagnostics::Running => return None,
+            NamespaceDiagnostics::Failed(error) => (
+                format!("Diagnostics failed: {}", error),
+                colors.error_red,
+                Vec::new(),
+            ),
+            NamespaceDiagnostics::Done(response) => {
+                let all_passed = response.passed_count() == response.interfaces.len();
+                let rows = response
+                    .interfaces
+                    .iter()
+                    .map(|entry| {
+                        let passed = entry.response.passed();
+                        let (icon, color) = if passed {
+                            (Icon::Check, colors.success_green)
+                        } else {
+                            (Icon::X, colors.error_red)
+                        };
+                        row![
+                            icon.svg_sized_colored(scaled(12, zoom), color),
+                            text(entry.interface.clone())
+                                .size(scaled(12, zoom))
+                                .width(Length::Fixed(scaled(140, zoom)))
+                                .style(move |_| text::Style {
+                                    color: Some(text_primary),
+                                }),
+                            text(entry.response.message.clone())
+                                .size(scaled(12, zoom))
+                                .style(move |_| text::Style {
+                                    color: Some(text_secondary),
+                                }),
+                        ]
+                        .spacing(scaled_spacing(8, zoom))
+                        .align_y(iced::Alignment::Center)
+                        .into()
+                    })
+                    .collect();
+                let color = if all_passed {
+                    colors.success_green
+                } else {
+                    colors.warning_orange
+                };
+                (format!("Diagnostics: {}", response.summary()), color, rows)
+            }
+        };
+
+    let dismiss = button(Icon::X.svg_sized_colored(scaled(12, zoom), text_secondary))
+        .padding(scaled_padding(4, zoom))
+        .on_press(TcGuiMessage::DismissNamespaceDiagnostics {
+            backend_name: backend_name.to_string(),
+            namespace: namespace_name.to_string(),
+        })
+        .style(|_, _| button::Style {
+            background: None,
+            ..button::Style::default()
+        });
+
+    let header = row![
+        Icon::Activity.svg_sized_colored(scaled(14, zoom), heading_color),
+        text(heading)
+            .size(scaled(13, zoom))
+            .style(move |_| text::Style {
+                color: Some(heading_color),
+            }),
+        space::horizontal(),
+        dismiss,
+    ]
+    .spacing(scaled_spacing(6, zoom))
+    .align_y(iced::Alignment::Center);
+
+    let body = column(rows)
+        .spacing(scaled_spacing(4, zoom))
+        .padding(iced::Padding::ZERO.left(scaled(20, zoom)));
+
+    Some(
+        container(column![header, body].spacing(scaled_spacing(6, zoom)))
+            .padding(scaled_padding(10, zoom))
+            .width(Length::Fill)
+            .style(move |_| container::Style {
+                background: Some(iced::Background::Color(Color {
+                    a: 0.08,
+                    ..heading_color
+                })),
+                border: iced::Border {
+                    radius: 8.0.into(),
+                    width: 1.0,
+                    color: Color {
+                        a: 0.4,
+                        ..heading_color
+                    },
+                },
+                ..container::Style::default()
+            })
+            .into(),
+    )
+}
+
 /// Renders the namespace bandwidth summary
 fn render_namespace_bandwidth_summary<'a>(
     namespace_key: String,
@@ -1229,17 +2548,13 @@ fn render_namespace_interfaces<'a>(
     theme: &'a Theme,
     zoom: f32,
     bandwidth_history: &'a BandwidthHistoryManager,
-    search: &'a str,
+    diagnostics_history: &'a DiagnosticsHistoryManager,
+    ui_state: &'a UiStateManager,
+    focused_interface: Option<&str>,
 ) -> Vec<Element<'a, TcGuiMessage>> {
-    // Sort interfaces alphabetically for consistent order, honoring the search filter.
-    let mut sorted_interfaces: Vec<_> = namespace_group
-        .tc_interfaces
-        .iter()
-        .filter(|(name, _)| interface_matches_search(name, search))
-        .collect();
-    sorted_interfaces.sort_by_key(|(name, _)| (*name).clone());
+    let focus_color = theme.colors.info;
 
-    sorted_interfaces
+    shown_interfaces(namespace_group, ui_state.interface_search())
         .into_iter()
         .map(|(name, interface)| {
             let name_clone = name.clone();
@@ -1247,8 +2562,18 @@ fn render_namespace_interfaces<'a>(
             let namespace_clone = namespace_name.to_string();
             // Get bandwidth history for this interface
             let history = bandwidth_history.get(backend_name, namespace_name, name);
-            interface
-                .view(preset_list, theme, zoom, history)
+            let diagnostics = diagnostics_history.get(namespace_name, name);
+            let style = ui_state.interface_style(backend_name, namespace_name, name);
+            let card = interface
+                .view(
+                    preset_list,
+                    ui_state.custom_presets().list(),
+                    theme,
+                    zoom,
+                    history,
+                    diagnostics,
+                    style,
+                )
                 .map(move |msg| {
                     TcGuiMessage::TcInterfaceMessage(
                         backend_clone.clone(),
@@ -1256,7 +2581,34 @@ fn render_namespace_interfaces<'a>(
                         name_clone.clone(),
                         msg,
                     )
+                });
+
+            // Right click opens the quick actions menu
+            let card = iced::widget::mouse_area(card).on_right_press(
+                TcGuiMessage::OpenInterfaceMenu(InterfaceKey {
+                    backend_name: backend_name.to_string(),
+                    namespace: namespace_name.to_string(),
+                    interface: name.clone(),
+                }),
+            );
+
+            // Keyboard focus indicator: an outline around the card
+            let is_focused = focused_interface == Some(name.as_str());
+            container(card)
+                .padding(2)
+                .style(move |_| container::Style {
+                    border: iced::Border {
+                        radius: 10.0.into(),
+                        width: if is_focused { 2.0 } else { 0.0 },
+                        color: if is_focused {
+                            focus_color
+                        } else {
+                            Color::TRANSPARENT
+                        },
+                    },
+                    ..container::Style::default()
                 })
+                .into()
         })
         .collect()
 }
@@ -1452,6 +2804,42 @@ fn format_bandwidth_rate(bytes_per_sec: f64) -> String {
     }
 }
 
+/// Wraps the selection dialog's content in its card and dimmed backdrop
+fn selection_dialog_overlay<'a>(
+    content: iced::widget::Column<'a, TcGuiMessage>,
+    colors: ColorPalette,
+    zoom: f32,
+) -> Element<'a, TcGuiMessage> {
+    container(
+        container(content)
+            .padding(scaled_padding(24, zoom))
+            .max_width(600)
+            .style(move |_| container::Style {
+                background: Some(iced::Background::Color(colors.background_card)),
+                border: iced::Border {
+                    radius: 12.0.into(),
+                    width: 1.0,
+                    color: colors.text_secondary,
+                },
+                shadow: iced::Shadow {
+                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                    offset: iced::Vector::new(0.0, 8.0),
+                    blur_radius: 16.0,
+                },
+                ..container::Style::default()
+            }),
+    )
+    .padding(scaled_padding(40, zoom))
+    .center(Length::Fill)
+    .style(move |_| container::Style {
+        background: Some(iced::Background::Color(Color::from_rgba(
+            0.0, 0.0, 0.0, 0.5,
+        ))),
+        ..container::Style::default()
+    })
+    .into()
+}
+
 /// Renders the interface selection dialog overlay
 fn render_interface_selection_dialog<'a>(
     backend_manager: &'a BackendManager,
@@ -1460,6 +2848,20 @@ fn render_interface_selection_dialog<'a>(
     zoom: f32,
 ) -> Element<'a, TcGuiMessage> {
     let dialog = ui_state.interface_selection_dialog();
+    let (title, instructions) = match dialog.purpose {
+        SelectionPurpose::InterfaceControl => (
+            " Bring Interfaces Up or Down".to_string(),
+            "Select a network namespace and the interfaces to bring up or down together:",
+        ),
+        SelectionPurpose::FeatureClear => (
+            " Remove an Impairment".to_string(),
+            "Select an impairment, a network namespace and the interfaces to remove it from; their other impairments are kept:",
+        ),
+        SelectionPurpose::ScenarioExecution => (
+            format!(" Select Interface for Scenario: {}", dialog.scenario_id),
+            "Please select a network namespace and interface to execute the scenario on:",
+        ),
+    };
 
     // Get the backend
     let backend_group = backend_manager.backends().get(&dialog.backend_name);
@@ -1469,14 +2871,11 @@ fn render_interface_selection_dialog<'a>(
             // Dialog header
             row![
                 Icon::Target.svg_sized_colored(scaled(18, zoom), colors.text_primary),
-                text(format!(
-                    " Select Interface for Scenario: {}",
-                    dialog.scenario_id
-                ))
-                .size(scaled(18, zoom))
-                .style(move |_| text::Style {
-                    color: Some(colors.text_primary),
-                }),
+                text(title)
+                    .size(scaled(18, zoom))
+                    .style(move |_| text::Style {
+                        color: Some(colors.text_primary),
+                    }),
                 space().width(Length::Fill),
                 button(Icon::X.svg_sized_colored(scaled(14, zoom), Color::WHITE))
                     .on_press(TcGuiMessage::HideInterfaceSelectionDialog)
@@ -1494,7 +2893,7 @@ fn render_interface_selection_dialog<'a>(
             .spacing(scaled_spacing(12, zoom))
             .align_y(iced::Alignment::Center),
             // Instructions
-            text("Please select a network namespace and interface to execute the scenario on:")
+            text(instructions)
                 .size(scaled(14, zoom))
                 .style(move |_| text::Style {
                     color: Some(colors.text_secondary),
@@ -1502,6 +2901,22 @@ fn render_interface_selection_dialog<'a>(
         ]
         .spacing(scaled_spacing(16, zoom));
 
+        // The remembered target (preselected below) is gone in whole or part
+        if let Some(note) = &dialog.target_note {
+            content = content.push(
+                row![
+                    Icon::AlertTriangle.svg_sized_colored(scaled(14, zoom), colors.warning_orange),
+                    text(note.as_str())
+                        .size(scaled(13, zoom))
+                        .style(move |_| text::Style {
+                            color: Some(colors.warning_orange),
+                        }),
+                ]
+                .spacing(scaled_spacing(6, zoom))
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
         // Namespace and interface selection
         let mut namespaces_column = column![].spacing(scaled_spacing(12, zoom));
 
@@ -1642,11 +3057,19 @@ fn render_interface_selection_dialog<'a>(
                 namespaces_column = namespaces_column.push(
                     container(
                         column![
-                            text(selection_info).size(scaled(12, zoom)).style(move |_| {
-                                text::Style {
-                                    color: Some(colors.text_secondary),
-                                }
-                            }),
+                            row![
+                                text(selection_info).size(scaled(12, zoom)).style(move |_| {
+                                    text::Style {
+                                        color: Some(colors.text_secondary),
+                                    }
+                                }),
+                                button(text("Select all").size(scaled(12, zoom)))
+                                    .padding(scaled_padding(2, zoom))
+                                    .on_press(TcGuiMessage::SelectAllExecutionInterfaces)
+                                    .style(button::secondary),
+                            ]
+                            .spacing(scaled_spacing(8, zoom))
+                            .align_y(iced::Alignment::Center),
                             interfaces_row.wrap()
                         ]
                         .spacing(scaled_spacing(8, zoom)),
@@ -1658,6 +3081,77 @@ fn render_interface_selection_dialog<'a>(
 
         content = content.push(namespaces_column);
 
+        let can_confirm = ui_state.can_confirm_execution();
+        let cancel_button = button(text("Cancel").size(scaled(14, zoom)))
+            .padding([scaled_padding(8, zoom), scaled_padding(16, zoom)])
+            .on_press(TcGuiMessage::HideInterfaceSelectionDialog)
+            .style(move |_, _| button::Style {
+                background: Some(iced::Background::Color(colors.background_card)),
+                text_color: colors.text_primary,
+                border: iced::Border {
+                    radius: 6.0.into(),
+                    width: 1.0,
+                    color: colors.text_secondary,
+                },
+                ..button::Style::default()
+            });
+
+        // Feature clear: pick the impairment, then remove it in one go
+        if dialog.purpose == SelectionPurpose::FeatureClear {
+            let mut features = row![].spacing(scaled_spacing(6, zoom));
+            for feature in TcFeature::ALL {
+                let selected = dialog.clear_feature == Some(feature);
+                features = features.push(
+                    button(text(feature.label()).size(scaled(13, zoom)))
+                        .padding([scaled_padding(4, zoom), scaled_padding(10, zoom)])
+                        .on_press(TcGuiMessage::SelectClearFeature(feature))
+                        .style(if selected {
+                            button::primary
+                        } else {
+                            button::secondary
+                        }),
+                );
+            }
+            let remove_label = match dialog.clear_feature {
+                Some(feature) => format!("Remove {}", feature),
+                None => "Remove".to_string(),
+            };
+            content = content.push(features).push(
+                row![
+                    cancel_button,
+                    space().width(Length::Fill),
+                    button(text(remove_label).size(scaled(14, zoom)))
+                        .padding([scaled_padding(8, zoom), scaled_padding(16, zoom)])
+                        .on_press_maybe(can_confirm.then_some(TcGuiMessage::ConfirmFeatureClear))
+                        .style(button::danger),
+                ]
+                .spacing(scaled_spacing(12, zoom)),
+            );
+            return selection_dialog_overlay(content, colors, zoom);
+        }
+
+        // Interface control has no timing options, just the two directions
+        if dialog.purpose == SelectionPurpose::InterfaceControl {
+            let confirm =
+                |operation| can_confirm.then_some(TcGuiMessage::ConfirmInterfaceControl(operation));
+            content = content.push(
+                row![
+                    cancel_button,
+                    space().width(Length::Fill),
+                    button(text("Bring down").size(scaled(14, zoom)))
+                        .padding([scaled_padding(8, zoom), scaled_padding(16, zoom)])
+                        .on_press_maybe(confirm(InterfaceControlOperation::Disable))
+                        .style(button::danger),
+                    button(text("Bring up").size(scaled(14, zoom)))
+                        .padding([scaled_padding(8, zoom), scaled_padding(16, zoom)])
+                        .on_press_maybe(confirm(InterfaceControlOperation::Enable))
+                        .style(button::success),
+                ]
+                .spacing(scaled_spacing(12, zoom)),
+            );
+            return selection_dialog_overlay(content, colors, zoom);
+        }
+
         // Loop execution toggle
         let loop_enabled = dialog.loop_execution;
         let loop_icon = if loop_enabled {
@@ -1715,79 +3209,67 @@ fn render_interface_selection_dialog<'a>(
 
         content = content.push(loop_toggle);
 
+        // Scheduled start time
+        let start_at_hint = match crate::scenario_manager::parse_start_time(
+            &dialog.start_at_input,
+            chrono::Local::now(),
+        ) {
+            Ok(None) => ("Starts immediately".to_string(), colors.text_secondary),
+            Ok(Some(start_at)) => (
+                format!(
+                    "Starts at {}",
+                    crate::scenario_manager::format_start_time(start_at)
+                ),
+                colors.primary_blue,
+            ),
+            Err(e) => (e, colors.error_red),
+        };
+        let start_at_row = row![
+            Icon::Clock.svg_sized_colored(scaled(16, zoom), colors.text_primary),
+            text_input("start at (HH:MM or +10m)", &dialog.start_at_input)
+                .on_input(TcGuiMessage::ExecutionStartAtChanged)
+                .size(scaled(13, zoom))
+                .padding(scaled_padding(6, zoom))
+                .width(Length::Fixed(200.0 * zoom)),
+            text(start_at_hint.0)
+                .size(scaled(13, zoom))
+                .style(move |_| text::Style {
+                    color: Some(start_at_hint.1),
+                })
+        ]
+        .spacing(scaled_spacing(10, zoom))
+        .align_y(iced::Alignment::Center);
+
+        content = content.push(start_at_row);
+
         // Action buttons
-        let can_confirm = ui_state.can_confirm_execution();
-        let action_row = row![
-            button(text("Cancel").size(scaled(14, zoom)))
+        let action_row = row![cancel_button, space().width(Length::Fill), {
+            let mut btn = button(text("Execute Scenario").size(scaled(14, zoom)))
                 .padding([scaled_padding(8, zoom), scaled_padding(16, zoom)])
-                .on_press(TcGuiMessage::HideInterfaceSelectionDialog)
                 .style(move |_, _| button::Style {
-                    background: Some(iced::Background::Color(colors.background_card)),
-                    text_color: colors.text_primary,
+                    background: Some(iced::Background::Color(if can_confirm {
+                        colors.success_green
+                    } else {
+                        colors.text_secondary
+                    })),
+                    text_color: Color::WHITE,
                     border: iced::Border {
                         radius: 6.0.into(),
-                        width: 1.0,
-                        color: colors.text_secondary,
+                        width: 0.0,
+                        color: Color::TRANSPARENT,
                     },
                     ..button::Style::default()
-                }),
-            space().width(Length::Fill),
-            {
-                let mut btn = button(text("Execute Scenario").size(scaled(14, zoom)))
-                    .padding([scaled_padding(8, zoom), scaled_padding(16, zoom)])
-                    .style(move |_, _| button::Style {
-                        background: Some(iced::Background::Color(if can_confirm {
-                            colors.success_green
-                        } else {
-                            colors.text_secondary
-                        })),
-                        text_color: Color::WHITE,
-                        border: iced::Border {
-                            radius: 6.0.into(),
-                            width: 0.0,
-                            color: Color::TRANSPARENT,
-                        },
-                        ..button::Style::default()
-                    });
-                if can_confirm {
-                    btn = btn.on_press(TcGuiMessage::ConfirmScenarioExecution);
-                }
-                btn
+                });
+            if can_confirm {
+                btn = btn.on_press(TcGuiMessage::ConfirmScenarioExecution);
             }
-        ]
+            btn
+        }]
         .spacing(scaled_spacing(12, zoom));
 
         content = content.push(action_row);
 
-        // Dialog container with backdrop
-        container(
-            container(content)
-                .padding(scaled_padding(24, zoom))
-                .max_width(600)
-                .style(move |_| container::Style {
-                    background: Some(iced::Background::Color(colors.background_card)),
-                    border: iced::Border {
-                        radius: 12.0.into(),
-                        width: 1.0,
-                        color: colors.text_secondary,
-                    },
-                    shadow: iced::Shadow {
-                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
-                        offset: iced::Vector::new(0.0, 8.0),
-                        blur_radius: 16.0,
-                    },
-                    ..container::Style::default()
-                }),
-        )
-        .padding(scaled_padding(40, zoom))
-        .center(Length::Fill)
-        .style(move |_| container::Style {
-            background: Some(iced::Background::Color(Color::from_rgba(
-                0.0, 0.0, 0.0, 0.5,
-            ))),
-            ..container::Style::default()
-        })
-        .into()
+        selection_dialog_overlay(content, colors, zoom)
     } else {
         // Backend not found, show error
         container(
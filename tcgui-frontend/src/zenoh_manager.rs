@@ -3,10 +3,15 @@ use iced::task::{Never, Sipper, sipper};
 use serde::de::DeserializeOwned;
 use std::sync::Arc;
 use tcgui_shared::{
-    BackendHealthStatus, BandwidthUpdate, InterfaceControlResponse, NetworkInterface,
-    TcConfigUpdate, TcResponse, TcStatisticsUpdate, ZenohConfig,
-    identity::RemoteOrigin,
+    BackendHealthStatus, BandwidthUpdate, ForceRefreshResponse, InterfaceControlBatchResponse,
+    InterfaceControlResponse, NamespaceDiagnosticsResponse, NetemCapabilities, NetworkInterface,
+    StatsResponse, TcConfigUpdate, TcFeatureClearResponse, TcResponse, TcStatisticsUpdate,
+    ZenohConfig,
+    config_history::ConfigHistoryResponse,
+    identity::{RemoteOrigin, querier_info},
     presets::CustomPreset,
+    qdisc_tree::TcTreeResponse,
+    rate_comparison::RateComparisonResponse,
     registry::tc,
     scenario::{NetworkScenario, ScenarioExecutionRequest, ScenarioExecutionUpdate},
     topics,
@@ -16,11 +21,22 @@ use tracing::{error, info, trace};
 use zenoh::sample::{Sample, SampleKind};
 use zenoh_ext::{AdvancedSubscriberBuilderExt, HistoryConfig, RecoveryConfig};
 
+use crate::inspector::InspectorEntry;
 use crate::messages::{
-    DiagnosticsQueryMessage, InterfaceControlQueryMessage, ScenarioExecutionQueryMessage,
-    ScenarioQueryMessage, TcQueryMessage, ZenohEvent,
+    DiagnosticsQuery, DiagnosticsQueryMessage, FeatureClearQueryMessage,
+    InterfaceBatchQueryMessage, InterfaceControlQueryMessage, RefreshQueryMessage,
+    ScenarioExecutionQueryMessage, ScenarioQueryMessage, TcQueryMessage, ZenohEvent,
 };
 
+/// How long to wait for a namespace-wide diagnostics reply. The backend
+/// diagnoses interfaces a few at a time, so this is far above the default
+/// query timeout.
+const NAMESPACE_DIAGNOSTICS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// How long to wait for a netem vs tbf comparison: two throughput runs plus
+/// reshaping and restoring the interface.
+const RATE_COMPARISON_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
 /// Extract a human-readable message from a Zenoh reply-error payload.
 ///
 /// Backends signal query failures on the reply-error channel (RFC keyspace-v2
@@ -54,6 +70,16 @@ fn deser_payload<T: DeserializeOwned>(sample: &Sample, ctx: &str) -> Option<T> {
     }
 }
 
+/// Hand a copy of a message to the inspector when it is enabled; the entry
+/// is only built then.
+macro_rules! inspect {
+    ($output:expr, $enabled:expr, $entry:expr) => {
+        if $enabled {
+            let _ = $output.send(ZenohEvent::Inspected(Box::new($entry))).await;
+        }
+    };
+}
+
 /// Route a `state`-plane sample (Put upsert or Delete tombstone).
 ///
 /// Every family must inspect [`Sample::kind`] now that removal is a Delete
@@ -246,10 +272,10 @@ fn handle_liveliness_sample(sample: Sample) -> Option<ZenohEvent> {
     }
 }
 
-/// Extract the (namespace, interface) target from an execution request. Every
-/// variant the frontend sends (Start/Stop/Pause/Resume) carries one; the
-/// query-only variants (Status/ListActive) do not and are not routed here.
-fn execution_target(request: &ScenarioExecutionRequest) -> Option<(&str, &str)> {
+/// Extract the (namespace, interface) target from an execution request.
+/// `ListActive` is fleet-wide and carries neither, so it is sent on the
+/// reserved `all/all` subject the backend also replies on.
+fn execution_target(request: &ScenarioExecutionRequest) -> (&str, &str) {
     match request {
         ScenarioExecutionRequest::Start {
             namespace,
@@ -271,14 +297,16 @@ fn execution_target(request: &ScenarioExecutionRequest) -> Option<(&str, &str)>
         | ScenarioExecutionRequest::Status {
             namespace,
             interface,
-        } => Some((namespace, interface)),
-        ScenarioExecutionRequest::ListActive => None,
+        } => (namespace, interface),
+        ScenarioExecutionRequest::ListActive => ("all", "all"),
     }
 }
 
 /// Zenoh session manager with configuration dependency injection
 pub struct ZenohManager {
     config: Arc<ZenohConfig>,
+    /// Copy raw messages to the inspector
+    inspector: bool,
 }
 
 impl ZenohManager {
@@ -286,23 +314,48 @@ impl ZenohManager {
     pub fn new(config: ZenohConfig) -> Self {
         Self {
             config: Arc::new(config),
+            inspector: false,
         }
     }
 
+    /// Emit a [`ZenohEvent::Inspected`] copy of every message exchanged.
+    pub fn with_inspector(mut self, enabled: bool) -> Self {
+        self.inspector = enabled;
+        self
+    }
+
+    /// Session configuration in use
+    pub fn config(&self) -> &ZenohConfig {
+        &self.config
+    }
+
+    /// A manager whose session also dials `endpoint`. Swapping it in restarts
+    /// the subscription, and with it the session.
+    pub fn with_connect_endpoint(&self, endpoint: &str) -> Result<Self, String> {
+        let config = (*self.config).clone().add_connect_endpoint(endpoint);
+        config.validate().map_err(|e| e.to_string())?;
+        Ok(Self {
+            config: Arc::new(config),
+            inspector: self.inspector,
+        })
+    }
+
     /// Create an Iced subscription for zenoh events with dependency-injected configuration
     ///
     /// Creates a subscription that uses the configured Zenoh settings.
     /// This properly respects the configuration passed during ZenohManager construction.
     pub fn subscription(&self) -> Subscription<ZenohEvent> {
         let config = Arc::clone(&self.config);
-        Subscription::run_with((*config).clone(), |config| {
-            zenoh_manager_with_arc(Arc::new(config.clone()))
-        })
+        Subscription::run_with(
+            ((*config).clone(), self.inspector),
+            |(config, inspector)| zenoh_manager_with_arc(Arc::new(config.clone()), *inspector),
+        )
     }
 
     /// Create the zenoh sipper with the configured settings
     pub fn create_sipper(self) -> impl Sipper<Never, ZenohEvent> {
         let config = self.config;
+        let inspector = self.inspector;
         sipper(async move |mut output| {
             loop {
                 tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
@@ -336,6 +389,12 @@ impl ZenohManager {
                         ) = mpsc::unbounded_channel::<ScenarioExecutionQueryMessage>();
                         let (diagnostics_query_sender, mut diagnostics_query_receiver) =
                             mpsc::unbounded_channel::<DiagnosticsQueryMessage>();
+                        let (refresh_query_sender, mut refresh_query_receiver) =
+                            mpsc::unbounded_channel::<RefreshQueryMessage>();
+                        let (interface_batch_query_sender, mut interface_batch_query_receiver) =
+                            mpsc::unbounded_channel::<InterfaceBatchQueryMessage>();
+                        let (feature_clear_query_sender, mut feature_clear_query_receiver) =
+                            mpsc::unbounded_channel::<FeatureClearQueryMessage>();
 
                         let _ = output
                             .send(ZenohEvent::TcQueryChannelReady(tc_query_sender))
@@ -358,6 +417,19 @@ impl ZenohManager {
                                 diagnostics_query_sender,
                             ))
                             .await;
+                        let _ = output
+                            .send(ZenohEvent::RefreshQueryChannelReady(refresh_query_sender))
+                            .await;
+                        let _ = output
+                            .send(ZenohEvent::InterfaceBatchQueryChannelReady(
+                                interface_batch_query_sender,
+                            ))
+                            .await;
+                        let _ = output
+                            .send(ZenohEvent::FeatureClearQueryChannelReady(
+                                feature_clear_query_sender,
+                            ))
+                            .await;
 
                         // Single state-plane subscriber (LWW; delete = tombstone).
                         // History detects late publishers; recovery uses
@@ -424,6 +496,7 @@ impl ZenohManager {
                                 sample_result = state_subscriber.recv_async() => {
                                     match sample_result {
                                         Ok(sample) => {
+                                            inspect!(output, inspector, InspectorEntry::sample(&sample));
                                             if let Some(event) = handle_state_sample(sample) {
                                                 let _ = output.send(event).await;
                                             }
@@ -439,6 +512,7 @@ impl ZenohManager {
                                 sample_result = telemetry_subscriber.recv_async() => {
                                     match sample_result {
                                         Ok(sample) => {
+                                            inspect!(output, inspector, InspectorEntry::sample(&sample));
                                             if let Some(event) = handle_telemetry_sample(sample) {
                                                 let _ = output.send(event).await;
                                             }
@@ -454,7 +528,46 @@ impl ZenohManager {
                                 sample_result = liveliness_subscriber.recv_async() => {
                                     match sample_result {
                                         Ok(sample) => {
+                                            inspect!(output, inspector, InspectorEntry::sample(&sample));
                                             if let Some(event) = handle_liveliness_sample(sample) {
+                                                // Ask a backend that (re)appears what netem
+                                                // features its kernel supports
+                                                if let ZenohEvent::BackendLiveliness { backend_name, alive: true } = &event
+                                                    && let Ok(origin) = RemoteOrigin::parse(backend_name)
+                                                {
+                                                    let topic = tc::capabilities_key(&origin);
+                                                    let mut output_clone = output.clone();
+                                                    let backend_name = backend_name.clone();
+                                                    inspect!(output, inspector, InspectorEntry::query(topic.as_str(), None));
+                                                    let query_key = topic.as_str().to_string();
+                                                    match session.get(topic.as_str()).source_info(querier_info(&session)).await {
+                                                        Ok(replies) => {
+                                                            tokio::spawn(async move {
+                                                                let Ok(reply) = replies.recv_async().await else {
+                                                                    error!("No capabilities reply from backend '{}'", backend_name);
+                                                                    return;
+                                                                };
+                                                                inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, &reply));
+                                                                match reply.into_result() {
+                                                                    Ok(sample) => {
+                                                                        if let Some(capabilities) = deser_payload::<NetemCapabilities>(&sample, "netem capabilities") {
+                                                                            let _ = output_clone.send(ZenohEvent::NetemCapabilities {
+                                                                                backend_name,
+                                                                                capabilities,
+                                                                            }).await;
+                                                                        }
+                                                                    }
+                                                                    Err(e) => {
+                                                                        error!("Capabilities query to '{}' failed: {}", backend_name, reply_error_message(&e));
+                                                                    }
+                                                                }
+                                                            });
+                                                        }
+                                                        Err(e) => {
+                                                            error!("Failed to send capabilities query to '{}': {}", backend_name, e);
+                                                        }
+                                                    }
+                                                }
                                                 let _ = output.send(event).await;
                                             }
                                         }
@@ -477,20 +590,27 @@ impl ZenohManager {
                                     let topic = tc::config_ns_iface_set_key(&origin, &tc_query.request.namespace, &tc_query.request.interface);
                                     let mut output_clone = output.clone();
                                     let backend_name = tc_query.backend_name.clone();
+                                    let namespace = tc_query.request.namespace.clone();
+                                    let interface = tc_query.request.interface.clone();
                                     match serde_json::to_string(&tc_query.request) {
                                         Ok(payload) => {
-                                            match session.get(topic.as_str()).payload(payload).await {
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
                                                 Ok(replies) => {
                                                     tokio::spawn(async move {
                                                         while let Ok(reply) = replies.recv_async().await {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, &reply));
                                                             match reply.into_result() {
                                                                 Ok(sample) => {
                                                                     let payload_bytes = sample.payload().to_bytes();
                                                                     if let Ok(payload_str) = std::str::from_utf8(&payload_bytes)
                                                                             && let Ok(response) = serde_json::from_str::<TcResponse>(payload_str) {
-                                                                                // Forward the result so the app can surface failures.
+                                                                                // Forward the result so the app can surface failures and what it reports.
                                                                                 let _ = output_clone.send(ZenohEvent::TcOperationResult {
                                                                                     backend_name: backend_name.clone(),
+                                                                                    namespace: namespace.clone(),
+                                                                                    interface: interface.clone(),
                                                                                     response,
                                                                                 }).await;
                                                                             }
@@ -498,8 +618,10 @@ impl ZenohManager {
                                                                 Err(e) => {
                                                                     let error = reply_error_message(&e);
                                                                     error!("TC query reply error: {}", error);
-                                                                    let _ = output_clone.send(ZenohEvent::QueryError {
+                                                                    let _ = output_clone.send(ZenohEvent::TcOperationError {
                                                                         backend_name: backend_name.clone(),
+                                                                        namespace: namespace.clone(),
+                                                                        interface: interface.clone(),
                                                                         error,
                                                                     }).await;
                                                                 }
@@ -530,20 +652,27 @@ impl ZenohManager {
                                     let topic = tc::interface_ns_iface_set_key(&origin, &interface_query.request.namespace, &interface_query.request.interface);
                                     let mut output_clone = output.clone();
                                     let backend_name = interface_query.backend_name.clone();
+                                    let namespace = interface_query.request.namespace.clone();
+                                    let interface = interface_query.request.interface.clone();
                                     match serde_json::to_string(&interface_query.request) {
                                         Ok(payload) => {
-                                            match session.get(topic.as_str()).payload(payload).await {
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
                                                 Ok(replies) => {
                                                     tokio::spawn(async move {
                                                         while let Ok(reply) = replies.recv_async().await {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, &reply));
                                                             match reply.into_result() {
                                                                 Ok(sample) => {
                                                                     let payload_bytes = sample.payload().to_bytes();
                                                                     if let Ok(payload_str) = std::str::from_utf8(&payload_bytes)
                                                                             && let Ok(response) = serde_json::from_str::<InterfaceControlResponse>(payload_str) {
-                                                                                // Forward the result so the app can surface failures.
+                                                                                // Forward the result so the app can surface failures and what it reports.
                                                                                 let _ = output_clone.send(ZenohEvent::InterfaceControlResult {
                                                                                     backend_name: backend_name.clone(),
+                                                                                    namespace: namespace.clone(),
+                                                                                    interface: interface.clone(),
                                                                                     response,
                                                                                 }).await;
                                                                             }
@@ -587,10 +716,13 @@ impl ZenohManager {
 
                                     match serde_json::to_string(&scenario_query.request) {
                                         Ok(payload) => {
-                                            match session.get(topic.as_str()).payload(payload).await {
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
                                                 Ok(replies) => {
                                                     tokio::spawn(async move {
                                                         while let Ok(reply) = replies.recv_async().await {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, &reply));
                                                             match reply.into_result() {
                                                                 Ok(sample) => {
                                                                     let payload_bytes = sample.payload().to_bytes();
@@ -637,29 +769,31 @@ impl ZenohManager {
                                             continue;
                                         }
                                     };
-                                    let (namespace, interface) = match execution_target(&execution_query.request) {
-                                        Some(target) => target,
-                                        None => {
-                                            error!("Scenario execution request has no interface target; skipping");
-                                            continue;
-                                        }
-                                    };
+                                    let (namespace, interface) = execution_target(&execution_query.request);
                                     let topic = tc::execution_ns_iface_set_key(&origin, namespace, interface);
                                     let mut output_clone = output.clone();
                                     let backend_name = execution_query.backend_name.clone();
                                     match serde_json::to_string(&execution_query.request) {
                                         Ok(payload) => {
-                                            match session.get(topic.as_str()).payload(payload).await {
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
                                                 Ok(replies) => {
                                                     tokio::spawn(async move {
                                                         while let Ok(reply) = replies.recv_async().await {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, &reply));
                                                             match reply.into_result() {
                                                                 Ok(sample) => {
                                                                     let payload_bytes = sample.payload().to_bytes();
                                                                     if let Ok(payload_str) = std::str::from_utf8(&payload_bytes)
                                                                             && let Ok(response) = serde_json::from_str::<ScenarioExecutionResponse>(payload_str) {
-                                                                                // Send response back via original response channel if available
-                                                                                if let Some(ref response_sender) = execution_query.response_sender {
+                                                                                if let ScenarioExecutionResponse::ActiveExecutions { executions } = response {
+                                                                                    let _ = output_clone.send(ZenohEvent::ActiveExecutionsListed {
+                                                                                        backend_name: backend_name.clone(),
+                                                                                        executions,
+                                                                                    }).await;
+                                                                                } else if let Some(ref response_sender) = execution_query.response_sender {
+                                                                                    // Send response back via original response channel if available
                                                                                     let _ = response_sender.send((execution_query.backend_name.clone(), response));
                                                                                 }
                                                                             }
@@ -687,6 +821,143 @@ impl ZenohManager {
                                     }
                                 }
 
+                                // Handle outgoing forced refresh queries
+                                Some(refresh_query) = refresh_query_receiver.recv() => {
+                                    let origin = match RemoteOrigin::parse(&refresh_query.backend_name) {
+                                        Ok(o) => o,
+                                        Err(_) => {
+                                            error!("Refusing refresh query: '{}' is not a concrete origin", refresh_query.backend_name);
+                                            continue;
+                                        }
+                                    };
+                                    let topic = tc::refresh_key(&origin);
+                                    let mut output_clone = output.clone();
+                                    let backend_name = refresh_query.backend_name;
+                                    inspect!(output, inspector, InspectorEntry::query(topic.as_str(), None));
+                                    let query_key = topic.as_str().to_string();
+                                    match session.get(topic.as_str()).source_info(querier_info(&session)).await {
+                                        Ok(replies) => {
+                                            tokio::spawn(async move {
+                                                let reply = replies.recv_async().await;
+                                                if let Ok(reply) = &reply {
+                                                    inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
+                                                }
+                                                let result = match reply {
+                                                    Ok(reply) => match reply.into_result() {
+                                                        Ok(sample) => deser_payload::<ForceRefreshResponse>(&sample, "refresh response")
+                                                            .ok_or_else(|| "Malformed refresh response".to_string()),
+                                                        Err(e) => Err(reply_error_message(&e)),
+                                                    },
+                                                    Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                };
+                                                let _ = output_clone.send(ZenohEvent::ForceRefreshResponse {
+                                                    backend_name,
+                                                    result,
+                                                }).await;
+                                            });
+                                        }
+                                        Err(e) => {
+                                            error!("Failed to send refresh query to '{}': {}", backend_name, e);
+                                        }
+                                    }
+                                }
+
+                                // Handle outgoing batch interface control queries
+                                Some(batch_query) = interface_batch_query_receiver.recv() => {
+                                    let origin = match RemoteOrigin::parse(&batch_query.backend_name) {
+                                        Ok(o) => o,
+                                        Err(_) => {
+                                            error!("Refusing interface batch query: '{}' is not a concrete origin", batch_query.backend_name);
+                                            continue;
+                                        }
+                                    };
+                                    let topic = tc::interfaces_ns_set_key(&origin, &batch_query.request.namespace);
+                                    let payload = match serde_json::to_string(&batch_query.request) {
+                                        Ok(p) => p,
+                                        Err(e) => {
+                                            error!("Failed to serialize interface batch request: {}", e);
+                                            continue;
+                                        }
+                                    };
+                                    let mut output_clone = output.clone();
+                                    let backend_name = batch_query.backend_name;
+                                    inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                    let query_key = topic.as_str().to_string();
+                                    match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
+                                        Ok(replies) => {
+                                            tokio::spawn(async move {
+                                                let reply = replies.recv_async().await;
+                                                if let Ok(reply) = &reply {
+                                                    inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
+                                                }
+                                                let result = match reply {
+                                                    Ok(reply) => match reply.into_result() {
+                                                        Ok(sample) => deser_payload::<InterfaceControlBatchResponse>(&sample, "interface batch response")
+                                                            .ok_or_else(|| "Malformed interface batch response".to_string()),
+                                                        Err(e) => Err(reply_error_message(&e)),
+                                                    },
+                                                    Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                };
+                                                let _ = output_clone.send(ZenohEvent::InterfaceBatchResponse {
+                                                    backend_name,
+                                                    result,
+                                                }).await;
+                                            });
+                                        }
+                                        Err(e) => {
+                                            error!("Failed to send interface batch query to '{}': {}", backend_name, e);
+                                        }
+                                    }
+                                }
+
+                                // Handle outgoing feature clear queries
+                                Some(clear_query) = feature_clear_query_receiver.recv() => {
+                                    let origin = match RemoteOrigin::parse(&clear_query.backend_name) {
+                                        Ok(o) => o,
+                                        Err(_) => {
+                                            error!("Refusing feature clear query: '{}' is not a concrete origin", clear_query.backend_name);
+                                            continue;
+                                        }
+                                    };
+                                    let topic = tc::configs_ns_clear_key(&origin, &clear_query.request.namespace);
+                                    let payload = match serde_json::to_string(&clear_query.request) {
+                                        Ok(p) => p,
+                                        Err(e) => {
+                                            error!("Failed to serialize feature clear request: {}", e);
+                                            continue;
+                                        }
+                                    };
+                                    let mut output_clone = output.clone();
+                                    let backend_name = clear_query.backend_name;
+                                    inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                    let query_key = topic.as_str().to_string();
+                                    match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
+                                        Ok(replies) => {
+                                            tokio::spawn(async move {
+                                                let reply = replies.recv_async().await;
+                                                if let Ok(reply) = &reply {
+                                                    inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
+                                                }
+                                                let result = match reply {
+                                                    Ok(reply) => match reply.into_result() {
+                                                        Ok(sample) => deser_payload::<TcFeatureClearResponse>(&sample, "feature clear response")
+                                                            .ok_or_else(|| "Malformed feature clear response".to_string()),
+                                                        Err(e) => Err(reply_error_message(&e)),
+                                                    },
+                                                    Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                };
+                                                let _ = output_clone.send(ZenohEvent::FeatureClearResponse {
+                                                    backend_name,
+                                                    result,
+                                                }).await;
+                                            });
+                                        }
+                                        Err(e) => {
+                                            error!("Failed to send feature clear query to '{}': {}", backend_name, e);
+                                        }
+                                    }
+                                }
+
                                 // Handle outgoing diagnostics queries
                                 Some(diag_query) = diagnostics_query_receiver.recv() => {
                                     use tcgui_shared::DiagnosticsResponse;
@@ -697,52 +968,282 @@ impl ZenohManager {
                                             continue;
                                         }
                                     };
-                                    let topic = tc::diagnostics_key(&origin);
-                                    let mut output_clone = output.clone();
-                                    let backend_name = diag_query.backend_name.clone();
-                                    let namespace = diag_query.request.namespace.clone();
-                                    let interface = diag_query.request.interface.clone();
+                                    match diag_query.request {
+                                        DiagnosticsQuery::Interface(request) => {
+                                            let topic = tc::diagnostics_key(&origin);
+                                            let mut output_clone = output.clone();
+                                            let backend_name = diag_query.backend_name.clone();
+                                            let namespace = request.namespace.clone();
+                                            let interface = request.interface.clone();
 
-                                    match serde_json::to_string(&diag_query.request) {
-                                        Ok(payload) => {
-                                            match session.get(topic.as_str()).payload(payload).await {
-                                                Ok(replies) => {
-                                                    tokio::spawn(async move {
-                                                        while let Ok(reply) = replies.recv_async().await {
-                                                            match reply.into_result() {
-                                                                Ok(sample) => {
-                                                                    let payload_bytes = sample.payload().to_bytes();
-                                                                    if let Ok(payload_str) = std::str::from_utf8(&payload_bytes)
-                                                                        && let Ok(response) = serde_json::from_str::<DiagnosticsResponse>(payload_str)
-                                                                    {
-                                                                        info!("Received diagnostics response for {}/{}: {}", namespace, interface, response.message);
-                                                                        let _ = output_clone.send(ZenohEvent::DiagnosticsResponse {
-                                                                            backend_name: backend_name.clone(),
-                                                                            namespace: namespace.clone(),
-                                                                            interface: interface.clone(),
-                                                                            response,
-                                                                        }).await;
+                                            match serde_json::to_string(&request) {
+                                                Ok(payload) => {
+                                                    inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                                    let query_key = topic.as_str().to_string();
+                                                    match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
+                                                        Ok(replies) => {
+                                                            tokio::spawn(async move {
+                                                                while let Ok(reply) = replies.recv_async().await {
+                                                                    inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, &reply));
+                                                                    match reply.into_result() {
+                                                                        Ok(sample) => {
+                                                                            let payload_bytes = sample.payload().to_bytes();
+                                                                            if let Ok(payload_str) = std::str::from_utf8(&payload_bytes)
+                                                                                && let Ok(response) = serde_json::from_str::<DiagnosticsResponse>(payload_str)
+                                                                            {
+                                                                                info!("Received diagnostics response for {}/{}: {}", namespace, interface, response.message);
+                                                                                let _ = output_clone.send(ZenohEvent::DiagnosticsResponse {
+                                                                                    backend_name: backend_name.clone(),
+                                                                                    namespace: namespace.clone(),
+                                                                                    interface: interface.clone(),
+                                                                                    response: Box::new(response),
+                                                                                }).await;
+                                                                            }
+                                                                        }
+                                                                        Err(e) => {
+                                                                            let error = reply_error_message(&e);
+                                                                            error!("Diagnostics query reply error: {}", error);
+                                                                            let _ = output_clone.send(ZenohEvent::QueryError {
+                                                                                backend_name: backend_name.clone(),
+                                                                                error,
+                                                                            }).await;
+                                                                        }
                                                                     }
                                                                 }
-                                                                Err(e) => {
-                                                                    let error = reply_error_message(&e);
-                                                                    error!("Diagnostics query reply error: {}", error);
-                                                                    let _ = output_clone.send(ZenohEvent::QueryError {
-                                                                        backend_name: backend_name.clone(),
-                                                                        error,
-                                                                    }).await;
+                                                            });
+                                                        }
+                                                        Err(e) => {
+                                                            error!("Failed to send diagnostics query to '{}': {}", backend_name, e);
+                                                        }
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    error!("Failed to serialize diagnostics request: {}", e);
+                                                }
+                                            }
+                                        }
+                                        DiagnosticsQuery::Namespace(request) => {
+                                            let topic = tc::diagnostics_namespace_key(&origin);
+                                            let mut output_clone = output.clone();
+                                            let backend_name = diag_query.backend_name.clone();
+                                            let namespace = request.namespace.clone();
+
+                                            match serde_json::to_string(&request) {
+                                                Ok(payload) => {
+                                                    inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                                    let query_key = topic.as_str().to_string();
+                                                    match session
+                                                        .get(topic.as_str())
+                                                        .source_info(querier_info(&session))
+                                                        .payload(payload)
+                                                        .timeout(NAMESPACE_DIAGNOSTICS_TIMEOUT)
+                                                        .await
+                                                    {
+                                                        Ok(replies) => {
+                                                            tokio::spawn(async move {
+                                                                let reply = replies.recv_async().await;
+                                                                if let Ok(reply) = &reply {
+                                                                    inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
                                                                 }
-                                                            }
+                                                                let result = match reply {
+                                                                    Ok(reply) => match reply.into_result() {
+                                                                        Ok(sample) => {
+                                                                            let payload_bytes = sample.payload().to_bytes();
+                                                                            std::str::from_utf8(&payload_bytes)
+                                                                                .map_err(|e| e.to_string())
+                                                                                .and_then(|payload_str| {
+                                                                                    serde_json::from_str::<NamespaceDiagnosticsResponse>(payload_str)
+                                                                                        .map_err(|e| e.to_string())
+                                                                                })
+                                                                        }
+                                                                        Err(e) => Err(reply_error_message(&e)),
+                                                                    },
+                                                                    Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                                };
+                                                                match &result {
+                                                                    Ok(response) => info!("Received namespace diagnostics for {}: {}", namespace, response.summary()),
+                                                                    Err(e) => error!("Namespace diagnostics query for {} failed: {}", namespace, e),
+                                                                }
+                                                                let _ = output_clone.send(ZenohEvent::NamespaceDiagnosticsResponse {
+                                                                    backend_name,
+                                                                    namespace,
+                                                                    result,
+                                                                }).await;
+                                                            });
                                                         }
+                                                        Err(e) => {
+                                                            error!("Failed to send namespace diagnostics query to '{}': {}", backend_name, e);
+                                                        }
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    error!("Failed to serialize namespace diagnostics request: {}", e);
+                                                }
+                                            }
+                                        }
+                                        DiagnosticsQuery::Tree(request) => {
+                                            let topic = tc::diagnostics_tree_key(&origin);
+                                            let payload = match serde_json::to_string(&request) {
+                                                Ok(p) => p,
+                                                Err(e) => {
+                                                    error!("Failed to serialize qdisc tree request: {}", e);
+                                                    continue;
+                                                }
+                                            };
+                                            let mut output_clone = output.clone();
+                                            let backend_name = diag_query.backend_name.clone();
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
+                                                Ok(replies) => {
+                                                    tokio::spawn(async move {
+                                                        let reply = replies.recv_async().await;
+                                                        if let Ok(reply) = &reply {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
+                                                        }
+                                                        let result = match reply {
+                                                            Ok(reply) => match reply.into_result() {
+                                                                Ok(sample) => deser_payload::<TcTreeResponse>(&sample, "qdisc tree response")
+                                                                    .ok_or_else(|| "Malformed qdisc tree response".to_string()),
+                                                                Err(e) => Err(reply_error_message(&e)),
+                                                            },
+                                                            Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                        };
+                                                        let _ = output_clone.send(ZenohEvent::QdiscTreeResponse {
+                                                            backend_name,
+                                                            namespace: request.namespace,
+                                                            interface: request.interface,
+                                                            result,
+                                                        }).await;
                                                     });
                                                 }
                                                 Err(e) => {
-                                                    error!("Failed to send diagnostics query to '{}': {}", backend_name, e);
+                                                    error!("Failed to send qdisc tree query to '{}': {}", backend_name, e);
                                                 }
                                             }
                                         }
-                                        Err(e) => {
-                                            error!("Failed to serialize diagnostics request: {}", e);
+                                        DiagnosticsQuery::RateComparison(request) => {
+                                            let topic = tc::diagnostics_ns_iface_rate_compare_key(&origin, &request.namespace, &request.interface);
+                                            let payload = match serde_json::to_string(&request) {
+                                                Ok(p) => p,
+                                                Err(e) => {
+                                                    error!("Failed to serialize rate comparison request: {}", e);
+                                                    continue;
+                                                }
+                                            };
+                                            let mut output_clone = output.clone();
+                                            let backend_name = diag_query.backend_name.clone();
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).timeout(RATE_COMPARISON_TIMEOUT).await {
+                                                Ok(replies) => {
+                                                    tokio::spawn(async move {
+                                                        let reply = replies.recv_async().await;
+                                                        if let Ok(reply) = &reply {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
+                                                        }
+                                                        let result = match reply {
+                                                            Ok(reply) => match reply.into_result() {
+                                                                Ok(sample) => deser_payload::<RateComparisonResponse>(&sample, "rate comparison response")
+                                                                    .ok_or_else(|| "Malformed rate comparison response".to_string()),
+                                                                Err(e) => Err(reply_error_message(&e)),
+                                                            },
+                                                            Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                        };
+                                                        let _ = output_clone.send(ZenohEvent::RateComparisonResponse {
+                                                            backend_name,
+                                                            namespace: request.namespace,
+                                                            interface: request.interface,
+                                                            result,
+                                                        }).await;
+                                                    });
+                                                }
+                                                Err(e) => {
+                                                    error!("Failed to send rate comparison query to '{}': {}", backend_name, e);
+                                                }
+                                            }
+                                        }
+                                        DiagnosticsQuery::ConfigHistory(request) => {
+                                            let topic = tc::config_ns_iface_history_key(&origin, &request.namespace, &request.interface);
+                                            let payload = match serde_json::to_string(&request) {
+                                                Ok(p) => p,
+                                                Err(e) => {
+                                                    error!("Failed to serialize config history request: {}", e);
+                                                    continue;
+                                                }
+                                            };
+                                            let mut output_clone = output.clone();
+                                            let backend_name = diag_query.backend_name.clone();
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
+                                                Ok(replies) => {
+                                                    tokio::spawn(async move {
+                                                        let reply = replies.recv_async().await;
+                                                        if let Ok(reply) = &reply {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
+                                                        }
+                                                        let result = match reply {
+                                                            Ok(reply) => match reply.into_result() {
+                                                                Ok(sample) => deser_payload::<ConfigHistoryResponse>(&sample, "config history response")
+                                                                    .ok_or_else(|| "Malformed config history response".to_string()),
+                                                                Err(e) => Err(reply_error_message(&e)),
+                                                            },
+                                                            Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                        };
+                                                        let _ = output_clone.send(ZenohEvent::ConfigHistoryResponse {
+                                                            backend_name,
+                                                            namespace: request.namespace,
+                                                            interface: request.interface,
+                                                            result,
+                                                        }).await;
+                                                    });
+                                                }
+                                                Err(e) => {
+                                                    error!("Failed to send config history query to '{}': {}", backend_name, e);
+                                                }
+                                            }
+                                        }
+                                        DiagnosticsQuery::Stats(request) => {
+                                            let topic = tc::bandwidth_ns_iface_snapshot_key(&origin, &request.namespace, &request.interface);
+                                            let payload = match serde_json::to_string(&request) {
+                                                Ok(p) => p,
+                                                Err(e) => {
+                                                    error!("Failed to serialize stats request: {}", e);
+                                                    continue;
+                                                }
+                                            };
+                                            let mut output_clone = output.clone();
+                                            let backend_name = diag_query.backend_name.clone();
+                                            inspect!(output, inspector, InspectorEntry::query(topic.as_str(), Some(&payload)));
+                                            let query_key = topic.as_str().to_string();
+                                            match session.get(topic.as_str()).source_info(querier_info(&session)).payload(payload).await {
+                                                Ok(replies) => {
+                                                    tokio::spawn(async move {
+                                                        let reply = replies.recv_async().await;
+                                                        if let Ok(reply) = &reply {
+                                                            inspect!(output_clone, inspector, InspectorEntry::reply(&query_key, reply));
+                                                        }
+                                                        let result = match reply {
+                                                            Ok(reply) => match reply.into_result() {
+                                                                Ok(sample) => deser_payload::<StatsResponse>(&sample, "stats response")
+                                                                    .ok_or_else(|| "Malformed stats response".to_string()),
+                                                                Err(e) => Err(reply_error_message(&e)),
+                                                            },
+                                                            Err(_) => Err("No reply from backend (timed out)".to_string()),
+                                                        };
+                                                        let _ = output_clone.send(ZenohEvent::StatsResponse {
+                                                            backend_name,
+                                                            namespace: request.namespace,
+                                                            interface: request.interface,
+                                                            result,
+                                                        }).await;
+                                                    });
+                                                }
+                                                Err(e) => {
+                                                    error!("Failed to send stats query to '{}': {}", backend_name, e);
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -763,8 +1264,11 @@ impl ZenohManager {
 }
 
 /// Creates a zenoh manager sipper with the provided configuration (takes Arc)
-pub fn zenoh_manager_with_arc(config: Arc<ZenohConfig>) -> impl Sipper<Never, ZenohEvent> {
-    ZenohManager { config }.create_sipper()
+pub fn zenoh_manager_with_arc(
+    config: Arc<ZenohConfig>,
+    inspector: bool,
+) -> impl Sipper<Never, ZenohEvent> {
+    ZenohManager { config, inspector }.create_sipper()
 }
 
 #[cfg(test)]
@@ -815,10 +1319,10 @@ mod tests {
             namespace: "default".to_string(),
             interface: "eth0".to_string(),
         };
-        assert_eq!(execution_target(&start), Some(("default", "eth0")));
+        assert_eq!(execution_target(&start), ("default", "eth0"));
         assert_eq!(
             execution_target(&ScenarioExecutionRequest::ListActive),
-            None
+            ("all", "all")
         );
     }
 
@@ -834,6 +1338,9 @@ mod tests {
             metadata: tcgui_shared::BackendMetadata::default(),
             namespace_count: 1,
             interface_count: 2,
+            failed_namespaces: Vec::new(),
+            connected_peers: 0,
+            peer_ids: Vec::new(),
         };
         let json = serde_json::to_string(&health).unwrap();
         let deserialized: BackendHealthStatus = serde_json::from_str(&json).unwrap();
@@ -0,0 +1,114 @@
+//! Recent TC configurations of one interface, as its backend published them.
+//!
+//! The backend keeps, per interface, the configurations it published and
+//! when, up to [`MAX_CONFIG_HISTORY`] entries. It's the per-interface slice
+//! of what an audit log would hold: it outlives frontend restarts (unlike the
+//! frontend's own change markers) but not a restart of the backend.
+
+use serde::{Deserialize, Serialize};
+
+use crate::TcConfiguration;
+
+/// Entries kept per interface; older ones are dropped first.
+pub const MAX_CONFIG_HISTORY: usize = 100;
+
+/// Recent configurations of one interface (Query)
+/// Query Service: config/{ns}/{iface}/history
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ConfigHistoryRequest {
+    /// Target network namespace
+    pub namespace: String,
+    /// Target interface name
+    pub interface: String,
+    /// Return at most this many of the most recent entries (all kept entries
+    /// when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// A configuration the backend published for an interface.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ConfigHistoryEntry {
+    /// Unix timestamp (seconds) of the publication
+    pub timestamp: u64,
+    /// The configuration from then on (None = cleared)
+    pub configuration: Option<TcConfiguration>,
+}
+
+/// Recent configurations of one interface (Reply)
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ConfigHistoryResponse {
+    pub namespace: String,
+    pub interface: String,
+    /// Oldest first; empty when nothing was published for the interface
+    pub entries: Vec<ConfigHistoryEntry>,
+}
+
+/// How long an interface had one configuration.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigPeriod<'a> {
+    /// Unix timestamp (seconds) the configuration was published
+    pub from: u64,
+    /// When the next one replaced it (None for the current configuration)
+    pub until: Option<u64>,
+    /// None when the interface had no configuration
+    pub configuration: Option<&'a TcConfiguration>,
+}
+
+impl ConfigHistoryResponse {
+    /// The entries as consecutive periods, oldest first: each lasts until
+    /// the next entry, the last one is still in effect.
+    pub fn periods(&self) -> Vec<ConfigPeriod<'_>> {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| ConfigPeriod {
+                from: entry.timestamp,
+                until: self.entries.get(i + 1).map(|next| next.timestamp),
+                configuration: entry.configuration.as_ref(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, delay_ms: Option<f32>) -> ConfigHistoryEntry {
+        ConfigHistoryEntry {
+            timestamp,
+            configuration: delay_ms.map(|delay_ms| TcConfiguration {
+                delay_ms: Some(delay_ms),
+                ..TcConfiguration::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_periods_run_until_the_next_entry() {
+        let response = ConfigHistoryResponse {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            entries: vec![entry(36_000, Some(100.0)), entry(36_300, None)],
+        };
+
+        let periods = response.periods();
+        assert_eq!(periods.len(), 2);
+        assert_eq!((periods[0].from, periods[0].until), (36_000, Some(36_300)));
+        assert_eq!(
+            periods[0].configuration.and_then(|c| c.delay_ms),
+            Some(100.0)
+        );
+        assert_eq!((periods[1].from, periods[1].until), (36_300, None));
+        assert!(periods[1].configuration.is_none());
+    }
+
+    #[test]
+    fn test_limit_is_optional_on_the_wire() {
+        let request: ConfigHistoryRequest =
+            serde_json::from_str(r#"{"namespace":"default","interface":"eth0"}"#).unwrap();
+        assert_eq!(request.limit, None);
+        assert!(!serde_json::to_string(&request).unwrap().contains("limit"));
+    }
+}
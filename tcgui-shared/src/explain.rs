@@ -0,0 +1,358 @@
+//! Plain-English descriptions of netem configurations.
+//!
+//! Turns a configuration into a sentence such as "Adds 100ms ± 10ms of
+//! latency (uniformly distributed), drops 5% of packets in bursts (25%
+//! correlated), and caps throughput at 1 Mbit/s." for users who do not read
+//! `tc` syntax. Impairments are described in the order a packet meets them
+//! from the user's point of view: latency first, throughput cap last.
+//!
+//! Jitter is described as uniformly distributed unless the configuration
+//! names one of the `distribution` tables.
+
+use crate::{DelayDistribution, RateLimitMode, TcConfiguration, TcNetemConfig};
+
+/// Describe a configuration as reported by the backend or built by the UI.
+pub fn explain(config: &TcConfiguration) -> String {
+    fn active(value: Option<f32>) -> Option<f32> {
+        value.filter(|v| *v > 0.0)
+    }
+
+    let mut clauses = Vec::new();
+
+    let delay = active(config.delay_ms);
+    if let Some(delay) = delay {
+        clauses.push(match active(config.delay_jitter_ms) {
+            Some(jitter) => {
+                let shape = match config.delay_distribution.unwrap_or_default() {
+                    DelayDistribution::Uniform => "uniformly distributed",
+                    DelayDistribution::Normal => "normally distributed",
+                    DelayDistribution::Pareto => "Pareto distributed",
+                    DelayDistribution::ParetoNormal => "Pareto-normal distributed",
+                };
+                let spread = match active(config.delay_correlation) {
+                    Some(corr) => format!(
+                        "{}, each delay {}% correlated with the previous one",
+                        shape, corr
+                    ),
+                    None => shape.to_string(),
+                };
+                format!("adds {}ms ± {}ms of latency ({})", delay, jitter, spread)
+            }
+            None => format!("adds {}ms of latency", delay),
+        });
+    }
+
+    // With ECN, netem marks the ECN-capable packets it picks and drops the rest
+    let drops = if config.loss_ecn {
+        "drops or ECN-marks"
+    } else {
+        "drops"
+    };
+    if let Some(model) = config.loss_gemodel {
+        clauses.push(format!(
+            "{} about {}% of packets in bursts (Gilbert-Elliott: {}% lost in bad \
+             stretches averaging {} packets, {}% otherwise)",
+            drops,
+            round(model.average_loss()),
+            model.one_h,
+            round(model.mean_burst_length()),
+            model.one_k
+        ));
+    } else if config.loss > 0.0 {
+        clauses.push(match active(config.correlation) {
+            Some(corr) => format!(
+                "{} {}% of packets in bursts ({}% correlated)",
+                drops, config.loss, corr
+            ),
+            None => format!("{} {}% of packets", drops, config.loss),
+        });
+    }
+
+    if let Some(duplicate) = active(config.duplicate_percent) {
+        clauses.push(format!(
+            "duplicates {}% of packets{}",
+            duplicate,
+            correlated(active(config.duplicate_correlation))
+        ));
+    }
+
+    let reorder = active(config.reorder_percent);
+    if let Some(reorder) = reorder {
+        let which = match config.reorder_gap.filter(|g| *g > 1) {
+            Some(gap) => format!("every {} packet {}% of the time", ordinal(gap), reorder),
+            None => format!("{}% of packets", reorder),
+        };
+        clauses.push(format!(
+            "sends {} immediately, ahead of the delayed ones{}",
+            which,
+            correlated(active(config.reorder_correlation))
+        ));
+    }
+
+    if let Some(corrupt) = active(config.corrupt_percent) {
+        let detail = match active(config.corrupt_correlation) {
+            Some(corr) => format!("one random bit flipped, {}% correlated", corr),
+            None => "one random bit flipped".to_string(),
+        };
+        clauses.push(format!("corrupts {}% of packets ({})", corrupt, detail));
+    }
+
+    if let Some(slot) = config.slot.as_ref().filter(|s| s.max_delay_ms > 0.0) {
+        let wait = if slot.min_delay_ms == slot.max_delay_ms {
+            format!("every {}ms", slot.max_delay_ms)
+        } else {
+            format!("every {}-{}ms", slot.min_delay_ms, slot.max_delay_ms)
+        };
+        let limits: Vec<String> = [
+            slot.packets.map(|p| format!("{} packets", p)),
+            slot.bytes.map(|b| format!("{} bytes", b)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        clauses.push(match limits.as_slice() {
+            [] => format!("delivers packets in bursts {}", wait),
+            _ => format!(
+                "delivers packets in bursts {} (at most {} each)",
+                wait,
+                limits.join(" and ")
+            ),
+        });
+    }
+
+    if let Some(rate) = config.rate_limit_kbps.filter(|r| *r > 0) {
+        clauses.push(match config.rate_limit_shaper {
+            RateLimitMode::Netem => format!("caps throughput at {}", format_rate(rate)),
+            RateLimitMode::Htb => format!(
+                "caps throughput at {}, queueing the excess like a bottleneck link",
+                format_rate(rate)
+            ),
+        });
+    }
+
+    if clauses.is_empty() {
+        return "Passes traffic through unchanged.".to_string();
+    }
+
+    let mut sentence = capitalize(&join_clauses(&clauses));
+    sentence.push('.');
+    if reorder.is_some() && delay.is_none() {
+        sentence.push_str(" Reordering has no effect until a delay is added.");
+    }
+    sentence
+}
+
+/// Describe a configuration as edited in the UI or stored in a scenario step.
+/// Disabled features are left out even if they carry values.
+pub fn explain_netem(config: &TcNetemConfig) -> String {
+    let on = |enabled: bool, value: f32| (enabled && value > 0.0).then_some(value);
+    let reorder = on(config.reorder.enabled, config.reorder.percentage);
+
+    explain(&TcConfiguration {
+        loss: on(config.loss.enabled, config.loss.percentage).unwrap_or(0.0),
+        correlation: on(config.loss.enabled, config.loss.correlation),
+        loss_gemodel: config.loss.model.gemodel().filter(|_| config.loss.enabled),
+        loss_ecn: config.loss.enabled && config.loss.ecn,
+        seed: config.seed,
+        delay_ms: on(config.delay.enabled, config.delay.base_ms),
+        delay_jitter_ms: on(config.delay.enabled, config.delay.jitter_ms),
+        delay_correlation: on(config.delay.enabled, config.delay.correlation),
+        delay_distribution: Some(config.delay.distribution).filter(|d| !d.is_uniform()),
+        duplicate_percent: on(config.duplicate.enabled, config.duplicate.percentage),
+        duplicate_correlation: on(config.duplicate.enabled, config.duplicate.correlation),
+        reorder_percent: reorder,
+        reorder_correlation: on(config.reorder.enabled, config.reorder.correlation),
+        reorder_gap: reorder.map(|_| config.reorder.gap),
+        corrupt_percent: on(config.corrupt.enabled, config.corrupt.percentage),
+        corrupt_correlation: on(config.corrupt.enabled, config.corrupt.correlation),
+        rate_limit_kbps: (config.rate_limit.enabled && config.rate_limit.rate_kbps > 0)
+            .then_some(config.rate_limit.rate_kbps),
+        rate_limit_shaper: if config.rate_limit.enabled {
+            config.rate_limit.shaper
+        } else {
+            RateLimitMode::Netem
+        },
+        slot: config.slot.is_active().then(|| config.slot.clone()),
+        command: String::new(),
+    })
+}
+
+/// `value` to one decimal place
+fn round(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+fn correlated(correlation: Option<f32>) -> String {
+    correlation.map_or_else(String::new, |corr| format!(" ({}% correlated)", corr))
+}
+
+/// "A", "A and B", "A, B, and C"
+fn join_clauses(clauses: &[String]) -> String {
+    match clauses {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Rate in the largest unit that keeps it readable, e.g. "1.5 Mbit/s".
+fn format_rate(kbps: u32) -> String {
+    let scaled = |divisor: f64| (f64::from(kbps) / divisor * 100.0).round() / 100.0;
+    if kbps >= 1_000_000 {
+        format!("{} Gbit/s", scaled(1_000_000.0))
+    } else if kbps >= 1000 {
+        format!("{} Mbit/s", scaled(1000.0))
+    } else {
+        format!("{} kbit/s", kbps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_single_features() {
+        assert_eq!(
+            explain(&TcConfiguration::default()),
+            "Passes traffic through unchanged."
+        );
+        assert_eq!(
+            explain(&TcConfiguration {
+                delay_ms: Some(50.0),
+                ..Default::default()
+            }),
+            "Adds 50ms of latency."
+        );
+        assert_eq!(
+            explain(&TcConfiguration {
+                rate_limit_kbps: Some(512),
+                ..Default::default()
+            }),
+            "Caps throughput at 512 kbit/s."
+        );
+        assert_eq!(
+            explain(&TcConfiguration {
+                corrupt_percent: Some(0.1),
+                ..Default::default()
+            }),
+            "Corrupts 0.1% of packets (one random bit flipped)."
+        );
+        assert_eq!(
+            explain(&TcConfiguration {
+                slot: Some(crate::TcSlotConfig {
+                    enabled: true,
+                    min_delay_ms: 10.0,
+                    max_delay_ms: 20.0,
+                    packets: Some(32),
+                    bytes: Some(1500),
+                }),
+                ..Default::default()
+            }),
+            "Delivers packets in bursts every 10-20ms (at most 32 packets and 1500 bytes each)."
+        );
+    }
+
+    #[test]
+    fn test_explain_loss_ecn() {
+        assert_eq!(
+            explain(&TcConfiguration {
+                loss: 2.0,
+                loss_ecn: true,
+                ..Default::default()
+            }),
+            "Drops or ECN-marks 2% of packets."
+        );
+    }
+
+    #[test]
+    fn test_explain_combined_config_with_correlations() {
+        let config = TcConfiguration {
+            loss: 5.0,
+            correlation: Some(25.0),
+            delay_ms: Some(100.0),
+            delay_jitter_ms: Some(10.0),
+            rate_limit_kbps: Some(1000),
+            ..Default::default()
+        };
+        assert_eq!(
+            explain(&config),
+            "Adds 100ms ± 10ms of latency (uniformly distributed), drops 5% of packets \
+             in bursts (25% correlated), and caps throughput at 1 Mbit/s."
+        );
+
+        let config = TcConfiguration {
+            delay_ms: Some(20.0),
+            delay_jitter_ms: Some(5.0),
+            delay_correlation: Some(50.0),
+            duplicate_percent: Some(1.0),
+            duplicate_correlation: Some(10.0),
+            reorder_percent: Some(25.0),
+            reorder_gap: Some(5),
+            corrupt_percent: Some(2.0),
+            corrupt_correlation: Some(30.0),
+            rate_limit_kbps: Some(1500),
+            ..Default::default()
+        };
+        assert_eq!(
+            explain(&config),
+            "Adds 20ms ± 5ms of latency (uniformly distributed, each delay 50% correlated \
+             with the previous one), duplicates 1% of packets (10% correlated), sends every \
+             5th packet 25% of the time immediately, ahead of the delayed ones, corrupts 2% \
+             of packets (one random bit flipped, 30% correlated), and caps throughput at \
+             1.5 Mbit/s."
+        );
+
+        let normal = TcConfiguration {
+            delay_ms: Some(100.0),
+            delay_jitter_ms: Some(20.0),
+            delay_distribution: Some(DelayDistribution::Normal),
+            ..Default::default()
+        };
+        assert_eq!(
+            explain(&normal),
+            "Adds 100ms ± 20ms of latency (normally distributed)."
+        );
+    }
+
+    #[test]
+    fn test_explain_netem_skips_disabled_features_and_flags_reorder_without_delay() {
+        let mut config = TcNetemConfig::new();
+        config.loss.percentage = 10.0; // not enabled
+        config.reorder.enabled = true;
+        config.reorder.percentage = 30.0;
+        config.reorder.correlation = 40.0;
+        config.reorder.gap = 1;
+        config.duplicate.enabled = true;
+        config.duplicate.percentage = 2.0;
+
+        assert_eq!(
+            explain_netem(&config),
+            "Duplicates 2% of packets and sends 30% of packets immediately, ahead of the \
+             delayed ones (40% correlated). Reordering has no effect until a delay is added."
+        );
+        assert_eq!(format_rate(2_500_000), "2.5 Gbit/s");
+        assert_eq!(ordinal(2), "2nd");
+        assert_eq!(ordinal(12), "12th");
+    }
+}
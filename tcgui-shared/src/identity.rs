@@ -54,6 +54,13 @@ pub fn local_origin_from_seed(seed: &str) -> LocalOrigin {
     LocalOrigin::from_seed(seed, PROFILE.salt())
 }
 
+/// Source info naming `session` as the sender of a query. Backends keep one
+/// query rate-limit bucket per querying session, keyed on this; a query sent
+/// without it is charged to the bucket shared by all anonymous queriers.
+pub fn querier_info(session: &zenoh::Session) -> zenoh::sample::SourceInfo {
+    zenoh::sample::SourceInfo::new(session.id(), 0)
+}
+
 /// Read a stable per-host seed for the origin hash: the machine id if available,
 /// otherwise a persisted random id, otherwise the hostname (last resort).
 ///
@@ -41,13 +41,19 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use zenoh::config::WhatAmI;
 
+pub mod config_history;
 pub mod errors;
+pub mod explain;
 pub mod identity;
+pub mod preset_import;
 pub mod preset_json;
 pub mod presets;
+pub mod qdisc_tree;
+pub mod rate_comparison;
 pub mod registry;
 pub mod scenario;
 pub mod scenario_json;
+pub mod shorthand;
 pub mod validation;
 
 /// keyspace-v2 key expressions: `tcgui/v1/<origin>/<class>/tc/<subject…>`.
@@ -155,6 +161,34 @@ pub mod topics {
         };
         parsed.subject.first().copied()
     }
+
+    /// The registered procedure a `v1/<origin>/@rpc/tc/…` call key names.
+    pub fn parse_procedure(key: &str) -> Option<tc::ProcedureId> {
+        let parsed = zenkey::grammar::parse(relative(key)).ok()?;
+        let zenkey::ClassOrPlane::Plane(zenkey::Plane::Rpc) = parsed.class else {
+            return None;
+        };
+        if parsed.producer.as_ref()?.name() != "tc" {
+            return None;
+        }
+        tc::ProcedureId::ALL.iter().copied().find(|procedure| {
+            let chunks = procedure.chunks();
+            chunks.len() == parsed.subject.len()
+                && chunks
+                    .iter()
+                    .zip(&parsed.subject)
+                    .all(|(pattern, chunk)| pattern.starts_with('{') || pattern == chunk)
+        })
+    }
+
+    /// The registered payload type carried on a key: the subject's type for
+    /// `state`/`telemetry` keys, the reply type for `@rpc` keys.
+    pub fn payload_type(key: &str) -> Option<&'static str> {
+        if let Some(procedure) = parse_procedure(key) {
+            return procedure.reply_type();
+        }
+        crate::registry::refine_key(relative(key)).map(|refined| refined.subject.payload_type())
+    }
 }
 
 /// Producer registration document for the `state/tc/sensor` subject.
@@ -207,10 +241,20 @@ pub mod schema {
             .json::<crate::TcStatisticsUpdate>("TcStatisticsUpdate")
             .json::<crate::TcAppliedEvent>("TcAppliedEvent")
             .json::<crate::TcResponse>("TcResponse")
+            .json::<crate::config_history::ConfigHistoryResponse>("ConfigHistoryResponse")
+            .json::<crate::StatsResponse>("StatsResponse")
             .json::<crate::InterfaceControlResponse>("InterfaceControlResponse")
+            .json::<crate::InterfaceControlBatchResponse>("InterfaceControlBatchResponse")
+            .json::<crate::NetworkOperationResponse>("NetworkOperationResponse")
+            .json::<crate::TcFeatureClearResponse>("TcFeatureClearResponse")
             .json::<crate::scenario::ScenarioResponse>("ScenarioResponse")
             .json::<crate::scenario::ScenarioExecutionResponse>("ScenarioExecutionResponse")
             .json::<crate::DiagnosticsResponse>("DiagnosticsResponse")
+            .json::<crate::NamespaceDiagnosticsResponse>("NamespaceDiagnosticsResponse")
+            .json::<crate::qdisc_tree::TcTreeResponse>("TcTreeResponse")
+            .json::<crate::rate_comparison::RateComparisonResponse>("RateComparisonResponse")
+            .json::<crate::NetemCapabilities>("NetemCapabilities")
+            .json::<crate::ForceRefreshResponse>("ForceRefreshResponse")
             // The describe reply's own envelope: a meta entry so the type
             // table stays total (the real schema is RFC 08 §7's shape).
             .entry(
@@ -262,6 +306,30 @@ pub struct BandwidthUpdate {
     pub backend_name: String,
 }
 
+/// Current statistics of one interface, sampled on demand (Query)
+/// Query Service: bandwidth/{ns}/{iface}/snapshot
+///
+/// For clients that want one reading instead of subscribing to the
+/// bandwidth stream. An interface the backend doesn't know is answered on
+/// the reply-error channel.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct StatsRequest {
+    /// Target network namespace
+    pub namespace: String,
+    /// Target interface name
+    pub interface: String,
+}
+
+/// Current statistics of one interface (Reply)
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct StatsResponse {
+    pub namespace: String,
+    pub interface: String,
+    /// Counters read when the query arrived; the rates are those of the
+    /// latest bandwidth sample (zero before the first one)
+    pub stats: NetworkBandwidthStats,
+}
+
 /// Interface state change event (pub/sub)
 /// Topic: tcgui/{backend_name}/interfaces/events
 /// QoS: Reliable delivery, history depth=10
@@ -302,6 +370,17 @@ pub struct BackendHealthStatus {
     pub namespace_count: usize,
     /// Number of managed interfaces across all namespaces
     pub interface_count: usize,
+    /// Namespaces whose interface discovery failed during the last refresh.
+    /// Their interfaces keep their last known state until discovery recovers.
+    #[serde(default)]
+    pub failed_namespaces: Vec<String>,
+    /// Number of Zenoh peers and routers connected to this backend's session
+    #[serde(default)]
+    pub connected_peers: usize,
+    /// Zenoh ids of the connected peers and routers. Only populated when the backend runs
+    /// with `--expose-peer-info`; otherwise observers are reported as a count.
+    #[serde(default)]
+    pub peer_ids: Vec<String>,
 }
 
 /// Traffic Control configuration status (pub/sub)
@@ -345,6 +424,30 @@ pub struct TcStatsQueue {
     pub requeues: u32,
     /// Total overlimit events (rate limiting triggered)
     pub overlimits: u32,
+    /// Configured queue limit in packets (netem `limit`), if known
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+impl TcStatsQueue {
+    /// Queue fill ratio at or above which drops are considered imminent.
+    pub const NEAR_LIMIT_RATIO: f32 = 0.8;
+
+    /// Fraction of the queue limit currently occupied (`qlen / limit`).
+    ///
+    /// Returns `None` when the limit is unknown or zero.
+    pub fn fill_ratio(&self) -> Option<f32> {
+        match self.limit {
+            Some(limit) if limit > 0 => Some(self.qlen as f32 / limit as f32),
+            _ => None,
+        }
+    }
+
+    /// Whether the backlog is close enough to the limit that tail drops are imminent.
+    pub fn is_near_limit(&self) -> bool {
+        self.fill_ratio()
+            .is_some_and(|ratio| ratio >= Self::NEAR_LIMIT_RATIO)
+    }
 }
 
 /// Rate estimator statistics from TC qdisc
@@ -387,8 +490,34 @@ pub struct TcRequest {
     pub interface: String,
     /// TC operation to perform
     pub operation: TcOperation,
+    /// Also perform the operation on the other end of the veth pair, when
+    /// the backend knows it
+    #[serde(default)]
+    pub apply_to_peer: bool,
+    /// The user confirmed impairing an interface that carries the backend's
+    /// own Zenoh traffic (see [`NetworkInterface::management`]); without it
+    /// the backend refuses anything but removal on such an interface
+    #[serde(default)]
+    pub confirm_management: bool,
+    /// Perform the operation at this Unix time (milliseconds, backend clock)
+    /// instead of on receipt, so several backends can change in lockstep.
+    /// A time already past is performed immediately; see
+    /// [`TcResponse::scheduled_at`] and [`MAX_APPLY_AHEAD_MS`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apply_at: Option<u64>,
+    /// Limit a netem apply to matching flows instead of all traffic on the
+    /// interface
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<TcFilterSpec>,
+    /// Which way a netem apply impairs traffic; the other direction is
+    /// cleared. Older clients only know egress
+    #[serde(default)]
+    pub direction: TcDirection,
 }
 
+/// How far ahead a [`TcRequest::apply_at`] may be (one hour)
+pub const MAX_APPLY_AHEAD_MS: u64 = 60 * 60 * 1000;
+
 /// Structured TC configuration for all netem features
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct TcNetemConfig {
@@ -398,6 +527,13 @@ pub struct TcNetemConfig {
     pub reorder: TcReorderConfig,
     pub corrupt: TcCorruptConfig,
     pub rate_limit: TcRateLimitConfig,
+    #[serde(default)]
+    pub slot: TcSlotConfig,
+    /// Seed for netem's random choices (loss, duplication, corruption,
+    /// reordering and jitter), so a run can be repeated exactly. Without
+    /// one the kernel picks a fresh seed on every change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 /// Packet loss configuration
@@ -406,6 +542,129 @@ pub struct TcLossConfig {
     pub enabled: bool,
     pub percentage: f32,  // 0.0-100.0
     pub correlation: f32, // 0.0-100.0
+    /// How lost packets are picked; `percentage` and `correlation` only
+    /// apply to the random model
+    #[serde(default, skip_serializing_if = "LossModel::is_random")]
+    pub model: LossModel,
+    /// Mark ECN-capable packets instead of dropping them (`loss ... ecn`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ecn: bool,
+}
+
+impl TcLossConfig {
+    /// Whether this drops any packets: enabled with a non-zero percentage,
+    /// or enabled with a state-based model.
+    pub fn is_active(&self) -> bool {
+        self.enabled && (self.percentage > 0.0 || self.model.gemodel().is_some())
+    }
+}
+
+/// Which packets netem drops
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LossModel {
+    /// Each packet independently, with the loss percentage (`loss random`)
+    #[default]
+    Random,
+    /// Bursts, from a two-state Gilbert-Elliott chain (`loss gemodel`)
+    GilbertElliott(GeModel),
+}
+
+impl LossModel {
+    /// Whether this is netem's default model
+    pub fn is_random(&self) -> bool {
+        *self == LossModel::Random
+    }
+
+    /// The Gilbert-Elliott parameters, if this is that model
+    pub fn gemodel(&self) -> Option<GeModel> {
+        match self {
+            LossModel::Random => None,
+            LossModel::GilbertElliott(model) => Some(*model),
+        }
+    }
+}
+
+/// Gilbert-Elliott loss model, in percent. The link moves between a good
+/// and a bad state and drops packets at a different rate in each, which
+/// gives the bursty loss of cellular and satellite links.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(default)]
+pub struct GeModel {
+    /// Chance per packet of going from the good to the bad state
+    pub p: f32,
+    /// Chance per packet of going from the bad back to the good state
+    pub r: f32,
+    /// Loss in the bad state (`1-h`)
+    pub one_h: f32,
+    /// Loss in the good state (`1-k`)
+    pub one_k: f32,
+}
+
+impl Default for GeModel {
+    /// The simple Gilbert model: rare bursts of about four packets, every
+    /// packet of a burst lost
+    fn default() -> Self {
+        Self {
+            p: 1.0,
+            r: 25.0,
+            one_h: 100.0,
+            one_k: 0.0,
+        }
+    }
+}
+
+impl GeModel {
+    /// Long-run share of packets lost, in percent
+    pub fn average_loss(&self) -> f32 {
+        let bad = self.p / (self.p + self.r);
+        bad * self.one_h + (1.0 - bad) * self.one_k
+    }
+
+    /// Average number of packets the link stays in the bad state
+    pub fn mean_burst_length(&self) -> f32 {
+        100.0 / self.r
+    }
+
+    /// The `loss` clause `tc` takes for this model
+    pub fn clause(&self) -> String {
+        format!(
+            "loss gemodel {}% {}% {}% {}%",
+            self.p, self.r, self.one_h, self.one_k
+        )
+    }
+}
+
+impl TcValidate for GeModel {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        for (field, name, value) in [
+            ("p", "p", self.p),
+            ("r", "r", self.r),
+            ("one_h", "1-h", self.one_h),
+            ("one_k", "1-k", self.one_k),
+        ] {
+            if !(0.0..=100.0).contains(&value) {
+                return Err(TcValidationError {
+                    field: field.to_string(),
+                    message: format!("Gilbert-Elliott {} must be 0.0-100.0, got {}", name, value),
+                });
+            }
+        }
+        for (field, value, state) in [("p", self.p, "bad"), ("r", self.r, "good")] {
+            if value == 0.0 {
+                return Err(TcValidationError {
+                    field: field.to_string(),
+                    message: format!(
+                        "Gilbert-Elliott {} must be above 0, or the link never enters the {} state",
+                        field, state
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Network delay configuration
@@ -415,6 +674,77 @@ pub struct TcDelayConfig {
     pub base_ms: f32,     // 0.0-5000.0
     pub jitter_ms: f32,   // 0.0-1000.0
     pub correlation: f32, // 0.0-100.0
+    /// How the jitter is spread around `base_ms`
+    #[serde(default, skip_serializing_if = "DelayDistribution::is_uniform")]
+    pub distribution: DelayDistribution,
+}
+
+/// Shape of the delay jitter: netem's built-in uniform spread, or one of the
+/// distribution tables iproute2 ships (`/usr/lib/tc/<name>.dist`).
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DelayDistribution {
+    #[default]
+    Uniform,
+    Normal,
+    Pareto,
+    ParetoNormal,
+}
+
+impl DelayDistribution {
+    /// Every distribution, in the order the GUI lists them
+    pub const ALL: [DelayDistribution; 4] = [
+        DelayDistribution::Uniform,
+        DelayDistribution::Normal,
+        DelayDistribution::Pareto,
+        DelayDistribution::ParetoNormal,
+    ];
+
+    /// Name as `tc` spells it, which is also the table's file name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DelayDistribution::Uniform => "uniform",
+            DelayDistribution::Normal => "normal",
+            DelayDistribution::Pareto => "pareto",
+            DelayDistribution::ParetoNormal => "paretonormal",
+        }
+    }
+
+    /// Whether this is netem's default, which needs no table.
+    pub fn is_uniform(&self) -> bool {
+        *self == DelayDistribution::Uniform
+    }
+
+    /// Whether the distribution only takes effect with a jitter to spread:
+    /// every table scales by the jitter, so without one it does nothing.
+    pub fn requires_jitter(&self) -> bool {
+        !self.is_uniform()
+    }
+}
+
+impl std::fmt::Display for DelayDistribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for DelayDistribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|d| d.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown delay distribution '{}'. Must be one of: {}",
+                    s,
+                    Self::ALL.map(|d| d.name()).join(", ")
+                )
+            })
+    }
 }
 
 /// Packet duplication configuration
@@ -447,149 +777,684 @@ pub struct TcCorruptConfig {
 pub struct TcRateLimitConfig {
     pub enabled: bool,
     pub rate_kbps: u32, // 1-1000000
+    /// How the rate is given; anything but [`TcRateLimitMode::Absolute`] is
+    /// resolved to `rate_kbps` by the backend when applying
+    #[serde(default, skip_serializing_if = "TcRateLimitMode::is_absolute")]
+    pub mode: TcRateLimitMode,
+    /// Qdisc layout that shapes the rate
+    #[serde(default, skip_serializing_if = "RateLimitMode::is_netem")]
+    pub shaper: RateLimitMode,
 }
 
-/// Validation trait for TC configuration structs
-pub trait TcValidate {
-    type Error: std::fmt::Display + std::fmt::Debug;
-    fn validate(&self) -> Result<(), Self::Error>;
+/// How the rate of a [`TcRateLimitConfig`] is expressed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TcRateLimitMode {
+    /// `rate_kbps` is the rate
+    #[default]
+    Absolute,
+    /// A percentage (above 0, at most 100) of the interface's link speed
+    PercentOfLinkSpeed(f32),
 }
 
-/// Validation error for TC configurations
-#[derive(Debug, Clone)]
-pub struct TcValidationError {
-    pub field: String,
-    pub message: String,
+impl TcRateLimitMode {
+    /// Whether the rate is given directly in kbps.
+    pub fn is_absolute(&self) -> bool {
+        matches!(self, TcRateLimitMode::Absolute)
+    }
 }
 
-impl std::fmt::Display for TcValidationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Validation error in field '{}': {}",
-            self.field, self.message
-        )
-    }
+/// How a rate limit is shaped.
+///
+/// netem's own `rate` delays each packet by its serialisation time, which
+/// doesn't model a bottleneck's buffer and adds to any configured delay.
+/// HTB shapes like a real link: an HTB root qdisc with a single class at the
+/// rate, and netem attached below the class for the other impairments.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitMode {
+    /// netem's `rate` (or a TBF root when nothing else is configured)
+    #[default]
+    Netem,
+    /// An HTB class with netem as its leaf qdisc
+    Htb,
 }
 
-impl std::error::Error for TcValidationError {}
+impl RateLimitMode {
+    /// Every mode, in the order the GUI lists them
+    pub const ALL: [RateLimitMode; 2] = [RateLimitMode::Netem, RateLimitMode::Htb];
 
-impl TcValidate for TcLossConfig {
-    type Error = TcValidationError;
+    /// Whether the rate is shaped by netem itself.
+    pub fn is_netem(&self) -> bool {
+        matches!(self, RateLimitMode::Netem)
+    }
 
-    fn validate(&self) -> Result<(), Self::Error> {
-        if self.percentage < 0.0 || self.percentage > 100.0 {
-            return Err(TcValidationError {
-                field: "percentage".to_string(),
-                message: format!("Loss percentage must be 0.0-100.0, got {}", self.percentage),
-            });
-        }
-        if self.correlation < 0.0 || self.correlation > 100.0 {
-            return Err(TcValidationError {
-                field: "correlation".to_string(),
-                message: format!(
-                    "Loss correlation must be 0.0-100.0, got {}",
-                    self.correlation
-                ),
-            });
+    /// Name shown in the GUI
+    pub fn label(&self) -> &'static str {
+        match self {
+            RateLimitMode::Netem => "netem",
+            RateLimitMode::Htb => "HTB + netem",
         }
-        Ok(())
     }
 }
 
-impl TcValidate for TcDelayConfig {
-    type Error = TcValidationError;
+impl std::fmt::Display for RateLimitMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
 
-    fn validate(&self) -> Result<(), Self::Error> {
-        if self.base_ms < 0.0 || self.base_ms > 5000.0 {
-            return Err(TcValidationError {
-                field: "base_ms".to_string(),
-                message: format!("Delay base must be 0.0-5000.0ms, got {}", self.base_ms),
-            });
-        }
-        if self.jitter_ms < 0.0 || self.jitter_ms > 1000.0 {
-            return Err(TcValidationError {
-                field: "jitter_ms".to_string(),
-                message: format!("Delay jitter must be 0.0-1000.0ms, got {}", self.jitter_ms),
-            });
+impl TcRateLimitConfig {
+    /// This config with the rate given in kbps, resolving a percentage
+    /// against `link_speed_mbps`. Fails when the link speed is needed but
+    /// unknown.
+    pub fn resolve(&self, link_speed_mbps: Option<u32>) -> Result<Self, TcValidationError> {
+        let TcRateLimitMode::PercentOfLinkSpeed(percent) = self.mode else {
+            return Ok(self.clone());
+        };
+        if !self.enabled {
+            return Ok(self.clone());
         }
-        if self.correlation < 0.0 || self.correlation > 100.0 {
+        let Some(mbps) = link_speed_mbps.filter(|m| *m > 0) else {
             return Err(TcValidationError {
-                field: "correlation".to_string(),
+                field: "mode".to_string(),
                 message: format!(
-                    "Delay correlation must be 0.0-100.0, got {}",
-                    self.correlation
+                    "Rate limit of {}% of link speed needs the link speed, which is unknown for this interface",
+                    percent
                 ),
             });
-        }
-        Ok(())
+        };
+        let rate_kbps = (f64::from(mbps) * 1000.0 * f64::from(percent) / 100.0).round();
+        Ok(Self {
+            enabled: self.enabled,
+            rate_kbps: (rate_kbps as u32).max(1),
+            mode: TcRateLimitMode::Absolute,
+            shaper: self.shaper,
+        })
     }
 }
 
-impl TcValidate for TcDuplicateConfig {
-    type Error = TcValidationError;
+/// Slotted delivery: netem holds packets back and releases them in bursts,
+/// the way 802.11 aggregation or an LTE scheduler delivers them. A slot
+/// opens after a random wait between `min_delay_ms` and `max_delay_ms` and
+/// releases at most `packets` packets and `bytes` bytes; the rest wait for
+/// the next slot.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcSlotConfig {
+    pub enabled: bool,
+    pub min_delay_ms: f32, // 0.0-5000.0
+    pub max_delay_ms: f32, // min_delay_ms-5000.0
+    /// Packets released per slot (no limit when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packets: Option<u32>,
+    /// Bytes released per slot (no limit when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u32>,
+}
 
-    fn validate(&self) -> Result<(), Self::Error> {
-        if self.percentage < 0.0 || self.percentage > 100.0 {
-            return Err(TcValidationError {
-                field: "percentage".to_string(),
-                message: format!(
-                    "Duplicate percentage must be 0.0-100.0, got {}",
-                    self.percentage
-                ),
-            });
+impl TcSlotConfig {
+    /// Upper bound for the slot delays, the same as for the base delay
+    pub const MAX_DELAY_MS: f32 = 5000.0;
+
+    /// A starting point for the GUI: 802.11-like aggregation of up to 32
+    /// packets every 10-20ms.
+    pub fn wifi_default() -> Self {
+        Self {
+            enabled: false,
+            min_delay_ms: 10.0,
+            max_delay_ms: 20.0,
+            packets: Some(32),
+            bytes: None,
         }
-        if self.correlation < 0.0 || self.correlation > 100.0 {
-            return Err(TcValidationError {
-                field: "correlation".to_string(),
-                message: format!(
-                    "Duplicate correlation must be 0.0-100.0, got {}",
-                    self.correlation
-                ),
-            });
+    }
+
+    /// Whether this shapes delivery: enabled with slots of some length.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.max_delay_ms > 0.0
+    }
+
+    /// The `slot` clause `tc` takes for this config
+    pub fn clause(&self) -> String {
+        let mut clause = format!("slot {}ms {}ms", self.min_delay_ms, self.max_delay_ms);
+        if let Some(packets) = self.packets {
+            clause.push_str(&format!(" packets {}", packets));
         }
-        Ok(())
+        if let Some(bytes) = self.bytes {
+            clause.push_str(&format!(" bytes {}", bytes));
+        }
+        clause
     }
 }
 
-impl TcValidate for TcReorderConfig {
-    type Error = TcValidationError;
+/// Random early detection (RED) parameters: an AQM qdisc that starts
+/// dropping packets at random once the average queue grows past `min` bytes,
+/// with a drop chance rising to `probability` at `max` bytes. Installed as
+/// the root qdisc in place of netem, for testing how traffic reacts to AQM.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RedParams {
+    /// Hard queue limit in bytes (required)
+    pub limit: Option<u32>,
+    /// Average queue size in bytes at which marking starts
+    pub min: Option<u32>,
+    /// Average queue size in bytes at which marking reaches `probability`
+    pub max: Option<u32>,
+    /// Average packet size in bytes (required)
+    pub avpkt: Option<u32>,
+    /// Packets a burst may hold, for the average queue computation
+    pub burst: Option<u32>,
+    /// Marking probability at `max` (0.0-1.0)
+    pub probability: Option<f32>,
+    /// Link bandwidth as `tc` takes it, e.g. "10mbit"
+    pub bandwidth: Option<String>,
+}
 
-    fn validate(&self) -> Result<(), Self::Error> {
-        if self.enabled {
-            if self.percentage < 0.0 || self.percentage > 100.0 {
-                return Err(TcValidationError {
-                    field: "percentage".to_string(),
-                    message: format!(
-                        "Reorder percentage must be 0.0-100.0, got {}",
-                        self.percentage
-                    ),
-                });
+impl RedParams {
+    /// The `tc` command installing this RED qdisc on `interface`, for display
+    pub fn command(&self, interface: &str) -> String {
+        let mut command = format!("tc qdisc replace dev {} root red", interface);
+        for (name, value) in [
+            ("limit", self.limit.map(|v| v.to_string())),
+            ("min", self.min.map(|v| v.to_string())),
+            ("max", self.max.map(|v| v.to_string())),
+            ("avpkt", self.avpkt.map(|v| v.to_string())),
+            ("burst", self.burst.map(|v| v.to_string())),
+            ("probability", self.probability.map(|v| v.to_string())),
+            ("bandwidth", self.bandwidth.clone()),
+        ] {
+            if let Some(value) = value {
+                command.push_str(&format!(" {} {}", name, value));
             }
-            if self.correlation < 0.0 || self.correlation > 100.0 {
-                return Err(TcValidationError {
-                    field: "correlation".to_string(),
-                    message: format!(
-                        "Reorder correlation must be 0.0-100.0, got {}",
-                        self.correlation
-                    ),
-                });
+        }
+        command
+    }
+}
+
+/// Stochastic fair queueing (SFQ) parameters: packets are hashed into flows
+/// served round robin, so no single flow can take the whole link.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SfqParams {
+    /// Seconds between reseeding the flow hash (0 or unset: never)
+    pub perturb: Option<u32>,
+    /// Bytes a flow may send per round (the MTU when unset)
+    pub quantum: Option<u32>,
+    /// Packets the qdisc may hold in total
+    pub limit: Option<u32>,
+}
+
+impl SfqParams {
+    /// The `tc` command installing this SFQ qdisc on `interface`, for display
+    pub fn command(&self, interface: &str) -> String {
+        let mut command = format!("tc qdisc replace dev {} root sfq", interface);
+        for (name, value) in [
+            ("perturb", self.perturb),
+            ("quantum", self.quantum),
+            ("limit", self.limit),
+        ] {
+            if let Some(value) = value {
+                command.push_str(&format!(" {} {}", name, value));
             }
-            if self.gap == 0 || self.gap > 10 {
-                return Err(TcValidationError {
-                    field: "gap".to_string(),
-                    message: format!("Reorder gap must be 1-10, got {}", self.gap),
-                });
+        }
+        command
+    }
+}
+
+/// Priority (PRIO) qdisc parameters: packets go to one of `bands` bands by
+/// their priority, and a band is only served while the ones before it are
+/// empty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PrioParams {
+    /// Number of bands (2-16, 3 when unset)
+    pub bands: Option<u32>,
+    /// Band for each of the 16 packet priorities, in priority order
+    pub priomap: Option<Vec<u32>>,
+}
+
+impl PrioParams {
+    /// Bounds for [`Self::bands`], as the kernel enforces them
+    pub const MIN_BANDS: u32 = 2;
+    pub const MAX_BANDS: u32 = 16;
+    /// Bands the kernel sets up when none are given
+    pub const DEFAULT_BANDS: u32 = 3;
+    /// Number of packet priorities a priomap covers
+    pub const PRIOMAP_LEN: usize = 16;
+
+    /// The `tc` command installing this PRIO qdisc on `interface`, for display
+    pub fn command(&self, interface: &str) -> String {
+        let mut command = format!("tc qdisc replace dev {} root prio", interface);
+        if let Some(bands) = self.bands {
+            command.push_str(&format!(" bands {}", bands));
+        }
+        if let Some(priomap) = &self.priomap {
+            command.push_str(" priomap");
+            for band in priomap {
+                command.push_str(&format!(" {}", band));
             }
         }
-        Ok(())
+        command
     }
 }
 
-impl TcValidate for TcCorruptConfig {
-    type Error = TcValidationError;
+/// Fair queueing CoDel (fq_codel) parameters: per-flow queues, each kept
+/// short by dropping (or ECN marking) once packets sit longer than `target`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FqCodelParams {
+    /// Acceptable standing queue delay, in microseconds (5ms when unset)
+    pub target_us: Option<u32>,
+    /// Window the delay must stay above target before dropping, in
+    /// microseconds (100ms when unset)
+    pub interval_us: Option<u32>,
+    /// Bytes a flow may dequeue per round (the MTU when unset)
+    pub quantum: Option<u32>,
+    /// Mark ECN-capable packets rather than dropping them (on when unset)
+    pub ecn: Option<bool>,
+}
 
-    fn validate(&self) -> Result<(), Self::Error> {
+impl FqCodelParams {
+    /// The `tc` command installing this fq_codel qdisc on `interface`, for
+    /// display
+    pub fn command(&self, interface: &str) -> String {
+        let mut command = format!("tc qdisc replace dev {} root fq_codel", interface);
+        if let Some(target) = self.target_us {
+            command.push_str(&format!(" target {}us", target));
+        }
+        if let Some(interval) = self.interval_us {
+            command.push_str(&format!(" interval {}us", interval));
+        }
+        if let Some(quantum) = self.quantum {
+            command.push_str(&format!(" quantum {}", quantum));
+        }
+        match self.ecn {
+            Some(true) => command.push_str(" ecn"),
+            Some(false) => command.push_str(" noecn"),
+            None => {}
+        }
+        command
+    }
+}
+
+/// How CAKE sorts traffic into priority tins by its DSCP marking
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CakeDiffserv {
+    /// A single tin: markings are ignored
+    Besteffort,
+    /// Three tins: bulk, best effort and voice (CAKE's default)
+    #[default]
+    Diffserv3,
+    /// Four tins: bulk, best effort, video and voice
+    Diffserv4,
+    /// Eight tins, one per DSCP class selector
+    Diffserv8,
+    /// Eight tins by the legacy IP precedence bits
+    Precedence,
+}
+
+impl CakeDiffserv {
+    /// Name as `tc` spells it
+    pub fn name(&self) -> &'static str {
+        match self {
+            CakeDiffserv::Besteffort => "besteffort",
+            CakeDiffserv::Diffserv3 => "diffserv3",
+            CakeDiffserv::Diffserv4 => "diffserv4",
+            CakeDiffserv::Diffserv8 => "diffserv8",
+            CakeDiffserv::Precedence => "precedence",
+        }
+    }
+}
+
+/// Common Applications Kept Enhanced (CAKE) parameters: a shaper with
+/// per-flow fair queueing and AQM built in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CakeParams {
+    /// Shaped rate in kbit/s (unlimited when unset)
+    pub bandwidth_kbps: Option<u32>,
+    /// Round trip time the AQM is tuned for, in milliseconds (100ms when
+    /// unset)
+    pub rtt_ms: Option<u32>,
+    /// Priority tins to sort traffic into
+    pub diffserv: Option<CakeDiffserv>,
+}
+
+impl CakeParams {
+    /// The shaped rate as `tc` takes it, in whole Mbit when it divides evenly
+    pub fn bandwidth(&self) -> Option<String> {
+        self.bandwidth_kbps.map(|kbps| {
+            if kbps % 1000 == 0 {
+                format!("{}Mbit", kbps / 1000)
+            } else {
+                format!("{}Kbit", kbps)
+            }
+        })
+    }
+
+    /// The `tc` command installing this CAKE qdisc on `interface`, for
+    /// display
+    pub fn command(&self, interface: &str) -> String {
+        let mut command = format!("tc qdisc replace dev {} root cake", interface);
+        if let Some(bandwidth) = self.bandwidth() {
+            command.push_str(&format!(" bandwidth {}", bandwidth));
+        }
+        if let Some(rtt) = self.rtt_ms {
+            command.push_str(&format!(" rtt {}ms", rtt));
+        }
+        if let Some(diffserv) = self.diffserv {
+            command.push_str(&format!(" {}", diffserv.name()));
+        }
+        command
+    }
+}
+
+/// Transport protocol a [`TcFilterSpec`] matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TcFilterProtocol {
+    Tcp,
+    Udp,
+    Icmp,
+}
+
+impl TcFilterProtocol {
+    /// IP protocol (IPv4) or next header (IPv6) number
+    pub fn number(&self, ipv6: bool) -> u8 {
+        match self {
+            TcFilterProtocol::Tcp => 6,
+            TcFilterProtocol::Udp => 17,
+            TcFilterProtocol::Icmp if ipv6 => 58,
+            TcFilterProtocol::Icmp => 1,
+        }
+    }
+}
+
+/// Flows an impairment is limited to. Each field left unset matches
+/// anything; the ones set must all match. Traffic outside the filter passes
+/// unimpaired.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcFilterSpec {
+    /// Source address or CIDR prefix (e.g. "10.0.0.1" or "10.0.0.0/24")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src_ip: Option<String>,
+    /// Destination address or CIDR prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dst_ip: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dst_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<TcFilterProtocol>,
+}
+
+impl TcFilterSpec {
+    /// Parse an address or CIDR prefix, a bare address being a full-length
+    /// prefix
+    pub fn parse_prefix(value: &str) -> Option<(std::net::IpAddr, u8)> {
+        let (addr, len) = match value.split_once('/') {
+            Some((addr, len)) => (addr, Some(len)),
+            None => (value, None),
+        };
+        let addr: std::net::IpAddr = addr.trim().parse().ok()?;
+        let max = if addr.is_ipv6() { 128 } else { 32 };
+        let len = match len {
+            Some(len) => len.trim().parse().ok().filter(|len| *len <= max)?,
+            None => max,
+        };
+        Some((addr, len))
+    }
+
+    /// Whether the filter matches IPv6 rather than IPv4 traffic, as decided
+    /// by its addresses (IPv4 when it has none)
+    pub fn is_ipv6(&self) -> bool {
+        [&self.src_ip, &self.dst_ip]
+            .into_iter()
+            .flatten()
+            .filter_map(|ip| Self::parse_prefix(ip))
+            .any(|(addr, _)| addr.is_ipv6())
+    }
+
+    /// The same flows seen from the other end of a link, sources and
+    /// destinations swapped
+    pub fn reversed(&self) -> Self {
+        Self {
+            src_ip: self.dst_ip.clone(),
+            dst_ip: self.src_ip.clone(),
+            src_port: self.dst_port,
+            dst_port: self.src_port,
+            protocol: self.protocol,
+        }
+    }
+
+    /// The `match` selectors of a `tc filter ... u32` rule for this filter
+    pub fn u32_matches(&self) -> Vec<String> {
+        let ipv6 = self.is_ipv6();
+        let family = if ipv6 { "ip6" } else { "ip" };
+        let mut matches = Vec::new();
+        for (name, ip) in [("src", &self.src_ip), ("dst", &self.dst_ip)] {
+            if let Some((addr, len)) = ip.as_deref().and_then(Self::parse_prefix) {
+                matches.push(format!("match {} {} {}/{}", family, name, addr, len));
+            }
+        }
+        for (name, port) in [("sport", self.src_port), ("dport", self.dst_port)] {
+            if let Some(port) = port {
+                matches.push(format!("match {} {} {} 0xffff", family, name, port));
+            }
+        }
+        if let Some(protocol) = self.protocol {
+            matches.push(format!(
+                "match {} protocol {} 0xff",
+                family,
+                protocol.number(ipv6)
+            ));
+        }
+        matches
+    }
+}
+
+/// Which traffic of an interface a [`TcRequest`] impairs
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TcDirection {
+    /// What the interface sends, shaped by its root qdisc
+    #[default]
+    Egress,
+    /// What the interface receives, redirected through an IFB device and
+    /// shaped on that device's egress
+    Ingress,
+    /// Both ways, with the same settings
+    Both,
+}
+
+impl TcDirection {
+    pub const ALL: [TcDirection; 3] =
+        [TcDirection::Egress, TcDirection::Ingress, TcDirection::Both];
+
+    /// Whether traffic the interface sends is impaired
+    pub fn includes_egress(&self) -> bool {
+        matches!(self, TcDirection::Egress | TcDirection::Both)
+    }
+
+    /// Whether traffic the interface receives is impaired
+    pub fn includes_ingress(&self) -> bool {
+        matches!(self, TcDirection::Ingress | TcDirection::Both)
+    }
+}
+
+impl std::fmt::Display for TcDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TcDirection::Egress => "Egress",
+            TcDirection::Ingress => "Ingress",
+            TcDirection::Both => "Both",
+        })
+    }
+}
+
+/// Validation trait for TC configuration structs
+pub trait TcValidate {
+    type Error: std::fmt::Display + std::fmt::Debug;
+    fn validate(&self) -> Result<(), Self::Error>;
+}
+
+/// Validation error for TC configurations
+#[derive(Debug, Clone)]
+pub struct TcValidationError {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for TcValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Validation error in field '{}': {}",
+            self.field, self.message
+        )
+    }
+}
+
+impl std::error::Error for TcValidationError {}
+
+impl TcValidate for TcLossConfig {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.percentage < 0.0 || self.percentage > 100.0 {
+            return Err(TcValidationError {
+                field: "percentage".to_string(),
+                message: format!("Loss percentage must be 0.0-100.0, got {}", self.percentage),
+            });
+        }
+        if self.correlation < 0.0 || self.correlation > 100.0 {
+            return Err(TcValidationError {
+                field: "correlation".to_string(),
+                message: format!(
+                    "Loss correlation must be 0.0-100.0, got {}",
+                    self.correlation
+                ),
+            });
+        }
+        if self.ecn && !self.enabled {
+            return Err(TcValidationError {
+                field: "ecn".to_string(),
+                message: "ECN marking requires packet loss to be enabled".to_string(),
+            });
+        }
+        if self.enabled
+            && let Some(model) = self.model.gemodel()
+        {
+            model.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for TcDelayConfig {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.base_ms < 0.0 || self.base_ms > 5000.0 {
+            return Err(TcValidationError {
+                field: "base_ms".to_string(),
+                message: format!("Delay base must be 0.0-5000.0ms, got {}", self.base_ms),
+            });
+        }
+        if self.jitter_ms < 0.0 || self.jitter_ms > 1000.0 {
+            return Err(TcValidationError {
+                field: "jitter_ms".to_string(),
+                message: format!("Delay jitter must be 0.0-1000.0ms, got {}", self.jitter_ms),
+            });
+        }
+        if self.correlation < 0.0 || self.correlation > 100.0 {
+            return Err(TcValidationError {
+                field: "correlation".to_string(),
+                message: format!(
+                    "Delay correlation must be 0.0-100.0, got {}",
+                    self.correlation
+                ),
+            });
+        }
+        if self.enabled && self.distribution.requires_jitter() && self.jitter_ms == 0.0 {
+            return Err(TcValidationError {
+                field: "distribution".to_string(),
+                message: format!(
+                    "The {} delay distribution needs a jitter to spread",
+                    self.distribution
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for TcDuplicateConfig {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.percentage < 0.0 || self.percentage > 100.0 {
+            return Err(TcValidationError {
+                field: "percentage".to_string(),
+                message: format!(
+                    "Duplicate percentage must be 0.0-100.0, got {}",
+                    self.percentage
+                ),
+            });
+        }
+        if self.correlation < 0.0 || self.correlation > 100.0 {
+            return Err(TcValidationError {
+                field: "correlation".to_string(),
+                message: format!(
+                    "Duplicate correlation must be 0.0-100.0, got {}",
+                    self.correlation
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for TcReorderConfig {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.enabled {
+            if self.percentage < 0.0 || self.percentage > 100.0 {
+                return Err(TcValidationError {
+                    field: "percentage".to_string(),
+                    message: format!(
+                        "Reorder percentage must be 0.0-100.0, got {}",
+                        self.percentage
+                    ),
+                });
+            }
+            if self.correlation < 0.0 || self.correlation > 100.0 {
+                return Err(TcValidationError {
+                    field: "correlation".to_string(),
+                    message: format!(
+                        "Reorder correlation must be 0.0-100.0, got {}",
+                        self.correlation
+                    ),
+                });
+            }
+            if self.gap == 0 || self.gap > 10 {
+                return Err(TcValidationError {
+                    field: "gap".to_string(),
+                    message: format!("Reorder gap must be 1-10, got {}", self.gap),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for TcCorruptConfig {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
         if self.percentage < 0.0 || self.percentage > 100.0 {
             return Err(TcValidationError {
                 field: "percentage".to_string(),
@@ -616,6 +1481,18 @@ impl TcValidate for TcRateLimitConfig {
     type Error = TcValidationError;
 
     fn validate(&self) -> Result<(), Self::Error> {
+        if let TcRateLimitMode::PercentOfLinkSpeed(percent) = self.mode {
+            if self.enabled && !(percent > 0.0 && percent <= 100.0) {
+                return Err(TcValidationError {
+                    field: "mode".to_string(),
+                    message: format!(
+                        "Rate limit percentage of link speed must be above 0 and at most 100, got {}",
+                        percent
+                    ),
+                });
+            }
+            return Ok(());
+        }
         if self.enabled && (self.rate_kbps == 0 || self.rate_kbps > 1000000) {
             return Err(TcValidationError {
                 field: "rate_kbps".to_string(),
@@ -629,6 +1506,303 @@ impl TcValidate for TcRateLimitConfig {
     }
 }
 
+impl TcValidate for TcSlotConfig {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !(0.0..=Self::MAX_DELAY_MS).contains(&self.min_delay_ms) {
+            return Err(TcValidationError {
+                field: "min_delay_ms".to_string(),
+                message: format!(
+                    "Slot min delay must be 0.0-{}ms, got {}",
+                    Self::MAX_DELAY_MS,
+                    self.min_delay_ms
+                ),
+            });
+        }
+        if !(self.max_delay_ms > 0.0 && self.max_delay_ms <= Self::MAX_DELAY_MS) {
+            return Err(TcValidationError {
+                field: "max_delay_ms".to_string(),
+                message: format!(
+                    "Slot max delay must be above 0 and at most {}ms, got {}",
+                    Self::MAX_DELAY_MS,
+                    self.max_delay_ms
+                ),
+            });
+        }
+        if self.max_delay_ms < self.min_delay_ms {
+            return Err(TcValidationError {
+                field: "max_delay_ms".to_string(),
+                message: format!(
+                    "Slot max delay ({}ms) must not be below the min delay ({}ms)",
+                    self.max_delay_ms, self.min_delay_ms
+                ),
+            });
+        }
+        if self.packets.is_none() && self.bytes.is_none() {
+            return Err(TcValidationError {
+                field: "packets".to_string(),
+                message: "Slot needs a packet or byte limit per slot".to_string(),
+            });
+        }
+        // The kernel takes both limits as signed 32-bit values, 0 meaning none
+        for (field, name, value) in [
+            ("packets", "packets", self.packets),
+            ("bytes", "bytes", self.bytes),
+        ] {
+            if let Some(value) = value
+                && (value == 0 || value > i32::MAX as u32)
+            {
+                return Err(TcValidationError {
+                    field: field.to_string(),
+                    message: format!(
+                        "Slot {} per slot must be 1-{}, got {}",
+                        name,
+                        i32::MAX,
+                        value
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for RedParams {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        let error = |field: &str, message: String| TcValidationError {
+            field: field.to_string(),
+            message,
+        };
+        // `tc` refuses RED without a limit and an average packet size
+        let limit = self
+            .limit
+            .filter(|limit| *limit > 0)
+            .ok_or_else(|| error("limit", "RED needs a limit above 0 bytes".to_string()))?;
+        self.avpkt.filter(|avpkt| *avpkt > 0).ok_or_else(|| {
+            error(
+                "avpkt",
+                "RED needs an average packet size above 0 bytes".to_string(),
+            )
+        })?;
+        if let (Some(min), Some(max)) = (self.min, self.max)
+            && min >= max
+        {
+            return Err(error(
+                "min",
+                format!("RED min ({min}) must be below max ({max})"),
+            ));
+        }
+        if let Some(max) = self.max
+            && max > limit
+        {
+            return Err(error(
+                "max",
+                format!("RED max ({max}) must not exceed the limit ({limit})"),
+            ));
+        }
+        if let Some(probability) = self.probability
+            && !(probability > 0.0 && probability <= 1.0)
+        {
+            return Err(error(
+                "probability",
+                format!("RED probability must be above 0 and at most 1, got {probability}"),
+            ));
+        }
+        if self.burst == Some(0) {
+            return Err(error("burst", "RED burst must be above 0".to_string()));
+        }
+        if self
+            .bandwidth
+            .as_deref()
+            .is_some_and(|bandwidth| bandwidth.trim().is_empty())
+        {
+            return Err(error("bandwidth", "RED bandwidth is empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for SfqParams {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        for (field, value) in [("quantum", self.quantum), ("limit", self.limit)] {
+            if value == Some(0) {
+                return Err(TcValidationError {
+                    field: field.to_string(),
+                    message: format!("SFQ {} must be above 0", field),
+                });
+            }
+        }
+        // The kernel keeps the perturbation period as seconds in an int
+        if let Some(perturb) = self.perturb
+            && perturb > i32::MAX as u32
+        {
+            return Err(TcValidationError {
+                field: "perturb".to_string(),
+                message: format!("SFQ perturb must be at most {}s, got {}", i32::MAX, perturb),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for PrioParams {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        let bands = self.bands.unwrap_or(Self::DEFAULT_BANDS);
+        if !(Self::MIN_BANDS..=Self::MAX_BANDS).contains(&bands) {
+            return Err(TcValidationError {
+                field: "bands".to_string(),
+                message: format!(
+                    "PRIO bands must be {}-{}, got {}",
+                    Self::MIN_BANDS,
+                    Self::MAX_BANDS,
+                    bands
+                ),
+            });
+        }
+        if let Some(priomap) = &self.priomap {
+            if priomap.is_empty() || priomap.len() > Self::PRIOMAP_LEN {
+                return Err(TcValidationError {
+                    field: "priomap".to_string(),
+                    message: format!(
+                        "PRIO priomap must map 1-{} priorities, got {}",
+                        Self::PRIOMAP_LEN,
+                        priomap.len()
+                    ),
+                });
+            }
+            if let Some(band) = priomap.iter().find(|band| **band >= bands) {
+                return Err(TcValidationError {
+                    field: "priomap".to_string(),
+                    message: format!(
+                        "PRIO priomap band {} doesn't exist with {} bands",
+                        band, bands
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for FqCodelParams {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        for (field, value) in [
+            ("target_us", self.target_us),
+            ("interval_us", self.interval_us),
+            ("quantum", self.quantum),
+        ] {
+            if value == Some(0) {
+                return Err(TcValidationError {
+                    field: field.to_string(),
+                    message: format!("fq_codel {} must be above 0", field),
+                });
+            }
+        }
+        // Only a target below the interval leaves time to see it exceeded
+        if let (Some(target), Some(interval)) = (self.target_us, self.interval_us)
+            && target >= interval
+        {
+            return Err(TcValidationError {
+                field: "target_us".to_string(),
+                message: format!(
+                    "fq_codel target ({}us) must be below the interval ({}us)",
+                    target, interval
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for CakeParams {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        // Leaving the field unset is how to ask for an unlimited shaper
+        for (field, value) in [
+            ("bandwidth_kbps", self.bandwidth_kbps),
+            ("rtt_ms", self.rtt_ms),
+        ] {
+            if value == Some(0) {
+                return Err(TcValidationError {
+                    field: field.to_string(),
+                    message: format!("CAKE {} must be above 0", field),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TcValidate for TcFilterSpec {
+    type Error = TcValidationError;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.src_ip.is_none()
+            && self.dst_ip.is_none()
+            && self.src_port.is_none()
+            && self.dst_port.is_none()
+            && self.protocol.is_none()
+        {
+            return Err(TcValidationError {
+                field: "filter".to_string(),
+                message: "Filter needs at least one of source/destination IP, port or protocol"
+                    .to_string(),
+            });
+        }
+        let mut families = Vec::new();
+        for (field, ip) in [("src_ip", &self.src_ip), ("dst_ip", &self.dst_ip)] {
+            if let Some(ip) = ip {
+                let Some((addr, _)) = Self::parse_prefix(ip) else {
+                    return Err(TcValidationError {
+                        field: field.to_string(),
+                        message: format!(
+                            "Filter {} must be an IP or CIDR prefix, got '{}'",
+                            field, ip
+                        ),
+                    });
+                };
+                families.push(addr.is_ipv6());
+            }
+        }
+        if families.windows(2).any(|pair| pair[0] != pair[1]) {
+            return Err(TcValidationError {
+                field: "dst_ip".to_string(),
+                message: "Filter source and destination must both be IPv4 or both IPv6".to_string(),
+            });
+        }
+        for (field, port) in [("src_port", self.src_port), ("dst_port", self.dst_port)] {
+            if port == Some(0) {
+                return Err(TcValidationError {
+                    field: field.to_string(),
+                    message: format!("Filter {} must be 1-65535", field),
+                });
+            }
+        }
+        if self.protocol == Some(TcFilterProtocol::Icmp)
+            && (self.src_port.is_some() || self.dst_port.is_some())
+        {
+            return Err(TcValidationError {
+                field: "protocol".to_string(),
+                message: "ICMP has no ports to match".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
 impl TcValidate for TcNetemConfig {
     type Error = TcValidationError;
 
@@ -639,6 +1813,7 @@ impl TcValidate for TcNetemConfig {
         self.reorder.validate()?;
         self.corrupt.validate()?;
         self.rate_limit.validate()?;
+        self.slot.validate()?;
         Ok(())
     }
 }
@@ -654,6 +1829,8 @@ pub struct FeatureState<T> {
     pub pending: bool,
     /// Last successfully applied configuration
     pub last_applied: Option<T>,
+    /// Whether the feature was enabled when last applied
+    pub applied_enabled: bool,
 }
 
 impl<T: Default> Default for FeatureState<T> {
@@ -663,6 +1840,7 @@ impl<T: Default> Default for FeatureState<T> {
             config: T::default(),
             pending: false,
             last_applied: None,
+            applied_enabled: false,
         }
     }
 }
@@ -675,6 +1853,7 @@ impl<T: Clone> FeatureState<T> {
             config,
             pending: false,
             last_applied: None,
+            applied_enabled: false,
         }
     }
 
@@ -696,16 +1875,20 @@ impl<T: Clone> FeatureState<T> {
     /// Mark configuration as successfully applied
     pub fn mark_applied(&mut self) {
         self.last_applied = Some(self.config.clone());
+        self.applied_enabled = self.enabled;
         self.pending = false;
     }
 
-    /// Check if current config differs from last applied
+    /// Check if the feature differs from what was last applied: it was
+    /// turned on or off since, or its config changed while it is on
     pub fn has_pending_changes(&self) -> bool
     where
         T: PartialEq,
     {
         match &self.last_applied {
-            Some(last) => &self.config != last,
+            Some(last) => {
+                self.enabled != self.applied_enabled || (self.enabled && &self.config != last)
+            }
             None => self.enabled,
         }
     }
@@ -720,6 +1903,7 @@ pub struct InterfaceFeatureStates {
     pub reorder: FeatureState<TcReorderConfig>,
     pub corrupt: FeatureState<TcCorruptConfig>,
     pub rate_limit: FeatureState<TcRateLimitConfig>,
+    pub slot: FeatureState<TcSlotConfig>,
 }
 
 impl InterfaceFeatureStates {
@@ -739,7 +1923,10 @@ impl InterfaceFeatureStates {
             rate_limit: FeatureState::new(TcRateLimitConfig {
                 enabled: false,
                 rate_kbps: 1000, // Sensible default rate (not 0)
+                mode: TcRateLimitMode::Absolute,
+                shaper: RateLimitMode::Netem,
             }),
+            slot: FeatureState::new(TcSlotConfig::wifi_default()),
         }
     }
 
@@ -752,6 +1939,8 @@ impl InterfaceFeatureStates {
             reorder: self.reorder.config.clone(),
             corrupt: self.corrupt.config.clone(),
             rate_limit: self.rate_limit.config.clone(),
+            slot: self.slot.config.clone(),
+            seed: None,
         };
 
         // Set enabled flags based on FeatureState enabled status
@@ -761,6 +1950,7 @@ impl InterfaceFeatureStates {
         config.reorder.enabled = self.reorder.enabled;
         config.corrupt.enabled = self.corrupt.enabled;
         config.rate_limit.enabled = self.rate_limit.enabled;
+        config.slot.enabled = self.slot.enabled;
 
         config
     }
@@ -773,6 +1963,7 @@ impl InterfaceFeatureStates {
             || self.reorder.enabled
             || self.corrupt.enabled
             || self.rate_limit.enabled
+            || self.slot.enabled
     }
 
     /// Check if any feature has pending changes
@@ -783,6 +1974,33 @@ impl InterfaceFeatureStates {
             || self.reorder.has_pending_changes()
             || self.corrupt.has_pending_changes()
             || self.rate_limit.has_pending_changes()
+            || self.slot.has_pending_changes()
+    }
+
+    /// Check if one feature has pending changes
+    pub fn has_pending_changes(&self, feature: TcFeature) -> bool {
+        match feature {
+            TcFeature::Loss => self.loss.has_pending_changes(),
+            TcFeature::Delay => self.delay.has_pending_changes(),
+            TcFeature::Duplicate => self.duplicate.has_pending_changes(),
+            TcFeature::Reorder => self.reorder.has_pending_changes(),
+            TcFeature::Corrupt => self.corrupt.has_pending_changes(),
+            TcFeature::RateLimit => self.rate_limit.has_pending_changes(),
+            TcFeature::Slot => self.slot.has_pending_changes(),
+        }
+    }
+
+    /// Mark one feature as applied
+    pub fn mark_applied(&mut self, feature: TcFeature) {
+        match feature {
+            TcFeature::Loss => self.loss.mark_applied(),
+            TcFeature::Delay => self.delay.mark_applied(),
+            TcFeature::Duplicate => self.duplicate.mark_applied(),
+            TcFeature::Reorder => self.reorder.mark_applied(),
+            TcFeature::Corrupt => self.corrupt.mark_applied(),
+            TcFeature::RateLimit => self.rate_limit.mark_applied(),
+            TcFeature::Slot => self.slot.mark_applied(),
+        }
     }
 
     /// Mark all features as applied
@@ -793,10 +2011,44 @@ impl InterfaceFeatureStates {
         self.reorder.mark_applied();
         self.corrupt.mark_applied();
         self.rate_limit.mark_applied();
+        self.slot.mark_applied();
     }
 }
 
 impl TcNetemConfig {
+    /// Validate feature by feature, naming the impairment whose settings are
+    /// out of range. Applies the same rules as [`TcValidate::validate`], so a
+    /// config passing here passes the backend's validation too.
+    pub fn validate_features(&self) -> Result<(), (TcFeature, TcValidationError)> {
+        self.loss.validate().map_err(|e| (TcFeature::Loss, e))?;
+        self.delay.validate().map_err(|e| (TcFeature::Delay, e))?;
+        self.duplicate
+            .validate()
+            .map_err(|e| (TcFeature::Duplicate, e))?;
+        self.reorder
+            .validate()
+            .map_err(|e| (TcFeature::Reorder, e))?;
+        self.corrupt
+            .validate()
+            .map_err(|e| (TcFeature::Corrupt, e))?;
+        self.rate_limit
+            .validate()
+            .map_err(|e| (TcFeature::RateLimit, e))?;
+        self.slot.validate().map_err(|e| (TcFeature::Slot, e))?;
+        Ok(())
+    }
+
+    /// [`Self::validate_features`], then check that a backend with
+    /// `capabilities` can apply every enabled feature (see
+    /// [`NetemCapabilities::check_config`]).
+    pub fn validate_for(
+        &self,
+        capabilities: &NetemCapabilities,
+    ) -> Result<(), (TcFeature, TcValidationError)> {
+        self.validate_features()?;
+        capabilities.check_config(self)
+    }
+
     /// Create a new config with sensible defaults and validation
     pub fn new() -> Self {
         Self {
@@ -813,7 +2065,11 @@ impl TcNetemConfig {
             rate_limit: TcRateLimitConfig {
                 enabled: false,
                 rate_kbps: 1000, // Default 1 Mbps
+                mode: TcRateLimitMode::Absolute,
+                shaper: RateLimitMode::Netem,
             },
+            slot: TcSlotConfig::wifi_default(),
+            seed: None,
         }
     }
 
@@ -825,94 +2081,104 @@ impl TcNetemConfig {
             || self.reorder.enabled
             || self.corrupt.enabled
             || self.rate_limit.enabled
+            || self.slot.enabled
     }
 
-    /// Convert to legacy parameter format for backward compatibility
-    #[allow(clippy::type_complexity)] // Acceptable for legacy compatibility
-    pub fn to_legacy_params(
-        &self,
-    ) -> (
-        f32,
-        Option<f32>,
-        Option<f32>,
-        Option<f32>,
-        Option<f32>,
-        Option<f32>,
-        Option<f32>,
-        Option<f32>,
-        Option<f32>,
-        Option<u32>,
-        Option<f32>,
-        Option<f32>,
-        Option<u32>,
-    ) {
-        (
-            if self.loss.enabled {
+    /// The flat [`TcConfiguration`] this amounts to, with no command
+    /// assembled yet (see [`TcConfiguration::to_command`])
+    pub fn to_legacy_params(&self) -> TcConfiguration {
+        let random_loss = self.loss.enabled && self.loss.model.is_random();
+        TcConfiguration {
+            loss: if random_loss {
                 self.loss.percentage
             } else {
                 0.0
             },
-            if self.loss.enabled && self.loss.correlation > 0.0 {
+            correlation: if random_loss && self.loss.correlation > 0.0 {
                 Some(self.loss.correlation)
             } else {
                 None
             },
-            if self.delay.enabled && self.delay.base_ms > 0.0 {
+            loss_gemodel: if self.loss.enabled {
+                self.loss.model.gemodel()
+            } else {
+                None
+            },
+            loss_ecn: self.loss.enabled && self.loss.ecn,
+            delay_ms: if self.delay.enabled && self.delay.base_ms > 0.0 {
                 Some(self.delay.base_ms)
             } else {
                 None
             },
-            if self.delay.enabled && self.delay.jitter_ms > 0.0 {
+            delay_jitter_ms: if self.delay.enabled && self.delay.jitter_ms > 0.0 {
                 Some(self.delay.jitter_ms)
             } else {
                 None
             },
-            if self.delay.enabled && self.delay.correlation > 0.0 {
+            delay_correlation: if self.delay.enabled && self.delay.correlation > 0.0 {
                 Some(self.delay.correlation)
             } else {
                 None
             },
-            if self.duplicate.enabled && self.duplicate.percentage > 0.0 {
+            delay_distribution: if self.delay.enabled
+                && self.delay.jitter_ms > 0.0
+                && !self.delay.distribution.is_uniform()
+            {
+                Some(self.delay.distribution)
+            } else {
+                None
+            },
+            duplicate_percent: if self.duplicate.enabled && self.duplicate.percentage > 0.0 {
                 Some(self.duplicate.percentage)
             } else {
                 None
             },
-            if self.duplicate.enabled && self.duplicate.correlation > 0.0 {
+            duplicate_correlation: if self.duplicate.enabled && self.duplicate.correlation > 0.0 {
                 Some(self.duplicate.correlation)
             } else {
                 None
             },
-            if self.reorder.enabled && self.reorder.percentage > 0.0 {
+            reorder_percent: if self.reorder.enabled && self.reorder.percentage > 0.0 {
                 Some(self.reorder.percentage)
             } else {
                 None
             },
-            if self.reorder.enabled && self.reorder.correlation > 0.0 {
+            reorder_correlation: if self.reorder.enabled && self.reorder.correlation > 0.0 {
                 Some(self.reorder.correlation)
             } else {
                 None
             },
-            if self.reorder.enabled && self.reorder.percentage > 0.0 {
+            // netem needs the gap whenever reorder is on, even before a
+            // percentage is set, so it isn't gated on the percentage
+            reorder_gap: if self.reorder.enabled {
                 Some(self.reorder.gap)
             } else {
                 None
             },
-            if self.corrupt.enabled && self.corrupt.percentage > 0.0 {
+            corrupt_percent: if self.corrupt.enabled && self.corrupt.percentage > 0.0 {
                 Some(self.corrupt.percentage)
             } else {
                 None
             },
-            if self.corrupt.enabled && self.corrupt.correlation > 0.0 {
+            corrupt_correlation: if self.corrupt.enabled && self.corrupt.correlation > 0.0 {
                 Some(self.corrupt.correlation)
             } else {
                 None
             },
-            if self.rate_limit.enabled {
+            rate_limit_kbps: if self.rate_limit.enabled {
                 Some(self.rate_limit.rate_kbps)
             } else {
                 None
             },
-        )
+            rate_limit_shaper: if self.rate_limit.enabled {
+                self.rate_limit.shaper
+            } else {
+                RateLimitMode::Netem
+            },
+            slot: self.slot.is_active().then(|| self.slot.clone()),
+            seed: self.seed,
+            command: String::new(),
+        }
     }
 
     /// Create from legacy parameter format for backward compatibility
@@ -937,12 +2203,15 @@ impl TcNetemConfig {
                 enabled: loss > 0.0,
                 percentage: loss,
                 correlation: correlation.unwrap_or(0.0),
+                model: LossModel::Random,
+                ecn: false,
             },
             delay: TcDelayConfig {
                 enabled: delay_ms.is_some_and(|d| d > 0.0),
                 base_ms: delay_ms.unwrap_or(0.0),
                 jitter_ms: delay_jitter_ms.unwrap_or(0.0),
                 correlation: delay_correlation.unwrap_or(0.0),
+                distribution: DelayDistribution::Uniform,
             },
             duplicate: TcDuplicateConfig {
                 enabled: duplicate_percent.is_some_and(|d| d > 0.0),
@@ -963,9 +2232,43 @@ impl TcNetemConfig {
             rate_limit: TcRateLimitConfig {
                 enabled: rate_limit_kbps.is_some_and(|r| r > 0),
                 rate_kbps: rate_limit_kbps.unwrap_or(1000),
+                mode: TcRateLimitMode::Absolute,
+                shaper: RateLimitMode::Netem,
             },
+            slot: TcSlotConfig::wifi_default(),
+            seed: None,
         }
     }
+
+    /// Add the settings the legacy parameters have no slot for: the delay
+    /// distribution, a Gilbert-Elliott loss model (which enables loss), the
+    /// rate limit shaper, slotted delivery (which enables it), ECN marking
+    /// of lost packets (kept only while loss is enabled) and the random seed
+    pub fn with_extensions(
+        mut self,
+        delay_distribution: Option<DelayDistribution>,
+        loss_gemodel: Option<GeModel>,
+        rate_limit_shaper: RateLimitMode,
+        slot: Option<TcSlotConfig>,
+        loss_ecn: bool,
+        seed: Option<u64>,
+    ) -> Self {
+        self.delay.distribution = delay_distribution.unwrap_or_default();
+        self.rate_limit.shaper = rate_limit_shaper;
+        if let Some(model) = loss_gemodel {
+            self.loss.enabled = true;
+            self.loss.model = LossModel::GilbertElliott(model);
+        }
+        if let Some(slot) = slot {
+            self.slot = TcSlotConfig {
+                enabled: true,
+                ..slot
+            };
+        }
+        self.loss.ecn = loss_ecn && self.loss.enabled;
+        self.seed = seed;
+        self
+    }
 }
 
 /// Traffic control operations with structured configuration
@@ -988,24 +2291,76 @@ pub enum TcOperation {
         corrupt_percent: Option<f32>, // NEW: Packet corruption percentage (0.0-100.0)
         corrupt_correlation: Option<f32>, // NEW: Corruption correlation (0.0-100.0)
         rate_limit_kbps: Option<u32>, // NEW: Rate limiting in kbps (1-1000000)
+        /// Shape of the delay jitter, when not netem's uniform default
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delay_distribution: Option<DelayDistribution>,
+        /// Gilbert-Elliott loss model, in place of the random `loss`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        loss_gemodel: Option<GeModel>,
+        /// How `rate_limit_kbps` is shaped
+        #[serde(default, skip_serializing_if = "RateLimitMode::is_netem")]
+        rate_limit_shaper: RateLimitMode,
+        /// Slotted delivery
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        slot: Option<TcSlotConfig>,
+        /// Mark ECN-capable packets instead of dropping them
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        loss_ecn: bool,
+        /// Seed for netem's random choices
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seed: Option<u64>,
+    },
+    /// Replace the interface's configuration with a RED (random early
+    /// detection) qdisc
+    ApplyRed { params: RedParams },
+    /// Replace the interface's configuration with an SFQ (stochastic
+    /// fairness queueing) qdisc
+    ApplySfq { params: SfqParams },
+    /// Replace the interface's configuration with a PRIO (priority bands)
+    /// qdisc
+    ApplyPrio { params: PrioParams },
+    /// Replace the interface's configuration with an fq_codel (fair
+    /// queueing CoDel) qdisc
+    ApplyFqCodel { params: FqCodelParams },
+    /// Replace the interface's configuration with a CAKE qdisc
+    ApplyCake { params: CakeParams },
+    /// Apply one configuration to several (namespace, interface) targets in
+    /// a single query. The request's own namespace and interface only
+    /// address the query; each target is checked and applied as if it had
+    /// been requested alone, and a failing target doesn't stop the others.
+    /// If any target fails, the query is answered with an error listing
+    /// each failed target and why
+    ApplyBatch {
+        targets: Vec<(String, String)>,
+        config: TcNetemConfig,
     },
     /// Remove all traffic control configuration
     Remove,
 }
 
 /// Traffic control configuration that was applied
-#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct TcConfiguration {
     /// Applied packet loss percentage
     pub loss: f32,
     /// Applied correlation percentage (if any)
     pub correlation: Option<f32>,
+    /// Applied Gilbert-Elliott loss model, in place of random loss (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loss_gemodel: Option<GeModel>,
+    /// Whether lost packets are ECN-marked rather than dropped
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub loss_ecn: bool,
     /// Applied delay in milliseconds (if any)
     pub delay_ms: Option<f32>,
     /// Applied delay jitter in milliseconds (if any)
     pub delay_jitter_ms: Option<f32>,
     /// Applied delay correlation (if any)
     pub delay_correlation: Option<f32>,
+    /// Applied delay distribution, when not netem's uniform default (only
+    /// set alongside a jitter)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_distribution: Option<DelayDistribution>,
     /// Applied packet duplication percentage (if any)
     pub duplicate_percent: Option<f32>,
     /// Applied duplication correlation (if any)
@@ -1022,10 +2377,493 @@ pub struct TcConfiguration {
     pub corrupt_correlation: Option<f32>,
     /// Applied rate limiting in kbps (if any)
     pub rate_limit_kbps: Option<u32>,
+    /// How the rate limit is shaped (only HTB alongside a rate limit)
+    #[serde(default, skip_serializing_if = "RateLimitMode::is_netem")]
+    pub rate_limit_shaper: RateLimitMode,
+    /// Applied slotted delivery (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<TcSlotConfig>,
+    /// Seed of netem's random choices, when one was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
     /// Full tc command that was executed
     pub command: String,
 }
 
+/// A single netem parameter on which two [`TcConfiguration`]s disagree.
+///
+/// `None` means the parameter is not active on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcConfigDifference {
+    /// Parameter label (e.g. "loss", "delay jitter", "rate")
+    pub parameter: &'static str,
+    /// Unit suffix for display ("%", "ms", "kbps" or empty)
+    pub unit: &'static str,
+    /// Value on the side `diff` was called on
+    pub ours: Option<f64>,
+    /// Value on the side passed to `diff`
+    pub theirs: Option<f64>,
+}
+
+impl TcConfigDifference {
+    /// The impairment the parameter belongs to
+    pub fn feature(&self) -> TcFeature {
+        match self.parameter.split(' ').next() {
+            Some("loss") => TcFeature::Loss,
+            Some("delay") => TcFeature::Delay,
+            Some("duplicate") => TcFeature::Duplicate,
+            Some("reorder") => TcFeature::Reorder,
+            Some("corrupt") => TcFeature::Corrupt,
+            Some("slot") => TcFeature::Slot,
+            _ => TcFeature::RateLimit,
+        }
+    }
+
+    /// Format one side's value, e.g. "5%" or "off".
+    pub fn format_value(&self, value: Option<f64>) -> String {
+        match value {
+            // Round away f32 -> f64 noise (0.1 would print as 0.10000000149)
+            Some(v) => format!("{}{}", (v * 1000.0).round() / 1000.0, self.unit),
+            None => "off".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for TcConfigDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} (here {})",
+            self.parameter,
+            self.format_value(self.theirs),
+            self.format_value(self.ours)
+        )
+    }
+}
+
+/// The netem impairment a `tc` command clause configures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TcFeature {
+    Loss,
+    Delay,
+    Duplicate,
+    Reorder,
+    Corrupt,
+    RateLimit,
+    Slot,
+}
+
+impl TcFeature {
+    /// Every feature, in the order the GUI lists them
+    pub const ALL: [TcFeature; 7] = [
+        TcFeature::Loss,
+        TcFeature::Delay,
+        TcFeature::Duplicate,
+        TcFeature::Reorder,
+        TcFeature::Corrupt,
+        TcFeature::RateLimit,
+        TcFeature::Slot,
+    ];
+
+    /// Human-readable name, e.g. "rate limit"
+    pub fn label(&self) -> &'static str {
+        match self {
+            TcFeature::Loss => "loss",
+            TcFeature::Delay => "delay",
+            TcFeature::Duplicate => "duplicate",
+            TcFeature::Reorder => "reorder",
+            TcFeature::Corrupt => "corrupt",
+            TcFeature::RateLimit => "rate limit",
+            TcFeature::Slot => "slot",
+        }
+    }
+}
+
+impl std::fmt::Display for TcFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// One clause of a `tc` command, e.g. `delay 100ms 10ms`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcCommandPart {
+    /// Impairment this clause configures; `None` for the `tc qdisc ...` head
+    pub feature: Option<TcFeature>,
+    /// Clause text as it appears in the command
+    pub clause: String,
+}
+
+impl TcConfiguration {
+    /// Tolerance for comparing values that went through `tc` and back.
+    const DIFF_EPSILON: f64 = 0.01;
+
+    /// Break the `tc` command for this configuration into ordered clauses.
+    ///
+    /// The first part is the `tc qdisc replace ...` head, followed by one
+    /// clause per active impairment. Joining the clauses with single spaces
+    /// yields [`Self::to_command`].
+    ///
+    /// A rate limit shaped by HTB turns the head into the command sequence
+    /// building the HTB root and its class (the class being the rate limit's
+    /// clause), ending in the `tc qdisc add` of the netem leaf.
+    pub fn command_parts(&self, interface: &str) -> Vec<TcCommandPart> {
+        fn active(value: Option<f32>) -> Option<f32> {
+            value.filter(|v| *v > 0.0)
+        }
+        fn rate_clause(rate: u32) -> String {
+            if rate >= 1000 {
+                format!("rate {}mbit", rate / 1000)
+            } else {
+                format!("rate {}kbit", rate)
+            }
+        }
+
+        let rate_limit = self.rate_limit_kbps.filter(|r| *r > 0);
+        let htb_rate = rate_limit.filter(|_| self.rate_limit_shaper == RateLimitMode::Htb);
+        let mut parts = match htb_rate {
+            Some(rate) => vec![
+                TcCommandPart {
+                    feature: None,
+                    clause: format!(
+                        "tc qdisc replace dev {} root handle 1: htb default 1 &&",
+                        interface
+                    ),
+                },
+                TcCommandPart {
+                    feature: Some(TcFeature::RateLimit),
+                    clause: format!(
+                        "tc class add dev {} parent 1: classid 1:1 htb {} &&",
+                        interface,
+                        rate_clause(rate)
+                    ),
+                },
+                TcCommandPart {
+                    feature: None,
+                    clause: format!("tc qdisc add dev {} parent 1:1 handle 10: netem", interface),
+                },
+            ],
+            None => vec![TcCommandPart {
+                feature: None,
+                clause: format!("tc qdisc replace dev {} root netem", interface),
+            }],
+        };
+        let mut push = |feature, clause| {
+            parts.push(TcCommandPart {
+                feature: Some(feature),
+                clause,
+            })
+        };
+
+        let loss_clause = if let Some(model) = self.loss_gemodel {
+            Some(model.clause())
+        } else if self.loss > 0.0 {
+            let mut clause = format!("loss {}%", self.loss);
+            if let Some(corr) = active(self.correlation) {
+                clause.push_str(&format!(" correlation {}%", corr));
+            }
+            Some(clause)
+        } else {
+            None
+        };
+        if let Some(mut clause) = loss_clause {
+            if self.loss_ecn {
+                clause.push_str(" ecn");
+            }
+            push(TcFeature::Loss, clause);
+        }
+
+        if let Some(delay) = active(self.delay_ms) {
+            let mut clause = format!("delay {}ms", delay);
+            if let Some(jitter) = active(self.delay_jitter_ms) {
+                clause.push_str(&format!(" {}ms", jitter));
+                if let Some(corr) = active(self.delay_correlation) {
+                    clause.push_str(&format!(" {}%", corr));
+                }
+                if let Some(distribution) = self.delay_distribution.filter(|d| !d.is_uniform()) {
+                    clause.push_str(&format!(" distribution {}", distribution));
+                }
+            }
+            push(TcFeature::Delay, clause);
+        }
+
+        if let Some(duplicate) = active(self.duplicate_percent) {
+            let mut clause = format!("duplicate {}%", duplicate);
+            if let Some(corr) = active(self.duplicate_correlation) {
+                clause.push_str(&format!(" {}%", corr));
+            }
+            push(TcFeature::Duplicate, clause);
+        }
+
+        if let Some(reorder) = active(self.reorder_percent) {
+            let mut clause = format!("reorder {}%", reorder);
+            if let Some(corr) = active(self.reorder_correlation) {
+                clause.push_str(&format!(" {}%", corr));
+            }
+            if let Some(gap) = self.reorder_gap.filter(|g| *g > 0) {
+                clause.push_str(&format!(" gap {}", gap));
+            }
+            push(TcFeature::Reorder, clause);
+        }
+
+        if let Some(corrupt) = active(self.corrupt_percent) {
+            let mut clause = format!("corrupt {}%", corrupt);
+            if let Some(corr) = active(self.corrupt_correlation) {
+                clause.push_str(&format!(" {}%", corr));
+            }
+            push(TcFeature::Corrupt, clause);
+        }
+
+        if let Some(slot) = self.slot.as_ref().filter(|slot| slot.max_delay_ms > 0.0) {
+            push(TcFeature::Slot, slot.clause());
+        }
+
+        if let Some(rate) = rate_limit.filter(|_| htb_rate.is_none()) {
+            push(TcFeature::RateLimit, rate_clause(rate));
+        }
+
+        // The seed belongs to no impairment; `tc` prints it last
+        if let Some(seed) = self.seed {
+            parts.push(TcCommandPart {
+                feature: None,
+                clause: format!("seed {}", seed),
+            });
+        }
+
+        parts
+    }
+
+    /// Whether `feature` is active in this configuration.
+    pub fn has_feature(&self, feature: TcFeature) -> bool {
+        fn active(value: Option<f32>) -> bool {
+            value.is_some_and(|v| v > 0.0)
+        }
+        match feature {
+            TcFeature::Loss => self.loss > 0.0 || self.loss_gemodel.is_some(),
+            TcFeature::Delay => active(self.delay_ms),
+            TcFeature::Duplicate => active(self.duplicate_percent),
+            TcFeature::Reorder => active(self.reorder_percent),
+            TcFeature::Corrupt => active(self.corrupt_percent),
+            TcFeature::RateLimit => self.rate_limit_kbps.is_some_and(|r| r > 0),
+            TcFeature::Slot => self.slot.as_ref().is_some_and(|s| s.max_delay_ms > 0.0),
+        }
+    }
+
+    /// This configuration with `feature` and its parameters cleared, every
+    /// other impairment left as it is.
+    pub fn without_feature(&self, feature: TcFeature) -> TcConfiguration {
+        let mut config = self.clone();
+        match feature {
+            TcFeature::Loss => {
+                config.loss = 0.0;
+                config.correlation = None;
+                config.loss_gemodel = None;
+                config.loss_ecn = false;
+            }
+            TcFeature::Delay => {
+                config.delay_ms = None;
+                config.delay_jitter_ms = None;
+                config.delay_correlation = None;
+                config.delay_distribution = None;
+            }
+            TcFeature::Duplicate => {
+                config.duplicate_percent = None;
+                config.duplicate_correlation = None;
+            }
+            TcFeature::Reorder => {
+                config.reorder_percent = None;
+                config.reorder_correlation = None;
+                config.reorder_gap = None;
+            }
+            TcFeature::Corrupt => {
+                config.corrupt_percent = None;
+                config.corrupt_correlation = None;
+            }
+            TcFeature::RateLimit => {
+                config.rate_limit_kbps = None;
+                config.rate_limit_shaper = RateLimitMode::Netem;
+            }
+            TcFeature::Slot => config.slot = None,
+        }
+        config
+    }
+
+    /// The operation that applies this configuration. With nothing active it
+    /// removes the qdisc.
+    pub fn to_operation(&self) -> TcOperation {
+        TcOperation::Apply {
+            loss: self.loss,
+            correlation: self.correlation,
+            delay_ms: self.delay_ms,
+            delay_jitter_ms: self.delay_jitter_ms,
+            delay_correlation: self.delay_correlation,
+            duplicate_percent: self.duplicate_percent,
+            duplicate_correlation: self.duplicate_correlation,
+            reorder_percent: self.reorder_percent,
+            reorder_correlation: self.reorder_correlation,
+            reorder_gap: self.reorder_gap,
+            corrupt_percent: self.corrupt_percent,
+            corrupt_correlation: self.corrupt_correlation,
+            rate_limit_kbps: self.rate_limit_kbps,
+            delay_distribution: self.delay_distribution,
+            loss_gemodel: self.loss_gemodel,
+            rate_limit_shaper: self.rate_limit_shaper,
+            slot: self.slot.clone(),
+            loss_ecn: self.loss_ecn,
+            seed: self.seed,
+        }
+    }
+
+    /// The `tc` command for this configuration as a single string, for
+    /// display, logging and copy/export.
+    pub fn to_command(&self, interface: &str) -> String {
+        self.command_parts(interface)
+            .iter()
+            .map(|part| part.clause.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// List the netem parameters on which `self` and `other` disagree.
+    ///
+    /// A zero value and an unset one both mean "inactive" and compare equal.
+    /// Correlations, jitter and the reorder gap are only compared when their
+    /// parent impairment is active on both sides; the seed and the `command`
+    /// string are ignored. An empty result means both describe the same qdisc.
+    pub fn diff(&self, other: &TcConfiguration) -> Vec<TcConfigDifference> {
+        fn active(value: Option<f64>) -> Option<f64> {
+            value.filter(|v| *v > 0.0)
+        }
+        fn f(value: Option<f32>) -> Option<f64> {
+            active(value.map(f64::from))
+        }
+        fn u(value: Option<u32>) -> Option<f64> {
+            active(value.map(f64::from))
+        }
+
+        let loss = (f(Some(self.loss)), f(Some(other.loss)));
+        let delay = (f(self.delay_ms), f(other.delay_ms));
+        let duplicate = (f(self.duplicate_percent), f(other.duplicate_percent));
+        let reorder = (f(self.reorder_percent), f(other.reorder_percent));
+        let corrupt = (f(self.corrupt_percent), f(other.corrupt_percent));
+        let slot_max = |config: &TcConfiguration| f(config.slot.as_ref().map(|s| s.max_delay_ms));
+        let slot_min = |config: &TcConfiguration| f(config.slot.as_ref().map(|s| s.min_delay_ms));
+        let slot_packets =
+            |config: &TcConfiguration| u(config.slot.as_ref().and_then(|s| s.packets));
+        let slot_bytes = |config: &TcConfiguration| u(config.slot.as_ref().and_then(|s| s.bytes));
+        let ecn = |config: &TcConfiguration| config.loss_ecn.then_some(1.0);
+        let slot = (slot_max(self), slot_max(other));
+        let both = |(a, b): (Option<f64>, Option<f64>)| a.is_some() && b.is_some();
+
+        // (parameter, unit, ours, theirs, whether to compare)
+        let candidates = [
+            ("loss", "%", loss.0, loss.1, true),
+            (
+                "loss correlation",
+                "%",
+                f(self.correlation),
+                f(other.correlation),
+                both(loss),
+            ),
+            ("loss ecn", "", ecn(self), ecn(other), both(loss)),
+            ("delay", "ms", delay.0, delay.1, true),
+            (
+                "delay jitter",
+                "ms",
+                f(self.delay_jitter_ms),
+                f(other.delay_jitter_ms),
+                both(delay),
+            ),
+            (
+                "delay correlation",
+                "%",
+                f(self.delay_correlation),
+                f(other.delay_correlation),
+                both(delay),
+            ),
+            ("duplicate", "%", duplicate.0, duplicate.1, true),
+            (
+                "duplicate correlation",
+                "%",
+                f(self.duplicate_correlation),
+                f(other.duplicate_correlation),
+                both(duplicate),
+            ),
+            ("reorder", "%", reorder.0, reorder.1, true),
+            (
+                "reorder correlation",
+                "%",
+                f(self.reorder_correlation),
+                f(other.reorder_correlation),
+                both(reorder),
+            ),
+            (
+                "reorder gap",
+                "",
+                u(self.reorder_gap),
+                u(other.reorder_gap),
+                both(reorder),
+            ),
+            ("corrupt", "%", corrupt.0, corrupt.1, true),
+            (
+                "corrupt correlation",
+                "%",
+                f(self.corrupt_correlation),
+                f(other.corrupt_correlation),
+                both(corrupt),
+            ),
+            (
+                "rate",
+                "kbps",
+                u(self.rate_limit_kbps),
+                u(other.rate_limit_kbps),
+                true,
+            ),
+            ("slot max delay", "ms", slot.0, slot.1, true),
+            (
+                "slot min delay",
+                "ms",
+                slot_min(self),
+                slot_min(other),
+                both(slot),
+            ),
+            (
+                "slot packets",
+                "",
+                slot_packets(self),
+                slot_packets(other),
+                both(slot),
+            ),
+            (
+                "slot bytes",
+                "",
+                slot_bytes(self),
+                slot_bytes(other),
+                both(slot),
+            ),
+        ];
+
+        candidates
+            .into_iter()
+            .filter(|(_, _, ours, theirs, compare)| {
+                *compare
+                    && match (ours, theirs) {
+                        (Some(a), Some(b)) => (a - b).abs() > Self::DIFF_EPSILON,
+                        (None, None) => false,
+                        _ => true,
+                    }
+            })
+            .map(|(parameter, unit, ours, theirs, _)| TcConfigDifference {
+                parameter,
+                unit,
+                ours,
+                theirs,
+            })
+            .collect()
+    }
+}
+
 /// Traffic control operation response (Reply)
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct TcResponse {
@@ -1037,6 +2875,33 @@ pub struct TcResponse {
     pub applied_config: Option<TcConfiguration>,
     /// Error details (if failed)
     pub error_code: Option<i32>,
+    /// Outcome on the veth peer, for requests with `apply_to_peer` set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer: Option<PeerApplyOutcome>,
+    /// Impairment the kernel (or validation) objected to, when a failure
+    /// could be traced to one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected_feature: Option<TcFeature>,
+    /// Set when a request with a future `apply_at` was accepted rather than
+    /// performed: the Unix time (milliseconds) it will be performed at. The
+    /// configuration update published then confirms it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_at: Option<u64>,
+}
+
+/// What happened on the other end of a veth pair for a [`TcRequest`] with
+/// `apply_to_peer` set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum PeerApplyOutcome {
+    /// The operation was also performed on the peer
+    Applied {
+        namespace: String,
+        interface: String,
+        success: bool,
+        message: String,
+    },
+    /// No peer could be identified; only the requested end was changed
+    NotFound,
 }
 
 /// Interface control request (enable/disable) (Query)
@@ -1052,12 +2917,23 @@ pub struct InterfaceControlRequest {
 }
 
 /// Interface control operations
-#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 pub enum InterfaceControlOperation {
     /// Bring interface UP
     Enable,
     /// Bring interface DOWN
     Disable,
+    /// Change the interface's MTU (68-65535 bytes)
+    SetMtu { mtu: u32 },
+    /// Read the interface's MTU
+    GetMtu,
+    /// Rename the interface (a Linux interface name of at most 15 bytes).
+    /// An interface that is up is brought down for the rename and back up.
+    Rename { new_name: String },
+    /// Assign an address, `"ip/prefix"` (IPv4 or IPv6)
+    AddAddress { cidr: String },
+    /// Remove an assigned address, `"ip/prefix"`
+    RemoveAddress { cidr: String },
 }
 
 /// Interface control operation response (Reply)
@@ -1071,6 +2947,210 @@ pub struct InterfaceControlResponse {
     pub new_state: bool,
     /// Error details (if failed)
     pub error_code: Option<i32>,
+    /// The interface's MTU after an MTU operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+    /// The interface's name after a rename
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    /// The addresses assigned to the interface after an address operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addresses: Option<Vec<String>>,
+}
+
+/// Bring several interfaces of one namespace up or down at once (Query)
+/// Query Service: interfaces/{ns}/set
+///
+/// Every interface is attempted, whatever happens to the others, in name
+/// order so the outcome doesn't depend on the order of the request.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct InterfaceControlBatchRequest {
+    /// Target network namespace
+    pub namespace: String,
+    /// Target interface names; duplicates are ignored
+    pub interfaces: Vec<String>,
+    /// Operation applied to every interface
+    pub operation: InterfaceControlOperation,
+}
+
+/// Outcome of a batch interface control request (Reply)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct InterfaceControlBatchResponse {
+    /// Namespace the interfaces are in
+    pub namespace: String,
+    /// Operation that was applied
+    pub operation: InterfaceControlOperation,
+    /// One result per distinct interface, in name order
+    pub results: Vec<InterfaceControlTargetResult>,
+}
+
+/// Outcome of a batch request for one interface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct InterfaceControlTargetResult {
+    /// Interface name
+    pub interface: String,
+    /// Whether the operation succeeded on this interface
+    pub success: bool,
+    /// Detailed message about the result
+    pub message: String,
+}
+
+impl InterfaceControlBatchResponse {
+    /// Results of the interfaces the operation failed on.
+    pub fn failures(&self) -> impl Iterator<Item = &InterfaceControlTargetResult> {
+        self.results.iter().filter(|r| !r.success)
+    }
+
+    /// One-line outcome, naming the interfaces that failed.
+    pub fn summary(&self) -> String {
+        let action = match &self.operation {
+            InterfaceControlOperation::Enable => "enabled".to_string(),
+            InterfaceControlOperation::Disable => "disabled".to_string(),
+            InterfaceControlOperation::SetMtu { mtu } => format!("set MTU {} on", mtu),
+            InterfaceControlOperation::GetMtu => "read the MTU of".to_string(),
+            InterfaceControlOperation::Rename { new_name } => format!("renamed to {}", new_name),
+            InterfaceControlOperation::AddAddress { cidr } => format!("added {} to", cidr),
+            InterfaceControlOperation::RemoveAddress { cidr } => format!("removed {} from", cidr),
+        };
+        let failed: Vec<&str> = self.failures().map(|r| r.interface.as_str()).collect();
+        let succeeded = self.results.len() - failed.len();
+        if failed.is_empty() {
+            format!("{}: {} {} interfaces", self.namespace, action, succeeded)
+        } else {
+            format!(
+                "{}: {} {} of {} interfaces; failed: {}",
+                self.namespace,
+                action,
+                succeeded,
+                self.results.len(),
+                failed.join(", ")
+            )
+        }
+    }
+}
+
+/// Create or delete links in one namespace (Query)
+/// Query Service: network/{ns}/set
+///
+/// For building test topologies from the GUI or a script. The interface list
+/// is republished once the operation is done.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NetworkOperationRequest {
+    /// Namespace the operation runs in
+    pub namespace: String,
+    /// What to create or delete
+    pub operation: NetworkOperation,
+}
+
+/// Link operations of a [`NetworkOperationRequest`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum NetworkOperation {
+    /// Create a veth pair: `name_a` in the request's namespace, `name_b`
+    /// there too, or in namespace `ns_b`
+    CreateVeth {
+        name_a: String,
+        name_b: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ns_b: Option<String>,
+    },
+    /// Delete a link; deleting either end of a veth pair deletes both
+    DeleteLink { name: String },
+}
+
+impl NetworkOperation {
+    /// Check the names and namespaces of the operation, run in `namespace`,
+    /// before anything is attempted.
+    pub fn validate(&self, namespace: &str) -> Result<(), String> {
+        match self {
+            NetworkOperation::CreateVeth {
+                name_a,
+                name_b,
+                ns_b,
+            } => {
+                validation::validate_new_interface_name(name_a)?;
+                validation::validate_new_interface_name(name_b)?;
+                if let Some(ns_b) = ns_b {
+                    validation::validate_namespace(ns_b)?;
+                }
+                let same_namespace = ns_b.as_deref().is_none_or(|ns_b| ns_b == namespace);
+                if same_namespace && name_a == name_b {
+                    return Err(format!(
+                        "both ends of the veth pair are named {name_a} in namespace {namespace}"
+                    ));
+                }
+                Ok(())
+            }
+            NetworkOperation::DeleteLink { name } => validation::validate_interface(name),
+        }
+    }
+}
+
+/// Outcome of a network operation (Reply)
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NetworkOperationResponse {
+    /// Namespace the operation ran in
+    pub namespace: String,
+    /// Operation that was carried out
+    pub operation: NetworkOperation,
+    /// Whether the operation succeeded
+    pub success: bool,
+    /// Detailed message about the result
+    pub message: String,
+}
+
+/// Remove one impairment from several interfaces of one namespace (Query)
+/// Query Service: configs/{ns}/clear
+///
+/// Each interface's current configuration is read back, the feature dropped
+/// and the rest reapplied; an interface left with nothing active has its
+/// qdisc removed. Interfaces are handled in name order, independently.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcFeatureClearRequest {
+    /// Target network namespace
+    pub namespace: String,
+    /// Target interface names; duplicates are ignored
+    pub interfaces: Vec<String>,
+    /// Impairment to remove
+    pub feature: TcFeature,
+}
+
+/// Outcome of a feature clear request (Reply)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcFeatureClearResponse {
+    /// Namespace the interfaces are in
+    pub namespace: String,
+    /// Impairment that was removed
+    pub feature: TcFeature,
+    /// One result per distinct interface, in name order
+    pub results: Vec<InterfaceControlTargetResult>,
+}
+
+impl TcFeatureClearResponse {
+    /// Results of the interfaces the feature could not be removed from.
+    pub fn failures(&self) -> impl Iterator<Item = &InterfaceControlTargetResult> {
+        self.results.iter().filter(|r| !r.success)
+    }
+
+    /// One-line outcome, naming the interfaces that failed.
+    pub fn summary(&self) -> String {
+        let failed: Vec<&str> = self.failures().map(|r| r.interface.as_str()).collect();
+        let succeeded = self.results.len() - failed.len();
+        if failed.is_empty() {
+            format!(
+                "{}: removed {} from {} interfaces",
+                self.namespace, self.feature, succeeded
+            )
+        } else {
+            format!(
+                "{}: removed {} from {} of {} interfaces; failed: {}",
+                self.namespace,
+                self.feature,
+                succeeded,
+                self.results.len(),
+                failed.join(", ")
+            )
+        }
+    }
 }
 
 // ============================================================================
@@ -1089,6 +3169,9 @@ pub struct DiagnosticsRequest {
     pub target: Option<String>,
     /// Timeout for diagnostic tests in milliseconds
     pub timeout_ms: u32,
+    /// Which diagnostic to run; the standard checks when absent
+    #[serde(default)]
+    pub test: DiagnosticsTest,
 }
 
 impl Default for DiagnosticsRequest {
@@ -1098,6 +3181,81 @@ impl Default for DiagnosticsRequest {
             interface: String::new(),
             target: None,
             timeout_ms: 5000,
+            test: DiagnosticsTest::default(),
+        }
+    }
+}
+
+/// Echo requests a ping diagnostic sends unless told otherwise
+pub const DEFAULT_PING_COUNT: u32 = 5;
+
+/// Most echo requests one ping diagnostic may send
+pub const MAX_PING_COUNT: u32 = 50;
+
+/// Length of a throughput probe unless told otherwise, in milliseconds
+pub const DEFAULT_PROBE_DURATION_MS: u32 = 3000;
+
+/// Shortest throughput probe, in milliseconds
+pub const MIN_PROBE_DURATION_MS: u32 = 500;
+
+/// Longest throughput probe, in milliseconds
+pub const MAX_PROBE_DURATION_MS: u32 = 30_000;
+
+/// Diagnostic run by a [`DiagnosticsRequest`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum DiagnosticsTest {
+    /// Link status, TC configuration and statistics, and a short ping of
+    /// the request's target
+    #[default]
+    Standard,
+    /// Ping `target` `count` times from the interface and report the
+    /// round-trip summary in [`DiagnosticsResults::ping`]
+    Ping { target: String, count: u32 },
+    /// Send over TCP for `duration_ms` from the interface's namespace and
+    /// report the rate achieved in [`DiagnosticsResults::throughput`].
+    /// `target` is a TCP sink ("host:port") reached through the interface;
+    /// without one the probe runs over loopback, a baseline the interface's
+    /// qdisc doesn't shape.
+    Throughput {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+        duration_ms: u32,
+    },
+    /// Read the impairment the kernel actually has on the interface into
+    /// [`DiagnosticsResults::reconciled`], to compare with (or replace) what
+    /// the UI believes is applied
+    ReconcileTc,
+}
+
+impl DiagnosticsTest {
+    /// Check the test's parameters before anything is run.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            DiagnosticsTest::Standard | DiagnosticsTest::ReconcileTc => Ok(()),
+            DiagnosticsTest::Ping { target, count } => {
+                validation::validate_ping_target(target)?;
+                if !(1..=MAX_PING_COUNT).contains(count) {
+                    return Err(format!(
+                        "invalid ping count {count}: expected 1..={MAX_PING_COUNT}"
+                    ));
+                }
+                Ok(())
+            }
+            DiagnosticsTest::Throughput {
+                target,
+                duration_ms,
+            } => {
+                if let Some(target) = target {
+                    validation::validate_probe_target(target)?;
+                }
+                if !(MIN_PROBE_DURATION_MS..=MAX_PROBE_DURATION_MS).contains(duration_ms) {
+                    return Err(format!(
+                        "invalid probe duration {duration_ms} ms: expected \
+                         {MIN_PROBE_DURATION_MS}..={MAX_PROBE_DURATION_MS}"
+                    ));
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -1115,6 +3273,320 @@ pub struct DiagnosticsResponse {
     pub error_code: Option<i32>,
 }
 
+impl DiagnosticsResponse {
+    /// Whether the interface passed: diagnostics ran, the link is up with
+    /// carrier, and the connectivity target (if tested) was reachable.
+    pub fn passed(&self) -> bool {
+        self.success
+            && self.results.link_status.is_up
+            && self.results.link_status.has_carrier
+            && self
+                .results
+                .connectivity
+                .as_ref()
+                .is_none_or(|c| c.reachable)
+    }
+}
+
+/// Namespace-wide diagnostics request (Query)
+/// Query Service: tcgui/{backend_name}/query/diagnostics/namespace
+///
+/// Runs the per-interface diagnostics on every interface the backend knows
+/// in `namespace` except loopback. Interfaces still running when the
+/// backend's time budget is spent are reported as failed.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NamespaceDiagnosticsRequest {
+    /// Target network namespace
+    pub namespace: String,
+    /// Optional target IP/host for connectivity tests (auto-detected per
+    /// interface if None)
+    pub target: Option<String>,
+    /// Timeout for each interface's diagnostic tests in milliseconds
+    pub timeout_ms: u32,
+}
+
+impl Default for NamespaceDiagnosticsRequest {
+    fn default() -> Self {
+        Self {
+            namespace: String::new(),
+            target: None,
+            timeout_ms: 5000,
+        }
+    }
+}
+
+/// Diagnostics outcome for one interface of a namespace-wide run
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct InterfaceDiagnostics {
+    /// Interface name
+    pub interface: String,
+    /// The interface's diagnostics response (a failed run is reported here,
+    /// it does not abort the other interfaces)
+    pub response: DiagnosticsResponse,
+}
+
+/// Namespace-wide diagnostics response (Reply)
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NamespaceDiagnosticsResponse {
+    /// Namespace that was diagnosed
+    pub namespace: String,
+    /// Per-interface results, sorted by interface name
+    pub interfaces: Vec<InterfaceDiagnostics>,
+}
+
+impl NamespaceDiagnosticsResponse {
+    /// Number of interfaces that passed
+    pub fn passed_count(&self) -> usize {
+        self.interfaces
+            .iter()
+            .filter(|i| i.response.passed())
+            .count()
+    }
+
+    /// Summary line, e.g. "3/4 interfaces passed"
+    pub fn summary(&self) -> String {
+        format!(
+            "{}/{} interfaces passed",
+            self.passed_count(),
+            self.interfaces.len()
+        )
+    }
+}
+
+/// A netem capability that a backend may or may not be able to apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+pub enum NetemCapability {
+    Delay,
+    Jitter,
+    /// Delay distribution tables (normal, pareto, ...)
+    DelayDistribution,
+    /// Random (Bernoulli) loss
+    Loss,
+    /// Gilbert-Elliott state-based loss
+    LossGilbertElliott,
+    Duplicate,
+    Corrupt,
+    Reorder,
+    Rate,
+    Slot,
+    Ecn,
+    Limit,
+    Seed,
+}
+
+impl NetemCapability {
+    /// Every capability, in the order they are reported
+    pub const ALL: [NetemCapability; 13] = [
+        NetemCapability::Delay,
+        NetemCapability::Jitter,
+        NetemCapability::DelayDistribution,
+        NetemCapability::Loss,
+        NetemCapability::LossGilbertElliott,
+        NetemCapability::Duplicate,
+        NetemCapability::Corrupt,
+        NetemCapability::Reorder,
+        NetemCapability::Rate,
+        NetemCapability::Slot,
+        NetemCapability::Ecn,
+        NetemCapability::Limit,
+        NetemCapability::Seed,
+    ];
+
+    /// The capability a GUI feature toggle depends on
+    pub fn for_feature(feature: TcFeature) -> Self {
+        match feature {
+            TcFeature::Loss => NetemCapability::Loss,
+            TcFeature::Delay => NetemCapability::Delay,
+            TcFeature::Duplicate => NetemCapability::Duplicate,
+            TcFeature::Reorder => NetemCapability::Reorder,
+            TcFeature::Corrupt => NetemCapability::Corrupt,
+            TcFeature::RateLimit => NetemCapability::Rate,
+            TcFeature::Slot => NetemCapability::Slot,
+        }
+    }
+}
+
+/// Whether one netem capability is available, and why not if it isn't.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NetemFeatureSupport {
+    pub capability: NetemCapability,
+    pub supported: bool,
+    /// Why the capability is unavailable (`None` when supported)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Netem capabilities of a backend (Reply)
+/// Query Service: tcgui/{backend_name}/query/capabilities
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NetemCapabilities {
+    /// Kernel release the backend runs on (e.g. "6.8.0-45-generic"), if known
+    pub kernel_release: Option<String>,
+    /// iproute2 version of the `tc` the backend runs commands with (e.g.
+    /// "6.1.0"), if it could be found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tc_version: Option<String>,
+    /// One entry per capability the backend knows about
+    pub features: Vec<NetemFeatureSupport>,
+}
+
+impl NetemCapabilities {
+    /// Whether `capability` is reported as supported. Capabilities the
+    /// backend doesn't list (an older backend) count as unsupported.
+    pub fn supports(&self, capability: NetemCapability) -> bool {
+        self.features
+            .iter()
+            .any(|f| f.capability == capability && f.supported)
+    }
+
+    /// Why `capability` can't be applied, or `None` when it can.
+    pub fn unsupported_reason(&self, capability: NetemCapability) -> Option<String> {
+        if self.supports(capability) {
+            return None;
+        }
+        Some(
+            self.features
+                .iter()
+                .find(|f| f.capability == capability)
+                .and_then(|f| f.reason.clone())
+                .unwrap_or_else(|| "not reported by the backend".to_string()),
+        )
+    }
+
+    /// Check that every impairment `config` enables can be applied here.
+    ///
+    /// The kernel accepts some netem parameters it doesn't implement and
+    /// silently ignores them, so a config relying on one is rejected up
+    /// front, naming the feature and the missing capability. Disabled
+    /// features are not checked.
+    pub fn check_config(
+        &self,
+        config: &TcNetemConfig,
+    ) -> Result<(), (TcFeature, TcValidationError)> {
+        // (feature, capability, field, whether the config relies on it)
+        let required = [
+            (
+                TcFeature::Loss,
+                NetemCapability::Loss,
+                "percentage",
+                config.loss.enabled && config.loss.percentage > 0.0,
+            ),
+            (
+                TcFeature::Loss,
+                NetemCapability::LossGilbertElliott,
+                "model",
+                config.loss.enabled && config.loss.model.gemodel().is_some(),
+            ),
+            (
+                TcFeature::Loss,
+                NetemCapability::Ecn,
+                "ecn",
+                config.loss.enabled && config.loss.ecn,
+            ),
+            (
+                TcFeature::Delay,
+                NetemCapability::Delay,
+                "base_ms",
+                config.delay.enabled,
+            ),
+            (
+                TcFeature::Delay,
+                NetemCapability::Jitter,
+                "jitter_ms",
+                config.delay.enabled && config.delay.jitter_ms > 0.0,
+            ),
+            (
+                TcFeature::Delay,
+                NetemCapability::DelayDistribution,
+                "distribution",
+                config.delay.enabled
+                    && config.delay.jitter_ms > 0.0
+                    && !config.delay.distribution.is_uniform(),
+            ),
+            (
+                TcFeature::Duplicate,
+                NetemCapability::Duplicate,
+                "percentage",
+                config.duplicate.enabled,
+            ),
+            (
+                TcFeature::Reorder,
+                NetemCapability::Reorder,
+                "percentage",
+                config.reorder.enabled,
+            ),
+            (
+                TcFeature::Corrupt,
+                NetemCapability::Corrupt,
+                "percentage",
+                config.corrupt.enabled,
+            ),
+            (
+                TcFeature::RateLimit,
+                NetemCapability::Rate,
+                "rate_kbps",
+                config.rate_limit.enabled,
+            ),
+            (
+                TcFeature::Slot,
+                NetemCapability::Slot,
+                "max_delay_ms",
+                config.slot.enabled,
+            ),
+        ];
+        for (feature, capability, field, used) in required {
+            if !used {
+                continue;
+            }
+            if let Some(reason) = self.unsupported_reason(capability) {
+                return Err((
+                    feature,
+                    TcValidationError {
+                        field: field.to_string(),
+                        message: format!(
+                            "{:?} is not supported by the backend: {}",
+                            capability, reason
+                        ),
+                    },
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The configurable features that can't be applied, with the reason.
+    pub fn unsupported_features(&self) -> Vec<(TcFeature, String)> {
+        [
+            TcFeature::Loss,
+            TcFeature::Delay,
+            TcFeature::Duplicate,
+            TcFeature::Reorder,
+            TcFeature::Corrupt,
+            TcFeature::RateLimit,
+            TcFeature::Slot,
+        ]
+        .into_iter()
+        .filter_map(|feature| {
+            let reason = self.unsupported_reason(NetemCapability::for_feature(feature))?;
+            Some((feature, reason))
+        })
+        .collect()
+    }
+}
+
+/// Result of a forced refresh (Reply)
+/// Query Service: tcgui/{backend_name}/query/refresh
+///
+/// A forced refresh rediscovers interfaces and republishes every interface
+/// record and TC configuration, whether or not anything changed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ForceRefreshResponse {
+    /// Interface records republished
+    pub interface_count: usize,
+    /// Interfaces republished with an active TC configuration
+    pub tc_config_count: usize,
+}
+
 /// Comprehensive diagnostic results
 #[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct DiagnosticsResults {
@@ -1128,6 +3600,88 @@ pub struct DiagnosticsResults {
     pub configured_tc: Option<TcNetemConfig>,
     /// TC qdisc statistics (if netem is configured)
     pub tc_stats: Option<TcDiagnosticStats>,
+    /// Summary of a ping diagnostic (only for [`DiagnosticsTest::Ping`])
+    #[serde(default)]
+    pub ping: Option<PingResult>,
+    /// Outcome of a throughput probe (only for
+    /// [`DiagnosticsTest::Throughput`])
+    #[serde(default)]
+    pub throughput: Option<ThroughputResult>,
+    /// What the kernel has on the interface (only for
+    /// [`DiagnosticsTest::ReconcileTc`])
+    #[serde(default)]
+    pub reconciled: Option<TcReconciliation>,
+}
+
+/// The interface's impairment as read back from the kernel
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcReconciliation {
+    /// Root qdisc as the kernel reports it, e.g. "qdisc netem root"; empty
+    /// without one
+    pub qdisc: String,
+    /// Impairment configured by that qdisc, None when it impairs nothing
+    pub configuration: Option<TcConfiguration>,
+}
+
+/// Rate achieved by a throughput probe, next to the interface's rate limit
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ThroughputResult {
+    /// TCP sink the probe sent to ("host:port"), None for loopback
+    pub target: Option<String>,
+    /// Bytes sent while measuring
+    pub bytes: u64,
+    /// Length of the measurement in milliseconds
+    pub measured_ms: u64,
+    /// Achieved throughput in kbit/s
+    pub throughput_kbps: Option<f64>,
+    /// Rate limit configured on the interface, None without one and for a
+    /// loopback probe, which the limit doesn't apply to
+    pub configured_rate_kbps: Option<u32>,
+    /// Why the probe failed (connection refused, link down, ...)
+    pub error: Option<String>,
+}
+
+impl ThroughputResult {
+    /// Throughput as a percentage of the configured rate limit.
+    pub fn rate_percent(&self) -> Option<f64> {
+        let rate_kbps = self.configured_rate_kbps.filter(|rate| *rate > 0)?;
+        self.throughput_kbps
+            .map(|kbps| kbps * 100.0 / rate_kbps as f64)
+    }
+}
+
+/// Summary printed by one ping run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PingResult {
+    /// Host or address that was pinged
+    pub target: String,
+    /// Echo requests sent
+    pub transmitted: u32,
+    /// Echo replies received
+    pub received: u32,
+    /// Packet loss percentage (0.0-100.0)
+    pub packet_loss_percent: f32,
+    /// Round-trip times, when at least one reply came back
+    pub rtt: Option<RttSummary>,
+    /// Why ping gave no summary (unresolvable host, timeout, ...)
+    pub error: Option<String>,
+}
+
+impl PingResult {
+    /// Whether any echo reply came back
+    pub fn reachable(&self) -> bool {
+        self.error.is_none() && self.received > 0
+    }
+}
+
+/// Round-trip time summary of a ping run, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RttSummary {
+    pub min_ms: f32,
+    pub avg_ms: f32,
+    pub max_ms: f32,
+    /// Standard deviation; busybox ping doesn't report it
+    pub mdev_ms: Option<f32>,
 }
 
 /// TC diagnostic statistics showing qdisc effectiveness
@@ -1186,31 +3740,261 @@ pub struct LatencyResult {
     pub samples: u32,
 }
 
-/// Quality of Service configuration for different message types
+/// Quality of Service configuration for different message types.
+///
+/// The constants are the defaults; a backend can override them per message
+/// type (`--qos TYPE=RELIABILITY,CONGESTION,HISTORY`), e.g. to make bandwidth
+/// updates reliable or keep a deeper history of interface events.
 pub mod qos {
+    use std::fmt;
+    use std::str::FromStr;
     use zenoh::qos::{CongestionControl, Reliability};
 
-    /// QoS configuration tuple: (reliability, congestion_control, history_depth)
-    pub type QosConfig = (Reliability, CongestionControl, Option<usize>);
+    /// Reliability, congestion control and history depth of one message type
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct QosProfile {
+        pub reliability: Reliability,
+        pub congestion_control: CongestionControl,
+        /// Samples cached for late joiners; `None` for no cache
+        pub history_depth: Option<usize>,
+    }
 
     /// QoS for interface list updates - reliable, keep last 1
-    pub const INTERFACE_LIST: QosConfig =
-        (Reliability::Reliable, CongestionControl::Block, Some(1));
+    pub const INTERFACE_LIST: QosProfile =
+        QosProfile::new(Reliability::Reliable, CongestionControl::Block, Some(1));
 
     /// QoS for bandwidth updates - best effort, no history, drop on congestion
-    pub const BANDWIDTH_UPDATES: QosConfig =
-        (Reliability::BestEffort, CongestionControl::Drop, None);
+    pub const BANDWIDTH_UPDATES: QosProfile =
+        QosProfile::new(Reliability::BestEffort, CongestionControl::Drop, None);
 
     /// QoS for interface events - reliable, keep last 10
-    pub const INTERFACE_EVENTS: QosConfig =
-        (Reliability::Reliable, CongestionControl::Block, Some(10));
+    pub const INTERFACE_EVENTS: QosProfile =
+        QosProfile::new(Reliability::Reliable, CongestionControl::Block, Some(10));
 
     /// QoS for backend health - reliable, keep last 1
-    pub const BACKEND_HEALTH: QosConfig =
-        (Reliability::Reliable, CongestionControl::Block, Some(1));
+    pub const BACKEND_HEALTH: QosProfile =
+        QosProfile::new(Reliability::Reliable, CongestionControl::Block, Some(1));
+
+    /// Largest accepted history depth
+    pub const MAX_HISTORY_DEPTH: usize = 1000;
 
     /// Query/Reply timeout in milliseconds
     pub const QUERY_TIMEOUT_MS: u64 = 5000;
+
+    impl QosProfile {
+        pub const fn new(
+            reliability: Reliability,
+            congestion_control: CongestionControl,
+            history_depth: Option<usize>,
+        ) -> Self {
+            Self {
+                reliability,
+                congestion_control,
+                history_depth,
+            }
+        }
+
+        /// Reject combinations that can't be what the operator meant: an
+        /// empty or oversized history, or a history deeper than one sample on
+        /// best-effort delivery, where the cached samples are the ones that
+        /// may already be lost.
+        pub fn validate(&self) -> Result<(), String> {
+            match self.history_depth {
+                Some(0) => Err("history depth 0: use 'none' for no history".to_string()),
+                Some(depth) if depth > MAX_HISTORY_DEPTH => Err(format!(
+                    "history depth {} exceeds the maximum of {}",
+                    depth, MAX_HISTORY_DEPTH
+                )),
+                Some(depth) if depth > 1 && self.reliability == Reliability::BestEffort => {
+                    Err(format!(
+                        "best_effort with a history of {} samples: use reliable, or a depth of 1",
+                        depth
+                    ))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Parses `RELIABILITY,CONGESTION,HISTORY`, e.g. `reliable,block,10` or
+    /// `best_effort,drop,none`.
+    impl FromStr for QosProfile {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let parts: Vec<&str> = s.split(',').map(str::trim).collect();
+            let [reliability, congestion, history] = parts[..] else {
+                return Err(format!(
+                    "Invalid QoS '{}'. Expected RELIABILITY,CONGESTION,HISTORY (e.g. reliable,block,10)",
+                    s
+                ));
+            };
+            let reliability = match reliability.to_lowercase().as_str() {
+                "reliable" => Reliability::Reliable,
+                "best_effort" | "besteffort" => Reliability::BestEffort,
+                other => {
+                    return Err(format!(
+                        "Invalid reliability '{}'. Must be 'reliable' or 'best_effort'",
+                        other
+                    ));
+                }
+            };
+            let congestion_control = match congestion.to_lowercase().as_str() {
+                "block" => CongestionControl::Block,
+                "drop" => CongestionControl::Drop,
+                other => {
+                    return Err(format!(
+                        "Invalid congestion control '{}'. Must be 'block' or 'drop'",
+                        other
+                    ));
+                }
+            };
+            let history_depth = match history.to_lowercase().as_str() {
+                "none" => None,
+                depth => Some(depth.parse().map_err(|_| {
+                    format!(
+                        "Invalid history depth '{}'. Must be a number or 'none'",
+                        depth
+                    )
+                })?),
+            };
+            Ok(Self::new(reliability, congestion_control, history_depth))
+        }
+    }
+
+    impl fmt::Display for QosProfile {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let reliability = match self.reliability {
+                Reliability::Reliable => "reliable",
+                Reliability::BestEffort => "best_effort",
+            };
+            let congestion = match self.congestion_control {
+                CongestionControl::Block => "block",
+                _ => "drop",
+            };
+            match self.history_depth {
+                Some(depth) => write!(f, "{},{},{}", reliability, congestion, depth),
+                None => write!(f, "{},{},none", reliability, congestion),
+            }
+        }
+    }
+
+    /// Message types whose QoS can be configured
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum QosMessageType {
+        /// Per-interface state documents
+        InterfaceList,
+        /// Bandwidth telemetry
+        BandwidthUpdates,
+        /// TC configuration changes on interfaces
+        InterfaceEvents,
+        /// Backend health documents
+        BackendHealth,
+    }
+
+    impl QosMessageType {
+        /// Every configurable message type
+        pub const ALL: [QosMessageType; 4] = [
+            Self::InterfaceList,
+            Self::BandwidthUpdates,
+            Self::InterfaceEvents,
+            Self::BackendHealth,
+        ];
+
+        /// Name used on the command line and in configuration dumps
+        pub fn name(&self) -> &'static str {
+            match self {
+                Self::InterfaceList => "interface_list",
+                Self::BandwidthUpdates => "bandwidth_updates",
+                Self::InterfaceEvents => "interface_events",
+                Self::BackendHealth => "backend_health",
+            }
+        }
+    }
+
+    impl FromStr for QosMessageType {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let s = s.to_lowercase().replace('-', "_");
+            Self::ALL
+                .into_iter()
+                .find(|kind| kind.name() == s)
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid QoS message type '{}'. Must be one of: {}",
+                        s,
+                        Self::ALL.map(|kind| kind.name()).join(", ")
+                    )
+                })
+        }
+    }
+
+    /// QoS of every message type, defaulting to the constants above
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct QosProfiles {
+        pub interface_list: QosProfile,
+        pub bandwidth_updates: QosProfile,
+        pub interface_events: QosProfile,
+        pub backend_health: QosProfile,
+    }
+
+    impl Default for QosProfiles {
+        fn default() -> Self {
+            Self {
+                interface_list: INTERFACE_LIST,
+                bandwidth_updates: BANDWIDTH_UPDATES,
+                interface_events: INTERFACE_EVENTS,
+                backend_health: BACKEND_HEALTH,
+            }
+        }
+    }
+
+    impl QosProfiles {
+        /// Defaults with `TYPE=PROFILE` overrides applied in order, e.g.
+        /// `bandwidth_updates=reliable,block,none`. Every override is
+        /// validated.
+        pub fn with_overrides<'a>(
+            overrides: impl IntoIterator<Item = &'a str>,
+        ) -> Result<Self, String> {
+            let mut profiles = Self::default();
+            for entry in overrides {
+                let (kind, profile) = entry.split_once('=').ok_or_else(|| {
+                    format!(
+                        "Invalid QoS override '{}'. Expected TYPE=RELIABILITY,CONGESTION,HISTORY",
+                        entry
+                    )
+                })?;
+                let kind: QosMessageType = kind.trim().parse()?;
+                let profile: QosProfile = profile.parse()?;
+                profile
+                    .validate()
+                    .map_err(|e| format!("Invalid QoS for {}: {}", kind.name(), e))?;
+                profiles.set(kind, profile);
+            }
+            Ok(profiles)
+        }
+
+        /// QoS of one message type
+        pub fn get(&self, kind: QosMessageType) -> QosProfile {
+            match kind {
+                QosMessageType::InterfaceList => self.interface_list,
+                QosMessageType::BandwidthUpdates => self.bandwidth_updates,
+                QosMessageType::InterfaceEvents => self.interface_events,
+                QosMessageType::BackendHealth => self.backend_health,
+            }
+        }
+
+        /// Replace the QoS of one message type
+        pub fn set(&mut self, kind: QosMessageType, profile: QosProfile) {
+            match kind {
+                QosMessageType::InterfaceList => self.interface_list = profile,
+                QosMessageType::BandwidthUpdates => self.bandwidth_updates = profile,
+                QosMessageType::InterfaceEvents => self.interface_events = profile,
+                QosMessageType::BackendHealth => self.backend_health = profile,
+            }
+        }
+    }
 }
 
 /// Zenoh session configuration
@@ -1731,6 +4515,110 @@ pub struct NetworkInterface {
     /// GUI show the link capacity and flag rate caps that exceed it.
     #[serde(default)]
     pub link_speed_mbps: Option<u32>,
+    /// Interface MTU in bytes, when reported by the kernel
+    #[serde(default)]
+    pub mtu: Option<u32>,
+    /// For a veth, the interface index of its peer as reported by the kernel
+    /// (IFLA_LINK). The index is local to the peer's namespace, so it only
+    /// identifies the peer together with [`NetworkInterface::is_veth_peer_of`].
+    #[serde(default)]
+    pub peer_index: Option<u32>,
+    /// Whether the backend's Zenoh traffic goes through this interface, so
+    /// impairing it can cut the GUI off from the backend
+    #[serde(default)]
+    pub management: bool,
+    /// Operator-provided description from the backend's configuration (e.g.
+    /// "upstream ISP"), shared by every frontend. `None` when not configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl NetworkInterface {
+    /// Whether `self` and `other` look like the two ends of one veth pair:
+    /// each one's peer index names the other.
+    pub fn is_veth_peer_of(&self, other: &NetworkInterface) -> bool {
+        !(self.namespace == other.namespace && self.name == other.name)
+            && self.peer_index == Some(other.index)
+            && other.peer_index == Some(self.index)
+    }
+}
+
+/// Find the other end of the veth `interface` among `candidates` (typically
+/// every interface of every namespace the backend knows about).
+///
+/// Interface indexes are only unique within a namespace, so two unrelated
+/// pairs can look alike; an ambiguous match is treated as no match.
+pub fn find_veth_peer<'a>(
+    candidates: impl IntoIterator<Item = &'a NetworkInterface>,
+    interface: &NetworkInterface,
+) -> Option<&'a NetworkInterface> {
+    interface.peer_index?;
+    let mut matches = candidates
+        .into_iter()
+        .filter(|candidate| candidate.is_veth_peer_of(interface));
+    let peer = matches.next()?;
+    matches.next().is_none().then_some(peer)
+}
+
+/// Ratio between the two ends of a veth pair at which a rate or delay counts
+/// as significantly asymmetric.
+pub const VETH_ASYMMETRY_RATIO: f64 = 10.0;
+
+/// Compare the two ends of a veth pair and describe mismatches that make link
+/// emulation behave confusingly: different MTUs, or rates or delays that
+/// differ by [`VETH_ASYMMETRY_RATIO`] or more. Each message is worded from
+/// `ours`' side (e.g. "peer has 10x lower rate"). Only values both ends
+/// report are compared; these are warnings, not errors.
+pub fn veth_asymmetry(
+    ours: &NetworkInterface,
+    our_config: Option<&TcConfiguration>,
+    peer: &NetworkInterface,
+    peer_config: Option<&TcConfiguration>,
+) -> Vec<String> {
+    fn ratio_warning(label: &str, unit: &str, ours: f64, theirs: f64) -> Option<String> {
+        if ours <= 0.0 || theirs <= 0.0 {
+            return None;
+        }
+        let ratio = ours.max(theirs) / ours.min(theirs);
+        (ratio >= VETH_ASYMMETRY_RATIO).then(|| {
+            format!(
+                "peer has {:.0}x {} {} ({}{unit} vs {}{unit} here)",
+                ratio,
+                if theirs > ours { "higher" } else { "lower" },
+                label,
+                theirs,
+                ours
+            )
+        })
+    }
+
+    let mut warnings = Vec::new();
+    if let (Some(our_mtu), Some(peer_mtu)) = (ours.mtu, peer.mtu)
+        && our_mtu != peer_mtu
+    {
+        warnings.push(format!("peer has MTU {} (here {})", peer_mtu, our_mtu));
+    }
+    if let (Some(our_config), Some(peer_config)) = (our_config, peer_config) {
+        if let (Some(our_rate), Some(peer_rate)) =
+            (our_config.rate_limit_kbps, peer_config.rate_limit_kbps)
+        {
+            warnings.extend(ratio_warning(
+                "rate",
+                " kbps",
+                our_rate as f64,
+                peer_rate as f64,
+            ));
+        }
+        if let (Some(our_delay), Some(peer_delay)) = (our_config.delay_ms, peer_config.delay_ms) {
+            warnings.extend(ratio_warning(
+                "delay",
+                " ms",
+                our_delay as f64,
+                peer_delay as f64,
+            ));
+        }
+    }
+    warnings
 }
 
 /// Classification of network interface types.
@@ -1759,7 +4647,7 @@ pub enum InterfaceType {
 /// Comprehensive network bandwidth statistics and rates.
 ///
 /// Contains both cumulative counters (total bytes/packets since interface creation)
-/// and calculated rates (bytes per second) for real-time monitoring. Statistics
+/// and calculated per-second rates of each counter for real-time monitoring. Statistics
 /// are collected from `/proc/net/dev` with additional rate calculations.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct NetworkBandwidthStats {
@@ -1785,6 +4673,24 @@ pub struct NetworkBandwidthStats {
     pub rx_bytes_per_sec: f64,
     /// Current transmit rate in bytes per second (calculated from deltas)
     pub tx_bytes_per_sec: f64,
+    /// Current receive rate in packets per second (calculated from deltas)
+    #[serde(default)]
+    pub rx_packets_per_sec: f64,
+    /// Current transmit rate in packets per second (calculated from deltas)
+    #[serde(default)]
+    pub tx_packets_per_sec: f64,
+    /// Receive errors per second (calculated from deltas)
+    #[serde(default)]
+    pub rx_errors_per_sec: f64,
+    /// Transmit errors per second (calculated from deltas)
+    #[serde(default)]
+    pub tx_errors_per_sec: f64,
+    /// Received packets dropped per second (calculated from deltas)
+    #[serde(default)]
+    pub rx_dropped_per_sec: f64,
+    /// Transmitted packets dropped per second (calculated from deltas)
+    #[serde(default)]
+    pub tx_dropped_per_sec: f64,
 }
 
 /// Type of network interface state change event.
@@ -1809,104 +4715,996 @@ pub enum InterfaceEventType {
 mod tests {
     use super::*;
 
-    #[test]
-    fn hostile_netlink_names_produce_safe_keys() {
-        // Names that Linux `dev_valid_name()` permits but that used to either
-        // panic the daemon or make it publish on a wildcard key. The generated
-        // constructors slug at the boundary (RFC 03 §2 / 08 §1.2), so every
-        // per-interface builder returns a concrete, wildcard-free key.
-        use crate::registry::tc;
-        let o = crate::identity::local_origin_from_seed("test");
-        for name in ["*", "**", "?", "#", "$x"] {
-            for key in [
-                tc::key(&o, &tc::Subject::bandwidth("myns", name)),
-                tc::key(&o, &tc::Subject::config("myns", name)),
-                tc::key(&o, &tc::Subject::qdisc("myns", name)),
-                tc::key(&o, &tc::Subject::execution("myns", name)),
-            ] {
-                let s = key.as_str();
-                assert!(
-                    !s.contains('*'),
-                    "wildcard leaked into key {s:?} for {name:?}"
-                );
-                assert!(!s.contains(['?', '#', '$']), "reserved char in key {s:?}");
-            }
+    #[test]
+    fn hostile_netlink_names_produce_safe_keys() {
+        // Names that Linux `dev_valid_name()` permits but that used to either
+        // panic the daemon or make it publish on a wildcard key. The generated
+        // constructors slug at the boundary (RFC 03 §2 / 08 §1.2), so every
+        // per-interface builder returns a concrete, wildcard-free key.
+        use crate::registry::tc;
+        let o = crate::identity::local_origin_from_seed("test");
+        for name in ["*", "**", "?", "#", "$x"] {
+            for key in [
+                tc::key(&o, &tc::Subject::bandwidth("myns", name)),
+                tc::key(&o, &tc::Subject::config("myns", name)),
+                tc::key(&o, &tc::Subject::qdisc("myns", name)),
+                tc::key(&o, &tc::Subject::execution("myns", name)),
+            ] {
+                let s = key.as_str();
+                assert!(
+                    !s.contains('*'),
+                    "wildcard leaked into key {s:?} for {name:?}"
+                );
+                assert!(!s.contains(['?', '#', '$']), "reserved char in key {s:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn clean_names_keep_their_wire_key() {
+        // The declared key starts at `v1/` (the `tcgui` base is the session
+        // namespace, prefixed transparently) and slugging is identity on clean
+        // names.
+        use crate::identity::ConcreteOrigin as _;
+        use crate::registry::tc;
+        let o = crate::identity::local_origin_from_seed("test");
+        let key = tc::key(&o, &tc::Subject::config("myns", "eth0.100"));
+        assert_eq!(
+            key.as_str(),
+            format!("v1/{}/state/tc/config/myns/eth0.100", o.chunk())
+        );
+    }
+
+    #[test]
+    fn state_key_roundtrips_through_parser() {
+        use crate::identity::ConcreteOrigin as _;
+        use crate::registry::tc;
+        let o = crate::identity::local_origin_from_seed("test");
+        let key = tc::key(&o, &tc::Subject::interface("default", "eth0"));
+        let parsed = topics::parse_state_key(key.as_str()).expect("parse");
+        assert_eq!(parsed.origin, o.chunk());
+        assert_eq!(parsed.subject, tc::Subject::interface("default", "eth0"));
+        assert_eq!(parsed.subject.family(), tc::Family::Interface);
+    }
+
+    #[test]
+    fn payload_type_follows_the_registry() {
+        use crate::registry::tc;
+        let o = crate::identity::local_origin_from_seed("test");
+        let state = tc::key(&o, &tc::Subject::config("default", "eth0"));
+        assert_eq!(topics::payload_type(state.as_str()), Some("TcConfigUpdate"));
+        let telemetry = tc::key(&o, &tc::Subject::bandwidth("default", "eth0"));
+        assert_eq!(
+            topics::payload_type(telemetry.as_str()),
+            Some("BandwidthUpdate")
+        );
+
+        let call = tc::interfaces_ns_set_key(&o, "lab");
+        assert_eq!(
+            topics::parse_procedure(call.as_str()),
+            Some(tc::ProcedureId::InterfacesNsSet)
+        );
+        assert_eq!(
+            topics::payload_type(call.as_str()),
+            Some("InterfaceControlBatchResponse")
+        );
+        let clear = tc::configs_ns_clear_key(&o, "lab");
+        assert_eq!(
+            topics::parse_procedure(clear.as_str()),
+            Some(tc::ProcedureId::ConfigsNsClear)
+        );
+        assert_eq!(
+            topics::payload_type(clear.as_str()),
+            Some("TcFeatureClearResponse")
+        );
+        let diagnostics = tc::diagnostics_namespace_key(&o);
+        assert_eq!(
+            topics::parse_procedure(diagnostics.as_str()),
+            Some(tc::ProcedureId::DiagnosticsNamespace)
+        );
+        let tree = tc::diagnostics_tree_key(&o);
+        assert_eq!(topics::payload_type(tree.as_str()), Some("TcTreeResponse"));
+        let compare = tc::diagnostics_ns_iface_rate_compare_key(&o, "lab", "eth0");
+        assert_eq!(
+            topics::parse_procedure(compare.as_str()),
+            Some(tc::ProcedureId::DiagnosticsNsIfaceRateCompare)
+        );
+        assert_eq!(
+            topics::payload_type(compare.as_str()),
+            Some("RateComparisonResponse")
+        );
+        let history = tc::config_ns_iface_history_key(&o, "lab", "eth0");
+        assert_eq!(
+            topics::parse_procedure(history.as_str()),
+            Some(tc::ProcedureId::ConfigNsIfaceHistory)
+        );
+        assert_eq!(
+            topics::payload_type(history.as_str()),
+            Some("ConfigHistoryResponse")
+        );
+        let snapshot = tc::bandwidth_ns_iface_snapshot_key(&o, "lab", "eth0");
+        assert_eq!(
+            topics::parse_procedure(snapshot.as_str()),
+            Some(tc::ProcedureId::BandwidthNsIfaceSnapshot)
+        );
+        assert_eq!(
+            topics::payload_type(snapshot.as_str()),
+            Some("StatsResponse")
+        );
+        let network = tc::network_ns_set_key(&o, "lab");
+        assert_eq!(
+            topics::parse_procedure(network.as_str()),
+            Some(tc::ProcedureId::NetworkNsSet)
+        );
+        assert_eq!(
+            topics::payload_type(network.as_str()),
+            Some("NetworkOperationResponse")
+        );
+        assert_eq!(topics::payload_type("somewhere/else"), None);
+    }
+
+    /// RFC 08 §7 totality: the served SchemaSet covers every type name the
+    /// registry references (build_verified panics otherwise — this test
+    /// forces the static's evaluation in CI).
+    #[test]
+    fn served_schemas_cover_the_registry() {
+        let set = &*crate::schema::SCHEMAS;
+        let names = crate::schema::schema_type_names();
+        assert!(set.len() >= names.len());
+        assert!(
+            set.get("NetworkInterface")
+                .unwrap()
+                .json_document()
+                .is_some()
+        );
+        // The introspect raw-TOML sentinel is deliberately not schema'd.
+        assert!(set.verify_covers(&names).is_ok());
+        assert!(!names.contains(&"toml"));
+    }
+
+    #[test]
+    fn test_tc_loss_config_validation() {
+        let mut config = TcLossConfig {
+            enabled: true,
+            percentage: 150.0, // Invalid
+            correlation: 50.0,
+            model: LossModel::Random,
+            ecn: false,
+        };
+
+        assert!(config.validate().is_err());
+
+        config.percentage = 50.0; // Valid
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_loss_ecn() {
+        let mut config = TcNetemConfig::new();
+        config.loss.ecn = true;
+        assert_eq!(config.loss.validate().unwrap_err().field, "ecn");
+
+        config.loss.enabled = true;
+        config.loss.percentage = 2.0;
+        assert!(config.loss.validate().is_ok());
+        let applied = config.to_legacy_params();
+        assert!(applied.loss_ecn);
+        assert_eq!(
+            applied.to_command("eth0"),
+            "tc qdisc replace dev eth0 root netem loss 2% ecn"
+        );
+        assert!(!applied.without_feature(TcFeature::Loss).loss_ecn);
+
+        // The flag rides along with a loss model too
+        let bursty = TcConfiguration {
+            loss_gemodel: Some(GeModel::default()),
+            ..applied.clone()
+        };
+        assert!(bursty.to_command("eth0").ends_with(" ecn"));
+
+        // Without loss there is nothing to mark
+        let restored = TcNetemConfig::from_legacy_params(
+            0.0, None, None, None, None, None, None, None, None, None, None, None, None,
+        )
+        .with_extensions(None, None, RateLimitMode::Netem, None, true, None);
+        assert!(!restored.loss.ecn);
+    }
+
+    #[test]
+    fn test_seed_round_trip() {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = 1.0;
+        config.seed = Some(42);
+        let applied = config.to_legacy_params();
+        assert_eq!(applied.seed, Some(42));
+        assert_eq!(
+            applied.to_command("eth0"),
+            "tc qdisc replace dev eth0 root netem loss 1% seed 42"
+        );
+        // Clearing an impairment keeps the seed, and it never shows as drift
+        assert_eq!(applied.without_feature(TcFeature::Loss).seed, Some(42));
+        let reseeded = TcConfiguration {
+            seed: Some(7),
+            ..applied.clone()
+        };
+        assert!(applied.diff(&reseeded).is_empty());
+
+        let restored = TcNetemConfig::from_legacy_params(
+            1.0, None, None, None, None, None, None, None, None, None, None, None, None,
+        )
+        .with_extensions(None, None, RateLimitMode::Netem, None, false, applied.seed);
+        assert_eq!(restored.seed, Some(42));
+    }
+
+    #[test]
+    fn test_gemodel_validation() {
+        let mut config = TcLossConfig {
+            enabled: true,
+            percentage: 0.0,
+            correlation: 0.0,
+            model: LossModel::GilbertElliott(GeModel::default()),
+            ecn: false,
+        };
+        assert!(config.is_active());
+        assert!(config.validate().is_ok());
+
+        let base = GeModel::default();
+        for (model, field) in [
+            (GeModel { p: 0.0, ..base }, "p"),
+            (GeModel { r: 0.0, ..base }, "r"),
+            (
+                GeModel {
+                    one_h: 101.0,
+                    ..base
+                },
+                "one_h",
+            ),
+            (
+                GeModel {
+                    one_k: -1.0,
+                    ..base
+                },
+                "one_k",
+            ),
+        ] {
+            config.model = LossModel::GilbertElliott(model);
+            let err = config.validate().unwrap_err();
+            assert_eq!(err.field, field);
+        }
+
+        // A disabled loss isn't checked
+        config.enabled = false;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_slot_validation() {
+        let mut slot = TcSlotConfig {
+            enabled: true,
+            ..TcSlotConfig::wifi_default()
+        };
+        assert!(slot.validate().is_ok());
+        assert_eq!(slot.clause(), "slot 10ms 20ms packets 32");
+
+        let base = slot.clone();
+        for (config, field) in [
+            (
+                TcSlotConfig {
+                    max_delay_ms: 5.0,
+                    ..base.clone()
+                },
+                "max_delay_ms",
+            ),
+            (
+                TcSlotConfig {
+                    min_delay_ms: 0.0,
+                    max_delay_ms: 0.0,
+                    ..base.clone()
+                },
+                "max_delay_ms",
+            ),
+            (
+                TcSlotConfig {
+                    packets: None,
+                    ..base.clone()
+                },
+                "packets",
+            ),
+            (
+                TcSlotConfig {
+                    packets: None,
+                    bytes: Some(0),
+                    ..base.clone()
+                },
+                "bytes",
+            ),
+        ] {
+            assert_eq!(config.validate().unwrap_err().field, field);
+        }
+
+        // Bytes alone are enough, and a disabled slot isn't checked
+        slot.packets = None;
+        slot.bytes = Some(3000);
+        assert!(slot.validate().is_ok());
+        assert_eq!(slot.clause(), "slot 10ms 20ms bytes 3000");
+        slot.bytes = None;
+        slot.enabled = false;
+        assert!(slot.validate().is_ok());
+    }
+
+    #[test]
+    fn test_fq_codel_validation() {
+        let fq_codel = FqCodelParams {
+            target_us: Some(5000),
+            interval_us: Some(100_000),
+            quantum: Some(1514),
+            ecn: Some(true),
+        };
+        assert!(fq_codel.validate().is_ok());
+        assert_eq!(
+            fq_codel.command("eth0"),
+            "tc qdisc replace dev eth0 root fq_codel target 5000us interval 100000us quantum 1514 ecn"
+        );
+        assert!(FqCodelParams::default().validate().is_ok());
+
+        for (params, field) in [
+            (
+                FqCodelParams {
+                    target_us: Some(0),
+                    ..fq_codel.clone()
+                },
+                "target_us",
+            ),
+            (
+                FqCodelParams {
+                    quantum: Some(0),
+                    ..fq_codel.clone()
+                },
+                "quantum",
+            ),
+            (
+                FqCodelParams {
+                    target_us: Some(200_000),
+                    ..fq_codel.clone()
+                },
+                "target_us",
+            ),
+        ] {
+            assert_eq!(params.validate().unwrap_err().field, field);
+        }
+    }
+
+    #[test]
+    fn test_cake_validation() {
+        let cake = CakeParams {
+            bandwidth_kbps: Some(10_000),
+            rtt_ms: Some(50),
+            diffserv: Some(CakeDiffserv::Diffserv4),
+        };
+        assert!(cake.validate().is_ok());
+        assert_eq!(
+            cake.command("eth0"),
+            "tc qdisc replace dev eth0 root cake bandwidth 10Mbit rtt 50ms diffserv4"
+        );
+        let odd_rate = CakeParams {
+            bandwidth_kbps: Some(1500),
+            ..CakeParams::default()
+        };
+        assert_eq!(odd_rate.bandwidth().as_deref(), Some("1500Kbit"));
+        assert!(CakeParams::default().validate().is_ok());
+
+        for (params, field) in [
+            (
+                CakeParams {
+                    bandwidth_kbps: Some(0),
+                    ..cake.clone()
+                },
+                "bandwidth_kbps",
+            ),
+            (
+                CakeParams {
+                    rtt_ms: Some(0),
+                    ..cake.clone()
+                },
+                "rtt_ms",
+            ),
+        ] {
+            assert_eq!(params.validate().unwrap_err().field, field);
+        }
+    }
+
+    #[test]
+    fn test_legacy_params_keep_default_reorder_gap() {
+        let mut config = TcNetemConfig::new();
+        config.reorder.enabled = true;
+        config.reorder.percentage = 25.0;
+        let applied = config.to_legacy_params();
+        assert_eq!(applied.reorder_percent, Some(25.0));
+        assert_eq!(applied.reorder_gap, Some(5));
+        let command = applied.to_command("eth0");
+        assert!(command.ends_with("reorder 25% gap 5"), "{}", command);
+
+        // Toggled on without moving the slider, the gap is still carried
+        config.reorder.percentage = 0.0;
+        let applied = config.to_legacy_params();
+        assert_eq!(applied.reorder_percent, None);
+        assert_eq!(applied.reorder_gap, Some(5));
+        // ... but no reorder clause is assembled around it
+        let command = applied.to_command("eth0");
+        assert!(!command.contains("gap"), "{}", command);
+
+        config.reorder.enabled = false;
+        assert_eq!(config.to_legacy_params().reorder_gap, None);
+    }
+
+    #[test]
+    fn test_red_validation() {
+        let red = RedParams {
+            limit: Some(400_000),
+            min: Some(30_000),
+            max: Some(90_000),
+            avpkt: Some(1000),
+            burst: Some(55),
+            probability: Some(0.02),
+            bandwidth: Some("10mbit".to_string()),
+        };
+        assert!(red.validate().is_ok());
+        assert_eq!(
+            red.command("eth0"),
+            "tc qdisc replace dev eth0 root red limit 400000 min 30000 max 90000 \
+             avpkt 1000 burst 55 probability 0.02 bandwidth 10mbit"
+        );
+
+        for (params, field) in [
+            (
+                RedParams {
+                    limit: None,
+                    ..red.clone()
+                },
+                "limit",
+            ),
+            (
+                RedParams {
+                    avpkt: Some(0),
+                    ..red.clone()
+                },
+                "avpkt",
+            ),
+            (
+                RedParams {
+                    min: Some(90_000),
+                    ..red.clone()
+                },
+                "min",
+            ),
+            (
+                RedParams {
+                    max: Some(500_000),
+                    ..red.clone()
+                },
+                "max",
+            ),
+            (
+                RedParams {
+                    probability: Some(1.5),
+                    ..red.clone()
+                },
+                "probability",
+            ),
+            (
+                RedParams {
+                    probability: Some(f32::NAN),
+                    ..red.clone()
+                },
+                "probability",
+            ),
+        ] {
+            assert_eq!(params.validate().unwrap_err().field, field);
+        }
+
+        // tc derives the thresholds it isn't given
+        let minimal = RedParams {
+            limit: Some(400_000),
+            avpkt: Some(1000),
+            ..RedParams::default()
+        };
+        assert!(minimal.validate().is_ok());
+        assert_eq!(
+            minimal.command("eth0"),
+            "tc qdisc replace dev eth0 root red limit 400000 avpkt 1000"
+        );
+    }
+
+    #[test]
+    fn test_sfq_validation() {
+        let sfq = SfqParams {
+            perturb: Some(10),
+            quantum: Some(1514),
+            limit: Some(127),
+        };
+        assert!(sfq.validate().is_ok());
+        assert_eq!(
+            sfq.command("eth0"),
+            "tc qdisc replace dev eth0 root sfq perturb 10 quantum 1514 limit 127"
+        );
+        assert!(SfqParams::default().validate().is_ok());
+
+        for (params, field) in [
+            (
+                SfqParams {
+                    quantum: Some(0),
+                    ..sfq.clone()
+                },
+                "quantum",
+            ),
+            (
+                SfqParams {
+                    limit: Some(0),
+                    ..sfq.clone()
+                },
+                "limit",
+            ),
+            (
+                SfqParams {
+                    perturb: Some(u32::MAX),
+                    ..sfq.clone()
+                },
+                "perturb",
+            ),
+        ] {
+            assert_eq!(params.validate().unwrap_err().field, field);
+        }
+    }
+
+    #[test]
+    fn test_prio_validation() {
+        let prio = PrioParams {
+            bands: Some(4),
+            priomap: Some(vec![1, 2, 3, 3, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1]),
+        };
+        assert!(prio.validate().is_ok());
+        assert_eq!(
+            prio.command("eth0"),
+            "tc qdisc replace dev eth0 root prio bands 4 priomap 1 2 3 3 0 0 0 0 1 1 1 1 1 1 1 1"
+        );
+        assert!(PrioParams::default().validate().is_ok());
+
+        for (params, field) in [
+            (
+                PrioParams {
+                    bands: Some(1),
+                    priomap: None,
+                },
+                "bands",
+            ),
+            (
+                PrioParams {
+                    bands: Some(17),
+                    priomap: None,
+                },
+                "bands",
+            ),
+            (
+                PrioParams {
+                    priomap: Some(vec![0; 17]),
+                    ..prio.clone()
+                },
+                "priomap",
+            ),
+            (
+                PrioParams {
+                    priomap: Some(Vec::new()),
+                    ..prio.clone()
+                },
+                "priomap",
+            ),
+            // Band 3 needs four bands; the default is three
+            (
+                PrioParams {
+                    bands: None,
+                    priomap: Some(vec![3]),
+                },
+                "priomap",
+            ),
+        ] {
+            assert_eq!(params.validate().unwrap_err().field, field);
+        }
+    }
+
+    #[test]
+    fn test_filter_spec_validation() {
+        let filter = TcFilterSpec {
+            dst_ip: Some("10.0.0.0/24".to_string()),
+            dst_port: Some(443),
+            protocol: Some(TcFilterProtocol::Tcp),
+            ..Default::default()
+        };
+        assert!(filter.validate().is_ok());
+        assert_eq!(
+            filter.u32_matches(),
+            vec![
+                "match ip dst 10.0.0.0/24",
+                "match ip dport 443 0xffff",
+                "match ip protocol 6 0xff",
+            ]
+        );
+        let v6 = TcFilterSpec {
+            src_ip: Some("fd00::1".to_string()),
+            protocol: Some(TcFilterProtocol::Icmp),
+            ..Default::default()
+        };
+        assert!(v6.validate().is_ok());
+        assert!(v6.is_ipv6());
+        assert_eq!(
+            v6.u32_matches(),
+            vec!["match ip6 src fd00::1/128", "match ip6 protocol 58 0xff"]
+        );
+
+        // At least one match field is required
+        assert_eq!(
+            TcFilterSpec::default().validate().unwrap_err().field,
+            "filter"
+        );
+        for (spec, field) in [
+            (
+                TcFilterSpec {
+                    src_ip: Some("10.0.0.300".to_string()),
+                    ..Default::default()
+                },
+                "src_ip",
+            ),
+            (
+                TcFilterSpec {
+                    dst_ip: Some("10.0.0.0/33".to_string()),
+                    ..Default::default()
+                },
+                "dst_ip",
+            ),
+            (
+                TcFilterSpec {
+                    src_ip: Some("10.0.0.1".to_string()),
+                    dst_ip: Some("fd00::1".to_string()),
+                    ..Default::default()
+                },
+                "dst_ip",
+            ),
+            (
+                TcFilterSpec {
+                    src_port: Some(0),
+                    ..Default::default()
+                },
+                "src_port",
+            ),
+            (
+                TcFilterSpec {
+                    dst_port: Some(53),
+                    protocol: Some(TcFilterProtocol::Icmp),
+                    ..Default::default()
+                },
+                "protocol",
+            ),
+        ] {
+            assert_eq!(spec.validate().unwrap_err().field, field);
+        }
+    }
+
+    #[test]
+    fn test_tc_netem_config_validation() {
+        let mut config = TcNetemConfig::new();
+
+        // Valid configuration
+        config.loss.enabled = true;
+        config.loss.percentage = 10.0;
+        assert!(config.validate().is_ok());
+
+        // Invalid configuration
+        config.delay.enabled = true;
+        config.delay.base_ms = -100.0; // Invalid negative delay
+        assert!(config.validate().is_err());
+
+        // The per-feature check names the offending impairment and field
+        let (feature, error) = config.validate_features().unwrap_err();
+        assert_eq!(feature, TcFeature::Delay);
+        assert_eq!(error.field, "base_ms");
+
+        config.delay.base_ms = 100.0;
+        config.reorder.enabled = true;
+        config.reorder.gap = 0;
+        let (feature, error) = config.validate_features().unwrap_err();
+        assert_eq!(feature, TcFeature::Reorder);
+        assert_eq!(error.field, "gap");
+
+        // A shaped distribution needs a jitter to shape
+        config.reorder.gap = 5;
+        config.delay.distribution = DelayDistribution::Pareto;
+        let (feature, error) = config.validate_features().unwrap_err();
+        assert_eq!(feature, TcFeature::Delay);
+        assert_eq!(error.field, "distribution");
+        config.delay.jitter_ms = 20.0;
+        assert!(config.validate().is_ok());
+        config.delay.jitter_ms = 0.0;
+        config.delay.distribution = DelayDistribution::Uniform;
+        assert!(config.validate().is_ok());
+    }
+
+    /// Capabilities of a kernel lacking `missing`
+    fn capabilities_without(missing: NetemCapability) -> NetemCapabilities {
+        NetemCapabilities {
+            kernel_release: Some("3.2.0".to_string()),
+            tc_version: None,
+            features: NetemCapability::ALL
+                .into_iter()
+                .map(|capability| NetemFeatureSupport {
+                    capability,
+                    supported: capability != missing,
+                    reason: (capability == missing)
+                        .then(|| "requires kernel 3.3 or newer (running 3.2)".to_string()),
+                })
+                .collect(),
         }
     }
 
     #[test]
-    fn clean_names_keep_their_wire_key() {
-        // The declared key starts at `v1/` (the `tcgui` base is the session
-        // namespace, prefixed transparently) and slugging is identity on clean
-        // names.
-        use crate::identity::ConcreteOrigin as _;
-        use crate::registry::tc;
-        let o = crate::identity::local_origin_from_seed("test");
-        let key = tc::key(&o, &tc::Subject::config("myns", "eth0.100"));
+    fn test_validation_rejects_unsupported_features() {
+        let mut config = TcNetemConfig::new();
+        config.loss.enabled = true;
+        config.loss.percentage = 5.0;
+        config.rate_limit.enabled = true;
+        config.rate_limit.rate_kbps = 1000;
+
+        let without_rate = capabilities_without(NetemCapability::Rate);
+        let (feature, error) = config.validate_for(&without_rate).unwrap_err();
+        assert_eq!(feature, TcFeature::RateLimit);
+        assert_eq!(error.field, "rate_kbps");
+        assert!(error.message.contains("Rate"), "{}", error.message);
+        assert!(error.message.contains("kernel 3.3"), "{}", error.message);
+
+        // A disabled feature doesn't need the capability
+        config.rate_limit.enabled = false;
+        assert!(config.validate_for(&without_rate).is_ok());
+
+        // Jitter is checked on its own: plain delay works without it
+        let without_jitter = capabilities_without(NetemCapability::Jitter);
+        config.delay.enabled = true;
+        config.delay.base_ms = 50.0;
+        assert!(config.validate_for(&without_jitter).is_ok());
+        config.delay.jitter_ms = 5.0;
+        let (feature, error) = config.validate_for(&without_jitter).unwrap_err();
+        assert_eq!(feature, TcFeature::Delay);
+        assert_eq!(error.field, "jitter_ms");
+
+        // ECN marking rides on loss
+        config.delay.jitter_ms = 0.0;
+        config.loss.ecn = true;
+        let without_ecn = capabilities_without(NetemCapability::Ecn);
+        let (feature, error) = config.validate_for(&without_ecn).unwrap_err();
+        assert_eq!(feature, TcFeature::Loss);
+        assert_eq!(error.field, "ecn");
+        config.loss.ecn = false;
+        assert!(config.validate_for(&without_ecn).is_ok());
+        config.delay.jitter_ms = 5.0;
+
+        // Range errors are still reported first
+        config.loss.percentage = 150.0;
+        let (feature, _) = config.validate_for(&without_jitter).unwrap_err();
+        assert_eq!(feature, TcFeature::Loss);
+
+        // A backend not listing a capability can't be relied on for it
+        config.loss.percentage = 5.0;
+        config.delay.enabled = false;
+        let (feature, error) = config
+            .validate_for(&NetemCapabilities::default())
+            .unwrap_err();
+        assert_eq!(feature, TcFeature::Loss);
+        assert!(error.message.contains("not reported"), "{}", error.message);
+    }
+
+    #[test]
+    fn test_interface_control_batch_summary_names_failures() {
+        let result = |interface: &str, success: bool| InterfaceControlTargetResult {
+            interface: interface.to_string(),
+            success,
+            message: String::new(),
+        };
+        let mut response = InterfaceControlBatchResponse {
+            namespace: "lab".to_string(),
+            operation: InterfaceControlOperation::Disable,
+            results: vec![result("veth0", true), result("veth1", true)],
+        };
+        assert_eq!(response.failures().count(), 0);
+        assert_eq!(response.summary(), "lab: disabled 2 interfaces");
+
+        response.results.push(result("veth2", false));
         assert_eq!(
-            key.as_str(),
-            format!("v1/{}/state/tc/config/myns/eth0.100", o.chunk())
+            response.summary(),
+            "lab: disabled 2 of 3 interfaces; failed: veth2"
+        );
+        response.operation = InterfaceControlOperation::SetMtu { mtu: 1400 };
+        assert_eq!(
+            response.summary(),
+            "lab: set MTU 1400 on 2 of 3 interfaces; failed: veth2"
         );
     }
 
     #[test]
-    fn state_key_roundtrips_through_parser() {
-        use crate::identity::ConcreteOrigin as _;
-        use crate::registry::tc;
-        let o = crate::identity::local_origin_from_seed("test");
-        let key = tc::key(&o, &tc::Subject::interface("default", "eth0"));
-        let parsed = topics::parse_state_key(key.as_str()).expect("parse");
-        assert_eq!(parsed.origin, o.chunk());
-        assert_eq!(parsed.subject, tc::Subject::interface("default", "eth0"));
-        assert_eq!(parsed.subject.family(), tc::Family::Interface);
+    fn test_interface_control_mtu_wire_format() {
+        let request = InterfaceControlRequest {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            operation: InterfaceControlOperation::SetMtu { mtu: 1400 },
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains(r#"{"SetMtu":{"mtu":1400}}"#), "{json}");
+        let parsed: InterfaceControlRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.operation, request.operation);
+
+        // Replies from before MTU operations have no MTU
+        let response: InterfaceControlResponse = serde_json::from_str(
+            r#"{"success":true,"message":"ok","new_state":true,"error_code":null}"#,
+        )
+        .unwrap();
+        assert_eq!(response.mtu, None);
     }
 
-    /// RFC 08 §7 totality: the served SchemaSet covers every type name the
-    /// registry references (build_verified panics otherwise — this test
-    /// forces the static's evaluation in CI).
     #[test]
-    fn served_schemas_cover_the_registry() {
-        let set = &*crate::schema::SCHEMAS;
-        let names = crate::schema::schema_type_names();
-        assert!(set.len() >= names.len());
+    fn test_interface_control_rename_wire_format() {
+        let request = InterfaceControlRequest {
+            namespace: "lab".to_string(),
+            interface: "veth0".to_string(),
+            operation: InterfaceControlOperation::Rename {
+                new_name: "uplink0".to_string(),
+            },
+        };
+        let json = serde_json::to_string(&request).unwrap();
         assert!(
-            set.get("NetworkInterface")
-                .unwrap()
-                .json_document()
-                .is_some()
+            json.contains(r#"{"Rename":{"new_name":"uplink0"}}"#),
+            "{json}"
         );
-        // The introspect raw-TOML sentinel is deliberately not schema'd.
-        assert!(set.verify_covers(&names).is_ok());
-        assert!(!names.contains(&"toml"));
+        let parsed: InterfaceControlRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.operation, request.operation);
+
+        let response: InterfaceControlResponse = serde_json::from_str(
+            r#"{"success":true,"message":"ok","new_state":true,"error_code":null,"new_name":"uplink0"}"#,
+        )
+        .unwrap();
+        assert_eq!(response.new_name.as_deref(), Some("uplink0"));
+        assert_eq!(response.mtu, None);
     }
 
     #[test]
-    fn test_tc_loss_config_validation() {
-        let mut config = TcLossConfig {
-            enabled: true,
-            percentage: 150.0, // Invalid
-            correlation: 50.0,
+    fn test_interface_control_address_wire_format() {
+        let operation = InterfaceControlOperation::AddAddress {
+            cidr: "10.0.0.5/24".to_string(),
         };
+        let json = serde_json::to_string(&operation).unwrap();
+        assert_eq!(json, r#"{"AddAddress":{"cidr":"10.0.0.5/24"}}"#);
 
-        assert!(config.validate().is_err());
+        let response: InterfaceControlResponse = serde_json::from_str(
+            r#"{"success":true,"message":"ok","new_state":true,"error_code":null,"addresses":["10.0.0.5/24","fe80::1/64"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.addresses,
+            Some(vec!["10.0.0.5/24".to_string(), "fe80::1/64".to_string()])
+        );
+    }
 
-        config.percentage = 50.0; // Valid
-        assert!(config.validate().is_ok());
+    #[test]
+    fn test_removing_rate_limit_keeps_other_features() {
+        let config = TcConfiguration {
+            loss: 5.0,
+            correlation: Some(25.0),
+            delay_ms: Some(100.0),
+            delay_jitter_ms: Some(10.0),
+            corrupt_percent: Some(1.0),
+            rate_limit_kbps: Some(2000),
+            ..Default::default()
+        };
+
+        let stripped = config.without_feature(TcFeature::RateLimit);
+        assert!(!stripped.has_feature(TcFeature::RateLimit));
+        for feature in [TcFeature::Loss, TcFeature::Delay, TcFeature::Corrupt] {
+            assert!(stripped.has_feature(feature), "{feature} survives");
+        }
+        let diff = config.diff(&stripped);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].parameter, "rate");
+
+        // The reapplied operation carries everything but the rate
+        let TcOperation::Apply {
+            loss,
+            correlation,
+            delay_ms,
+            delay_jitter_ms,
+            corrupt_percent,
+            rate_limit_kbps,
+            ..
+        } = stripped.to_operation()
+        else {
+            panic!("expected an apply operation");
+        };
+        assert_eq!(
+            (
+                loss,
+                correlation,
+                delay_ms,
+                delay_jitter_ms,
+                corrupt_percent
+            ),
+            (5.0, Some(25.0), Some(100.0), Some(10.0), Some(1.0))
+        );
+        assert_eq!(rate_limit_kbps, None);
+
+        // Clearing the last feature leaves nothing to apply
+        let only_rate = TcConfiguration {
+            rate_limit_kbps: Some(2000),
+            ..Default::default()
+        };
+        let emptied = only_rate.without_feature(TcFeature::RateLimit);
+        assert!(!TcFeature::ALL.iter().any(|f| emptied.has_feature(*f)));
+
+        let response = TcFeatureClearResponse {
+            namespace: "lab".to_string(),
+            feature: TcFeature::RateLimit,
+            results: vec![
+                InterfaceControlTargetResult {
+                    interface: "veth0".to_string(),
+                    success: true,
+                    message: String::new(),
+                },
+                InterfaceControlTargetResult {
+                    interface: "veth1".to_string(),
+                    success: false,
+                    message: String::new(),
+                },
+            ],
+        };
+        assert_eq!(
+            response.summary(),
+            "lab: removed rate limit from 1 of 2 interfaces; failed: veth1"
+        );
     }
 
     #[test]
-    fn test_tc_netem_config_validation() {
-        let mut config = TcNetemConfig::new();
+    fn test_rate_limit_percent_of_link_speed_resolution() {
+        let percent = |p: f32| TcRateLimitConfig {
+            enabled: true,
+            rate_kbps: 1000,
+            mode: TcRateLimitMode::PercentOfLinkSpeed(p),
+            shaper: RateLimitMode::Netem,
+        };
 
-        // Valid configuration
-        config.loss.enabled = true;
-        config.loss.percentage = 10.0;
-        assert!(config.validate().is_ok());
+        // 50% of 1 Gbit/s, 12.5% of 100 Mbit/s, 0.05% of 1 Mbit/s rounds up to 1 kbps
+        for (p, mbps, kbps) in [(50.0, 1000, 500_000), (12.5, 100, 12_500), (0.05, 1, 1)] {
+            let resolved = percent(p).resolve(Some(mbps)).unwrap();
+            assert_eq!(resolved.rate_kbps, kbps, "{p}% of {mbps} Mbit/s");
+            assert_eq!(resolved.mode, TcRateLimitMode::Absolute);
+            assert!(resolved.validate().is_ok());
+        }
 
-        // Invalid configuration
-        config.delay.enabled = true;
-        config.delay.base_ms = -100.0; // Invalid negative delay
-        assert!(config.validate().is_err());
+        // Unknown link speed is an error; an absolute rate doesn't need it
+        assert_eq!(percent(50.0).resolve(None).unwrap_err().field, "mode");
+        assert!(percent(50.0).resolve(Some(0)).is_err());
+        let absolute = TcRateLimitConfig {
+            enabled: true,
+            rate_kbps: 2000,
+            mode: TcRateLimitMode::Absolute,
+            shaper: RateLimitMode::Netem,
+        };
+        assert_eq!(absolute.resolve(None).unwrap(), absolute);
+
+        // Out of range percentages are rejected; 50% of 10 Gbit/s exceeds the cap
+        assert!(percent(0.0).validate().is_err());
+        assert!(percent(120.0).validate().is_err());
+        assert!(percent(100.0).validate().is_ok());
+        assert!(
+            percent(50.0)
+                .resolve(Some(10_000))
+                .unwrap()
+                .validate()
+                .is_err()
+        );
     }
 
     #[test]
@@ -1915,6 +5713,8 @@ mod tests {
             enabled: true,
             percentage: 10.0,
             correlation: 5.0,
+            model: LossModel::Random,
+            ecn: false,
         });
 
         // Should have pending changes when enabled but not applied
@@ -1928,6 +5728,15 @@ mod tests {
         // Should have pending changes when config is modified
         feature_state.config.percentage = 20.0;
         assert!(feature_state.has_pending_changes());
+
+        // Turning an applied feature off is a pending change too, but
+        // editing a feature that is off is not
+        feature_state.mark_applied();
+        feature_state.disable();
+        assert!(feature_state.has_pending_changes());
+        feature_state.mark_applied();
+        feature_state.config.percentage = 30.0;
+        assert!(!feature_state.has_pending_changes());
     }
 
     #[test]
@@ -2075,4 +5884,532 @@ mod tests {
             assert!(e.to_string().contains("may require special privileges"));
         }
     }
+
+    #[test]
+    fn test_tc_stats_queue_fill_ratio() {
+        let mut queue = TcStatsQueue {
+            qlen: 850,
+            limit: Some(1000),
+            ..Default::default()
+        };
+        assert_eq!(queue.fill_ratio(), Some(0.85));
+        assert!(queue.is_near_limit());
+
+        queue.qlen = 100;
+        assert!(!queue.is_near_limit());
+
+        // Unknown limit (e.g. stats from an older backend) never warns
+        queue.limit = None;
+        assert_eq!(queue.fill_ratio(), None);
+        assert!(!queue.is_near_limit());
+    }
+
+    #[test]
+    fn test_tc_configuration_diff() {
+        let ours = TcConfiguration {
+            loss: 5.0,
+            correlation: Some(25.0),
+            delay_ms: Some(100.0),
+            command: "tc qdisc replace ...".to_string(),
+            ..Default::default()
+        };
+
+        // Identical parameters (and a different command string) match
+        let same = TcConfiguration {
+            command: String::new(),
+            ..ours.clone()
+        };
+        assert!(ours.diff(&same).is_empty());
+
+        // Zero and unset both mean "inactive"
+        let zeroes = TcConfiguration {
+            rate_limit_kbps: Some(0),
+            duplicate_percent: Some(0.0),
+            ..ours.clone()
+        };
+        assert!(ours.diff(&zeroes).is_empty());
+
+        // Values that round-tripped through tc compare equal
+        let rounded = TcConfiguration {
+            loss: 5.000_001,
+            ..ours.clone()
+        };
+        assert!(ours.diff(&rounded).is_empty());
+
+        let theirs = TcConfiguration {
+            loss: 10.0,
+            delay_ms: None,
+            delay_jitter_ms: Some(10.0),
+            rate_limit_kbps: Some(1000),
+            ..ours.clone()
+        };
+        let diff = ours.diff(&theirs);
+        let parameters: Vec<_> = diff.iter().map(|d| d.parameter).collect();
+        // Jitter is not reported: delay is off on their side
+        assert_eq!(parameters, vec!["loss", "delay", "rate"]);
+        let features: Vec<_> = diff.iter().map(TcConfigDifference::feature).collect();
+        assert_eq!(
+            features,
+            vec![TcFeature::Loss, TcFeature::Delay, TcFeature::RateLimit]
+        );
+        assert_eq!(diff[0].ours, Some(5.0));
+        assert_eq!(diff[0].theirs, Some(10.0));
+        assert_eq!(diff[1].to_string(), "delay off (here 100ms)");
+        assert_eq!(diff[2].to_string(), "rate 1000kbps (here off)");
+        assert_eq!(diff[0].format_value(Some(f64::from(0.1f32))), "0.1%");
+    }
+
+    #[test]
+    fn test_tc_configuration_diff_correlation_needs_active_parent() {
+        let ours = TcConfiguration {
+            correlation: Some(25.0),
+            ..Default::default()
+        };
+        let theirs = TcConfiguration::default();
+        // Loss is off on both sides, so its correlation is irrelevant
+        assert!(ours.diff(&theirs).is_empty());
+
+        let ours = TcConfiguration {
+            loss: 1.0,
+            correlation: Some(25.0),
+            ..Default::default()
+        };
+        let theirs = TcConfiguration {
+            loss: 1.0,
+            ..Default::default()
+        };
+        let diff = ours.diff(&theirs);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].parameter, "loss correlation");
+    }
+
+    #[test]
+    fn test_namespace_diagnostics_summary() {
+        let response =
+            |success: bool, has_carrier: bool, reachable: Option<bool>| DiagnosticsResponse {
+                success,
+                message: String::new(),
+                results: DiagnosticsResults {
+                    link_status: LinkStatus {
+                        is_up: true,
+                        has_carrier,
+                        mtu: 1500,
+                    },
+                    connectivity: reachable.map(|reachable| ConnectivityResult {
+                        target: "10.0.0.1".to_string(),
+                        reachable,
+                        method: "ping".to_string(),
+                    }),
+                    ..Default::default()
+                },
+                error_code: None,
+            };
+
+        let namespace = NamespaceDiagnosticsResponse {
+            namespace: "ns1".to_string(),
+            interfaces: [
+                ("veth0", response(true, true, Some(true))),
+                ("veth1", response(true, true, None)),
+                ("veth2", response(true, false, None)),
+                ("veth3", response(true, true, Some(false))),
+                ("veth4", response(false, true, Some(true))),
+            ]
+            .into_iter()
+            .map(|(interface, response)| InterfaceDiagnostics {
+                interface: interface.to_string(),
+                response,
+            })
+            .collect(),
+        };
+
+        let passed: Vec<_> = namespace
+            .interfaces
+            .iter()
+            .map(|i| i.response.passed())
+            .collect();
+        assert_eq!(passed, vec![true, true, false, false, false]);
+        assert_eq!(namespace.summary(), "2/5 interfaces passed");
+    }
+
+    #[test]
+    fn test_command_parts_match_command_string() {
+        let config = TcConfiguration {
+            loss: 10.0,
+            correlation: Some(25.0),
+            delay_ms: Some(100.0),
+            delay_jitter_ms: Some(10.0),
+            delay_correlation: Some(50.0),
+            duplicate_percent: Some(5.0),
+            reorder_percent: Some(20.0),
+            reorder_gap: Some(3),
+            corrupt_percent: Some(1.0),
+            rate_limit_kbps: Some(500),
+            ..Default::default()
+        };
+
+        let parts = config.command_parts("eth0");
+        let features: Vec<_> = parts.iter().map(|p| p.feature).collect();
+        assert_eq!(
+            features,
+            vec![
+                None,
+                Some(TcFeature::Loss),
+                Some(TcFeature::Delay),
+                Some(TcFeature::Duplicate),
+                Some(TcFeature::Reorder),
+                Some(TcFeature::Corrupt),
+                Some(TcFeature::RateLimit),
+            ]
+        );
+        assert_eq!(parts[2].clause, "delay 100ms 10ms 50%");
+        assert_eq!(parts[4].clause, "reorder 20% gap 3");
+
+        let joined = parts
+            .iter()
+            .map(|p| p.clause.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(joined, config.to_command("eth0"));
+        assert_eq!(
+            joined,
+            "tc qdisc replace dev eth0 root netem loss 10% correlation 25% \
+             delay 100ms 10ms 50% duplicate 5% reorder 20% gap 3 corrupt 1% rate 500kbit"
+        );
+
+        let shaped = TcConfiguration {
+            delay_distribution: Some(DelayDistribution::ParetoNormal),
+            ..config.clone()
+        };
+        assert_eq!(
+            shaped.command_parts("eth0")[2].clause,
+            "delay 100ms 10ms 50% distribution paretonormal"
+        );
+
+        // A Gilbert-Elliott model replaces the random loss clause
+        let bursty = TcConfiguration {
+            loss: 0.0,
+            correlation: None,
+            loss_gemodel: Some(GeModel {
+                p: 1.5,
+                r: 25.0,
+                one_h: 90.0,
+                one_k: 0.0,
+            }),
+            ..config.clone()
+        };
+        assert!(bursty.has_feature(TcFeature::Loss));
+        assert_eq!(
+            bursty.command_parts("eth0")[1].clause,
+            "loss gemodel 1.5% 25% 90% 0%"
+        );
+
+        // Slotted delivery goes before the rate, and clears on its own
+        let slotted = TcConfiguration {
+            slot: Some(TcSlotConfig {
+                enabled: true,
+                min_delay_ms: 5.0,
+                max_delay_ms: 15.0,
+                packets: Some(16),
+                bytes: Some(64000),
+            }),
+            ..config
+        };
+        let parts = slotted.command_parts("eth0");
+        assert_eq!(parts[6].feature, Some(TcFeature::Slot));
+        assert_eq!(parts[6].clause, "slot 5ms 15ms packets 16 bytes 64000");
+        assert_eq!(parts[7].feature, Some(TcFeature::RateLimit));
+        let cleared = slotted.without_feature(TcFeature::Slot);
+        assert!(!cleared.has_feature(TcFeature::Slot));
+        assert!(cleared.has_feature(TcFeature::RateLimit));
+        assert_eq!(slotted.diff(&cleared)[0].feature(), TcFeature::Slot);
+    }
+
+    #[test]
+    fn test_command_parts_skip_inactive_features() {
+        let config = TcConfiguration {
+            loss: 0.0,
+            correlation: Some(25.0),
+            delay_ms: Some(0.0),
+            rate_limit_kbps: Some(2000),
+            ..Default::default()
+        };
+
+        let parts = config.command_parts("veth0");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1].feature, Some(TcFeature::RateLimit));
+        assert_eq!(
+            config.to_command("veth0"),
+            "tc qdisc replace dev veth0 root netem rate 2mbit"
+        );
+    }
+
+    fn veth(namespace: &str, name: &str, index: u32, peer_index: Option<u32>) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            index,
+            namespace: namespace.to_string(),
+            is_up: true,
+            is_oper_up: true,
+            has_tc_qdisc: false,
+            interface_type: InterfaceType::Veth,
+            addresses: Vec::new(),
+            qdisc_kind: None,
+            link_speed_mbps: None,
+            mtu: None,
+            peer_index,
+            management: false,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_veth_asymmetry() {
+        let mut host = veth("default", "veth-a", 7, Some(2));
+        let mut inside = veth("ns1", "eth0", 2, Some(7));
+        let rate = |kbps| TcConfiguration {
+            rate_limit_kbps: Some(kbps),
+            ..Default::default()
+        };
+
+        // Nothing known, or matching ends
+        assert!(veth_asymmetry(&host, None, &inside, None).is_empty());
+        assert!(veth_asymmetry(&host, Some(&rate(1000)), &inside, Some(&rate(5000))).is_empty());
+
+        // 10x apart is flagged, worded from each side
+        assert_eq!(
+            veth_asymmetry(&host, Some(&rate(1000)), &inside, Some(&rate(10_000))),
+            vec!["peer has 10x higher rate (10000 kbps vs 1000 kbps here)"]
+        );
+        assert_eq!(
+            veth_asymmetry(&inside, Some(&rate(10_000)), &host, Some(&rate(1000))),
+            vec!["peer has 10x lower rate (1000 kbps vs 10000 kbps here)"]
+        );
+
+        // Only one end limited or delayed isn't compared
+        assert!(
+            veth_asymmetry(
+                &host,
+                Some(&rate(1000)),
+                &inside,
+                Some(&TcConfiguration::default())
+            )
+            .is_empty()
+        );
+
+        let delay = |ms| TcConfiguration {
+            delay_ms: Some(ms),
+            ..Default::default()
+        };
+        host.mtu = Some(1500);
+        inside.mtu = Some(9000);
+        assert_eq!(
+            veth_asymmetry(&host, Some(&delay(5.0)), &inside, Some(&delay(200.0))),
+            vec![
+                "peer has MTU 9000 (here 1500)",
+                "peer has 40x higher delay (200 ms vs 5 ms here)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_qos_overrides() {
+        use qos::{QosMessageType, QosProfile, QosProfiles};
+        use zenoh::qos::{CongestionControl, Reliability};
+
+        assert_eq!(
+            QosProfiles::with_overrides([]).unwrap(),
+            QosProfiles::default()
+        );
+
+        let profiles = QosProfiles::with_overrides([
+            "bandwidth_updates=reliable,block,none",
+            "interface-events=reliable,block,50",
+        ])
+        .unwrap();
+        assert_eq!(
+            profiles.get(QosMessageType::BandwidthUpdates),
+            QosProfile::new(Reliability::Reliable, CongestionControl::Block, None)
+        );
+        assert_eq!(profiles.interface_events.history_depth, Some(50));
+        assert_eq!(profiles.interface_list, qos::INTERFACE_LIST);
+        assert_eq!(
+            profiles.bandwidth_updates.to_string(),
+            "reliable,block,none"
+        );
+
+        // Odd or malformed combinations are rejected
+        for bad in [
+            "bandwidth_updates=best_effort,drop,10",
+            "backend_health=reliable,block,0",
+            "interface_list=reliable,block,100000",
+            "interface_list=reliable,block",
+            "interface_list=sometimes,block,1",
+            "telemetry=reliable,block,1",
+            "interface_list",
+        ] {
+            assert!(QosProfiles::with_overrides([bad]).is_err(), "{bad}");
+        }
+        // A single cached sample is fine on best effort
+        assert!(QosProfiles::with_overrides(["bandwidth_updates=best_effort,drop,1"]).is_ok());
+    }
+
+    #[test]
+    fn test_find_veth_peer() {
+        let host = veth("default", "veth-a", 7, Some(2));
+        let inside = veth("ns1", "eth0", 2, Some(7));
+        let unrelated = veth("ns2", "eth0", 3, Some(9));
+        let all = [host.clone(), inside.clone(), unrelated.clone()];
+
+        assert_eq!(find_veth_peer(&all, &host), Some(&inside));
+        assert_eq!(find_veth_peer(&all, &inside), Some(&host));
+        assert_eq!(find_veth_peer(&all, &unrelated), None);
+
+        // Not a veth
+        let plain = veth("default", "eth1", 4, None);
+        assert_eq!(find_veth_peer(&all, &plain), None);
+
+        // Another namespace with the same indexes makes the match ambiguous
+        let lookalike = veth("ns3", "eth0", 2, Some(7));
+        let all = [host.clone(), inside, lookalike];
+        assert_eq!(find_veth_peer(&all, &host), None);
+    }
+
+    #[test]
+    fn test_bandwidth_stats_without_packet_rates_deserialize() {
+        let json = r#"{"rx_bytes":1000,"rx_packets":10,"rx_errors":0,"rx_dropped":0,"tx_bytes":500,"tx_packets":5,"tx_errors":0,"tx_dropped":0,"timestamp":0,"rx_bytes_per_sec":125.0,"tx_bytes_per_sec":62.5}"#;
+        let stats: NetworkBandwidthStats = serde_json::from_str(json).unwrap();
+        assert_eq!(stats.rx_bytes_per_sec, 125.0);
+        assert_eq!(stats.rx_packets_per_sec, 0.0);
+        assert_eq!(stats.tx_packets_per_sec, 0.0);
+        assert_eq!(stats.rx_dropped_per_sec, 0.0);
+    }
+
+    #[test]
+    fn test_stats_query_round_trip() {
+        let request: StatsRequest =
+            serde_json::from_str(r#"{"namespace":"default","interface":"eth0"}"#).unwrap();
+        assert_eq!(
+            (request.namespace.as_str(), request.interface.as_str()),
+            ("default", "eth0")
+        );
+
+        let json = r#"{"rx_bytes":1000,"rx_packets":10,"rx_errors":0,"rx_dropped":0,"tx_bytes":500,"tx_packets":5,"tx_errors":0,"tx_dropped":0,"timestamp":42,"rx_bytes_per_sec":125.0,"tx_bytes_per_sec":62.5}"#;
+        let response = StatsResponse {
+            namespace: request.namespace,
+            interface: request.interface,
+            stats: serde_json::from_str(json).unwrap(),
+        };
+        let parsed: StatsResponse =
+            serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+        assert_eq!(parsed.interface, "eth0");
+        assert_eq!(parsed.stats.rx_bytes, 1000);
+        assert_eq!(parsed.stats.timestamp, 42);
+        assert_eq!(parsed.stats.tx_bytes_per_sec, 62.5);
+    }
+
+    #[test]
+    fn test_network_operation_validation() {
+        let veth = |a: &str, b: &str, ns_b: Option<&str>| NetworkOperation::CreateVeth {
+            name_a: a.to_string(),
+            name_b: b.to_string(),
+            ns_b: ns_b.map(str::to_string),
+        };
+        assert!(veth("veth0", "veth1", None).validate("lab").is_ok());
+        assert!(veth("veth0", "veth0", Some("peer")).validate("lab").is_ok());
+        assert!(
+            veth("veth0", "veth0", None)
+                .validate("lab")
+                .unwrap_err()
+                .contains("both ends")
+        );
+        assert!(veth("veth0", "veth0", Some("lab")).validate("lab").is_err());
+        assert!(veth("veth0", "a/b", None).validate("lab").is_err());
+        assert!(veth("veth0:1", "veth1", None).validate("lab").is_err());
+        assert!(
+            veth("veth0", "veth1", Some("../etc"))
+                .validate("lab")
+                .is_err()
+        );
+
+        let delete = NetworkOperation::DeleteLink {
+            name: "veth0".to_string(),
+        };
+        assert!(delete.validate("default").is_ok());
+        let json = serde_json::to_string(&NetworkOperationRequest {
+            namespace: "lab".to_string(),
+            operation: veth("veth0", "veth1", None),
+        })
+        .unwrap();
+        assert!(
+            json.contains(r#"{"CreateVeth":{"name_a":"veth0","name_b":"veth1"}}"#),
+            "{json}"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_ping_request() {
+        // Requests from older peers run the standard checks
+        let json = r#"{"namespace":"lab","interface":"veth0","target":null,"timeout_ms":5000}"#;
+        let request: DiagnosticsRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.test, DiagnosticsTest::Standard);
+
+        let ping = |target: &str, count: u32| DiagnosticsTest::Ping {
+            target: target.to_string(),
+            count,
+        };
+        assert!(ping("10.0.0.1", DEFAULT_PING_COUNT).validate().is_ok());
+        assert!(ping("10.0.0.1", 0).validate().is_err());
+        assert!(ping("10.0.0.1", MAX_PING_COUNT + 1).validate().is_err());
+        assert!(ping("-c1000", 1).validate().is_err());
+
+        let json = serde_json::to_string(&ping("10.0.0.1", 3)).unwrap();
+        assert_eq!(json, r#"{"Ping":{"target":"10.0.0.1","count":3}}"#);
+
+        let unreachable = PingResult {
+            target: "10.0.0.1".to_string(),
+            transmitted: 3,
+            packet_loss_percent: 100.0,
+            ..Default::default()
+        };
+        assert!(!unreachable.reachable());
+    }
+
+    #[test]
+    fn test_diagnostics_throughput_request() {
+        let probe = |target: Option<&str>, duration_ms: u32| DiagnosticsTest::Throughput {
+            target: target.map(str::to_string),
+            duration_ms,
+        };
+        assert!(probe(None, DEFAULT_PROBE_DURATION_MS).validate().is_ok());
+        assert!(probe(Some("10.0.0.1:5001"), 1000).validate().is_ok());
+        assert!(probe(Some("[fd00::1]:9"), 1000).validate().is_ok());
+        assert!(probe(Some("10.0.0.1"), 1000).validate().is_err());
+        assert!(probe(None, MIN_PROBE_DURATION_MS - 1).validate().is_err());
+        assert!(probe(None, MAX_PROBE_DURATION_MS + 1).validate().is_err());
+
+        let json = serde_json::to_string(&probe(None, 3000)).unwrap();
+        assert_eq!(json, r#"{"Throughput":{"duration_ms":3000}}"#);
+
+        let mut result = ThroughputResult {
+            throughput_kbps: Some(9500.0),
+            configured_rate_kbps: Some(10_000),
+            ..Default::default()
+        };
+        assert_eq!(result.rate_percent(), Some(95.0));
+        result.configured_rate_kbps = None;
+        assert_eq!(result.rate_percent(), None);
+    }
+
+    #[test]
+    fn test_tc_request_without_peer_flag_deserializes() {
+        let json = r#"{"namespace":"default","interface":"eth0","operation":"Remove"}"#;
+        let request: TcRequest = serde_json::from_str(json).unwrap();
+        assert!(!request.apply_to_peer);
+        assert_eq!(request.direction, TcDirection::Egress);
+
+        let json =
+            r#"{"namespace":"default","interface":"eth0","operation":"Remove","direction":"both"}"#;
+        let request: TcRequest = serde_json::from_str(json).unwrap();
+        assert!(request.direction.includes_egress() && request.direction.includes_ingress());
+    }
 }
@@ -0,0 +1,442 @@
+//! Importing condition profiles from other network emulation tools.
+//!
+//! Teams moving to tcgui often have their conditions written down for another
+//! tool. Two formats are understood, each turned into a validated
+//! [`TcNetemConfig`]:
+//!
+//! | Source | File name | Content |
+//! |--------|-----------|---------|
+//! | [Comcast](https://github.com/tylertreat/comcast) | `*.comcast` | command line arguments, e.g. `--latency=250 --packet-loss=10%` |
+//! | [toxiproxy](https://github.com/Shopify/toxiproxy) | `*.toxiproxy.json` | a proxy with its `toxics`, a list of toxics or a single toxic |
+//!
+//! Settings without a netem equivalent (traffic targeting, toxics such as
+//! `slicer`, partial toxicity) are not an error: they are listed in
+//! [`Imported::unsupported`] so the user knows what was left out.
+
+use std::path::Path;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::presets::CustomPreset;
+use crate::{TcNetemConfig, TcValidate, TcValidationError};
+
+/// Why a profile could not be imported.
+#[derive(Error, Debug, Clone)]
+pub enum PresetImportError {
+    #[error("No network conditions found to import")]
+    Empty,
+
+    #[error("'{value}' is not a valid value for {setting}")]
+    InvalidValue { setting: String, value: String },
+
+    #[error("{setting} needs a value")]
+    MissingValue { setting: String },
+
+    #[error("Invalid toxiproxy JSON: {0}")]
+    Json(String),
+
+    #[error("{}", .0.message)]
+    OutOfRange(TcValidationError),
+
+    #[error("I/O error: {0}")]
+    Io(String),
+}
+
+/// Tool a profile comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Comcast command line arguments
+    Comcast,
+    /// toxiproxy proxy or toxics JSON
+    Toxiproxy,
+}
+
+impl ImportFormat {
+    /// Format of a preset file, going by its name: `*.comcast` or
+    /// `*.toxiproxy.json`. Returns the format and the name without the
+    /// suffix, which becomes the preset id.
+    pub fn from_path(path: &Path) -> Option<(Self, String)> {
+        let file_name = path.file_name()?.to_str()?;
+        [
+            (".comcast", ImportFormat::Comcast),
+            (".toxiproxy.json", ImportFormat::Toxiproxy),
+        ]
+        .into_iter()
+        .find_map(|(suffix, format)| {
+            file_name
+                .strip_suffix(suffix)
+                .filter(|stem| !stem.is_empty())
+                .map(|stem| (format, stem.to_string()))
+        })
+    }
+
+    /// Name of the tool, e.g. "Comcast"
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImportFormat::Comcast => "Comcast",
+            ImportFormat::Toxiproxy => "toxiproxy",
+        }
+    }
+}
+
+/// A configuration converted from another tool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Imported {
+    pub config: TcNetemConfig,
+    /// Name given in the source, if it has one
+    pub name: Option<String>,
+    /// Settings of the source that could not be mapped, one line each
+    pub unsupported: Vec<String>,
+}
+
+/// Import Comcast arguments such as `--latency=250 --target-bw=1000
+/// --packet-loss=10%`.
+///
+/// Flags may use one or two dashes and take their value after `=` or as the
+/// next argument; a leading `comcast` is skipped. `--device`, `--mode`,
+/// `--dry-run` and `--stop` only steer the tool and are ignored.
+pub fn import_comcast(args: &str) -> Result<Imported, PresetImportError> {
+    let mut config = TcNetemConfig::new();
+    let mut unsupported = Vec::new();
+    let mut mapped = false;
+
+    let mut tokens = args
+        .split_whitespace()
+        .skip_while(|token| *token == "comcast")
+        .peekable();
+    while let Some(token) = tokens.next() {
+        let Some(flag) = token.strip_prefix("--").or_else(|| token.strip_prefix('-')) else {
+            unsupported.push(format!("unexpected argument '{}'", token));
+            continue;
+        };
+        let (flag, inline_value) = match flag.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (flag, None),
+        };
+        if matches!(flag, "dry-run" | "stop") {
+            continue;
+        }
+        let value = match inline_value {
+            Some(value) => value,
+            None => match tokens.next_if(|next| !next.starts_with('-')) {
+                Some(value) => value.to_string(),
+                None => {
+                    return Err(PresetImportError::MissingValue {
+                        setting: format!("--{}", flag),
+                    });
+                }
+            },
+        };
+
+        match flag {
+            "latency" => {
+                config.delay.enabled = true;
+                config.delay.base_ms = parse_number(&value, "--latency")?;
+                mapped = true;
+            }
+            "packet-loss" | "packetloss" => {
+                let number = value.strip_suffix('%').unwrap_or(&value);
+                config.loss.enabled = true;
+                config.loss.percentage = parse_number(number, "--packet-loss")?;
+                mapped = true;
+            }
+            "target-bw" => {
+                config.rate_limit.enabled = true;
+                config.rate_limit.rate_kbps = parse_number::<u32>(&value, "--target-bw")?;
+                mapped = true;
+            }
+            "device" | "mode" => {}
+            "target-addr" | "target-port" | "target-proto" => unsupported.push(format!(
+                "--{}={}: the preset applies to all traffic of the interface",
+                flag, value
+            )),
+            "default-bw" => unsupported.push(format!(
+                "--default-bw={}: there is no separate limit for untargeted traffic",
+                value
+            )),
+            _ => unsupported.push(format!("unknown flag --{}", flag)),
+        }
+    }
+
+    if !mapped {
+        return Err(PresetImportError::Empty);
+    }
+    config.validate().map_err(PresetImportError::OutOfRange)?;
+    Ok(Imported {
+        config,
+        name: None,
+        unsupported,
+    })
+}
+
+/// Import toxiproxy toxics: a proxy object with a `toxics` list (as returned
+/// by `GET /proxies/{name}`), a list of toxics, or a single toxic.
+///
+/// `latency` (with `jitter`) becomes delay and `bandwidth` (KB/s) a rate
+/// limit. Other toxic types, a second toxic of the same type (e.g. for the
+/// other stream) and a `toxicity` below 1 are reported as unsupported.
+pub fn import_toxiproxy(json: &str) -> Result<Imported, PresetImportError> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| PresetImportError::Json(e.to_string()))?;
+
+    let (name, toxics) = match &value {
+        Value::Array(toxics) => (None, toxics.as_slice()),
+        Value::Object(object) if object.contains_key("type") => {
+            (None, std::slice::from_ref(&value))
+        }
+        Value::Object(object) => (
+            object
+                .get("name")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            object
+                .get("toxics")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        ),
+        _ => {
+            return Err(PresetImportError::Json(
+                "expected a proxy, a list of toxics or a toxic".to_string(),
+            ));
+        }
+    };
+
+    let mut config = TcNetemConfig::new();
+    let mut unsupported = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+    for (index, toxic) in toxics.iter().enumerate() {
+        let kind = toxic.get("type").and_then(Value::as_str).unwrap_or("");
+        let label = match toxic.get("name").and_then(Value::as_str) {
+            Some(name) => format!("toxic '{}' ({})", name, kind),
+            None => format!("toxic #{} ({})", index + 1, kind),
+        };
+        if !matches!(kind, "latency" | "bandwidth") {
+            unsupported.push(format!("{}: no netem equivalent", label));
+            continue;
+        }
+        if seen.contains(&kind) {
+            unsupported.push(format!(
+                "{}: only one {} toxic can be imported",
+                label, kind
+            ));
+            continue;
+        }
+        seen.push(kind);
+
+        let attribute = |key: &str| toxic.get("attributes").and_then(|a| a.get(key));
+        let number = |key: &str| -> Result<Option<f64>, PresetImportError> {
+            attribute(key)
+                .map(|value| {
+                    value
+                        .as_f64()
+                        .ok_or_else(|| PresetImportError::InvalidValue {
+                            setting: format!("{} attribute '{}'", label, key),
+                            value: value.to_string(),
+                        })
+                })
+                .transpose()
+        };
+        if kind == "latency" {
+            config.delay.enabled = true;
+            config.delay.base_ms = number("latency")?.unwrap_or(0.0) as f32;
+            config.delay.jitter_ms = number("jitter")?.unwrap_or(0.0) as f32;
+        } else {
+            let rate_kb_per_s = number("rate")?.ok_or_else(|| PresetImportError::MissingValue {
+                setting: format!("{} attribute 'rate'", label),
+            })?;
+            config.rate_limit.enabled = true;
+            config.rate_limit.rate_kbps = (rate_kb_per_s * 8.0).round() as u32;
+        }
+
+        let toxicity = toxic.get("toxicity").and_then(Value::as_f64).unwrap_or(1.0);
+        if toxicity < 1.0 {
+            unsupported.push(format!(
+                "{}: toxicity {} applies to some connections only; imported for all traffic",
+                label, toxicity
+            ));
+        }
+    }
+
+    if seen.is_empty() {
+        return Err(PresetImportError::Empty);
+    }
+    config.validate().map_err(PresetImportError::OutOfRange)?;
+    Ok(Imported {
+        config,
+        name,
+        unsupported,
+    })
+}
+
+/// A preset imported from a file, with what could not be mapped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedPreset {
+    pub preset: CustomPreset,
+    pub unsupported: Vec<String>,
+}
+
+/// Import a preset file of another tool. The id is the file name without its
+/// suffix (see [`ImportFormat::from_path`]), which also names the preset
+/// unless the source has a name of its own.
+pub fn import_preset_file(
+    path: &Path,
+    format: ImportFormat,
+    id: &str,
+) -> Result<ImportedPreset, PresetImportError> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| PresetImportError::Io(e.to_string()))?;
+    let imported = match format {
+        ImportFormat::Comcast => import_comcast(&content)?,
+        ImportFormat::Toxiproxy => import_toxiproxy(&content)?,
+    };
+    Ok(ImportedPreset {
+        preset: CustomPreset {
+            id: id.to_string(),
+            name: imported.name.unwrap_or_else(|| id.to_string()),
+            description: format!("Imported from {}", format.label()),
+            config: imported.config,
+        },
+        unsupported: imported.unsupported,
+    })
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, setting: &str) -> Result<T, PresetImportError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| PresetImportError::InvalidValue {
+            setting: setting.to_string(),
+            value: value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_comcast_arguments() {
+        let imported = import_comcast(
+            "comcast --device=eth0 --latency=250 --target-bw 1000 --packet-loss=10% --dry-run",
+        )
+        .unwrap();
+        let mut expected = TcNetemConfig::new();
+        expected.delay.enabled = true;
+        expected.delay.base_ms = 250.0;
+        expected.loss.enabled = true;
+        expected.loss.percentage = 10.0;
+        expected.rate_limit.enabled = true;
+        expected.rate_limit.rate_kbps = 1000;
+        assert_eq!(imported.config, expected);
+        assert!(imported.unsupported.is_empty());
+
+        // Go-style single dashes, targeting reported as unsupported
+        let imported =
+            import_comcast("-packetloss=0.5 -target-addr=10.0.0.0/8 -target-proto=tcp").unwrap();
+        assert_eq!(imported.config.loss.percentage, 0.5);
+        assert!(!imported.config.delay.enabled);
+        assert_eq!(imported.unsupported.len(), 2);
+        assert!(imported.unsupported[0].starts_with("--target-addr=10.0.0.0/8"));
+    }
+
+    #[test]
+    fn test_import_comcast_errors() {
+        assert!(matches!(
+            import_comcast("--device eth0 --mode start"),
+            Err(PresetImportError::Empty)
+        ));
+        assert!(matches!(
+            import_comcast("--latency=slow"),
+            Err(PresetImportError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            import_comcast("--latency"),
+            Err(PresetImportError::MissingValue { .. })
+        ));
+        assert!(matches!(
+            import_comcast("--packet-loss=150%"),
+            Err(PresetImportError::OutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_toxiproxy_proxy() {
+        let imported = import_toxiproxy(
+            r#"{
+                "name": "redis",
+                "listen": "127.0.0.1:26379",
+                "upstream": "127.0.0.1:6379",
+                "enabled": true,
+                "toxics": [
+                    {"name": "slow", "type": "latency", "stream": "downstream",
+                     "toxicity": 1.0, "attributes": {"latency": 1000, "jitter": 100}},
+                    {"name": "narrow", "type": "bandwidth", "stream": "downstream",
+                     "toxicity": 0.5, "attributes": {"rate": 125}},
+                    {"name": "slow_up", "type": "latency", "stream": "upstream",
+                     "attributes": {"latency": 10}},
+                    {"name": "cut", "type": "slicer", "attributes": {"average_size": 64}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let mut expected = TcNetemConfig::new();
+        expected.delay.enabled = true;
+        expected.delay.base_ms = 1000.0;
+        expected.delay.jitter_ms = 100.0;
+        expected.rate_limit.enabled = true;
+        expected.rate_limit.rate_kbps = 1000;
+        assert_eq!(imported.config, expected);
+        assert_eq!(imported.name.as_deref(), Some("redis"));
+        assert_eq!(
+            imported.unsupported,
+            vec![
+                "toxic 'narrow' (bandwidth): toxicity 0.5 applies to some connections only; imported for all traffic",
+                "toxic 'slow_up' (latency): only one latency toxic can be imported",
+                "toxic 'cut' (slicer): no netem equivalent",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_toxiproxy_toxics_and_errors() {
+        let imported =
+            import_toxiproxy(r#"{"type": "latency", "attributes": {"latency": 40}}"#).unwrap();
+        assert_eq!(imported.config.delay.base_ms, 40.0);
+        assert_eq!(imported.config.delay.jitter_ms, 0.0);
+
+        let imported =
+            import_toxiproxy(r#"[{"type": "bandwidth", "attributes": {"rate": 64}}]"#).unwrap();
+        assert_eq!(imported.config.rate_limit.rate_kbps, 512);
+        assert!(!imported.config.delay.enabled);
+
+        assert!(matches!(
+            import_toxiproxy(r#"{"name": "idle", "toxics": []}"#),
+            Err(PresetImportError::Empty)
+        ));
+        assert!(matches!(
+            import_toxiproxy(r#"[{"type": "bandwidth", "attributes": {}}]"#),
+            Err(PresetImportError::MissingValue { .. })
+        ));
+        assert!(matches!(
+            import_toxiproxy("not json"),
+            Err(PresetImportError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_format_from_path() {
+        assert_eq!(
+            ImportFormat::from_path(Path::new("/presets/mobile.comcast")),
+            Some((ImportFormat::Comcast, "mobile".to_string()))
+        );
+        assert_eq!(
+            ImportFormat::from_path(Path::new("redis.toxiproxy.json")),
+            Some((ImportFormat::Toxiproxy, "redis".to_string()))
+        );
+        assert_eq!(ImportFormat::from_path(Path::new("office.json5")), None);
+        assert_eq!(ImportFormat::from_path(Path::new(".comcast")), None);
+    }
+}
@@ -20,11 +20,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::scenario_json::{
     CorruptConfigJson, DelayConfigJson, DuplicateConfigJson, LossConfigJson, RateLimitConfigJson,
-    ReorderConfigJson,
+    ReorderConfigJson, SlotConfigJson,
 };
 use crate::{
-    TcCorruptConfig, TcDelayConfig, TcDuplicateConfig, TcLossConfig, TcNetemConfig,
-    TcRateLimitConfig, TcReorderConfig,
+    LossModel, RateLimitMode, TcCorruptConfig, TcDelayConfig, TcDuplicateConfig, TcLossConfig,
+    TcNetemConfig, TcRateLimitConfig, TcRateLimitMode, TcReorderConfig, TcSlotConfig,
 };
 
 /// Error type for preset JSON5 parsing
@@ -91,6 +91,11 @@ pub struct PresetFile {
     pub corrupt: Option<CorruptConfigJson>,
     /// Rate limit configuration (presence implies enabled)
     pub rate_limit: Option<RateLimitConfigJson>,
+    /// Slot configuration (presence implies enabled)
+    pub slot: Option<SlotConfigJson>,
+    /// Seed for netem's random choices
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 impl PresetFile {
@@ -103,6 +108,10 @@ impl PresetFile {
                     enabled: true,
                     percentage: loss.percentage,
                     correlation: loss.correlation,
+                    model: loss
+                        .gemodel
+                        .map_or(LossModel::Random, LossModel::GilbertElliott),
+                    ecn: loss.ecn,
                 },
                 None => TcLossConfig::default(),
             },
@@ -112,6 +121,7 @@ impl PresetFile {
                     base_ms: delay.base_ms,
                     jitter_ms: delay.jitter_ms,
                     correlation: delay.correlation,
+                    distribution: delay.distribution,
                 },
                 None => TcDelayConfig::default(),
             },
@@ -146,17 +156,21 @@ impl PresetFile {
                 None => TcCorruptConfig::default(),
             },
             rate_limit: match self.rate_limit {
-                Some(rate) => TcRateLimitConfig {
-                    enabled: true,
-                    rate_kbps: rate
-                        .to_rate_kbps()
-                        .map_err(PresetParseError::ValidationError)?,
-                },
+                Some(rate) => rate
+                    .to_rate_limit_config()
+                    .map_err(PresetParseError::ValidationError)?,
                 None => TcRateLimitConfig {
                     enabled: false,
                     rate_kbps: 1000,
+                    mode: TcRateLimitMode::Absolute,
+                    shaper: RateLimitMode::Netem,
                 },
             },
+            slot: match self.slot {
+                Some(slot) => slot.to_slot_config(),
+                None => TcSlotConfig::wifi_default(),
+            },
+            seed: self.seed,
         };
 
         Ok(CustomPreset {
@@ -2,12 +2,61 @@
 //!
 //! All presets (including built-in ones) are loaded from JSON5 files.
 //! See docs/preset-format.md for the file format specification.
+//!
+//! Preset lists can also be exported to a JSON file and imported elsewhere
+//! to share tuned profiles between machines. The file carries a version so
+//! that later schema changes are detected instead of misread.
+
+use std::path::Path;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
 
+use crate::TcValidate;
 // Re-export CustomPreset from preset_json for convenience
 pub use crate::preset_json::CustomPreset;
 
+/// Version of the preset files written by [`PresetList::export_to_file`].
+pub const PRESET_FILE_VERSION: u32 = 1;
+
+/// Why a preset file could not be exported or imported.
+#[derive(Error, Debug, Clone)]
+pub enum PresetFileError {
+    #[error("I/O error: {0}")]
+    Io(String),
+
+    #[error("Invalid preset file: {0}")]
+    Json(String),
+
+    #[error("Preset file version {0} is newer than this version of tcgui supports")]
+    UnsupportedVersion(u32),
+}
+
+/// A preset file as read, before each preset is checked.
+#[derive(Deserialize)]
+struct PresetFile {
+    version: u32,
+    presets: Vec<Value>,
+}
+
+/// A preset left out of an import, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedPreset {
+    /// Id of the preset, or its position in the file if it has none
+    pub id: String,
+    pub reason: String,
+}
+
+/// Presets read from a preset file.
+#[derive(Debug, Clone, Default)]
+pub struct PresetImport {
+    /// The valid presets, in file order
+    pub presets: PresetList,
+    /// The presets that were malformed or failed validation
+    pub skipped: Vec<SkippedPreset>,
+}
+
 /// Message sent from backend to frontend containing all available presets.
 /// All presets (including built-in ones) are loaded from JSON5 files.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, schemars::JsonSchema)]
@@ -46,6 +95,55 @@ impl PresetList {
     pub fn is_empty(&self) -> bool {
         self.presets.is_empty()
     }
+
+    /// The presets as a versioned preset file
+    pub fn export_to_json(&self) -> Result<String, PresetFileError> {
+        let file = serde_json::json!({
+            "version": PRESET_FILE_VERSION,
+            "presets": self.presets,
+        });
+        serde_json::to_string_pretty(&file).map_err(|e| PresetFileError::Json(e.to_string()))
+    }
+
+    /// Write the presets to `path` as a versioned preset file
+    pub fn export_to_file(&self, path: &Path) -> Result<(), PresetFileError> {
+        std::fs::write(path, self.export_to_json()?).map_err(|e| PresetFileError::Io(e.to_string()))
+    }
+
+    /// Read a preset file. Presets that are malformed or fail validation are
+    /// skipped and reported rather than failing the whole import.
+    pub fn import_from_json(json: &str) -> Result<PresetImport, PresetFileError> {
+        let file: PresetFile =
+            serde_json::from_str(json).map_err(|e| PresetFileError::Json(e.to_string()))?;
+        if file.version > PRESET_FILE_VERSION {
+            return Err(PresetFileError::UnsupportedVersion(file.version));
+        }
+
+        let mut import = PresetImport::default();
+        for (index, value) in file.presets.into_iter().enumerate() {
+            let id = value
+                .get("id")
+                .and_then(Value::as_str)
+                .map_or_else(|| format!("#{}", index + 1), str::to_string);
+            let checked = serde_json::from_value::<CustomPreset>(value)
+                .map_err(|e| e.to_string())
+                .and_then(|preset| match preset.config.validate() {
+                    Ok(()) => Ok(preset),
+                    Err(e) => Err(e.to_string()),
+                });
+            match checked {
+                Ok(preset) => import.presets.presets.push(preset),
+                Err(reason) => import.skipped.push(SkippedPreset { id, reason }),
+            }
+        }
+        Ok(import)
+    }
+
+    /// Read the preset file at `path` (see [`PresetList::import_from_json`])
+    pub fn import_from_file(path: &Path) -> Result<PresetImport, PresetFileError> {
+        let json = std::fs::read_to_string(path).map_err(|e| PresetFileError::Io(e.to_string()))?;
+        Self::import_from_json(&json)
+    }
 }
 
 #[cfg(test)]
@@ -100,4 +198,52 @@ mod tests {
         assert!(list.is_empty());
         assert_eq!(list.len(), 0);
     }
+
+    #[test]
+    fn test_preset_file_round_trip() {
+        let mut lossy = create_test_preset("lossy", "Lossy");
+        lossy.config.loss.enabled = true;
+        lossy.config.loss.percentage = 2.5;
+        lossy.config.delay.enabled = true;
+        lossy.config.delay.base_ms = 40.0;
+        let list = PresetList::new(vec![lossy, create_test_preset("idle", "Idle")]);
+
+        let path = std::env::temp_dir().join(format!("tcgui-presets-{}.json", std::process::id()));
+        list.export_to_file(&path).unwrap();
+        let import = PresetList::import_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(import.skipped.is_empty());
+        assert_eq!(import.presets.all(), list.all());
+    }
+
+    #[test]
+    fn test_preset_import_skips_invalid() {
+        let mut bad = create_test_preset("bad", "Bad");
+        bad.config.loss.percentage = 150.0;
+        let exported = PresetList::new(vec![create_test_preset("ok", "Ok"), bad])
+            .export_to_json()
+            .unwrap();
+        let mut file: Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(file["version"], PRESET_FILE_VERSION);
+        file["presets"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({ "name": "No id" }));
+
+        let import = PresetList::import_from_json(&file.to_string()).unwrap();
+        assert_eq!(import.presets.len(), 1);
+        assert!(import.presets.contains("ok"));
+        let skipped: Vec<_> = import.skipped.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(skipped, ["bad", "#3"]);
+
+        assert!(matches!(
+            PresetList::import_from_json(r#"{"version": 2, "presets": []}"#),
+            Err(PresetFileError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            PresetList::import_from_json("[]"),
+            Err(PresetFileError::Json(_))
+        ));
+    }
 }
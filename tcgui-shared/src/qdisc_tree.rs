@@ -0,0 +1,343 @@
+//! The qdisc/class/filter tree of an interface, as the kernel reports it.
+//!
+//! A flat command string can't show how a classful configuration hangs
+//! together (an HTB class feeding a netem leaf, a flower filter steering into
+//! a class). The backend dumps an interface's qdiscs, classes and filters as
+//! [`TcTreeEntry`] values and [`build_tree`] links them by parent handle into
+//! [`TcTreeNode`]s, the way `tc qdisc/class/filter show` output is read.
+//!
+//! Nothing here fails: an object whose options could not be decoded, or whose
+//! parent is not in the dump, is kept with a [`TcTreeNode::note`] saying so.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Parent handle of a root qdisc (`TC_H_ROOT`)
+pub const HANDLE_ROOT: u32 = 0xFFFF_FFFF;
+/// Parent handle of an ingress or clsact qdisc (`TC_H_INGRESS`)
+pub const HANDLE_INGRESS: u32 = 0xFFFF_FFF1;
+
+/// What a node of the tree is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TcObjectKind {
+    Qdisc,
+    Class,
+    Filter,
+}
+
+impl TcObjectKind {
+    /// Word `tc` uses for the object, e.g. "qdisc"
+    pub fn label(&self) -> &'static str {
+        match self {
+            TcObjectKind::Qdisc => "qdisc",
+            TcObjectKind::Class => "class",
+            TcObjectKind::Filter => "filter",
+        }
+    }
+}
+
+/// One qdisc, class or filter from a kernel dump, before linking.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcTreeEntry {
+    pub kind: TcObjectKind,
+    /// Raw `major:minor` handle
+    pub handle: u32,
+    /// Raw handle of the object this one is attached to
+    pub parent: u32,
+    /// Qdisc, class or filter type (e.g. "htb", "netem", "flower"); `None`
+    /// when the kernel did not name it
+    pub type_name: Option<String>,
+    /// Decoded parameters, e.g. "rate 10mbit"
+    pub details: Vec<String>,
+    /// Why the entry is only partly understood, if it is
+    pub note: Option<String>,
+}
+
+/// One node of an interface's traffic control tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcTreeNode {
+    pub kind: TcObjectKind,
+    /// Handle as `tc` prints it, e.g. "1:" or "1:10"
+    pub handle: String,
+    /// Parent as `tc` prints it, e.g. "root" or "1:1"
+    pub parent: String,
+    /// Qdisc, class or filter type, "unknown" if the kernel did not name it
+    pub type_name: String,
+    /// Decoded parameters, e.g. "rate 10mbit"
+    pub details: Vec<String>,
+    /// Why the node is only partly understood, if it is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Classes, qdiscs and filters attached to this node
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TcTreeNode>,
+}
+
+impl TcTreeNode {
+    /// One line in `tc show` style, e.g. "qdisc netem 1: parent root".
+    pub fn headline(&self) -> String {
+        format!(
+            "{} {} {} parent {}",
+            self.kind.label(),
+            self.type_name,
+            self.handle,
+            self.parent
+        )
+    }
+
+    /// This node and all of its descendants, depth first.
+    pub fn walk(&self) -> Vec<&TcTreeNode> {
+        let mut nodes = vec![self];
+        for child in &self.children {
+            nodes.extend(child.walk());
+        }
+        nodes
+    }
+}
+
+/// Ask a backend for the traffic control tree of one interface (Query)
+/// Query Service: diagnostics/tree
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcTreeRequest {
+    /// Target network namespace
+    pub namespace: String,
+    /// Target interface name
+    pub interface: String,
+}
+
+/// The traffic control tree of one interface (Reply)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TcTreeResponse {
+    pub namespace: String,
+    pub interface: String,
+    /// Top-level nodes: root and ingress qdiscs, then anything whose parent
+    /// was not found
+    pub roots: Vec<TcTreeNode>,
+}
+
+impl TcTreeResponse {
+    /// Every node of the tree, depth first.
+    pub fn nodes(&self) -> Vec<&TcTreeNode> {
+        self.roots.iter().flat_map(TcTreeNode::walk).collect()
+    }
+
+    /// Nodes that are only partly understood.
+    pub fn unparsed(&self) -> Vec<&TcTreeNode> {
+        self.nodes()
+            .into_iter()
+            .filter(|node| node.note.is_some())
+            .collect()
+    }
+}
+
+/// Format a handle the way `tc` prints it: "root", "ingress", "1:" for a
+/// qdisc or "1:a" for a class (hexadecimal).
+pub fn format_handle(handle: u32) -> String {
+    match handle {
+        HANDLE_ROOT => "root".to_string(),
+        HANDLE_INGRESS => "ingress".to_string(),
+        _ => {
+            let (major, minor) = (handle >> 16, handle & 0xFFFF);
+            if minor == 0 {
+                format!("{:x}:", major)
+            } else {
+                format!("{:x}:{:x}", major, minor)
+            }
+        }
+    }
+}
+
+/// Link dumped entries into a tree by parent handle.
+///
+/// Qdiscs and classes can be parents, filters never are. Entries are kept in
+/// dump order under their parent. An entry whose parent is neither the root,
+/// ingress nor another entry is kept at the top level with a note, as is any
+/// entry caught in a parent cycle.
+pub fn build_tree(entries: Vec<TcTreeEntry>) -> Vec<TcTreeNode> {
+    // First qdisc or class with each (non-zero) handle
+    let mut parents: HashMap<u32, usize> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.kind != TcObjectKind::Filter && entry.handle != 0 {
+            parents.entry(entry.handle).or_insert(index);
+        }
+    }
+
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        match parents.get(&entry.parent) {
+            Some(&parent) if parent != index => children.entry(parent).or_default().push(index),
+            _ => roots.push(index),
+        }
+    }
+
+    let mut placed = HashSet::new();
+    let mut tree = Vec::new();
+    for index in roots {
+        if let Some(mut root) = link(index, &entries, &children, &mut placed) {
+            if root.note.is_none() && !matches!(entries[index].parent, HANDLE_ROOT | HANDLE_INGRESS)
+            {
+                root.note = Some(format!("parent {} not found", root.parent));
+            }
+            tree.push(root);
+        }
+    }
+
+    // Anything not reachable from a top-level entry sits in a parent cycle
+    for (index, entry) in entries.iter().enumerate() {
+        if !placed.contains(&index) {
+            let mut node = node(entry);
+            node.note = Some("part of a parent cycle".to_string());
+            tree.push(node);
+        }
+    }
+    tree
+}
+
+fn node(entry: &TcTreeEntry) -> TcTreeNode {
+    TcTreeNode {
+        kind: entry.kind,
+        handle: format_handle(entry.handle),
+        parent: format_handle(entry.parent),
+        type_name: entry
+            .type_name
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+        details: entry.details.clone(),
+        note: entry.note.clone(),
+        children: Vec::new(),
+    }
+}
+
+fn link(
+    index: usize,
+    entries: &[TcTreeEntry],
+    children: &HashMap<usize, Vec<usize>>,
+    placed: &mut HashSet<usize>,
+) -> Option<TcTreeNode> {
+    if !placed.insert(index) {
+        return None;
+    }
+    let mut tree_node = node(&entries[index]);
+    tree_node.children = children
+        .get(&index)
+        .into_iter()
+        .flatten()
+        .filter_map(|&child| link(child, entries, children, placed))
+        .collect();
+    Some(tree_node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: TcObjectKind, handle: u32, parent: u32, type_name: &str) -> TcTreeEntry {
+        TcTreeEntry {
+            kind,
+            handle,
+            parent,
+            type_name: Some(type_name.to_string()),
+            details: Vec::new(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_format_handle() {
+        assert_eq!(format_handle(HANDLE_ROOT), "root");
+        assert_eq!(format_handle(HANDLE_INGRESS), "ingress");
+        assert_eq!(format_handle(0x0001_0000), "1:");
+        assert_eq!(format_handle(0x0001_0010), "1:10");
+        assert_eq!(format_handle(0xFFFF_0000), "ffff:");
+    }
+
+    #[test]
+    fn test_single_netem_is_a_trivial_tree() {
+        let tree = build_tree(vec![entry(
+            TcObjectKind::Qdisc,
+            0x8001_0000,
+            HANDLE_ROOT,
+            "netem",
+        )]);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].headline(), "qdisc netem 8001: parent root");
+        assert!(tree[0].children.is_empty());
+        assert_eq!(tree[0].note, None);
+    }
+
+    #[test]
+    fn test_htb_classes_filters_and_leaves_are_linked() {
+        use TcObjectKind::*;
+        let tree = build_tree(vec![
+            entry(Qdisc, 0x0001_0000, HANDLE_ROOT, "htb"),
+            entry(Qdisc, 0x0010_0000, 0x0001_0010, "netem"),
+            entry(Qdisc, 0xFFFF_0000, HANDLE_INGRESS, "ingress"),
+            entry(Class, 0x0001_0001, 0x0001_0000, "htb"),
+            entry(Class, 0x0001_0010, 0x0001_0001, "htb"),
+            entry(Filter, 0, 0x0001_0000, "flower"),
+            entry(Filter, 0, 0xFFFF_0000, "u32"),
+        ]);
+
+        assert_eq!(tree.len(), 2);
+        let root = &tree[0];
+        assert_eq!(root.handle, "1:");
+        let kinds: Vec<_> = root.children.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, vec![Class, Filter]);
+        let leaf_class = &root.children[0].children[0];
+        assert_eq!(leaf_class.handle, "1:10");
+        assert_eq!(
+            leaf_class.children[0].headline(),
+            "qdisc netem 10: parent 1:10"
+        );
+        assert_eq!(tree[1].children[0].type_name, "u32");
+
+        let response = TcTreeResponse {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            roots: tree,
+        };
+        assert_eq!(response.nodes().len(), 7);
+        assert!(response.unparsed().is_empty());
+    }
+
+    #[test]
+    fn test_unparseable_nodes_are_reported_not_dropped() {
+        use TcObjectKind::*;
+        let mut undecoded = entry(Qdisc, 0x0001_0000, HANDLE_ROOT, "mystery");
+        undecoded.note = Some("options not decoded (12 bytes)".to_string());
+        let mut unnamed = entry(Filter, 0, 0x0001_0000, "");
+        unnamed.type_name = None;
+        let tree = build_tree(vec![
+            undecoded,
+            unnamed,
+            entry(Class, 0x0002_0001, 0x0002_0000, "htb"),
+            entry(Class, 0x0003_0001, 0x0003_0002, "htb"),
+            entry(Class, 0x0003_0002, 0x0003_0001, "htb"),
+        ]);
+        let response = TcTreeResponse {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            roots: tree,
+        };
+
+        assert_eq!(response.nodes().len(), 5);
+        assert_eq!(response.roots[0].children[0].type_name, "unknown");
+        let notes: Vec<_> = response
+            .unparsed()
+            .iter()
+            .map(|node| node.note.clone().unwrap())
+            .collect();
+        assert_eq!(
+            notes,
+            vec![
+                "options not decoded (12 bytes)",
+                "parent 2: not found",
+                "part of a parent cycle",
+                "part of a parent cycle",
+            ]
+        );
+    }
+}
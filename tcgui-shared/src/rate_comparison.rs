@@ -0,0 +1,250 @@
+//! Side-by-side comparison of netem's `rate` and TBF at the same target rate.
+//!
+//! Both shape to a rate, but not the same way: netem's rate clause queues
+//! everything it can't send yet in its (by default 1000 packet) queue, so a
+//! burst turns into delay; TBF keeps a small buffer and drops what doesn't fit,
+//! so latency stays close to the unloaded value while TCP backs off. The
+//! backend applies the rate with each qdisc in turn, measures throughput
+//! (iperf3) and latency under that load (ping), and then puts the interface's
+//! original configuration back.
+//!
+//! Throughput needs iperf3 on the backend host and an iperf3 server at the
+//! target. Without either the comparison is latency-only, with a
+//! [`RateComparisonResponse::notes`] entry saying why.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::LatencyResult;
+
+/// Latency difference (ms, average under load) worth pointing out.
+pub const NOTABLE_LATENCY_MS: f32 = 5.0;
+
+/// Compare netem and TBF shaping on one interface (Query)
+/// Query Service: diagnostics/{ns}/{iface}/rate-compare
+///
+/// The interface is reshaped twice while the comparison runs and restored
+/// afterwards; traffic through it sees both shapers.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RateComparisonRequest {
+    /// Target network namespace
+    pub namespace: String,
+    /// Target interface name
+    pub interface: String,
+    /// Rate both shapers are set to, in kbit/s
+    pub rate_kbps: u32,
+    /// Host to measure against (iperf3 server and ping target); the default
+    /// gateway when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// The qdisc a measurement was taken with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Shaper {
+    Netem,
+    Tbf,
+}
+
+impl fmt::Display for Shaper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shaper::Netem => write!(f, "netem"),
+            Shaper::Tbf => write!(f, "tbf"),
+        }
+    }
+}
+
+/// What one shaper achieved.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ShaperMeasurement {
+    pub shaper: Shaper,
+    /// Ping results taken while the throughput test ran (or on an idle link
+    /// when there was none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<LatencyResult>,
+    /// Throughput iperf3 measured, in kbit/s
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throughput_kbps: Option<f64>,
+    /// TCP retransmissions during the throughput test
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retransmits: Option<u64>,
+    /// Why the shaper could not be applied or measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ShaperMeasurement {
+    /// A measurement that could not be taken.
+    pub fn failed(shaper: Shaper, error: String) -> Self {
+        Self {
+            shaper,
+            latency: None,
+            throughput_kbps: None,
+            retransmits: None,
+            error: Some(error),
+        }
+    }
+
+    /// Throughput as a percentage of `rate_kbps`.
+    pub fn rate_percent(&self, rate_kbps: u32) -> Option<f64> {
+        self.throughput_kbps
+            .filter(|_| rate_kbps > 0)
+            .map(|kbps| kbps * 100.0 / rate_kbps as f64)
+    }
+}
+
+/// Outcome of a netem vs TBF comparison (Reply)
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RateComparisonResponse {
+    pub namespace: String,
+    pub interface: String,
+    /// Rate both shapers were set to, in kbit/s
+    pub rate_kbps: u32,
+    /// Host the measurements were taken against
+    pub target: String,
+    pub netem: ShaperMeasurement,
+    pub tbf: ShaperMeasurement,
+    /// How the run deviated from a full comparison, e.g. "iperf3 is not
+    /// installed; comparing latency only"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Whether reading the interface back after the run showed its original
+    /// configuration
+    pub restored: bool,
+}
+
+impl RateComparisonResponse {
+    /// Whether both shapers have a throughput figure.
+    pub fn throughput_measured(&self) -> bool {
+        self.netem.throughput_kbps.is_some() && self.tbf.throughput_kbps.is_some()
+    }
+
+    /// Plain-language observations drawn from the two measurements.
+    pub fn findings(&self) -> Vec<String> {
+        let mut findings = Vec::new();
+
+        if let (Some(netem), Some(tbf)) = (
+            self.netem.rate_percent(self.rate_kbps),
+            self.tbf.rate_percent(self.rate_kbps),
+        ) {
+            findings.push(format!(
+                "Throughput reached {:.0}% of the target rate with netem and {:.0}% with tbf",
+                netem, tbf
+            ));
+        }
+
+        if let (Some(netem), Some(tbf)) = (&self.netem.latency, &self.tbf.latency) {
+            let difference = netem.avg_ms - tbf.avg_ms;
+            let under = if self.throughput_measured() {
+                "under load"
+            } else {
+                "on an idle link"
+            };
+            if difference.abs() < NOTABLE_LATENCY_MS {
+                findings.push(format!(
+                    "Average latency {} was about the same ({:.1} ms vs {:.1} ms)",
+                    under, netem.avg_ms, tbf.avg_ms
+                ));
+            } else {
+                let (higher, lower) = if difference > 0.0 {
+                    (Shaper::Netem, Shaper::Tbf)
+                } else {
+                    (Shaper::Tbf, Shaper::Netem)
+                };
+                findings.push(format!(
+                    "Average latency {} was {:.1} ms higher with {} than with {}",
+                    under,
+                    difference.abs(),
+                    higher,
+                    lower
+                ));
+                if higher == Shaper::Netem {
+                    findings.push(
+                        "netem queues packets it can't send yet, so bursts become delay; \
+                         tbf drops them instead and TCP slows down"
+                            .to_string(),
+                    );
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(shaper: Shaper, avg_ms: f32, throughput_kbps: Option<f64>) -> ShaperMeasurement {
+        ShaperMeasurement {
+            shaper,
+            latency: Some(LatencyResult {
+                target: "10.0.0.1".to_string(),
+                min_ms: avg_ms / 2.0,
+                avg_ms,
+                max_ms: avg_ms * 2.0,
+                packet_loss_percent: 0.0,
+                samples: 5,
+            }),
+            throughput_kbps,
+            retransmits: None,
+            error: None,
+        }
+    }
+
+    fn response(netem: ShaperMeasurement, tbf: ShaperMeasurement) -> RateComparisonResponse {
+        RateComparisonResponse {
+            namespace: "default".to_string(),
+            interface: "eth0".to_string(),
+            rate_kbps: 10_000,
+            target: "10.0.0.1".to_string(),
+            netem,
+            tbf,
+            notes: Vec::new(),
+            restored: true,
+        }
+    }
+
+    #[test]
+    fn test_findings_compare_throughput_and_latency_under_load() {
+        let comparison = response(
+            measurement(Shaper::Netem, 120.0, Some(9_600.0)),
+            measurement(Shaper::Tbf, 4.0, Some(9_400.0)),
+        );
+        assert!(comparison.throughput_measured());
+
+        let findings = comparison.findings();
+        assert_eq!(
+            findings[0],
+            "Throughput reached 96% of the target rate with netem and 94% with tbf"
+        );
+        assert_eq!(
+            findings[1],
+            "Average latency under load was 116.0 ms higher with netem than with tbf"
+        );
+        assert_eq!(findings.len(), 3);
+    }
+
+    #[test]
+    fn test_latency_only_comparison() {
+        let comparison = response(
+            measurement(Shaper::Netem, 3.0, None),
+            measurement(Shaper::Tbf, 2.0, None),
+        );
+        assert!(!comparison.throughput_measured());
+        assert_eq!(
+            comparison.findings(),
+            vec!["Average latency on an idle link was about the same (3.0 ms vs 2.0 ms)"]
+        );
+
+        let failed = response(
+            ShaperMeasurement::failed(Shaper::Netem, "no such device".to_string()),
+            measurement(Shaper::Tbf, 2.0, None),
+        );
+        assert!(failed.findings().is_empty());
+    }
+}
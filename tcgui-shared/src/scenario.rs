@@ -23,8 +23,16 @@ pub struct NetworkScenario {
     pub description: String,
     /// Sequence of network condition changes
     pub steps: Vec<ScenarioStep>,
+    /// Named groups of consecutive steps, in step order. Purely
+    /// organizational; empty when the scenario has no phases.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<ScenarioPhase>,
     /// Whether to loop the scenario when it completes
     pub loop_scenario: bool,
+    /// How many times the scenario runs; looping (`loop_scenario` or a
+    /// looping start request) runs it forever regardless
+    #[serde(default, skip_serializing_if = "ScenarioRepeat::is_once")]
+    pub repeat: ScenarioRepeat,
     /// Creation timestamp (Unix timestamp in seconds)
     pub created_at: u64,
     /// Last modification timestamp (Unix timestamp in seconds)
@@ -34,6 +42,67 @@ pub struct NetworkScenario {
     /// Whether to restore original TC configuration on failure/abort (default: true)
     #[serde(default = "default_cleanup_on_failure")]
     pub cleanup_on_failure: bool,
+    /// Whether to read the configuration back after each step and fail the
+    /// execution if it did not take effect (default: false)
+    #[serde(default)]
+    pub verify_steps: bool,
+    /// What to do with the interface's TC configuration when the scenario
+    /// completes or is stopped. Unset keeps the last step applied on
+    /// completion and restores the previous configuration on stop.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleanup_policy: Option<CleanupPolicy>,
+}
+
+/// What happens to the interface's TC configuration when a scenario ends.
+/// Failures are handled by `cleanup_on_failure` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupPolicy {
+    /// Keep the last applied step's configuration
+    LeaveApplied,
+    /// Remove all TC configuration from the interface
+    RemoveAll,
+    /// Reapply the configuration captured when the scenario started
+    RestorePrevious,
+}
+
+/// How many times a scenario runs through its steps.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ScenarioRepeat {
+    /// Run the steps once
+    #[default]
+    Once,
+    /// Run the steps this many times
+    Count(u32),
+    /// Restart from the first step until stopped
+    Forever,
+}
+
+impl ScenarioRepeat {
+    pub fn is_once(&self) -> bool {
+        *self == ScenarioRepeat::Once
+    }
+
+    /// Whether another cycle follows once `cycles_done` have run
+    pub fn runs_again(&self, cycles_done: u32) -> bool {
+        match self {
+            ScenarioRepeat::Once => false,
+            ScenarioRepeat::Count(count) => cycles_done < *count,
+            ScenarioRepeat::Forever => true,
+        }
+    }
+}
+
+/// How a scenario execution ended, for picking its cleanup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioEnd {
+    /// Every step ran (and, for a looping execution, never happens)
+    Completed,
+    /// The user stopped the execution
+    Stopped,
 }
 
 /// Default value for cleanup_on_failure (true)
@@ -48,12 +117,25 @@ pub struct ScenarioMetadata {
     pub tags: Vec<String>,
     /// Author or creator of the scenario
     pub author: Option<String>,
+    /// Library category (e.g., "mobile", "regression")
+    #[serde(default)]
+    pub category: Option<String>,
     /// Scenario version for tracking updates
     pub version: String,
     /// Expected duration in milliseconds (calculated from steps)
     pub duration_ms: u64,
 }
 
+/// A named group of consecutive steps, e.g. "Warmup" or "Outage".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ScenarioPhase {
+    /// Phase name, unique within the scenario
+    pub name: String,
+    /// Number of steps in the phase; phases follow each other, so a phase
+    /// starts where the previous one ended
+    pub step_count: usize,
+}
+
 /// Individual step in a network scenario
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ScenarioStep {
@@ -63,6 +145,10 @@ pub struct ScenarioStep {
     pub tc_config: TcNetemConfig,
     /// Human-readable description of this step
     pub description: String,
+    /// Move gradually from the previous step's configuration to this one
+    /// over the step, instead of switching at its start
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ramp: Option<RampSpec>,
 }
 
 /// Current execution state of a running scenario
@@ -85,9 +171,18 @@ pub struct ScenarioExecution {
     /// Whether this execution should loop indefinitely
     #[serde(default)]
     pub loop_execution: bool,
-    /// Current loop iteration (0-based, only relevant when loop_execution is true)
+    /// Current cycle through the steps (0-based), counting up while the
+    /// execution loops or repeats
     #[serde(default)]
     pub loop_iteration: u32,
+    /// Time spent paused since the start (milliseconds), which doesn't
+    /// count as elapsed
+    #[serde(default)]
+    pub paused_ms: u64,
+    /// Name of the phase the current step belongs to, if the scenario has
+    /// phases
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_phase: Option<String>,
 }
 
 /// Execution statistics for monitoring and debugging
@@ -252,6 +347,11 @@ impl std::fmt::Display for ScenarioError {
 /// Current state of scenario execution
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub enum ExecutionState {
+    /// Scenario is waiting for its scheduled start time
+    Scheduled {
+        /// When the scenario will start (Unix timestamp in milliseconds)
+        start_at: u64,
+    },
     /// Scenario is actively running
     Running,
     /// Scenario is temporarily paused
@@ -270,6 +370,16 @@ pub enum ExecutionState {
     },
 }
 
+impl ExecutionState {
+    /// Whether the execution has ended (stopped, completed or failed)
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ExecutionState::Stopped | ExecutionState::Completed | ExecutionState::Failed { .. }
+        )
+    }
+}
+
 /// Scenario management request messages (Query/Reply pattern)
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub enum ScenarioRequest {
@@ -326,6 +436,14 @@ pub enum ScenarioExecutionRequest {
         /// Whether to loop the scenario indefinitely (overrides scenario's loop_scenario field)
         #[serde(default)]
         loop_execution: bool,
+        /// Defer the start until this time (Unix timestamp in milliseconds).
+        /// A time in the past starts immediately.
+        #[serde(default)]
+        start_at: Option<u64>,
+        /// Defer the start by this long (milliseconds) after the backend
+        /// receives the request; not combined with `start_at`
+        #[serde(default)]
+        start_after_ms: Option<u64>,
     },
     /// Stop execution on specified interface
     Stop {
@@ -358,6 +476,15 @@ pub enum ScenarioExecutionResponse {
     Started {
         execution_id: String,
         estimated_duration_ms: u64,
+        /// Additional information about the start (e.g. a start time that had already passed)
+        #[serde(default)]
+        note: Option<String>,
+    },
+    /// Execution was scheduled to start later
+    Scheduled {
+        execution_id: String,
+        /// When the scenario will start (Unix timestamp in milliseconds)
+        start_at: u64,
     },
     /// Execution stopped successfully
     Stopped { success: bool },
@@ -375,6 +502,24 @@ pub enum ScenarioExecutionResponse {
     Error { error: ScenarioError },
 }
 
+/// When a start request wants the execution to begin (Unix timestamp in
+/// milliseconds), given the time `now_ms` it was received: `start_at`, or
+/// `start_after_ms` from now. `None` starts immediately.
+pub fn requested_start(
+    start_at: Option<u64>,
+    start_after_ms: Option<u64>,
+    now_ms: u64,
+) -> Result<Option<u64>, ScenarioError> {
+    match (start_at, start_after_ms) {
+        (Some(_), Some(_)) => Err(ScenarioError::validation(
+            "A start time and a start delay cannot both be given",
+        )),
+        (start_at, None) => Ok(start_at),
+        (None, Some(0)) => Ok(None),
+        (None, Some(delay_ms)) => Ok(Some(now_ms.saturating_add(delay_ms))),
+    }
+}
+
 /// Scenario execution status update (Pub/Sub)
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ScenarioExecutionUpdate {
@@ -412,6 +557,12 @@ impl TcValidate for NetworkScenario {
             });
         }
 
+        self.validate_phases()?;
+
+        if self.repeat == ScenarioRepeat::Count(0) {
+            return Err(ScenarioValidationError::ZeroRepeatCount);
+        }
+
         // Validate steps
         for (index, step) in self.steps.iter().enumerate() {
             step.validate()
@@ -435,6 +586,40 @@ impl TcValidate for NetworkScenario {
     }
 }
 
+impl NetworkScenario {
+    /// Phases must have unique, non-empty names and together cover every
+    /// step exactly once.
+    fn validate_phases(&self) -> Result<(), ScenarioValidationError> {
+        if self.phases.is_empty() {
+            return Ok(());
+        }
+        let mut names = std::collections::HashSet::new();
+        for phase in &self.phases {
+            if phase.name.trim().is_empty() {
+                return Err(ScenarioValidationError::EmptyField(
+                    "phase name".to_string(),
+                ));
+            }
+            if phase.step_count == 0 {
+                return Err(ScenarioValidationError::EmptyPhase(phase.name.clone()));
+            }
+            if !names.insert(phase.name.as_str()) {
+                return Err(ScenarioValidationError::DuplicatePhaseName(
+                    phase.name.clone(),
+                ));
+            }
+        }
+        let covered: usize = self.phases.iter().map(|p| p.step_count).sum();
+        if covered != self.steps.len() {
+            return Err(ScenarioValidationError::PhaseStepMismatch {
+                phase_steps: covered,
+                steps: self.steps.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
 impl TcValidate for ScenarioStep {
     type Error = ScenarioStepValidationError;
 
@@ -454,6 +639,11 @@ impl TcValidate for ScenarioStep {
             return Err(ScenarioStepValidationError::InvalidDuration(0));
         }
 
+        // Validate ramp pacing
+        if self.ramp.is_some_and(|ramp| ramp.interval_ms == 0) {
+            return Err(ScenarioStepValidationError::InvalidRampInterval);
+        }
+
         Ok(())
     }
 }
@@ -474,6 +664,17 @@ pub enum ScenarioValidationError {
         count: usize,
         max: usize,
     },
+    /// Two phases share a name
+    DuplicatePhaseName(String),
+    /// A phase without steps
+    EmptyPhase(String),
+    /// The phases don't cover exactly the scenario's steps
+    PhaseStepMismatch {
+        phase_steps: usize,
+        steps: usize,
+    },
+    /// A repeat count of zero
+    ZeroRepeatCount,
 }
 
 impl std::fmt::Display for ScenarioValidationError {
@@ -498,6 +699,22 @@ impl std::fmt::Display for ScenarioValidationError {
             ScenarioValidationError::TooManySteps { count, max } => {
                 write!(f, "Scenario has too many steps ({} > {})", count, max)
             }
+            ScenarioValidationError::DuplicatePhaseName(name) => {
+                write!(f, "Phase name '{}' is used more than once", name)
+            }
+            ScenarioValidationError::EmptyPhase(name) => {
+                write!(f, "Phase '{}' has no steps", name)
+            }
+            ScenarioValidationError::PhaseStepMismatch { phase_steps, steps } => {
+                write!(
+                    f,
+                    "Phases cover {} steps but the scenario has {}",
+                    phase_steps, steps
+                )
+            }
+            ScenarioValidationError::ZeroRepeatCount => {
+                write!(f, "Scenario repeat count must be at least 1")
+            }
         }
     }
 }
@@ -510,6 +727,8 @@ pub enum ScenarioStepValidationError {
     TcConfigError(TcValidationError),
     EmptyDescription,
     InvalidDuration(u64),
+    /// A ramp re-applying TC every 0ms
+    InvalidRampInterval,
 }
 
 impl std::fmt::Display for ScenarioStepValidationError {
@@ -528,6 +747,9 @@ impl std::fmt::Display for ScenarioStepValidationError {
                     duration
                 )
             }
+            ScenarioStepValidationError::InvalidRampInterval => {
+                write!(f, "Ramp interval must be greater than 0ms")
+            }
         }
     }
 }
@@ -547,11 +769,35 @@ impl NetworkScenario {
             name,
             description,
             steps: Vec::new(),
+            phases: Vec::new(),
             loop_scenario: false,
+            repeat: ScenarioRepeat::Once,
             created_at: now,
             modified_at: now,
             metadata: ScenarioMetadata::default(),
             cleanup_on_failure: true,
+            verify_steps: false,
+            cleanup_policy: None,
+        }
+    }
+
+    /// The cleanup for an execution that ended with `end`: the scenario's
+    /// policy, or without one, leave a completed scenario applied and
+    /// restore a stopped one.
+    pub fn cleanup_on(&self, end: ScenarioEnd) -> CleanupPolicy {
+        self.cleanup_policy.unwrap_or(match end {
+            ScenarioEnd::Completed => CleanupPolicy::LeaveApplied,
+            ScenarioEnd::Stopped => CleanupPolicy::RestorePrevious,
+        })
+    }
+
+    /// How an execution runs the steps: forever if it or the scenario
+    /// loops, otherwise as the scenario's repeat says.
+    pub fn repeat_for(&self, loop_execution: bool) -> ScenarioRepeat {
+        if loop_execution || self.loop_scenario {
+            ScenarioRepeat::Forever
+        } else {
+            self.repeat
         }
     }
 
@@ -579,6 +825,22 @@ impl NetworkScenario {
     pub fn estimated_total_duration_ms(&self) -> u64 {
         self.steps.iter().map(|step| step.duration_ms).sum()
     }
+
+    /// Each phase with the range of step indices it covers.
+    pub fn phase_ranges(&self) -> impl Iterator<Item = (&ScenarioPhase, std::ops::Range<usize>)> {
+        self.phases.iter().scan(0, |start, phase| {
+            let range = *start..*start + phase.step_count;
+            *start = range.end;
+            Some((phase, range))
+        })
+    }
+
+    /// Name of the phase containing the step at `step_index`, if any.
+    pub fn phase_of(&self, step_index: usize) -> Option<&str> {
+        self.phase_ranges()
+            .find(|(_, range)| range.contains(&step_index))
+            .map(|(phase, _)| phase.name.as_str())
+    }
 }
 
 impl ScenarioStep {
@@ -588,28 +850,145 @@ impl ScenarioStep {
             duration_ms,
             tc_config,
             description,
+            ramp: None,
+        }
+    }
+
+    /// Ramp into this step, re-applying TC every `interval_ms`
+    pub fn with_ramp(mut self, interval_ms: u64) -> Self {
+        self.ramp = Some(RampSpec { interval_ms });
+        self
+    }
+}
+
+/// Default shortest time between two applies during a ramp
+pub const DEFAULT_RAMP_INTERVAL_MS: u64 = 100;
+
+/// How often a ramp between two configurations may re-apply TC.
+///
+/// Applying on every tick of a smooth ramp would replace the qdisc thousands
+/// of times; pacing quantizes the ramp into applies at least
+/// `min_interval_ms` apart instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RampPacing {
+    /// Shortest time between two applies (at least 1ms)
+    pub min_interval_ms: u64,
+}
+
+/// One apply of a paced ramp.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RampPoint {
+    /// Time since the ramp started
+    pub offset_ms: u64,
+    /// How far along the ramp this apply is, from 0.0 (exclusive) to 1.0
+    pub progress: f64,
+}
+
+impl Default for RampPacing {
+    fn default() -> Self {
+        Self::new(DEFAULT_RAMP_INTERVAL_MS)
+    }
+}
+
+impl RampPacing {
+    /// Pace applies at least `min_interval_ms` apart
+    pub fn new(min_interval_ms: u64) -> Self {
+        Self {
+            min_interval_ms: min_interval_ms.max(1),
         }
     }
+
+    /// When to apply during a ramp of `duration_ms`, evenly spaced and never
+    /// closer than the minimum interval. The last point is always the end of
+    /// the ramp at progress 1.0, so the target value is applied exactly.
+    pub fn schedule(&self, duration_ms: u64) -> Vec<RampPoint> {
+        let applies = (duration_ms / self.min_interval_ms).max(1);
+        (1..=applies)
+            .map(|i| {
+                if i == applies {
+                    RampPoint {
+                        offset_ms: duration_ms,
+                        progress: 1.0,
+                    }
+                } else {
+                    RampPoint {
+                        offset_ms: duration_ms * i / applies,
+                        progress: i as f64 / applies as f64,
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// How a step ramps into its configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RampSpec {
+    /// Time between two applies during the ramp (milliseconds)
+    #[serde(default = "default_ramp_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_ramp_interval_ms() -> u64 {
+    DEFAULT_RAMP_INTERVAL_MS
+}
+
+impl Default for RampSpec {
+    fn default() -> Self {
+        Self {
+            interval_ms: DEFAULT_RAMP_INTERVAL_MS,
+        }
+    }
+}
+
+impl RampSpec {
+    /// Pacing of the applies during the ramp
+    pub fn pacing(&self) -> RampPacing {
+        RampPacing::new(self.interval_ms)
+    }
+}
+
+impl RampPoint {
+    /// Value between `from` and `to` at this point of the ramp
+    pub fn interpolate(&self, from: f32, to: f32) -> f32 {
+        if self.progress >= 1.0 {
+            return to;
+        }
+        from + (to - from) * self.progress as f32
+    }
 }
 
 impl ScenarioExecution {
+    /// Move to the step at `step_index`, tracking the phase it belongs to.
+    pub fn set_current_step(&mut self, step_index: usize) {
+        self.current_step = step_index;
+        self.current_phase = self.scenario.phase_of(step_index).map(str::to_string);
+    }
+
     /// Calculate current progress percentage (0.0-100.0)
     pub fn calculate_progress(&self) -> f32 {
         match self.state {
             ExecutionState::Completed => 100.0,
             ExecutionState::Failed { .. } => self.stats.progress_percent,
             ExecutionState::Stopped => self.stats.progress_percent,
+            ExecutionState::Scheduled { .. } => 0.0,
             ExecutionState::Running | ExecutionState::Paused { .. } => {
                 if self.scenario.steps.is_empty() {
                     return 100.0;
                 }
 
-                let current_time = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as u64;
-
-                let elapsed_ms = current_time.saturating_sub(self.start_time);
+                // Time stands still while paused
+                let current_time = match self.state {
+                    ExecutionState::Paused { paused_at } => paused_at,
+                    _ => std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis() as u64,
+                };
+
+                let elapsed_ms = current_time
+                    .saturating_sub(self.start_time)
+                    .saturating_sub(self.paused_ms);
                 let total_duration = self.scenario.estimated_total_duration_ms();
 
                 if total_duration == 0 {
@@ -621,11 +1000,13 @@ impl ScenarioExecution {
         }
     }
 
-    /// Check if execution is currently active (running or paused)
+    /// Check if execution is currently active (scheduled, running or paused)
     pub fn is_active(&self) -> bool {
         matches!(
             self.state,
-            ExecutionState::Running | ExecutionState::Paused { .. }
+            ExecutionState::Scheduled { .. }
+                | ExecutionState::Running
+                | ExecutionState::Paused { .. }
         )
     }
 
@@ -639,6 +1020,31 @@ impl ScenarioExecution {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ramp_pacing_bounds_the_number_of_applies() {
+        let pacing = RampPacing::default();
+        let schedule = pacing.schedule(10_000);
+        assert_eq!(schedule.len(), 100);
+        assert!(
+            schedule
+                .windows(2)
+                .all(|pair| pair[1].offset_ms - pair[0].offset_ms >= pacing.min_interval_ms)
+        );
+
+        // An interval that does not divide the duration still ends exactly
+        let schedule = RampPacing::new(300).schedule(1_000);
+        assert_eq!(schedule.len(), 3);
+        let last = schedule.last().unwrap();
+        assert_eq!((last.offset_ms, last.progress), (1_000, 1.0));
+        assert_eq!(last.interpolate(10.0, 0.3), 0.3);
+
+        // Shorter than one interval: a single apply at the end
+        let schedule = RampPacing::new(500).schedule(200);
+        assert_eq!(schedule.len(), 1);
+        assert_eq!(schedule[0].offset_ms, 200);
+        assert_eq!(RampPacing::new(0).min_interval_ms, 1);
+    }
+
     #[test]
     fn test_scenario_creation() {
         let scenario = NetworkScenario::new(
@@ -652,10 +1058,89 @@ mod tests {
         assert_eq!(scenario.description, "A test scenario for validation");
         assert!(scenario.steps.is_empty());
         assert!(!scenario.loop_scenario);
+        assert_eq!(scenario.repeat, ScenarioRepeat::Once);
         assert!(scenario.created_at > 0);
         assert_eq!(scenario.created_at, scenario.modified_at);
     }
 
+    #[test]
+    fn test_requested_start() {
+        assert_eq!(requested_start(None, None, 1_000).unwrap(), None);
+        assert_eq!(
+            requested_start(Some(5_000), None, 1_000).unwrap(),
+            Some(5_000)
+        );
+        assert_eq!(
+            requested_start(None, Some(300), 1_000).unwrap(),
+            Some(1_300)
+        );
+        assert_eq!(requested_start(None, Some(0), 1_000).unwrap(), None);
+        let error = requested_start(Some(5_000), Some(300), 1_000).unwrap_err();
+        assert_eq!(error.category, ScenarioErrorCategory::Validation);
+    }
+
+    #[test]
+    fn test_scenario_repeat() {
+        assert!(!ScenarioRepeat::Once.runs_again(1));
+        assert!(ScenarioRepeat::Count(3).runs_again(2));
+        assert!(!ScenarioRepeat::Count(3).runs_again(3));
+        assert!(ScenarioRepeat::Forever.runs_again(u32::MAX));
+
+        let mut scenario = NetworkScenario::new(
+            "soak".to_string(),
+            "Soak".to_string(),
+            "Repeat a loss step".to_string(),
+        );
+        scenario.add_step(ScenarioStep::new(
+            1000,
+            "Loss".to_string(),
+            TcNetemConfig::new(),
+        ));
+        scenario.repeat = ScenarioRepeat::Count(3);
+        assert_eq!(scenario.repeat_for(false), ScenarioRepeat::Count(3));
+        assert_eq!(scenario.repeat_for(true), ScenarioRepeat::Forever);
+        assert!(scenario.validate().is_ok());
+
+        let json = serde_json::to_value(&scenario).unwrap();
+        assert_eq!(json["repeat"], serde_json::json!({ "count": 3 }));
+
+        scenario.repeat = ScenarioRepeat::Count(0);
+        assert!(matches!(
+            scenario.validate(),
+            Err(ScenarioValidationError::ZeroRepeatCount)
+        ));
+    }
+
+    #[test]
+    fn test_cleanup_policy_per_scenario_end() {
+        let mut scenario = NetworkScenario::new(
+            "outage".to_string(),
+            "Outage".to_string(),
+            "Emulate an outage".to_string(),
+        );
+
+        // Unset: a completed scenario stays applied, a stopped one is restored
+        assert_eq!(
+            scenario.cleanup_on(ScenarioEnd::Completed),
+            CleanupPolicy::LeaveApplied
+        );
+        assert_eq!(
+            scenario.cleanup_on(ScenarioEnd::Stopped),
+            CleanupPolicy::RestorePrevious
+        );
+
+        // A set policy applies however the execution ended
+        for policy in [
+            CleanupPolicy::LeaveApplied,
+            CleanupPolicy::RemoveAll,
+            CleanupPolicy::RestorePrevious,
+        ] {
+            scenario.cleanup_policy = Some(policy);
+            assert_eq!(scenario.cleanup_on(ScenarioEnd::Completed), policy);
+            assert_eq!(scenario.cleanup_on(ScenarioEnd::Stopped), policy);
+        }
+    }
+
     #[test]
     fn test_scenario_step_creation() {
         let mut tc_config = TcNetemConfig::new();
@@ -704,6 +1189,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scenario_validation_phases() {
+        let mut scenario = NetworkScenario::new(
+            "test".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+        );
+        for i in 0..3 {
+            scenario.add_step(ScenarioStep::new(
+                1000,
+                format!("Step {}", i),
+                TcNetemConfig::new(),
+            ));
+        }
+        let phase = |name: &str, step_count| ScenarioPhase {
+            name: name.to_string(),
+            step_count,
+        };
+
+        scenario.phases = vec![phase("Warmup", 1), phase("Outage", 2)];
+        assert!(scenario.validate().is_ok());
+        assert_eq!(scenario.phase_of(1), Some("Outage"));
+
+        scenario.phases = vec![phase("Outage", 1), phase("Outage", 2)];
+        assert!(matches!(
+            scenario.validate(),
+            Err(ScenarioValidationError::DuplicatePhaseName(name)) if name == "Outage"
+        ));
+
+        scenario.phases = vec![phase("Warmup", 1), phase("Outage", 1)];
+        assert!(matches!(
+            scenario.validate(),
+            Err(ScenarioValidationError::PhaseStepMismatch {
+                phase_steps: 2,
+                steps: 3
+            })
+        ));
+    }
+
     #[test]
     fn test_scenario_step_validation() {
         let mut tc_config = TcNetemConfig::new();
@@ -746,6 +1270,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scenario_step_ramp() {
+        let mut tc_config = TcNetemConfig::new();
+        tc_config.loss.enabled = true;
+        tc_config.loss.percentage = 10.0;
+        let step = ScenarioStep::new(2000, "Ramp".to_string(), tc_config).with_ramp(1000);
+        assert!(step.validate().is_ok());
+
+        // A 1s tick over 2s applies half way, then the step's value
+        let schedule = step.ramp.unwrap().pacing().schedule(step.duration_ms);
+        let applied: Vec<_> = schedule
+            .iter()
+            .map(|point| (point.offset_ms, point.interpolate(0.0, 10.0)))
+            .collect();
+        assert_eq!(applied, [(1000, 5.0), (2000, 10.0)]);
+
+        let json = serde_json::to_string(&step).unwrap();
+        let parsed: ScenarioStep = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.ramp, Some(RampSpec { interval_ms: 1000 }));
+        let parsed: ScenarioStep =
+            serde_json::from_str(&json.replace(r#"{"interval_ms":1000}"#, "{}")).unwrap();
+        assert_eq!(parsed.ramp, Some(RampSpec::default()));
+
+        let step = step.with_ramp(0);
+        assert!(matches!(
+            step.validate(),
+            Err(ScenarioStepValidationError::InvalidRampInterval)
+        ));
+    }
+
     #[test]
     fn test_scenario_duration_calculation() {
         let mut scenario = NetworkScenario::new(
@@ -793,6 +1347,8 @@ mod tests {
             stats: ExecutionStats::default(),
             loop_execution: false,
             loop_iteration: 0,
+            paused_ms: 0,
+            current_phase: None,
         };
 
         // Should be 100% for empty scenario
@@ -810,6 +1366,41 @@ mod tests {
         assert_eq!(execution.calculate_progress(), 50.0);
     }
 
+    #[test]
+    fn test_scenario_execution_progress_excludes_paused_time() {
+        let mut scenario = NetworkScenario::new(
+            "test".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+        );
+        scenario.add_step(ScenarioStep::new(
+            20_000,
+            "Step".to_string(),
+            TcNetemConfig::new(),
+        ));
+
+        // 6s in, 1s of which paused: 5s of the 20s have elapsed
+        let mut execution = ScenarioExecution {
+            scenario,
+            start_time: 1_000_000,
+            current_step: 0,
+            state: ExecutionState::Paused {
+                paused_at: 1_006_000,
+            },
+            target_namespace: "default".to_string(),
+            target_interface: "eth0".to_string(),
+            stats: ExecutionStats::default(),
+            loop_execution: false,
+            loop_iteration: 0,
+            paused_ms: 1_000,
+            current_phase: None,
+        };
+        assert_eq!(execution.calculate_progress(), 25.0);
+
+        execution.paused_ms = 6_000;
+        assert_eq!(execution.calculate_progress(), 0.0);
+    }
+
     #[test]
     fn test_scenario_execution_active_state() {
         let scenario = NetworkScenario::new(
@@ -828,6 +1419,8 @@ mod tests {
             stats: ExecutionStats::default(),
             loop_execution: false,
             loop_iteration: 0,
+            paused_ms: 0,
+            current_phase: None,
         };
 
         assert!(execution.is_active());
@@ -835,6 +1428,10 @@ mod tests {
         execution.state = ExecutionState::Paused { paused_at: 12345 };
         assert!(execution.is_active());
 
+        execution.state = ExecutionState::Scheduled { start_at: 67890 };
+        assert!(execution.is_active());
+        assert_eq!(execution.calculate_progress(), 0.0);
+
         execution.state = ExecutionState::Completed;
         assert!(!execution.is_active());
 
@@ -865,6 +1462,8 @@ mod tests {
             stats: ExecutionStats::default(),
             loop_execution: false,
             loop_iteration: 0,
+            paused_ms: 0,
+            current_phase: None,
         };
 
         assert_eq!(execution.execution_key(), "test-namespace/eth1");
@@ -8,12 +8,16 @@
 //! "50ms", "5s", "1m", "1h", "1d", "5m30s", etc.
 
 use duration_string::DurationString;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::scenario::{NetworkScenario, ScenarioMetadata, ScenarioStep};
+use crate::scenario::{
+    CleanupPolicy, NetworkScenario, RampSpec, ScenarioMetadata, ScenarioPhase, ScenarioRepeat,
+    ScenarioStep,
+};
 use crate::{
-    TcCorruptConfig, TcDelayConfig, TcDuplicateConfig, TcLossConfig, TcNetemConfig,
-    TcRateLimitConfig, TcReorderConfig,
+    DelayDistribution, GeModel, LossModel, RateLimitMode, TcCorruptConfig, TcDelayConfig,
+    TcDuplicateConfig, TcLossConfig, TcNetemConfig, TcRateLimitConfig, TcRateLimitMode,
+    TcReorderConfig, TcSlotConfig,
 };
 
 /// Parse a duration string like "50ms", "5s", "1m", "1h" into milliseconds
@@ -28,6 +32,26 @@ pub fn parse_duration_string(s: &str) -> Result<u64, String> {
     Ok(std_duration.as_millis() as u64)
 }
 
+/// Format milliseconds as the shortest duration string that parses back
+/// exactly, e.g. `"1m30s"`, `"5s"` or `"1500ms"`.
+pub fn format_duration_ms(ms: u64) -> String {
+    if ms == 0 || !ms.is_multiple_of(1000) {
+        return format!("{}ms", ms);
+    }
+    let mut secs = ms / 1000;
+    let mut out = String::new();
+    for (unit, size) in [("h", 3600), ("m", 60)] {
+        if secs >= size {
+            out.push_str(&format!("{}{}", secs / size, unit));
+            secs %= size;
+        }
+    }
+    if secs > 0 {
+        out.push_str(&format!("{}s", secs));
+    }
+    out
+}
+
 /// Error type for scenario JSON5 parsing
 #[derive(Debug)]
 pub enum ScenarioParseError {
@@ -58,7 +82,7 @@ impl From<std::io::Error> for ScenarioParseError {
 }
 
 /// Intermediate struct for JSON5 deserialization of a scenario file
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct ScenarioFile {
     pub id: String,
     pub name: String,
@@ -66,12 +90,28 @@ pub struct ScenarioFile {
     pub description: String,
     #[serde(default)]
     pub loop_scenario: bool,
+    /// How many times the steps run (`once`, `{ count: N }` or `forever`)
+    #[serde(default, skip_serializing_if = "ScenarioRepeat::is_once")]
+    pub repeat: ScenarioRepeat,
     #[serde(default)]
     pub metadata: ScenarioMetadataJson,
+    /// Steps of a scenario without phases
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub steps: Vec<ScenarioStepJson>,
+    /// Named groups of steps, run in order (mutually exclusive with `steps`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<ScenarioPhaseJson>,
     /// Whether to restore original TC configuration on failure/abort (default: true)
     #[serde(default = "default_cleanup_on_failure")]
     pub cleanup_on_failure: bool,
+    /// Whether to read the configuration back after each step and fail if it
+    /// did not take effect (default: false)
+    #[serde(default)]
+    pub verify_steps: bool,
+    /// What to do with the TC configuration when the scenario completes or
+    /// is stopped (`leave_applied`, `remove_all` or `restore_previous`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleanup_policy: Option<CleanupPolicy>,
 }
 
 fn default_cleanup_on_failure() -> bool {
@@ -79,11 +119,14 @@ fn default_cleanup_on_failure() -> bool {
 }
 
 /// Intermediate struct for scenario metadata
-#[derive(Debug, Clone, Default, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct ScenarioMetadataJson {
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
     #[serde(default = "default_version")]
     pub version: String,
 }
@@ -92,8 +135,15 @@ fn default_version() -> String {
     "1.0".to_string()
 }
 
+/// Intermediate struct for a named phase of a scenario
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ScenarioPhaseJson {
+    pub name: String,
+    pub steps: Vec<ScenarioStepJson>,
+}
+
 /// Intermediate struct for a scenario step
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct ScenarioStepJson {
     /// Duration as a string like "30s", "500ms", "1m"
     pub duration: String,
@@ -103,32 +153,62 @@ pub struct ScenarioStepJson {
     pub tc_config: TcConfigJson,
     /// Reference to a preset by ID (mutually exclusive with `tc_config`)
     /// When both are provided, `preset` takes precedence
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub preset: Option<String>,
+    /// Ramp from the previous step's configuration over the step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ramp: Option<RampJson>,
+}
+
+/// Intermediate struct for a step's ramp
+#[derive(Debug, Clone, Default, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct RampJson {
+    /// Time between two applies as a string like "500ms" (default 100ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
 }
 
 /// Intermediate struct for TC config with implicit enabled
 /// Any field present automatically means enabled=true
-#[derive(Debug, Clone, Default, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct TcConfigJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub loss: Option<LossConfigJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub delay: Option<DelayConfigJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub duplicate: Option<DuplicateConfigJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reorder: Option<ReorderConfigJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub corrupt: Option<CorruptConfigJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfigJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<SlotConfigJson>,
+    /// Seed for netem's random choices, to make the step repeatable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 /// Loss configuration for JSON5 parsing (presence implies enabled)
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct LossConfigJson {
     #[serde(default)]
     pub percentage: f32,
     #[serde(default)]
     pub correlation: f32,
+    /// Gilbert-Elliott model used instead of `percentage`; parameters left
+    /// out take the [`GeModel`] defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemodel: Option<GeModel>,
+    /// ECN-mark lost packets that are ECN-capable instead of dropping them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ecn: bool,
 }
 
 /// Delay configuration for JSON5 parsing (presence implies enabled)
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct DelayConfigJson {
     #[serde(default)]
     pub base_ms: f32,
@@ -136,10 +216,13 @@ pub struct DelayConfigJson {
     pub jitter_ms: f32,
     #[serde(default)]
     pub correlation: f32,
+    /// `normal`, `pareto` or `paretonormal`; uniform when left out
+    #[serde(default, skip_serializing_if = "DelayDistribution::is_uniform")]
+    pub distribution: DelayDistribution,
 }
 
 /// Duplicate configuration for JSON5 parsing (presence implies enabled)
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct DuplicateConfigJson {
     #[serde(default)]
     pub percentage: f32,
@@ -148,7 +231,7 @@ pub struct DuplicateConfigJson {
 }
 
 /// Reorder configuration for JSON5 parsing (presence implies enabled)
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct ReorderConfigJson {
     #[serde(default)]
     pub percentage: f32,
@@ -163,7 +246,7 @@ fn default_gap() -> u32 {
 }
 
 /// Corrupt configuration for JSON5 parsing (presence implies enabled)
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct CorruptConfigJson {
     #[serde(default)]
     pub percentage: f32,
@@ -171,17 +254,63 @@ pub struct CorruptConfigJson {
     pub correlation: f32,
 }
 
+/// Slot configuration for JSON5 parsing (presence implies enabled)
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SlotConfigJson {
+    #[serde(default)]
+    pub min_delay_ms: f32,
+    pub max_delay_ms: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packets: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u32>,
+}
+
+impl SlotConfigJson {
+    /// Convert to an enabled [`TcSlotConfig`].
+    pub fn to_slot_config(&self) -> TcSlotConfig {
+        TcSlotConfig {
+            enabled: true,
+            min_delay_ms: self.min_delay_ms,
+            max_delay_ms: self.max_delay_ms,
+            packets: self.packets,
+            bytes: self.bytes,
+        }
+    }
+}
+
+impl From<&TcSlotConfig> for SlotConfigJson {
+    fn from(slot: &TcSlotConfig) -> Self {
+        Self {
+            min_delay_ms: slot.min_delay_ms,
+            max_delay_ms: slot.max_delay_ms,
+            packets: slot.packets,
+            bytes: slot.bytes,
+        }
+    }
+}
+
 /// Rate limit configuration for JSON5 parsing (presence implies enabled)
 /// Supports both human-readable rate strings (e.g., "10mbit") and legacy rate_kbps values.
-#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct RateLimitConfigJson {
     /// Human-readable rate string (e.g., "10mbit", "1gbit", "500kbit")
     /// Takes precedence over rate_kbps if both are provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rate: Option<String>,
 
     /// Legacy: rate in kbps (deprecated, use `rate` instead)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_kbps: Option<u32>,
+
+    /// Rate as a percentage of the interface's link speed, resolved by the
+    /// backend when applying. Takes precedence over `rate` and `rate_kbps`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent_of_link_speed: Option<f32>,
+
+    /// How the rate is shaped: `netem` (default) or `htb`
+    #[serde(default, skip_serializing_if = "RateLimitMode::is_netem")]
+    pub shaper: RateLimitMode,
 }
 
 impl RateLimitConfigJson {
@@ -196,6 +325,24 @@ impl RateLimitConfigJson {
             Ok(1000) // Default 1000 kbps
         }
     }
+
+    /// Convert to an enabled [`TcRateLimitConfig`].
+    pub fn to_rate_limit_config(&self) -> Result<TcRateLimitConfig, String> {
+        Ok(match self.percent_of_link_speed {
+            Some(percent) => TcRateLimitConfig {
+                enabled: true,
+                rate_kbps: 1000, // Placeholder until resolved against the link speed
+                mode: TcRateLimitMode::PercentOfLinkSpeed(percent),
+                shaper: self.shaper,
+            },
+            None => TcRateLimitConfig {
+                enabled: true,
+                rate_kbps: self.to_rate_kbps()?,
+                mode: TcRateLimitMode::Absolute,
+                shaper: self.shaper,
+            },
+        })
+    }
 }
 
 /// Parse a human-readable TC rate string (e.g., `"10mbit"`, `"1gbit"`) to kbps
@@ -230,6 +377,10 @@ impl TcConfigJson {
                     enabled: true, // Implicit!
                     percentage: loss.percentage,
                     correlation: loss.correlation,
+                    model: loss
+                        .gemodel
+                        .map_or(LossModel::Random, LossModel::GilbertElliott),
+                    ecn: loss.ecn,
                 },
                 None => TcLossConfig::default(),
             },
@@ -239,6 +390,7 @@ impl TcConfigJson {
                     base_ms: delay.base_ms,
                     jitter_ms: delay.jitter_ms,
                     correlation: delay.correlation,
+                    distribution: delay.distribution,
                 },
                 None => TcDelayConfig::default(),
             },
@@ -273,19 +425,81 @@ impl TcConfigJson {
                 None => TcCorruptConfig::default(),
             },
             rate_limit: match &self.rate_limit {
-                Some(rate) => TcRateLimitConfig {
-                    enabled: true, // Implicit!
-                    rate_kbps: rate.to_rate_kbps()?,
-                },
+                Some(rate) => rate.to_rate_limit_config()?, // Implicitly enabled
                 None => TcRateLimitConfig {
                     enabled: false,
                     rate_kbps: 1000, // Default rate
+                    mode: TcRateLimitMode::Absolute,
+                    shaper: RateLimitMode::Netem,
                 },
             },
+            slot: match &self.slot {
+                Some(slot) => slot.to_slot_config(),
+                None => TcSlotConfig::wifi_default(),
+            },
+            seed: self.seed,
         })
     }
 }
 
+impl From<&TcNetemConfig> for TcConfigJson {
+    /// The file form of a configuration: only enabled features are written,
+    /// since presence is what enables them.
+    fn from(config: &TcNetemConfig) -> Self {
+        Self {
+            loss: config.loss.enabled.then_some(LossConfigJson {
+                percentage: config.loss.percentage,
+                correlation: config.loss.correlation,
+                gemodel: config.loss.model.gemodel(),
+                ecn: config.loss.ecn,
+            }),
+            delay: config.delay.enabled.then_some(DelayConfigJson {
+                base_ms: config.delay.base_ms,
+                jitter_ms: config.delay.jitter_ms,
+                correlation: config.delay.correlation,
+                distribution: config.delay.distribution,
+            }),
+            duplicate: config.duplicate.enabled.then_some(DuplicateConfigJson {
+                percentage: config.duplicate.percentage,
+                correlation: config.duplicate.correlation,
+            }),
+            reorder: config.reorder.enabled.then_some(ReorderConfigJson {
+                percentage: config.reorder.percentage,
+                correlation: config.reorder.correlation,
+                gap: config.reorder.gap,
+            }),
+            corrupt: config.corrupt.enabled.then_some(CorruptConfigJson {
+                percentage: config.corrupt.percentage,
+                correlation: config.corrupt.correlation,
+            }),
+            // kbps is written as-is: it is what the config holds, and a rate
+            // string would have to survive another unit conversion
+            rate_limit: config
+                .rate_limit
+                .enabled
+                .then_some(match config.rate_limit.mode {
+                    TcRateLimitMode::Absolute => RateLimitConfigJson {
+                        rate: None,
+                        rate_kbps: Some(config.rate_limit.rate_kbps),
+                        percent_of_link_speed: None,
+                        shaper: config.rate_limit.shaper,
+                    },
+                    TcRateLimitMode::PercentOfLinkSpeed(percent) => RateLimitConfigJson {
+                        rate: None,
+                        rate_kbps: None,
+                        percent_of_link_speed: Some(percent),
+                        shaper: config.rate_limit.shaper,
+                    },
+                }),
+            slot: config
+                .slot
+                .enabled
+                .then(|| SlotConfigJson::from(&config.slot)),
+            seed: config.seed,
+        }
+    }
+}
+
 /// Trait for resolving preset IDs to TC configurations
 pub trait PresetResolver {
     /// Resolve a preset ID to its TC configuration
@@ -335,10 +549,28 @@ impl ScenarioStepJson {
             })?
         };
 
+        let ramp = match &self.ramp {
+            Some(RampJson {
+                interval: Some(interval),
+            }) => Some(RampSpec {
+                interval_ms: parse_duration_string(interval).map_err(|e| {
+                    ScenarioParseError::ValidationError(format!(
+                        "Invalid ramp interval '{}' in step {}: {}",
+                        interval,
+                        step_index + 1,
+                        e
+                    ))
+                })?,
+            }),
+            Some(RampJson { interval: None }) => Some(RampSpec::default()),
+            None => None,
+        };
+
         Ok(ScenarioStep {
             duration_ms,
             description: self.description.clone(),
             tc_config,
+            ramp,
         })
     }
 }
@@ -370,8 +602,36 @@ impl ScenarioFile {
             .unwrap_or_default()
             .as_secs();
 
-        let mut steps: Vec<ScenarioStep> = Vec::with_capacity(self.steps.len());
-        for (i, step) in self.steps.iter().enumerate() {
+        if !self.steps.is_empty() && !self.phases.is_empty() {
+            return Err(ScenarioParseError::ValidationError(
+                "A scenario has either 'steps' or 'phases', not both".to_string(),
+            ));
+        }
+        let mut names = std::collections::HashSet::new();
+        for phase in &self.phases {
+            if !names.insert(phase.name.as_str()) {
+                return Err(ScenarioParseError::ValidationError(format!(
+                    "Duplicate phase name '{}'",
+                    phase.name
+                )));
+            }
+        }
+
+        let phases: Vec<ScenarioPhase> = self
+            .phases
+            .iter()
+            .map(|phase| ScenarioPhase {
+                name: phase.name.clone(),
+                step_count: phase.steps.len(),
+            })
+            .collect();
+        let step_files: Vec<&ScenarioStepJson> = self
+            .steps
+            .iter()
+            .chain(self.phases.iter().flat_map(|phase| &phase.steps))
+            .collect();
+        let mut steps: Vec<ScenarioStep> = Vec::with_capacity(step_files.len());
+        for (i, step) in step_files.into_iter().enumerate() {
             steps.push(step.to_scenario_step(i, preset_resolver)?);
         }
 
@@ -383,20 +643,83 @@ impl ScenarioFile {
             name: self.name,
             description: self.description,
             steps,
+            phases,
             loop_scenario: self.loop_scenario,
+            repeat: self.repeat,
             created_at: now,
             modified_at: now,
             metadata: ScenarioMetadata {
                 tags: self.metadata.tags,
                 author: self.metadata.author,
+                category: self.metadata.category,
                 version: self.metadata.version,
                 duration_ms,
             },
             cleanup_on_failure: self.cleanup_on_failure,
+            verify_steps: self.verify_steps,
+            cleanup_policy: self.cleanup_policy,
         })
     }
 }
 
+impl From<&ScenarioStep> for ScenarioStepJson {
+    fn from(step: &ScenarioStep) -> Self {
+        Self {
+            duration: format_duration_ms(step.duration_ms),
+            description: step.description.clone(),
+            tc_config: TcConfigJson::from(&step.tc_config),
+            preset: None,
+            ramp: step.ramp.map(|ramp| RampJson {
+                interval: Some(format_duration_ms(ramp.interval_ms)),
+            }),
+        }
+    }
+}
+
+impl From<&NetworkScenario> for ScenarioFile {
+    fn from(scenario: &NetworkScenario) -> Self {
+        Self {
+            id: scenario.id.clone(),
+            name: scenario.name.clone(),
+            description: scenario.description.clone(),
+            loop_scenario: scenario.loop_scenario,
+            repeat: scenario.repeat,
+            metadata: ScenarioMetadataJson {
+                tags: scenario.metadata.tags.clone(),
+                author: scenario.metadata.author.clone(),
+                category: scenario.metadata.category.clone(),
+                version: scenario.metadata.version.clone(),
+            },
+            steps: if scenario.phases.is_empty() {
+                scenario.steps.iter().map(ScenarioStepJson::from).collect()
+            } else {
+                Vec::new()
+            },
+            phases: scenario
+                .phase_ranges()
+                .map(|(phase, range)| ScenarioPhaseJson {
+                    name: phase.name.clone(),
+                    steps: scenario.steps[range]
+                        .iter()
+                        .map(ScenarioStepJson::from)
+                        .collect(),
+                })
+                .collect(),
+            cleanup_on_failure: scenario.cleanup_on_failure,
+            verify_steps: scenario.verify_steps,
+            cleanup_policy: scenario.cleanup_policy,
+        }
+    }
+}
+
+/// Render a scenario in the scenario file format, ready to be edited and
+/// dropped into a scenario directory. The output is plain JSON, which every
+/// JSON5 reader accepts.
+pub fn scenario_to_json5(scenario: &NetworkScenario) -> String {
+    serde_json::to_string_pretty(&ScenarioFile::from(scenario))
+        .expect("scenario file types always serialize")
+}
+
 /// Parse a scenario from a JSON5 string
 pub fn parse_scenario_json5(json5_content: &str) -> Result<ScenarioFile, ScenarioParseError> {
     json5::from_str(json5_content).map_err(|e| ScenarioParseError::Json5Error(e.to_string()))
@@ -460,6 +783,142 @@ mod tests {
         assert_eq!(parse_duration_string("  30s  ").unwrap(), 30000);
     }
 
+    #[test]
+    fn test_format_duration_ms_round_trips() {
+        assert_eq!(format_duration_ms(1500), "1500ms");
+        assert_eq!(format_duration_ms(30_000), "30s");
+        assert_eq!(format_duration_ms(90_000), "1m30s");
+        assert_eq!(format_duration_ms(3_600_000), "1h");
+        for ms in [1, 999, 1000, 61_000, 3_725_000, 3_725_001] {
+            assert_eq!(parse_duration_string(&format_duration_ms(ms)).unwrap(), ms);
+        }
+    }
+
+    #[test]
+    fn test_scenario_to_json5_round_trips() {
+        let mut first = TcNetemConfig::new();
+        first.delay.enabled = true;
+        first.delay.base_ms = 100.0;
+        first.delay.jitter_ms = 10.0;
+        let mut second = first.clone();
+        second.loss.enabled = true;
+        second.loss.percentage = 5.0;
+        second.rate_limit.enabled = true;
+        second.rate_limit.rate_kbps = 2000;
+
+        let original = NetworkScenario {
+            id: "recorded".to_string(),
+            name: "Recorded".to_string(),
+            description: "From manual changes".to_string(),
+            steps: vec![
+                ScenarioStep {
+                    duration_ms: 12_500,
+                    tc_config: first,
+                    description: "delay".to_string(),
+                    ramp: None,
+                },
+                ScenarioStep {
+                    duration_ms: 30_000,
+                    tc_config: second,
+                    description: "delay, loss, rate".to_string(),
+                    ramp: Some(RampSpec { interval_ms: 500 }),
+                },
+            ],
+            phases: Vec::new(),
+            loop_scenario: false,
+            repeat: ScenarioRepeat::Once,
+            created_at: 0,
+            modified_at: 0,
+            metadata: ScenarioMetadata {
+                tags: vec!["recorded".to_string()],
+                author: None,
+                category: Some("regression".to_string()),
+                version: "1.0".to_string(),
+                duration_ms: 42_500,
+            },
+            cleanup_on_failure: true,
+            verify_steps: true,
+            cleanup_policy: None,
+        };
+
+        let text = scenario_to_json5(&original);
+        assert!(text.contains("\"12500ms\""));
+        assert!(!text.contains("duplicate"));
+
+        let parsed = parse_scenario(&text).unwrap();
+        assert_eq!(parsed.id, original.id);
+        assert_eq!(parsed.metadata.tags, original.metadata.tags);
+        assert!(parsed.verify_steps);
+        assert_eq!(parsed.metadata.category, original.metadata.category);
+        assert_eq!(parsed.metadata.duration_ms, 42_500);
+        for (parsed, original) in parsed.steps.iter().zip(&original.steps) {
+            assert_eq!(parsed.duration_ms, original.duration_ms);
+            assert_eq!(parsed.description, original.description);
+            assert_eq!(parsed.tc_config, original.tc_config);
+            assert_eq!(parsed.ramp, original.ramp);
+        }
+    }
+
+    #[test]
+    fn test_parse_phases() {
+        let json5 = r#"
+        {
+            id: "phased",
+            name: "Phased",
+            phases: [
+                {
+                    name: "Warmup",
+                    steps: [{ duration: "10s", description: "clean" }],
+                },
+                {
+                    name: "Outage",
+                    steps: [
+                        { duration: "5s", description: "lossy", tc_config: { loss: { percentage: 50 } } },
+                        { duration: "5s", description: "down", tc_config: { loss: { percentage: 100 } } },
+                    ],
+                },
+            ],
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        assert_eq!(scenario.steps.len(), 3);
+        assert_eq!(scenario.metadata.duration_ms, 20_000);
+        assert_eq!(scenario.phase_of(0), Some("Warmup"));
+        assert_eq!(scenario.phase_of(2), Some("Outage"));
+        assert_eq!(scenario.phase_of(3), None);
+        assert!(crate::TcValidate::validate(&scenario).is_ok());
+
+        // Phases survive a round trip through the file format
+        let reparsed = parse_scenario(&scenario_to_json5(&scenario)).unwrap();
+        assert_eq!(reparsed.phases, scenario.phases);
+        assert_eq!(reparsed.steps.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_phases_rejects_duplicates_and_mixing_with_steps() {
+        let duplicate = r#"
+        {
+            id: "dup", name: "Dup",
+            phases: [
+                { name: "Outage", steps: [{ duration: "1s", description: "a" }] },
+                { name: "Outage", steps: [{ duration: "1s", description: "b" }] },
+            ],
+        }
+        "#;
+        let err = parse_scenario(duplicate).unwrap_err();
+        assert!(err.to_string().contains("Duplicate phase name 'Outage'"));
+
+        let mixed = r#"
+        {
+            id: "mixed", name: "Mixed",
+            steps: [{ duration: "1s", description: "a" }],
+            phases: [{ name: "Outage", steps: [{ duration: "1s", description: "b" }] }],
+        }
+        "#;
+        assert!(parse_scenario(mixed).is_err());
+    }
+
     #[test]
     fn test_parse_duration_string_invalid() {
         assert!(parse_duration_string("").is_err());
@@ -565,6 +1024,7 @@ mod tests {
             metadata: {
                 tags: ["test", "demo"],
                 author: "Test Author",
+                category: "mobile",
                 version: "2.0"
             },
             steps: [
@@ -577,6 +1037,7 @@ mod tests {
         assert_eq!(scenario.description, "A test scenario");
         assert_eq!(scenario.metadata.tags, vec!["test", "demo"]);
         assert_eq!(scenario.metadata.author, Some("Test Author".to_string()));
+        assert_eq!(scenario.metadata.category, Some("mobile".to_string()));
         assert_eq!(scenario.metadata.version, "2.0");
     }
 
@@ -619,6 +1080,93 @@ mod tests {
         assert!(scenario.loop_scenario);
     }
 
+    #[test]
+    fn test_parse_ramp() {
+        let json5 = r#"
+        {
+            id: "fade",
+            name: "Fade",
+            steps: [
+                { duration: "10s", description: "clean" },
+                { duration: "2s", description: "fade", tc_config: { loss: { percentage: 10 } }, ramp: { interval: "500ms" } },
+                { duration: "2s", description: "recover", ramp: {} }
+            ]
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        assert_eq!(scenario.steps[0].ramp, None);
+        assert_eq!(scenario.steps[1].ramp, Some(RampSpec { interval_ms: 500 }));
+        assert_eq!(scenario.steps[2].ramp, Some(RampSpec::default()));
+
+        let bad = json5.replace("\"500ms\"", "\"soon\"");
+        assert!(parse_scenario(&bad).is_err());
+    }
+
+    #[test]
+    fn test_parse_repeat() {
+        let json5 = r#"
+        {
+            id: "soak",
+            name: "Soak",
+            repeat: { count: 3 },
+            steps: [
+                { duration: "10s", description: "Step 1", tc_config: {} }
+            ]
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        assert_eq!(scenario.repeat, ScenarioRepeat::Count(3));
+        assert_eq!(
+            ScenarioFile::from(&scenario).repeat,
+            ScenarioRepeat::Count(3)
+        );
+
+        let forever = json5.replace("{ count: 3 }", "\"forever\"");
+        assert_eq!(
+            parse_scenario(&forever).unwrap().repeat,
+            ScenarioRepeat::Forever
+        );
+    }
+
+    #[test]
+    fn test_parse_cleanup_policy() {
+        let json5 = r#"
+        {
+            id: "outage",
+            name: "Outage",
+            cleanup_policy: "restore_previous",
+            steps: [
+                { duration: "10s", description: "Down", tc_config: { loss: { percentage: 100 } } }
+            ]
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        assert_eq!(
+            scenario.cleanup_policy,
+            Some(CleanupPolicy::RestorePrevious)
+        );
+        let reparsed = parse_scenario(&scenario_to_json5(&scenario)).unwrap();
+        assert_eq!(reparsed.cleanup_policy, scenario.cleanup_policy);
+
+        for (name, policy) in [
+            ("leave_applied", CleanupPolicy::LeaveApplied),
+            ("remove_all", CleanupPolicy::RemoveAll),
+        ] {
+            let json5 = json5.replace("restore_previous", name);
+            assert_eq!(parse_scenario(&json5).unwrap().cleanup_policy, Some(policy));
+        }
+        assert!(parse_scenario_json5(&json5.replace("restore_previous", "restore")).is_err());
+
+        // Left out, it stays unset and isn't written back
+        let unset = json5.replace(r#"cleanup_policy: "restore_previous","#, "");
+        let scenario = parse_scenario(&unset).unwrap();
+        assert_eq!(scenario.cleanup_policy, None);
+        assert!(!scenario_to_json5(&scenario).contains("cleanup_policy"));
+    }
+
     #[test]
     fn test_parse_reorder_with_gap() {
         let json5 = r#"
@@ -794,12 +1342,15 @@ mod tests {
                             enabled: true,
                             percentage: 5.0,
                             correlation: 10.0,
+                            model: crate::LossModel::Random,
+                            ecn: false,
                         },
                         delay: crate::TcDelayConfig {
                             enabled: true,
                             base_ms: 100.0,
                             jitter_ms: 20.0,
                             correlation: 0.0,
+                            distribution: DelayDistribution::Normal,
                         },
                         ..Default::default()
                     }),
@@ -840,6 +1391,10 @@ mod tests {
         assert_eq!(scenario.steps[0].tc_config.loss.percentage, 5.0);
         assert!(scenario.steps[0].tc_config.delay.enabled);
         assert_eq!(scenario.steps[0].tc_config.delay.base_ms, 100.0);
+        assert_eq!(
+            scenario.steps[0].tc_config.delay.distribution,
+            DelayDistribution::Normal
+        );
 
         // Second step should use inline config
         assert!(scenario.steps[1].tc_config.loss.enabled);
@@ -847,6 +1402,61 @@ mod tests {
         assert!(!scenario.steps[1].tc_config.delay.enabled);
     }
 
+    #[test]
+    fn test_delay_distribution_parses_and_round_trips() {
+        let json5 = r#"
+        {
+            id: "shaped",
+            name: "Shaped jitter",
+            steps: [
+                {
+                    duration: "10s",
+                    description: "Pareto jitter",
+                    tc_config: {
+                        delay: { base_ms: 80, jitter_ms: 30, distribution: "pareto" }
+                    }
+                }
+            ]
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        let delay = &scenario.steps[0].tc_config.delay;
+        assert_eq!(delay.distribution, DelayDistribution::Pareto);
+        let reparsed = parse_scenario(&scenario_to_json5(&scenario)).unwrap();
+        assert_eq!(reparsed.steps[0].tc_config.delay, *delay);
+
+        let unknown = json5.replace("\"pareto\"", "\"weibull\"");
+        assert!(parse_scenario_json5(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_slot_parses_and_round_trips() {
+        let json5 = r#"
+        {
+            id: "wifi",
+            name: "Aggregated delivery",
+            steps: [
+                {
+                    duration: "10s",
+                    description: "802.11 aggregation",
+                    tc_config: {
+                        slot: { min_delay_ms: 5, max_delay_ms: 15, packets: 16 }
+                    }
+                }
+            ]
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        let slot = &scenario.steps[0].tc_config.slot;
+        assert!(slot.enabled);
+        assert_eq!((slot.min_delay_ms, slot.max_delay_ms), (5.0, 15.0));
+        assert_eq!((slot.packets, slot.bytes), (Some(16), None));
+        let reparsed = parse_scenario(&scenario_to_json5(&scenario)).unwrap();
+        assert_eq!(reparsed.steps[0].tc_config.slot, *slot);
+    }
+
     #[test]
     fn test_preset_reference_unknown_preset() {
         struct EmptyResolver;
@@ -981,6 +1591,68 @@ mod tests {
         assert_eq!(scenario.steps[0].tc_config.rate_limit.rate_kbps, 5000);
     }
 
+    #[test]
+    fn test_rate_limit_config_percent_of_link_speed_round_trips() {
+        let json5 = r#"
+        {
+            id: "test",
+            name: "Test",
+            steps: [
+                {
+                    duration: "10s",
+                    description: "Half the link",
+                    tc_config: {
+                        rate_limit: { percent_of_link_speed: 50 }
+                    }
+                }
+            ]
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        let rate_limit = &scenario.steps[0].tc_config.rate_limit;
+        assert!(rate_limit.enabled);
+        assert_eq!(rate_limit.mode, TcRateLimitMode::PercentOfLinkSpeed(50.0));
+
+        let written = TcConfigJson::from(&scenario.steps[0].tc_config);
+        let rate_json = written.rate_limit.as_ref().unwrap();
+        assert_eq!(rate_json.percent_of_link_speed, Some(50.0));
+        assert_eq!(rate_json.rate_kbps, None);
+        assert_eq!(
+            written.to_tc_netem_config().unwrap().rate_limit,
+            *rate_limit
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_config_htb_shaper_round_trips() {
+        let json5 = r#"
+        {
+            id: "test",
+            name: "Test",
+            steps: [
+                {
+                    duration: "10s",
+                    description: "Bottleneck",
+                    tc_config: {
+                        rate_limit: { rate: "10mbit", shaper: "htb" }
+                    }
+                }
+            ]
+        }
+        "#;
+
+        let scenario = parse_scenario(json5).unwrap();
+        let rate_limit = &scenario.steps[0].tc_config.rate_limit;
+        assert_eq!(rate_limit.shaper, RateLimitMode::Htb);
+
+        let written = TcConfigJson::from(&scenario.steps[0].tc_config);
+        assert_eq!(
+            written.to_tc_netem_config().unwrap().rate_limit,
+            *rate_limit
+        );
+    }
+
     #[test]
     fn test_rate_limit_config_invalid_rate() {
         let json5 = r#"
@@ -0,0 +1,339 @@
+//! Shorthand notation for netem configurations.
+//!
+//! Lets a user type something like `"100ms/5%/10mbit"` instead of moving
+//! several sliders. Tokens are separated by `/`, `,` or whitespace and may
+//! appear in any order; features that are not mentioned stay disabled.
+//!
+//! Bare tokens are classified by their unit:
+//!
+//! | Token          | Meaning                        |
+//! |----------------|--------------------------------|
+//! | `5%`           | packet loss                    |
+//! | `100ms`, `1s`  | delay (`us`, `ms`, `s`)        |
+//! | `100ms~10ms`   | delay with jitter (`±` works too) |
+//! | `10mbit`       | rate limit (`kbit`, `mbit`, `gbit`, `kbps`, ...) |
+//!
+//! Everything else needs a `key:value` (or `key=value`) prefix: `loss`,
+//! `delay`, `jitter`, `duplicate`/`dup`, `reorder`, `corrupt`, `rate`.
+//! Percentages may omit the `%`, delays default to milliseconds and rates to
+//! kbit/s. The result is checked with [`TcValidate`], so it is always within
+//! the ranges the backend accepts.
+
+use thiserror::Error;
+
+use crate::{TcNetemConfig, TcValidate, TcValidationError};
+
+/// Why a shorthand string could not be turned into a configuration.
+#[derive(Error, Debug, Clone)]
+pub enum ShorthandError {
+    #[error("Nothing to apply: enter e.g. \"100ms/5%/10mbit\"")]
+    Empty,
+
+    #[error("Unknown setting '{0}'")]
+    UnknownKey(String),
+
+    #[error("Cannot tell what '{0}' means: use a unit (%, ms, mbit) or a key like 'dup:{0}'")]
+    UnknownToken(String),
+
+    #[error("'{token}' is not a valid {what}")]
+    InvalidValue { token: String, what: &'static str },
+
+    #[error("{0} is given more than once")]
+    Duplicate(&'static str),
+
+    #[error("Jitter needs a delay to apply to")]
+    JitterWithoutDelay,
+
+    #[error("{}", .0.message)]
+    OutOfRange(TcValidationError),
+}
+
+/// Which field a token sets, used for duplicate detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Loss,
+    Delay,
+    Jitter,
+    Duplicate,
+    Reorder,
+    Corrupt,
+    Rate,
+}
+
+impl Field {
+    fn from_key(key: &str) -> Option<Self> {
+        match key.to_ascii_lowercase().as_str() {
+            "loss" => Some(Field::Loss),
+            "delay" | "latency" => Some(Field::Delay),
+            "jitter" => Some(Field::Jitter),
+            "dup" | "duplicate" => Some(Field::Duplicate),
+            "reorder" => Some(Field::Reorder),
+            "corrupt" => Some(Field::Corrupt),
+            "rate" => Some(Field::Rate),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Field::Loss => "Loss",
+            Field::Delay => "Delay",
+            Field::Jitter => "Jitter",
+            Field::Duplicate => "Duplicate",
+            Field::Reorder => "Reorder",
+            Field::Corrupt => "Corrupt",
+            Field::Rate => "Rate",
+        }
+    }
+}
+
+/// Parse a shorthand string such as `"100ms~10ms/5%/10mbit"` into a
+/// validated [`TcNetemConfig`].
+pub fn parse_shorthand(input: &str) -> Result<TcNetemConfig, ShorthandError> {
+    let mut config = TcNetemConfig::new();
+    let mut seen: Vec<Field> = Vec::new();
+    let mut mark = |field: Field| {
+        if seen.contains(&field) {
+            Err(ShorthandError::Duplicate(field.name()))
+        } else {
+            seen.push(field);
+            Ok(())
+        }
+    };
+
+    let tokens = input
+        .split(|c: char| c == '/' || c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty());
+
+    for token in tokens {
+        let (field, value) = match token.split_once([':', '=']) {
+            Some((key, value)) => (
+                Field::from_key(key).ok_or_else(|| ShorthandError::UnknownKey(key.to_string()))?,
+                value,
+            ),
+            None => (classify(token)?, token),
+        };
+        mark(field)?;
+
+        match field {
+            Field::Loss => {
+                config.loss.enabled = true;
+                config.loss.percentage = parse_percent(value)?;
+            }
+            Field::Delay => {
+                let (base, jitter) = match value.split_once(['~', '±']) {
+                    Some((base, jitter)) => (base, Some(jitter)),
+                    None => (value, None),
+                };
+                config.delay.enabled = true;
+                config.delay.base_ms = parse_time_ms(base)?;
+                if let Some(jitter) = jitter {
+                    mark(Field::Jitter)?;
+                    config.delay.jitter_ms = parse_time_ms(jitter)?;
+                }
+            }
+            Field::Jitter => config.delay.jitter_ms = parse_time_ms(value)?,
+            Field::Duplicate => {
+                config.duplicate.enabled = true;
+                config.duplicate.percentage = parse_percent(value)?;
+            }
+            Field::Reorder => {
+                config.reorder.enabled = true;
+                config.reorder.percentage = parse_percent(value)?;
+            }
+            Field::Corrupt => {
+                config.corrupt.enabled = true;
+                config.corrupt.percentage = parse_percent(value)?;
+            }
+            Field::Rate => {
+                config.rate_limit.enabled = true;
+                config.rate_limit.rate_kbps = parse_rate_kbps(value)?;
+            }
+        }
+    }
+
+    if seen.is_empty() {
+        return Err(ShorthandError::Empty);
+    }
+    if seen.contains(&Field::Jitter) && !seen.contains(&Field::Delay) {
+        return Err(ShorthandError::JitterWithoutDelay);
+    }
+
+    config.validate().map_err(ShorthandError::OutOfRange)?;
+    Ok(config)
+}
+
+/// Work out which field a bare (unkeyed) token sets from its unit.
+fn classify(token: &str) -> Result<Field, ShorthandError> {
+    let lower = token.to_ascii_lowercase();
+    let (_, unit) = split_unit(&lower);
+    if lower.ends_with('%') {
+        Ok(Field::Loss)
+    } else if RATE_UNITS.iter().any(|(u, _)| *u == unit) {
+        Ok(Field::Rate)
+    } else if lower.contains(['~', '±']) || matches!(unit, "us" | "µs" | "ms" | "s") {
+        Ok(Field::Delay)
+    } else {
+        Err(ShorthandError::UnknownToken(token.to_string()))
+    }
+}
+
+/// Rate units and how many kbit/s one of them is.
+const RATE_UNITS: &[(&str, f64)] = &[
+    ("kbit", 1.0),
+    ("kbps", 1.0),
+    ("mbit", 1_000.0),
+    ("mbps", 1_000.0),
+    ("gbit", 1_000_000.0),
+    ("gbps", 1_000_000.0),
+];
+
+/// Split `"100ms"` into `("100", "ms")`.
+fn split_unit(token: &str) -> (&str, &str) {
+    let idx = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(token.len());
+    token.split_at(idx)
+}
+
+fn parse_number(number: &str, token: &str, what: &'static str) -> Result<f64, ShorthandError> {
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| ShorthandError::InvalidValue {
+            token: token.to_string(),
+            what,
+        })
+}
+
+fn parse_percent(token: &str) -> Result<f32, ShorthandError> {
+    let number = token.strip_suffix('%').unwrap_or(token);
+    parse_number(number, token, "percentage").map(|v| v as f32)
+}
+
+fn parse_time_ms(token: &str) -> Result<f32, ShorthandError> {
+    let lower = token.to_ascii_lowercase();
+    let (number, unit) = split_unit(&lower);
+    let scale = match unit {
+        "" | "ms" => 1.0,
+        "us" | "µs" => 0.001,
+        "s" => 1_000.0,
+        _ => {
+            return Err(ShorthandError::InvalidValue {
+                token: token.to_string(),
+                what: "time (use us, ms or s)",
+            });
+        }
+    };
+    parse_number(number, token, "time").map(|v| (v * scale) as f32)
+}
+
+fn parse_rate_kbps(token: &str) -> Result<u32, ShorthandError> {
+    let lower = token.to_ascii_lowercase();
+    let (number, unit) = split_unit(&lower);
+    let scale = if unit.is_empty() {
+        1.0
+    } else {
+        RATE_UNITS
+            .iter()
+            .find(|(u, _)| *u == unit)
+            .map(|(_, scale)| *scale)
+            .ok_or_else(|| ShorthandError::InvalidValue {
+                token: token.to_string(),
+                what: "rate (use kbit, mbit or gbit)",
+            })?
+    };
+    let kbps = (parse_number(number, token, "rate")? * scale).round();
+    // Out-of-range values are reported by validation, so only clamp to what
+    // fits in the field
+    Ok(kbps.clamp(0.0, u32::MAX as f64) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_common_forms() {
+        let config = parse_shorthand("100ms/5%/10mbit").unwrap();
+        assert!(config.delay.enabled);
+        assert_eq!(config.delay.base_ms, 100.0);
+        assert!(config.loss.enabled);
+        assert_eq!(config.loss.percentage, 5.0);
+        assert!(config.rate_limit.enabled);
+        assert_eq!(config.rate_limit.rate_kbps, 10_000);
+        assert!(!config.duplicate.enabled);
+
+        // Any order and separator, jitter, keyed fields
+        let config = parse_shorthand("dup:1 , 50ms~10ms  reorder=25% corrupt:0.5%").unwrap();
+        assert_eq!(config.delay.base_ms, 50.0);
+        assert_eq!(config.delay.jitter_ms, 10.0);
+        assert_eq!(config.duplicate.percentage, 1.0);
+        assert_eq!(config.reorder.percentage, 25.0);
+        assert_eq!(config.reorder.gap, 5);
+        assert_eq!(config.corrupt.percentage, 0.5);
+        assert!(!config.loss.enabled);
+        assert!(!config.rate_limit.enabled);
+    }
+
+    #[test]
+    fn test_parse_units_and_omitted_fields() {
+        let config = parse_shorthand("1s").unwrap();
+        assert_eq!(config.delay.base_ms, 1000.0);
+        assert!(!config.loss.enabled && !config.rate_limit.enabled);
+
+        let config = parse_shorthand("512kbit").unwrap();
+        assert_eq!(config.rate_limit.rate_kbps, 512);
+        assert!(!config.delay.enabled);
+
+        assert_eq!(
+            parse_shorthand("2mbps").unwrap().rate_limit.rate_kbps,
+            2_000
+        );
+        assert_eq!(
+            parse_shorthand("delay:20 jitter:500us")
+                .unwrap()
+                .delay
+                .jitter_ms,
+            0.5
+        );
+        assert_eq!(
+            parse_shorthand("rate:300").unwrap().rate_limit.rate_kbps,
+            300
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(parse_shorthand(" / "), Err(ShorthandError::Empty)));
+        assert!(matches!(
+            parse_shorthand("100ms/banana"),
+            Err(ShorthandError::UnknownToken(t)) if t == "banana"
+        ));
+        assert!(matches!(
+            parse_shorthand("jam:5%"),
+            Err(ShorthandError::UnknownKey(k)) if k == "jam"
+        ));
+        assert!(matches!(
+            parse_shorthand("5%/10%"),
+            Err(ShorthandError::Duplicate("Loss"))
+        ));
+        assert!(matches!(
+            parse_shorthand("loss:abc"),
+            Err(ShorthandError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            parse_shorthand("jitter:5ms"),
+            Err(ShorthandError::JitterWithoutDelay)
+        ));
+
+        let err = parse_shorthand("150%").unwrap_err();
+        assert!(matches!(err, ShorthandError::OutOfRange(_)));
+        assert!(err.to_string().contains("0.0-100.0"), "{err}");
+        assert!(matches!(
+            parse_shorthand("10s"),
+            Err(ShorthandError::OutOfRange(_))
+        ));
+    }
+}
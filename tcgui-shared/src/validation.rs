@@ -7,6 +7,8 @@
 //! validated against a strict allowlist before use — defense-in-depth for the
 //! privileged daemon, independent of the Zenoh-network trust boundary.
 
+use std::net::IpAddr;
+
 /// Maximum accepted size for a single Zenoh request payload (64 KiB).
 ///
 /// Generous for any legitimate TC/interface/diagnostics request, but bounds the
@@ -87,6 +89,140 @@ pub fn validate_target(namespace: &str, interface: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Validate the name to rename an interface to. Stricter than
+/// [`validate_interface`]: the kernel refuses a `:` in a new name, so the
+/// alias syntax accepted for existing names is rejected here.
+pub fn validate_new_interface_name(name: &str) -> Result<(), String> {
+    validate_interface(name)?;
+    if name.contains(':') {
+        return Err(format!(
+            "invalid interface name {name:?}: ':' is not allowed in a new name"
+        ));
+    }
+    Ok(())
+}
+
+/// Smallest MTU accepted, the minimum IPv4 requires (RFC 791).
+pub const MIN_MTU: u32 = 68;
+
+/// Largest MTU accepted, the largest IPv4 datagram.
+pub const MAX_MTU: u32 = 65535;
+
+/// Validate an MTU to set on an interface: [`MIN_MTU`]..=[`MAX_MTU`] bytes.
+/// The driver may still refuse a value in range that it can't carry.
+pub fn validate_mtu(mtu: u32) -> Result<(), String> {
+    if !(MIN_MTU..=MAX_MTU).contains(&mtu) {
+        return Err(format!(
+            "invalid MTU {mtu}: expected {MIN_MTU}..={MAX_MTU} bytes"
+        ));
+    }
+    Ok(())
+}
+
+/// Longest host name DNS allows.
+const MAX_HOST_NAME_LEN: usize = 253;
+
+/// Validate a host name or IP address to ping. Only the characters of host
+/// names and IPv4/IPv6 addresses are accepted, and a leading `-` is refused
+/// so the target can't be read as an option of `ping`.
+pub fn validate_ping_target(target: &str) -> Result<(), String> {
+    if target.is_empty() {
+        return Err("ping target must not be empty".to_string());
+    }
+    if target.len() > MAX_HOST_NAME_LEN {
+        return Err(format!(
+            "invalid ping target: longer than {MAX_HOST_NAME_LEN} characters"
+        ));
+    }
+    if target.starts_with('-') {
+        return Err(format!(
+            "invalid ping target {target:?}: must not start with '-'"
+        ));
+    }
+    if let Some(c) = target
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':' | '_' | '%')))
+    {
+        return Err(format!(
+            "invalid ping target {target:?}: {c:?} is not allowed"
+        ));
+    }
+    Ok(())
+}
+
+/// Validate the TCP sink of a throughput probe, `"host:port"` or
+/// `"[ipv6]:port"`: the host as for [`validate_ping_target`] and a port
+/// other than 0.
+pub fn validate_probe_target(target: &str) -> Result<(), String> {
+    let (host, port) = target
+        .rsplit_once(':')
+        .ok_or_else(|| format!("invalid probe target {target:?}: expected host:port"))?;
+    let host = match host.strip_prefix('[') {
+        Some(v6) => v6.strip_suffix(']').ok_or_else(|| {
+            format!("invalid probe target {target:?}: unclosed '[' around the address")
+        })?,
+        None if host.contains(':') => {
+            return Err(format!(
+                "invalid probe target {target:?}: write an IPv6 address as [address]:port"
+            ));
+        }
+        None => host,
+    };
+    validate_ping_target(host)?;
+    match port.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(()),
+        _ => Err(format!(
+            "invalid probe target {target:?}: bad port {port:?}"
+        )),
+    }
+}
+
+/// Parse an address to assign to an interface, `"ip/prefix"` (IPv4 or
+/// IPv6), into the address and prefix length. The prefix is required; the
+/// unspecified and multicast addresses are refused.
+pub fn parse_cidr(cidr: &str) -> Result<(IpAddr, u8), String> {
+    let (address, prefix) = cidr
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| format!("invalid address {cidr:?}: expected ip/prefix"))?;
+    let address: IpAddr = address
+        .parse()
+        .map_err(|_| format!("invalid address {cidr:?}: {address:?} is not an IP address"))?;
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix
+        .parse::<u8>()
+        .ok()
+        .filter(|prefix| *prefix <= max_prefix)
+        .ok_or_else(|| format!("invalid address {cidr:?}: prefix must be 0..={max_prefix}"))?;
+    if address.is_unspecified() || address.is_multicast() {
+        return Err(format!(
+            "invalid address {cidr:?}: {address} can't be assigned to an interface"
+        ));
+    }
+    Ok((address, prefix))
+}
+
+/// Decode a JSON request payload, describing what is wrong with it when it
+/// can't be decoded: empty, not UTF-8, cut short, not JSON, or JSON that
+/// doesn't match the request type (serde names the missing or unknown field
+/// and where it went wrong). A peer speaking another protocol version gets
+/// this back instead of a silently dropped query.
+pub fn decode_request<T: serde::de::DeserializeOwned>(payload: &[u8]) -> Result<T, String> {
+    use serde_json::error::Category;
+
+    if payload.iter().all(u8::is_ascii_whitespace) {
+        return Err("malformed request: empty payload".to_string());
+    }
+    let text = std::str::from_utf8(payload)
+        .map_err(|e| format!("malformed request: payload is not UTF-8 ({e})"))?;
+    serde_json::from_str(text).map_err(|e| match e.classify() {
+        Category::Eof => format!("malformed request: truncated payload ({e})"),
+        Category::Syntax => format!("malformed request: invalid JSON ({e})"),
+        Category::Data => format!("malformed request: {e}"),
+        Category::Io => format!("malformed request: {e}"),
+    })
+}
+
 /// Reserved prefix marking an escaped key chunk. Chosen so it begins with an
 /// alphanumeric (`0`) — the G4 erratum requirement that the escape can never
 /// regress into another non-alphanumeric leading character — while being a
@@ -157,6 +293,122 @@ pub fn slug_key_chunk(name: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn validate_mtu_bounds() {
+        assert!(validate_mtu(MIN_MTU).is_ok());
+        assert!(validate_mtu(1500).is_ok());
+        assert!(validate_mtu(MAX_MTU).is_ok());
+        assert!(validate_mtu(MIN_MTU - 1).unwrap_err().contains("67"));
+        assert!(validate_mtu(MAX_MTU + 1).is_err());
+        assert!(validate_mtu(0).is_err());
+    }
+
+    #[test]
+    fn validate_ping_target_rules() {
+        for target in ["10.0.0.1", "fe80::1%veth0", "gateway.lab", "host_1"] {
+            assert!(validate_ping_target(target).is_ok(), "{target:?}");
+        }
+        assert!(validate_ping_target("-f").unwrap_err().contains("'-'"));
+        for target in ["", "10.0.0.1 -f", "a;reboot", "$(id)"] {
+            assert!(validate_ping_target(target).is_err(), "{target:?}");
+        }
+        assert!(validate_ping_target(&"a".repeat(254)).is_err());
+    }
+
+    #[test]
+    fn validate_probe_target_rules() {
+        for target in ["10.0.0.1:5001", "sink.lab:9", "[fe80::1%veth0]:5001"] {
+            assert!(validate_probe_target(target).is_ok(), "{target:?}");
+        }
+        for target in [
+            "10.0.0.1",
+            "10.0.0.1:0",
+            "10.0.0.1:http",
+            "fe80::1:9",
+            "[::1:9",
+            ":9",
+        ] {
+            assert!(validate_probe_target(target).is_err(), "{target:?}");
+        }
+    }
+
+    #[test]
+    fn validate_new_interface_name_rules() {
+        assert!(validate_new_interface_name("veth-lab0").is_ok());
+        assert!(validate_new_interface_name("eth0.100").is_ok());
+        assert!(validate_new_interface_name("a23456789012345").is_ok());
+        assert!(
+            validate_new_interface_name("a234567890123456")
+                .unwrap_err()
+                .contains("too long")
+        );
+        for name in ["", ".", "..", "a/b", "a b", "eth0\t", "eth0:1", "eth0\n"] {
+            assert!(validate_new_interface_name(name).is_err(), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn parse_cidr_accepts_ipv4_and_ipv6() {
+        assert_eq!(
+            parse_cidr("10.0.0.5/24"),
+            Ok(("10.0.0.5".parse().unwrap(), 24))
+        );
+        assert_eq!(
+            parse_cidr(" fd00::1/64 "),
+            Ok(("fd00::1".parse().unwrap(), 64))
+        );
+        assert_eq!(parse_cidr("10.0.0.5/32").unwrap().1, 32);
+        assert_eq!(parse_cidr("fd00::1/128").unwrap().1, 128);
+    }
+
+    #[test]
+    fn parse_cidr_rejects_invalid_addresses() {
+        assert!(parse_cidr("10.0.0.5").unwrap_err().contains("ip/prefix"));
+        assert!(parse_cidr("10.0.0.5/33").unwrap_err().contains("0..=32"));
+        assert!(parse_cidr("fd00::1/129").unwrap_err().contains("0..=128"));
+        assert!(parse_cidr("10.0.0.256/24").is_err());
+        assert!(parse_cidr("10.0.0.5/-1").is_err());
+        assert!(parse_cidr("10.0.0.5/").is_err());
+        assert!(parse_cidr("host/24").is_err());
+        assert!(parse_cidr("0.0.0.0/0").is_err());
+        assert!(parse_cidr("224.0.0.1/4").is_err());
+        assert!(parse_cidr("::/0").is_err());
+    }
+
+    #[test]
+    fn decode_request_reports_what_is_wrong() {
+        use crate::TcRequest;
+
+        let valid = br#"{"namespace":"default","interface":"eth0","operation":"Remove"}"#;
+        assert!(decode_request::<TcRequest>(valid).is_ok());
+
+        let err = decode_request::<TcRequest>(&valid[..30]).unwrap_err();
+        assert!(err.contains("truncated"), "{err}");
+
+        let err = decode_request::<TcRequest>(br#"{"namespace":"default","interface":"eth0"}"#)
+            .unwrap_err();
+        assert!(err.contains("missing field `operation`"), "{err}");
+
+        let err = decode_request::<TcRequest>(
+            br#"{"namespace":"default","interface":"eth0","operation":"Explode"}"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("unknown variant `Explode`"), "{err}");
+
+        let err = decode_request::<TcRequest>(b"not json").unwrap_err();
+        assert!(err.contains("invalid JSON"), "{err}");
+        assert!(
+            decode_request::<TcRequest>(b"")
+                .unwrap_err()
+                .contains("empty")
+        );
+        assert!(
+            decode_request::<TcRequest>(b"\xff\xfe")
+                .unwrap_err()
+                .contains("UTF-8")
+        );
+    }
+
     #[test]
     fn accepts_real_targets() {
         assert!(validate_target("default", "eth0").is_ok());